@@ -0,0 +1,105 @@
+use bevy::{
+    ecs::{entity::Entity, query::QueryState, world::World},
+    utils::array_init,
+};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+criterion_group!(benches, query_get_pointed_entities);
+criterion_main!(benches);
+
+/// Numbers of target entities the benchmarks are run with.
+const TARGET_COUNTS: [usize; 4] = [1_000, 10_000, 100_000, 1_000_000];
+const POINTER_COUNT: usize = 1_000;
+const POINTS_PER_ENTITY: usize = 16;
+
+struct Position(f32);
+
+/// References to other entities, resolved with `Query::get`.
+struct PointEntities([Entity; POINTS_PER_ENTITY]);
+
+struct A;
+struct B;
+struct C;
+struct D;
+
+/// A small xorshift generator, so the access pattern is random but identical between runs.
+struct XorShift(u64);
+
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
+
+/// Spawns the targets spread over 16 tables, then pointer entities whose references are picked
+/// with `pick`.
+fn setup(target_count: usize, mut pick: impl FnMut(&[Entity]) -> Entity) -> World {
+    let mut world = World::default();
+    let targets = (0..target_count)
+        .map(|i| {
+            let mut entity = world.spawn();
+            entity.insert(Position(i as f32));
+            if i & 1 != 0 {
+                entity.insert(A);
+            }
+            if i & 2 != 0 {
+                entity.insert(B);
+            }
+            if i & 4 != 0 {
+                entity.insert(C);
+            }
+            if i & 8 != 0 {
+                entity.insert(D);
+            }
+            entity.id()
+        })
+        .collect::<Vec<_>>();
+
+    for _ in 0..POINTER_COUNT {
+        let points = array_init(|_| pick(&targets));
+        world.spawn().insert(PointEntities(points));
+    }
+    world
+}
+
+fn query_get_pointed_entities(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("query_get_pointed_entities");
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    let mut rng = XorShift(0x2545_f491_4f6c_dd1d);
+    for target_count in TARGET_COUNTS.iter().copied() {
+        let worlds = vec![
+            // every reference points to the same entity, which stays in cache
+            ("same_entity", setup(target_count, |targets| targets[0])),
+            // references point to random entities across all tables
+            (
+                "random_entities",
+                setup(target_count, |targets| {
+                    targets[rng.next() as usize % targets.len()]
+                }),
+            ),
+        ];
+
+        for (name, mut world) in worlds {
+            let mut pointers = QueryState::<&PointEntities>::new(&mut world);
+            let mut positions = QueryState::<&Position>::new(&mut world);
+            group.bench_function(BenchmarkId::new(name, target_count), |bencher| {
+                bencher.iter(|| {
+                    let mut sum = 0.0;
+                    for points in pointers.iter(&world) {
+                        for entity in points.0.iter() {
+                            sum += positions.get(&world, *entity).unwrap().0;
+                        }
+                    }
+                    black_box(sum);
+                });
+            });
+        }
+    }
+
+    group.finish();
+}
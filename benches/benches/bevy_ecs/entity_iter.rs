@@ -0,0 +1,104 @@
+use bevy::ecs::{
+    query::QueryState,
+    system::{Query, SystemState},
+    world::World,
+};
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+criterion_group!(
+    benches,
+    spawn_batch::<4>,
+    spawn_batch::<64>,
+    spawn_batch::<256>,
+    iter::<4>,
+    iter::<64>,
+    iter::<256>,
+    iter_slices::<4>,
+    iter_slices::<64>,
+    iter_slices::<256>,
+);
+criterion_main!(benches);
+
+/// Entity counts every benchmark in this file is run with.
+const ENTITY_COUNTS: [usize; 4] = [1_000, 10_000, 100_000, 1_000_000];
+
+/// A component of `SIZE` bytes.
+struct Data<const SIZE: usize>([u8; SIZE]);
+
+struct Counter(u32);
+
+fn spawn_batch<const SIZE: usize>(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group(format!("spawn_batch_{}_bytes", SIZE));
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+    group.sample_size(10);
+
+    for entity_count in ENTITY_COUNTS.iter().copied() {
+        group.throughput(Throughput::Elements(entity_count as u64));
+        group.bench_with_input(
+            BenchmarkId::from_parameter(entity_count),
+            &entity_count,
+            |bencher, &entity_count| {
+                bencher.iter_with_large_drop(|| {
+                    let mut world = World::default();
+                    world.spawn_batch((0..entity_count).map(|_| (Data([0u8; SIZE]),)));
+                    world
+                });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn iter<const SIZE: usize>(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group(format!("iter_{}_bytes", SIZE));
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    for entity_count in ENTITY_COUNTS.iter().copied() {
+        let mut world = World::default();
+        world.spawn_batch((0..entity_count).map(|_| (Data([1u8; SIZE]), Counter(0))));
+        let mut query = QueryState::<(&Data<SIZE>, &mut Counter)>::new(&mut world);
+
+        group.throughput(Throughput::Elements(entity_count as u64));
+        group.bench_function(BenchmarkId::from_parameter(entity_count), |bencher| {
+            bencher.iter(|| {
+                for (data, mut counter) in query.iter_mut(&mut world) {
+                    counter.0 += data.0[SIZE - 1] as u32;
+                }
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn iter_slices<const SIZE: usize>(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group(format!("iter_slices_{}_bytes", SIZE));
+    group.warm_up_time(std::time::Duration::from_millis(500));
+    group.measurement_time(std::time::Duration::from_secs(4));
+
+    for entity_count in ENTITY_COUNTS.iter().copied() {
+        let mut world = World::default();
+        world.spawn_batch((0..entity_count).map(|_| (Data([1u8; SIZE]), Counter(0))));
+        let mut state = SystemState::<Query<&Data<SIZE>>>::new(&mut world);
+
+        group.throughput(Throughput::Elements(entity_count as u64));
+        group.bench_function(BenchmarkId::from_parameter(entity_count), |bencher| {
+            bencher.iter(|| {
+                let query = state.get(&world);
+                let mut sum = 0u32;
+                for slice in query.iter_slices::<Data<SIZE>>().unwrap() {
+                    sum += slice
+                        .iter()
+                        .map(|data| data.0[SIZE - 1] as u32)
+                        .sum::<u32>();
+                }
+                criterion::black_box(sum)
+            });
+        });
+    }
+
+    group.finish();
+}
@@ -1,13 +1,19 @@
 use crate::{
     app::{App, AppExit},
     plugin::Plugin,
-    CoreStage, PluginGroup, PluginGroupBuilder, StartupStage,
+    BuildMessages, CoreStage, PluginGroup, PluginGroupBuilder, StartupStage,
 };
 use bevy_ecs::{
     component::{Component, ComponentDescriptor},
+    entity::Entity,
     event::Events,
+    observer::{
+        dispatch_component_observers_system, dispatch_entity_events_system,
+        init_component_observers, ComponentObservers, ComponentTrigger, EntityEvent, Observers,
+    },
     schedule::{
-        IntoSystemDescriptor, RunOnce, Schedule, Stage, StageLabel, State, SystemSet, SystemStage,
+        ExclusiveSystemDescriptorCoercion, IntoSystemDescriptor, RunOnce, Schedule, Stage,
+        StageLabel, State, SystemSet, SystemStage,
     },
     system::{IntoExclusiveSystem, IntoSystem},
     world::{FromWorld, World},
@@ -18,13 +24,13 @@ use std::{fmt::Debug, hash::Hash};
 /// Configure [App]s using the builder pattern
 pub struct AppBuilder {
     pub app: App,
+    plugins: Vec<Box<dyn Plugin>>,
+    build_messages: BuildMessages,
 }
 
 impl Default for AppBuilder {
     fn default() -> Self {
-        let mut app_builder = AppBuilder {
-            app: App::default(),
-        };
+        let mut app_builder = AppBuilder::empty();
 
         #[cfg(feature = "bevy_reflect")]
         app_builder.init_resource::<bevy_reflect::TypeRegistryArc>();
@@ -46,6 +52,8 @@ impl AppBuilder {
     pub fn empty() -> AppBuilder {
         AppBuilder {
             app: App::default(),
+            plugins: Vec::new(),
+            build_messages: BuildMessages::default(),
         }
     }
 
@@ -66,6 +74,7 @@ impl AppBuilder {
     ///     .run();
     /// ```
     pub fn run(&mut self) {
+        self.finish_plugins();
         let app = std::mem::take(&mut self.app);
         app.run();
     }
@@ -318,6 +327,72 @@ impl AppBuilder {
             .add_system_to_stage(CoreStage::First, Events::<T>::update_system.system())
     }
 
+    /// Setup the application to manage events of type `T` that are kept until they are removed,
+    /// instead of for two frames.
+    ///
+    /// This is done by adding a `Resource` of type `Events::<T>`, without the
+    /// `Events::<T>::update_system` system. The events are removed with an
+    /// [`EventConsumer`](bevy_ecs::event::EventConsumer), or by calling
+    /// [`Events::update`] when suitable, e.g. in a fixed timestep stage.
+    pub fn add_manual_event<T>(&mut self) -> &mut Self
+    where
+        T: Component,
+    {
+        self.insert_resource(Events::<T>::default())
+    }
+
+    /// Setup the application to manage events of type `T` that target entities, which callbacks
+    /// added with [`EntityCommands::observe`](bevy_ecs::system::EntityCommands::observe) react
+    /// to.
+    ///
+    /// This adds the events like [`AppBuilder::add_event`], and the
+    /// [`dispatch_entity_events_system`] at the start of `CoreStage::PostUpdate`, so that events
+    /// sent during `CoreStage::Update` are handled in the same frame.
+    pub fn add_entity_event<T>(&mut self) -> &mut Self
+    where
+        T: EntityEvent,
+    {
+        self.add_event::<T>()
+            .init_resource::<Observers<T>>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                dispatch_entity_events_system::<T>
+                    .exclusive_system()
+                    .at_start(),
+            )
+    }
+
+    /// Runs `system` for every entity on which the component `T` is added, changed or removed, as
+    /// per `trigger`, with the entity as [`In`](bevy_ecs::system::In) input.
+    ///
+    /// The first observer of `T` registers the component hooks recording the additions and
+    /// removals of `T`, and adds the [`dispatch_component_observers_system`] at the end of
+    /// `CoreStage::PostUpdate`, so that observers react to the changes of the frame in the same
+    /// frame. `Changed` observers run at most once per entity and frame.
+    pub fn add_component_observer<T, Params>(
+        &mut self,
+        trigger: ComponentTrigger,
+        system: impl IntoSystem<Entity, (), Params>,
+    ) -> &mut Self
+    where
+        T: Component,
+    {
+        if !self.world().contains_resource::<ComponentObservers<T>>() {
+            init_component_observers::<T>(self.world_mut());
+            self.add_system_to_stage(
+                CoreStage::PostUpdate,
+                dispatch_component_observers_system::<T>
+                    .exclusive_system()
+                    .at_end(),
+            );
+        }
+        self.world_mut()
+            .get_resource_mut::<ComponentObservers<T>>()
+            .unwrap()
+            .insert(trigger, system);
+        self
+    }
+
     /// Inserts a resource to the current [App] and overwrites any resource previously added of the same type.
     ///
     /// A resource in Bevy represents globally unique data. Resources must be added to Bevy Apps
@@ -461,11 +536,60 @@ impl AppBuilder {
     where
         T: Plugin,
     {
+        self.add_boxed_plugin(Box::new(plugin))
+    }
+
+    /// Adds a single plugin that was already boxed, e.g. by a [`PluginGroup`] or because it was
+    /// loaded dynamically
+    pub fn add_boxed_plugin(&mut self, plugin: Box<dyn Plugin>) -> &mut Self {
         debug!("added plugin: {}", plugin.name());
         plugin.build(self);
+        self.plugins.push(plugin);
         self
     }
 
+    /// Runs [`Plugin::finish`] and then [`Plugin::cleanup`] for all plugins added since the last
+    /// call, in the order they were added.
+    ///
+    /// This is done by [`AppBuilder::run`], and only needs to be called directly by code that
+    /// updates the [App] without running it, e.g. in tests.
+    pub fn finish_plugins(&mut self) -> &mut Self {
+        // plugins may add other plugins while finishing
+        while !self.plugins.is_empty() {
+            let plugins = std::mem::take(&mut self.plugins);
+            for plugin in plugins.iter() {
+                plugin.finish(self);
+            }
+            for plugin in plugins.iter() {
+                plugin.cleanup(self);
+            }
+        }
+        for type_name in self.build_messages.pending_types() {
+            debug!(
+                "no plugin consumed the build messages of type {}",
+                type_name
+            );
+        }
+        self.build_messages.clear();
+        self
+    }
+
+    /// Sends a message to the other plugins of the app, see [`BuildMessages`]
+    pub fn send_build_message<T: Send + Sync + 'static>(&mut self, message: T) -> &mut Self {
+        self.build_messages.send(message);
+        self
+    }
+
+    /// Returns the build messages of type `T` sent so far, leaving them for other plugins
+    pub fn read_build_messages<T: Send + Sync + 'static>(&self) -> &[T] {
+        self.build_messages.read()
+    }
+
+    /// Removes and returns the build messages of type `T` sent so far
+    pub fn drain_build_messages<T: Send + Sync + 'static>(&mut self) -> Vec<T> {
+        self.build_messages.drain()
+    }
+
     /// Adds a group of plugins
     ///
     /// Bevy plugins can be grouped into a set of plugins. Bevy provides
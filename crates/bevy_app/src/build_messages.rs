@@ -0,0 +1,90 @@
+use bevy_utils::HashMap;
+use std::any::{Any, TypeId};
+
+struct MessageQueue {
+    type_name: &'static str,
+    messages: Box<dyn Any + Send + Sync>,
+}
+
+/// Typed messages that plugins send each other while the app is built, so that a plugin can
+/// request something from another plugin, e.g. an asset loader to install, without depending on
+/// it.
+///
+/// Messages are sent with [`AppBuilder::send_build_message`](crate::AppBuilder::send_build_message)
+/// and are usually consumed in [`Plugin::finish`](crate::Plugin::finish), once every plugin
+/// had the opportunity to send them. Messages that are left once the plugins are finished are
+/// dropped.
+///
+/// ```
+/// # use bevy_app::prelude::*;
+/// struct AttachmentRequest(&'static str);
+///
+/// struct BloomPlugin;
+///
+/// impl Plugin for BloomPlugin {
+///     fn build(&self, app: &mut AppBuilder) {
+///         app.send_build_message(AttachmentRequest("bloom"));
+///     }
+/// }
+///
+/// struct RendererPlugin;
+///
+/// impl Plugin for RendererPlugin {
+///     fn build(&self, _app: &mut AppBuilder) {}
+///
+///     fn finish(&self, app: &mut AppBuilder) {
+///         for request in app.drain_build_messages::<AttachmentRequest>() {
+///             println!("creating the {} attachment", request.0);
+///         }
+///     }
+/// }
+///
+/// App::build()
+///     .add_plugin(RendererPlugin)
+///     .add_plugin(BloomPlugin)
+///     .run();
+/// ```
+#[derive(Default)]
+pub struct BuildMessages {
+    queues: HashMap<TypeId, MessageQueue>,
+}
+
+impl BuildMessages {
+    pub fn send<T: Send + Sync + 'static>(&mut self, message: T) {
+        self.queues
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| MessageQueue {
+                type_name: std::any::type_name::<T>(),
+                messages: Box::new(Vec::<T>::new()),
+            })
+            .messages
+            .downcast_mut::<Vec<T>>()
+            .unwrap()
+            .push(message);
+    }
+
+    /// Returns the messages of type `T` sent so far, leaving them for other plugins.
+    pub fn read<T: Send + Sync + 'static>(&self) -> &[T] {
+        self.queues
+            .get(&TypeId::of::<T>())
+            .map(|queue| queue.messages.downcast_ref::<Vec<T>>().unwrap().as_slice())
+            .unwrap_or(&[])
+    }
+
+    /// Removes and returns the messages of type `T` sent so far.
+    pub fn drain<T: Send + Sync + 'static>(&mut self) -> Vec<T> {
+        self.queues
+            .remove(&TypeId::of::<T>())
+            .map(|queue| *queue.messages.downcast::<Vec<T>>().unwrap())
+            .unwrap_or_default()
+    }
+
+    /// The type names of the messages that were not drained
+    pub fn pending_types(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.queues.values().map(|queue| queue.type_name)
+    }
+
+    pub fn clear(&mut self) {
+        self.queues.clear();
+    }
+}
@@ -1,5 +1,6 @@
 mod app;
 mod app_builder;
+mod build_messages;
 mod plugin;
 mod plugin_group;
 mod schedule_runner;
@@ -11,6 +12,7 @@ pub use app::*;
 pub use app_builder::*;
 pub use bevy_derive::DynamicPlugin;
 pub use bevy_ecs::event::*;
+pub use build_messages::*;
 pub use plugin::*;
 pub use plugin_group::*;
 pub use schedule_runner::*;
@@ -5,11 +5,30 @@ use std::any::Any;
 ///
 /// Plugins use [AppBuilder] to configure an [App](crate::App). When an [App](crate::App) registers
 /// a plugin, the plugin's [Plugin::build] function is run.
+///
+/// Once all plugins are built, before the app runs, the [Plugin::finish] functions of all plugins
+/// run in registration order, followed by their [Plugin::cleanup] functions. Plugins that need
+/// resources inserted by other plugins, like the render device, can use them there regardless of
+/// the order the plugins were added in.
 pub trait Plugin: Any + Send + Sync {
     fn build(&self, app: &mut AppBuilder);
+
+    /// Finishes configuring the app, once all plugins are built.
+    fn finish(&self, _app: &mut AppBuilder) {}
+
+    /// Cleans up after the setup of the app, once all plugins are finished.
+    fn cleanup(&self, _app: &mut AppBuilder) {}
+
     fn name(&self) -> &str {
         std::any::type_name::<Self>()
     }
 }
 
 pub type CreatePlugin = unsafe fn() -> *mut dyn Plugin;
+
+/// The version of the interface between an app and the plugins it loads dynamically, exported
+/// by dynamic plugins as `_bevy_plugin_abi_version`. A plugin built against another version is
+/// refused instead of crashing the app. Bumped whenever that interface changes.
+pub const DYNAMIC_PLUGIN_ABI_VERSION: u32 = 1;
+
+pub type PluginAbiVersion = unsafe fn() -> u32;
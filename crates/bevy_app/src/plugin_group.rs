@@ -1,5 +1,5 @@
 use crate::{AppBuilder, Plugin};
-use bevy_utils::{tracing::debug, HashMap};
+use bevy_utils::HashMap;
 use std::any::TypeId;
 
 pub trait PluginGroup {
@@ -96,12 +96,11 @@ impl PluginGroupBuilder {
         self
     }
 
-    pub fn finish(self, app: &mut AppBuilder) {
+    pub fn finish(mut self, app: &mut AppBuilder) {
         for ty in self.order.iter() {
-            if let Some(entry) = self.plugins.get(ty) {
+            if let Some(entry) = self.plugins.remove(ty) {
                 if entry.enabled {
-                    debug!("added plugin: {}", entry.plugin.name());
-                    entry.plugin.build(app);
+                    app.add_boxed_plugin(entry.plugin);
                 }
             }
         }
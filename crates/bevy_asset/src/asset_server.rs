@@ -1,17 +1,22 @@
 use crate::{
     path::{AssetPath, AssetPathId, SourcePathId},
-    Asset, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel, AssetLifecycleEvent,
-    AssetLoader, Assets, Handle, HandleId, HandleUntyped, LabelId, LoadContext, LoadState,
-    RefChange, RefChangeChannel, SourceInfo, SourceMeta,
+    Asset, AssetFolder, AssetIo, AssetIoError, AssetLifecycle, AssetLifecycleChannel,
+    AssetLifecycleEvent, AssetLoader, Assets, FolderSettings, Handle, HandleId, HandleUntyped,
+    LabelId, LoadContext, LoadState, RefChange, RefChangeChannel, SourceInfo, SourceMeta,
 };
 use anyhow::Result;
+use bevy_app::EventWriter;
 use bevy_ecs::system::{Res, ResMut};
 use bevy_log::warn;
 use bevy_tasks::TaskPool;
 use bevy_utils::{HashMap, Uuid};
-use crossbeam_channel::TryRecvError;
+use crossbeam_channel::{Receiver, Sender, TryRecvError};
 use parking_lot::{Mutex, RwLock};
-use std::{collections::hash_map::Entry, path::Path, sync::Arc};
+use std::{
+    collections::hash_map::Entry,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
 use thiserror::Error;
 
 /// Errors that occur while loading assets with an AssetServer
@@ -41,6 +46,28 @@ fn format_missing_asset_ext(exts: &[String]) -> String {
     }
 }
 
+/// An event sent when an asset fails to load. The [`LoadState`] of the asset is
+/// [`LoadState::Failed`] at this point.
+#[derive(Debug)]
+pub struct AssetLoadError {
+    pub path: AssetPath<'static>,
+    /// The [`AssetLoader::name`] of the loader used for the asset, if one was found.
+    pub loader: Option<&'static str>,
+    pub error: AssetServerError,
+}
+
+pub(crate) struct LoadErrorChannel {
+    pub sender: Sender<AssetLoadError>,
+    pub receiver: Receiver<AssetLoadError>,
+}
+
+impl Default for LoadErrorChannel {
+    fn default() -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        LoadErrorChannel { sender, receiver }
+    }
+}
+
 #[derive(Default)]
 pub(crate) struct AssetRefCounter {
     pub(crate) channel: Arc<RefChangeChannel>,
@@ -56,6 +83,7 @@ pub struct AssetServerInternal {
     loaders: RwLock<Vec<Arc<Box<dyn AssetLoader>>>>,
     extension_to_loader_index: RwLock<HashMap<String, usize>>,
     handle_to_path: Arc<RwLock<HashMap<HandleId, AssetPath<'static>>>>,
+    load_errors: LoadErrorChannel,
     task_pool: TaskPool,
 }
 
@@ -86,6 +114,7 @@ impl AssetServer {
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
+                load_errors: Default::default(),
                 task_pool,
                 asset_io,
             }),
@@ -184,6 +213,37 @@ impl AssetServer {
             .cloned()
     }
 
+    /// Returns the [TypeUuid](bevy_reflect::TypeUuid) of the asset the given handle points to.
+    ///
+    /// For handles created from an [AssetPath], the type is only known once the asset source has
+    /// been loaded. Returns `None` otherwise.
+    pub fn get_handle_type_uuid<H: Into<HandleId>>(&self, handle: H) -> Option<Uuid> {
+        match handle.into() {
+            HandleId::Id(type_uuid, _) => Some(type_uuid),
+            HandleId::AssetPathId(id) => self
+                .server
+                .asset_sources
+                .read()
+                .get(&id.source_path_id())
+                .and_then(|source_info| source_info.get_asset_type(id.label_id())),
+        }
+    }
+
+    /// Returns a typed clone of the given untyped handle, checking that it points to an asset of
+    /// type `T`.
+    ///
+    /// Handles created from an [AssetPath] can only be checked once the asset source has been
+    /// loaded, see [AssetServer::get_load_state].
+    pub fn get_typed_handle<T: Asset>(
+        &self,
+        handle: &HandleUntyped,
+    ) -> Result<Handle<T>, AssetServerError> {
+        match self.get_handle_type_uuid(handle) {
+            Some(type_uuid) if type_uuid == T::TYPE_UUID => Ok(handle.clone().typed()),
+            _ => Err(AssetServerError::IncorrectHandleType),
+        }
+    }
+
     pub fn get_load_state<H: Into<HandleId>>(&self, handle: H) -> LoadState {
         match handle.into() {
             HandleId::AssetPathId(id) => {
@@ -371,8 +431,18 @@ impl AssetServer {
         self.server
             .task_pool
             .spawn(async move {
-                if let Err(err) = server.load_async(owned_path, force).await {
+                if let Err(err) = server.load_async(owned_path.clone(), force).await {
                     warn!("{}", err);
+                    let loader = server
+                        .get_path_asset_loader(owned_path.path())
+                        .ok()
+                        .map(|loader| loader.name());
+                    // the receiver is owned by the server, so this can't fail
+                    let _ = server.server.load_errors.sender.send(AssetLoadError {
+                        path: owned_path,
+                        loader,
+                        error: err,
+                    });
                 }
             })
             .detach();
@@ -387,33 +457,108 @@ impl AssetServer {
         asset_path.into()
     }
 
+    /// Loads all assets in the given folder and its subfolders.
+    ///
+    /// This is an alias of [AssetServer::load_untyped_folder].
+    #[deprecated(note = "use `load_folder_typed` or `load_untyped_folder` instead")]
     #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
     pub fn load_folder<P: AsRef<Path>>(
         &self,
         path: P,
     ) -> Result<Vec<HandleUntyped>, AssetServerError> {
-        let path = path.as_ref();
+        self.load_untyped_folder(path)
+    }
+
+    /// Loads all assets in the given folder and its subfolders, skipping files without a
+    /// registered [AssetLoader].
+    ///
+    /// The returned handles may point to assets of different types. Once loaded, they can be
+    /// sorted by type with [AssetServer::get_handle_type_uuid] or converted with
+    /// [AssetServer::get_typed_handle].
+    #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
+    pub fn load_untyped_folder<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Vec<HandleUntyped>, AssetServerError> {
+        Ok(self
+            .folder_asset_paths(path.as_ref(), &FolderSettings::default())?
+            .into_iter()
+            .map(|path| self.load_untyped(path))
+            .collect())
+    }
+
+    /// Loads all assets of type `T` in the given folder and its subfolders, skipping files
+    /// without a registered [AssetLoader].
+    ///
+    /// Every loaded file is expected to contain an asset of type `T`, use
+    /// [AssetServer::load_folder_typed_with] to only load some of the files.
+    #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
+    pub fn load_folder_typed<T: Asset, P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<AssetFolder<T>, AssetServerError> {
+        self.load_folder_typed_with(path, &FolderSettings::default())
+    }
+
+    /// Loads the assets of type `T` in the given folder that match the [FolderSettings].
+    ///
+    /// ```no_run
+    /// # use bevy_asset::{AssetServer, FolderSettings, Handle};
+    /// # #[derive(bevy_reflect::TypeUuid)]
+    /// # #[uuid = "4c3b2ab2-2a13-4e4e-8f2b-b2d1f3e1a1c5"]
+    /// # struct Texture;
+    /// # fn load(asset_server: &AssetServer) {
+    /// let sprites = asset_server
+    ///     .load_folder_typed_with::<Texture, _>(
+    ///         "textures/rpg",
+    ///         &FolderSettings::default().filter("**/*.png"),
+    ///     )
+    ///     .unwrap();
+    /// let vendor: &Handle<Texture> = sprites.get("textures/rpg/chars/vendor.png").unwrap();
+    /// # }
+    /// ```
+    #[must_use = "not using the returned strong handles may result in the unexpected release of the assets"]
+    pub fn load_folder_typed_with<T: Asset, P: AsRef<Path>>(
+        &self,
+        path: P,
+        settings: &FolderSettings,
+    ) -> Result<AssetFolder<T>, AssetServerError> {
+        let mut folder = AssetFolder::default();
+        for path in self.folder_asset_paths(path.as_ref(), settings)? {
+            let handle = self.load(path.as_path());
+            folder.insert(path, handle);
+        }
+        Ok(folder)
+    }
+
+    fn folder_asset_paths(
+        &self,
+        path: &Path,
+        settings: &FolderSettings,
+    ) -> Result<Vec<PathBuf>, AssetServerError> {
         if !self.server.asset_io.is_directory(path) {
             return Err(AssetServerError::AssetFolderNotADirectory(
                 path.to_str().unwrap().to_string(),
             ));
         }
 
-        let mut handles = Vec::new();
-        for child_path in self.server.asset_io.read_directory(path.as_ref())? {
-            if self.server.asset_io.is_directory(&child_path) {
-                handles.extend(self.load_folder(&child_path)?);
-            } else {
-                if self.get_path_asset_loader(&child_path).is_err() {
-                    continue;
+        let mut paths = Vec::new();
+        let mut folders = vec![path.to_path_buf()];
+        while let Some(folder) = folders.pop() {
+            for child_path in self.server.asset_io.read_directory(&folder)? {
+                if self.server.asset_io.is_directory(&child_path) {
+                    if settings.recursive {
+                        folders.push(child_path);
+                    }
+                } else if self.get_path_asset_loader(&child_path).is_ok()
+                    && settings.matches(child_path.strip_prefix(path).unwrap_or(&child_path))
+                {
+                    paths.push(child_path);
                 }
-                let handle =
-                    self.load_untyped(child_path.to_str().expect("Path should be a valid string."));
-                handles.push(handle);
             }
         }
 
-        Ok(handles)
+        Ok(paths)
     }
 
     pub fn free_unused_assets(&self) {
@@ -550,10 +695,21 @@ pub fn free_unused_assets_system(asset_server: Res<AssetServer>) {
     free_unused_assets_system_impl(&asset_server);
 }
 
+/// Sends an [`AssetLoadError`] event for each asset which failed to load since the last run.
+pub fn asset_load_error_system(
+    asset_server: Res<AssetServer>,
+    mut load_errors: EventWriter<AssetLoadError>,
+) {
+    for load_error in asset_server.server.load_errors.receiver.try_iter() {
+        load_errors.send(load_error);
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     use crate::{loader::LoadedAsset, update_asset_storage_system};
+    use bevy_app::{Events, ManualEventReader};
     use bevy_ecs::prelude::*;
     use bevy_reflect::TypeUuid;
     use bevy_utils::BoxedFuture;
@@ -619,6 +775,7 @@ mod test {
                 asset_ref_counter: Default::default(),
                 handle_to_path: Default::default(),
                 asset_lifecycles: Default::default(),
+                load_errors: Default::default(),
                 task_pool: Default::default(),
                 asset_io: Box::new(FileAssetIo::new(asset_path)),
             }),
@@ -760,6 +917,49 @@ mod test {
         assert_eq!(asset_server.get_load_state(handle), LoadState::Failed);
     }
 
+    #[test]
+    fn test_load_error_events() {
+        let dir = create_dir_and_file("fake.fail");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FailingLoader);
+
+        let mut world = World::new();
+        world.insert_resource(asset_server.clone());
+        world.insert_resource(Events::<AssetLoadError>::default());
+        let mut asset_load_error_system = asset_load_error_system.system();
+        asset_load_error_system.initialize(&mut world);
+
+        let _handle = asset_server.load_untyped("fake.fail");
+
+        // the asset is loaded on a background task, wait for it to fail
+        let mut reader = ManualEventReader::<AssetLoadError>::default();
+        let mut load_errors = Vec::new();
+        for _ in 0..1000 {
+            asset_load_error_system.run((), &mut world);
+            let events = world.get_resource::<Events<AssetLoadError>>().unwrap();
+            load_errors.extend(reader.iter(events).map(|load_error| {
+                (
+                    load_error.path.get_id(),
+                    load_error.loader,
+                    matches!(load_error.error, AssetServerError::AssetLoaderError(_)),
+                )
+            }));
+            if !load_errors.is_empty() {
+                break;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(10));
+        }
+
+        assert_eq!(
+            load_errors,
+            vec![(
+                AssetPath::from("fake.fail").get_id(),
+                Some(std::any::type_name::<FailingLoader>()),
+                true
+            )]
+        );
+    }
+
     #[test]
     fn test_asset_lifecycle() {
         let dir = create_dir_and_file("fake.png");
@@ -842,6 +1042,94 @@ mod test {
         assert!(get_asset(&handle, &world).is_some());
     }
 
+    #[test]
+    fn test_typed_handle_conversion() {
+        let dir = create_dir_and_file("fake.png");
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        let mut assets = asset_server.register_asset_type::<PngAsset>();
+
+        // ids created by `Assets` carry their type
+        let handle = assets.add(PngAsset).clone_untyped();
+        assert_eq!(
+            asset_server.get_handle_type_uuid(&handle),
+            Some(PngAsset::TYPE_UUID)
+        );
+        assert!(asset_server.get_typed_handle::<PngAsset>(&handle).is_ok());
+
+        let other = HandleUntyped::weak(HandleId::new(Uuid::new_v4(), 42));
+        assert!(other.try_typed::<PngAsset>().is_err());
+
+        // path-based ids only know their type once loaded
+        let path: AssetPath = "fake.png".into();
+        let handle = asset_server.get_handle_untyped(path.get_id());
+        assert_eq!(asset_server.get_handle_type_uuid(&handle), None);
+        assert!(matches!(
+            asset_server.get_typed_handle::<PngAsset>(&handle),
+            Err(AssetServerError::IncorrectHandleType)
+        ));
+
+        futures_lite::future::block_on(asset_server.load_async(path, true)).unwrap();
+        assert_eq!(
+            asset_server.get_handle_type_uuid(&handle),
+            Some(PngAsset::TYPE_UUID)
+        );
+        let typed = asset_server.get_typed_handle::<PngAsset>(&handle).unwrap();
+        assert!(typed.is_strong());
+    }
+
+    #[test]
+    fn test_load_folder_typed() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("sprites/chars")).unwrap();
+        for file in &[
+            "sprites/a.png",
+            "sprites/b.fail",
+            "sprites/notes.txt",
+            "sprites/chars/c.png",
+        ] {
+            std::fs::write(dir.path().join(file), &[]).unwrap();
+        }
+        let asset_server = setup(dir.path());
+        asset_server.add_loader(FakePngLoader);
+        asset_server.add_loader(FailingLoader);
+
+        // files without a loader are skipped
+        let folder = asset_server
+            .load_folder_typed::<PngAsset, _>("sprites")
+            .unwrap();
+        assert_eq!(folder.len(), 3);
+        let handle = folder.get("sprites/chars/c.png").unwrap();
+        assert!(handle.is_strong());
+        assert_eq!(
+            HandleId::from(handle),
+            HandleId::from(AssetPath::from("sprites/chars/c.png"))
+        );
+
+        let folder = asset_server
+            .load_folder_typed_with::<PngAsset, _>(
+                "sprites",
+                &FolderSettings::default().filter("**/*.png"),
+            )
+            .unwrap();
+        assert_eq!(folder.len(), 2);
+        assert!(folder.get("sprites/b.fail").is_none());
+
+        let folder = asset_server
+            .load_folder_typed_with::<PngAsset, _>(
+                "sprites",
+                &FolderSettings::default().recursive(false).filter("*.png"),
+            )
+            .unwrap();
+        let paths = folder.iter().map(|(path, _)| path).collect::<Vec<_>>();
+        assert_eq!(paths, vec![Path::new("sprites/a.png")]);
+
+        assert!(matches!(
+            asset_server.load_folder_typed::<PngAsset, _>("sprites/a.png"),
+            Err(AssetServerError::AssetFolderNotADirectory(_))
+        ));
+    }
+
     #[test]
     fn test_get_handle_path() {
         const PATH: &str = "path/file.png";
@@ -0,0 +1,163 @@
+use crate::{Asset, Handle, HandleId};
+use bevy_utils::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+/// Settings of [`AssetServer::load_folder_typed_with`](crate::AssetServer::load_folder_typed_with).
+#[derive(Debug, Clone)]
+pub struct FolderSettings {
+    /// Whether the assets of subfolders are loaded too.
+    pub recursive: bool,
+    /// A glob pattern the paths of the assets must match, relative to the loaded folder and using
+    /// `/` as separator. `*` and `?` match any characters but `/`, and `**` matches any number of
+    /// folders, e.g. `**/*.png`.
+    pub filter: Option<String>,
+}
+
+impl Default for FolderSettings {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            filter: None,
+        }
+    }
+}
+
+impl FolderSettings {
+    pub fn recursive(mut self, recursive: bool) -> Self {
+        self.recursive = recursive;
+        self
+    }
+
+    pub fn filter(mut self, pattern: impl Into<String>) -> Self {
+        self.filter = Some(pattern.into());
+        self
+    }
+
+    /// Returns `true` if the asset at `path`, relative to the loaded folder, should be loaded.
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        let pattern = match &self.filter {
+            Some(pattern) => pattern,
+            None => return true,
+        };
+        let components = path
+            .components()
+            .filter_map(|component| match component {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        let pattern = pattern
+            .split('/')
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>();
+        glob_match(&pattern, &components)
+    }
+}
+
+fn glob_match(pattern: &[&str], path: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => path.is_empty(),
+        Some((&"**", rest)) => (0..=path.len()).any(|skipped| glob_match(rest, &path[skipped..])),
+        Some((part, rest)) => match path.split_first() {
+            Some((name, path)) => {
+                name_match(part.as_bytes(), name.as_bytes()) && glob_match(rest, path)
+            }
+            None => false,
+        },
+    }
+}
+
+fn name_match(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skipped| name_match(rest, &name[skipped..])),
+        Some((b'?', rest)) => !name.is_empty() && name_match(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && name_match(rest, &name[1..]),
+    }
+}
+
+/// The assets of a folder loaded with
+/// [`AssetServer::load_folder_typed`](crate::AssetServer::load_folder_typed), with a strong
+/// handle for each asset path.
+#[derive(Debug)]
+pub struct AssetFolder<T: Asset> {
+    handles: HashMap<PathBuf, Handle<T>>,
+}
+
+impl<T: Asset> Default for AssetFolder<T> {
+    fn default() -> Self {
+        Self {
+            handles: Default::default(),
+        }
+    }
+}
+
+impl<T: Asset> Clone for AssetFolder<T> {
+    fn clone(&self) -> Self {
+        Self {
+            handles: self.handles.clone(),
+        }
+    }
+}
+
+impl<T: Asset> AssetFolder<T> {
+    pub(crate) fn insert(&mut self, path: PathBuf, handle: Handle<T>) {
+        self.handles.insert(path, handle);
+    }
+
+    /// Returns the handle of the asset at `path`, relative to the asset folder, e.g.
+    /// `textures/rpg/chars/vendor.png`.
+    pub fn get<P: AsRef<Path>>(&self, path: P) -> Option<&Handle<T>> {
+        self.handles.get(path.as_ref())
+    }
+
+    /// Iterates over the paths of the assets and their handles.
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &Handle<T>)> {
+        self.handles
+            .iter()
+            .map(|(path, handle)| (path.as_path(), handle))
+    }
+
+    pub fn handles(&self) -> impl Iterator<Item = &Handle<T>> {
+        self.handles.values()
+    }
+
+    /// The ids of the handles, which can be passed to
+    /// [`AssetServer::get_group_load_state`](crate::AssetServer::get_group_load_state).
+    pub fn ids(&self) -> impl Iterator<Item = HandleId> + '_ {
+        self.handles.values().map(|handle| handle.id)
+    }
+
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FolderSettings;
+    use std::path::Path;
+
+    #[test]
+    fn glob_filter() {
+        let matches = |pattern: &str, path: &str| {
+            FolderSettings::default()
+                .filter(pattern)
+                .matches(Path::new(path))
+        };
+        assert!(matches("*.png", "a.png"));
+        assert!(!matches("*.png", "a.jpg"));
+        assert!(!matches("*.png", "chars/a.png"));
+        assert!(matches("**/*.png", "a.png"));
+        assert!(matches("**/*.png", "chars/vendor/a.png"));
+        assert!(matches("chars/*/a?.png", "chars/vendor/ab.png"));
+        assert!(!matches("chars/*/a?.png", "chars/vendor/a.png"));
+        assert!(matches("chars/**", "chars/vendor/a.png"));
+        assert!(!matches("chars/**", "tiles/a.png"));
+        assert!(FolderSettings::default().matches(Path::new("a.jpg")));
+    }
+}
@@ -52,6 +52,20 @@ impl HandleId {
     pub const fn new(type_uuid: Uuid, id: u64) -> Self {
         HandleId::Id(type_uuid, id)
     }
+
+    /// Returns the [TypeUuid](bevy_reflect::TypeUuid) of the asset this id points to, if it is
+    /// encoded in the id.
+    ///
+    /// Ids created from an [AssetPath] do not carry their asset type. Use
+    /// [AssetServer::get_handle_type_uuid](crate::AssetServer::get_handle_type_uuid) to resolve
+    /// the type of loaded path-based ids.
+    #[inline]
+    pub fn type_uuid(&self) -> Option<Uuid> {
+        match self {
+            HandleId::Id(type_uuid, _) => Some(*type_uuid),
+            HandleId::AssetPathId(_) => None,
+        }
+    }
 }
 
 /// A handle into a specific Asset of type `T`
@@ -275,10 +289,28 @@ impl HandleUntyped {
         matches!(self.handle_type, HandleType::Strong(_))
     }
 
-    pub fn typed<T: Asset>(mut self) -> Handle<T> {
-        if let HandleId::Id(type_uuid, _) = self.id {
+    /// Converts this handle into a typed [Handle].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the handle id encodes an asset type other than `T`. See
+    /// [HandleUntyped::try_typed] for a non-panicking version.
+    pub fn typed<T: Asset>(self) -> Handle<T> {
+        self.try_typed()
+            .unwrap_or_else(|_| panic!("Attempted to convert handle to invalid type."))
+    }
+
+    /// Converts this handle into a typed [Handle], or gives it back if the handle id encodes an
+    /// asset type other than `T`.
+    ///
+    /// Handles created from an [AssetPath] do not carry their asset type, so the conversion
+    /// always succeeds for them. Use
+    /// [AssetServer::get_typed_handle](crate::AssetServer::get_typed_handle) to check those
+    /// against the type of the loaded asset.
+    pub fn try_typed<T: Asset>(mut self) -> Result<Handle<T>, HandleUntyped> {
+        if let Some(type_uuid) = self.id.type_uuid() {
             if T::TYPE_UUID != type_uuid {
-                panic!("Attempted to convert handle to invalid type.");
+                return Err(self);
             }
         }
         let handle_type = match &self.handle_type {
@@ -287,11 +319,11 @@ impl HandleUntyped {
         };
         // ensure we don't send the RefChange event when "self" is dropped
         self.handle_type = HandleType::Weak;
-        Handle {
+        Ok(Handle {
             handle_type,
             id: self.id,
             marker: PhantomData::default(),
-        }
+        })
     }
 }
 
@@ -308,6 +340,17 @@ impl Drop for HandleUntyped {
     }
 }
 
+impl<T: Asset> From<Handle<T>> for HandleUntyped {
+    fn from(mut value: Handle<T>) -> Self {
+        // take the handle type to ensure we don't send the RefChange event when "value" is dropped
+        let handle_type = std::mem::replace(&mut value.handle_type, HandleType::Weak);
+        HandleUntyped {
+            id: value.id,
+            handle_type,
+        }
+    }
+}
+
 impl From<&HandleUntyped> for HandleId {
     fn from(value: &HandleUntyped) -> Self {
         value.id
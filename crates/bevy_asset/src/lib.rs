@@ -6,6 +6,7 @@ pub mod diagnostic;
     all(not(target_arch = "wasm32"), not(target_os = "android"))
 ))]
 mod filesystem_watcher;
+mod folder;
 mod handle;
 mod info;
 mod io;
@@ -14,12 +15,15 @@ mod path;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{AddAsset, AssetEvent, AssetServer, Assets, Handle, HandleUntyped};
+    pub use crate::{
+        AddAsset, AssetEvent, AssetLoadError, AssetServer, Assets, Handle, HandleUntyped,
+    };
 }
 
 pub use asset_server::*;
 pub use assets::*;
 pub use bevy_utils::BoxedFuture;
+pub use folder::*;
 pub use handle::*;
 pub use info::*;
 pub use io::*;
@@ -104,9 +108,14 @@ impl Plugin for AssetPlugin {
             SystemStage::parallel(),
         )
         .register_type::<HandleId>()
+        .add_event::<AssetLoadError>()
         .add_system_to_stage(
             bevy_app::CoreStage::PreUpdate,
             asset_server::free_unused_assets_system.system(),
+        )
+        .add_system_to_stage(
+            bevy_app::CoreStage::PreUpdate,
+            asset_server::asset_load_error_system.system(),
         );
 
         #[cfg(all(
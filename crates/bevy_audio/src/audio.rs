@@ -1,14 +1,33 @@
 use crate::{AudioSource, Decodable};
 use bevy_asset::{Asset, Handle};
 use parking_lot::RwLock;
-use std::{collections::VecDeque, fmt};
+use std::{
+    collections::VecDeque,
+    fmt,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Identifies a sound instance started with [`Audio::play`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PlaybackId(u64);
+
+/// Sent when a sound instance started with [`Audio::play`] finished playing, so that gameplay can
+/// chain sounds or clean up after it.
+///
+/// Without an audio device, sounds finish as soon as they are played.
+#[derive(Debug)]
+pub struct PlaybackFinished<P: Asset = AudioSource> {
+    pub id: PlaybackId,
+    pub handle: Handle<P>,
+}
 
 /// The external struct used to play audio
 pub struct Audio<P = AudioSource>
 where
     P: Asset + Decodable,
 {
-    pub queue: RwLock<VecDeque<Handle<P>>>,
+    pub queue: RwLock<VecDeque<(PlaybackId, Handle<P>)>>,
+    next_id: AtomicU64,
 }
 
 impl<P: Asset> fmt::Debug for Audio<P>
@@ -27,6 +46,7 @@ where
     fn default() -> Self {
         Self {
             queue: Default::default(),
+            next_id: Default::default(),
         }
     }
 }
@@ -37,7 +57,11 @@ where
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    pub fn play(&self, audio_source: Handle<P>) {
-        self.queue.write().push_front(audio_source);
+    /// Queues a sound to play, returning the id of the instance reported in its
+    /// [`PlaybackFinished`] event.
+    pub fn play(&self, audio_source: Handle<P>) -> PlaybackId {
+        let id = PlaybackId(self.next_id.fetch_add(1, Ordering::Relaxed));
+        self.queue.write().push_front((id, audio_source));
+        id
     }
 }
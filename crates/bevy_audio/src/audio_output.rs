@@ -1,5 +1,6 @@
-use crate::{Audio, AudioSource, Decodable};
-use bevy_asset::{Asset, Assets};
+use crate::{Audio, AudioSource, Decodable, PlaybackFinished, PlaybackId};
+use bevy_app::Events;
+use bevy_asset::{Asset, Assets, Handle};
 use bevy_ecs::world::World;
 use bevy_utils::tracing::warn;
 use rodio::{OutputStream, OutputStreamHandle, Sink};
@@ -8,31 +9,44 @@ use std::marker::PhantomData;
 /// Used internally to play audio on the current "audio device"
 pub struct AudioOutput<P = AudioSource>
 where
-    P: Decodable,
+    P: Asset + Decodable,
 {
     _stream: Option<OutputStream>,
     stream_handle: Option<OutputStreamHandle>,
+    /// The sinks of the sounds playing, checked each frame for [`PlaybackFinished`] events.
+    playing: Vec<(PlaybackId, Handle<P>, Sink)>,
     phantom: PhantomData<P>,
 }
 
 impl<P> Default for AudioOutput<P>
 where
-    P: Decodable,
+    P: Asset + Decodable,
 {
     fn default() -> Self {
         if let Ok((stream, stream_handle)) = OutputStream::try_default() {
             Self {
                 _stream: Some(stream),
                 stream_handle: Some(stream_handle),
+                playing: Vec::new(),
                 phantom: PhantomData,
             }
         } else {
             warn!("No audio device found.");
-            Self {
-                _stream: None,
-                stream_handle: None,
-                phantom: PhantomData,
-            }
+            Self::without_device()
+        }
+    }
+}
+
+impl<P> AudioOutput<P>
+where
+    P: Asset + Decodable,
+{
+    fn without_device() -> Self {
+        Self {
+            _stream: None,
+            stream_handle: None,
+            playing: Vec::new(),
+            phantom: PhantomData,
         }
     }
 }
@@ -43,32 +57,62 @@ where
     <P as Decodable>::Decoder: rodio::Source + Send + Sync,
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
-    fn play_source(&self, audio_source: &P) {
+    fn play_source(&mut self, id: PlaybackId, handle: Handle<P>, audio_source: &P) -> bool {
         if let Some(stream_handle) = &self.stream_handle {
             let sink = Sink::try_new(&stream_handle).unwrap();
             sink.append(audio_source.decoder());
-            sink.detach();
+            self.playing.push((id, handle, sink));
+            true
+        } else {
+            false
         }
     }
 
-    fn try_play_queued(&self, audio_sources: &Assets<P>, audio: &mut Audio<P>) {
+    fn try_play_queued(
+        &mut self,
+        audio_sources: &Assets<P>,
+        audio: &mut Audio<P>,
+        finished: &mut Events<PlaybackFinished<P>>,
+    ) {
         let mut queue = audio.queue.write();
         let len = queue.len();
         let mut i = 0;
         while i < len {
-            let audio_source_handle = queue.pop_back().unwrap();
+            let (id, audio_source_handle) = queue.pop_back().unwrap();
             if let Some(audio_source) = audio_sources.get(&audio_source_handle) {
-                self.play_source(audio_source);
+                if !self.play_source(id, audio_source_handle.clone(), audio_source) {
+                    finished.send(PlaybackFinished {
+                        id,
+                        handle: audio_source_handle,
+                    });
+                }
             } else {
                 // audio source hasn't loaded yet. add it back to the queue
-                queue.push_front(audio_source_handle);
+                queue.push_front((id, audio_source_handle));
             }
             i += 1;
         }
     }
+
+    /// Sends a [`PlaybackFinished`] event for each sound that finished playing.
+    fn check_finished(&mut self, finished: &mut Events<PlaybackFinished<P>>) {
+        let mut i = 0;
+        while i < self.playing.len() {
+            if self.playing[i].2.empty() {
+                let (id, handle, _) = self.playing.swap_remove(i);
+                finished.send(PlaybackFinished { id, handle });
+            } else {
+                i += 1;
+            }
+        }
+    }
 }
 
-/// Plays audio currently queued in the [Audio] resource through the [AudioOutput] resource
+/// Plays audio currently queued in the [Audio] resource through the [AudioOutput] resource, and
+/// sends the [PlaybackFinished] events of the sounds that finished playing.
+///
+/// It is added with its resources and events by
+/// [`AddAudioSource::add_audio_source`](crate::AddAudioSource::add_audio_source).
 pub fn play_queued_audio_system<P: Asset>(world: &mut World)
 where
     P: Decodable,
@@ -76,10 +120,65 @@ where
     <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
 {
     let world = world.cell();
-    let audio_output = world.get_non_send::<AudioOutput<P>>().unwrap();
+    let mut audio_output = world.get_non_send_mut::<AudioOutput<P>>().unwrap();
     let mut audio = world.get_resource_mut::<Audio<P>>().unwrap();
+    let mut finished = world
+        .get_resource_mut::<Events<PlaybackFinished<P>>>()
+        .unwrap();
 
+    audio_output.check_finished(&mut *finished);
     if let Some(audio_sources) = world.get_resource::<Assets<P>>() {
-        audio_output.try_play_queued(&*audio_sources, &mut *audio);
+        audio_output.try_play_queued(&*audio_sources, &mut *audio, &mut *finished);
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{AddAudioSource, Audio, AudioOutput, AudioSource, PlaybackFinished};
+    use bevy_app::{App, Events};
+    use bevy_asset::{AssetPlugin, AssetServer, Assets, FileAssetIo, Handle, HandleId};
+    use bevy_tasks::TaskPool;
+
+    #[test]
+    fn finished_without_device() {
+        let mut app = App::build();
+        app.insert_resource(AssetServer::new(FileAssetIo::new("."), TaskPool::new()))
+            .insert_non_send_resource(AudioOutput::<AudioSource>::without_device())
+            .add_plugin(AssetPlugin)
+            .add_audio_source::<AudioSource>();
+        let world = app.world_mut();
+        let handle = world
+            .get_resource_mut::<Assets<AudioSource>>()
+            .unwrap()
+            .add(AudioSource {
+                bytes: Vec::new().into(),
+            });
+        let not_loaded = Handle::<AudioSource>::weak(HandleId::random::<AudioSource>());
+
+        let audio = world.get_resource::<Audio<AudioSource>>().unwrap();
+        let first = audio.play(handle.clone());
+        let waiting = audio.play(not_loaded.clone());
+        let second = audio.play(handle.clone());
+        assert_ne!(first, second);
+
+        app.app.update();
+        let world = &app.app.world;
+        let events = world
+            .get_resource::<Events<PlaybackFinished<AudioSource>>>()
+            .unwrap();
+        let finished = events
+            .get_reader()
+            .iter(events)
+            .map(|finished| (finished.id, finished.handle.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(finished, vec![(first, handle.clone()), (second, handle)]);
+
+        // sounds that haven't loaded yet stay queued
+        let audio = world.get_resource::<Audio<AudioSource>>().unwrap();
+        let queue = audio.queue.read();
+        assert_eq!(
+            queue.iter().cloned().collect::<Vec<_>>(),
+            vec![(waiting, not_loaded)]
+        );
+    }
+}
@@ -1,8 +1,10 @@
+use crate::wav;
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_reflect::TypeUuid;
 use bevy_utils::BoxedFuture;
 use std::{io::Cursor, sync::Arc};
+use thiserror::Error;
 
 /// A source of audio data
 #[derive(Debug, Clone, TypeUuid)]
@@ -17,16 +19,99 @@ impl AsRef<[u8]> for AudioSource {
     }
 }
 
-/// Loads mp3 files as [AudioSource] [Assets](bevy_asset::Assets)
+/// The audio formats [`AudioLoader`] can recognize.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AudioFormat {
+    Mp3,
+    Flac,
+    Wav,
+    /// Ogg Vorbis, which is decoded progressively while playing.
+    Vorbis,
+}
+
+impl AudioFormat {
+    /// Detects the format of an audio file from its first bytes.
+    pub fn detect(bytes: &[u8]) -> Option<AudioFormat> {
+        match bytes {
+            [b'f', b'L', b'a', b'C', ..] => Some(AudioFormat::Flac),
+            [b'O', b'g', b'g', b'S', ..] => Some(AudioFormat::Vorbis),
+            [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'A', b'V', b'E', ..] => {
+                Some(AudioFormat::Wav)
+            }
+            // an ID3 tag, or the sync word of an MPEG audio frame
+            [b'I', b'D', b'3', ..] => Some(AudioFormat::Mp3),
+            [0xFF, second, ..] if second & 0xE0 == 0xE0 => Some(AudioFormat::Mp3),
+            _ => None,
+        }
+    }
+
+    /// The cargo feature enabling support for this format.
+    pub fn feature(&self) -> &'static str {
+        match self {
+            AudioFormat::Mp3 => "mp3",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Wav => "wav",
+            AudioFormat::Vorbis => "vorbis",
+        }
+    }
+
+    /// Returns `true` if support for this format was compiled in.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            AudioFormat::Mp3 => cfg!(feature = "mp3"),
+            AudioFormat::Flac => cfg!(feature = "flac"),
+            AudioFormat::Wav => cfg!(feature = "wav"),
+            AudioFormat::Vorbis => cfg!(feature = "vorbis"),
+        }
+    }
+}
+
+/// Errors that occur while loading an [`AudioSource`]. They are reported with
+/// [`AssetLoadError`](bevy_asset::AssetLoadError) events.
+#[derive(Error, Debug)]
+pub enum AudioFormatError {
+    #[error("unrecognized audio format")]
+    UnknownFormat,
+    #[error("{format:?} audio support is disabled, enable the `{}` feature", .format.feature())]
+    DisabledFormat { format: AudioFormat },
+    #[error("unsupported WAV encoding: format tag {tag:#06x}, {bits_per_sample} bits per sample")]
+    UnsupportedWavEncoding { tag: u16, bits_per_sample: u16 },
+    #[error("invalid WAV file: {0}")]
+    InvalidWav(&'static str),
+}
+
+/// Loads mp3, flac, wav and ogg files as [AudioSource] [Assets](bevy_asset::Assets), depending
+/// on the enabled features.
+///
+/// WAV files in 8, 24 or 32-bit PCM, floating point or ADPCM encodings are converted to 16-bit
+/// PCM when loaded.
 #[derive(Default)]
-pub struct Mp3Loader;
-
-impl AssetLoader for Mp3Loader {
-    fn load(&self, bytes: &[u8], load_context: &mut LoadContext) -> BoxedFuture<Result<()>> {
-        load_context.set_default_asset(LoadedAsset::new(AudioSource {
-            bytes: bytes.into(),
-        }));
-        Box::pin(async move { Ok(()) })
+pub struct AudioLoader;
+
+#[deprecated(note = "renamed to `AudioLoader`")]
+pub type Mp3Loader = AudioLoader;
+
+impl AssetLoader for AudioLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<()>> {
+        Box::pin(async move {
+            let format = AudioFormat::detect(bytes).ok_or(AudioFormatError::UnknownFormat)?;
+            if !format.is_enabled() {
+                return Err(AudioFormatError::DisabledFormat { format }.into());
+            }
+            let bytes = match format {
+                AudioFormat::Wav => match wav::convert_to_pcm16(bytes)? {
+                    Some(converted) => converted.into(),
+                    None => bytes.into(),
+                },
+                _ => bytes.into(),
+            };
+            load_context.set_default_asset(LoadedAsset::new(AudioSource { bytes }));
+            Ok(())
+        })
     }
 
     fn extensions(&self) -> &[&str] {
@@ -39,6 +124,8 @@ impl AssetLoader for Mp3Loader {
             "wav",
             #[cfg(feature = "vorbis")]
             "ogg",
+            #[cfg(feature = "vorbis")]
+            "oga",
         ]
     }
 }
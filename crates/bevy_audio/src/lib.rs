@@ -1,10 +1,11 @@
 mod audio;
 mod audio_output;
 mod audio_source;
+mod wav;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Audio, AudioOutput, AudioSource, Decodable};
+    pub use crate::{AddAudioSource, Audio, AudioOutput, AudioSource, Decodable, PlaybackFinished};
 }
 
 pub use audio::*;
@@ -12,7 +13,7 @@ pub use audio_output::*;
 pub use audio_source::*;
 
 use bevy_app::prelude::*;
-use bevy_asset::AddAsset;
+use bevy_asset::{AddAsset, Asset};
 use bevy_ecs::system::IntoExclusiveSystem;
 
 /// Adds support for audio playback to an App
@@ -21,15 +22,38 @@ pub struct AudioPlugin;
 
 impl Plugin for AudioPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        app.init_non_send_resource::<AudioOutput<AudioSource>>()
-            .add_asset::<AudioSource>()
-            .init_resource::<Audio<AudioSource>>()
-            .add_system_to_stage(
-                CoreStage::PostUpdate,
-                play_queued_audio_system::<AudioSource>.exclusive_system(),
-            );
+        app.add_audio_source::<AudioSource>();
 
         #[cfg(any(feature = "mp3", feature = "flac", feature = "wav", feature = "vorbis"))]
-        app.init_asset_loader::<Mp3Loader>();
+        app.init_asset_loader::<AudioLoader>();
+    }
+}
+
+/// Adds the playback of a [`Decodable`] asset type to an App
+pub trait AddAudioSource {
+    /// Adds the asset `P`, the [`Audio`] and [`AudioOutput`] resources playing it, its
+    /// [`PlaybackFinished`] events and the [`play_queued_audio_system`] sending them.
+    fn add_audio_source<P>(&mut self) -> &mut Self
+    where
+        P: Asset + Decodable,
+        <P as Decodable>::Decoder: rodio::Source + Send + Sync,
+        <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync;
+}
+
+impl AddAudioSource for AppBuilder {
+    fn add_audio_source<P>(&mut self) -> &mut Self
+    where
+        P: Asset + Decodable,
+        <P as Decodable>::Decoder: rodio::Source + Send + Sync,
+        <<P as Decodable>::Decoder as Iterator>::Item: rodio::Sample + Send + Sync,
+    {
+        self.init_non_send_resource::<AudioOutput<P>>()
+            .add_asset::<P>()
+            .init_resource::<Audio<P>>()
+            .add_event::<PlaybackFinished<P>>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                play_queued_audio_system::<P>.exclusive_system(),
+            )
     }
 }
@@ -0,0 +1,406 @@
+//! Converts the WAV encodings the decoder doesn't support to 16-bit PCM.
+
+use crate::AudioFormatError;
+use std::convert::TryInto;
+
+const WAVE_FORMAT_PCM: u16 = 0x0001;
+const WAVE_FORMAT_ADPCM: u16 = 0x0002;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+const WAVE_FORMAT_IMA_ADPCM: u16 = 0x0011;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+struct Format<'a> {
+    tag: u16,
+    channels: u16,
+    sample_rate: u32,
+    block_align: u16,
+    bits_per_sample: u16,
+    extra: &'a [u8],
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn i16_at(bytes: &[u8], offset: usize) -> Option<i16> {
+    u16_at(bytes, offset).map(|value| value as i16)
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn invalid(reason: &'static str) -> AudioFormatError {
+    AudioFormatError::InvalidWav(reason)
+}
+
+/// Returns the `fmt ` and `data` chunks of a RIFF WAVE file.
+fn chunks(bytes: &[u8]) -> Result<(Format<'_>, &[u8]), AudioFormatError> {
+    let mut format = None;
+    let mut data = None;
+    let mut offset = 12;
+    while let (Some(id), Some(len)) = (bytes.get(offset..offset + 4), u32_at(bytes, offset + 4)) {
+        let start = offset + 8;
+        let end = bytes.len().min(start + len as usize);
+        let chunk = &bytes[start..end];
+        match id {
+            b"fmt " => {
+                let header = chunk
+                    .get(..16)
+                    .ok_or_else(|| invalid("truncated fmt chunk"))?;
+                let extra = chunk.get(18..).unwrap_or(&[]);
+                let mut tag = u16_at(header, 0).unwrap();
+                if tag == WAVE_FORMAT_EXTENSIBLE {
+                    // the actual format is the start of the sub-format GUID
+                    tag = u16_at(extra, 6).ok_or_else(|| invalid("truncated fmt chunk"))?;
+                }
+                format = Some(Format {
+                    tag,
+                    channels: u16_at(header, 2).unwrap(),
+                    sample_rate: u32_at(header, 4).unwrap(),
+                    block_align: u16_at(header, 12).unwrap(),
+                    bits_per_sample: u16_at(header, 14).unwrap(),
+                    extra,
+                });
+            }
+            b"data" => data = Some(chunk),
+            _ => {}
+        }
+        // chunks are padded to an even size
+        offset = start + len as usize + (len as usize & 1);
+    }
+    let format = format.ok_or_else(|| invalid("missing fmt chunk"))?;
+    if format.channels == 0 || format.block_align == 0 {
+        return Err(invalid("invalid fmt chunk"));
+    }
+    Ok((format, data.ok_or_else(|| invalid("missing data chunk"))?))
+}
+
+/// Converts a WAV file to 16-bit PCM if it uses another encoding. Returns `None` if the file is
+/// already 16-bit PCM.
+pub(crate) fn convert_to_pcm16(bytes: &[u8]) -> Result<Option<Vec<u8>>, AudioFormatError> {
+    let (format, data) = chunks(bytes)?;
+    let samples = match (format.tag, format.bits_per_sample) {
+        (WAVE_FORMAT_PCM, 16) => return Ok(None),
+        (WAVE_FORMAT_PCM, 8) => data.iter().map(|&s| (s as i16 - 128) << 8).collect(),
+        (WAVE_FORMAT_PCM, 24) => data
+            .chunks_exact(3)
+            .map(|s| i16::from_le_bytes([s[1], s[2]]))
+            .collect(),
+        (WAVE_FORMAT_PCM, 32) => data
+            .chunks_exact(4)
+            .map(|s| i16::from_le_bytes([s[2], s[3]]))
+            .collect(),
+        (WAVE_FORMAT_IEEE_FLOAT, 32) => data
+            .chunks_exact(4)
+            .map(|s| float_to_i16(f32::from_le_bytes(s.try_into().unwrap()) as f64))
+            .collect(),
+        (WAVE_FORMAT_IEEE_FLOAT, 64) => data
+            .chunks_exact(8)
+            .map(|s| float_to_i16(f64::from_le_bytes(s.try_into().unwrap())))
+            .collect(),
+        (WAVE_FORMAT_IMA_ADPCM, 4) => decode_ima_adpcm(&format, data)?,
+        (WAVE_FORMAT_ADPCM, 4) => decode_ms_adpcm(&format, data)?,
+        (tag, bits_per_sample) => {
+            return Err(AudioFormatError::UnsupportedWavEncoding {
+                tag,
+                bits_per_sample,
+            })
+        }
+    };
+    Ok(Some(write_pcm16(&format, &samples)))
+}
+
+fn float_to_i16(sample: f64) -> i16 {
+    (sample.max(-1.0).min(1.0) * i16::MAX as f64) as i16
+}
+
+fn write_pcm16(format: &Format<'_>, samples: &[i16]) -> Vec<u8> {
+    let data_len = samples.len() as u32 * 2;
+    let block_align = format.channels * 2;
+    let mut bytes = Vec::with_capacity(44 + data_len as usize);
+    bytes.extend_from_slice(b"RIFF");
+    bytes.extend_from_slice(&(36 + data_len).to_le_bytes());
+    bytes.extend_from_slice(b"WAVEfmt ");
+    bytes.extend_from_slice(&16u32.to_le_bytes());
+    bytes.extend_from_slice(&WAVE_FORMAT_PCM.to_le_bytes());
+    bytes.extend_from_slice(&format.channels.to_le_bytes());
+    bytes.extend_from_slice(&format.sample_rate.to_le_bytes());
+    bytes.extend_from_slice(&(format.sample_rate * block_align as u32).to_le_bytes());
+    bytes.extend_from_slice(&block_align.to_le_bytes());
+    bytes.extend_from_slice(&16u16.to_le_bytes());
+    bytes.extend_from_slice(b"data");
+    bytes.extend_from_slice(&data_len.to_le_bytes());
+    for sample in samples {
+        bytes.extend_from_slice(&sample.to_le_bytes());
+    }
+    bytes
+}
+
+fn clamp_i16(sample: i32) -> i16 {
+    sample.max(i16::MIN as i32).min(i16::MAX as i32) as i16
+}
+
+const IMA_INDEX_TABLE: [i32; 8] = [-1, -1, -1, -1, 2, 4, 6, 8];
+
+const IMA_STEP_TABLE: [i32; 89] = [
+    7, 8, 9, 10, 11, 12, 13, 14, 16, 17, 19, 21, 23, 25, 28, 31, 34, 37, 41, 45, 50, 55, 60, 66,
+    73, 80, 88, 97, 107, 118, 130, 143, 157, 173, 190, 209, 230, 253, 279, 307, 337, 371, 408, 449,
+    494, 544, 598, 658, 724, 796, 876, 963, 1060, 1166, 1282, 1411, 1552, 1707, 1878, 2066, 2272,
+    2499, 2749, 3024, 3327, 3660, 4026, 4428, 4871, 5358, 5894, 6484, 7132, 7845, 8630, 9493,
+    10442, 11487, 12635, 13899, 15289, 16818, 18500, 20350, 22385, 24623, 27086, 29794, 32767,
+];
+
+struct ImaChannel {
+    predictor: i32,
+    index: i32,
+}
+
+impl ImaChannel {
+    fn decode(&mut self, nibble: u8) -> i16 {
+        let step = IMA_STEP_TABLE[self.index as usize];
+        let mut diff = step >> 3;
+        if nibble & 4 != 0 {
+            diff += step;
+        }
+        if nibble & 2 != 0 {
+            diff += step >> 1;
+        }
+        if nibble & 1 != 0 {
+            diff += step >> 2;
+        }
+        if nibble & 8 != 0 {
+            diff = -diff;
+        }
+        self.predictor = clamp_i16(self.predictor + diff) as i32;
+        self.index = (self.index + IMA_INDEX_TABLE[(nibble & 7) as usize])
+            .max(0)
+            .min(88);
+        self.predictor as i16
+    }
+}
+
+/// Decodes IMA (DVI) ADPCM, where each block starts with a 4-byte header per channel, followed
+/// by 4-byte groups of 8 samples for each channel in turn.
+fn decode_ima_adpcm(format: &Format<'_>, data: &[u8]) -> Result<Vec<i16>, AudioFormatError> {
+    let channels = format.channels as usize;
+    let mut samples = Vec::new();
+    for block in data.chunks(format.block_align as usize) {
+        let header = block
+            .get(..4 * channels)
+            .ok_or_else(|| invalid("truncated ADPCM block"))?;
+        let mut decoders = header
+            .chunks_exact(4)
+            .map(|header| ImaChannel {
+                predictor: i16::from_le_bytes([header[0], header[1]]) as i32,
+                index: (header[2] as i32).min(88),
+            })
+            .collect::<Vec<_>>();
+        samples.extend(decoders.iter().map(|decoder| decoder.predictor as i16));
+
+        for group in block[4 * channels..].chunks_exact(4 * channels) {
+            let start = samples.len();
+            samples.resize(start + 8 * channels, 0);
+            for (channel, bytes) in group.chunks_exact(4).enumerate() {
+                let decoder = &mut decoders[channel];
+                for (i, byte) in bytes.iter().enumerate() {
+                    samples[start + (2 * i) * channels + channel] = decoder.decode(byte & 0x0F);
+                    samples[start + (2 * i + 1) * channels + channel] = decoder.decode(byte >> 4);
+                }
+            }
+        }
+    }
+    Ok(samples)
+}
+
+const MS_ADAPTATION_TABLE: [i32; 16] = [
+    230, 230, 230, 230, 307, 409, 512, 614, 768, 614, 512, 409, 307, 230, 230, 230,
+];
+
+/// The bounds of the quantization step of Microsoft ADPCM. It is stored as a 16-bit value in the
+/// block headers, and never drops below 16.
+const MS_MIN_DELTA: i32 = 16;
+const MS_MAX_DELTA: i32 = i16::MAX as i32;
+
+const MS_COEFFICIENTS: [(i32, i32); 7] = [
+    (256, 0),
+    (512, -256),
+    (0, 0),
+    (192, 64),
+    (240, 0),
+    (460, -208),
+    (392, -232),
+];
+
+struct MsChannel {
+    coefficients: (i32, i32),
+    delta: i32,
+    sample1: i32,
+    sample2: i32,
+}
+
+impl MsChannel {
+    fn decode(&mut self, nibble: u8) -> i16 {
+        let signed = if nibble & 8 != 0 {
+            nibble as i32 - 16
+        } else {
+            nibble as i32
+        };
+        let predictor =
+            (self.sample1 * self.coefficients.0 + self.sample2 * self.coefficients.1) >> 8;
+        let sample = clamp_i16(predictor + signed * self.delta);
+        self.sample2 = self.sample1;
+        self.sample1 = sample as i32;
+        self.delta = ((MS_ADAPTATION_TABLE[nibble as usize] * self.delta) >> 8)
+            .clamp(MS_MIN_DELTA, MS_MAX_DELTA);
+        sample
+    }
+}
+
+/// Decodes Microsoft ADPCM, where each block starts with the predictor, delta and two initial
+/// samples of every channel, followed by interleaved nibbles, high nibble first.
+fn decode_ms_adpcm(format: &Format<'_>, data: &[u8]) -> Result<Vec<i16>, AudioFormatError> {
+    let channels = format.channels as usize;
+    // the fmt chunk may define its own coefficients after the samples per block
+    let coefficients = match u16_at(format.extra, 2) {
+        Some(count) if count > 0 => (0..count as usize)
+            .map(|i| {
+                let offset = 4 + 4 * i;
+                Some((
+                    i16_at(format.extra, offset)? as i32,
+                    i16_at(format.extra, offset + 2)? as i32,
+                ))
+            })
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| invalid("truncated ADPCM coefficients"))?,
+        _ => MS_COEFFICIENTS.to_vec(),
+    };
+
+    let mut samples = Vec::new();
+    for block in data.chunks(format.block_align as usize) {
+        if block.len() < 7 * channels {
+            return Err(invalid("truncated ADPCM block"));
+        }
+        let field = |index: usize, channel: usize| {
+            i16_at(block, channels + 2 * (index * channels + channel)).unwrap() as i32
+        };
+        let mut decoders = (0..channels)
+            .map(|channel| {
+                Ok(MsChannel {
+                    coefficients: *coefficients
+                        .get(block[channel] as usize)
+                        .ok_or_else(|| invalid("invalid ADPCM predictor"))?,
+                    delta: field(0, channel).max(MS_MIN_DELTA),
+                    sample1: field(1, channel),
+                    sample2: field(2, channel),
+                })
+            })
+            .collect::<Result<Vec<_>, AudioFormatError>>()?;
+        samples.extend(decoders.iter().map(|decoder| decoder.sample2 as i16));
+        samples.extend(decoders.iter().map(|decoder| decoder.sample1 as i16));
+
+        let mut channel = 0;
+        for byte in &block[7 * channels..] {
+            for &nibble in &[byte >> 4, byte & 0x0F] {
+                samples.push(decoders[channel].decode(nibble));
+                channel = (channel + 1) % channels;
+            }
+        }
+    }
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convert_to_pcm16, WAVE_FORMAT_ADPCM, WAVE_FORMAT_IMA_ADPCM};
+
+    /// Builds a WAV file with a single channel, 4 bits per sample and the given data.
+    fn wav(tag: u16, block_align: u16, data: &[u8]) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(4 + 28 + 8 + data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&20u32.to_le_bytes());
+        bytes.extend_from_slice(&tag.to_le_bytes());
+        bytes.extend_from_slice(&1u16.to_le_bytes());
+        bytes.extend_from_slice(&8000u32.to_le_bytes());
+        bytes.extend_from_slice(&4000u32.to_le_bytes());
+        bytes.extend_from_slice(&block_align.to_le_bytes());
+        bytes.extend_from_slice(&4u16.to_le_bytes());
+        // no extra bytes, so Microsoft ADPCM uses the standard coefficients
+        bytes.extend_from_slice(&2u16.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(data);
+        bytes
+    }
+
+    fn samples(pcm: &[u8]) -> Vec<i16> {
+        pcm[44..]
+            .chunks_exact(2)
+            .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+            .collect()
+    }
+
+    fn ms_block(predictor: u8, delta: i16, sample1: i16, sample2: i16, nibbles: &[u8]) -> Vec<u8> {
+        let mut block = vec![predictor];
+        block.extend_from_slice(&delta.to_le_bytes());
+        block.extend_from_slice(&sample1.to_le_bytes());
+        block.extend_from_slice(&sample2.to_le_bytes());
+        block.extend_from_slice(nibbles);
+        block
+    }
+
+    #[test]
+    fn decode_ms_adpcm_block() {
+        let block = ms_block(0, 16, 100, 50, &[0x12, 0x8F]);
+        let pcm = convert_to_pcm16(&wav(WAVE_FORMAT_ADPCM, 9, &block))
+            .unwrap()
+            .unwrap();
+        // the header samples come first, oldest first. With the first predictor, each sample is
+        // the previous one plus the nibble times the delta, which adapts after each nibble.
+        assert_eq!(samples(&pcm), vec![50, 100, 116, 148, 20, -28]);
+
+        // the second predictor extrapolates from the two previous samples
+        let block = ms_block(1, 16, 100, 50, &[0x10]);
+        let pcm = convert_to_pcm16(&wav(WAVE_FORMAT_ADPCM, 8, &block))
+            .unwrap()
+            .unwrap();
+        assert_eq!(samples(&pcm), vec![50, 100, 166, 232]);
+    }
+
+    #[test]
+    fn ms_adpcm_delta_is_clamped() {
+        // the largest nibbles triple the delta every time, which would overflow without a maximum
+        let block = ms_block(2, i16::MAX, 0, 0, &[0x77; 32]);
+        let pcm = convert_to_pcm16(&wav(WAVE_FORMAT_ADPCM, block.len() as u16, &block))
+            .unwrap()
+            .unwrap();
+        assert!(samples(&pcm)[2..].iter().all(|&sample| sample == i16::MAX));
+
+        // an invalid delta in the header is raised to the minimum
+        let block = ms_block(2, 0, 0, 0, &[0x10]);
+        let pcm = convert_to_pcm16(&wav(WAVE_FORMAT_ADPCM, 8, &block))
+            .unwrap()
+            .unwrap();
+        assert_eq!(samples(&pcm), vec![0, 0, 16, 0]);
+    }
+
+    #[test]
+    fn decode_ima_adpcm_block() {
+        // the header holds the initial sample and step index, then 8 nibbles, low nibble first
+        let block = [100, 0, 0, 0, 0x70, 0x00, 0x08, 0x00];
+        let pcm = convert_to_pcm16(&wav(WAVE_FORMAT_IMA_ADPCM, 8, &block))
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            samples(&pcm),
+            vec![100, 100, 111, 113, 114, 113, 114, 115, 116]
+        );
+    }
+}
@@ -0,0 +1,375 @@
+use bevy_ecs::world::World;
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+use std::{any::type_name, str::FromStr};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum ConsoleError {
+    #[error("unknown command `{0}`, type `help` for the list of commands")]
+    UnknownCommand(String),
+    #[error("missing argument `{name}` of type {type_name}")]
+    MissingArgument {
+        name: &'static str,
+        type_name: &'static str,
+    },
+    #[error("argument `{name}` expects a value of type {type_name}, got `{value}`")]
+    InvalidArgument {
+        name: &'static str,
+        type_name: &'static str,
+        value: String,
+    },
+    #[error("too many arguments, `{command}` takes {count}")]
+    TooManyArguments { command: String, count: usize },
+    #[error("unterminated quote")]
+    UnterminatedQuote,
+}
+
+/// An argument of a [`ConsoleCommand`], parsed from text into a reflected value.
+#[derive(Clone)]
+pub struct ConsoleArg {
+    pub name: &'static str,
+    pub type_name: &'static str,
+    pub optional: bool,
+    parse: fn(&str) -> Option<Box<dyn Reflect>>,
+}
+
+impl ConsoleArg {
+    pub fn new<T: Reflect + FromStr>(name: &'static str) -> Self {
+        ConsoleArg {
+            name,
+            type_name: short_type_name(type_name::<T>()),
+            optional: false,
+            parse: |value| {
+                value
+                    .parse::<T>()
+                    .ok()
+                    .map(|value| Box::new(value) as Box<dyn Reflect>)
+            },
+        }
+    }
+
+    fn parse(&self, value: &str) -> Result<Box<dyn Reflect>, ConsoleError> {
+        (self.parse)(value).ok_or_else(|| ConsoleError::InvalidArgument {
+            name: self.name,
+            type_name: self.type_name,
+            value: value.to_string(),
+        })
+    }
+}
+
+fn short_type_name(type_name: &'static str) -> &'static str {
+    type_name.rsplit("::").next().unwrap_or(type_name)
+}
+
+/// The parsed arguments given to a [`ConsoleCommand`], in the order they were declared.
+/// Optional arguments that weren't given are `None`.
+#[derive(Debug, Default)]
+pub struct ConsoleArgs {
+    values: Vec<Option<Box<dyn Reflect>>>,
+}
+
+impl ConsoleArgs {
+    /// The value of the argument at `index`, if it was given and is of type `T`
+    pub fn get<T: Reflect>(&self, index: usize) -> Option<&T> {
+        self.values.get(index)?.as_ref()?.downcast_ref::<T>()
+    }
+
+    /// The reflected value of the argument at `index`, if it was given
+    pub fn get_reflect(&self, index: usize) -> Option<&dyn Reflect> {
+        self.values.get(index)?.as_deref()
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+}
+
+/// The lines a [`ConsoleCommand`] prints to the console.
+#[derive(Debug, Default)]
+pub struct ConsoleOutput {
+    pub(crate) lines: Vec<String>,
+}
+
+impl ConsoleOutput {
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.lines.push(line.into());
+    }
+}
+
+type CommandHandler = Box<dyn Fn(&mut World, &ConsoleArgs, &mut ConsoleOutput) + Send + Sync>;
+
+/// A command that can be run from the [`Console`](crate::Console), with typed arguments.
+///
+/// ```
+/// # use bevy_console::{ConsoleArgs, ConsoleCommand, ConsoleOutput};
+/// # use bevy_ecs::world::World;
+/// struct Gravity(f32);
+///
+/// let command = ConsoleCommand::new(
+///     "gravity",
+///     "Sets the gravity",
+///     |world: &mut World, args: &ConsoleArgs, output: &mut ConsoleOutput| {
+///         let value = *args.get::<f32>(0).unwrap();
+///         world.insert_resource(Gravity(value));
+///         output.print(format!("gravity set to {}", value));
+///     },
+/// )
+/// .with_arg::<f32>("value");
+/// ```
+pub struct ConsoleCommand {
+    pub name: String,
+    pub description: String,
+    args: Vec<ConsoleArg>,
+    handler: CommandHandler,
+}
+
+impl ConsoleCommand {
+    pub fn new(
+        name: impl Into<String>,
+        description: impl Into<String>,
+        handler: impl Fn(&mut World, &ConsoleArgs, &mut ConsoleOutput) + Send + Sync + 'static,
+    ) -> Self {
+        ConsoleCommand {
+            name: name.into(),
+            description: description.into(),
+            args: Vec::new(),
+            handler: Box::new(handler),
+        }
+    }
+
+    /// Adds a required argument. Required arguments must come before optional ones.
+    pub fn with_arg<T: Reflect + FromStr>(mut self, name: &'static str) -> Self {
+        debug_assert!(
+            self.args.iter().all(|arg| !arg.optional),
+            "required arguments must come before optional ones"
+        );
+        self.args.push(ConsoleArg::new::<T>(name));
+        self
+    }
+
+    /// Adds an optional argument, `None` in the [`ConsoleArgs`] when it isn't given.
+    pub fn with_optional_arg<T: Reflect + FromStr>(mut self, name: &'static str) -> Self {
+        self.args.push(ConsoleArg {
+            optional: true,
+            ..ConsoleArg::new::<T>(name)
+        });
+        self
+    }
+
+    pub fn args(&self) -> &[ConsoleArg] {
+        &self.args
+    }
+
+    /// The name and arguments of the command, as shown by `help`
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.clone();
+        for arg in self.args.iter() {
+            if arg.optional {
+                usage.push_str(&format!(" [{}: {}]", arg.name, arg.type_name));
+            } else {
+                usage.push_str(&format!(" <{}: {}>", arg.name, arg.type_name));
+            }
+        }
+        usage
+    }
+
+    fn parse_args(&self, values: &[String]) -> Result<ConsoleArgs, ConsoleError> {
+        if values.len() > self.args.len() {
+            return Err(ConsoleError::TooManyArguments {
+                command: self.name.clone(),
+                count: self.args.len(),
+            });
+        }
+        let values = self
+            .args
+            .iter()
+            .enumerate()
+            .map(|(index, arg)| match values.get(index) {
+                Some(value) => arg.parse(value).map(Some),
+                None if arg.optional => Ok(None),
+                None => Err(ConsoleError::MissingArgument {
+                    name: arg.name,
+                    type_name: arg.type_name,
+                }),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(ConsoleArgs { values })
+    }
+}
+
+/// The commands registered with
+/// [`AddConsoleCommand::add_console_command`](crate::AddConsoleCommand::add_console_command).
+#[derive(Default)]
+pub struct ConsoleCommands {
+    commands: HashMap<String, ConsoleCommand>,
+}
+
+impl ConsoleCommands {
+    /// The name of the built-in command listing the commands
+    pub const HELP: &'static str = "help";
+
+    /// Registers a command, replacing any command of the same name.
+    pub fn add(&mut self, command: ConsoleCommand) {
+        self.commands.insert(command.name.clone(), command);
+    }
+
+    pub fn get(&self, name: &str) -> Option<&ConsoleCommand> {
+        self.commands.get(name)
+    }
+
+    /// Iterates over the commands, sorted by name
+    pub fn iter(&self) -> impl Iterator<Item = &ConsoleCommand> {
+        let mut commands = self.commands.values().collect::<Vec<_>>();
+        commands.sort_by(|a, b| a.name.cmp(&b.name));
+        commands.into_iter()
+    }
+
+    /// The names of the commands starting with `prefix`, sorted
+    pub fn complete(&self, prefix: &str) -> Vec<&str> {
+        let mut names = self
+            .commands
+            .keys()
+            .map(|name| name.as_str())
+            .chain(std::iter::once(Self::HELP))
+            .filter(|name| name.starts_with(prefix))
+            .collect::<Vec<_>>();
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Prints the usage and description of each command.
+    pub fn help(&self, output: &mut ConsoleOutput) {
+        output.print(format!("{} - Lists the commands", Self::HELP));
+        for command in self.iter() {
+            output.print(format!("{} - {}", command.usage(), command.description));
+        }
+    }
+
+    /// Parses and runs a line of input.
+    pub fn run(
+        &self,
+        world: &mut World,
+        line: &str,
+        output: &mut ConsoleOutput,
+    ) -> Result<(), ConsoleError> {
+        let words = tokenize(line)?;
+        let (name, values) = match words.split_first() {
+            Some(split) => split,
+            None => return Ok(()),
+        };
+        if name == Self::HELP && self.get(name).is_none() {
+            self.help(output);
+            return Ok(());
+        }
+        let command = self
+            .get(name)
+            .ok_or_else(|| ConsoleError::UnknownCommand(name.clone()))?;
+        let args = command.parse_args(values)?;
+        (command.handler)(world, &args, output);
+        Ok(())
+    }
+}
+
+/// Splits a line into words separated by whitespace. Double quotes group words into one.
+pub fn tokenize(line: &str) -> Result<Vec<String>, ConsoleError> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    for character in line.chars() {
+        match character {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            character if character.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            character => {
+                word.push(character);
+                in_word = true;
+            }
+        }
+    }
+    if quoted {
+        return Err(ConsoleError::UnterminatedQuote);
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tokenize, ConsoleCommand, ConsoleCommands, ConsoleError, ConsoleOutput};
+    use bevy_ecs::world::World;
+
+    #[test]
+    fn tokenize_quotes() {
+        assert_eq!(
+            tokenize(r#"say  "hello world" "" 2"#).unwrap(),
+            vec!["say", "hello world", "", "2"]
+        );
+        assert_eq!(
+            tokenize(r#"say "hello"#),
+            Err(ConsoleError::UnterminatedQuote)
+        );
+    }
+
+    #[test]
+    fn typed_arguments() {
+        struct Spawned(u32, Option<String>);
+
+        let mut commands = ConsoleCommands::default();
+        commands.add(
+            ConsoleCommand::new("spawn", "", |world, args, output| {
+                let count = *args.get::<u32>(0).unwrap();
+                let name = args.get::<String>(1).cloned();
+                output.print(format!("spawned {}", count));
+                world.insert_resource(Spawned(count, name));
+            })
+            .with_arg::<u32>("count")
+            .with_optional_arg::<String>("name"),
+        );
+        let mut world = World::default();
+        let mut output = ConsoleOutput::default();
+
+        commands.run(&mut world, "spawn 3", &mut output).unwrap();
+        assert_eq!(output.lines, vec!["spawned 3"]);
+        let spawned = world.get_resource::<Spawned>().unwrap();
+        assert_eq!((spawned.0, spawned.1.as_deref()), (3, None));
+
+        commands
+            .run(&mut world, "spawn 4 \"big one\"", &mut output)
+            .unwrap();
+        let spawned = world.get_resource::<Spawned>().unwrap();
+        assert_eq!((spawned.0, spawned.1.as_deref()), (4, Some("big one")));
+
+        assert!(matches!(
+            commands.run(&mut world, "spawn many", &mut output),
+            Err(ConsoleError::InvalidArgument { name: "count", .. })
+        ));
+        assert!(matches!(
+            commands.run(&mut world, "spawn", &mut output),
+            Err(ConsoleError::MissingArgument { name: "count", .. })
+        ));
+        assert!(matches!(
+            commands.run(&mut world, "spawn 1 a b", &mut output),
+            Err(ConsoleError::TooManyArguments { count: 2, .. })
+        ));
+        assert_eq!(
+            commands.run(&mut world, "despawn", &mut output),
+            Err(ConsoleError::UnknownCommand("despawn".to_string()))
+        );
+    }
+}
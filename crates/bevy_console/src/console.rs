@@ -0,0 +1,204 @@
+use crate::ConsoleCommands;
+use std::collections::VecDeque;
+
+/// The state of the developer console: whether it's open, the line being typed, the submitted
+/// lines waiting to run, the history of submitted lines and the output.
+#[derive(Debug)]
+pub struct Console {
+    pub open: bool,
+    /// The line being typed
+    pub input: String,
+    /// The maximum number of output lines kept
+    pub max_output: usize,
+    /// The maximum number of submitted lines kept in the history
+    pub max_history: usize,
+    output: VecDeque<String>,
+    history: VecDeque<String>,
+    history_position: Option<usize>,
+    pending: Vec<String>,
+}
+
+impl Default for Console {
+    fn default() -> Self {
+        Console {
+            open: false,
+            input: String::new(),
+            max_output: 100,
+            max_history: 50,
+            output: VecDeque::new(),
+            history: VecDeque::new(),
+            history_position: None,
+            pending: Vec::new(),
+        }
+    }
+}
+
+impl Console {
+    pub fn toggle(&mut self) {
+        self.open = !self.open;
+    }
+
+    /// Queues the line being typed to run, and adds it to the history.
+    pub fn submit(&mut self) {
+        let line = std::mem::take(&mut self.input);
+        self.history_position = None;
+        if line.trim().is_empty() {
+            return;
+        }
+        if self.history.back() != Some(&line) {
+            self.history.push_back(line.clone());
+            if self.history.len() > self.max_history {
+                self.history.pop_front();
+            }
+        }
+        self.pending.push(line);
+    }
+
+    /// Queues a line to run, as if it was typed, without adding it to the history.
+    pub fn run(&mut self, line: impl Into<String>) {
+        self.pending.push(line.into());
+    }
+
+    pub(crate) fn take_pending(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.pending)
+    }
+
+    /// Adds a line to the output.
+    pub fn print(&mut self, line: impl Into<String>) {
+        self.output.push_back(line.into());
+        while self.output.len() > self.max_output {
+            self.output.pop_front();
+        }
+    }
+
+    /// Clears the output.
+    pub fn clear(&mut self) {
+        self.output.clear();
+    }
+
+    /// The output lines, from the oldest
+    pub fn output(&self) -> impl Iterator<Item = &str> {
+        self.output.iter().map(|line| line.as_str())
+    }
+
+    /// The submitted lines, from the oldest
+    pub fn history(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(|line| line.as_str())
+    }
+
+    /// Replaces the input with the previous line of the history.
+    pub fn history_previous(&mut self) {
+        if self.history.is_empty() {
+            return;
+        }
+        let position = match self.history_position {
+            Some(position) => position.saturating_sub(1),
+            None => self.history.len() - 1,
+        };
+        self.history_position = Some(position);
+        self.input = self.history[position].clone();
+    }
+
+    /// Replaces the input with the next line of the history, or clears it past the last line.
+    pub fn history_next(&mut self) {
+        match self.history_position {
+            Some(position) if position + 1 < self.history.len() => {
+                self.history_position = Some(position + 1);
+                self.input = self.history[position + 1].clone();
+            }
+            Some(_) => {
+                self.history_position = None;
+                self.input.clear();
+            }
+            None => {}
+        }
+    }
+
+    /// Completes the command name being typed up to the longest prefix shared by the matching
+    /// commands. Prints the matching commands when there are several.
+    pub fn autocomplete(&mut self, commands: &ConsoleCommands) {
+        if self.input.contains(char::is_whitespace) {
+            return;
+        }
+        let matches = commands.complete(&self.input);
+        let (first, rest) = match matches.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        if rest.is_empty() {
+            self.input = format!("{} ", first);
+            return;
+        }
+        let common = rest.iter().fold(first.to_string(), |common, name| {
+            common
+                .chars()
+                .zip(name.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect()
+        });
+        if common.len() > self.input.len() {
+            self.input = common;
+        } else {
+            let line = matches.join("  ");
+            self.print(line);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Console;
+    use crate::{ConsoleCommand, ConsoleCommands};
+
+    #[test]
+    fn history() {
+        let mut console = Console::default();
+        for line in ["first", "second", "second", " "].iter() {
+            console.input = line.to_string();
+            console.submit();
+        }
+        assert_eq!(console.take_pending(), vec!["first", "second", "second"]);
+        assert_eq!(
+            console.history().collect::<Vec<_>>(),
+            vec!["first", "second"]
+        );
+
+        console.history_previous();
+        assert_eq!(console.input, "second");
+        console.history_previous();
+        console.history_previous();
+        assert_eq!(console.input, "first");
+        console.history_next();
+        assert_eq!(console.input, "second");
+        console.history_next();
+        assert_eq!(console.input, "");
+    }
+
+    #[test]
+    fn autocomplete() {
+        let mut commands = ConsoleCommands::default();
+        for name in ["spawn", "spawn_enemy", "speed"].iter() {
+            commands.add(ConsoleCommand::new(*name, "", |_, _, _| {}));
+        }
+        let mut console = Console::default();
+
+        console.input = "s".to_string();
+        console.autocomplete(&commands);
+        assert_eq!(console.input, "sp");
+        console.autocomplete(&commands);
+        assert_eq!(console.input, "sp");
+        assert_eq!(
+            console.output().collect::<Vec<_>>(),
+            vec!["spawn  spawn_enemy  speed"]
+        );
+
+        console.input = "spa".to_string();
+        console.autocomplete(&commands);
+        assert_eq!(console.input, "spawn");
+
+        console.input = "h".to_string();
+        console.autocomplete(&commands);
+        assert_eq!(console.input, "help ");
+    }
+}
@@ -0,0 +1,67 @@
+mod command;
+mod console;
+mod systems;
+
+pub use command::*;
+pub use console::*;
+pub use systems::*;
+
+pub mod prelude {
+    #[doc(hidden)]
+    pub use crate::{
+        AddConsoleCommand, Console, ConsoleArgs, ConsoleCommand, ConsoleOutput, ConsoleText,
+    };
+}
+
+use bevy_app::prelude::*;
+use bevy_ecs::{
+    schedule::ParallelSystemDescriptorCoercion,
+    system::{IntoExclusiveSystem, IntoSystem},
+    world::World,
+};
+use bevy_input::InputSystem;
+
+/// Adds an in-game developer console, opened with [`CONSOLE_TOGGLE_KEY`], that runs the commands
+/// registered with [`AddConsoleCommand::add_console_command`], along with the built-in `help` and
+/// `clear` commands. Spawn a [`ConsoleText`] to show it.
+#[derive(Default)]
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<Console>()
+            .init_resource::<ConsoleCommands>()
+            .add_console_command(ConsoleCommand::new(
+                "clear",
+                "Clears the console output",
+                |world: &mut World, _: &ConsoleArgs, _: &mut ConsoleOutput| {
+                    if let Some(mut console) = world.get_resource_mut::<Console>() {
+                        console.clear();
+                    }
+                },
+            ))
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                console_input_system.system().after(InputSystem),
+            )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                console_command_system.exclusive_system().at_end(),
+            )
+            .add_system(console_text_system.system());
+    }
+}
+
+pub trait AddConsoleCommand {
+    /// Registers a command that can be run from the [`Console`].
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self;
+}
+
+impl AddConsoleCommand for AppBuilder {
+    fn add_console_command(&mut self, command: ConsoleCommand) -> &mut Self {
+        self.world_mut()
+            .get_resource_or_insert_with(ConsoleCommands::default)
+            .add(command);
+        self
+    }
+}
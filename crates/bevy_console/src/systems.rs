@@ -0,0 +1,119 @@
+use crate::{Console, ConsoleCommands, ConsoleOutput};
+use bevy_ecs::{
+    event::EventReader,
+    query::With,
+    system::{Query, Res, ResMut},
+    world::{Mut, World},
+};
+use bevy_input::{keyboard::KeyCode, Input};
+use bevy_text::Text;
+use bevy_ui::{Display, Style};
+use bevy_window::ReceivedCharacter;
+
+/// Marks the [`Text`] showing the [`Console`]. Its first section gets the output and the line
+/// being typed, and its [`Style`] is hidden while the console is closed.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ConsoleText;
+
+/// The key opening and closing the console
+pub const CONSOLE_TOGGLE_KEY: KeyCode = KeyCode::Grave;
+
+/// Edits the [`Console`] input from the keyboard. While the console is open, the keys pressed are
+/// cleared from the [`Input<KeyCode>`] so that the game doesn't react to them.
+pub fn console_input_system(
+    mut console: ResMut<Console>,
+    commands: Res<ConsoleCommands>,
+    mut keyboard: ResMut<Input<KeyCode>>,
+    mut received_characters: EventReader<ReceivedCharacter>,
+) {
+    if keyboard.just_pressed(CONSOLE_TOGGLE_KEY) {
+        console.toggle();
+        keyboard.clear_just_pressed(CONSOLE_TOGGLE_KEY);
+        // the toggle key also sends a character, which shouldn't be typed
+        received_characters.iter().for_each(drop);
+        return;
+    }
+    if !console.open {
+        received_characters.iter().for_each(drop);
+        return;
+    }
+
+    for event in received_characters.iter() {
+        if !event.char.is_control() {
+            console.input.push(event.char);
+        }
+    }
+    if keyboard.just_pressed(KeyCode::Back) {
+        console.input.pop();
+    }
+    if keyboard.just_pressed(KeyCode::Return) || keyboard.just_pressed(KeyCode::NumpadEnter) {
+        console.submit();
+    }
+    if keyboard.just_pressed(KeyCode::Up) {
+        console.history_previous();
+    }
+    if keyboard.just_pressed(KeyCode::Down) {
+        console.history_next();
+    }
+    if keyboard.just_pressed(KeyCode::Tab) {
+        console.autocomplete(&commands);
+    }
+    keyboard.clear();
+}
+
+/// Runs the lines submitted to the [`Console`], printing their output and errors.
+pub fn console_command_system(world: &mut World) {
+    let lines = match world.get_resource_mut::<Console>() {
+        Some(mut console) => console.take_pending(),
+        None => return,
+    };
+    if lines.is_empty() {
+        return;
+    }
+    world.resource_scope(|world, commands: Mut<ConsoleCommands>| {
+        for line in lines {
+            if let Some(mut console) = world.get_resource_mut::<Console>() {
+                console.print(format!("> {}", line));
+            }
+            let mut output = ConsoleOutput::default();
+            let result = commands.run(world, &line, &mut output);
+            if let Some(mut console) = world.get_resource_mut::<Console>() {
+                for line in output.lines {
+                    console.print(line);
+                }
+                if let Err(err) = result {
+                    console.print(format!("error: {}", err));
+                }
+            }
+        }
+    });
+}
+
+/// Shows the [`Console`] in the [`ConsoleText`] entities.
+pub fn console_text_system(
+    console: Res<Console>,
+    mut query: Query<(&mut Text, &mut Style), With<ConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+    let display = if console.open {
+        Display::Flex
+    } else {
+        Display::None
+    };
+    for (mut text, mut style) in query.iter_mut() {
+        if style.display != display {
+            style.display = display;
+        }
+        if let Some(section) = text.sections.first_mut() {
+            section.value.clear();
+            for line in console.output() {
+                section.value.push_str(line);
+                section.value.push('\n');
+            }
+            section.value.push_str("> ");
+            section.value.push_str(&console.input);
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::Entity,
+    event::Events,
+    system::Command,
+    world::{FromWorld, World},
+};
+use bevy_tasks::{IoTaskPool, TaskPool};
+use bevy_utils::tracing::warn;
+use crossbeam_channel::{Receiver, Sender};
+use std::future::Future;
+
+type WorldUpdate = Box<dyn FnOnce(&mut World) + Send>;
+
+/// Runs futures, such as HTTP requests, file dialogs or database queries, on the [`IoTaskPool`]
+/// and hands their outputs back to the app at the start of the next frame, as events or
+/// components, so that systems never have to poll tasks.
+///
+/// ```
+/// # use bevy_core::AsyncIo;
+/// # use bevy_ecs::prelude::*;
+/// struct LeaderboardLoaded(Vec<String>);
+///
+/// fn request_leaderboard_system(async_io: Res<AsyncIo>) {
+///     async_io.spawn_event(async {
+///         // await the request here
+///         LeaderboardLoaded(vec!["Alice".to_string()])
+///     });
+/// }
+///
+/// fn show_leaderboard_system(mut events: EventReader<LeaderboardLoaded>) {
+///     for leaderboard in events.iter() {
+///         // ...
+///     }
+/// }
+/// # request_leaderboard_system.system();
+/// # show_leaderboard_system.system();
+/// ```
+pub struct AsyncIo {
+    pool: TaskPool,
+    sender: Sender<WorldUpdate>,
+    receiver: Receiver<WorldUpdate>,
+}
+
+impl FromWorld for AsyncIo {
+    fn from_world(world: &mut World) -> Self {
+        let pool = world
+            .get_resource::<IoTaskPool>()
+            .expect("AsyncIo requires the IoTaskPool resource")
+            .0
+            .clone();
+        AsyncIo::new(pool)
+    }
+}
+
+impl AsyncIo {
+    /// Runs the futures on `pool`.
+    pub fn new(pool: TaskPool) -> Self {
+        let (sender, receiver) = crossbeam_channel::unbounded();
+        AsyncIo {
+            pool,
+            sender,
+            receiver,
+        }
+    }
+
+    /// Runs `future`, and sends its output as an event. The [`Events<T>`] resource must exist,
+    /// e.g. added with `AppBuilder::add_event`.
+    pub fn spawn_event<T: Component>(&self, future: impl Future<Output = T> + Send + 'static) {
+        self.spawn_world_update(future, |event, world| {
+            match world.get_resource_mut::<Events<T>>() {
+                Some(mut events) => events.send(event),
+                None => warn!(
+                    "Dropped the output of an async task: the {} events weren't added",
+                    std::any::type_name::<T>()
+                ),
+            }
+        });
+    }
+
+    /// Runs `future`, and inserts its output onto `entity`, if it still exists.
+    pub fn spawn_insert<B: Bundle>(
+        &self,
+        entity: Entity,
+        future: impl Future<Output = B> + Send + 'static,
+    ) {
+        self.spawn_world_update(future, move |bundle, world| {
+            if let Some(mut entity) = world.get_entity_mut(entity) {
+                entity.insert_bundle(bundle);
+            }
+        });
+    }
+
+    /// Runs `future`, and applies the [`Command`] it outputs to the world.
+    pub fn spawn_command<C: Command>(&self, future: impl Future<Output = C> + Send + 'static) {
+        self.spawn_world_update(future, |command, world| command.write(world));
+    }
+
+    fn spawn_world_update<T: Send + 'static>(
+        &self,
+        future: impl Future<Output = T> + Send + 'static,
+        update: impl FnOnce(T, &mut World) + Send + 'static,
+    ) {
+        let sender = self.sender.clone();
+        self.pool
+            .spawn(async move {
+                let output = future.await;
+                // the receiver only disconnects when the app is dropped
+                let _ = sender.send(Box::new(move |world: &mut World| update(output, world)));
+            })
+            .detach();
+    }
+}
+
+/// Hands the outputs of the [`AsyncIo`] futures that completed to the app.
+pub fn async_io_system(world: &mut World) {
+    let receiver = match world.get_resource::<AsyncIo>() {
+        Some(async_io) => async_io.receiver.clone(),
+        None => return,
+    };
+    for update in receiver.try_iter() {
+        update(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{async_io_system, AsyncIo};
+    use bevy_ecs::{event::Events, world::World};
+    use bevy_tasks::TaskPool;
+    use std::time::{Duration, Instant};
+
+    #[derive(Debug, PartialEq)]
+    struct Loaded(u32);
+
+    #[test]
+    fn outputs_to_world() {
+        let mut world = World::default();
+        world.insert_resource(Events::<Loaded>::default());
+        world.insert_resource(AsyncIo::new(TaskPool::new()));
+        let entity = world.spawn().id();
+
+        let async_io = world.get_resource::<AsyncIo>().unwrap();
+        async_io.spawn_event(async { Loaded(1) });
+        async_io.spawn_insert(entity, async { (Loaded(2),) });
+
+        let start = Instant::now();
+        while world.get::<Loaded>(entity).is_none()
+            || world
+                .get_resource::<Events<Loaded>>()
+                .unwrap()
+                .iter_current_update_events()
+                .next()
+                .is_none()
+        {
+            assert!(
+                start.elapsed() < Duration::from_secs(5),
+                "tasks never completed"
+            );
+            std::thread::yield_now();
+            async_io_system(&mut world);
+        }
+        assert_eq!(world.get::<Loaded>(entity), Some(&Loaded(2)));
+        let events = world.get_resource::<Events<Loaded>>().unwrap();
+        assert_eq!(
+            events.iter_current_update_events().collect::<Vec<_>>(),
+            vec![&Loaded(1)]
+        );
+    }
+}
@@ -80,7 +80,7 @@ impl EntityLabels {
 
 pub(crate) fn entity_labels_system(
     mut entity_labels: ResMut<EntityLabels>,
-    removed_labels: RemovedComponents<Labels>,
+    mut removed_labels: RemovedComponents<Labels>,
     query: Query<(Entity, &Labels), Changed<Labels>>,
 ) {
     let entity_labels = entity_labels.deref_mut();
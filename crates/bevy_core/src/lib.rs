@@ -1,20 +1,27 @@
+mod async_io;
 mod bytes;
 mod float_ord;
 mod label;
 mod name;
+mod rng;
 mod task_pool_options;
 mod time;
 
+pub use async_io::*;
 pub use bytes::*;
 pub use float_ord::*;
 pub use label::*;
 pub use name::*;
+pub use rng::*;
 pub use task_pool_options::DefaultTaskPoolOptions;
 pub use time::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{DefaultTaskPoolOptions, EntityLabels, Labels, Name, Time, Timer};
+    pub use crate::{
+        AsyncIo, DefaultTaskPoolOptions, EntityLabels, FrameCount, Labels, Name, Rng,
+        SimulationTick, SystemRng, Time, Timer,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -47,6 +54,10 @@ impl Plugin for CorePlugin {
             .create_default_pools(app.world_mut());
 
         app.init_resource::<Time>()
+            .init_resource::<FrameCount>()
+            .init_resource::<SimulationTick>()
+            .init_resource::<Rng>()
+            .init_resource::<AsyncIo>()
             .init_resource::<EntityLabels>()
             .init_resource::<FixedTimesteps>()
             .register_type::<HashSet<String>>()
@@ -62,8 +73,14 @@ impl Plugin for CorePlugin {
                 CoreStage::First,
                 time_system.exclusive_system().label(CoreSystem::Time),
             )
+            // the outputs of async tasks are handed over once per frame, before the app updates
+            .add_system_to_stage(
+                CoreStage::First,
+                async_io_system.exclusive_system().at_end(),
+            )
             .add_startup_system_to_stage(StartupStage::PostStartup, entity_labels_system.system())
-            .add_system_to_stage(CoreStage::PostUpdate, entity_labels_system.system());
+            .add_system_to_stage(CoreStage::PostUpdate, entity_labels_system.system())
+            .add_system_to_stage(CoreStage::Last, frame_count_system.system());
 
         register_rust_types(app);
         register_math_types(app);
@@ -0,0 +1,301 @@
+use bevy_ecs::{
+    archetype::Archetype,
+    entity::Entity,
+    system::{ResState, SystemMeta, SystemParam, SystemParamFetch, SystemParamState},
+    world::World,
+};
+use std::ops::{Deref, DerefMut, Range};
+
+/// A deterministic random number generator, and the resource seeding the randomness of the app.
+///
+/// Gameplay randomness drawn from the [`Rng`] resource, from [`SystemRng`] or from generators
+/// forked with [`Rng::for_entity`] is reproducible from the seed, for replays and tests, unlike
+/// a thread local generator. The default seed is fixed: reseed with [`Rng::with_seed`], e.g. from
+/// the current time, for randomness that differs between runs.
+///
+/// The sequence of numbers is stable across platforms and versions of Bevy.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    seed: u64,
+    state: u64,
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Rng::with_seed(0x2545_f491_4f6c_dd1d)
+    }
+}
+
+impl Rng {
+    pub fn with_seed(seed: u64) -> Self {
+        Rng { seed, state: seed }
+    }
+
+    /// The seed this generator started from
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// A generator independent from this one, seeded from the seed of this one and `stream`,
+    /// regardless of how many numbers this one generated.
+    pub fn fork(&self, stream: u64) -> Rng {
+        Rng::with_seed(mix(self.seed ^ mix(stream)))
+    }
+
+    /// A generator for `entity`, e.g. to store in a component. It is the same for the same seed
+    /// and entity, so entities must be spawned in the same order for the randomness to be
+    /// reproducible.
+    pub fn for_entity(&self, entity: Entity) -> Rng {
+        self.fork(entity.to_bits())
+    }
+
+    /// A random `u64`, using SplitMix64
+    pub fn u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        mix(self.state)
+    }
+
+    pub fn u32(&mut self) -> u32 {
+        (self.u64() >> 32) as u32
+    }
+
+    pub fn bool(&mut self) -> bool {
+        self.u64() >> 63 == 1
+    }
+
+    /// A random `f32` in `0.0..1.0`
+    pub fn f32(&mut self) -> f32 {
+        (self.u64() >> 40) as f32 * (1.0 / (1u64 << 24) as f32)
+    }
+
+    /// A random `f64` in `0.0..1.0`
+    pub fn f64(&mut self) -> f64 {
+        (self.u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// A random `f32` in `range`
+    pub fn f32_range(&mut self, range: Range<f32>) -> f32 {
+        range.start + (range.end - range.start) * self.f32()
+    }
+
+    /// A random `u64` in `range`. Panics if the range is empty.
+    pub fn u64_range(&mut self, range: Range<u64>) -> u64 {
+        assert!(range.start < range.end, "cannot sample an empty range");
+        let span = range.end - range.start;
+        // reject the values that would make the lowest results more likely
+        let zone = u64::MAX - u64::MAX % span;
+        loop {
+            let value = self.u64();
+            if value < zone {
+                return range.start + value % span;
+            }
+        }
+    }
+
+    /// A random `usize` in `range`. Panics if the range is empty.
+    pub fn usize_range(&mut self, range: Range<usize>) -> usize {
+        self.u64_range(range.start as u64..range.end as u64) as usize
+    }
+
+    /// Returns true with the given `probability`, between 0 and 1.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        self.f64() < probability
+    }
+
+    /// A random element of `items`, or `None` if it's empty
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            None
+        } else {
+            items.get(self.usize_range(0..items.len()))
+        }
+    }
+
+    /// Shuffles `items` in place.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.usize_range(0..i + 1));
+        }
+    }
+}
+
+fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+// FNV-1a, stable unlike the std hashers
+fn stable_hash(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// A [`SystemParam`] giving a system its own [`Rng`], forked from the [`Rng`] resource for each
+/// run of the system.
+///
+/// The generator depends on the seed of the resource, the label configured for the system and
+/// the number of times the system ran, not on what other systems drew or on the order systems ran
+/// in, so systems running in parallel stay deterministic. Systems with the same label, including
+/// all the systems without one, share their sequence of generators: label each system drawing
+/// randomness to make them independent. The label is part of the reproducible state, so keep it
+/// when renaming the system.
+///
+/// ```
+/// # use bevy_core::SystemRng;
+/// # use bevy_ecs::prelude::*;
+/// # struct Enemy;
+/// fn wander_system(mut rng: SystemRng, mut query: Query<&mut Enemy>) {
+///     for enemy in query.iter_mut() {
+///         let turn = rng.f32_range(-1.0..1.0);
+///         // ...
+///     }
+/// }
+/// # wander_system.system()
+/// #     .config(|config| config.0 = Some("wander"));
+/// ```
+pub struct SystemRng<'a> {
+    rng: &'a mut Rng,
+}
+
+impl<'a> Deref for SystemRng<'a> {
+    type Target = Rng;
+
+    fn deref(&self) -> &Self::Target {
+        self.rng
+    }
+}
+
+impl<'a> DerefMut for SystemRng<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.rng
+    }
+}
+
+/// The [`SystemParamState`] of [`SystemRng`].
+pub struct SystemRngState {
+    seed: ResState<Rng>,
+    stream: u64,
+    runs: u64,
+    rng: Rng,
+}
+
+impl<'a> SystemParam for SystemRng<'a> {
+    type Fetch = SystemRngState;
+}
+
+// SAFE: only reads the Rng resource, whose access is registered by ResState
+unsafe impl SystemParamState for SystemRngState {
+    type Config = Option<&'static str>;
+
+    fn init(world: &mut World, system_meta: &mut SystemMeta, config: Self::Config) -> Self {
+        SystemRngState {
+            seed: ResState::init(world, system_meta, ()),
+            stream: config.map_or(0, stable_hash),
+            runs: 0,
+            rng: Rng::default(),
+        }
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype, system_meta: &mut SystemMeta) {
+        self.seed.new_archetype(archetype, system_meta);
+    }
+
+    fn default_config() -> Option<&'static str> {
+        None
+    }
+}
+
+impl<'a> SystemParamFetch<'a> for SystemRngState {
+    type Item = SystemRng<'a>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        system_meta: &SystemMeta,
+        world: &'a World,
+        change_tick: u32,
+    ) -> Self::Item {
+        let seed = ResState::get_param(&mut state.seed, system_meta, world, change_tick).seed();
+        state.rng = Rng::with_seed(seed).fork(state.stream).fork(state.runs);
+        state.runs += 1;
+        SystemRng {
+            rng: &mut state.rng,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{stable_hash, Rng, SystemRng};
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::{IntoSystem, ResMut},
+        world::World,
+    };
+
+    #[test]
+    fn deterministic() {
+        let mut a = Rng::with_seed(42);
+        let mut b = Rng::with_seed(42);
+        let values = (0..8).map(|_| a.u64()).collect::<Vec<_>>();
+        assert_eq!(values, (0..8).map(|_| b.u64()).collect::<Vec<_>>());
+        assert_ne!(values, (0..8).map(|_| a.u64()).collect::<Vec<_>>());
+
+        // forks don't depend on how many numbers were generated
+        assert_eq!(a.fork(1), b.fork(1));
+        assert_ne!(a.fork(1), a.fork(2));
+
+        for _ in 0..100 {
+            assert!((3..7).contains(&a.u64_range(3..7)));
+            let value = a.f32();
+            assert!((0.0..1.0).contains(&value));
+        }
+        let mut items = [1, 2, 3, 4, 5];
+        a.shuffle(&mut items);
+        items.sort_unstable();
+        assert_eq!(items, [1, 2, 3, 4, 5]);
+        assert_eq!(a.choose::<u32>(&[]), None);
+    }
+
+    #[test]
+    fn system_rng() {
+        #[derive(Default)]
+        struct Draws(Vec<u64>);
+
+        fn draw_system(mut rng: SystemRng, mut draws: ResMut<Draws>) {
+            draws.0.push(rng.u64());
+        }
+
+        let run_labelled = |seed, label| {
+            let mut world = World::default();
+            world.insert_resource(Rng::with_seed(seed));
+            world.insert_resource(Draws::default());
+            let mut stage = SystemStage::parallel();
+            stage.add_system(draw_system.system().config(|config| config.0 = label));
+            for _ in 0..3 {
+                stage.run(&mut world);
+            }
+            world.remove_resource::<Draws>().unwrap().0
+        };
+        let run = |seed| run_labelled(seed, None);
+        let draws = run(7);
+        assert_eq!(draws, run(7));
+        assert_ne!(draws, run(8));
+        // each run of the system gets a different generator
+        assert_ne!(draws[0], draws[1]);
+        assert_ne!(draws[1], draws[2]);
+
+        // labels give systems independent sequences, which only depend on the label
+        let wander = run_labelled(7, Some("wander"));
+        assert_ne!(wander, draws);
+        assert_eq!(wander, run_labelled(7, Some("wander")));
+        assert_ne!(wander, run_labelled(7, Some("attack")));
+        assert_eq!(
+            wander[0],
+            Rng::with_seed(7).fork(stable_hash("wander")).fork(0).u64()
+        );
+    }
+}
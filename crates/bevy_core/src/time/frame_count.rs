@@ -0,0 +1,63 @@
+use bevy_ecs::system::ResMut;
+
+/// The number of frames the app has completed, incremented at the end of each update.
+///
+/// Use it for staggered updates and frame-stamped data instead of keeping a counter per system.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FrameCount(pub u64);
+
+pub(crate) fn frame_count_system(mut frame_count: ResMut<FrameCount>) {
+    frame_count.0 += 1;
+}
+
+/// A counter of simulation steps, distinct from [`FrameCount`]: a simulation running on a
+/// [`FixedTimestep`](crate::FixedTimestep) may step several times in a frame, or not at all.
+///
+/// The tick isn't advanced on its own. Add [`simulation_tick_system`] to the stage that runs the
+/// simulation, or call [`SimulationTick::advance`], so that networking and replay code can stamp
+/// and compare simulation states.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SimulationTick(u64);
+
+impl SimulationTick {
+    /// The number of simulation steps made
+    pub fn get(&self) -> u64 {
+        self.0
+    }
+
+    /// Moves to the next simulation step, returning its tick.
+    pub fn advance(&mut self) -> u64 {
+        self.0 += 1;
+        self.0
+    }
+}
+
+/// Advances the [`SimulationTick`] each time it runs.
+pub fn simulation_tick_system(mut simulation_tick: ResMut<SimulationTick>) {
+    simulation_tick.advance();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{frame_count_system, simulation_tick_system, FrameCount, SimulationTick};
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+
+    #[test]
+    fn counters() {
+        let mut world = World::default();
+        world.insert_resource(FrameCount::default());
+        world.insert_resource(SimulationTick::default());
+        let mut stage = SystemStage::parallel();
+        stage.add_system(frame_count_system.system());
+        stage.add_system(simulation_tick_system.system());
+        for _ in 0..3 {
+            stage.run(&mut world);
+        }
+        assert_eq!(*world.get_resource::<FrameCount>().unwrap(), FrameCount(3));
+        assert_eq!(world.get_resource::<SimulationTick>().unwrap().get(), 3);
+    }
+}
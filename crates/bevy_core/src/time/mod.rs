@@ -1,10 +1,14 @@
 mod fixed_timestep;
+mod frame_count;
+mod stagger;
 mod stopwatch;
 #[allow(clippy::module_inception)]
 mod time;
 mod timer;
 
 pub use fixed_timestep::*;
+pub use frame_count::*;
+pub use stagger::*;
 pub use stopwatch::*;
 pub use time::*;
 pub use timer::*;
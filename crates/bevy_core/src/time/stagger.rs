@@ -0,0 +1,164 @@
+use crate::FrameCount;
+use bevy_ecs::{
+    schedule::ShouldRun,
+    system::{IntoSystem, Res, System},
+};
+use std::iter::{Peekable, Skip};
+
+/// A run criteria that runs the system once every `interval` frames, counted with
+/// [`FrameCount`]. Give expensive systems sharing an interval different offsets to spread them
+/// over the frames.
+///
+/// ```
+/// # use bevy_core::every_n_frames;
+/// # use bevy_ecs::prelude::*;
+/// # fn pathfinding_system() {}
+/// # fn perception_system() {}
+/// # let mut stage = SystemStage::parallel();
+/// stage
+///     .add_system(pathfinding_system.system().with_run_criteria(every_n_frames(4, 0)))
+///     .add_system(perception_system.system().with_run_criteria(every_n_frames(4, 2)));
+/// ```
+pub fn every_n_frames(interval: u64, offset: u64) -> impl System<In = (), Out = ShouldRun> {
+    let interval = interval.max(1);
+    (move |frame_count: Res<FrameCount>| {
+        if frame_count.0 % interval == offset % interval {
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
+        }
+    })
+    .system()
+}
+
+/// Spreads the processing of many items, such as the entities of a query, over several frames.
+/// Store it in a [`Local`](bevy_ecs::system::Local), and iterate over the items through
+/// [`StaggeredCursor::iter`] every frame: each call yields at most a batch of items, resuming
+/// after the last item yielded by the previous call.
+///
+/// Items are told apart by their position, so the iteration order must be stable between frames,
+/// which is the case for queries as long as entities don't change archetypes. Items added or
+/// removed in between can be skipped or processed twice in the current pass.
+///
+/// ```
+/// # use bevy_core::StaggeredCursor;
+/// # use bevy_ecs::prelude::*;
+/// # struct Agent;
+/// fn ai_system(mut cursor: Local<StaggeredCursor>, agents: Query<&Agent>) {
+///     for agent in cursor.iter(agents.iter(), 16) {
+///         // plan the next move of at most 16 agents per frame
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct StaggeredCursor {
+    position: usize,
+}
+
+impl StaggeredCursor {
+    /// The number of items processed in the current pass
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// Starts the next call over from the first item.
+    pub fn reset(&mut self) {
+        self.position = 0;
+    }
+
+    /// Iterates over at most `batch_size` of `items`, starting after the last one yielded by the
+    /// previous calls. Once the items are exhausted, the next call starts over from the first one.
+    pub fn iter<I: IntoIterator>(&mut self, items: I, batch_size: usize) -> StaggeredIter<'_, I> {
+        let position = self.position;
+        StaggeredIter {
+            items: items.into_iter().skip(position).peekable(),
+            cursor: self,
+            remaining: batch_size,
+        }
+    }
+}
+
+/// An iterator over a batch of items, returned by [`StaggeredCursor::iter`].
+pub struct StaggeredIter<'a, I: IntoIterator> {
+    items: Peekable<Skip<I::IntoIter>>,
+    cursor: &'a mut StaggeredCursor,
+    remaining: usize,
+}
+
+impl<'a, I: IntoIterator> Iterator for StaggeredIter<'a, I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        match self.items.next() {
+            Some(item) => {
+                self.remaining -= 1;
+                self.cursor.position += 1;
+                // start over right away if this was the last item, not after an empty batch
+                if self.remaining == 0 && self.items.peek().is_none() {
+                    self.cursor.position = 0;
+                }
+                Some(item)
+            }
+            None => {
+                self.remaining = 0;
+                self.cursor.position = 0;
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{every_n_frames, StaggeredCursor};
+    use crate::FrameCount;
+    use bevy_ecs::{
+        schedule::{ParallelSystemDescriptorCoercion, Stage, SystemStage},
+        system::{IntoSystem, Res, ResMut},
+        world::World,
+    };
+
+    #[test]
+    fn staggered_cursor() {
+        let items = [0, 1, 2, 3, 4, 5];
+        let mut cursor = StaggeredCursor::default();
+        let batch = |cursor: &mut StaggeredCursor, batch_size| {
+            cursor.iter(&items, batch_size).copied().collect::<Vec<_>>()
+        };
+        assert_eq!(batch(&mut cursor, 2), vec![0, 1]);
+        assert_eq!(batch(&mut cursor, 2), vec![2, 3]);
+        assert_eq!(batch(&mut cursor, 2), vec![4, 5]);
+        assert_eq!(batch(&mut cursor, 2), vec![0, 1]);
+        assert_eq!(batch(&mut cursor, 3), vec![2, 3, 4]);
+        assert_eq!(batch(&mut cursor, 3), vec![5]);
+        assert_eq!(batch(&mut cursor, 3), vec![0, 1, 2]);
+
+        // only the items actually yielded are skipped
+        assert_eq!(cursor.iter(&items, 2).next(), Some(&3));
+        assert_eq!(cursor.position(), 4);
+    }
+
+    #[test]
+    fn frame_interval() {
+        #[derive(Default)]
+        struct Runs(Vec<u64>);
+
+        let mut world = World::default();
+        world.insert_resource(FrameCount::default());
+        world.insert_resource(Runs::default());
+        let mut stage = SystemStage::parallel();
+        stage.add_system(
+            (|frame_count: Res<FrameCount>, mut runs: ResMut<Runs>| runs.0.push(frame_count.0))
+                .system()
+                .with_run_criteria(every_n_frames(3, 1)),
+        );
+        for frame in 0..7 {
+            world.insert_resource(FrameCount(frame));
+            stage.run(&mut world);
+        }
+        assert_eq!(world.get_resource::<Runs>().unwrap().0, vec![1, 4]);
+    }
+}
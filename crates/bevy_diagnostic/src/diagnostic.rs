@@ -1,6 +1,6 @@
 use bevy_log::warn;
 use bevy_utils::{Duration, Instant, StableHashMap, Uuid};
-use std::{borrow::Cow, collections::VecDeque};
+use std::{borrow::Cow, collections::VecDeque, io};
 
 use crate::MAX_DIAGNOSTIC_NAME_WIDTH;
 
@@ -119,6 +119,43 @@ impl Diagnostic {
     pub fn get_max_history_length(&self) -> usize {
         self.max_history_length
     }
+
+    /// Sets the number of measurements kept in the history, dropping the oldest measurements if
+    /// the history is currently longer.
+    pub fn set_max_history_length(&mut self, max_history_length: usize) {
+        while self.history.len() > max_history_length {
+            if let Some(removed_diagnostic) = self.history.pop_back() {
+                self.sum -= removed_diagnostic.value;
+            }
+        }
+        self.max_history_length = max_history_length;
+    }
+
+    /// Iterates over the measurements in the history, from oldest to newest.
+    pub fn measurements(&self) -> impl Iterator<Item = &DiagnosticMeasurement> {
+        self.history.iter().rev()
+    }
+
+    /// Returns the value below which `percentile` percent of the measurements in the history fall,
+    /// using the nearest-rank method. `percentile` must be between 0 and 100.
+    pub fn percentile(&self, percentile: f64) -> Option<f64> {
+        assert!(
+            (0.0..=100.0).contains(&percentile),
+            "percentile must be between 0 and 100"
+        );
+        if self.history.is_empty() {
+            return None;
+        }
+
+        let mut values = self
+            .history
+            .iter()
+            .map(|measurement| measurement.value)
+            .collect::<Vec<_>>();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        let rank = (percentile / 100.0 * values.len() as f64).ceil() as usize;
+        Some(values[rank.max(1) - 1])
+    }
 }
 
 /// A collection of [Diagnostic]s
@@ -157,4 +194,165 @@ impl Diagnostics {
     pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
         self.diagnostics.values()
     }
+
+    /// The time of the oldest measurement of all diagnostics, used as the origin of the times
+    /// written by the exporters.
+    fn oldest_measurement_time(&self) -> Option<Instant> {
+        self.iter()
+            .filter_map(|diagnostic| diagnostic.history.back())
+            .map(|measurement| measurement.time)
+            .min()
+    }
+
+    /// Writes the history of all diagnostics as CSV, with one row per measurement. Times are in
+    /// seconds since the oldest measurement.
+    pub fn write_csv(&self, mut writer: impl io::Write) -> io::Result<()> {
+        writeln!(writer, "id,name,time,value")?;
+        let origin = match self.oldest_measurement_time() {
+            Some(origin) => origin,
+            None => return Ok(()),
+        };
+        for diagnostic in self.iter() {
+            for measurement in diagnostic.measurements() {
+                writeln!(
+                    writer,
+                    "{},\"{}\",{},{}",
+                    diagnostic.id.0,
+                    diagnostic.name.replace('"', "\"\""),
+                    measurement.time.duration_since(origin).as_secs_f64(),
+                    measurement.value
+                )?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the history of all diagnostics as JSON, along with their average and p50, p95 and
+    /// p99 percentiles. Times are in seconds since the oldest measurement.
+    pub fn write_json(&self, mut writer: impl io::Write) -> io::Result<()> {
+        fn json_number(value: Option<f64>) -> String {
+            match value {
+                Some(value) if value.is_finite() => value.to_string(),
+                _ => "null".to_string(),
+            }
+        }
+
+        fn json_string(value: &str) -> String {
+            let mut escaped = String::with_capacity(value.len() + 2);
+            escaped.push('"');
+            for c in value.chars() {
+                match c {
+                    '"' => escaped.push_str("\\\""),
+                    '\\' => escaped.push_str("\\\\"),
+                    c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+                    c => escaped.push(c),
+                }
+            }
+            escaped.push('"');
+            escaped
+        }
+
+        let origin = self.oldest_measurement_time();
+        write!(writer, "{{\"diagnostics\":[")?;
+        for (index, diagnostic) in self.iter().enumerate() {
+            if index > 0 {
+                write!(writer, ",")?;
+            }
+            write!(
+                writer,
+                "{{\"id\":\"{}\",\"name\":{},\"suffix\":{},\"average\":{},\"p50\":{},\"p95\":{},\"p99\":{},\"measurements\":[",
+                diagnostic.id.0,
+                json_string(&diagnostic.name),
+                json_string(&diagnostic.suffix),
+                json_number(diagnostic.average()),
+                json_number(diagnostic.percentile(50.0)),
+                json_number(diagnostic.percentile(95.0)),
+                json_number(diagnostic.percentile(99.0)),
+            )?;
+            for (index, measurement) in diagnostic.measurements().enumerate() {
+                if index > 0 {
+                    write!(writer, ",")?;
+                }
+                let time =
+                    origin.map(|origin| measurement.time.duration_since(origin).as_secs_f64());
+                write!(
+                    writer,
+                    "{{\"time\":{},\"value\":{}}}",
+                    json_number(time),
+                    json_number(Some(measurement.value))
+                )?;
+            }
+            write!(writer, "]}}")?;
+        }
+        writeln!(writer, "]}}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diagnostic, DiagnosticId, Diagnostics};
+
+    const ID: DiagnosticId = DiagnosticId::from_u128(1);
+
+    fn diagnostic(values: &[f64]) -> Diagnostic {
+        let mut diagnostic = Diagnostic::new(ID, "test", 10);
+        for value in values.iter() {
+            diagnostic.add_measurement(*value);
+        }
+        diagnostic
+    }
+
+    #[test]
+    fn percentiles() {
+        let diagnostic = diagnostic(&[5.0, 1.0, 4.0, 2.0, 3.0, 10.0, 9.0, 8.0, 7.0, 6.0]);
+        assert_eq!(diagnostic.percentile(0.0), Some(1.0));
+        assert_eq!(diagnostic.percentile(50.0), Some(5.0));
+        assert_eq!(diagnostic.percentile(95.0), Some(10.0));
+        assert_eq!(diagnostic.percentile(100.0), Some(10.0));
+        assert_eq!(Diagnostic::new(ID, "empty", 10).percentile(50.0), None);
+    }
+
+    #[test]
+    fn history_length() {
+        let mut diagnostic = diagnostic(&[1.0, 2.0, 3.0, 4.0]);
+        diagnostic.set_max_history_length(2);
+        assert_eq!(diagnostic.history_len(), 2);
+        assert_eq!(diagnostic.sum(), 7.0);
+        assert_eq!(
+            diagnostic
+                .measurements()
+                .map(|measurement| measurement.value)
+                .collect::<Vec<_>>(),
+            vec![3.0, 4.0]
+        );
+
+        diagnostic.add_measurement(5.0);
+        diagnostic.add_measurement(6.0);
+        assert_eq!(diagnostic.history_len(), 2);
+        assert_eq!(diagnostic.sum(), 11.0);
+    }
+
+    #[test]
+    fn export() {
+        let mut diagnostics = Diagnostics::default();
+        diagnostics.add(diagnostic(&[1.0, 2.0]));
+
+        let mut csv = Vec::new();
+        diagnostics.write_csv(&mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        let lines = csv.lines().collect::<Vec<_>>();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], "id,name,time,value");
+        assert!(lines[1].starts_with(&format!("{},\"test\",", ID.0)));
+        assert!(lines[2].ends_with(",2"));
+
+        let mut json = Vec::new();
+        diagnostics.write_json(&mut json).unwrap();
+        let json = String::from_utf8(json).unwrap();
+        assert!(json.starts_with(&format!(
+            "{{\"diagnostics\":[{{\"id\":\"{}\",\"name\":\"test\",\"suffix\":\"\",\"average\":1.5,\"p50\":1,\"p95\":2,\"p99\":2,",
+            ID.0
+        )));
+        assert!(json.trim_end().ends_with("\"value\":2}]}]}"));
+    }
 }
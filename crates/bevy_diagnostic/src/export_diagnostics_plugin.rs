@@ -0,0 +1,102 @@
+use super::Diagnostics;
+use bevy_app::{prelude::*, AppExit, EventReader};
+use bevy_ecs::system::{IntoSystem, Res};
+use bevy_log::{error, info};
+use std::{fs::File, io::BufWriter, path::PathBuf};
+
+/// The file formats diagnostics can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagnosticsExportFormat {
+    /// One row per measurement. See [Diagnostics::write_csv]
+    Csv,
+    /// Measurements grouped by diagnostic, with percentiles. See [Diagnostics::write_json]
+    Json,
+}
+
+/// Send this event to export the diagnostics history with the [ExportDiagnosticsPlugin]
+#[derive(Debug, Clone, Default)]
+pub struct ExportDiagnostics;
+
+/// An App Plugin that writes the history of all diagnostics to a file when the app exits, and
+/// whenever an [ExportDiagnostics] event is sent.
+pub struct ExportDiagnosticsPlugin {
+    pub path: PathBuf,
+    pub format: DiagnosticsExportFormat,
+    pub export_on_exit: bool,
+}
+
+/// State used by the [ExportDiagnosticsPlugin]
+struct ExportDiagnosticsState {
+    path: PathBuf,
+    format: DiagnosticsExportFormat,
+    export_on_exit: bool,
+}
+
+impl Default for ExportDiagnosticsPlugin {
+    fn default() -> Self {
+        ExportDiagnosticsPlugin {
+            path: PathBuf::from("diagnostics.csv"),
+            format: DiagnosticsExportFormat::Csv,
+            export_on_exit: true,
+        }
+    }
+}
+
+impl ExportDiagnosticsPlugin {
+    pub fn csv(path: impl Into<PathBuf>) -> Self {
+        ExportDiagnosticsPlugin {
+            path: path.into(),
+            format: DiagnosticsExportFormat::Csv,
+            ..Default::default()
+        }
+    }
+
+    pub fn json(path: impl Into<PathBuf>) -> Self {
+        ExportDiagnosticsPlugin {
+            path: path.into(),
+            format: DiagnosticsExportFormat::Json,
+            ..Default::default()
+        }
+    }
+
+    fn export_diagnostics_system(
+        state: Res<ExportDiagnosticsState>,
+        diagnostics: Res<Diagnostics>,
+        mut export_events: EventReader<ExportDiagnostics>,
+        mut app_exit_events: EventReader<AppExit>,
+    ) {
+        let requested = export_events.iter().count() > 0;
+        let exiting = app_exit_events.iter().count() > 0;
+        if !requested && !(exiting && state.export_on_exit) {
+            return;
+        }
+
+        let result = File::create(&state.path).and_then(|file| {
+            let writer = BufWriter::new(file);
+            match state.format {
+                DiagnosticsExportFormat::Csv => diagnostics.write_csv(writer),
+                DiagnosticsExportFormat::Json => diagnostics.write_json(writer),
+            }
+        });
+        match result {
+            Ok(()) => info!("exported diagnostics to {}", state.path.display()),
+            Err(err) => error!(
+                "failed to export diagnostics to {}: {}",
+                state.path.display(),
+                err
+            ),
+        }
+    }
+}
+
+impl Plugin for ExportDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.insert_resource(ExportDiagnosticsState {
+            path: self.path.clone(),
+            format: self.format,
+            export_on_exit: self.export_on_exit,
+        })
+        .add_event::<ExportDiagnostics>()
+        .add_system_to_stage(CoreStage::Last, Self::export_diagnostics_system.system());
+    }
+}
@@ -1,17 +1,20 @@
 use crate::{Diagnostic, DiagnosticId, Diagnostics};
 use bevy_app::prelude::*;
-use bevy_core::Time;
+use bevy_core::{FrameCount, Time};
 use bevy_ecs::system::{IntoSystem, Res, ResMut};
 
 /// Adds "frame time" diagnostic to an App, specifically "frame time", "fps" and "frame count"
 #[derive(Default)]
 pub struct FrameTimeDiagnosticsPlugin;
 
+/// The frame count of the [`FrameTimeDiagnosticsPlugin`], kept for compatibility.
+#[deprecated(note = "use the `FrameCount` resource of `bevy_core` instead")]
 pub struct FrameTimeDiagnosticsState {
     frame_count: f64,
 }
 
 impl Plugin for FrameTimeDiagnosticsPlugin {
+    #[allow(deprecated)]
     fn build(&self, app: &mut bevy_app::AppBuilder) {
         app.add_startup_system(Self::setup_system.system())
             .insert_resource(FrameTimeDiagnosticsState { frame_count: 0.0 })
@@ -32,12 +35,15 @@ impl FrameTimeDiagnosticsPlugin {
         diagnostics.add(Diagnostic::new(Self::FRAME_COUNT, "frame_count", 1));
     }
 
+    #[allow(deprecated)]
     pub fn diagnostic_system(
         mut diagnostics: ResMut<Diagnostics>,
         time: Res<Time>,
         mut state: ResMut<FrameTimeDiagnosticsState>,
+        frame_count: Res<FrameCount>,
     ) {
-        state.frame_count += 1.0;
+        // the frame count is incremented at the end of the frame
+        state.frame_count = (frame_count.0 + 1) as f64;
         diagnostics.add_measurement(Self::FRAME_COUNT, state.frame_count);
 
         if time.delta_seconds_f64() == 0.0 {
@@ -1,9 +1,13 @@
 mod diagnostic;
 mod entity_count_diagnostics_plugin;
+mod export_diagnostics_plugin;
 mod frame_time_diagnostics_plugin;
 mod log_diagnostics_plugin;
 pub use diagnostic::*;
 pub use entity_count_diagnostics_plugin::EntityCountDiagnosticsPlugin;
+pub use export_diagnostics_plugin::{
+    DiagnosticsExportFormat, ExportDiagnostics, ExportDiagnosticsPlugin,
+};
 pub use frame_time_diagnostics_plugin::FrameTimeDiagnosticsPlugin;
 pub use log_diagnostics_plugin::LogDiagnosticsPlugin;
 
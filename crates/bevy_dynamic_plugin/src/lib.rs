@@ -1,3 +1,11 @@
+// dynamic libraries can't be loaded on the web
+#[cfg(not(target_arch = "wasm32"))]
 mod loader;
+#[cfg(not(target_arch = "wasm32"))]
+mod reload;
 
+pub use bevy_app::DYNAMIC_PLUGIN_ABI_VERSION;
+#[cfg(not(target_arch = "wasm32"))]
 pub use loader::*;
+#[cfg(not(target_arch = "wasm32"))]
+pub use reload::*;
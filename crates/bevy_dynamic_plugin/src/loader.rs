@@ -1,6 +1,42 @@
 use libloading::{Library, Symbol};
+use thiserror::Error;
 
-use bevy_app::{AppBuilder, CreatePlugin, Plugin};
+use bevy_app::{AppBuilder, CreatePlugin, Plugin, PluginAbiVersion, DYNAMIC_PLUGIN_ABI_VERSION};
+
+#[derive(Error, Debug)]
+pub enum DynamicPluginLoadError {
+    #[error("cannot load the library: {0}")]
+    Library(libloading::Error),
+    #[error("cannot copy the library: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("the library doesn't export `{0}`")]
+    MissingSymbol(&'static str),
+    #[error("the library was built for plugin ABI version {found}, expected {expected}")]
+    AbiMismatch { expected: u32, found: u32 },
+    #[error("the state of the logic can't be handed over, as the type of a field changed")]
+    IncompatibleState,
+}
+
+/// Opens the library at `path` and checks that it was built for the same
+/// [`DYNAMIC_PLUGIN_ABI_VERSION`] as this program.
+///
+/// # Safety
+///
+/// Loading a library runs its initialization code.
+pub unsafe fn load_plugin_library(path: &str) -> Result<Library, DynamicPluginLoadError> {
+    let lib = Library::new(path).map_err(DynamicPluginLoadError::Library)?;
+    let abi_version: Symbol<PluginAbiVersion> = lib
+        .get(b"_bevy_plugin_abi_version")
+        .map_err(|_| DynamicPluginLoadError::MissingSymbol("_bevy_plugin_abi_version"))?;
+    let found = abi_version();
+    if found != DYNAMIC_PLUGIN_ABI_VERSION {
+        return Err(DynamicPluginLoadError::AbiMismatch {
+            expected: DYNAMIC_PLUGIN_ABI_VERSION,
+            found,
+        });
+    }
+    Ok(lib)
+}
 
 /// Dynamically links a plugin a the given path. The plugin must export a function with the
 /// [`CreatePlugin`] signature named `_bevy_create_plugin`.
@@ -10,11 +46,15 @@ use bevy_app::{AppBuilder, CreatePlugin, Plugin};
 /// The specified plugin must be linked against the exact same libbevy.so as this program.
 /// In addition the `_bevy_create_plugin` symbol must not be manually created, but instead created
 /// by deriving `DynamicPlugin` on a unit struct implementing [`Plugin`].
-pub unsafe fn dynamically_load_plugin(path: &str) -> (Library, Box<dyn Plugin>) {
-    let lib = Library::new(path).unwrap();
-    let func: Symbol<CreatePlugin> = lib.get(b"_bevy_create_plugin").unwrap();
+pub unsafe fn dynamically_load_plugin(
+    path: &str,
+) -> Result<(Library, Box<dyn Plugin>), DynamicPluginLoadError> {
+    let lib = load_plugin_library(path)?;
+    let func: Symbol<CreatePlugin> = lib
+        .get(b"_bevy_create_plugin")
+        .map_err(|_| DynamicPluginLoadError::MissingSymbol("_bevy_create_plugin"))?;
     let plugin = Box::from_raw(func());
-    (lib, plugin)
+    Ok((lib, plugin))
 }
 
 pub trait DynamicPluginExt {
@@ -26,9 +66,9 @@ pub trait DynamicPluginExt {
 
 impl DynamicPluginExt for AppBuilder {
     unsafe fn load_plugin(&mut self, path: &str) -> &mut Self {
-        let (lib, plugin) = dynamically_load_plugin(path);
+        let (lib, plugin) = dynamically_load_plugin(path)
+            .unwrap_or_else(|err| panic!("Failed to load the plugin {}: {}", path, err));
         std::mem::forget(lib); // Ensure that the library is not automatically unloaded
-        plugin.build(self);
-        self
+        self.add_boxed_plugin(plugin)
     }
 }
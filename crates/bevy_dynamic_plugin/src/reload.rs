@@ -0,0 +1,384 @@
+use crate::{load_plugin_library, DynamicPluginLoadError};
+use bevy_app::AppBuilder;
+use bevy_ecs::{system::IntoExclusiveSystem, world::World};
+use bevy_reflect::{Reflect, ReflectRef};
+use bevy_utils::tracing::{error, info};
+use libloading::{Library, Symbol};
+use std::{
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+/// Game logic living in a dynamic library that can be rebuilt and reloaded while the app runs.
+///
+/// Systems can't be removed from a running app, so the logic isn't a [`Plugin`](bevy_app::Plugin)
+/// registering systems: the host calls [`ReloadableLogic::update`] every frame instead. Keep the
+/// game state in the [`World`], which outlives reloads. The fields of the logic itself are handed
+/// over to the reloaded version through reflection, by applying the old value to the new one, so
+/// fields that were added start with their default value and fields that were removed are dropped.
+/// If the type of a field changed, the reload fails and the current logic is kept: loading the new
+/// logic requires restarting the app.
+pub trait ReloadableLogic: Reflect {
+    /// Runs once the logic is loaded or reloaded.
+    fn load(&mut self, _world: &mut World) {}
+
+    /// Runs every frame.
+    fn update(&mut self, world: &mut World);
+
+    /// Runs before the logic is unloaded to be reloaded.
+    fn unload(&mut self, _world: &mut World) {}
+}
+
+/// The signature of the `_bevy_create_reloadable_logic` function exported by
+/// [`export_reloadable_logic!`]. The logic is boxed twice so that the function returns a thin
+/// pointer, which has a defined representation across `extern "C"`.
+pub type CreateReloadableLogic = unsafe extern "C" fn() -> *mut Box<dyn ReloadableLogic>;
+
+/// Exports a [`ReloadableLogic`] type implementing [`Default`] from a dynamic library, to be loaded
+/// with [`ReloadableLogicExt::add_reloadable_logic`].
+///
+/// ```ignore
+/// #[derive(Default, Reflect)]
+/// struct Gameplay {
+///     spawn_rate: f32,
+/// }
+///
+/// impl ReloadableLogic for Gameplay {
+///     fn update(&mut self, world: &mut World) {
+///         // ...
+///     }
+/// }
+///
+/// export_reloadable_logic!(Gameplay);
+/// ```
+#[macro_export]
+macro_rules! export_reloadable_logic {
+    ($logic:ty) => {
+        #[no_mangle]
+        pub extern "C" fn _bevy_create_reloadable_logic(
+        ) -> *mut ::std::boxed::Box<dyn $crate::ReloadableLogic> {
+            let logic: ::std::boxed::Box<dyn $crate::ReloadableLogic> =
+                ::std::boxed::Box::new(<$logic as ::std::default::Default>::default());
+            ::std::boxed::Box::into_raw(::std::boxed::Box::new(logic))
+        }
+
+        #[no_mangle]
+        pub extern "C" fn _bevy_plugin_abi_version() -> u32 {
+            $crate::DYNAMIC_PLUGIN_ABI_VERSION
+        }
+    };
+}
+
+/// A [`ReloadableLogic`] loaded from a dynamic library, reloaded when the library is rebuilt.
+///
+/// The library is copied before being loaded, so that the original file can be overwritten by
+/// the next build, and so that the new build isn't mistaken for the library already loaded.
+pub struct ReloadableLogicHost {
+    path: PathBuf,
+    // dropped before the library its code lives in
+    logic: Box<dyn ReloadableLogic>,
+    library: Library,
+    loaded_path: PathBuf,
+    modified: Option<SystemTime>,
+    generation: u32,
+}
+
+impl ReloadableLogicHost {
+    /// How long the library must stay unmodified before being reloaded, so that a library still
+    /// being written by the linker isn't loaded
+    pub const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+    /// Loads the logic from the library at `path`.
+    ///
+    /// # Safety
+    ///
+    /// The library must be linked against the exact same libbevy.so as this program, and export
+    /// its logic with [`export_reloadable_logic`].
+    pub unsafe fn load(path: impl Into<PathBuf>) -> Result<Self, DynamicPluginLoadError> {
+        let path = path.into();
+        let modified = modified_time(&path);
+        let (library, loaded_path, logic) = load_logic(&path, 0)?;
+        Ok(ReloadableLogicHost {
+            path,
+            logic,
+            library,
+            loaded_path,
+            modified,
+            generation: 0,
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The number of times the logic was reloaded
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+
+    pub fn logic(&self) -> &dyn ReloadableLogic {
+        &*self.logic
+    }
+
+    pub fn logic_mut(&mut self) -> &mut dyn ReloadableLogic {
+        &mut *self.logic
+    }
+
+    /// Whether the library was rebuilt since it was loaded, and hasn't been modified for
+    /// [`Self::SETTLE_DELAY`]
+    pub fn needs_reload(&self) -> bool {
+        match modified_time(&self.path) {
+            Some(modified) => {
+                Some(modified) != self.modified
+                    && modified
+                        .elapsed()
+                        .map_or(false, |elapsed| elapsed >= Self::SETTLE_DELAY)
+            }
+            None => false,
+        }
+    }
+
+    /// Reloads the logic from the library, handing over its state. The current logic is kept if
+    /// the new library can't be loaded, or if its state can't be handed over.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`ReloadableLogicHost::load`].
+    pub unsafe fn reload(&mut self, world: &mut World) -> Result<(), DynamicPluginLoadError> {
+        self.modified = modified_time(&self.path);
+        let generation = self.generation + 1;
+        let (library, loaded_path, mut logic) = load_logic(&self.path, generation)?;
+        // the state is dropped while the code that created it is still loaded
+        if !can_apply(logic.reflect_ref(), &*self.logic.clone_value()) {
+            drop(logic);
+            drop(library);
+            let _ = std::fs::remove_file(loaded_path);
+            return Err(DynamicPluginLoadError::IncompatibleState);
+        }
+
+        self.logic.unload(world);
+        {
+            let state = self.logic.clone_value();
+            logic.apply(&*state);
+        }
+        let old_logic = std::mem::replace(&mut self.logic, logic);
+        drop(old_logic);
+        let old_library = std::mem::replace(&mut self.library, library);
+        drop(old_library);
+        let old_path = std::mem::replace(&mut self.loaded_path, loaded_path);
+        let _ = std::fs::remove_file(old_path);
+
+        self.generation = generation;
+        self.logic.load(world);
+        Ok(())
+    }
+}
+
+impl Drop for ReloadableLogicHost {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_file(&self.loaded_path);
+    }
+}
+
+/// Whether `value` can be applied to `target` with [`Reflect::apply`], which panics if the types
+/// of their fields differ. The fields missing from either side are ignored, as they are by
+/// `apply`.
+fn can_apply(target: ReflectRef, value: &dyn Reflect) -> bool {
+    match (target, value.reflect_ref()) {
+        (ReflectRef::Struct(target), ReflectRef::Struct(value)) => {
+            value.iter_fields().enumerate().all(|(i, field)| {
+                target
+                    .field(value.name_at(i).unwrap())
+                    .map_or(true, |target_field| {
+                        can_apply(target_field.reflect_ref(), field)
+                    })
+            })
+        }
+        (ReflectRef::TupleStruct(target), ReflectRef::TupleStruct(value)) => {
+            value.iter_fields().enumerate().all(|(i, field)| {
+                target.field(i).map_or(true, |target_field| {
+                    can_apply(target_field.reflect_ref(), field)
+                })
+            })
+        }
+        (ReflectRef::Tuple(target), ReflectRef::Tuple(value)) => {
+            value.iter_fields().enumerate().all(|(i, field)| {
+                target.field(i).map_or(true, |target_field| {
+                    can_apply(target_field.reflect_ref(), field)
+                })
+            })
+        }
+        (ReflectRef::List(target), ReflectRef::List(_)) => target.type_name() == value.type_name(),
+        (ReflectRef::Map(target), ReflectRef::Map(_)) => target.type_name() == value.type_name(),
+        (ReflectRef::Value(target), ReflectRef::Value(_)) => {
+            target.type_name() == value.type_name()
+        }
+        _ => false,
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|meta| meta.modified())
+        .ok()
+}
+
+unsafe fn load_logic(
+    path: &Path,
+    generation: u32,
+) -> Result<(Library, PathBuf, Box<dyn ReloadableLogic>), DynamicPluginLoadError> {
+    let file_name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let loaded_path = std::env::temp_dir().join(format!(
+        "{}-{}-{}",
+        std::process::id(),
+        generation,
+        file_name
+    ));
+    std::fs::copy(path, &loaded_path)?;
+    let load = || -> Result<_, DynamicPluginLoadError> {
+        let library = load_plugin_library(&loaded_path.to_string_lossy())?;
+        // the symbol borrows the library, which is moved out below
+        let logic = {
+            let create: Symbol<CreateReloadableLogic> =
+                library.get(b"_bevy_create_reloadable_logic").map_err(|_| {
+                    DynamicPluginLoadError::MissingSymbol("_bevy_create_reloadable_logic")
+                })?;
+            *Box::from_raw(create())
+        };
+        Ok((library, logic))
+    };
+    match load() {
+        Ok((library, logic)) => Ok((library, loaded_path, logic)),
+        Err(err) => {
+            let _ = std::fs::remove_file(&loaded_path);
+            Err(err)
+        }
+    }
+}
+
+/// The [`ReloadableLogicHost`]s of the app, updated by [`reloadable_logic_system`]
+#[derive(Default)]
+pub struct ReloadableLogics {
+    pub hosts: Vec<ReloadableLogicHost>,
+}
+
+/// Reloads the [`ReloadableLogics`] whose library was rebuilt, and updates them.
+pub fn reloadable_logic_system(world: &mut World) {
+    let mut logics = match world.remove_non_send::<ReloadableLogics>() {
+        Some(logics) => logics,
+        None => return,
+    };
+    for host in logics.hosts.iter_mut() {
+        if host.needs_reload() {
+            // SAFETY: the library was checked when first loaded with `ReloadableLogicHost::load`
+            match unsafe { host.reload(world) } {
+                Ok(()) => info!("Reloaded {}", host.path.display()),
+                Err(err) => error!("Failed to reload {}: {}", host.path.display(), err),
+            }
+        }
+        host.logic.update(world);
+    }
+    world.insert_non_send(logics);
+}
+
+pub trait ReloadableLogicExt {
+    /// Loads the [`ReloadableLogic`] exported by the library at `path`, updated every frame and
+    /// reloaded whenever the library is rebuilt.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`ReloadableLogicHost::load`].
+    unsafe fn add_reloadable_logic(&mut self, path: &str) -> &mut Self;
+}
+
+impl ReloadableLogicExt for AppBuilder {
+    unsafe fn add_reloadable_logic(&mut self, path: &str) -> &mut Self {
+        let mut host = ReloadableLogicHost::load(path)
+            .unwrap_or_else(|err| panic!("Failed to load the logic {}: {}", path, err));
+        host.logic.load(self.world_mut());
+        let world = self.world_mut();
+        if world.get_non_send_resource::<ReloadableLogics>().is_none() {
+            world.insert_non_send(ReloadableLogics::default());
+            self.add_system(reloadable_logic_system.exclusive_system());
+        }
+        self.world_mut()
+            .get_non_send_resource_mut::<ReloadableLogics>()
+            .unwrap()
+            .hosts
+            .push(host);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::can_apply;
+    use bevy_reflect::{Reflect, ReflectRef};
+
+    #[derive(Reflect, Default)]
+    struct Logic {
+        spawn_rate: f32,
+        names: Vec<String>,
+        timer: Timer,
+    }
+
+    #[derive(Reflect, Default)]
+    struct Timer(f32, bool);
+
+    // the next version of `Logic`, with a field added and a field removed
+    #[derive(Reflect, Default)]
+    struct AddedField {
+        spawn_rate: f32,
+        timer: Timer,
+        speed: f32,
+    }
+
+    #[derive(Reflect, Default)]
+    struct ChangedField {
+        spawn_rate: f64,
+    }
+
+    #[derive(Reflect, Default)]
+    struct ChangedList {
+        names: Vec<u32>,
+    }
+
+    #[derive(Reflect, Default)]
+    struct ChangedTupleStruct {
+        timer: ChangedTimer,
+    }
+
+    #[derive(Reflect, Default)]
+    struct ChangedTimer(f32, u32);
+
+    fn can_hand_over<T: Reflect>(target: &T) -> bool {
+        let state = Logic::default().clone_value();
+        can_apply(target.reflect_ref(), &*state)
+    }
+
+    #[test]
+    fn hand_over_compatible_state() {
+        assert!(can_hand_over(&Logic::default()));
+
+        let mut logic = AddedField::default();
+        let state = Logic {
+            spawn_rate: 2.0,
+            ..Default::default()
+        };
+        assert!(can_apply(logic.reflect_ref(), &*state.clone_value()));
+        logic.apply(&*state.clone_value());
+        assert_eq!(logic.spawn_rate, 2.0);
+    }
+
+    #[test]
+    fn refuse_incompatible_state() {
+        assert!(!can_hand_over(&ChangedField::default()));
+        assert!(!can_hand_over(&ChangedList::default()));
+        assert!(!can_hand_over(&ChangedTupleStruct::default()));
+        assert!(!can_apply(ReflectRef::Value(&1u32), &Logic::default()));
+    }
+}
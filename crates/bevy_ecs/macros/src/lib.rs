@@ -430,6 +430,139 @@ pub fn derive_system_param(input: TokenStream) -> TokenStream {
     })
 }
 
+static WORLD_QUERY_ATTRIBUTE_NAME: &str = "world_query";
+
+/// Implement `WorldQuery` to use a struct of queries as a query, whose items are a struct of the
+/// items of its fields. With `#[world_query(filter)]`, the struct is a filter that matches the
+/// entities matched by all of its fields.
+#[proc_macro_derive(WorldQuery, attributes(world_query))]
+pub fn derive_world_query(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+    let fields = match &ast.data {
+        Data::Struct(DataStruct {
+            fields: Fields::Named(fields),
+            ..
+        }) => &fields.named,
+        _ => panic!("Expected a struct with named fields."),
+    };
+    if !ast.generics.params.is_empty() {
+        panic!("Deriving WorldQuery is not supported for generic structs.");
+    }
+    let path = bevy_ecs_path();
+
+    let mut is_filter = false;
+    for attr in ast.attrs.iter() {
+        if attr
+            .path
+            .get_ident()
+            .map_or(false, |ident| ident == WORLD_QUERY_ATTRIBUTE_NAME)
+        {
+            syn::custom_keyword!(filter);
+            attr.parse_args_with(|input: ParseStream| {
+                input.parse::<filter>()?;
+                is_filter = true;
+                Ok(())
+            })
+            .expect("Invalid 'world_query' attribute format.");
+        }
+    }
+
+    let struct_name = &ast.ident;
+    let visibility = &ast.vis;
+    let field_names = fields
+        .iter()
+        .map(|field| field.ident.as_ref().unwrap())
+        .collect::<Vec<_>>();
+    let field_types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let field_visibilities = fields.iter().map(|field| &field.vis).collect::<Vec<_>>();
+
+    // The struct only describes the query and is never constructed, so its fields are marked as
+    // read to silence the dead code lint.
+    let read_fields = quote! {
+        const _: () = {
+            #[allow(dead_code)]
+            fn read_fields(query: &#struct_name) {
+                #(let _ = &query.#field_names;)*
+            }
+        };
+    };
+
+    if is_filter {
+        return TokenStream::from(quote! {
+            #read_fields
+
+            impl #path::query::WorldQuery for #struct_name {
+                type Fetch = <(#(#field_types,)*) as #path::query::WorldQuery>::Fetch;
+                type State = <(#(#field_types,)*) as #path::query::WorldQuery>::State;
+            }
+        });
+    }
+
+    let item_struct_name = format_ident!("{}Item", struct_name);
+    let fetch_struct_name = format_ident!("{}Fetch", struct_name);
+    let item_doc = format!("The item of the [`{}`] query.", struct_name);
+
+    TokenStream::from(quote! {
+        #read_fields
+
+        #[doc = #item_doc]
+        #visibility struct #item_struct_name<'w> {
+            #(#field_visibilities #field_names: <<#field_types as #path::query::WorldQuery>::Fetch as #path::query::Fetch<'w>>::Item,)*
+        }
+
+        impl #path::query::WorldQuery for #struct_name {
+            type Fetch = #fetch_struct_name<(#(<#field_types as #path::query::WorldQuery>::Fetch,)*)>;
+            type State = <(#(#field_types,)*) as #path::query::WorldQuery>::State;
+        }
+
+        #[doc(hidden)]
+        #visibility struct #fetch_struct_name<TFetch> {
+            fetch: TFetch,
+        }
+
+        impl<'w> #path::query::Fetch<'w> for #fetch_struct_name<(#(<#field_types as #path::query::WorldQuery>::Fetch,)*)> {
+            type Item = #item_struct_name<'w>;
+            type State = <(#(#field_types,)*) as #path::query::WorldQuery>::State;
+
+            unsafe fn init(world: &#path::world::World, state: &Self::State, last_change_tick: u32, change_tick: u32) -> Self {
+                Self {
+                    fetch: #path::query::Fetch::init(world, state, last_change_tick, change_tick),
+                }
+            }
+
+            #[inline]
+            fn is_dense(&self) -> bool {
+                self.fetch.is_dense()
+            }
+
+            #[inline]
+            unsafe fn set_archetype(&mut self, state: &Self::State, archetype: &#path::archetype::Archetype, tables: &#path::storage::Tables) {
+                self.fetch.set_archetype(state, archetype, tables)
+            }
+
+            #[inline]
+            unsafe fn set_table(&mut self, state: &Self::State, table: &#path::storage::Table) {
+                self.fetch.set_table(state, table)
+            }
+
+            #[inline]
+            unsafe fn table_fetch(&mut self, table_row: usize) -> Self::Item {
+                let (#(#field_names,)*) = self.fetch.table_fetch(table_row);
+                #item_struct_name { #(#field_names,)* }
+            }
+
+            #[inline]
+            unsafe fn archetype_fetch(&mut self, archetype_index: usize) -> Self::Item {
+                let (#(#field_names,)*) = self.fetch.archetype_fetch(archetype_index);
+                #item_struct_name { #(#field_names,)* }
+            }
+        }
+
+        // SAFE: each field is read only
+        unsafe impl<TFetch: #path::query::ReadOnlyFetch> #path::query::ReadOnlyFetch for #fetch_struct_name<TFetch> {}
+    })
+}
+
 #[proc_macro_derive(SystemLabel)]
 pub fn derive_system_label(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
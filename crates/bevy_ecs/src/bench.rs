@@ -0,0 +1,289 @@
+//! Utilities to measure the performance of systems and queries on a custom [`World`].
+//!
+//! This module is only available with the `bench` feature enabled.
+//!
+//! ```
+//! # use bevy_ecs::{bench::Bench, prelude::*};
+//! struct Position(f32);
+//! struct Velocity(f32);
+//!
+//! let mut bench = Bench::new().iterations(10);
+//! bench
+//!     .spawn(1_000, |i| (Position(i as f32), Velocity(1.0)))
+//!     .fragment(8);
+//!
+//! fn movement(mut query: Query<(&mut Position, &Velocity)>) {
+//!     for (mut position, velocity) in query.iter_mut() {
+//!         position.0 += velocity.0;
+//!     }
+//! }
+//!
+//! let report = bench.run_system("movement", movement);
+//! println!("{}", report);
+//! ```
+
+use crate::{
+    bundle::Bundle,
+    entity::Entity,
+    system::{IntoSystem, System},
+    world::World,
+};
+use std::{
+    fmt,
+    time::{Duration, Instant},
+};
+
+/// A [`World`] set up for benchmarking, along with the number of times each measured operation
+/// is repeated.
+pub struct Bench {
+    world: World,
+    entities: Vec<Entity>,
+    warmup_iterations: usize,
+    iterations: usize,
+}
+
+impl Default for Bench {
+    fn default() -> Self {
+        Self {
+            world: World::new(),
+            entities: Vec::new(),
+            warmup_iterations: 3,
+            iterations: 100,
+        }
+    }
+}
+
+impl Bench {
+    /// Creates a new benchmark with an empty [`World`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the number of unmeasured runs done before measuring.
+    pub fn warmup_iterations(mut self, warmup_iterations: usize) -> Self {
+        self.warmup_iterations = warmup_iterations;
+        self
+    }
+
+    /// Sets the number of measured runs.
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        assert!(iterations > 0, "at least one iteration must be measured");
+        self.iterations = iterations;
+        self
+    }
+
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Returns all entities spawned with [`Bench::spawn`], in spawn order.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Spawns `count` entities, using `func` to create the bundle of the entity at each index.
+    pub fn spawn<B, F>(&mut self, count: usize, mut func: F) -> &mut Self
+    where
+        B: Bundle,
+        F: FnMut(usize) -> B,
+    {
+        let entities = self.world.spawn_batch((0..count).map(&mut func));
+        self.entities.extend(entities);
+        self
+    }
+
+    /// Spreads the entities spawned so far over `archetypes` different archetypes (and tables),
+    /// by inserting combinations of marker components. At most 256 archetypes are supported.
+    ///
+    /// This simulates the fragmentation of a real application, where queries have to visit many
+    /// tables.
+    pub fn fragment(&mut self, archetypes: usize) -> &mut Self {
+        assert!(
+            archetypes > 0 && archetypes <= 256,
+            "the number of archetypes must be between 1 and 256"
+        );
+        for (index, entity) in self.entities.iter().enumerate() {
+            let bits = index % archetypes;
+            let mut entity = self.world.entity_mut(*entity);
+            macro_rules! insert_markers {
+                ($($bit:literal),*) => {
+                    $(if bits & (1 << $bit) != 0 {
+                        entity.insert(FragmentMarker::<$bit>);
+                    })*
+                };
+            }
+            insert_markers!(0, 1, 2, 3, 4, 5, 6, 7);
+        }
+        self
+    }
+
+    /// Measures `func` running on the benchmark [`World`].
+    pub fn run<F>(&mut self, name: &str, mut func: F) -> BenchReport
+    where
+        F: FnMut(&mut World),
+    {
+        for _ in 0..self.warmup_iterations {
+            func(&mut self.world);
+        }
+        let samples = (0..self.iterations)
+            .map(|_| {
+                let start = Instant::now();
+                func(&mut self.world);
+                start.elapsed()
+            })
+            .collect();
+        BenchReport::new(name, samples)
+    }
+
+    /// Measures a single run of `system` on the benchmark [`World`], including the application
+    /// of its command buffers.
+    pub fn run_system<Param>(
+        &mut self,
+        name: &str,
+        system: impl IntoSystem<(), (), Param>,
+    ) -> BenchReport {
+        let mut system = system.system();
+        system.initialize(&mut self.world);
+        for archetype in self.world.archetypes().iter() {
+            system.new_archetype(archetype);
+        }
+        self.run(name, |world| {
+            system.run((), world);
+            system.apply_buffers(world);
+        })
+    }
+}
+
+/// Marker component used by [`Bench::fragment`].
+struct FragmentMarker<const BIT: u8>;
+
+/// The timings measured by a [`Bench`].
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    name: String,
+    samples: Vec<Duration>,
+}
+
+impl BenchReport {
+    /// Creates a report from the given samples, which must not be empty.
+    pub fn new(name: &str, mut samples: Vec<Duration>) -> Self {
+        assert!(!samples.is_empty(), "a report needs at least one sample");
+        samples.sort();
+        Self {
+            name: name.to_string(),
+            samples,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the measured samples, sorted from fastest to slowest.
+    pub fn samples(&self) -> &[Duration] {
+        &self.samples
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples[0]
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples[self.samples.len() - 1]
+    }
+
+    pub fn median(&self) -> Duration {
+        let len = self.samples.len();
+        if len % 2 == 0 {
+            (self.samples[len / 2 - 1] + self.samples[len / 2]) / 2
+        } else {
+            self.samples[len / 2]
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        self.samples.iter().sum::<Duration>() / self.samples.len() as u32
+    }
+
+    /// Returns the standard deviation of the samples.
+    pub fn std_dev(&self) -> Duration {
+        let mean = self.mean().as_secs_f64();
+        let variance = self
+            .samples
+            .iter()
+            .map(|sample| (sample.as_secs_f64() - mean).powi(2))
+            .sum::<f64>()
+            / self.samples.len() as f64;
+        Duration::from_secs_f64(variance.sqrt())
+    }
+}
+
+impl fmt::Display for BenchReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: mean {:?} ± {:?}, median {:?}, min {:?}, max {:?} ({} samples)",
+            self.name,
+            self.mean(),
+            self.std_dev(),
+            self.median(),
+            self.min(),
+            self.max(),
+            self.samples.len()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Bench, BenchReport};
+    use crate::system::{Query, ResMut};
+    use std::time::Duration;
+
+    struct A(usize);
+
+    #[test]
+    fn spawn_and_fragment() {
+        let mut bench = Bench::new();
+        bench.spawn(64, |i| (A(i),)).fragment(16);
+        assert_eq!(bench.entities().len(), 64);
+        assert_eq!(bench.world().entities().len(), 64);
+        // the empty and resource archetypes, plus the archetype of `A` and its 15 fragments
+        assert_eq!(bench.world().archetypes().len(), 18);
+    }
+
+    #[test]
+    fn run_system() {
+        let mut bench = Bench::new().warmup_iterations(2).iterations(5);
+        bench.spawn(10, |i| (A(i),));
+        bench.world_mut().insert_resource(0usize);
+
+        fn sum_system(query: Query<&A>, mut sum: ResMut<usize>) {
+            *sum += query.iter().map(|a| a.0).sum::<usize>();
+        }
+
+        let report = bench.run_system("sum", sum_system);
+        assert_eq!(report.samples().len(), 5);
+        assert_eq!(*bench.world().get_resource::<usize>().unwrap(), 7 * 45);
+    }
+
+    #[test]
+    fn report_statistics() {
+        let report = BenchReport::new(
+            "report",
+            [4, 1, 3, 2]
+                .iter()
+                .map(|millis| Duration::from_millis(*millis))
+                .collect(),
+        );
+        assert_eq!(report.min(), Duration::from_millis(1));
+        assert_eq!(report.max(), Duration::from_millis(4));
+        assert_eq!(report.median(), Duration::from_micros(2500));
+        assert_eq!(report.mean(), Duration::from_micros(2500));
+        assert_eq!((report.std_dev().as_secs_f64() * 1e6).round(), 1118.0);
+    }
+}
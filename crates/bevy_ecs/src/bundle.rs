@@ -140,30 +140,62 @@ impl BundleInfo {
         // bundle_info.component_ids are also in "bundle order"
         let mut bundle_component = 0;
         bundle.get_components(|component_ptr| {
-            let component_id = *self.component_ids.get_unchecked(bundle_component);
-            match self.storage_types[bundle_component] {
-                StorageType::Table => {
-                    let column = table.get_column_mut(component_id).unwrap();
-                    match bundle_status.get_unchecked(bundle_component) {
-                        ComponentStatus::Added => {
-                            column.initialize(
-                                table_row,
-                                component_ptr,
-                                ComponentTicks::new(change_tick),
-                            );
-                        }
-                        ComponentStatus::Mutated => {
-                            column.replace(table_row, component_ptr, change_tick);
-                        }
+            self.write_component(
+                sparse_sets,
+                entity,
+                table,
+                table_row,
+                bundle_status,
+                bundle_component,
+                component_ptr,
+                change_tick,
+            );
+            bundle_component += 1;
+        });
+    }
+
+    /// Moves the value behind `component_ptr` into the storage of the component at index
+    /// `bundle_component` of this bundle.
+    ///
+    /// # Safety
+    /// table row must exist, entity must be valid, `bundle_component` must be a valid index and
+    /// `component_ptr` must point to a value of the type of that component, which is then owned
+    /// by the storage
+    #[allow(clippy::too_many_arguments)]
+    #[inline]
+    pub(crate) unsafe fn write_component(
+        &self,
+        sparse_sets: &mut SparseSets,
+        entity: Entity,
+        table: &mut Table,
+        table_row: usize,
+        bundle_status: &[ComponentStatus],
+        bundle_component: usize,
+        component_ptr: *mut u8,
+        change_tick: u32,
+    ) {
+        let component_id = *self.component_ids.get_unchecked(bundle_component);
+        match self.storage_types[bundle_component] {
+            StorageType::Table => {
+                let column = table.get_column_mut(component_id).unwrap();
+                match bundle_status.get_unchecked(bundle_component) {
+                    ComponentStatus::Added => {
+                        column.initialize(
+                            table_row,
+                            component_ptr,
+                            ComponentTicks::new(change_tick),
+                        );
+                    }
+                    ComponentStatus::Mutated => {
+                        column.replace(table_row, component_ptr, change_tick);
                     }
-                }
-                StorageType::SparseSet => {
-                    let sparse_set = sparse_sets.get_mut(component_id).unwrap();
-                    sparse_set.insert(entity, component_ptr, change_tick);
                 }
             }
-            bundle_component += 1;
-        });
+            StorageType::SparseSet => {
+                let sparse_set = sparse_sets.get_mut(component_id).unwrap();
+                sparse_set.insert(entity, component_ptr, change_tick);
+            }
+        }
     }
 
     #[inline]
@@ -186,6 +218,7 @@ impl BundleInfo {
 pub struct Bundles {
     bundle_infos: Vec<BundleInfo>,
     bundle_ids: HashMap<TypeId, BundleId>,
+    dynamic_bundle_ids: HashMap<Vec<ComponentId>, BundleId>,
 }
 
 impl Bundles {
@@ -215,6 +248,49 @@ impl Bundles {
         // SAFE: index either exists, or was initialized
         unsafe { self.bundle_infos.get_unchecked(id.0) }
     }
+
+    /// Returns the [`BundleInfo`] of a bundle of the given components, which don't need to have a
+    /// Rust type.
+    ///
+    /// # Panics
+    /// Panics if `component_ids` contains duplicates or components that are not registered in
+    /// `components`.
+    pub(crate) fn init_dynamic_info<'a>(
+        &'a mut self,
+        components: &Components,
+        component_ids: &[ComponentId],
+    ) -> &'a BundleInfo {
+        let bundle_infos = &mut self.bundle_infos;
+        let id = self
+            .dynamic_bundle_ids
+            .entry(component_ids.to_vec())
+            .or_insert_with(|| {
+                let mut deduped = component_ids.to_vec();
+                deduped.sort();
+                deduped.dedup();
+                if deduped.len() != component_ids.len() {
+                    panic!("Bundle {:?} has duplicate components", component_ids);
+                }
+                let storage_types = component_ids
+                    .iter()
+                    .map(|id| {
+                        components
+                            .get_info(*id)
+                            .unwrap_or_else(|| panic!("Component {:?} does not exist", id))
+                            .storage_type()
+                    })
+                    .collect();
+                let id = BundleId(bundle_infos.len());
+                bundle_infos.push(BundleInfo {
+                    id,
+                    component_ids: component_ids.to_vec(),
+                    storage_types,
+                });
+                id
+            });
+        // SAFE: index either exists, or was initialized
+        unsafe { self.bundle_infos.get_unchecked(id.0) }
+    }
 }
 
 fn initialize_bundle(
@@ -103,6 +103,18 @@ impl ComponentInfo {
         self.is_send_and_sync
     }
 
+    /// A descriptor of this component, to register it in another [`World`](crate::world::World).
+    pub(crate) fn descriptor(&self) -> ComponentDescriptor {
+        ComponentDescriptor {
+            name: self.name.clone(),
+            storage_type: self.storage_type,
+            is_send_and_sync: self.is_send_and_sync,
+            type_id: self.type_id,
+            layout: self.layout,
+            drop: self.drop,
+        }
+    }
+
     fn new(id: ComponentId, descriptor: ComponentDescriptor) -> Self {
         ComponentInfo {
             id,
@@ -165,6 +177,28 @@ impl ComponentDescriptor {
         }
     }
 
+    /// Creates the descriptor of a component that has no Rust type, e.g. one defined by a
+    /// scripting language. Its values are `layout.size()` bytes that are dropped with `drop`.
+    ///
+    /// # Safety
+    /// The values of this component must be safe to send and share between threads, and `drop`
+    /// must be safe to call on a pointer to any value inserted for this component.
+    pub unsafe fn new_with_layout(
+        name: impl Into<String>,
+        storage_type: StorageType,
+        layout: Layout,
+        drop: unsafe fn(*mut u8),
+    ) -> Self {
+        Self {
+            name: name.into(),
+            storage_type,
+            is_send_and_sync: true,
+            type_id: None,
+            layout,
+            drop,
+        }
+    }
+
     #[inline]
     pub fn storage_type(&self) -> StorageType {
         self.storage_type
@@ -322,6 +356,21 @@ impl Components {
     }
 }
 
+/// The oldest a change tick can get relative to the current change tick, as old ticks are
+/// clamped by [`World::check_change_ticks`](crate::world::World::check_change_ticks). Changes
+/// older than this are no longer detected, but changes are never detected twice because of a
+/// wraparound.
+pub const MAX_CHANGE_AGE: u32 = (u32::MAX / 4) * 3;
+
+/// How many ticks may pass between two checks of the change ticks of a world. Lower than
+/// `u32::MAX - MAX_CHANGE_AGE` so that no tick can wrap around before it's clamped.
+pub const CHECK_TICK_THRESHOLD: u32 = u32::MAX / 8;
+
+/// The age old change ticks are clamped to. Ticks are only checked every
+/// [`CHECK_TICK_THRESHOLD`] ticks, so they are clamped with that much margin to stay within
+/// [`MAX_CHANGE_AGE`] until the next check.
+pub(crate) const CLAMPED_CHANGE_AGE: u32 = MAX_CHANGE_AGE - CHECK_TICK_THRESHOLD;
+
 #[derive(Clone, Debug)]
 pub struct ComponentTicks {
     pub(crate) added: u32,
@@ -378,11 +427,10 @@ impl ComponentTicks {
     }
 }
 
-fn check_tick(last_change_tick: &mut u32, change_tick: u32) {
+pub(crate) fn check_tick(last_change_tick: &mut u32, change_tick: u32) {
     let tick_delta = change_tick.wrapping_sub(*last_change_tick);
-    const MAX_DELTA: u32 = (u32::MAX / 4) * 3;
     // Clamp to max delta
-    if tick_delta > MAX_DELTA {
-        *last_change_tick = change_tick.wrapping_sub(MAX_DELTA);
+    if tick_delta > CLAMPED_CHANGE_AGE {
+        *last_change_tick = change_tick.wrapping_sub(CLAMPED_CHANGE_AGE);
     }
 }
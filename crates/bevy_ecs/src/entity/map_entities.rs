@@ -1,5 +1,4 @@
-use crate::entity::Entity;
-use bevy_utils::HashMap;
+use crate::entity::{Entity, EntityHashMap};
 use std::collections::hash_map::Entry;
 use thiserror::Error;
 
@@ -15,7 +14,7 @@ pub trait MapEntities {
 
 #[derive(Default, Debug)]
 pub struct EntityMap {
-    map: HashMap<Entity, Entity>,
+    map: EntityHashMap<Entity>,
 }
 
 impl EntityMap {
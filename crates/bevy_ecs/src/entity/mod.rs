@@ -5,9 +5,14 @@ pub use self::serde::*;
 pub use map_entities::*;
 
 use crate::{archetype::ArchetypeId, storage::SparseSetIndex};
+use bevy_utils::EntityHash;
 use std::{
+    cmp::Ordering as CmpOrdering,
     convert::TryFrom,
-    fmt, mem,
+    fmt,
+    hash::{Hash, Hasher},
+    mem,
+    num::NonZeroU32,
     sync::atomic::{AtomicI64, Ordering},
 };
 
@@ -20,16 +25,31 @@ use std::{
 /// `Entity` can be a part of a query, e.g. `Query<(Entity, &MyComponent)>`.
 /// Components of a specific entity can be accessed using
 /// [`Query::get`](crate::system::Query::get) and related methods.
-#[derive(Clone, Copy, Hash, Eq, Ord, PartialEq, PartialOrd)]
+///
+/// Generations start at 1, so `Option<Entity>` is the same size as `Entity`. The fields are laid
+/// out like the `u64` of [`Entity::to_bits`], so that comparing and hashing entities compiles to
+/// single `u64` operations.
+#[derive(Clone, Copy)]
+#[repr(C, align(8))]
 pub struct Entity {
-    pub(crate) generation: u32,
+    #[cfg(target_endian = "little")]
+    pub(crate) id: u32,
+    pub(crate) generation: NonZeroU32,
+    #[cfg(target_endian = "big")]
     pub(crate) id: u32,
 }
 
+/// The generation of entities whose id was never used before
+// SAFE: 1 is not zero
+pub(crate) const FIRST_GENERATION: NonZeroU32 = unsafe { NonZeroU32::new_unchecked(1) };
+
 impl Entity {
-    /// Creates a new entity reference with a generation of 0.
+    /// Creates a new entity reference with the first generation.
     pub fn new(id: u32) -> Entity {
-        Entity { id, generation: 0 }
+        Entity {
+            id,
+            generation: FIRST_GENERATION,
+        }
     }
 
     /// Convert to a form convenient for passing outside of rust.
@@ -38,18 +58,28 @@ impl Entity {
     /// for serialization between runs.
     ///
     /// No particular structure is guaranteed for the returned bits.
+    #[inline]
     pub fn to_bits(self) -> u64 {
-        u64::from(self.generation) << 32 | u64::from(self.id)
+        u64::from(self.generation.get()) << 32 | u64::from(self.id)
     }
 
     /// Reconstruct an `Entity` previously destructured with [`Entity::to_bits`].
     ///
     /// Only useful when applied to results from `to_bits` in the same instance of an application.
+    /// Panics if the bits don't come from `to_bits`, see [`Entity::try_from_bits`].
+    #[inline]
     pub fn from_bits(bits: u64) -> Self {
-        Self {
-            generation: (bits >> 32) as u32,
+        Self::try_from_bits(bits).expect("the bits of an entity have a non-zero generation")
+    }
+
+    /// Like [`Entity::from_bits`], but returns `None` if the bits don't come from
+    /// [`Entity::to_bits`].
+    #[inline]
+    pub fn try_from_bits(bits: u64) -> Option<Self> {
+        Some(Self {
+            generation: NonZeroU32::new((bits >> 32) as u32)?,
             id: bits as u32,
-        }
+        })
     }
 
     /// Return a transiently unique identifier.
@@ -67,7 +97,37 @@ impl Entity {
     /// given id has been reused (id, generation) pairs uniquely identify a given Entity.
     #[inline]
     pub fn generation(self) -> u32 {
-        self.generation
+        self.generation.get()
+    }
+}
+
+impl PartialEq for Entity {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bits() == other.to_bits()
+    }
+}
+
+impl Eq for Entity {}
+
+impl PartialOrd for Entity {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entity {
+    #[inline]
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.to_bits().cmp(&other.to_bits())
+    }
+}
+
+impl Hash for Entity {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_bits().hash(state);
     }
 }
 
@@ -87,6 +147,13 @@ impl SparseSetIndex for Entity {
     }
 }
 
+/// A hash map keyed by entities, using the [`EntityHasher`](bevy_utils::EntityHasher). Much
+/// faster than [`HashMap`](bevy_utils::HashMap) for entity keys.
+pub type EntityHashMap<V> = std::collections::HashMap<Entity, V, EntityHash>;
+
+/// A hash set of entities, using the [`EntityHasher`](bevy_utils::EntityHasher).
+pub type EntityHashSet = std::collections::HashSet<Entity, EntityHash>;
+
 /// An [`Iterator`] returning a sequence of [`Entity`] values from
 /// [`Entities::reserve_entities`](crate::entity::Entities::reserve_entities).
 pub struct ReserveEntitiesIterator<'a> {
@@ -110,7 +177,7 @@ impl<'a> Iterator for ReserveEntitiesIterator<'a> {
                 generation: self.meta[id as usize].generation,
                 id,
             })
-            .or_else(|| self.id_range.next().map(|id| Entity { generation: 0, id }))
+            .or_else(|| self.id_range.next().map(|id| Entity::new(id)))
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
@@ -168,7 +235,14 @@ pub struct Entities {
 impl Entities {
     /// Reserve entity IDs concurrently.
     ///
-    /// Storage for entity generation and location is lazily allocated by calling `flush`.
+    /// This is lock-free, and can be called from any thread holding a reference to the
+    /// [`Entities`], e.g. from the tasks of a system taking a `&Entities` parameter. Tasks that
+    /// outlive the system, like those of the `AsyncComputeTaskPool`, should be given IDs reserved
+    /// before they are spawned.
+    ///
+    /// Storage for entity generation and location is lazily allocated by calling `flush`, which
+    /// [`World::flush`](crate::world::World::flush) does. Until then, the reserved entities
+    /// don't exist in the world.
     pub fn reserve_entities(&self, count: u32) -> ReserveEntitiesIterator {
         // Use one atomic subtract to grab a range of new IDs. The range might be
         // entirely nonnegative, meaning all IDs come from the freelist, or entirely
@@ -208,7 +282,7 @@ impl Entities {
         }
     }
 
-    /// Reserve one entity ID concurrently.
+    /// Reserve one entity ID concurrently, see [`Entities::reserve_entities`].
     ///
     /// Equivalent to `self.reserve_entities(1).next().unwrap()`, but more efficient.
     pub fn reserve_entity(&self) -> Entity {
@@ -226,10 +300,7 @@ impl Entities {
             //
             // As `self.free_cursor` goes more and more negative, we return IDs farther
             // and farther beyond `meta.len()`.
-            Entity {
-                generation: 0,
-                id: u32::try_from(self.meta.len() as i64 - n).expect("too many entities"),
-            }
+            Entity::new(u32::try_from(self.meta.len() as i64 - n).expect("too many entities"))
         }
     }
 
@@ -258,7 +329,7 @@ impl Entities {
         } else {
             let id = u32::try_from(self.meta.len()).expect("too many entities");
             self.meta.push(EntityMeta::EMPTY);
-            Entity { generation: 0, id }
+            Entity::new(id)
         }
     }
 
@@ -294,6 +365,75 @@ impl Entities {
         loc
     }
 
+    /// Allocate a specific entity ID, unless it's used by an entity of another generation.
+    ///
+    /// The location of a newly allocated entity must be written immediately.
+    pub fn alloc_at_without_replacement(&mut self, entity: Entity) -> AllocAtWithoutReplacement {
+        self.verify_flushed();
+
+        if entity.id as usize >= self.meta.len() {
+            self.pending.extend((self.meta.len() as u32)..entity.id);
+            let new_free_cursor = self.pending.len() as i64;
+            *self.free_cursor.get_mut() = new_free_cursor;
+            self.meta.resize(entity.id as usize + 1, EntityMeta::EMPTY);
+        } else if let Some(index) = self.pending.iter().position(|item| *item == entity.id) {
+            self.pending.swap_remove(index);
+            let new_free_cursor = self.pending.len() as i64;
+            *self.free_cursor.get_mut() = new_free_cursor;
+        } else {
+            let meta = &self.meta[entity.id as usize];
+            return if meta.generation == entity.generation {
+                AllocAtWithoutReplacement::Exists(meta.location)
+            } else {
+                AllocAtWithoutReplacement::ExistsWithWrongGeneration
+            };
+        }
+
+        self.len += 1;
+        self.meta[entity.id as usize].generation = entity.generation;
+        AllocAtWithoutReplacement::DidNotExist
+    }
+
+    /// Like [`Entities::alloc_at_without_replacement`], but in constant time: a freed ID that is
+    /// allocated again stays in the freelist, until [`Entities::remove_allocated_from_freelist`]
+    /// is called. The locations of the entities allocated in between must be written before that
+    /// call, and no entity can be reserved or allocated by other means until then.
+    pub(crate) fn alloc_at_without_replacement_unlisted(
+        &mut self,
+        entity: Entity,
+    ) -> AllocAtWithoutReplacement {
+        self.verify_flushed();
+
+        if entity.id as usize >= self.meta.len() {
+            self.pending.extend((self.meta.len() as u32)..entity.id);
+            let new_free_cursor = self.pending.len() as i64;
+            *self.free_cursor.get_mut() = new_free_cursor;
+            self.meta.resize(entity.id as usize + 1, EntityMeta::EMPTY);
+        } else {
+            let meta = &self.meta[entity.id as usize];
+            if !meta.is_free() {
+                return if meta.generation == entity.generation {
+                    AllocAtWithoutReplacement::Exists(meta.location)
+                } else {
+                    AllocAtWithoutReplacement::ExistsWithWrongGeneration
+                };
+            }
+        }
+
+        self.len += 1;
+        self.meta[entity.id as usize].generation = entity.generation;
+        AllocAtWithoutReplacement::DidNotExist
+    }
+
+    /// Removes the IDs allocated by [`Entities::alloc_at_without_replacement_unlisted`] from the
+    /// freelist.
+    pub(crate) fn remove_allocated_from_freelist(&mut self) {
+        let meta = &self.meta;
+        self.pending.retain(|id| meta[*id as usize].is_free());
+        let new_free_cursor = self.pending.len() as i64;
+        *self.free_cursor.get_mut() = new_free_cursor;
+    }
+
     /// Destroy an entity, allowing it to be reused.
     ///
     /// Must not be called while reserved entities are awaiting `flush()`.
@@ -304,7 +444,9 @@ impl Entities {
         if meta.generation != entity.generation {
             return None;
         }
-        meta.generation += 1;
+        // wrap around to the first generation rather than panicking
+        meta.generation =
+            NonZeroU32::new(meta.generation.get().wrapping_add(1)).unwrap_or(FIRST_GENERATION);
 
         let loc = mem::replace(&mut meta.location, EntityMeta::EMPTY.location);
 
@@ -386,8 +528,8 @@ impl Entities {
             let num_pending = std::cmp::max(-free_cursor, 0) as usize;
 
             if meta_len + num_pending > id as usize {
-                // Pending entities will have generation 0.
-                Entity { generation: 0, id }
+                // Pending entities will have the first generation.
+                Entity::new(id)
             } else {
                 panic!("entity id is out of range");
             }
@@ -447,22 +589,72 @@ impl Entities {
     pub fn is_empty(&self) -> bool {
         self.len == 0
     }
+
+    /// Captures the generations and free list, so that [`Entities::restore_allocator`] makes the
+    /// same IDs be allocated next.
+    pub(crate) fn snapshot_allocator(&mut self) -> EntitiesSnapshot {
+        self.verify_flushed();
+        EntitiesSnapshot {
+            generations: self.meta.iter().map(|meta| meta.generation).collect(),
+            pending: self.pending.clone(),
+            len: self.len,
+        }
+    }
+
+    /// Restores the generations and free list of `snapshot`. The entities alive in the snapshot
+    /// must be exactly the allocated ones, with their locations already written.
+    pub(crate) fn restore_allocator(&mut self, snapshot: &EntitiesSnapshot) {
+        self.verify_flushed();
+        self.meta
+            .resize(snapshot.generations.len(), EntityMeta::EMPTY);
+        for (meta, generation) in self.meta.iter_mut().zip(snapshot.generations.iter()) {
+            meta.generation = *generation;
+        }
+        self.pending = snapshot.pending.clone();
+        *self.free_cursor.get_mut() = self.pending.len() as i64;
+        self.len = snapshot.len;
+    }
+}
+
+/// The allocator state of [`Entities`], kept by a [`WorldSnapshot`](crate::world::WorldSnapshot).
+#[derive(Clone, Debug)]
+pub(crate) struct EntitiesSnapshot {
+    generations: Vec<NonZeroU32>,
+    pending: Vec<u32>,
+    len: u32,
+}
+
+/// The outcome of [`Entities::alloc_at_without_replacement`]
+#[derive(Copy, Clone, Debug)]
+pub enum AllocAtWithoutReplacement {
+    /// The entity already exists, at this location
+    Exists(EntityLocation),
+    /// The entity was allocated
+    DidNotExist,
+    /// The id is used by an entity of another generation, so nothing was allocated
+    ExistsWithWrongGeneration,
 }
 
 #[derive(Copy, Clone, Debug)]
 pub struct EntityMeta {
-    pub generation: u32,
+    pub generation: NonZeroU32,
     pub location: EntityLocation,
 }
 
 impl EntityMeta {
     const EMPTY: EntityMeta = EntityMeta {
-        generation: 0,
+        generation: FIRST_GENERATION,
         location: EntityLocation {
             archetype_id: ArchetypeId::empty(),
             index: usize::max_value(), // dummy value, to be filled in
         },
     };
+
+    /// Whether the ID is unused: freed IDs and IDs skipped over have the dummy location of
+    /// [`EntityMeta::EMPTY`], until they're allocated again.
+    fn is_free(&self) -> bool {
+        self.location.index == EntityMeta::EMPTY.location.index
+    }
 }
 
 /// A location of an entity in an archetype.
@@ -482,10 +674,50 @@ mod tests {
     #[test]
     fn entity_bits_roundtrip() {
         let e = Entity {
-            generation: 0xDEADBEEF,
+            generation: NonZeroU32::new(0xDEADBEEF).unwrap(),
             id: 0xBAADF00D,
         };
         assert_eq!(Entity::from_bits(e.to_bits()), e);
+        assert_eq!(Entity::try_from_bits(0xBAADF00D), None);
+    }
+
+    #[test]
+    fn entity_niche() {
+        assert_eq!(mem::size_of::<Entity>(), mem::size_of::<u64>());
+        assert_eq!(mem::size_of::<Option<Entity>>(), mem::size_of::<Entity>());
+        assert_eq!(Entity::new(7).to_bits(), 1 << 32 | 7);
+    }
+
+    #[test]
+    fn entity_ordering() {
+        let old = Entity::from_bits(1 << 32 | 5);
+        let new = Entity::from_bits(2 << 32 | 3);
+        assert!(old < new);
+        assert!(Entity::new(3) < Entity::new(5));
+    }
+
+    #[test]
+    fn entity_hash_map() {
+        let mut map = EntityHashMap::default();
+        map.insert(Entity::new(3), "a");
+        map.insert(Entity::from_bits(2 << 32 | 3), "b");
+        assert_eq!(map.get(&Entity::new(3)), Some(&"a"));
+        assert_eq!(map.len(), 2);
+
+        let mut set = EntityHashSet::default();
+        assert!(set.insert(Entity::new(3)));
+        assert!(!set.insert(Entity::new(3)));
+        assert!(set.contains(&Entity::new(3)));
+    }
+
+    #[test]
+    fn generation_wraps_around() {
+        let mut entities = Entities::default();
+        let entity = entities.alloc();
+        entities.meta[entity.id as usize].generation = NonZeroU32::new(u32::MAX).unwrap();
+        let entity = Entity::from_bits(u64::from(u32::MAX) << 32 | u64::from(entity.id));
+        entities.free(entity);
+        assert_eq!(entities.alloc().generation(), 1);
     }
 
     #[test]
@@ -1,6 +1,7 @@
 use crate as bevy_ecs;
 use crate::{
     component::Component,
+    entity::{Entity, EntityHashMap},
     system::{Local, Res, ResMut, SystemParam},
 };
 use bevy_utils::tracing::trace;
@@ -117,11 +118,25 @@ enum State {
 /// This complicates consumption and risks ever-expanding memory usage if not cleaned up,
 /// but can be done by adding your event as a resource instead of using [`AppBuilder::add_event`].
 ///
+/// Events sent with [`Events::send_to`] are also indexed by their target entity, so that
+/// [`EventReader::read_for`] finds the events of an entity without going through the others.
+///
+/// Events added with [`AppBuilder::add_manual_event`] are never updated automatically. They are
+/// kept until they are removed with [`Events::drain`], [`Events::consume`] or an
+/// [`EventConsumer`], e.g. by a single handler or by a system of a fixed timestep stage.
+/// [`EventReader::peek`] and [`EventReader::acknowledge`] let a reader leave some events unread
+/// until a later run.
+///
 /// [`AppBuilder::add_event`]: https://docs.rs/bevy/*/bevy/app/struct.AppBuilder.html#method.add_event
+/// [`AppBuilder::add_manual_event`]: https://docs.rs/bevy/*/bevy/app/struct.AppBuilder.html#method.add_manual_event
 #[derive(Debug)]
 pub struct Events<T> {
     events_a: Vec<EventInstance<T>>,
     events_b: Vec<EventInstance<T>>,
+    /// The indices of the events of each target in `events_a`
+    targets_a: EntityHashMap<Vec<usize>>,
+    /// The indices of the events of each target in `events_b`
+    targets_b: EntityHashMap<Vec<usize>>,
     a_start_event_count: usize,
     b_start_event_count: usize,
     event_count: usize,
@@ -136,6 +151,8 @@ impl<T> Default for Events<T> {
             event_count: 0,
             events_a: Vec::new(),
             events_b: Vec::new(),
+            targets_a: EntityHashMap::default(),
+            targets_b: EntityHashMap::default(),
             state: State::A,
         }
     }
@@ -153,6 +170,7 @@ fn map_instance_event<T>(event_instance: &EventInstance<T>) -> &T {
 #[derive(SystemParam)]
 pub struct EventReader<'a, T: Component> {
     last_event_count: Local<'a, (usize, PhantomData<T>)>,
+    target_reader: Local<'a, ManualTargetedEventReader<T>>,
     events: Res<'a, Events<T>>,
 }
 
@@ -162,11 +180,36 @@ pub struct EventWriter<'a, T: Component> {
     events: ResMut<'a, Events<T>>,
 }
 
+/// Removes events of type `T`, so that each event is handled by a single consumer. Mostly useful
+/// with events added with
+/// [`AppBuilder::add_manual_event`](https://docs.rs/bevy/*/bevy/app/struct.AppBuilder.html#method.add_manual_event).
+#[derive(SystemParam)]
+pub struct EventConsumer<'a, T: Component> {
+    events: ResMut<'a, Events<T>>,
+}
+
+impl<'a, T: Component> EventConsumer<'a, T> {
+    /// Removes and returns all the events. See [`Events::drain`].
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.events.drain()
+    }
+
+    /// Removes the events that `handler` returns `true` for. See [`Events::consume`].
+    pub fn consume(&mut self, handler: impl FnMut(&T) -> bool) {
+        self.events.consume(handler);
+    }
+}
+
 impl<'a, T: Component> EventWriter<'a, T> {
     pub fn send(&mut self, event: T) {
         self.events.send(event);
     }
 
+    /// Sends an event aimed at `target`. See [`Events::send_to`].
+    pub fn send_to(&mut self, target: Entity, event: T) {
+        self.events.send_to(target, event);
+    }
+
     pub fn send_batch(&mut self, events: impl Iterator<Item = T>) {
         self.events.extend(events);
     }
@@ -192,6 +235,19 @@ impl<T> ManualEventReader<T> {
         internal_event_reader(&mut self.last_event_count, events).map(|(e, _)| e)
     }
 
+    /// See [`EventReader::peek`]
+    pub fn peek<'a>(
+        &self,
+        events: &'a Events<T>,
+    ) -> impl DoubleEndedIterator<Item = (&'a T, EventId<T>)> {
+        unread_events(self.last_event_count, events)
+    }
+
+    /// See [`EventReader::acknowledge`]
+    pub fn acknowledge(&mut self, event_id: EventId<T>) {
+        self.last_event_count = self.last_event_count.max(event_id.id + 1);
+    }
+
     /// See [`EventReader::iter_with_id`]
     pub fn iter_with_id<'a>(
         &mut self,
@@ -201,55 +257,109 @@ impl<T> ManualEventReader<T> {
     }
 }
 
+/// Reads the events sent to each target with [`Events::send_to`], tracking separately which
+/// events of each target have already been read. See [`EventReader::read_for`].
+pub struct ManualTargetedEventReader<T> {
+    last_event_counts: EntityHashMap<usize>,
+    /// The id of the oldest event in the buffers when the counts were last pruned
+    oldest_event_count: usize,
+    _marker: PhantomData<T>,
+}
+
+impl<T> Default for ManualTargetedEventReader<T> {
+    fn default() -> Self {
+        ManualTargetedEventReader {
+            last_event_counts: EntityHashMap::default(),
+            oldest_event_count: 0,
+            _marker: Default::default(),
+        }
+    }
+}
+
+impl<T> ManualTargetedEventReader<T> {
+    /// See [`EventReader::read_for`]
+    pub fn iter_for<'a>(
+        &mut self,
+        events: &'a Events<T>,
+        target: Entity,
+    ) -> impl DoubleEndedIterator<Item = &'a T> {
+        // the counts of targets that weren't read since the oldest event left in the buffers was
+        // sent are the same as no count, as all of their events are unread
+        let oldest_event_count = events.a_start_event_count.min(events.b_start_event_count);
+        if oldest_event_count != self.oldest_event_count {
+            self.oldest_event_count = oldest_event_count;
+            self.last_event_counts
+                .retain(|_, last_event_count| *last_event_count > oldest_event_count);
+        }
+        let last_event_count = self
+            .last_event_counts
+            .insert(target, events.event_count)
+            .unwrap_or(0);
+
+        let (older, older_targets, newer, newer_targets) = match events.state {
+            State::A => (
+                &events.events_b,
+                &events.targets_b,
+                &events.events_a,
+                &events.targets_a,
+            ),
+            State::B => (
+                &events.events_a,
+                &events.targets_a,
+                &events.events_b,
+                &events.targets_b,
+            ),
+        };
+        let targeted = move |buffer: &'a Vec<EventInstance<T>>,
+                             targets: &'a EntityHashMap<Vec<usize>>| {
+            targets
+                .get(&target)
+                .into_iter()
+                .flatten()
+                .map(move |index| &buffer[*index])
+        };
+        targeted(older, older_targets)
+            .chain(targeted(newer, newer_targets))
+            .filter(move |instance| instance.event_id.id >= last_event_count)
+            .map(map_instance_event)
+    }
+}
+
 /// Like [`iter_with_id`](EventReader::iter_with_id) except not emitting any traces for read
 /// messages.
 fn internal_event_reader<'a, T>(
     last_event_count: &mut usize,
     events: &'a Events<T>,
 ) -> impl DoubleEndedIterator<Item = (&'a T, EventId<T>)> {
-    // if the reader has seen some of the events in a buffer, find the proper index offset.
-    // otherwise read all events in the buffer
-    let a_index = if *last_event_count > events.a_start_event_count {
-        *last_event_count - events.a_start_event_count
-    } else {
-        0
-    };
-    let b_index = if *last_event_count > events.b_start_event_count {
-        *last_event_count - events.b_start_event_count
-    } else {
-        0
-    };
+    let unread = unread_events(*last_event_count, events);
     *last_event_count = events.event_count;
-    match events.state {
-        State::A => events
-            .events_b
-            .get(b_index..)
-            .unwrap_or_else(|| &[])
-            .iter()
-            .map(map_instance_event_with_id)
-            .chain(
-                events
-                    .events_a
-                    .get(a_index..)
-                    .unwrap_or_else(|| &[])
-                    .iter()
-                    .map(map_instance_event_with_id),
-            ),
-        State::B => events
-            .events_a
-            .get(a_index..)
-            .unwrap_or_else(|| &[])
-            .iter()
-            .map(map_instance_event_with_id)
-            .chain(
-                events
-                    .events_b
-                    .get(b_index..)
-                    .unwrap_or_else(|| &[])
-                    .iter()
-                    .map(map_instance_event_with_id),
-            ),
-    }
+    unread
+}
+
+/// The events sent since the event `last_event_count`, without marking them as read.
+fn unread_events<'a, T>(
+    last_event_count: usize,
+    events: &'a Events<T>,
+) -> impl DoubleEndedIterator<Item = (&'a T, EventId<T>)> {
+    let (older, newer) = match events.state {
+        State::A => (&events.events_b, &events.events_a),
+        State::B => (&events.events_a, &events.events_b),
+    };
+    unread_buffer_events(older, last_event_count)
+        .chain(unread_buffer_events(newer, last_event_count))
+}
+
+fn unread_buffer_events<T>(
+    buffer: &[EventInstance<T>],
+    last_event_count: usize,
+) -> impl DoubleEndedIterator<Item = (&T, EventId<T>)> {
+    // the ids of the events of a buffer are sorted, but not contiguous once some events were
+    // consumed
+    let index =
+        match buffer.binary_search_by_key(&last_event_count, |instance| instance.event_id.id) {
+            Ok(index) | Err(index) => index,
+        };
+    buffer[index..].iter().map(map_instance_event_with_id)
 }
 
 impl<'a, T: Component> EventReader<'a, T> {
@@ -267,6 +377,27 @@ impl<'a, T: Component> EventReader<'a, T> {
             (event, id)
         })
     }
+
+    /// Iterates over the events this EventReader has not seen yet, without marking them as read.
+    /// Use [`acknowledge`](Self::acknowledge) to mark the events that were handled.
+    pub fn peek(&self) -> impl DoubleEndedIterator<Item = (&T, EventId<T>)> {
+        unread_events(self.last_event_count.0, &self.events)
+    }
+
+    /// Marks the event `event_id` and the events sent before it as read, so that the next
+    /// [`iter`](Self::iter) or [`peek`](Self::peek) starts after it.
+    pub fn acknowledge(&mut self, event_id: EventId<T>) {
+        let last_event_count = &mut self.last_event_count.0;
+        *last_event_count = (*last_event_count).max(event_id.id + 1);
+    }
+
+    /// Iterates over the events sent to `target` with [`EventWriter::send_to`] that this
+    /// EventReader has not read for that target yet, without going through the events of other
+    /// targets. Which events were read is tracked per target, and separately from
+    /// [`iter`](Self::iter), which also yields targeted events.
+    pub fn read_for(&mut self, target: Entity) -> impl DoubleEndedIterator<Item = &T> {
+        self.target_reader.iter_for(&self.events, target)
+    }
 }
 
 impl<T: Component> Events<T> {
@@ -289,6 +420,20 @@ impl<T: Component> Events<T> {
         self.event_count += 1;
     }
 
+    /// Sends an `event` aimed at `target`. It is read like any other event, and can also be read
+    /// with the other events of its target through [`EventReader::read_for`].
+    pub fn send_to(&mut self, target: Entity, event: T) {
+        let (events, targets) = match self.state {
+            State::A => (&self.events_a, &mut self.targets_a),
+            State::B => (&self.events_b, &mut self.targets_b),
+        };
+        targets
+            .entry(target)
+            .or_insert_with(Vec::new)
+            .push(events.len());
+        self.send(event);
+    }
+
     /// Gets a new [ManualEventReader]. This will include all events already in the event buffers.
     pub fn get_reader(&self) -> ManualEventReader<T> {
         ManualEventReader {
@@ -312,11 +457,13 @@ impl<T: Component> Events<T> {
         match self.state {
             State::A => {
                 self.events_b = Vec::new();
+                self.targets_b.clear();
                 self.state = State::B;
                 self.b_start_event_count = self.event_count;
             }
             State::B => {
                 self.events_a = Vec::new();
+                self.targets_a.clear();
                 self.state = State::A;
                 self.a_start_event_count = self.event_count;
             }
@@ -340,6 +487,8 @@ impl<T: Component> Events<T> {
         self.reset_start_event_count();
         self.events_a.clear();
         self.events_b.clear();
+        self.targets_a.clear();
+        self.targets_b.clear();
     }
 
     /// Returns true if there are no events in this collection.
@@ -348,9 +497,26 @@ impl<T: Component> Events<T> {
         self.events_a.is_empty() && self.events_b.is_empty()
     }
 
+    /// Passes each event to `handler`, oldest first, and removes the events it returns `true` for,
+    /// so that other readers and consumers don't see them.
+    pub fn consume(&mut self, mut handler: impl FnMut(&T) -> bool) {
+        match self.state {
+            State::A => {
+                consume_buffer_events(&mut self.events_b, &mut self.targets_b, &mut handler);
+                consume_buffer_events(&mut self.events_a, &mut self.targets_a, &mut handler);
+            }
+            State::B => {
+                consume_buffer_events(&mut self.events_a, &mut self.targets_a, &mut handler);
+                consume_buffer_events(&mut self.events_b, &mut self.targets_b, &mut handler);
+            }
+        }
+    }
+
     /// Creates a draining iterator that removes all events.
     pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
         self.reset_start_event_count();
+        self.targets_a.clear();
+        self.targets_b.clear();
 
         let map = |i: EventInstance<T>| i.event;
         match self.state {
@@ -381,6 +547,42 @@ impl<T: Component> Events<T> {
     }
 }
 
+fn consume_buffer_events<T>(
+    events: &mut Vec<EventInstance<T>>,
+    targets: &mut EntityHashMap<Vec<usize>>,
+    handler: &mut impl FnMut(&T) -> bool,
+) {
+    let consumed = events
+        .iter()
+        .map(|instance| handler(&instance.event))
+        .collect::<Vec<_>>();
+    if !consumed.contains(&true) {
+        return;
+    }
+    let mut consumed_iter = consumed.iter();
+    events.retain(|_| !*consumed_iter.next().unwrap());
+    if targets.is_empty() {
+        return;
+    }
+    // the events left move to lower indices
+    let mut new_index = 0;
+    let new_indices = consumed
+        .iter()
+        .map(|consumed| {
+            if *consumed {
+                None
+            } else {
+                new_index += 1;
+                Some(new_index - 1)
+            }
+        })
+        .collect::<Vec<_>>();
+    for indices in targets.values_mut() {
+        *indices = indices.iter().filter_map(|i| new_indices[*i]).collect();
+    }
+    targets.retain(|_, indices| !indices.is_empty());
+}
+
 impl<T> std::iter::Extend<T> for Events<T> {
     fn extend<I>(&mut self, iter: I)
     where
@@ -580,4 +782,102 @@ mod tests {
         events.update();
         assert!(events.is_empty());
     }
+
+    fn read_for(
+        events: &Events<TestEvent>,
+        reader: &mut ManualTargetedEventReader<TestEvent>,
+        target: Entity,
+    ) -> Vec<usize> {
+        reader
+            .iter_for(events, target)
+            .map(|event| event.i)
+            .collect()
+    }
+
+    #[test]
+    fn test_targeted_events() {
+        let mut events = Events::<TestEvent>::default();
+        let mut reader = events.get_reader();
+        let mut target_reader = ManualTargetedEventReader::default();
+        let (first, second) = (Entity::new(0), Entity::new(1));
+
+        events.send_to(first, TestEvent { i: 0 });
+        events.send(TestEvent { i: 1 });
+        events.send_to(second, TestEvent { i: 2 });
+        events.update();
+        events.send_to(first, TestEvent { i: 3 });
+
+        assert_eq!(read_for(&events, &mut target_reader, first), vec![0, 3]);
+        assert_eq!(read_for(&events, &mut target_reader, first), vec![]);
+        assert_eq!(read_for(&events, &mut target_reader, second), vec![2]);
+        // targeted events are also read as any other event
+        assert_eq!(reader.iter(&events).count(), 4);
+
+        events.update();
+        events.send_to(first, TestEvent { i: 4 });
+        assert_eq!(read_for(&events, &mut target_reader, first), vec![4]);
+        assert_eq!(read_for(&events, &mut target_reader, second), vec![]);
+    }
+
+    #[test]
+    fn test_events_consume() {
+        let mut events = Events::<TestEvent>::default();
+        let mut reader = events.get_reader();
+        let mut target_reader = ManualTargetedEventReader::default();
+        let target = Entity::new(0);
+
+        events.send(TestEvent { i: 0 });
+        events.send_to(target, TestEvent { i: 1 });
+        events.update();
+        events.send(TestEvent { i: 2 });
+        events.send_to(target, TestEvent { i: 3 });
+
+        let mut handled = Vec::new();
+        events.consume(|event| {
+            handled.push(event.i);
+            event.i % 2 == 0
+        });
+        assert_eq!(handled, vec![0, 1, 2, 3]);
+        assert_eq!(
+            reader
+                .iter(&events)
+                .map(|event| event.i)
+                .collect::<Vec<_>>(),
+            vec![1, 3]
+        );
+        assert_eq!(read_for(&events, &mut target_reader, target), vec![1, 3]);
+
+        events.send(TestEvent { i: 4 });
+        events.consume(|event| event.i == 3);
+        assert_eq!(
+            reader
+                .iter(&events)
+                .map(|event| event.i)
+                .collect::<Vec<_>>(),
+            vec![4]
+        );
+    }
+
+    #[test]
+    fn test_events_peek_and_acknowledge() {
+        let mut events = Events::<TestEvent>::default();
+        let mut reader = events.get_reader();
+
+        events.send(TestEvent { i: 0 });
+        events.send(TestEvent { i: 1 });
+        events.send(TestEvent { i: 2 });
+
+        let (_, first_id) = reader.peek(&events).next().unwrap();
+        assert_eq!(reader.peek(&events).count(), 3);
+        reader.acknowledge(first_id);
+        assert_eq!(
+            reader
+                .peek(&events)
+                .map(|(event, _)| event.i)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(reader.iter(&events).count(), 2);
+        assert_eq!(reader.peek(&events).count(), 0);
+    }
 }
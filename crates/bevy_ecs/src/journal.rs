@@ -0,0 +1,617 @@
+//! An undo/redo journal for [`World`] edits, based on reflection.
+//!
+//! Edits applied through the [`Journal`] record their inverse, so they can be undone and redone.
+//! Only components registered with `#[reflect(Component)]` in the
+//! [`TypeRegistryArc`] resource can be journaled. In particular, entities with components that
+//! aren't reflected can't be despawned through the journal, as undoing the despawn couldn't
+//! restore them.
+
+use crate::{
+    entity::Entity,
+    reflect::ReflectComponent,
+    system::{Command, Commands, EntityCommands},
+    world::{Mut, World},
+};
+use bevy_reflect::{GetPath, Reflect, TypeRegistry, TypeRegistryArc};
+use bevy_utils::{tracing::error, HashMap};
+use std::any::type_name;
+use thiserror::Error;
+
+/// A reversible edit of a [`World`].
+#[derive(Debug)]
+pub enum Edit {
+    /// Inserts a component, or replaces its value if the entity already has it.
+    Insert {
+        entity: Entity,
+        component: Box<dyn Reflect>,
+    },
+    /// Removes the component with the given type name.
+    Remove { entity: Entity, type_name: String },
+    /// Sets the field at `path` of the component with the given type name.
+    Set {
+        entity: Entity,
+        type_name: String,
+        path: String,
+        value: Box<dyn Reflect>,
+    },
+    /// Spawns a new entity with the given components. In later edits, `entity` refers to the
+    /// spawned entity.
+    Spawn {
+        entity: Entity,
+        components: Vec<Box<dyn Reflect>>,
+    },
+    /// Despawns an entity, saving its components. Fails if one of them isn't reflected.
+    Despawn { entity: Entity },
+    /// Several edits applied, undone and redone together. If one of them fails, those already
+    /// applied are reverted.
+    Group(Vec<Edit>),
+}
+
+#[derive(Error, Debug)]
+pub enum JournalError {
+    #[error("the world doesn't have a TypeRegistryArc resource")]
+    MissingTypeRegistry,
+    #[error("`{0}` is not registered as a reflected component")]
+    UnregisteredComponent(String),
+    #[error("entity {0:?} does not exist")]
+    NoSuchEntity(Entity),
+    #[error("entity {entity:?} doesn't have the component `{type_name}`")]
+    MissingComponent { entity: Entity, type_name: String },
+    #[error("cannot set `{path}` of component `{type_name}`: {reason}")]
+    InvalidPath {
+        type_name: String,
+        path: String,
+        reason: String,
+    },
+}
+
+fn reflect_component<'a>(
+    type_registry: &'a TypeRegistry,
+    type_name: &str,
+) -> Result<&'a ReflectComponent, JournalError> {
+    type_registry
+        .get_with_name(type_name)
+        .and_then(|registration| registration.data::<ReflectComponent>())
+        .ok_or_else(|| JournalError::UnregisteredComponent(type_name.to_string()))
+}
+
+/// Finds the current id of an entity, which changes when undoing its despawn.
+fn resolve(
+    world: &World,
+    entities: &HashMap<Entity, Entity>,
+    entity: Entity,
+) -> Result<Entity, JournalError> {
+    let resolved = entities.get(&entity).copied().unwrap_or(entity);
+    if world.get_entity(resolved).is_some() {
+        Ok(resolved)
+    } else {
+        Err(JournalError::NoSuchEntity(entity))
+    }
+}
+
+impl Edit {
+    /// Applies the edit to `world`, returning the edit that reverts it. `entities` maps the
+    /// entities of previous edits to the entities respawned when undoing a despawn.
+    ///
+    /// The world is left untouched if the edit fails.
+    fn apply(
+        &self,
+        world: &mut World,
+        type_registry: &TypeRegistry,
+        entities: &mut HashMap<Entity, Entity>,
+    ) -> Result<Edit, JournalError> {
+        Ok(match self {
+            Edit::Insert { entity, component } => {
+                let entity = *entity;
+                let resolved = resolve(world, entities, entity)?;
+                let reflect_component = reflect_component(type_registry, component.type_name())?;
+                let previous = reflect_component
+                    .reflect_component(world, resolved)
+                    .map(|previous| previous.clone_value());
+                match previous {
+                    Some(previous) => {
+                        reflect_component.apply_component(world, resolved, &**component);
+                        Edit::Insert {
+                            entity,
+                            component: previous,
+                        }
+                    }
+                    None => {
+                        reflect_component.add_component(world, resolved, &**component);
+                        Edit::Remove {
+                            entity,
+                            type_name: component.type_name().to_string(),
+                        }
+                    }
+                }
+            }
+            Edit::Remove { entity, type_name } => {
+                let entity = *entity;
+                let resolved = resolve(world, entities, entity)?;
+                let reflect_component = reflect_component(type_registry, type_name)?;
+                let previous = reflect_component
+                    .reflect_component(world, resolved)
+                    .ok_or_else(|| JournalError::MissingComponent {
+                        entity,
+                        type_name: type_name.clone(),
+                    })?
+                    .clone_value();
+                reflect_component.remove_component(world, resolved);
+                Edit::Insert {
+                    entity,
+                    component: previous,
+                }
+            }
+            Edit::Set {
+                entity,
+                type_name,
+                path,
+                value,
+            } => {
+                let entity = *entity;
+                let resolved = resolve(world, entities, entity)?;
+                let reflect_component = reflect_component(type_registry, type_name)?;
+                let mut component = match reflect_component.reflect_component_mut(world, resolved) {
+                    Some(component) => component,
+                    None => {
+                        return Err(JournalError::MissingComponent {
+                            entity,
+                            type_name: type_name.clone(),
+                        })
+                    }
+                };
+                let field = match component.path_mut(path) {
+                    Ok(field) => field,
+                    Err(err) => {
+                        return Err(JournalError::InvalidPath {
+                            reason: err.to_string(),
+                            type_name: type_name.clone(),
+                            path: path.clone(),
+                        })
+                    }
+                };
+                let previous = field.clone_value();
+                field.apply(&**value);
+                Edit::Set {
+                    entity,
+                    type_name: type_name.clone(),
+                    path: path.clone(),
+                    value: previous,
+                }
+            }
+            Edit::Spawn { entity, components } => {
+                let reflect_components = components
+                    .iter()
+                    .map(|component| reflect_component(type_registry, component.type_name()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let spawned = world.spawn().id();
+                for (component, reflect_component) in components.iter().zip(reflect_components) {
+                    reflect_component.add_component(world, spawned, &**component);
+                }
+                entities.insert(*entity, spawned);
+                Edit::Despawn { entity: *entity }
+            }
+            Edit::Despawn { entity } => {
+                let entity = *entity;
+                let resolved = resolve(world, entities, entity)?;
+                let entity_ref = world.entity(resolved);
+                let components = entity_ref
+                    .archetype()
+                    .components()
+                    .map(|component_id| {
+                        let info = world.components().get_info(component_id).unwrap();
+                        let reflect_component = info
+                            .type_id()
+                            .and_then(|type_id| type_registry.get(type_id))
+                            .and_then(|registration| registration.data::<ReflectComponent>())
+                            .ok_or_else(|| {
+                                JournalError::UnregisteredComponent(info.name().to_string())
+                            })?;
+                        Ok(reflect_component
+                            .reflect_component(world, resolved)
+                            .unwrap()
+                            .clone_value())
+                    })
+                    .collect::<Result<_, _>>()?;
+                world.despawn(resolved);
+                Edit::Spawn { entity, components }
+            }
+            Edit::Group(edits) => {
+                let mut inverses = Vec::with_capacity(edits.len());
+                for edit in edits {
+                    match edit.apply(world, type_registry, entities) {
+                        Ok(inverse) => inverses.push(inverse),
+                        Err(err) => {
+                            for inverse in inverses.iter().rev() {
+                                if let Err(err) = inverse.apply(world, type_registry, entities) {
+                                    error!("Failed to revert a partially applied edit: {}", err);
+                                }
+                            }
+                            return Err(err);
+                        }
+                    }
+                }
+                inverses.reverse();
+                Edit::Group(inverses)
+            }
+        })
+    }
+}
+
+/// A resource recording the inverse of every [`Edit`] applied through it, so they can be undone
+/// and redone.
+///
+/// Edits are usually applied with the [`JournalCommands`] and [`JournalEntityCommands`]
+/// extensions, or with [`Journal::apply`] in exclusive systems.
+#[derive(Debug, Default)]
+pub struct Journal {
+    undo: Vec<Edit>,
+    redo: Vec<Edit>,
+    entities: HashMap<Entity, Entity>,
+}
+
+impl Journal {
+    /// Applies `edit` to the world and records it, clearing the edits that could be redone.
+    /// Nothing is recorded if the edit fails.
+    pub fn apply(world: &mut World, edit: Edit) -> Result<(), JournalError> {
+        Self::scope(world, |world, journal, type_registry| {
+            let inverse = edit.apply(world, type_registry, &mut journal.entities)?;
+            journal.undo.push(inverse);
+            journal.redo.clear();
+            Ok(())
+        })
+    }
+
+    /// Reverts the last edit. Returns `false` if there was nothing to undo. If reverting fails,
+    /// the edit stays at the top of the undo history.
+    pub fn undo(world: &mut World) -> Result<bool, JournalError> {
+        Self::scope(world, |world, journal, type_registry| {
+            let edit = match journal.undo.last() {
+                Some(edit) => edit,
+                None => return Ok(false),
+            };
+            let inverse = edit.apply(world, type_registry, &mut journal.entities)?;
+            journal.undo.pop();
+            journal.redo.push(inverse);
+            Ok(true)
+        })
+    }
+
+    /// Re-applies the last undone edit. Returns `false` if there was nothing to redo. If
+    /// re-applying fails, the edit stays at the top of the redo history.
+    pub fn redo(world: &mut World) -> Result<bool, JournalError> {
+        Self::scope(world, |world, journal, type_registry| {
+            let edit = match journal.redo.last() {
+                Some(edit) => edit,
+                None => return Ok(false),
+            };
+            let inverse = edit.apply(world, type_registry, &mut journal.entities)?;
+            journal.redo.pop();
+            journal.undo.push(inverse);
+            Ok(true)
+        })
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Forgets all recorded edits.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.entities.clear();
+    }
+
+    fn scope<T>(
+        world: &mut World,
+        func: impl FnOnce(&mut World, &mut Journal, &TypeRegistry) -> Result<T, JournalError>,
+    ) -> Result<T, JournalError> {
+        let type_registry = world
+            .get_resource::<TypeRegistryArc>()
+            .ok_or(JournalError::MissingTypeRegistry)?
+            .clone();
+        let type_registry = type_registry.read();
+        world.get_resource_or_insert_with(Journal::default);
+        world.resource_scope(|world, mut journal: Mut<Journal>| {
+            func(world, &mut *journal, &type_registry)
+        })
+    }
+}
+
+/// A [`Command`] applying an [`Edit`] through the [`Journal`].
+pub struct JournalEdit(pub Edit);
+
+impl Command for JournalEdit {
+    fn write(self, world: &mut World) {
+        if let Err(err) = Journal::apply(world, self.0) {
+            error!("Failed to apply journaled edit: {}", err);
+        }
+    }
+}
+
+/// A [`Command`] undoing the last edit of the [`Journal`].
+pub struct Undo;
+
+impl Command for Undo {
+    fn write(self, world: &mut World) {
+        if let Err(err) = Journal::undo(world) {
+            error!("Failed to undo edit: {}", err);
+        }
+    }
+}
+
+/// A [`Command`] redoing the last undone edit of the [`Journal`].
+pub struct Redo;
+
+impl Command for Redo {
+    fn write(self, world: &mut World) {
+        if let Err(err) = Journal::redo(world) {
+            error!("Failed to redo edit: {}", err);
+        }
+    }
+}
+
+pub trait JournalCommands {
+    /// Applies `edit` through the [`Journal`].
+    fn journaled(&mut self, edit: Edit);
+    /// See [`Journal::undo`].
+    fn undo(&mut self);
+    /// See [`Journal::redo`].
+    fn redo(&mut self);
+}
+
+impl<'a> JournalCommands for Commands<'a> {
+    fn journaled(&mut self, edit: Edit) {
+        self.add(JournalEdit(edit));
+    }
+
+    fn undo(&mut self) {
+        self.add(Undo);
+    }
+
+    fn redo(&mut self) {
+        self.add(Redo);
+    }
+}
+
+/// Journaled versions of the [`EntityCommands`] edits, which can be undone with
+/// [`JournalCommands::undo`].
+pub trait JournalEntityCommands {
+    fn insert_journaled(&mut self, component: impl Reflect) -> &mut Self;
+    fn remove_journaled<T: Reflect>(&mut self) -> &mut Self;
+    /// Sets the field at `path` of component `T`.
+    fn set_journaled<T: Reflect>(&mut self, path: &str, value: impl Reflect) -> &mut Self;
+    fn despawn_journaled(&mut self);
+}
+
+impl<'a, 'b> JournalEntityCommands for EntityCommands<'a, 'b> {
+    fn insert_journaled(&mut self, component: impl Reflect) -> &mut Self {
+        let entity = self.id();
+        self.commands().journaled(Edit::Insert {
+            entity,
+            component: Box::new(component),
+        });
+        self
+    }
+
+    fn remove_journaled<T: Reflect>(&mut self) -> &mut Self {
+        let entity = self.id();
+        self.commands().journaled(Edit::Remove {
+            entity,
+            type_name: type_name::<T>().to_string(),
+        });
+        self
+    }
+
+    fn set_journaled<T: Reflect>(&mut self, path: &str, value: impl Reflect) -> &mut Self {
+        let entity = self.id();
+        self.commands().journaled(Edit::Set {
+            entity,
+            type_name: type_name::<T>().to_string(),
+            path: path.to_string(),
+            value: Box::new(value),
+        });
+        self
+    }
+
+    fn despawn_journaled(&mut self) {
+        let entity = self.id();
+        self.commands().journaled(Edit::Despawn { entity });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Edit, Journal, JournalCommands, JournalEntityCommands};
+    use crate as bevy_ecs;
+    use crate::{
+        reflect::ReflectComponent,
+        system::{CommandQueue, Commands},
+        world::World,
+    };
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Position {
+        x: f32,
+        y: f32,
+    }
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component)]
+    struct Name(String);
+
+    fn world() -> World {
+        let mut world = World::default();
+        let type_registry = TypeRegistryArc::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Position>();
+            type_registry.register::<Name>();
+        }
+        world.insert_resource(type_registry);
+        world
+    }
+
+    #[test]
+    fn undo_redo_commands() {
+        let mut world = world();
+        let entity = world.spawn().insert(Position { x: 1.0, y: 2.0 }).id();
+        let mut queue = CommandQueue::default();
+
+        Commands::new(&mut queue, &world)
+            .entity(entity)
+            .set_journaled::<Position>("x", 5.0f32)
+            .insert_journaled(Name("a".to_string()));
+        queue.apply(&mut world);
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 5.0);
+        assert_eq!(world.get::<Name>(entity).unwrap().0, "a");
+
+        let mut commands = Commands::new(&mut queue, &world);
+        commands.undo();
+        commands.undo();
+        queue.apply(&mut world);
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 1.0);
+        assert!(world.get::<Name>(entity).is_none());
+        assert!(!world.get_resource::<Journal>().unwrap().can_undo());
+
+        Commands::new(&mut queue, &world).redo();
+        queue.apply(&mut world);
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 5.0);
+        assert!(world.get::<Name>(entity).is_none());
+
+        // a new edit clears the redo history
+        Commands::new(&mut queue, &world)
+            .entity(entity)
+            .remove_journaled::<Position>();
+        queue.apply(&mut world);
+        assert!(world.get::<Position>(entity).is_none());
+        let journal = world.get_resource::<Journal>().unwrap();
+        assert!(journal.can_undo());
+        assert!(!journal.can_redo());
+
+        Journal::undo(&mut world).unwrap();
+        assert_eq!(
+            world.get::<Position>(entity),
+            Some(&Position { x: 5.0, y: 2.0 })
+        );
+    }
+
+    #[test]
+    fn undo_despawn() {
+        let mut world = world();
+        let entity = world
+            .spawn()
+            .insert_bundle((Position { x: 1.0, y: 2.0 }, Name("a".to_string())))
+            .id();
+
+        Journal::apply(&mut world, Edit::Despawn { entity }).unwrap();
+        assert!(world.get_entity(entity).is_none());
+
+        // the despawned entity is respawned with a new id, but later edits still find it
+        Journal::undo(&mut world).unwrap();
+        let respawned = world
+            .query::<(bevy_ecs::entity::Entity, &Name)>()
+            .iter(&world)
+            .map(|(entity, _)| entity)
+            .next()
+            .unwrap();
+        assert_eq!(
+            world.get::<Position>(respawned),
+            Some(&Position { x: 1.0, y: 2.0 })
+        );
+        assert!(Journal::redo(&mut world).unwrap());
+        assert!(world.get_entity(respawned).is_none());
+        assert!(Journal::undo(&mut world).unwrap());
+        assert!(!Journal::undo(&mut world).unwrap());
+    }
+
+    #[test]
+    fn undo_group() {
+        let mut world = world();
+        let entity = world.spawn().insert(Position::default()).id();
+        Journal::apply(
+            &mut world,
+            Edit::Group(vec![
+                Edit::Set {
+                    entity,
+                    type_name: std::any::type_name::<Position>().to_string(),
+                    path: "x".to_string(),
+                    value: Box::new(1.0f32),
+                },
+                Edit::Set {
+                    entity,
+                    type_name: std::any::type_name::<Position>().to_string(),
+                    path: "x".to_string(),
+                    value: Box::new(2.0f32),
+                },
+            ]),
+        )
+        .unwrap();
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 2.0);
+        Journal::undo(&mut world).unwrap();
+        assert_eq!(world.get::<Position>(entity).unwrap().x, 0.0);
+    }
+
+    #[test]
+    fn failed_group_is_reverted() {
+        let mut world = world();
+        let entity = world.spawn().insert(Position::default()).id();
+        let result = Journal::apply(
+            &mut world,
+            Edit::Group(vec![
+                Edit::Insert {
+                    entity,
+                    component: Box::new(Name("a".to_string())),
+                },
+                Edit::Set {
+                    entity,
+                    type_name: std::any::type_name::<Position>().to_string(),
+                    path: "z".to_string(),
+                    value: Box::new(1.0f32),
+                },
+            ]),
+        );
+        assert!(result.is_err());
+        assert!(world.get::<Name>(entity).is_none());
+        assert!(!world.get_resource::<Journal>().unwrap().can_undo());
+    }
+
+    #[test]
+    fn failed_undo_keeps_edit() {
+        let mut world = world();
+        let entity = world.spawn().insert(Position::default()).id();
+        Journal::apply(
+            &mut world,
+            Edit::Insert {
+                entity,
+                component: Box::new(Name("a".to_string())),
+            },
+        )
+        .unwrap();
+        world.entity_mut(entity).remove::<Name>();
+        assert!(Journal::undo(&mut world).is_err());
+        assert!(world.get_resource::<Journal>().unwrap().can_undo());
+
+        world.entity_mut(entity).insert(Name("b".to_string()));
+        assert!(Journal::undo(&mut world).unwrap());
+        assert!(world.get::<Name>(entity).is_none());
+    }
+
+    #[test]
+    fn despawn_refuses_unreflected_components() {
+        let mut world = world();
+        let entity = world
+            .spawn()
+            .insert_bundle((Position::default(), 1u32))
+            .id();
+        assert!(Journal::apply(&mut world, Edit::Despawn { entity }).is_err());
+        assert_eq!(world.get::<u32>(entity), Some(&1));
+    }
+}
@@ -1,9 +1,14 @@
 pub mod archetype;
+#[cfg(feature = "bench")]
+pub mod bench;
 pub mod bundle;
 pub mod change_detection;
 pub mod component;
 pub mod entity;
 pub mod event;
+#[cfg(feature = "bevy_reflect")]
+pub mod journal;
+pub mod observer;
 pub mod query;
 #[cfg(feature = "bevy_reflect")]
 pub mod reflect;
@@ -21,16 +26,22 @@ pub mod prelude {
         bundle::Bundle,
         change_detection::DetectChanges,
         entity::Entity,
-        event::{EventReader, EventWriter},
-        query::{Added, ChangeTrackers, Changed, Or, QueryState, With, WithBundle, Without},
+        event::{EventConsumer, EventReader, EventWriter},
+        observer::{ComponentTrigger, EntityEvent},
+        query::{
+            Added, BatchingStrategy, ChangeTrackers, Changed, Or, QueryState, With, WithBundle,
+            Without,
+        },
         schedule::{
             AmbiguitySetLabel, ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion,
-            RunCriteria, RunCriteriaDescriptorCoercion, RunCriteriaLabel, RunCriteriaPiping,
-            Schedule, Stage, StageLabel, State, SystemLabel, SystemSet, SystemStage,
+            RunCriteria, RunCriteriaCombinators, RunCriteriaDescriptorCoercion, RunCriteriaLabel,
+            RunCriteriaPiping, Schedule, Stage, StageLabel, State, SystemLabel, SystemSet,
+            SystemStage,
         },
         system::{
-            Commands, ConfigurableSystem, In, IntoChainSystem, IntoExclusiveSystem, IntoSystem,
-            Local, NonSend, NonSendMut, Query, QuerySet, RemovedComponents, Res, ResMut, System,
+            CommandErrorPolicy, Commands, ConfigurableSystem, EntityPlaceholder, In,
+            IntoChainSystem, IntoExclusiveSystem, IntoSystem, Local, NonSend, NonSendMut,
+            ParallelCommands, Query, QuerySet, RemovedComponents, Res, ResMut, System, SystemState,
         },
         world::{FromWorld, Mut, World},
     };
@@ -40,15 +51,18 @@ pub mod prelude {
 mod tests {
     use crate as bevy_ecs;
     use crate::{
+        archetype::ArchetypeId,
         bundle::Bundle,
         component::{Component, ComponentDescriptor, ComponentId, StorageType, TypeInfo},
         entity::Entity,
         query::{
-            Added, ChangeTrackers, Changed, FilterFetch, FilteredAccess, With, Without, WorldQuery,
+            Added, BatchingStrategy, ChangeTrackers, Changed, FilterFetch, FilteredAccess, With,
+            Without, WorldQuery,
         },
-        world::{Mut, World},
+        world::{insert_or_spawn_bundles, BundleBatch, Mut, World},
     };
     use bevy_tasks::TaskPool;
+    use bevy_utils::Duration;
     use parking_lot::Mutex;
     use std::{
         any::TypeId,
@@ -318,6 +332,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn par_for_each_batched() {
+        let mut world = World::new();
+        let task_pool = TaskPool::default();
+        world.spawn_batch((0..1000).map(|i| (i,)));
+        world.spawn_batch((1000..1100).map(|i| (i, true)));
+        let mut query = world.query::<&i32>();
+        let strategies = [
+            BatchingStrategy::default(),
+            BatchingStrategy::Fixed(7),
+            BatchingStrategy::Adaptive {
+                batch_time: Duration::from_micros(10),
+            },
+        ];
+        for &batching in strategies.iter() {
+            // twice, for the adaptive strategy to use the time of the first run
+            for _ in 0..2 {
+                let sum = AtomicUsize::new(0);
+                query.par_for_each_batched(&world, &task_pool, batching, |&i| {
+                    sum.fetch_add(i as usize, Ordering::Relaxed);
+                });
+                assert_eq!(sum.into_inner(), (0..1100).sum::<usize>());
+            }
+        }
+    }
+
     #[test]
     fn query_missing_component() {
         let mut world = World::new();
@@ -569,6 +609,93 @@ mod tests {
         assert_eq!(values, expected);
     }
 
+    #[test]
+    fn insert_or_spawn_batch() {
+        let mut world = World::new();
+        let existing = world.spawn().insert(B(0)).id();
+        let despawned = world.spawn().id();
+        world.despawn(despawned);
+        let reused = world.spawn().id();
+        let new = Entity::new(10);
+
+        let result = world.insert_or_spawn_batch(vec![
+            (existing, (A(1),)),
+            (new, (A(2),)),
+            (despawned, (A(3),)),
+        ]);
+        assert_eq!(result, Err(vec![despawned]));
+        assert_eq!(world.get::<A>(existing), Some(&A(1)));
+        assert!(world.get::<B>(existing).is_some());
+        assert_eq!(world.get::<A>(new), Some(&A(2)));
+        assert_eq!(world.get::<A>(reused), None);
+        // the ids skipped over are still free
+        assert_eq!(world.entities().len(), 3);
+        world.spawn_batch((0..8).map(|_| (C,))).for_each(drop);
+        assert_eq!(world.entities().len(), 11);
+    }
+
+    #[test]
+    fn bundle_batch() {
+        let mut world = World::new();
+        let mut batch = BundleBatch::default();
+        let a = batch.spawn(&world, (A(0),));
+        let ab = batch.spawn(&world, (A(1), B(1)));
+        batch.spawn(&world, (A(2),));
+        batch.insert(a, (C,));
+        assert_eq!(batch.len(), 4);
+        batch.write(&mut world).unwrap();
+
+        assert_eq!(world.get::<A>(ab), Some(&A(1)));
+        assert!(world.get::<B>(ab).is_some());
+        assert!(world.get::<C>(a).is_some());
+        let mut values = world
+            .query::<&A>()
+            .iter(&world)
+            .map(|a| a.0)
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 1, 2]);
+    }
+
+    #[test]
+    fn insert_or_spawn_reserved_entities() {
+        let mut world = World::new();
+        let existing = world.spawn().id();
+        let reserved = (0..3)
+            .map(|_| world.entities().reserve_entity())
+            .collect::<Vec<_>>();
+        let unused = world.entities().reserve_entity();
+
+        let mut invalid_entities = Vec::new();
+        let bundles = reserved
+            .iter()
+            .enumerate()
+            .map(|(i, entity)| (*entity, (A(i),)))
+            .chain(vec![
+                (existing, (A(3),)),
+                (Entity::new(8), (A(4),)),
+                // an id skipped over by the previous entity
+                (Entity::new(7), (A(5),)),
+            ]);
+        let spawned = insert_or_spawn_bundles(&mut world, bundles, &mut invalid_entities);
+
+        // the reserved entities aren't flushed to the empty archetype first
+        assert_eq!(spawned, 5);
+        assert!(invalid_entities.is_empty());
+        for (i, entity) in reserved.iter().enumerate() {
+            assert_eq!(world.get::<A>(*entity), Some(&A(i)));
+        }
+        assert_eq!(world.get::<A>(existing), Some(&A(3)));
+        assert!(world.get_entity(unused).is_some());
+        assert_eq!(world.archetypes()[ArchetypeId::empty()].len(), 1);
+        assert_eq!(world.get::<A>(Entity::new(7)), Some(&A(5)));
+        assert_eq!(world.entities().len(), 7);
+        // the ids skipped over that weren't spawned are still free
+        assert_eq!(world.spawn().id().id(), 6);
+        assert_eq!(world.spawn().id().id(), 5);
+        assert_eq!(world.spawn().id().id(), 9);
+    }
+
     #[test]
     fn query_get() {
         let mut world = World::new();
@@ -1198,6 +1325,23 @@ mod tests {
         assert_eq!(*world.get_resource::<i32>().unwrap(), 1);
     }
 
+    #[test]
+    fn resource_override() {
+        let mut world = World::default();
+        world.insert_resource::<i32>(0);
+        let value = world.with_resource_override::<i32, _>(5, |world| {
+            *world.get_resource_mut::<i32>().unwrap() += 1;
+            *world.get_resource::<i32>().unwrap()
+        });
+        assert_eq!(value, 6);
+        assert_eq!(*world.get_resource::<i32>().unwrap(), 0);
+
+        world.with_resource_override(1u64, |world| {
+            assert_eq!(*world.get_resource::<u64>().unwrap(), 1);
+        });
+        assert!(!world.contains_resource::<u64>());
+    }
+
     #[test]
     fn insert_overwrite_drop() {
         let (dropck1, dropped1) = DropCk::new_pair();
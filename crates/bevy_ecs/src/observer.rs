@@ -0,0 +1,530 @@
+use crate::{
+    archetype::ArchetypeGeneration,
+    component::{check_tick, Component},
+    entity::{Entity, EntityHashMap},
+    event::{Events, ManualEventReader},
+    query::{Changed, QueryState},
+    system::{BoxedSystem, Command, CommandQueue, Commands, IntoSystem},
+    world::{Mut, World},
+};
+use std::{any::TypeId, marker::PhantomData};
+
+/// An event aimed at a single entity, such as damage or an interaction, that
+/// [`EntityCommands::observe`](crate::system::EntityCommands::observe) callbacks can react to.
+pub trait EntityEvent: Component {
+    /// The entity the event is aimed at
+    fn target(&self) -> Entity;
+}
+
+type Observer<E> = Box<dyn Fn(&E, &mut Commands) + Send + Sync>;
+
+/// The callbacks observing the [`EntityEvent`]s of type `E`, by target entity.
+///
+/// [`dispatch_entity_events_system`] runs the callbacks of the target of each event, instead of
+/// every system filtering every event by entity. Callbacks mutate the world through
+/// [`Commands`], which are applied once all the events have been dispatched. The callbacks of
+/// despawned entities are removed.
+pub struct Observers<E> {
+    observers: EntityHashMap<Vec<Observer<E>>>,
+    reader: ManualEventReader<E>,
+}
+
+impl<E> Default for Observers<E> {
+    fn default() -> Self {
+        Observers {
+            observers: Default::default(),
+            reader: Default::default(),
+        }
+    }
+}
+
+impl<E: EntityEvent> Observers<E> {
+    /// Runs `observer` for every event aimed at `entity`.
+    pub fn insert(
+        &mut self,
+        entity: Entity,
+        observer: impl Fn(&E, &mut Commands) + Send + Sync + 'static,
+    ) {
+        self.observers
+            .entry(entity)
+            .or_insert_with(Vec::new)
+            .push(Box::new(observer));
+    }
+
+    /// Removes the callbacks observing `entity`.
+    pub fn remove(&mut self, entity: Entity) {
+        self.observers.remove(&entity);
+    }
+
+    /// Whether any callback observes `entity`
+    pub fn contains(&self, entity: Entity) -> bool {
+        self.observers.contains_key(&entity)
+    }
+}
+
+/// Runs the [`Observers`] of the [`EntityEvent`]s sent since it last ran.
+pub fn dispatch_entity_events_system<E: EntityEvent>(world: &mut World) {
+    if !world.contains_resource::<Observers<E>>() {
+        return;
+    }
+    world.resource_scope(|world, mut observers: Mut<Observers<E>>| {
+        let observers = &mut *observers;
+        let entities = world.entities();
+        observers
+            .observers
+            .retain(|entity, _| entities.contains(*entity));
+
+        let events = match world.get_resource::<Events<E>>() {
+            Some(events) => events,
+            None => return,
+        };
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, world);
+        for event in observers.reader.iter(events) {
+            if let Some(callbacks) = observers.observers.get(&event.target()) {
+                for callback in callbacks {
+                    callback(event, &mut commands);
+                }
+            }
+        }
+        queue.apply(world);
+    });
+}
+
+pub struct Observe<E> {
+    pub entity: Entity,
+    pub observer: Observer<E>,
+}
+
+impl<E: EntityEvent> Command for Observe<E> {
+    fn write(self, world: &mut World) {
+        world
+            .get_resource_or_insert_with(Observers::<E>::default)
+            .observers
+            .entry(self.entity)
+            .or_insert_with(Vec::new)
+            .push(self.observer);
+    }
+}
+
+/// What makes the systems of [`ComponentObservers`] run for an entity.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ComponentTrigger {
+    /// The component was added to the entity, or the entity was spawned with it
+    Added,
+    /// The component was added or mutated, like with the [`Changed`] filter.
+    ///
+    /// Unlike the other triggers, changes are polled when the observers are dispatched, so an
+    /// entity whose component changed several times since the last dispatch triggers once.
+    Changed,
+    /// The component was removed from the entity, or the entity was despawned
+    Removed,
+}
+
+struct ObserverSystem {
+    trigger: ComponentTrigger,
+    system: BoxedSystem<Entity, ()>,
+    archetype_generation: ArchetypeGeneration,
+    initialized: bool,
+}
+
+impl ObserverSystem {
+    fn run(&mut self, entity: Entity, world: &mut World) {
+        if !self.initialized {
+            self.system.initialize(world);
+            self.initialized = true;
+        }
+        let archetypes = world.archetypes();
+        let new_generation = archetypes.generation();
+        let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
+        let archetype_index_range = old_generation.value()..new_generation.value();
+        for archetype in archetypes.archetypes[archetype_index_range].iter() {
+            self.system.new_archetype(archetype);
+        }
+
+        self.system.run(entity, world);
+        self.system.apply_buffers(world);
+    }
+}
+
+/// The systems that react to the component `T` being added, changed or removed on any entity,
+/// instead of polling with [`Changed`] filters.
+///
+/// [`init_component_observers`] registers the component hooks that record the entities on which
+/// `T` is added or removed. [`dispatch_component_observers_system`] then runs each system once for
+/// every entity that triggered it since the last dispatch, with the entity as
+/// [`In`](crate::system::In) input. The commands of the systems are applied after each run.
+///
+/// ```
+/// # use bevy_ecs::observer::{
+/// #     dispatch_component_observers_system, init_component_observers, ComponentObservers,
+/// # };
+/// # use bevy_ecs::prelude::*;
+/// struct Item(&'static str);
+/// #[derive(Default)]
+/// struct Log(Vec<&'static str>);
+///
+/// fn log_pickup(In(entity): In<Entity>, items: Query<&Item>, mut log: ResMut<Log>) {
+///     log.0.push(items.get(entity).unwrap().0);
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Log::default());
+/// init_component_observers::<Item>(&mut world);
+/// let mut observers = world.get_resource_mut::<ComponentObservers<Item>>().unwrap();
+/// observers.insert(ComponentTrigger::Added, log_pickup.system());
+///
+/// world.spawn().insert(Item("sword"));
+/// dispatch_component_observers_system::<Item>(&mut world);
+/// assert_eq!(world.get_resource::<Log>().unwrap().0, vec!["sword"]);
+/// ```
+pub struct ComponentObservers<T: Component> {
+    observers: Vec<ObserverSystem>,
+    changed: Option<QueryState<Entity, Changed<T>>>,
+    last_change_tick: u32,
+}
+
+impl<T: Component> Default for ComponentObservers<T> {
+    fn default() -> Self {
+        ComponentObservers {
+            observers: Vec::new(),
+            changed: None,
+            last_change_tick: 0,
+        }
+    }
+}
+
+impl<T: Component> ComponentObservers<T> {
+    /// Runs `system` for every entity on which `T` is added, changed or removed, as per
+    /// `trigger`.
+    pub fn insert<Params>(
+        &mut self,
+        trigger: ComponentTrigger,
+        system: impl IntoSystem<Entity, (), Params>,
+    ) {
+        self.observers.push(ObserverSystem {
+            trigger,
+            system: Box::new(system.system()),
+            archetype_generation: ArchetypeGeneration::initial(),
+            initialized: false,
+        });
+    }
+
+    fn check_change_ticks(&mut self, change_tick: u32) {
+        check_tick(&mut self.last_change_tick, change_tick);
+        for observer in self.observers.iter_mut() {
+            observer.system.check_change_tick(change_tick);
+        }
+    }
+
+    fn has_trigger(&self, trigger: ComponentTrigger) -> bool {
+        self.observers
+            .iter()
+            .any(|observer| observer.trigger == trigger)
+    }
+}
+
+/// The entities on which `T` was added or removed since the last dispatch, in order, recorded
+/// by the component hooks of [`init_component_observers`].
+struct ComponentTriggers<T> {
+    added: Vec<Entity>,
+    removed: Vec<Entity>,
+    marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Default for ComponentTriggers<T> {
+    fn default() -> Self {
+        ComponentTriggers {
+            added: Vec::new(),
+            removed: Vec::new(),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Inserts the [`ComponentObservers`] of `T`, and registers the component hooks recording the
+/// entities on which `T` is added or removed, if not done yet.
+///
+/// Additions and removals are only observed from then on.
+pub fn init_component_observers<T: Component>(world: &mut World) {
+    if world.contains_resource::<ComponentTriggers<T>>() {
+        return;
+    }
+    world.insert_resource(ComponentTriggers::<T>::default());
+    if !world.contains_resource::<ComponentObservers<T>>() {
+        world.insert_resource(ComponentObservers::<T>::default());
+    }
+    world
+        .on_add(|world, entity, _: &T| {
+            if let Some(mut triggers) = world.get_resource_mut::<ComponentTriggers<T>>() {
+                triggers.added.push(entity);
+            }
+        })
+        .on_remove(|world, entity, _: &T| {
+            if let Some(mut triggers) = world.get_resource_mut::<ComponentTriggers<T>>() {
+                triggers.removed.push(entity);
+            }
+        });
+}
+
+fn check_component_observers_ticks<T: Component>(world: &mut World, change_tick: u32) {
+    if let Some(observers) = world.get_resource_mut::<ComponentObservers<T>>() {
+        // clamping the ticks doesn't count as a change of the resource
+        observers.value.check_change_ticks(change_tick);
+    }
+}
+
+/// Runs the [`ComponentObservers`] of `T` for the entities on which `T` was added, changed or
+/// removed since it last ran.
+///
+/// Additions and removals are recorded as they happen by the hooks of
+/// [`init_component_observers`], which this calls if needed. Changes are polled with a
+/// [`Changed`] query, so they are batched per dispatch: in an app, once per frame at the end of
+/// `CoreStage::PostUpdate`.
+///
+/// The change ticks of the observer systems are then clamped by
+/// [`World::check_change_ticks`], like those of the systems of a stage.
+pub fn dispatch_component_observers_system<T: Component>(world: &mut World) {
+    let resource_id = match world
+        .components
+        .get_resource_id(TypeId::of::<ComponentObservers<T>>())
+    {
+        Some(resource_id) if world.contains_resource::<ComponentObservers<T>>() => resource_id,
+        _ => return,
+    };
+    init_component_observers::<T>(world);
+    if !world.resource_tick_checks.contains(resource_id) {
+        world
+            .resource_tick_checks
+            .insert(resource_id, check_component_observers_ticks::<T>);
+    }
+    let (added, removed) = {
+        let mut triggers = world.get_resource_mut::<ComponentTriggers<T>>().unwrap();
+        (
+            std::mem::take(&mut triggers.added),
+            std::mem::take(&mut triggers.removed),
+        )
+    };
+    world.resource_scope(|world, mut observers: Mut<ComponentObservers<T>>| {
+        let observers = &mut *observers;
+        let change_tick = world.increment_change_tick();
+        let last_change_tick = std::mem::replace(&mut observers.last_change_tick, change_tick);
+
+        let changed = if observers.has_trigger(ComponentTrigger::Changed) {
+            let state = observers
+                .changed
+                .get_or_insert_with(|| QueryState::new(world));
+            state.validate_world_and_update_archetypes(world);
+            // SAFE: the query only reads entities, and the world is borrowed mutably
+            unsafe {
+                state
+                    .iter_unchecked_manual(world, last_change_tick, change_tick)
+                    .collect()
+            }
+        } else {
+            Vec::new()
+        };
+
+        for observer in observers.observers.iter_mut() {
+            let entities = match observer.trigger {
+                ComponentTrigger::Added => &added,
+                ComponentTrigger::Changed => &changed,
+                ComponentTrigger::Removed => &removed,
+            };
+            for &entity in entities.iter() {
+                // an observer that ran before may have removed the component
+                if observer.trigger == ComponentTrigger::Removed || world.get::<T>(entity).is_some()
+                {
+                    observer.run(entity, world);
+                }
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        dispatch_component_observers_system, dispatch_entity_events_system,
+        init_component_observers, ComponentObservers, ComponentTrigger, EntityEvent, Observers,
+    };
+    use crate::{
+        component::{CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE},
+        entity::Entity,
+        event::Events,
+        query::Changed,
+        system::{CommandQueue, Commands, In, IntoSystem, Query, ResMut},
+        world::World,
+    };
+
+    struct Damage {
+        target: Entity,
+        amount: u32,
+    }
+
+    impl EntityEvent for Damage {
+        fn target(&self) -> Entity {
+            self.target
+        }
+    }
+
+    struct Health(u32);
+
+    #[test]
+    fn dispatch_to_target() {
+        let mut world = World::default();
+        world.insert_resource(Events::<Damage>::default());
+        let observed = world.spawn().insert(Health(10)).id();
+        let other = world.spawn().insert(Health(10)).id();
+
+        let mut queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut queue, &world);
+        commands
+            .entity(observed)
+            .observe(|damage: &Damage, commands: &mut Commands| {
+                let health = Health(10 - damage.amount);
+                commands.entity(damage.target).insert(health);
+            });
+        queue.apply(&mut world);
+
+        let mut events = world.get_resource_mut::<Events<Damage>>().unwrap();
+        events.send(Damage {
+            target: observed,
+            amount: 3,
+        });
+        events.send(Damage {
+            target: other,
+            amount: 5,
+        });
+        dispatch_entity_events_system::<Damage>(&mut world);
+        assert_eq!(world.get::<Health>(observed).unwrap().0, 7);
+        assert_eq!(world.get::<Health>(other).unwrap().0, 10);
+
+        // events are only dispatched once
+        dispatch_entity_events_system::<Damage>(&mut world);
+        assert_eq!(world.get::<Health>(observed).unwrap().0, 7);
+
+        world.despawn(observed);
+        dispatch_entity_events_system::<Damage>(&mut world);
+        assert!(!world
+            .get_resource::<Observers<Damage>>()
+            .unwrap()
+            .contains(observed));
+    }
+
+    #[test]
+    fn component_observers() {
+        #[derive(Default)]
+        struct Triggered(Vec<(ComponentTrigger, Entity)>);
+
+        fn on_added(In(entity): In<Entity>, mut triggered: ResMut<Triggered>) {
+            triggered.0.push((ComponentTrigger::Added, entity));
+        }
+        fn on_changed(In(entity): In<Entity>, mut triggered: ResMut<Triggered>) {
+            triggered.0.push((ComponentTrigger::Changed, entity));
+        }
+        fn on_removed(In(entity): In<Entity>, mut triggered: ResMut<Triggered>) {
+            triggered.0.push((ComponentTrigger::Removed, entity));
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Triggered::default());
+        init_component_observers::<Health>(&mut world);
+        let mut observers = world
+            .get_resource_mut::<ComponentObservers<Health>>()
+            .unwrap();
+        observers.insert(ComponentTrigger::Added, on_added.system());
+        observers.insert(ComponentTrigger::Changed, on_changed.system());
+        observers.insert(ComponentTrigger::Removed, on_removed.system());
+        let dispatch = |world: &mut World| {
+            dispatch_component_observers_system::<Health>(world);
+            std::mem::take(&mut world.get_resource_mut::<Triggered>().unwrap().0)
+        };
+
+        let entity = world.spawn().insert(Health(10)).id();
+        assert_eq!(
+            dispatch(&mut world),
+            vec![
+                (ComponentTrigger::Added, entity),
+                (ComponentTrigger::Changed, entity)
+            ]
+        );
+        assert!(dispatch(&mut world).is_empty());
+
+        world.get_mut::<Health>(entity).unwrap().0 = 5;
+        assert_eq!(
+            dispatch(&mut world),
+            vec![(ComponentTrigger::Changed, entity)]
+        );
+
+        // removals are recorded by hooks, so they are still observed after the trackers are
+        // cleared
+        world.entity_mut(entity).remove::<Health>();
+        world.clear_trackers();
+        world.clear_trackers();
+        assert_eq!(
+            dispatch(&mut world),
+            vec![(ComponentTrigger::Removed, entity)]
+        );
+        assert!(dispatch(&mut world).is_empty());
+
+        // every addition and removal between two dispatches triggers, but changes are batched
+        world.entity_mut(entity).insert(Health(1));
+        world.entity_mut(entity).remove::<Health>();
+        world.entity_mut(entity).insert(Health(2));
+        world.get_mut::<Health>(entity).unwrap().0 = 3;
+        let other = world.spawn().insert(Health(10)).id();
+        world.despawn(other);
+        assert_eq!(
+            dispatch(&mut world),
+            vec![
+                (ComponentTrigger::Added, entity),
+                (ComponentTrigger::Added, entity),
+                (ComponentTrigger::Changed, entity),
+                (ComponentTrigger::Removed, entity),
+                (ComponentTrigger::Removed, other)
+            ]
+        );
+    }
+
+    #[test]
+    fn component_observers_change_ticks_wrapover() {
+        #[derive(Default)]
+        struct Changes(Vec<Vec<Entity>>);
+
+        fn on_added(
+            In(_): In<Entity>,
+            changed: Query<Entity, Changed<Health>>,
+            mut changes: ResMut<Changes>,
+        ) {
+            changes.0.push(changed.iter().collect());
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Changes::default());
+        init_component_observers::<Health>(&mut world);
+        world
+            .get_resource_mut::<ComponentObservers<Health>>()
+            .unwrap()
+            .insert(ComponentTrigger::Added, on_added.system());
+
+        let old = world.spawn().insert(Health(10)).id();
+        dispatch_component_observers_system::<Health>(&mut world);
+
+        // Overflow twice
+        for _ in 0..20 {
+            let change_tick = world.change_tick.get_mut();
+            *change_tick = change_tick.wrapping_add(CHECK_TICK_THRESHOLD + 1);
+            world.check_change_ticks();
+            let change_tick = world.change_tick();
+            let observers = world.get_resource::<ComponentObservers<Health>>().unwrap();
+            let age = change_tick.wrapping_sub(observers.last_change_tick);
+            assert!(age <= MAX_CHANGE_AGE);
+        }
+
+        let new = world.spawn().insert(Health(10)).id();
+        dispatch_component_observers_system::<Health>(&mut world);
+        let changes = world.get_resource::<Changes>().unwrap();
+        assert_eq!(changes.0, vec![vec![old], vec![new]]);
+    }
+}
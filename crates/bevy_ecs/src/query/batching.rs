@@ -0,0 +1,119 @@
+use bevy_utils::Duration;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// How [`Query::par_for_each`](crate::system::Query::par_for_each) splits the query results into
+/// batches, each batch being processed by a task.
+///
+/// Smaller batches balance the work better between threads, but each batch adds the overhead of
+/// a task. A `usize` converts to [`BatchingStrategy::Fixed`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchingStrategy {
+    /// Batches of a fixed number of results.
+    Fixed(usize),
+    /// Splits the results into `batches_per_thread` batches for each thread of the task pool,
+    /// with at least `min_batch_size` results per batch.
+    PerThread {
+        batches_per_thread: usize,
+        min_batch_size: usize,
+    },
+    /// Sizes the batches from the time the previous run of the query took per result, so that a
+    /// batch takes about `batch_time` to process. The first run uses the default strategy.
+    Adaptive { batch_time: Duration },
+}
+
+impl Default for BatchingStrategy {
+    fn default() -> Self {
+        BatchingStrategy::PerThread {
+            batches_per_thread: 4,
+            min_batch_size: 16,
+        }
+    }
+}
+
+impl From<usize> for BatchingStrategy {
+    fn from(batch_size: usize) -> Self {
+        BatchingStrategy::Fixed(batch_size)
+    }
+}
+
+impl BatchingStrategy {
+    /// Returns the size of the batches to process `count` results with `threads` threads.
+    /// `item_time` is the time the last run took per result, if known.
+    pub fn batch_size(&self, count: usize, threads: usize, item_time: Option<Duration>) -> usize {
+        let batch_size = match *self {
+            BatchingStrategy::Fixed(batch_size) => batch_size,
+            BatchingStrategy::PerThread {
+                batches_per_thread,
+                min_batch_size,
+            } => {
+                let batches = threads.max(1) * batches_per_thread.max(1);
+                ((count + batches - 1) / batches).max(min_batch_size)
+            }
+            BatchingStrategy::Adaptive { batch_time } => match item_time {
+                Some(item_time) if item_time.as_nanos() > 0 => {
+                    (batch_time.as_nanos() / item_time.as_nanos()).min(count as u128) as usize
+                }
+                _ => BatchingStrategy::default().batch_size(count, threads, None),
+            },
+        };
+        batch_size.max(1)
+    }
+}
+
+/// The time a parallel iteration took per result, kept by a query state for
+/// [`BatchingStrategy::Adaptive`].
+#[derive(Debug, Default)]
+pub(crate) struct ItemTime(AtomicU64);
+
+impl ItemTime {
+    pub(crate) fn get(&self) -> Option<Duration> {
+        match self.0.load(Ordering::Relaxed) {
+            0 => None,
+            nanos => Some(Duration::from_nanos(nanos)),
+        }
+    }
+
+    /// Records a run that processed `count` results with `threads` threads in `elapsed`.
+    pub(crate) fn record(&self, elapsed: Duration, count: usize, threads: usize) {
+        if count == 0 {
+            return;
+        }
+        let nanos = elapsed.as_nanos() * threads.max(1) as u128 / count as u128;
+        self.0.store(nanos.max(1) as u64, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BatchingStrategy;
+    use bevy_utils::Duration;
+
+    #[test]
+    fn batch_sizes() {
+        assert_eq!(BatchingStrategy::Fixed(8).batch_size(100, 4, None), 8);
+        assert_eq!(BatchingStrategy::Fixed(0).batch_size(100, 4, None), 1);
+
+        let per_thread = BatchingStrategy::PerThread {
+            batches_per_thread: 2,
+            min_batch_size: 10,
+        };
+        assert_eq!(per_thread.batch_size(1000, 4, None), 125);
+        assert_eq!(per_thread.batch_size(20, 4, None), 10);
+
+        let adaptive = BatchingStrategy::Adaptive {
+            batch_time: Duration::from_micros(100),
+        };
+        assert_eq!(
+            adaptive.batch_size(10_000, 4, Some(Duration::from_micros(1))),
+            100
+        );
+        assert_eq!(
+            adaptive.batch_size(50, 4, Some(Duration::from_micros(1))),
+            50
+        );
+        assert_eq!(
+            adaptive.batch_size(10_000, 4, None),
+            BatchingStrategy::default().batch_size(10_000, 4, None)
+        );
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{
+    archetype::{ArchetypeGeneration, ArchetypeId},
+    component::ComponentId,
+    entity::{Entity, EntityLocation},
+    world::{get_component, get_component_and_ticks, World, WorldId},
+};
+
+/// A query for the entities that have all of a list of components, chosen at runtime. Unlike
+/// [`QueryState`](super::QueryState), it also works with components without a Rust type, see
+/// [`World::register_component_with_layout`].
+///
+/// The components of an entity are returned as pointers, in the order of the ids given to
+/// [`DynamicQuery::new`].
+///
+/// ```
+/// use bevy_ecs::{query::DynamicQuery, world::World};
+///
+/// let mut world = World::new();
+/// world.spawn().insert_bundle((1u32, 2u64));
+/// world.spawn().insert(3u32);
+/// let a = world.components().get_id(std::any::TypeId::of::<u32>()).unwrap();
+/// let b = world.components().get_id(std::any::TypeId::of::<u64>()).unwrap();
+///
+/// let mut query = DynamicQuery::new(&world, vec![a, b]);
+/// let values = query
+///     .iter(&world)
+///     .map(|(_, components)| unsafe { *components[1].cast::<u64>() })
+///     .collect::<Vec<_>>();
+/// assert_eq!(values, vec![2]);
+/// ```
+pub struct DynamicQuery {
+    world_id: WorldId,
+    component_ids: Vec<ComponentId>,
+    archetype_generation: ArchetypeGeneration,
+    matched_archetype_ids: Vec<ArchetypeId>,
+}
+
+impl DynamicQuery {
+    pub fn new(world: &World, component_ids: Vec<ComponentId>) -> Self {
+        let mut query = DynamicQuery {
+            world_id: world.id(),
+            component_ids,
+            archetype_generation: ArchetypeGeneration::initial(),
+            matched_archetype_ids: Vec::new(),
+        };
+        query.validate_world_and_update_archetypes(world);
+        query
+    }
+
+    #[inline]
+    pub fn component_ids(&self) -> &[ComponentId] {
+        &self.component_ids
+    }
+
+    pub fn validate_world_and_update_archetypes(&mut self, world: &World) {
+        if world.id() != self.world_id {
+            panic!("Attempted to use a DynamicQuery with a mismatched World. DynamicQueries can only be used with the World they were created from.");
+        }
+        let archetypes = world.archetypes();
+        let new_generation = archetypes.generation();
+        let old_generation = std::mem::replace(&mut self.archetype_generation, new_generation);
+        for archetype_index in old_generation.value()..new_generation.value() {
+            let archetype = &archetypes[ArchetypeId::new(archetype_index)];
+            if self.component_ids.iter().all(|id| archetype.contains(*id)) {
+                self.matched_archetype_ids.push(archetype.id());
+            }
+        }
+    }
+
+    /// Returns the matching entities, with pointers to their components.
+    pub fn iter<'w>(
+        &'w mut self,
+        world: &'w World,
+    ) -> impl Iterator<Item = (Entity, Vec<*const u8>)> + 'w {
+        self.validate_world_and_update_archetypes(world);
+        let component_ids = &self.component_ids;
+        self.locations(world).map(move |(entity, location)| {
+            let components = component_ids
+                .iter()
+                // SAFE: the location is valid, and the archetype has all of the components
+                .map(|id| unsafe {
+                    get_component(world, *id, entity, location).unwrap() as *const u8
+                })
+                .collect();
+            (entity, components)
+        })
+    }
+
+    /// Returns the matching entities, with mutable pointers to their components. The components
+    /// are marked as changed.
+    pub fn iter_mut<'w>(
+        &'w mut self,
+        world: &'w mut World,
+    ) -> impl Iterator<Item = (Entity, Vec<*mut u8>)> + 'w {
+        self.validate_world_and_update_archetypes(world);
+        let change_tick = world.change_tick();
+        let world = &*world;
+        let component_ids = &self.component_ids;
+        self.locations(world).map(move |(entity, location)| {
+            let components = component_ids
+                .iter()
+                // SAFE: world access is unique, the location is valid, and the archetype has all
+                // of the components
+                .map(|id| unsafe {
+                    let (value, ticks) =
+                        get_component_and_ticks(world, *id, entity, location).unwrap();
+                    (*ticks).set_changed(change_tick);
+                    value
+                })
+                .collect();
+            (entity, components)
+        })
+    }
+
+    fn locations<'w>(
+        &'w self,
+        world: &'w World,
+    ) -> impl Iterator<Item = (Entity, EntityLocation)> + 'w {
+        self.matched_archetype_ids
+            .iter()
+            .flat_map(move |archetype_id| {
+                world.archetypes()[*archetype_id]
+                    .entities()
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, entity)| {
+                        (
+                            *entity,
+                            EntityLocation {
+                                archetype_id: *archetype_id,
+                                index,
+                            },
+                        )
+                    })
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{query::DynamicQuery, world::World};
+    use std::{
+        alloc::Layout,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe fn drop_value(value: *mut u8) {
+        DROPPED.fetch_add(*value.cast::<u32>() as usize, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn dynamic_components() {
+        let mut world = World::new();
+        let (a, b) = unsafe {
+            (
+                world.register_component_with_layout("A", Layout::new::<u32>(), drop_value),
+                world.register_component_with_layout("B", Layout::new::<u32>(), drop_value),
+            )
+        };
+        let mut values = vec![1u32, 2, 10, 20];
+        let mut insert = |world: &mut World, id, index: usize| {
+            let value = (&mut values[index] as *mut u32).cast::<u8>();
+            let entity = world.spawn().id();
+            unsafe { world.entity_mut(entity).insert_by_id(id, value) };
+            entity
+        };
+        let e1 = insert(&mut world, a, 0);
+        let e2 = insert(&mut world, a, 1);
+        unsafe {
+            world
+                .entity_mut(e2)
+                .insert_by_id(b, (&mut values[2] as *mut u32).cast());
+            world
+                .entity_mut(e2)
+                .insert_by_id(b, (&mut values[3] as *mut u32).cast());
+        }
+        // the replaced value of B was dropped
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 10);
+
+        let read = |ptr: *const u8| unsafe { *ptr.cast::<u32>() };
+        assert_eq!(world.entity(e1).get_by_id(a).map(read), Some(1));
+        assert_eq!(world.entity(e1).get_by_id(b), None);
+        assert_eq!(world.entity(e2).get_by_id(b).map(read), Some(20));
+
+        let mut query = DynamicQuery::new(&world, vec![b, a]);
+        let results = query
+            .iter(&world)
+            .map(|(entity, components)| (entity, read(components[0]), read(components[1])))
+            .collect::<Vec<_>>();
+        assert_eq!(results, vec![(e2, 20, 2)]);
+
+        let mut query = DynamicQuery::new(&world, vec![a]);
+        for (_, components) in query.iter_mut(&mut world) {
+            unsafe { *components[0].cast::<u32>() += 100 };
+        }
+        assert_eq!(world.entity(e1).get_by_id(a).map(read), Some(101));
+
+        world.despawn(e1);
+        assert_eq!(DROPPED.load(Ordering::SeqCst), 111);
+    }
+}
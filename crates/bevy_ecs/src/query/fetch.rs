@@ -7,6 +7,8 @@ use crate::{
     storage::{ComponentSparseSet, Table, Tables},
     world::{Mut, World},
 };
+pub use bevy_ecs_macros::WorldQuery;
+
 use bevy_ecs_macros::all_tuples;
 use std::{
     cell::UnsafeCell,
@@ -39,6 +41,43 @@ use std::{
 /// [`With`](crate::query::With), [`Without`](crate::query::Without) and [`Or`].
 /// For more information on these consult the item's corresponding documentation.
 ///
+/// # Derive
+///
+/// Queries with many components can be declared as a struct of world queries with
+/// `#[derive(WorldQuery)]`. Its items are a generated struct named after it, with the items of its
+/// fields. With `#[world_query(filter)]`, the struct is instead a filter matching the entities
+/// matched by all of its fields. Both are limited to 15 fields.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::query::WorldQuery;
+///
+/// struct Health(f32);
+/// struct Shield(f32);
+/// struct Player;
+///
+/// #[derive(WorldQuery)]
+/// struct Defense {
+///     entity: Entity,
+///     health: &'static mut Health,
+///     shield: Option<&'static Shield>,
+/// }
+///
+/// #[derive(WorldQuery)]
+/// #[world_query(filter)]
+/// struct DamagedPlayer {
+///     player: With<Player>,
+///     health: Changed<Health>,
+/// }
+///
+/// fn regenerate(mut query: Query<Defense, DamagedPlayer>) {
+///     for mut defense in query.iter_mut() {
+///         defense.health.0 += defense.shield.map_or(1.0, |shield| shield.0);
+///     }
+/// }
+/// # regenerate.system();
+/// ```
+///
 /// [`Or`]: crate::query::Or
 pub trait WorldQuery {
     type Fetch: for<'a> Fetch<'a, State = Self::State>;
@@ -41,6 +41,13 @@ where
     }
 }
 
+/// A query filter that only depends on the archetype of an entity, never on its individual
+/// component values or change ticks.
+///
+/// Queries with such a filter match whole tables at once, which allows handing out the columns of
+/// a table as slices, see [`Query::iter_slices`](crate::system::Query::iter_slices).
+pub trait ArchetypeFilter {}
+
 /// Filter that selects entities with a component `T`.
 ///
 /// This can be used in a [`Query`](crate::system::Query) if entities are required to have the
@@ -68,6 +75,8 @@ where
 /// ```
 pub struct With<T>(PhantomData<T>);
 
+impl<T> ArchetypeFilter for With<T> {}
+
 impl<T: Component> WorldQuery for With<T> {
     type Fetch = WithFetch<T>;
     type State = WithState<T>;
@@ -187,6 +196,8 @@ impl<'a, T: Component> Fetch<'a> for WithFetch<T> {
 /// ```
 pub struct Without<T>(PhantomData<T>);
 
+impl<T> ArchetypeFilter for Without<T> {}
+
 impl<T: Component> WorldQuery for Without<T> {
     type Fetch = WithoutFetch<T>;
     type State = WithoutState<T>;
@@ -284,6 +295,8 @@ impl<'a, T: Component> Fetch<'a> for WithoutFetch<T> {
 
 pub struct WithBundle<T: Bundle>(PhantomData<T>);
 
+impl<T: Bundle> ArchetypeFilter for WithBundle<T> {}
+
 impl<T: Bundle> WorldQuery for WithBundle<T> {
     type Fetch = WithBundleFetch<T>;
     type State = WithBundleState<T>;
@@ -420,6 +433,10 @@ pub struct OrFetch<T: FilterFetch> {
 
 macro_rules! impl_query_filter_tuple {
     ($(($filter: ident, $state: ident)),*) => {
+        impl<$($filter: ArchetypeFilter),*> ArchetypeFilter for ($($filter,)*) {}
+
+        impl<$($filter: ArchetypeFilter),*> ArchetypeFilter for Or<($($filter,)*)> {}
+
         #[allow(unused_variables)]
         #[allow(non_snake_case)]
         impl<'a, $($filter: FilterFetch),*> FilterFetch for ($($filter,)*) {
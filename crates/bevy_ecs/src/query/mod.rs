@@ -1,10 +1,14 @@
 mod access;
+mod batching;
+mod dynamic;
 mod fetch;
 mod filter;
 mod iter;
 mod state;
 
 pub use access::*;
+pub use batching::*;
+pub use dynamic::*;
 pub use fetch::*;
 pub use filter::*;
 pub use iter::*;
@@ -14,6 +18,7 @@ pub use state::*;
 mod tests {
     use crate::{
         component::{ComponentDescriptor, StorageType},
+        query::QueryEntityError,
         world::World,
     };
 
@@ -37,6 +42,30 @@ mod tests {
         assert_eq!(values, vec![&B(3)]);
     }
 
+    #[test]
+    fn query_get_multiple_mut() {
+        let mut world = World::new();
+        let a = world.spawn().insert_bundle((A(1), B(1))).id();
+        let b = world.spawn().insert_bundle((A(2), B(2))).id();
+        let c = world.spawn().insert_bundle((A(3),)).id();
+
+        let mut query = world.query::<&mut A>();
+        let [mut first, mut second] = query.get_multiple_mut(&mut world, [a, b]).unwrap();
+        std::mem::swap(&mut first.0, &mut second.0);
+        assert_eq!(world.get::<A>(a), Some(&A(2)));
+        assert_eq!(world.get::<A>(b), Some(&A(1)));
+
+        assert!(matches!(
+            query.get_multiple_mut(&mut world, [a, c, a]),
+            Err(QueryEntityError::AliasedMutability(entity)) if entity == a
+        ));
+        let mut query = world.query::<&mut B>();
+        assert!(matches!(
+            query.get_multiple_mut(&mut world, [a, c]),
+            Err(QueryEntityError::QueryDoesNotMatch)
+        ));
+    }
+
     #[test]
     fn query_iter_combinations() {
         let mut world = World::new();
@@ -186,4 +215,48 @@ mod tests {
         let values = world.query::<&B>().iter(&world).collect::<Vec<&B>>();
         assert_eq!(values, vec![&B(3)]);
     }
+
+    #[test]
+    fn derived_world_query() {
+        use crate as bevy_ecs;
+        use crate::{
+            entity::Entity,
+            query::{With, Without, WorldQuery},
+        };
+
+        #[derive(WorldQuery)]
+        struct Named {
+            entity: Entity,
+            a: &'static mut A,
+            b: Option<&'static B>,
+        }
+
+        #[derive(WorldQuery)]
+        #[world_query(filter)]
+        struct AWithoutB {
+            a: With<A>,
+            b: Without<B>,
+        }
+
+        let mut world = World::new();
+        let e1 = world.spawn().insert_bundle((A(1), B(10))).id();
+        let e2 = world.spawn().insert_bundle((A(2),)).id();
+        world.spawn().insert_bundle((B(3),));
+
+        for mut item in world.query::<Named>().iter_mut(&mut world) {
+            item.a.0 += item.b.map_or(0, |b| b.0);
+        }
+        let values = world
+            .query::<Named>()
+            .iter_mut(&mut world)
+            .map(|item| (item.entity, item.a.0, item.b.map(|b| b.0)))
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![(e1, 11, Some(10)), (e2, 2, None)]);
+
+        let values = world
+            .query_filtered::<Entity, AWithoutB>()
+            .iter(&world)
+            .collect::<Vec<_>>();
+        assert_eq!(values, vec![e2]);
+    }
 }
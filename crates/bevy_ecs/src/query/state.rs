@@ -3,14 +3,16 @@ use crate::{
     component::ComponentId,
     entity::Entity,
     query::{
-        Access, Fetch, FetchState, FilterFetch, FilteredAccess, QueryCombinationIter, QueryIter,
-        ReadOnlyFetch, WorldQuery,
+        Access, BatchingStrategy, Fetch, FetchState, FilterFetch, FilteredAccess, ItemTime,
+        QueryCombinationIter, QueryIter, ReadOnlyFetch, WorldQuery,
     },
     storage::TableId,
     world::{World, WorldId},
 };
 use bevy_tasks::TaskPool;
+use bevy_utils::Instant;
 use fixedbitset::FixedBitSet;
+use std::convert::TryInto;
 use thiserror::Error;
 
 pub struct QueryState<Q: WorldQuery, F: WorldQuery = ()>
@@ -29,6 +31,7 @@ where
     pub(crate) matched_archetype_ids: Vec<ArchetypeId>,
     pub(crate) fetch_state: Q::State,
     pub(crate) filter_state: F::State,
+    pub(crate) item_time: ItemTime,
 }
 
 impl<Q: WorldQuery, F: WorldQuery> QueryState<Q, F>
@@ -63,6 +66,7 @@ where
             matched_tables: Default::default(),
             matched_archetypes: Default::default(),
             archetype_component_access: Default::default(),
+            item_time: Default::default(),
         };
         state.validate_world_and_update_archetypes(world);
         state
@@ -139,6 +143,26 @@ where
         unsafe { self.get_unchecked(world, entity) }
     }
 
+    /// Gets the query results for several distinct entities at once, so that they can all be
+    /// mutated at the same time. Fails if an entity is given more than once.
+    #[inline]
+    pub fn get_multiple_mut<'w, const N: usize>(
+        &mut self,
+        world: &'w mut World,
+        entities: [Entity; N],
+    ) -> Result<[<Q::Fetch as Fetch<'w>>::Item; N], QueryEntityError> {
+        self.validate_world_and_update_archetypes(world);
+        // SAFETY: query has unique world access, and the entities are checked to be distinct
+        unsafe {
+            self.get_multiple_unchecked_manual(
+                world,
+                entities,
+                world.last_change_tick(),
+                world.read_change_tick(),
+            )
+        }
+    }
+
     /// # Safety
     ///
     /// This does not check for mutable query correctness. To be safe, make sure mutable queries
@@ -158,6 +182,34 @@ where
         )
     }
 
+    /// Gets the query results for several entities, failing with
+    /// [`QueryEntityError::AliasedMutability`] if an entity is given more than once.
+    ///
+    /// # Safety
+    /// This does not check for mutable query correctness. To be safe, make sure mutable queries
+    /// have unique access to the components they query.
+    pub unsafe fn get_multiple_unchecked_manual<'w, const N: usize>(
+        &self,
+        world: &'w World,
+        entities: [Entity; N],
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Result<[<Q::Fetch as Fetch<'w>>::Item; N], QueryEntityError> {
+        for (i, entity) in entities.iter().enumerate() {
+            if entities[..i].contains(entity) {
+                return Err(QueryEntityError::AliasedMutability(*entity));
+            }
+        }
+        let mut items = Vec::with_capacity(N);
+        for entity in entities.iter() {
+            items.push(self.get_unchecked_manual(world, *entity, last_change_tick, change_tick)?);
+        }
+        // there are exactly N items
+        Ok(items
+            .try_into()
+            .unwrap_or_else(|_| unreachable!("one item per entity")))
+    }
+
     /// # Safety
     /// This does not check for mutable query correctness. To be safe, make sure mutable queries
     /// have unique access to the components they query.
@@ -343,10 +395,24 @@ where
         func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
     ) where
         Q::Fetch: ReadOnlyFetch,
+    {
+        self.par_for_each_batched(world, task_pool, BatchingStrategy::Fixed(batch_size), func);
+    }
+
+    /// Runs `func` on each query result in parallel, in batches sized by `batching`.
+    #[inline]
+    pub fn par_for_each_batched<'w>(
+        &mut self,
+        world: &'w World,
+        task_pool: &TaskPool,
+        batching: BatchingStrategy,
+        func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
+    ) where
+        Q::Fetch: ReadOnlyFetch,
     {
         // SAFETY: query is read only
         unsafe {
-            self.par_for_each_unchecked(world, task_pool, batch_size, func);
+            self.par_for_each_unchecked(world, task_pool, batching, func);
         }
     }
 
@@ -357,10 +423,22 @@ where
         task_pool: &TaskPool,
         batch_size: usize,
         func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
+    ) {
+        self.par_for_each_batched_mut(world, task_pool, BatchingStrategy::Fixed(batch_size), func);
+    }
+
+    /// Runs `func` on each query result in parallel, in batches sized by `batching`.
+    #[inline]
+    pub fn par_for_each_batched_mut<'w>(
+        &mut self,
+        world: &'w mut World,
+        task_pool: &TaskPool,
+        batching: BatchingStrategy,
+        func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
     ) {
         // SAFETY: query has unique world access
         unsafe {
-            self.par_for_each_unchecked(world, task_pool, batch_size, func);
+            self.par_for_each_unchecked(world, task_pool, batching, func);
         }
     }
 
@@ -373,14 +451,14 @@ where
         &mut self,
         world: &'w World,
         task_pool: &TaskPool,
-        batch_size: usize,
+        batching: BatchingStrategy,
         func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
     ) {
         self.validate_world_and_update_archetypes(world);
         self.par_for_each_unchecked_manual(
             world,
             task_pool,
-            batch_size,
+            batching,
             func,
             world.last_change_tick(),
             world.read_change_tick(),
@@ -449,20 +527,36 @@ where
         &'s self,
         world: &'w World,
         task_pool: &TaskPool,
-        batch_size: usize,
+        batching: BatchingStrategy,
         func: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
         last_change_tick: u32,
         change_tick: u32,
     ) {
         // NOTE: If you are changing query iteration code, remember to update the following places, where relevant:
         // QueryIter, QueryIterationCursor, QueryState::for_each_unchecked_manual, QueryState::par_for_each_unchecked_manual
-        task_pool.scope(|scope| {
-            let fetch =
-                <Q::Fetch as Fetch>::init(world, &self.fetch_state, last_change_tick, change_tick);
-            let filter =
-                <F::Fetch as Fetch>::init(world, &self.filter_state, last_change_tick, change_tick);
+        let fetch =
+            <Q::Fetch as Fetch>::init(world, &self.fetch_state, last_change_tick, change_tick);
+        let filter =
+            <F::Fetch as Fetch>::init(world, &self.filter_state, last_change_tick, change_tick);
+        let is_dense = fetch.is_dense() && filter.is_dense();
+        let count = if is_dense {
+            let tables = &world.storages().tables;
+            self.matched_table_ids
+                .iter()
+                .map(|id| tables[*id].len())
+                .sum()
+        } else {
+            self.matched_archetype_ids
+                .iter()
+                .map(|id| world.archetypes[*id].len())
+                .sum()
+        };
+        let threads = task_pool.thread_num();
+        let batch_size = batching.batch_size(count, threads, self.item_time.get());
+        let start = Instant::now();
 
-            if fetch.is_dense() && filter.is_dense() {
+        task_pool.scope(|scope| {
+            if is_dense {
                 let tables = &world.storages().tables;
                 for table_id in self.matched_table_ids.iter() {
                     let table = &tables[*table_id];
@@ -536,6 +630,10 @@ where
                 }
             }
         });
+
+        if let BatchingStrategy::Adaptive { .. } = batching {
+            self.item_time.record(start.elapsed(), count, threads);
+        }
     }
 }
 
@@ -546,4 +644,6 @@ pub enum QueryEntityError {
     QueryDoesNotMatch,
     #[error("The requested entity does not exist.")]
     NoSuchEntity,
+    #[error("The entity {0:?} was requested mutably more than once.")]
+    AliasedMutability(Entity),
 }
@@ -23,7 +23,7 @@ pub use system_set::*;
 use std::fmt::Debug;
 
 use crate::{
-    system::{IntoSystem, System},
+    system::{BoxedSystem, IntoSystem, System},
     world::World,
 };
 use bevy_utils::HashMap;
@@ -173,6 +173,40 @@ impl Schedule {
         })
     }
 
+    /// Replaces the first parallel system labelled `label`, in any [`SystemStage`] of the
+    /// schedule or of its nested schedules, with `system`. See [`SystemStage::replace_system`].
+    ///
+    /// Returns `false` if no parallel system has the label.
+    pub fn replace_system<S: System<In = (), Out = ()>>(
+        &mut self,
+        label: impl SystemLabel,
+        system: S,
+    ) -> bool {
+        self.replace_system_boxed(&label, Box::new(system)).is_ok()
+    }
+
+    fn replace_system_boxed(
+        &mut self,
+        label: &dyn SystemLabel,
+        mut system: BoxedSystem<(), ()>,
+    ) -> Result<(), BoxedSystem<(), ()>> {
+        for stage_label in self.stage_order.iter() {
+            let stage = self.stages.get_mut(stage_label).unwrap();
+            let result = if let Some(stage) = stage.downcast_mut::<SystemStage>() {
+                stage.replace_system_boxed(label, system)
+            } else if let Some(schedule) = stage.downcast_mut::<Schedule>() {
+                schedule.replace_system_boxed(label, system)
+            } else {
+                Err(system)
+            };
+            match result {
+                Ok(()) => return Ok(()),
+                Err(returned) => system = returned,
+            }
+        }
+        Err(system)
+    }
+
     pub fn stage<T: Stage, F: FnOnce(&mut T) -> &mut T>(
         &mut self,
         label: impl StageLabel,
@@ -1,9 +1,9 @@
 use crate::{
     archetype::{Archetype, ArchetypeComponentId, ArchetypeGeneration},
-    component::ComponentId,
-    query::Access,
+    component::{Component, ComponentId},
+    query::{Access, With},
     schedule::{BoxedRunCriteriaLabel, GraphNode, RunCriteriaLabel},
-    system::{BoxedSystem, IntoSystem, System, SystemId},
+    system::{BoxedSystem, IntoSystem, Query, Res, System, SystemId},
     world::World,
 };
 use std::borrow::Cow;
@@ -88,6 +88,12 @@ impl BoxedRunCriteria {
             ShouldRun::Yes
         }
     }
+
+    pub fn check_change_tick(&mut self, change_tick: u32) {
+        if let Some(ref mut run_criteria) = self.criteria_system {
+            run_criteria.check_change_tick(change_tick);
+        }
+    }
 }
 
 pub(crate) enum RunCriteriaInner {
@@ -136,6 +142,13 @@ impl RunCriteriaContainer {
         }
     }
 
+    pub fn check_change_tick(&mut self, change_tick: u32) {
+        match &mut self.inner {
+            RunCriteriaInner::Single(system) => system.check_change_tick(change_tick),
+            RunCriteriaInner::Piped { system, .. } => system.check_change_tick(change_tick),
+        }
+    }
+
     pub fn update_archetypes(&mut self, world: &World) {
         let archetypes = world.archetypes();
         let new_generation = archetypes.generation();
@@ -455,3 +468,449 @@ impl System for RunOnce {
 
     fn check_change_tick(&mut self, _change_tick: u32) {}
 }
+
+impl From<bool> for ShouldRun {
+    fn from(should_run: bool) -> Self {
+        if should_run {
+            ShouldRun::Yes
+        } else {
+            ShouldRun::No
+        }
+    }
+}
+
+/// A run criteria that runs the system when the resource `T` was added or changed since the
+/// criteria was last checked, e.g. to skip work while a configuration resource stays the same.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule::resource_changed};
+/// # struct Settings;
+/// # fn apply_settings_system() {}
+/// # let mut stage = SystemStage::parallel();
+/// stage.add_system(
+///     apply_settings_system
+///         .system()
+///         .with_run_criteria(resource_changed::<Settings>()),
+/// );
+/// ```
+pub fn resource_changed<T: Component>() -> impl System<In = (), Out = ShouldRun> {
+    (|resource: Option<Res<T>>| {
+        ShouldRun::from(resource.map_or(false, |resource| resource.is_changed()))
+    })
+    .system()
+}
+
+/// A run criteria that runs the system when the resource `T` was added since the criteria was
+/// last checked.
+pub fn resource_added<T: Component>() -> impl System<In = (), Out = ShouldRun> {
+    (|resource: Option<Res<T>>| {
+        ShouldRun::from(resource.map_or(false, |resource| resource.is_added()))
+    })
+    .system()
+}
+
+/// A run criteria that runs the system while the resource `T` exists.
+pub fn resource_exists<T: Component>() -> impl System<In = (), Out = ShouldRun> {
+    (|resource: Option<Res<T>>| ShouldRun::from(resource.is_some())).system()
+}
+
+/// A run criteria that runs the system while the resource `T` exists and is equal to `value`.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule::resource_equals};
+/// # #[derive(PartialEq)]
+/// # enum Difficulty { Easy, Hard }
+/// # fn spawn_boss_system() {}
+/// # let mut stage = SystemStage::parallel();
+/// stage.add_system(
+///     spawn_boss_system
+///         .system()
+///         .with_run_criteria(resource_equals(Difficulty::Hard)),
+/// );
+/// ```
+pub fn resource_equals<T: Component + PartialEq>(
+    value: T,
+) -> impl System<In = (), Out = ShouldRun> {
+    (move |resource: Option<Res<T>>| {
+        ShouldRun::from(resource.map_or(false, |resource| *resource == value))
+    })
+    .system()
+}
+
+/// A run criteria that runs the system while any entity has the component `T`.
+pub fn any_with_component<T: Component>() -> impl System<In = (), Out = ShouldRun> {
+    (|query: Query<(), With<T>>| ShouldRun::from(!query.is_empty())).system()
+}
+
+impl ShouldRun {
+    /// Whether the system should run now
+    pub fn should_run(self) -> bool {
+        matches!(self, ShouldRun::Yes | ShouldRun::YesAndCheckAgain)
+    }
+
+    /// Whether the criteria should be checked again once the systems of the stage ran
+    pub fn check_again(self) -> bool {
+        matches!(
+            self,
+            ShouldRun::YesAndCheckAgain | ShouldRun::NoAndCheckAgain
+        )
+    }
+
+    fn new(should_run: bool, check_again: bool) -> Self {
+        match (should_run, check_again) {
+            (true, false) => ShouldRun::Yes,
+            (false, false) => ShouldRun::No,
+            (true, true) => ShouldRun::YesAndCheckAgain,
+            (false, true) => ShouldRun::NoAndCheckAgain,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum RunCriteriaOperator {
+    And,
+    Or,
+}
+
+/// A run criteria combining two run criteria, created with [`RunCriteriaCombinators::and`] or
+/// [`RunCriteriaCombinators::or`].
+///
+/// Both criteria are always evaluated, so that criteria with change detection, such as
+/// [`resource_changed`], stay up to date. The combination is checked again if either criteria
+/// asks to be.
+pub struct CombinedRunCriteria<SystemA, SystemB> {
+    system_a: SystemA,
+    system_b: SystemB,
+    operator: RunCriteriaOperator,
+    name: Cow<'static, str>,
+    id: SystemId,
+    component_access: Access<ComponentId>,
+    archetype_component_access: Access<ArchetypeComponentId>,
+}
+
+impl<In, SystemA, SystemB> System for CombinedRunCriteria<SystemA, SystemB>
+where
+    In: Copy + 'static,
+    SystemA: System<In = In, Out = ShouldRun>,
+    SystemB: System<In = In, Out = ShouldRun>,
+{
+    type In = In;
+    type Out = ShouldRun;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn id(&self) -> SystemId {
+        self.id
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype) {
+        self.system_a.new_archetype(archetype);
+        self.system_b.new_archetype(archetype);
+
+        self.archetype_component_access
+            .extend(self.system_a.archetype_component_access());
+        self.archetype_component_access
+            .extend(self.system_b.archetype_component_access());
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        &self.component_access
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        &self.archetype_component_access
+    }
+
+    fn is_send(&self) -> bool {
+        self.system_a.is_send() && self.system_b.is_send()
+    }
+
+    unsafe fn run_unsafe(&mut self, input: In, world: &World) -> ShouldRun {
+        let a = self.system_a.run_unsafe(input, world);
+        let b = self.system_b.run_unsafe(input, world);
+        let should_run = match self.operator {
+            RunCriteriaOperator::And => a.should_run() && b.should_run(),
+            RunCriteriaOperator::Or => a.should_run() || b.should_run(),
+        };
+        ShouldRun::new(should_run, a.check_again() || b.check_again())
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        self.system_a.apply_buffers(world);
+        self.system_b.apply_buffers(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system_a.initialize(world);
+        self.system_b.initialize(world);
+        self.component_access
+            .extend(self.system_a.component_access());
+        self.component_access
+            .extend(self.system_b.component_access());
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.system_a.check_change_tick(change_tick);
+        self.system_b.check_change_tick(change_tick);
+    }
+}
+
+/// A run criteria inverting another one, created with [`RunCriteriaCombinators::not`]. Whether
+/// the criteria is checked again is kept.
+pub struct NotRunCriteria<S> {
+    system: S,
+    name: Cow<'static, str>,
+}
+
+impl<S: System<Out = ShouldRun>> System for NotRunCriteria<S> {
+    type In = S::In;
+    type Out = ShouldRun;
+
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn id(&self) -> SystemId {
+        self.system.id()
+    }
+
+    fn new_archetype(&mut self, archetype: &Archetype) {
+        self.system.new_archetype(archetype);
+    }
+
+    fn component_access(&self) -> &Access<ComponentId> {
+        self.system.component_access()
+    }
+
+    fn archetype_component_access(&self) -> &Access<ArchetypeComponentId> {
+        self.system.archetype_component_access()
+    }
+
+    fn is_send(&self) -> bool {
+        self.system.is_send()
+    }
+
+    unsafe fn run_unsafe(&mut self, input: S::In, world: &World) -> ShouldRun {
+        let should_run = self.system.run_unsafe(input, world);
+        ShouldRun::new(!should_run.should_run(), should_run.check_again())
+    }
+
+    fn apply_buffers(&mut self, world: &mut World) {
+        self.system.apply_buffers(world);
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        self.system.initialize(world);
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        self.system.check_change_tick(change_tick);
+    }
+}
+
+/// Combines run criteria into new run criteria, which can be labelled and piped like any other.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, schedule::{resource_changed, resource_exists}};
+/// # struct Paused;
+/// # struct Level;
+/// # fn spawn_enemies_system() {}
+/// # let mut stage = SystemStage::parallel();
+/// stage.add_system(
+///     spawn_enemies_system.system().with_run_criteria(
+///         resource_exists::<Level>().and(resource_exists::<Paused>().not()),
+///     ),
+/// );
+/// ```
+pub trait RunCriteriaCombinators<In, Param>: IntoSystem<In, ShouldRun, Param> + Sized {
+    /// Runs the system when both criteria say so.
+    fn and<ParamB, B: IntoSystem<In, ShouldRun, ParamB>>(
+        self,
+        other: B,
+    ) -> CombinedRunCriteria<Self::System, B::System> {
+        combine(self.system(), other.system(), RunCriteriaOperator::And)
+    }
+
+    /// Runs the system when either criteria says so.
+    fn or<ParamB, B: IntoSystem<In, ShouldRun, ParamB>>(
+        self,
+        other: B,
+    ) -> CombinedRunCriteria<Self::System, B::System> {
+        combine(self.system(), other.system(), RunCriteriaOperator::Or)
+    }
+
+    /// Runs the system when the criteria says not to.
+    fn not(self) -> NotRunCriteria<Self::System> {
+        let system = self.system();
+        NotRunCriteria {
+            name: format!("not {}", system.name()).into(),
+            system,
+        }
+    }
+}
+
+impl<In, Param, S> RunCriteriaCombinators<In, Param> for S where S: IntoSystem<In, ShouldRun, Param> {}
+
+fn combine<A: System, B: System>(
+    system_a: A,
+    system_b: B,
+    operator: RunCriteriaOperator,
+) -> CombinedRunCriteria<A, B> {
+    let operator_name = match operator {
+        RunCriteriaOperator::And => "and",
+        RunCriteriaOperator::Or => "or",
+    };
+    CombinedRunCriteria {
+        name: format!(
+            "({} {} {})",
+            system_a.name(),
+            operator_name,
+            system_b.name()
+        )
+        .into(),
+        system_a,
+        system_b,
+        operator,
+        id: SystemId::new(),
+        component_access: Default::default(),
+        archetype_component_access: Default::default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        any_with_component, resource_added, resource_changed, resource_equals, resource_exists,
+        RunCriteriaCombinators, ShouldRun,
+    };
+    use crate::{
+        schedule::{
+            ParallelSystemDescriptorCoercion, RunCriteriaDescriptorCoercion, RunCriteriaPiping,
+            Stage, SystemStage,
+        },
+        system::{In, IntoSystem, ResMut},
+        world::World,
+    };
+
+    #[derive(Default)]
+    struct Runs(Vec<&'static str>);
+
+    #[derive(PartialEq)]
+    struct Settings(u32);
+
+    #[test]
+    fn resource_run_criteria() {
+        let mut world = World::default();
+        world.insert_resource(Runs::default());
+        let mut stage = SystemStage::parallel();
+        stage
+            .add_system(
+                (|mut runs: ResMut<Runs>| runs.0.push("changed"))
+                    .system()
+                    .with_run_criteria(resource_changed::<Settings>()),
+            )
+            .add_system(
+                (|mut runs: ResMut<Runs>| runs.0.push("added"))
+                    .system()
+                    .with_run_criteria(resource_added::<Settings>()),
+            )
+            .add_system(
+                (|mut runs: ResMut<Runs>| runs.0.push("exists"))
+                    .system()
+                    .with_run_criteria(resource_exists::<Settings>()),
+            )
+            .add_system(
+                (|mut runs: ResMut<Runs>| runs.0.push("equals"))
+                    .system()
+                    .with_run_criteria(resource_equals(Settings(0))),
+            );
+        let mut run = |world: &mut World| {
+            stage.run(world);
+            let mut runs = std::mem::take(&mut world.get_resource_mut::<Runs>().unwrap().0);
+            runs.sort_unstable();
+            runs
+        };
+
+        assert!(run(&mut world).is_empty());
+        world.insert_resource(Settings(0));
+        assert_eq!(
+            run(&mut world),
+            vec!["added", "changed", "equals", "exists"]
+        );
+        assert_eq!(run(&mut world), vec!["equals", "exists"]);
+        world.get_resource_mut::<Settings>().unwrap().0 = 1;
+        assert_eq!(run(&mut world), vec!["changed", "exists"]);
+        world.remove_resource::<Settings>();
+        assert!(run(&mut world).is_empty());
+    }
+
+    #[test]
+    fn any_with_component_run_criteria() {
+        let mut world = World::default();
+        world.insert_resource(Runs::default());
+        let mut stage = SystemStage::parallel().with_system(
+            (|mut runs: ResMut<Runs>| runs.0.push("any"))
+                .system()
+                .with_run_criteria(any_with_component::<Settings>()),
+        );
+        let mut run = |world: &mut World| {
+            stage.run(world);
+            std::mem::take(&mut world.get_resource_mut::<Runs>().unwrap().0)
+        };
+
+        assert!(run(&mut world).is_empty());
+        let entity = world.spawn().insert(Settings(0)).id();
+        assert_eq!(run(&mut world), vec!["any"]);
+        world.despawn(entity);
+        assert!(run(&mut world).is_empty());
+    }
+
+    #[test]
+    fn combined_run_criteria() {
+        struct Paused;
+
+        let mut world = World::default();
+        world.insert_resource(Runs::default());
+        let mut stage = SystemStage::parallel();
+        stage
+            .add_system(
+                (|mut runs: ResMut<Runs>| runs.0.push("playing"))
+                    .system()
+                    .with_run_criteria(
+                        resource_exists::<Settings>()
+                            .and(resource_exists::<Paused>().not())
+                            .label("playing"),
+                    ),
+            )
+            .add_system(
+                (|mut runs: ResMut<Runs>| runs.0.push("any"))
+                    .system()
+                    .with_run_criteria(
+                        resource_exists::<Settings>().or(resource_exists::<Paused>()),
+                    ),
+            )
+            .add_system(
+                (|mut runs: ResMut<Runs>| runs.0.push("not playing"))
+                    .system()
+                    .with_run_criteria(
+                        "playing".pipe((|should_run: In<ShouldRun>| should_run.0).system().not()),
+                    ),
+            );
+        let mut run = |world: &mut World| {
+            stage.run(world);
+            let mut runs = std::mem::take(&mut world.get_resource_mut::<Runs>().unwrap().0);
+            runs.sort_unstable();
+            runs
+        };
+
+        assert_eq!(run(&mut world), vec!["not playing"]);
+        world.insert_resource(Settings(0));
+        assert_eq!(run(&mut world), vec!["any", "playing"]);
+        world.insert_resource(Paused);
+        assert_eq!(run(&mut world), vec!["any", "not playing"]);
+        world.remove_resource::<Settings>();
+        assert_eq!(run(&mut world), vec!["any", "not playing"]);
+    }
+}
@@ -1,17 +1,20 @@
 use crate::{
-    component::ComponentId,
+    component::{ComponentId, CHECK_TICK_THRESHOLD},
     schedule::{
         graph_utils::{self, DependencyGraphError},
         BoxedRunCriteria, BoxedRunCriteriaLabel, BoxedSystemLabel, DuplicateLabelStrategy,
         ExclusiveSystemContainer, GraphNode, InsertionPoint, ParallelExecutor,
         ParallelSystemContainer, ParallelSystemExecutor, RunCriteriaContainer,
         RunCriteriaDescriptor, RunCriteriaDescriptorOrLabel, RunCriteriaInner, ShouldRun,
-        SingleThreadedExecutor, SystemContainer, SystemDescriptor, SystemSet,
+        SingleThreadedExecutor, SystemContainer, SystemDescriptor, SystemLabel, SystemSet,
     },
-    system::System,
+    system::{BoxedSystem, System},
     world::{World, WorldId},
 };
-use bevy_utils::{tracing::info, HashMap, HashSet};
+use bevy_utils::{
+    tracing::{info, warn},
+    HashMap, HashSet,
+};
 use downcast_rs::{impl_downcast, Downcast};
 use fixedbitset::FixedBitSet;
 use std::fmt::Debug;
@@ -41,12 +44,37 @@ impl_downcast!(Stage);
 /// without blocking each other) or spurious, as the exact combination of archetypes used may
 /// prevent them from ever conflicting during actual gameplay. You can resolve the warnings produced
 /// by the ambiguity checker by adding `.before` or `.after` to one of the conflicting systems
-/// referencing the other system to force a specific ordering.
+/// referencing the other system to force a specific ordering, or silence the intentional ones with
+/// `.ambiguous_with` or `.in_ambiguity_set`. See [`AmbiguityDetection`] to report the ambiguities
+/// of a single stage, as warnings or panics.
 ///
 /// The checker may report a system more times than the amount of constraints it would actually need
 /// to have unambiguous order with regards to a group of already-constrained systems.
 pub struct ReportExecutionOrderAmbiguities;
 
+/// What a [`SystemStage`] does with the pairs of systems with ambiguous execution order it finds
+/// when its systems change, see [`ReportExecutionOrderAmbiguities`].
+///
+/// Intentional ambiguities can be excluded from the report with
+/// [`ambiguous_with`](crate::schedule::ParallelSystemDescriptorCoercion::ambiguous_with) or
+/// [`in_ambiguity_set`](crate::schedule::ParallelSystemDescriptorCoercion::in_ambiguity_set).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AmbiguityDetection {
+    /// Only logs the ambiguities at the info level, if the [`ReportExecutionOrderAmbiguities`]
+    /// resource exists
+    Ignore,
+    /// Logs the ambiguities as a warning
+    Warn,
+    /// Panics if there are any ambiguities, e.g. to enforce an unambiguous schedule in tests
+    Panic,
+}
+
+impl Default for AmbiguityDetection {
+    fn default() -> Self {
+        AmbiguityDetection::Ignore
+    }
+}
+
 /// Stores and executes systems. Execution order is not defined unless explicitly specified;
 /// see `SystemDescriptor` documentation.
 pub struct SystemStage {
@@ -83,6 +111,8 @@ pub struct SystemStage {
     uninitialized_parallel: Vec<usize>,
     /// Saves the value of the World change_tick during the last tick check
     last_tick_check: u32,
+    /// What to do with the execution order ambiguities between systems.
+    ambiguity_detection: AmbiguityDetection,
 }
 
 impl SystemStage {
@@ -104,6 +134,7 @@ impl SystemStage {
             uninitialized_before_commands: vec![],
             uninitialized_at_end: vec![],
             last_tick_check: Default::default(),
+            ambiguity_detection: Default::default(),
         }
     }
 
@@ -133,6 +164,21 @@ impl SystemStage {
         self.executor = executor;
     }
 
+    /// Sets what to do with the pairs of systems with ambiguous execution order in this stage.
+    pub fn set_ambiguity_detection(
+        &mut self,
+        ambiguity_detection: AmbiguityDetection,
+    ) -> &mut Self {
+        self.ambiguity_detection = ambiguity_detection;
+        self
+    }
+
+    /// Builder variant of [`Self::set_ambiguity_detection`].
+    pub fn with_ambiguity_detection(mut self, ambiguity_detection: AmbiguityDetection) -> Self {
+        self.set_ambiguity_detection(ambiguity_detection);
+        self
+    }
+
     pub fn with_system<Params>(mut self, system: impl IntoSystemDescriptor<Params>) -> Self {
         self.add_system(system);
         self
@@ -203,6 +249,42 @@ impl SystemStage {
         }
     }
 
+    /// Replaces the first parallel system labelled `label` with `system`, e.g. with a stub
+    /// standing in for a system that needs rendering or asset IO in a test. The replacement keeps
+    /// the labels, ordering and run criteria of the replaced system, and is initialized the next
+    /// time the stage runs.
+    ///
+    /// Returns `false` if no parallel system has the label.
+    pub fn replace_system<S: System<In = (), Out = ()>>(
+        &mut self,
+        label: impl SystemLabel,
+        system: S,
+    ) -> bool {
+        self.replace_system_boxed(&label, Box::new(system)).is_ok()
+    }
+
+    /// Replaces a system labelled `label`, or gives `system` back if there's none.
+    pub(crate) fn replace_system_boxed(
+        &mut self,
+        label: &dyn SystemLabel,
+        system: BoxedSystem<(), ()>,
+    ) -> Result<(), BoxedSystem<(), ()>> {
+        let index = match self
+            .parallel
+            .iter()
+            .position(|container| container.labels().iter().any(|l| &**l == label))
+        {
+            Some(index) => index,
+            None => return Err(system),
+        };
+        self.parallel[index].set_system(system);
+        if !self.uninitialized_parallel.contains(&index) {
+            self.uninitialized_parallel.push(index);
+        }
+        self.systems_modified = true;
+        Ok(())
+    }
+
     /// Topologically sorted parallel systems.
     ///
     /// Note that systems won't be fully-formed until the stage has been run at least once.
@@ -466,8 +548,23 @@ impl SystemStage {
         );
     }
 
-    /// Logs execution order ambiguities between systems. System orders must be fresh.
+    /// Logs execution order ambiguities between systems, or panics, depending on the
+    /// [`AmbiguityDetection`] of the stage. System orders must be fresh.
     fn report_ambiguities(&self, world: &World) {
+        let report = match self.ambiguity_report(world) {
+            Some(report) => report,
+            None => return,
+        };
+        match self.ambiguity_detection {
+            AmbiguityDetection::Ignore => info!("{}", report),
+            AmbiguityDetection::Warn => warn!("{}", report),
+            AmbiguityDetection::Panic => panic!("{}", report),
+        }
+    }
+
+    /// Returns a report of the execution order ambiguities between systems, with the components
+    /// and resources they conflict on, if there are any. System orders must be fresh.
+    fn ambiguity_report(&self, world: &World) -> Option<String> {
         debug_assert!(!self.systems_modified);
         use std::fmt::Write;
         fn write_display_names_of_pairs(
@@ -485,9 +582,21 @@ impl SystemStage {
                 )
                 .unwrap();
                 if !conflicts.is_empty() {
+                    let components = world.components();
                     let names = conflicts
                         .iter()
-                        .map(|id| world.components().get_info(*id).unwrap().name())
+                        .map(|id| {
+                            let info = components.get_info(*id).unwrap();
+                            let is_resource = info
+                                .type_id()
+                                .and_then(|type_id| components.get_resource_id(type_id))
+                                == Some(*id);
+                            if is_resource {
+                                format!("resource {}", info.name())
+                            } else {
+                                info.name().to_string()
+                            }
+                        })
                         .collect::<Vec<_>>();
                     writeln!(string, "    conflicts: {:?}", names).unwrap();
                 }
@@ -531,7 +640,9 @@ impl SystemStage {
                 writeln!(string, " * Exclusive systems at end of stage:").unwrap();
                 write_display_names_of_pairs(&mut string, &self.exclusive_at_end, at_end, world);
             }
-            info!("{}", string);
+            Some(string)
+        } else {
+            None
         }
     }
 
@@ -541,11 +652,13 @@ impl SystemStage {
         let time_since_last_check = change_tick.wrapping_sub(self.last_tick_check);
         // Only check after at least `u32::MAX / 8` counts, and at most `u32::MAX / 4` counts
         // since the max number of [System] in a [SystemStage] is limited to `u32::MAX / 8`
-        // and this function is called at the end of each [SystemStage] loop
-        const MIN_TIME_SINCE_LAST_CHECK: u32 = u32::MAX / 8;
-
-        if time_since_last_check > MIN_TIME_SINCE_LAST_CHECK {
+        // and this function is called at the start and end of each [SystemStage] run
+        if time_since_last_check > CHECK_TICK_THRESHOLD {
             // Check all system change ticks
+            self.stage_run_criteria.check_change_tick(change_tick);
+            for run_criteria in &mut self.run_criteria {
+                run_criteria.check_change_tick(change_tick);
+            }
             for exclusive_system in &mut self.exclusive_at_start {
                 exclusive_system.system_mut().check_change_tick(change_tick);
             }
@@ -559,11 +672,11 @@ impl SystemStage {
                 parallel_system.system_mut().check_change_tick(change_tick);
             }
 
-            // Check component ticks
-            world.check_change_ticks();
-
             self.last_tick_check = change_tick;
         }
+
+        // Check component ticks, which the world only does once in a while
+        world.check_change_ticks();
     }
 
     /// Sorts run criteria and populates resolved input-criteria for piping.
@@ -658,6 +771,14 @@ fn process_systems(
     Ok(())
 }
 
+/// Whether `system` was marked as ambiguous with one of the labels of `other`.
+fn is_ambiguous_with(system: &impl SystemContainer, other: &impl SystemContainer) -> bool {
+    system
+        .ambiguous_with()
+        .iter()
+        .any(|label| other.labels().contains(label))
+}
+
 /// Returns vector containing all pairs of indices of systems with ambiguous execution order,
 /// along with specific components that have triggered the warning.
 /// Systems must be topologically sorted beforehand.
@@ -716,6 +837,8 @@ fn find_ambiguities(systems: &[impl SystemContainer]) -> Vec<(usize, usize, Vec<
         {
             if !processed.contains(index_b)
                 && all_ambiguity_sets[index_a].is_disjoint(&all_ambiguity_sets[index_b])
+                && !is_ambiguous_with(&systems[index_a], &systems[index_b])
+                && !is_ambiguous_with(&systems[index_b], &systems[index_a])
             {
                 let a_access = systems[index_a].component_access();
                 let b_access = systems[index_b].component_access();
@@ -751,7 +874,9 @@ impl Stage for SystemStage {
             self.systems_modified = false;
             self.executor.rebuild_cached_data(&self.parallel);
             self.executor_modified = false;
-            if world.contains_resource::<ReportExecutionOrderAmbiguities>() {
+            if self.ambiguity_detection != AmbiguityDetection::Ignore
+                || world.contains_resource::<ReportExecutionOrderAmbiguities>()
+            {
                 self.report_ambiguities(world);
             }
         } else if self.executor_modified {
@@ -759,6 +884,9 @@ impl Stage for SystemStage {
             self.executor_modified = false;
         }
 
+        // The systems of a stage that didn't run for a long time may hold ticks that wrapped around
+        self.check_change_ticks(world);
+
         let mut run_stage_loop = true;
         while run_stage_loop {
             let should_run = self.stage_run_criteria.should_run(world);
@@ -833,6 +961,8 @@ impl Stage for SystemStage {
                         container.system_mut().apply_buffers(world);
                     }
                 }
+                // Materialize the entities reserved by the systems, e.g. from other threads.
+                world.flush();
 
                 // Run systems that want to be at the end of stage.
                 for container in &mut self.exclusive_at_end {
@@ -890,16 +1020,18 @@ impl Stage for SystemStage {
 #[cfg(test)]
 mod tests {
     use crate::{
-        entity::Entity,
+        component::{CHECK_TICK_THRESHOLD, MAX_CHANGE_AGE},
+        entity::{Entities, Entity},
         query::{ChangeTrackers, Changed},
         schedule::{
             BoxedSystemLabel, ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion,
-            RunCriteria, RunCriteriaDescriptorCoercion, RunCriteriaPiping, ShouldRun,
+            RunCriteria, RunCriteriaDescriptorCoercion, RunCriteriaPiping, Schedule, ShouldRun,
             SingleThreadedExecutor, Stage, SystemSet, SystemStage,
         },
         system::{In, IntoExclusiveSystem, IntoSystem, Local, Query, ResMut},
         world::World,
     };
+    use bevy_tasks::TaskPool;
 
     fn make_exclusive(tag: usize) -> impl FnMut(&mut World) {
         move |world| world.get_resource_mut::<Vec<usize>>().unwrap().push(tag)
@@ -1586,6 +1718,54 @@ mod tests {
         stage.run(&mut world);
     }
 
+    #[test]
+    fn ambiguous_with() {
+        use super::{find_ambiguities, AmbiguityDetection};
+
+        fn resource(_: ResMut<usize>) {}
+        fn component(_: Query<&mut f32>) {}
+
+        fn ambiguity_panic_message(stage: &mut SystemStage, world: &mut World) -> String {
+            let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+                stage.run(world);
+            }));
+            *result.unwrap_err().downcast::<String>().unwrap()
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0usize);
+
+        let mut stage = SystemStage::parallel()
+            .with_system(resource.system().label("0"))
+            .with_system(resource.system().label("1").ambiguous_with("0"))
+            .with_system(component.system().label("2"))
+            .with_system(component.system().label("3"))
+            .with_system(component.exclusive_system().label("4"))
+            .with_system(component.exclusive_system().ambiguous_with("4"));
+        stage.initialize_systems(&mut world);
+        stage.rebuild_orders_and_dependencies();
+        assert_eq!(find_ambiguities(&stage.parallel).len(), 1);
+        assert_eq!(find_ambiguities(&stage.exclusive_at_start).len(), 0);
+
+        stage.set_ambiguity_detection(AmbiguityDetection::Panic);
+        let report = ambiguity_panic_message(&mut stage, &mut world);
+        assert!(report.contains("f32"), "{}", report);
+        assert!(!report.contains("usize"), "{}", report);
+
+        let mut stage = SystemStage::parallel()
+            .with_ambiguity_detection(AmbiguityDetection::Panic)
+            .with_system(resource.system())
+            .with_system(resource.system());
+        let report = ambiguity_panic_message(&mut stage, &mut world);
+        assert!(report.contains("resource usize"), "{}", report);
+
+        let mut stage = SystemStage::parallel()
+            .with_ambiguity_detection(AmbiguityDetection::Panic)
+            .with_system(resource.system().label("0"))
+            .with_system(resource.system().ambiguous_with("0"));
+        stage.run(&mut world);
+    }
+
     #[test]
     fn ambiguity_detection() {
         use super::{find_ambiguities, SystemContainer};
@@ -2033,8 +2213,8 @@ mod tests {
 
     #[test]
     fn change_ticks_wrapover() {
-        const MIN_TIME_SINCE_LAST_CHECK: u32 = u32::MAX / 8;
-        const MAX_DELTA: u32 = (u32::MAX / 4) * 3;
+        const MIN_TIME_SINCE_LAST_CHECK: u32 = CHECK_TICK_THRESHOLD;
+        const MAX_DELTA: u32 = MAX_CHANGE_AGE;
 
         let mut world = World::new();
         world.spawn().insert(0usize);
@@ -2062,6 +2242,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn change_ticks_of_idle_stage() {
+        struct C;
+        fn count_changed(query: Query<&C, Changed<C>>, mut count: ResMut<usize>) {
+            *count += query.iter().count();
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0usize);
+        world.spawn().insert(C);
+        let mut stage = SystemStage::parallel();
+        stage.add_system(count_changed.system());
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<usize>().unwrap(), 1);
+
+        // other stages keep the world running for most of the tick range while this one is idle
+        for _ in 0..7 {
+            let change_tick = world.change_tick.get_mut();
+            *change_tick = change_tick.wrapping_add(CHECK_TICK_THRESHOLD);
+            world.clear_trackers();
+        }
+        stage.run(&mut world);
+        assert_eq!(*world.get_resource::<usize>().unwrap(), 1);
+    }
+
     #[test]
     fn change_query_wrapover() {
         struct C;
@@ -2169,4 +2374,52 @@ mod tests {
         stage_spawn.run(&mut world);
         assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![0, 2]);
     }
+
+    #[test]
+    fn replace_system() {
+        let mut world = World::new();
+        world.insert_resource(Vec::<usize>::new());
+        let mut stage = SystemStage::parallel()
+            .with_system(make_parallel!(0).system().label("0"))
+            .with_system(make_parallel!(1).system().label("1").after("0"))
+            .with_system(make_parallel!(2).system().label("2").after("1"));
+        stage.run(&mut world);
+        assert!(stage.replace_system("1", make_parallel!(10).system()));
+        assert!(!stage.replace_system("3", make_parallel!(30).system()));
+        stage.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<Vec<usize>>().unwrap(),
+            vec![0, 1, 2, 0, 10, 2]
+        );
+
+        let mut schedule = Schedule::default().with_stage("stage", stage);
+        assert!(schedule.replace_system("2", make_parallel!(20).system()));
+        schedule.run_once(&mut world);
+        assert_eq!(
+            *world.get_resource::<Vec<usize>>().unwrap(),
+            vec![0, 1, 2, 0, 10, 2, 0, 10, 20]
+        );
+    }
+
+    #[test]
+    fn flush_reserved_entities() {
+        fn reserve(entities: &Entities, mut reserved: ResMut<Vec<Entity>>) {
+            let task_pool = TaskPool::new();
+            reserved.extend(task_pool.scope(|scope| {
+                for _ in 0..4 {
+                    scope.spawn(async move { entities.reserve_entity() });
+                }
+            }));
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Vec::<Entity>::new());
+        let mut stage = SystemStage::parallel().with_system(reserve.system());
+        stage.run(&mut world);
+        let reserved = world.get_resource::<Vec<Entity>>().unwrap().clone();
+        assert_eq!(reserved.len(), 4);
+        for entity in reserved {
+            assert!(world.get_entity(entity).is_some());
+        }
+    }
 }
@@ -104,55 +104,26 @@ where
         .label_discard_if_duplicate(StateCallback::Update.into_label(s))
     }
 
+    /// Runs while `s` is in the stack, but isn't the current state, such as a game paused by a
+    /// menu pushed over it.
     pub fn on_inactive_update(s: T) -> RunCriteriaDescriptor {
-        (|state: Res<State<T>>, mut is_inactive: Local<bool>, pred: Local<Option<T>>| match &state
-            .transition
-        {
-            Some(StateTransition::Pausing(ref relevant, _))
-            | Some(StateTransition::Resuming(_, ref relevant)) => {
-                if relevant == pred.as_ref().unwrap() {
-                    *is_inactive = !*is_inactive;
-                }
-                false
-            }
-            Some(_) => false,
-            None => *is_inactive,
+        (|state: Res<State<T>>, pred: Local<Option<T>>| {
+            state.transition.is_none() && state.inactives().contains(pred.as_ref().unwrap())
         })
         .system()
-        .config(|(_, _, pred)| *pred = Some(Some(s.clone())))
+        .config(|(_, pred)| *pred = Some(Some(s.clone())))
         .chain(should_run_adapter::<T>.system())
         .after(DriverLabel::of::<T>())
         .label_discard_if_duplicate(StateCallback::InactiveUpdate.into_label(s))
     }
 
+    /// Runs while `s` is in the stack, whether it's the current state or not.
     pub fn on_in_stack_update(s: T) -> RunCriteriaDescriptor {
-        (|state: Res<State<T>>, mut is_in_stack: Local<bool>, pred: Local<Option<T>>| match &state
-            .transition
-        {
-            Some(StateTransition::Entering(ref relevant, _))
-            | Some(StateTransition::ExitingToResume(_, ref relevant)) => {
-                if relevant == pred.as_ref().unwrap() {
-                    *is_in_stack = !*is_in_stack;
-                }
-                false
-            }
-            Some(StateTransition::ExitingFull(_, ref relevant)) => {
-                if relevant == pred.as_ref().unwrap() {
-                    *is_in_stack = !*is_in_stack;
-                }
-                false
-            }
-            Some(StateTransition::Startup) => {
-                if state.stack.last().unwrap() == pred.as_ref().unwrap() {
-                    *is_in_stack = !*is_in_stack;
-                }
-                false
-            }
-            Some(_) => false,
-            None => *is_in_stack,
+        (|state: Res<State<T>>, pred: Local<Option<T>>| {
+            state.transition.is_none() && state.stack.contains(pred.as_ref().unwrap())
         })
         .system()
-        .config(|(_, _, pred)| *pred = Some(Some(s.clone())))
+        .config(|(_, pred)| *pred = Some(Some(s.clone())))
         .chain(should_run_adapter::<T>.system())
         .after(DriverLabel::of::<T>())
         .label_discard_if_duplicate(StateCallback::InStackUpdate.into_label(s))
@@ -238,6 +209,10 @@ where
         SystemSet::new().with_run_criteria(Self::on_inactive_update(s))
     }
 
+    pub fn on_in_stack_update_set(s: T) -> SystemSet {
+        SystemSet::new().with_run_criteria(Self::on_in_stack_update(s))
+    }
+
     pub fn on_enter_set(s: T) -> SystemSet {
         SystemSet::new().with_run_criteria(Self::on_enter(s))
     }
@@ -655,6 +630,65 @@ mod test {
         );
     }
 
+    #[test]
+    fn push_pop() {
+        #[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+        enum AppState {
+            InGame,
+            Paused,
+        }
+
+        let mut world = World::default();
+        world.insert_resource(State::new(AppState::InGame));
+        world.insert_resource(Vec::<&'static str>::new());
+
+        let mut stage = SystemStage::parallel();
+        stage
+            .add_system_set(State::<AppState>::get_driver())
+            .add_system_set(
+                State::on_update_set(AppState::InGame).with_system(
+                    (|mut r: ResMut<Vec<&'static str>>| r.push("update InGame")).system(),
+                ),
+            )
+            .add_system_set(State::on_inactive_update_set(AppState::InGame).with_system(
+                (|mut r: ResMut<Vec<&'static str>>| r.push("inactive InGame")).system(),
+            ))
+            .add_system_set(State::on_in_stack_update_set(AppState::InGame).with_system(
+                (|mut r: ResMut<Vec<&'static str>>| r.push("in stack InGame")).system(),
+            ))
+            .add_system_set(State::on_in_stack_update_set(AppState::Paused).with_system(
+                (|mut r: ResMut<Vec<&'static str>>| r.push("in stack Paused")).system(),
+            ));
+        let mut run = |world: &mut World| {
+            stage.run(world);
+            let mut runs =
+                std::mem::take(&mut *world.get_resource_mut::<Vec<&'static str>>().unwrap());
+            runs.sort_unstable();
+            runs
+        };
+
+        assert_eq!(run(&mut world), vec!["in stack InGame", "update InGame"]);
+        world
+            .get_resource_mut::<State<AppState>>()
+            .unwrap()
+            .push(AppState::Paused)
+            .unwrap();
+        assert_eq!(
+            run(&mut world),
+            vec!["in stack InGame", "in stack Paused", "inactive InGame"]
+        );
+        assert_eq!(
+            run(&mut world),
+            vec!["in stack InGame", "in stack Paused", "inactive InGame"]
+        );
+        world
+            .get_resource_mut::<State<AppState>>()
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert_eq!(run(&mut world), vec!["in stack InGame", "update InGame"]);
+    }
+
     #[test]
     fn issue_1753() {
         #[derive(Clone, PartialEq, Eq, Debug, Hash)]
@@ -5,7 +5,7 @@ use crate::{
         BoxedAmbiguitySetLabel, BoxedRunCriteriaLabel, BoxedSystemLabel, ExclusiveSystemDescriptor,
         GraphNode, ParallelSystemDescriptor,
     },
-    system::{ExclusiveSystem, System},
+    system::{BoxedSystem, ExclusiveSystem, System},
 };
 use std::{borrow::Cow, cell::UnsafeCell};
 
@@ -21,6 +21,7 @@ pub trait SystemContainer: GraphNode<Label = BoxedSystemLabel> {
     fn set_run_criteria(&mut self, index: usize);
     fn run_criteria_label(&self) -> Option<&BoxedRunCriteriaLabel>;
     fn ambiguity_sets(&self) -> &[BoxedAmbiguitySetLabel];
+    fn ambiguous_with(&self) -> &[BoxedSystemLabel];
     fn component_access(&self) -> Option<&Access<ComponentId>>;
 }
 
@@ -33,6 +34,7 @@ pub(super) struct ExclusiveSystemContainer {
     before: Vec<BoxedSystemLabel>,
     after: Vec<BoxedSystemLabel>,
     ambiguity_sets: Vec<BoxedAmbiguitySetLabel>,
+    ambiguous_with: Vec<BoxedSystemLabel>,
 }
 
 impl ExclusiveSystemContainer {
@@ -46,6 +48,7 @@ impl ExclusiveSystemContainer {
             before: descriptor.before,
             after: descriptor.after,
             ambiguity_sets: descriptor.ambiguity_sets,
+            ambiguous_with: descriptor.ambiguous_with,
         }
     }
 
@@ -100,6 +103,10 @@ impl SystemContainer for ExclusiveSystemContainer {
         &self.ambiguity_sets
     }
 
+    fn ambiguous_with(&self) -> &[BoxedSystemLabel] {
+        &self.ambiguous_with
+    }
+
     fn component_access(&self) -> Option<&Access<ComponentId>> {
         None
     }
@@ -115,6 +122,7 @@ pub struct ParallelSystemContainer {
     before: Vec<BoxedSystemLabel>,
     after: Vec<BoxedSystemLabel>,
     ambiguity_sets: Vec<BoxedAmbiguitySetLabel>,
+    ambiguous_with: Vec<BoxedSystemLabel>,
 }
 
 unsafe impl Send for ParallelSystemContainer {}
@@ -133,6 +141,7 @@ impl ParallelSystemContainer {
             before: descriptor.before,
             after: descriptor.after,
             ambiguity_sets: descriptor.ambiguity_sets,
+            ambiguous_with: descriptor.ambiguous_with,
         }
     }
 
@@ -149,6 +158,12 @@ impl ParallelSystemContainer {
         self.system.get_mut()
     }
 
+    /// Replaces the system, keeping its labels, ordering and run criteria.
+    pub(crate) fn set_system(&mut self, system: BoxedSystem<(), ()>) {
+        // SAFE: it is fine to wrap inner value with UnsafeCell, as it is repr(transparent)
+        self.system = unsafe { Box::from_raw(Box::into_raw(system) as *mut _) };
+    }
+
     /// # Safety
     /// Ensure no other borrows exist along with this one.
     #[allow(clippy::mut_from_ref)]
@@ -211,6 +226,10 @@ impl SystemContainer for ParallelSystemContainer {
         &self.ambiguity_sets
     }
 
+    fn ambiguous_with(&self) -> &[BoxedSystemLabel] {
+        &self.ambiguous_with
+    }
+
     fn component_access(&self) -> Option<&Access<ComponentId>> {
         Some(self.system().component_access())
     }
@@ -3,7 +3,10 @@ use crate::{
         AmbiguitySetLabel, BoxedAmbiguitySetLabel, BoxedSystemLabel, IntoRunCriteria,
         RunCriteriaDescriptorOrLabel, SystemLabel,
     },
-    system::{BoxedSystem, ExclusiveSystem, ExclusiveSystemCoerced, ExclusiveSystemFn, IntoSystem},
+    system::{
+        BoxedSystem, ExclusiveSystem, ExclusiveSystemCoerced, ExclusiveSystemFn,
+        ExclusiveSystemStateFn, IntoSystem, SystemParam,
+    },
 };
 
 /// Encapsulates a system and information on when it run in a `SystemStage`.
@@ -84,6 +87,12 @@ impl IntoSystemDescriptor<()> for ExclusiveSystemCoerced {
     }
 }
 
+impl<Param: SystemParam + 'static> IntoSystemDescriptor<()> for ExclusiveSystemStateFn<Param> {
+    fn into_descriptor(self) -> SystemDescriptor {
+        new_exclusive_descriptor(Box::new(self)).into_descriptor()
+    }
+}
+
 /// Encapsulates a parallel system and information on when it runs in a `SystemStage`.
 pub struct ParallelSystemDescriptor {
     pub(crate) system: BoxedSystem<(), ()>,
@@ -92,6 +101,7 @@ pub struct ParallelSystemDescriptor {
     pub(crate) before: Vec<BoxedSystemLabel>,
     pub(crate) after: Vec<BoxedSystemLabel>,
     pub(crate) ambiguity_sets: Vec<BoxedAmbiguitySetLabel>,
+    pub(crate) ambiguous_with: Vec<BoxedSystemLabel>,
 }
 
 fn new_parallel_descriptor(system: BoxedSystem<(), ()>) -> ParallelSystemDescriptor {
@@ -102,6 +112,7 @@ fn new_parallel_descriptor(system: BoxedSystem<(), ()>) -> ParallelSystemDescrip
         before: Vec::new(),
         after: Vec::new(),
         ambiguity_sets: Vec::new(),
+        ambiguous_with: Vec::new(),
     }
 }
 
@@ -125,6 +136,11 @@ pub trait ParallelSystemDescriptorCoercion<Params> {
     /// Specifies that the system is exempt from execution order ambiguity detection
     /// with other systems in this set.
     fn in_ambiguity_set(self, set: impl AmbiguitySetLabel) -> ParallelSystemDescriptor;
+
+    /// Specifies that the system is exempt from execution order ambiguity detection with the
+    /// systems with the given label, e.g. because they access the same data without caring about
+    /// their order.
+    fn ambiguous_with(self, label: impl SystemLabel) -> ParallelSystemDescriptor;
 }
 
 impl ParallelSystemDescriptorCoercion<()> for ParallelSystemDescriptor {
@@ -155,6 +171,11 @@ impl ParallelSystemDescriptorCoercion<()> for ParallelSystemDescriptor {
         self.ambiguity_sets.push(Box::new(set));
         self
     }
+
+    fn ambiguous_with(mut self, label: impl SystemLabel) -> ParallelSystemDescriptor {
+        self.ambiguous_with.push(Box::new(label));
+        self
+    }
 }
 
 impl<S, Params> ParallelSystemDescriptorCoercion<Params> for S
@@ -183,6 +204,10 @@ where
     fn in_ambiguity_set(self, set: impl AmbiguitySetLabel) -> ParallelSystemDescriptor {
         new_parallel_descriptor(Box::new(self.system())).in_ambiguity_set(set)
     }
+
+    fn ambiguous_with(self, label: impl SystemLabel) -> ParallelSystemDescriptor {
+        new_parallel_descriptor(Box::new(self.system())).ambiguous_with(label)
+    }
 }
 
 impl ParallelSystemDescriptorCoercion<()> for BoxedSystem<(), ()> {
@@ -208,6 +233,10 @@ impl ParallelSystemDescriptorCoercion<()> for BoxedSystem<(), ()> {
     fn in_ambiguity_set(self, set: impl AmbiguitySetLabel) -> ParallelSystemDescriptor {
         new_parallel_descriptor(self).in_ambiguity_set(set)
     }
+
+    fn ambiguous_with(self, label: impl SystemLabel) -> ParallelSystemDescriptor {
+        new_parallel_descriptor(self).ambiguous_with(label)
+    }
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -225,6 +254,7 @@ pub struct ExclusiveSystemDescriptor {
     pub(crate) before: Vec<BoxedSystemLabel>,
     pub(crate) after: Vec<BoxedSystemLabel>,
     pub(crate) ambiguity_sets: Vec<BoxedAmbiguitySetLabel>,
+    pub(crate) ambiguous_with: Vec<BoxedSystemLabel>,
     pub(crate) insertion_point: InsertionPoint,
 }
 
@@ -236,6 +266,7 @@ fn new_exclusive_descriptor(system: Box<dyn ExclusiveSystem>) -> ExclusiveSystem
         before: Vec::new(),
         after: Vec::new(),
         ambiguity_sets: Vec::new(),
+        ambiguous_with: Vec::new(),
         insertion_point: InsertionPoint::AtStart,
     }
 }
@@ -261,6 +292,11 @@ pub trait ExclusiveSystemDescriptorCoercion {
     /// with other systems in this set.
     fn in_ambiguity_set(self, set: impl AmbiguitySetLabel) -> ExclusiveSystemDescriptor;
 
+    /// Specifies that the system is exempt from execution order ambiguity detection with the
+    /// systems with the given label, e.g. because they access the same data without caring about
+    /// their order.
+    fn ambiguous_with(self, label: impl SystemLabel) -> ExclusiveSystemDescriptor;
+
     /// Specifies that the system should run with other exclusive systems at the start of stage.
     fn at_start(self) -> ExclusiveSystemDescriptor;
 
@@ -301,6 +337,11 @@ impl ExclusiveSystemDescriptorCoercion for ExclusiveSystemDescriptor {
         self
     }
 
+    fn ambiguous_with(mut self, label: impl SystemLabel) -> ExclusiveSystemDescriptor {
+        self.ambiguous_with.push(Box::new(label));
+        self
+    }
+
     fn at_start(mut self) -> ExclusiveSystemDescriptor {
         self.insertion_point = InsertionPoint::AtStart;
         self
@@ -344,6 +385,10 @@ where
         new_exclusive_descriptor(Box::new(self)).in_ambiguity_set(set)
     }
 
+    fn ambiguous_with(self, label: impl SystemLabel) -> ExclusiveSystemDescriptor {
+        new_exclusive_descriptor(Box::new(self)).ambiguous_with(label)
+    }
+
     fn at_start(self) -> ExclusiveSystemDescriptor {
         new_exclusive_descriptor(Box::new(self)).at_start()
     }
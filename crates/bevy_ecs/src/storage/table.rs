@@ -237,6 +237,24 @@ impl Table {
         }
     }
 
+    /// Removes the entity at the given row without dropping its components, which the caller must
+    /// have moved out. Returns the entity swapped in to replace it (if an entity was swapped in)
+    ///
+    /// # Safety
+    /// `row` must be in-bounds
+    pub(crate) unsafe fn swap_remove_and_forget_unchecked(&mut self, row: usize) -> Option<Entity> {
+        for column in self.columns.values_mut() {
+            column.swap_remove_and_forget_unchecked(row);
+        }
+        let is_last = row == self.entities.len() - 1;
+        self.entities.swap_remove(row);
+        if is_last {
+            None
+        } else {
+            Some(self.entities[row])
+        }
+    }
+
     /// Moves the `row` column values to `new_table`, for the columns shared between both tables.
     /// Returns the index of the new row in `new_table` and the entity in this table swapped in
     /// to replace it (if an entity was swapped in). missing columns will be "forgotten". It is
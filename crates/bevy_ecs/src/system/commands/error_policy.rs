@@ -0,0 +1,106 @@
+use super::Command;
+use crate::{entity::Entity, world::World};
+use bevy_utils::tracing::{debug, error};
+use thiserror::Error;
+
+/// An error that occurred while applying a [`Command`] to the [`World`].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    #[error("Could not insert {component} into entity {entity:?} because it does not exist.")]
+    NoSuchEntity {
+        entity: Entity,
+        component: &'static str,
+    },
+    #[error("Could not remove {component} from entity {entity:?} because it does not exist.")]
+    RemoveFromMissingEntity {
+        entity: Entity,
+        component: &'static str,
+    },
+    #[error("Could not despawn entity {entity:?} because it does not exist.")]
+    DespawnMissingEntity { entity: Entity },
+    #[error("Could not clone entity {entity:?} because it does not exist.")]
+    CloneMissingEntity { entity: Entity },
+}
+
+/// What to do when a command fails, e.g. because it targets an entity despawned earlier in the
+/// frame.
+///
+/// The policy of a command is set with
+/// [`EntityCommands::on_error`](super::EntityCommands::on_error) or
+/// [`FallibleCommand::on_error`]. It defaults to the `CommandErrorPolicy` resource of the
+/// [`World`], if any, then to the [`FallibleCommand::DEFAULT_ERROR_POLICY`] of the command:
+/// inserts panic, while removals and despawns are skipped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandErrorPolicy {
+    /// Panics with the error
+    Panic,
+    /// Logs the error, and skips the command
+    Log,
+    /// Silently skips the command
+    Ignore,
+}
+
+impl Default for CommandErrorPolicy {
+    fn default() -> Self {
+        CommandErrorPolicy::Panic
+    }
+}
+
+impl CommandErrorPolicy {
+    /// Returns the policy of `world` if it has one, or `default`.
+    pub fn resolve(world: &World, default: CommandErrorPolicy) -> CommandErrorPolicy {
+        world
+            .get_resource::<CommandErrorPolicy>()
+            .copied()
+            .unwrap_or(default)
+    }
+
+    /// Handles `error` according to this policy.
+    pub fn handle(self, error: CommandError) {
+        match self {
+            CommandErrorPolicy::Panic => panic!("{}", error),
+            CommandErrorPolicy::Log => error!("{}", error),
+            CommandErrorPolicy::Ignore => debug!("{}", error),
+        }
+    }
+}
+
+/// A [`Command`] that can fail, and whose failures are handled by a [`CommandErrorPolicy`].
+pub trait FallibleCommand: Command + Sized {
+    /// The policy used when neither the command nor the [`World`] sets one.
+    const DEFAULT_ERROR_POLICY: CommandErrorPolicy;
+
+    /// Applies the command, or returns why it can't be applied.
+    fn try_write(self, world: &mut World) -> Result<(), CommandError>;
+
+    /// Handles the failures of this command with `error_policy`, instead of the policy of the
+    /// [`World`].
+    fn on_error(self, error_policy: CommandErrorPolicy) -> WithErrorPolicy<Self> {
+        WithErrorPolicy {
+            command: self,
+            error_policy,
+        }
+    }
+}
+
+/// Applies a [`FallibleCommand`], handling its failure with the policy of `world`.
+pub(crate) fn write_fallible<C: FallibleCommand>(command: C, world: &mut World) {
+    if let Err(error) = command.try_write(world) {
+        CommandErrorPolicy::resolve(world, C::DEFAULT_ERROR_POLICY).handle(error);
+    }
+}
+
+/// A [`FallibleCommand`] with its own [`CommandErrorPolicy`], created with
+/// [`FallibleCommand::on_error`].
+pub struct WithErrorPolicy<C> {
+    command: C,
+    error_policy: CommandErrorPolicy,
+}
+
+impl<C: FallibleCommand> Command for WithErrorPolicy<C> {
+    fn write(self, world: &mut World) {
+        if let Err(error) = self.command.try_write(world) {
+            self.error_policy.handle(error);
+        }
+    }
+}
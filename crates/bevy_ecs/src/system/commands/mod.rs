@@ -1,14 +1,21 @@
 mod command_queue;
+mod error_policy;
+mod parallel_scope;
 
 use crate::{
     bundle::Bundle,
     component::Component,
     entity::{Entities, Entity},
+    observer::{EntityEvent, Observe},
     world::World,
 };
-use bevy_utils::tracing::debug;
 pub use command_queue::CommandQueue;
-use std::marker::PhantomData;
+pub use error_policy::*;
+pub use parallel_scope::*;
+use std::{
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
 
 /// A [`World`] mutation.
 pub trait Command: Send + Sync + 'static {
@@ -30,7 +37,11 @@ impl<'a> Commands<'a> {
         }
     }
 
-    /// Creates a new empty [`Entity`] and returns an [`EntityCommands`] builder for it.
+    /// Creates a new empty [`Entity`] and returns a [`SpawnCommands`] builder for it.
+    ///
+    /// The entity is reserved right away, so its [`EntityPlaceholder`] can be referenced by the
+    /// commands queued after this one, e.g. to insert it into another entity's component. It
+    /// exists once the commands are applied.
     ///
     /// # Example
     ///
@@ -38,8 +49,11 @@ impl<'a> Commands<'a> {
     /// use bevy_ecs::prelude::*;
     ///
     /// fn example_system(mut commands: Commands) {
-    ///     // Create a new empty entity and retrieve its id.
-    ///     let empty_entity = commands.spawn().id();
+    ///     // Create a new empty entity and retrieve its placeholder.
+    ///     let empty_entity = commands.spawn().placeholder();
+    ///
+    ///     // Reference it from another entity
+    ///     commands.spawn().insert(empty_entity.entity());
     ///
     ///     // Create another empty entity, then add some component to it
     ///     commands.spawn()
@@ -50,17 +64,20 @@ impl<'a> Commands<'a> {
     /// }
     /// # example_system.system();
     /// ```
-    pub fn spawn(&mut self) -> EntityCommands<'a, '_> {
+    pub fn spawn(&mut self) -> SpawnCommands<'a, '_> {
         let entity = self.entities.reserve_entity();
-        EntityCommands {
-            entity,
-            commands: self,
+        SpawnCommands {
+            entity_commands: EntityCommands {
+                entity,
+                error_policy: None,
+                commands: self,
+            },
         }
     }
 
     /// Creates a new entity with the components contained in `bundle`.
     ///
-    /// This returns an [`EntityCommands`] builder, which enables inserting more components and
+    /// This returns a [`SpawnCommands`] builder, which enables inserting more components and
     /// bundles using a "builder pattern".
     ///
     /// Note that `bundle` is a [`Bundle`], which is a collection of components. [`Bundle`] is
@@ -98,7 +115,7 @@ impl<'a> Commands<'a> {
     /// }
     /// # example_system.system();
     /// ```
-    pub fn spawn_bundle<'b, T: Bundle>(&'b mut self, bundle: T) -> EntityCommands<'a, 'b> {
+    pub fn spawn_bundle<'b, T: Bundle>(&'b mut self, bundle: T) -> SpawnCommands<'a, 'b> {
         let mut e = self.spawn();
         e.insert_bundle(bundle);
         e
@@ -126,6 +143,19 @@ impl<'a> Commands<'a> {
     pub fn entity(&mut self, entity: Entity) -> EntityCommands<'a, '_> {
         EntityCommands {
             entity,
+            error_policy: None,
+            commands: self,
+        }
+    }
+
+    /// Reserves a new [`Entity`] that will get a copy of the components of `source`, see
+    /// [`World::clone_entity`], and returns an [`EntityCommands`] builder for it.
+    pub fn clone_entity(&mut self, source: Entity) -> EntityCommands<'a, '_> {
+        let target = self.entities.reserve_entity();
+        self.add(CloneEntity { source, target });
+        EntityCommands {
+            entity: target,
+            error_policy: None,
             commands: self,
         }
     }
@@ -161,6 +191,7 @@ impl<'a> Commands<'a> {
 /// A list of commands that will be run to modify an [`Entity`].
 pub struct EntityCommands<'a, 'b> {
     entity: Entity,
+    error_policy: Option<CommandErrorPolicy>,
     commands: &'b mut Commands<'a>,
 }
 
@@ -171,9 +202,40 @@ impl<'a, 'b> EntityCommands<'a, 'b> {
         self.entity
     }
 
+    /// Sets what to do when the commands queued afterwards by this builder fail, e.g. because the
+    /// entity was despawned before they are applied, instead of the [`World`]'s
+    /// [`CommandErrorPolicy`]. This applies to inserts, removals and despawns.
+    ///
+    /// ```
+    /// use bevy_ecs::prelude::*;
+    ///
+    /// fn example_system(mut commands: Commands, query: Query<Entity>) {
+    ///     for entity in query.iter() {
+    ///         // the entity might have been despawned by another system's commands
+    ///         commands
+    ///             .entity(entity)
+    ///             .on_error(CommandErrorPolicy::Ignore)
+    ///             .insert("hello world");
+    ///     }
+    /// }
+    /// # example_system.system();
+    /// ```
+    pub fn on_error(&mut self, error_policy: CommandErrorPolicy) -> &mut Self {
+        self.error_policy = Some(error_policy);
+        self
+    }
+
+    /// Queues `command`, with the [`CommandErrorPolicy`] set by [`Self::on_error`] if any.
+    fn add_fallible<C: FallibleCommand>(&mut self, command: C) {
+        match self.error_policy {
+            Some(error_policy) => self.commands.add(command.on_error(error_policy)),
+            None => self.commands.add(command),
+        }
+    }
+
     /// Adds a [`Bundle`] of components to the current entity.
     pub fn insert_bundle(&mut self, bundle: impl Bundle) -> &mut Self {
-        self.commands.add(InsertBundle {
+        self.add_fallible(InsertBundle {
             entity: self.entity,
             bundle,
         });
@@ -213,7 +275,7 @@ impl<'a, 'b> EntityCommands<'a, 'b> {
     /// # example_system.system();
     /// ```
     pub fn insert(&mut self, component: impl Component) -> &mut Self {
-        self.commands.add(Insert {
+        self.add_fallible(Insert {
             entity: self.entity,
             component,
         });
@@ -225,7 +287,7 @@ impl<'a, 'b> EntityCommands<'a, 'b> {
     where
         T: Bundle,
     {
-        self.commands.add(RemoveBundle::<T> {
+        self.add_fallible(RemoveBundle::<T> {
             entity: self.entity,
             phantom: PhantomData,
         });
@@ -237,16 +299,30 @@ impl<'a, 'b> EntityCommands<'a, 'b> {
     where
         T: Component,
     {
-        self.commands.add(Remove::<T> {
+        self.add_fallible(Remove::<T> {
             entity: self.entity,
             phantom: PhantomData,
         });
         self
     }
 
+    /// Runs `observer` for every [`EntityEvent`] of type `E` aimed at the current entity, once
+    /// [`dispatch_entity_events_system`](crate::observer::dispatch_entity_events_system) runs for
+    /// that event type. The callback is removed when the entity is despawned.
+    pub fn observe<E: EntityEvent>(
+        &mut self,
+        observer: impl Fn(&E, &mut Commands) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.commands.add(Observe {
+            entity: self.entity,
+            observer: Box::new(observer),
+        });
+        self
+    }
+
     /// Despawns only the specified entity, not including its children.
     pub fn despawn(&mut self) {
-        self.commands.add(Despawn {
+        self.add_fallible(Despawn {
             entity: self.entity,
         })
     }
@@ -257,6 +333,65 @@ impl<'a, 'b> EntityCommands<'a, 'b> {
     }
 }
 
+/// An entity reserved by [`Commands::spawn`], which exists once the commands queued up to its
+/// spawn are applied.
+///
+/// Unlike an arbitrary [`Entity`], which may have been despawned by the time a command referencing
+/// it is applied, a placeholder is guaranteed to exist for the commands queued after its spawn in
+/// the same [`CommandQueue`], unless they despawn it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EntityPlaceholder {
+    entity: Entity,
+}
+
+impl EntityPlaceholder {
+    /// The id the entity will have once spawned
+    #[inline]
+    pub fn entity(self) -> Entity {
+        self.entity
+    }
+}
+
+impl From<EntityPlaceholder> for Entity {
+    fn from(placeholder: EntityPlaceholder) -> Self {
+        placeholder.entity
+    }
+}
+
+/// An [`EntityCommands`] builder for an entity spawned by [`Commands::spawn`], which gives access
+/// to its [`EntityPlaceholder`].
+pub struct SpawnCommands<'a, 'b> {
+    entity_commands: EntityCommands<'a, 'b>,
+}
+
+impl<'a, 'b> SpawnCommands<'a, 'b> {
+    /// The placeholder of the spawned entity, to reference it from the commands queued afterwards.
+    #[inline]
+    pub fn placeholder(&self) -> EntityPlaceholder {
+        EntityPlaceholder {
+            entity: self.entity_commands.entity,
+        }
+    }
+
+    pub fn into_entity_commands(self) -> EntityCommands<'a, 'b> {
+        self.entity_commands
+    }
+}
+
+impl<'a, 'b> Deref for SpawnCommands<'a, 'b> {
+    type Target = EntityCommands<'a, 'b>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.entity_commands
+    }
+}
+
+impl<'a, 'b> DerefMut for SpawnCommands<'a, 'b> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.entity_commands
+    }
+}
+
 #[derive(Debug)]
 pub struct Spawn<T> {
     pub bundle: T,
@@ -296,9 +431,47 @@ pub struct Despawn {
 
 impl Command for Despawn {
     fn write(self, world: &mut World) {
-        if !world.despawn(self.entity) {
-            debug!("Failed to despawn non-existent entity {:?}", self.entity);
+        write_fallible(self, world);
+    }
+}
+
+impl FallibleCommand for Despawn {
+    const DEFAULT_ERROR_POLICY: CommandErrorPolicy = CommandErrorPolicy::Ignore;
+
+    fn try_write(self, world: &mut World) -> Result<(), CommandError> {
+        if world.despawn(self.entity) {
+            Ok(())
+        } else {
+            Err(CommandError::DespawnMissingEntity {
+                entity: self.entity,
+            })
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct CloneEntity {
+    pub source: Entity,
+    pub target: Entity,
+}
+
+impl Command for CloneEntity {
+    fn write(self, world: &mut World) {
+        write_fallible(self, world);
+    }
+}
+
+impl FallibleCommand for CloneEntity {
+    const DEFAULT_ERROR_POLICY: CommandErrorPolicy = CommandErrorPolicy::Ignore;
+
+    fn try_write(self, world: &mut World) -> Result<(), CommandError> {
+        if !world.entities().contains(self.source) {
+            return Err(CommandError::CloneMissingEntity {
+                entity: self.source,
+            });
         }
+        world.clone_components(self.source, self.target, |_| true);
+        Ok(())
     }
 }
 
@@ -312,7 +485,26 @@ where
     T: Bundle + 'static,
 {
     fn write(self, world: &mut World) {
-        world.entity_mut(self.entity).insert_bundle(self.bundle);
+        write_fallible(self, world);
+    }
+}
+
+impl<T> FallibleCommand for InsertBundle<T>
+where
+    T: Bundle + 'static,
+{
+    const DEFAULT_ERROR_POLICY: CommandErrorPolicy = CommandErrorPolicy::Panic;
+
+    fn try_write(self, world: &mut World) -> Result<(), CommandError> {
+        let mut entity_mut =
+            world
+                .get_entity_mut(self.entity)
+                .ok_or(CommandError::NoSuchEntity {
+                    entity: self.entity,
+                    component: std::any::type_name::<T>(),
+                })?;
+        entity_mut.insert_bundle(self.bundle);
+        Ok(())
     }
 }
 
@@ -327,7 +519,26 @@ where
     T: Component,
 {
     fn write(self, world: &mut World) {
-        world.entity_mut(self.entity).insert(self.component);
+        write_fallible(self, world);
+    }
+}
+
+impl<T> FallibleCommand for Insert<T>
+where
+    T: Component,
+{
+    const DEFAULT_ERROR_POLICY: CommandErrorPolicy = CommandErrorPolicy::Panic;
+
+    fn try_write(self, world: &mut World) -> Result<(), CommandError> {
+        let mut entity_mut =
+            world
+                .get_entity_mut(self.entity)
+                .ok_or(CommandError::NoSuchEntity {
+                    entity: self.entity,
+                    component: std::any::type_name::<T>(),
+                })?;
+        entity_mut.insert(self.component);
+        Ok(())
     }
 }
 
@@ -342,9 +553,26 @@ where
     T: Component,
 {
     fn write(self, world: &mut World) {
-        if let Some(mut entity_mut) = world.get_entity_mut(self.entity) {
-            entity_mut.remove::<T>();
-        }
+        write_fallible(self, world);
+    }
+}
+
+impl<T> FallibleCommand for Remove<T>
+where
+    T: Component,
+{
+    const DEFAULT_ERROR_POLICY: CommandErrorPolicy = CommandErrorPolicy::Ignore;
+
+    fn try_write(self, world: &mut World) -> Result<(), CommandError> {
+        let mut entity_mut =
+            world
+                .get_entity_mut(self.entity)
+                .ok_or(CommandError::RemoveFromMissingEntity {
+                    entity: self.entity,
+                    component: std::any::type_name::<T>(),
+                })?;
+        entity_mut.remove::<T>();
+        Ok(())
     }
 }
 
@@ -359,11 +587,28 @@ where
     T: Bundle,
 {
     fn write(self, world: &mut World) {
-        if let Some(mut entity_mut) = world.get_entity_mut(self.entity) {
-            // remove intersection to gracefully handle components that were removed before running
-            // this command
-            entity_mut.remove_bundle_intersection::<T>();
-        }
+        write_fallible(self, world);
+    }
+}
+
+impl<T> FallibleCommand for RemoveBundle<T>
+where
+    T: Bundle,
+{
+    const DEFAULT_ERROR_POLICY: CommandErrorPolicy = CommandErrorPolicy::Ignore;
+
+    fn try_write(self, world: &mut World) -> Result<(), CommandError> {
+        let mut entity_mut =
+            world
+                .get_entity_mut(self.entity)
+                .ok_or(CommandError::RemoveFromMissingEntity {
+                    entity: self.entity,
+                    component: std::any::type_name::<T>(),
+                })?;
+        // remove intersection to gracefully handle components that were removed before running
+        // this command
+        entity_mut.remove_bundle_intersection::<T>();
+        Ok(())
     }
 }
 
@@ -392,7 +637,9 @@ impl<T: Component> Command for RemoveResource<T> {
 mod tests {
     use crate::{
         component::{ComponentDescriptor, StorageType},
-        system::{CommandQueue, Commands},
+        system::{
+            CommandErrorPolicy, CommandQueue, Commands, EntityPlaceholder, FallibleCommand, Insert,
+        },
         world::World,
     };
     use std::sync::{
@@ -445,6 +692,100 @@ mod tests {
         assert_eq!(results2, vec![]);
     }
 
+    #[test]
+    fn spawned_entity_placeholder() {
+        let mut world = World::default();
+        let mut command_queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut command_queue, &world);
+        let target = commands.spawn().placeholder();
+        let follower = commands.spawn().insert(target).id();
+        commands.entity(target.entity()).insert(1u32);
+        command_queue.apply(&mut world);
+        assert_eq!(world.get::<EntityPlaceholder>(follower), Some(&target));
+        assert_eq!(world.get::<u32>(target.entity()), Some(&1));
+    }
+
+    #[test]
+    fn insert_into_despawned_entity() {
+        let mut world = World::default();
+        let entity = world.spawn().id();
+        let mut command_queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut command_queue, &world);
+        commands.entity(entity).despawn();
+        commands
+            .entity(entity)
+            .on_error(CommandErrorPolicy::Ignore)
+            .insert(1u32)
+            .insert_bundle((2u64,));
+        command_queue.apply(&mut world);
+        assert!(world.get_entity(entity).is_none());
+
+        world.insert_resource(CommandErrorPolicy::Log);
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .insert(1u32);
+        command_queue.apply(&mut world);
+
+        let mut commands = Commands::new(&mut command_queue, &world);
+        commands.add(
+            Insert {
+                entity,
+                component: 1u32,
+            }
+            .on_error(CommandErrorPolicy::Ignore),
+        );
+        command_queue.apply(&mut world);
+    }
+
+    #[test]
+    #[should_panic(expected = "Could not despawn entity")]
+    fn despawn_missing_entity_with_policy() {
+        let mut world = World::default();
+        let entity = world.spawn().id();
+        let mut command_queue = CommandQueue::default();
+        let mut commands = Commands::new(&mut command_queue, &world);
+        commands.entity(entity).despawn();
+        commands
+            .entity(entity)
+            .remove::<u32>()
+            .remove_bundle::<(u32,)>();
+        command_queue.apply(&mut world);
+
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .on_error(CommandErrorPolicy::Panic)
+            .despawn();
+        command_queue.apply(&mut world);
+    }
+
+    #[test]
+    #[should_panic(expected = "because it does not exist")]
+    fn insert_into_despawned_entity_panics() {
+        let mut world = World::default();
+        let entity = world.spawn().id();
+        world.despawn(entity);
+        let mut command_queue = CommandQueue::default();
+        Commands::new(&mut command_queue, &world)
+            .entity(entity)
+            .insert(1u32);
+        command_queue.apply(&mut world);
+    }
+
+    #[test]
+    fn clone_entity() {
+        let mut world = World::default();
+        world.register_clone::<u32>();
+        let source = world.spawn().insert_bundle((1u32, 2u64)).id();
+        let mut command_queue = CommandQueue::default();
+        let copy = Commands::new(&mut command_queue, &world)
+            .clone_entity(source)
+            .insert(3u64)
+            .id();
+        command_queue.apply(&mut world);
+        assert_eq!(world.get::<u32>(copy), Some(&1));
+        assert_eq!(world.get::<u64>(copy), Some(&3));
+    }
+
     #[test]
     fn remove_components() {
         let mut world = World::default();
@@ -0,0 +1,144 @@
+use super::{CommandQueue, Commands};
+use crate::{
+    system::{
+        ReadOnlySystemParamFetch, SystemMeta, SystemParam, SystemParamFetch, SystemParamState,
+    },
+    world::World,
+};
+use std::cell::Cell;
+use thread_local::ThreadLocal;
+
+/// A [`SystemParam`] issuing [`Commands`] from several threads at once, such as from the closure
+/// of [`Query::par_for_each`](crate::system::Query::par_for_each).
+///
+/// Each thread writes to its own command queue, which is reused by all the calls to
+/// [`ParallelCommands::command_scope`] on that thread. The queues are applied one after the other
+/// at the end of the stage, like the queue of [`Commands`], so commands issued from different
+/// threads run in no particular order.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_tasks::ComputeTaskPool;
+/// # struct Health(f32);
+/// # struct Dead;
+/// fn death_system(
+///     pool: Res<ComputeTaskPool>,
+///     query: Query<(Entity, &Health)>,
+///     commands: ParallelCommands,
+/// ) {
+///     query.par_for_each(&pool, 64, |(entity, health)| {
+///         if health.0 <= 0.0 {
+///             commands.command_scope(|mut commands| {
+///                 commands.entity(entity).insert(Dead);
+///             });
+///         }
+///     });
+/// }
+/// # death_system.system();
+/// ```
+pub struct ParallelCommands<'a> {
+    state: &'a ParallelCommandsState,
+    world: &'a World,
+}
+
+impl<'a> ParallelCommands<'a> {
+    /// Runs `f` with [`Commands`] writing to the queue of the current thread.
+    ///
+    /// Command scopes must not be nested, as the queue of the thread is only available to one
+    /// scope at a time.
+    pub fn command_scope<R>(&self, f: impl FnOnce(Commands) -> R) -> R {
+        let queue_cell = self.state.thread_queues.get_or_default();
+        let mut queue = queue_cell.take();
+        let result = f(Commands::new(&mut queue, self.world));
+        queue_cell.set(queue);
+        result
+    }
+}
+
+/// The [`SystemParamState`] of [`ParallelCommands`]: the command queue of each thread, reused
+/// across runs.
+#[derive(Default)]
+pub struct ParallelCommandsState {
+    thread_queues: ThreadLocal<Cell<CommandQueue>>,
+}
+
+impl<'a> SystemParam for ParallelCommands<'a> {
+    type Fetch = ParallelCommandsState;
+}
+
+// SAFE: ParallelCommands only accesses internal state
+unsafe impl ReadOnlySystemParamFetch for ParallelCommandsState {}
+
+// SAFE: only local state is accessed
+unsafe impl SystemParamState for ParallelCommandsState {
+    type Config = ();
+
+    fn init(_world: &mut World, _system_meta: &mut SystemMeta, _config: Self::Config) -> Self {
+        Default::default()
+    }
+
+    fn apply(&mut self, world: &mut World) {
+        for queue in self.thread_queues.iter_mut() {
+            queue.get_mut().apply(world);
+        }
+    }
+
+    fn default_config() {}
+}
+
+impl<'a> SystemParamFetch<'a> for ParallelCommandsState {
+    type Item = ParallelCommands<'a>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        _system_meta: &SystemMeta,
+        world: &'a World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        ParallelCommands { state, world }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ParallelCommands;
+    use crate::{
+        entity::Entity,
+        schedule::{Stage, SystemStage},
+        system::{IntoSystem, Query, Res},
+        world::World,
+    };
+    use bevy_tasks::TaskPool;
+
+    #[test]
+    fn parallel_commands() {
+        struct Pool(TaskPool);
+        struct Doubled(u32);
+
+        fn double_system(
+            pool: Res<Pool>,
+            query: Query<(Entity, &u32)>,
+            commands: ParallelCommands,
+        ) {
+            query.par_for_each(&pool.0, 4, |(entity, value)| {
+                commands.command_scope(|mut commands| {
+                    commands.entity(entity).insert(Doubled(value * 2));
+                });
+            });
+        }
+
+        let mut world = World::default();
+        world.insert_resource(Pool(TaskPool::new()));
+        world.spawn_batch((0..100u32).map(|i| (i,)));
+        let mut stage = SystemStage::parallel();
+        stage.add_system(double_system.system());
+        stage.run(&mut world);
+
+        let mut query = world.query::<(&u32, &Doubled)>();
+        assert_eq!(query.iter(&world).count(), 100);
+        for (value, doubled) in query.iter(&world) {
+            assert_eq!(doubled.0, value * 2);
+        }
+    }
+}
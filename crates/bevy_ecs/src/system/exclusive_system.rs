@@ -1,6 +1,8 @@
 use crate::{
     archetype::ArchetypeGeneration,
-    system::{check_system_change_tick, BoxedSystem, IntoSystem, SystemId},
+    system::{
+        check_system_change_tick, BoxedSystem, IntoSystem, SystemId, SystemParam, SystemState,
+    },
     world::World,
 };
 use std::borrow::Cow;
@@ -73,6 +75,109 @@ where
     }
 }
 
+type BoxedExclusiveStateFn<Param> =
+    Box<dyn FnMut(&mut World, &mut SystemState<Param>) + Send + Sync + 'static>;
+
+/// An exclusive system taking a cached [`SystemState`] along with the [`World`], so that it can
+/// use queries and resources like a normal system, in between direct accesses to the world.
+///
+/// The state is created when the system is initialized, and its commands are applied after each
+/// run. As the state outlives every run, references in the query types of the state need a
+/// `'static` lifetime, e.g. `Query<&'static Health>`. The lifetimes of the parameters themselves
+/// can be elided.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// struct Health(u32);
+/// struct Despawned(usize);
+///
+/// fn cleanup(
+///     world: &mut World,
+///     state: &mut SystemState<(Commands, Query<(Entity, &'static Health)>, ResMut<Despawned>)>,
+/// ) {
+///     let (mut commands, query, mut despawned) = state.get_mut(world);
+///     for (entity, health) in query.iter() {
+///         if health.0 == 0 {
+///             commands.entity(entity).despawn();
+///             despawned.0 += 1;
+///         }
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.insert_resource(Despawned(0));
+/// world.spawn().insert(Health(0));
+/// let mut stage = SystemStage::single(cleanup.exclusive_system());
+/// stage.run(&mut world);
+/// assert_eq!(world.get_resource::<Despawned>().unwrap().0, 1);
+/// assert_eq!(world.entities().len(), 0);
+/// ```
+pub struct ExclusiveSystemStateFn<Param: SystemParam> {
+    func: BoxedExclusiveStateFn<Param>,
+    state: Option<SystemState<Param>>,
+    name: Cow<'static, str>,
+    id: SystemId,
+    last_change_tick: u32,
+}
+
+impl<Param: SystemParam + 'static> ExclusiveSystem for ExclusiveSystemStateFn<Param> {
+    fn name(&self) -> Cow<'static, str> {
+        self.name.clone()
+    }
+
+    fn id(&self) -> SystemId {
+        self.id
+    }
+
+    fn run(&mut self, world: &mut World) {
+        let state = self
+            .state
+            .as_mut()
+            .expect("an exclusive system is initialized before it runs");
+        let saved_last_tick = world.last_change_tick;
+        world.last_change_tick = self.last_change_tick;
+
+        (self.func)(world, state);
+        state.apply(world);
+
+        let change_tick = world.change_tick.get_mut();
+        self.last_change_tick = *change_tick;
+        *change_tick += 1;
+
+        world.last_change_tick = saved_last_tick;
+    }
+
+    fn initialize(&mut self, world: &mut World) {
+        if self.state.is_none() {
+            self.state = Some(SystemState::new(world));
+        }
+    }
+
+    fn check_change_tick(&mut self, change_tick: u32) {
+        check_system_change_tick(&mut self.last_change_tick, change_tick, self.name.as_ref());
+        if let Some(state) = &mut self.state {
+            state.check_change_tick(change_tick);
+        }
+    }
+}
+
+impl<F, Param> IntoExclusiveSystem<(&mut World, SystemState<Param>), ExclusiveSystemStateFn<Param>>
+    for F
+where
+    F: FnMut(&mut World, &mut SystemState<Param>) + Send + Sync + 'static,
+    Param: SystemParam + 'static,
+{
+    fn exclusive_system(self) -> ExclusiveSystemStateFn<Param> {
+        ExclusiveSystemStateFn {
+            func: Box::new(self),
+            state: None,
+            name: core::any::type_name::<F>().into(),
+            id: SystemId::new(),
+            last_change_tick: 0,
+        }
+    }
+}
+
 pub struct ExclusiveSystemCoerced {
     system: BoxedSystem<(), ()>,
     archetype_generation: ArchetypeGeneration,
@@ -128,7 +233,7 @@ mod tests {
         entity::Entity,
         query::With,
         schedule::{Stage, SystemStage},
-        system::{Commands, IntoExclusiveSystem, IntoSystem, Query, ResMut},
+        system::{Commands, IntoExclusiveSystem, IntoSystem, Query, Res, ResMut, SystemState},
         world::World,
     };
     #[test]
@@ -182,4 +287,35 @@ mod tests {
         stage.run(&mut world);
         assert_eq!(*world.get_resource::<Vec<usize>>().unwrap(), vec![0, 1]);
     }
+
+    #[test]
+    fn exclusive_system_with_state() {
+        struct Speed(u32);
+        struct Position(u32);
+
+        fn step(
+            world: &mut World,
+            state: &mut SystemState<(Res<Speed>, Query<&'static mut Position>)>,
+        ) {
+            let (speed, mut query) = state.get_mut(world);
+            for mut position in query.iter_mut() {
+                position.0 += speed.0;
+            }
+            world.spawn().insert(Position(0));
+        }
+
+        let mut world = World::new();
+        world.insert_resource(Speed(2));
+        world.spawn().insert(Position(0));
+        let mut stage = SystemStage::single(step.exclusive_system());
+        stage.run(&mut world);
+        stage.run(&mut world);
+        let mut positions = world
+            .query::<&Position>()
+            .iter(&world)
+            .map(|position| position.0)
+            .collect::<Vec<_>>();
+        positions.sort_unstable();
+        assert_eq!(positions, vec![0, 2, 4]);
+    }
 }
@@ -35,6 +35,12 @@ impl SystemMeta {
         }
     }
 
+    /// Returns the name of the system.
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
     /// Returns true if the system is [`Send`].
     #[inline]
     pub fn is_send(&self) -> bool {
@@ -52,7 +58,27 @@ impl SystemMeta {
 
 // TODO: Actually use this in FunctionSystem. We should probably only do this once Systems are constructed using a World reference
 // (to avoid the need for unwrapping to retrieve SystemMeta)
-/// Holds on to persistent state required to drive [`SystemParam`] for a [`System`].  
+/// Holds on to persistent state required to drive [`SystemParam`] for a [`System`].
+///
+/// It can also be used to fetch system parameters directly from a [`World`], e.g. in tools, tests
+/// or custom runners. Like a system, it caches the state of its queries and the archetypes they
+/// match, so it should be kept between calls rather than recreated.
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// struct Speed(f32);
+/// struct Position(f32);
+///
+/// let mut world = World::default();
+/// world.insert_resource(Speed(2.0));
+/// world.spawn().insert(Position(1.0));
+///
+/// let mut state: SystemState<(Res<Speed>, Query<&mut Position>)> = SystemState::new(&mut world);
+/// let (speed, mut query) = state.get_mut(&mut world);
+/// for mut position in query.iter_mut() {
+///     position.0 += speed.0;
+/// }
+/// ```
 pub struct SystemState<Param: SystemParam> {
     meta: SystemMeta,
     param_state: <Param as SystemParam>::Fetch,
@@ -115,6 +141,14 @@ impl<Param: SystemParam> SystemState<Param> {
         self.param_state.apply(world);
     }
 
+    pub(crate) fn check_change_tick(&mut self, change_tick: u32) {
+        check_system_change_tick(
+            &mut self.meta.last_change_tick,
+            change_tick,
+            self.meta.name.as_ref(),
+        );
+    }
+
     #[inline]
     pub fn matches_world(&self, world: &World) -> bool {
         self.world_id == world.id()
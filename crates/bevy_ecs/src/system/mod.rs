@@ -27,8 +27,9 @@ mod tests {
         query::{Added, Changed, Or, With, Without},
         schedule::{Schedule, Stage, SystemStage},
         system::{
-            ConfigurableSystem, IntoExclusiveSystem, IntoSystem, Local, Query, QuerySet,
-            RemovedComponents, Res, ResMut, System, SystemState,
+            ignore_err, log_err, panic_on_err, ConfigurableSystem, Deferred, IntoChainSystem,
+            IntoExclusiveSystem, IntoSystem, Local, Query, QuerySet, QuerySingleError,
+            RemovedComponents, Res, ResMut, System, SystemBuffer, SystemState,
         },
         world::{FromWorld, World},
     };
@@ -126,6 +127,43 @@ mod tests {
         assert!(*world.get_resource::<bool>().unwrap(), "system ran");
     }
 
+    #[test]
+    fn query_system_slices() {
+        fn double_system(mut query: Query<&mut u32, Without<A>>) {
+            assert!(query.iter_slices::<u64>().is_err());
+            let mut lengths = query
+                .iter_slices::<u32>()
+                .unwrap()
+                .map(|slice| slice.len())
+                .collect::<Vec<_>>();
+            lengths.sort_unstable();
+            assert_eq!(lengths, vec![3, 5]);
+            for slice in query.iter_slices_mut::<u32>().unwrap() {
+                for value in slice {
+                    *value *= 2;
+                }
+            }
+        }
+
+        let mut world = World::default();
+        world.spawn_batch((0..5u32).map(|i| (i,)));
+        world.spawn_batch((0..3u32).map(|i| (i, 0u64)));
+        world.spawn_batch((0..4u32).map(|i| (i, A)));
+        world.clear_trackers();
+
+        run_system(&mut world, double_system.system());
+
+        let mut changed = world.query_filtered::<(), Changed<u32>>();
+        assert_eq!(changed.iter(&world).count(), 8);
+        let mut values = world
+            .query_filtered::<&u32, Without<A>>()
+            .iter(&world)
+            .copied()
+            .collect::<Vec<_>>();
+        values.sort_unstable();
+        assert_eq!(values, vec![0, 0, 2, 2, 4, 4, 6, 8]);
+    }
+
     #[test]
     fn or_query_set_system() {
         // Regression test for issue #762
@@ -327,6 +365,32 @@ mod tests {
         assert!(*world.get_resource::<bool>().unwrap());
     }
 
+    #[test]
+    fn deferred_system() {
+        #[derive(Default)]
+        struct Counter(Vec<u32>);
+
+        impl SystemBuffer for Counter {
+            fn apply(&mut self, world: &mut World) {
+                let sum = self.0.drain(..).sum::<u32>();
+                *world.get_resource_or_insert_with(|| 0u32) += sum;
+            }
+        }
+
+        fn sys(mut counter: Deferred<Counter>) {
+            counter.0.push(1);
+            counter.0.push(2);
+        }
+
+        let mut world = World::default();
+        let mut update = SystemStage::parallel();
+        update.add_system(sys.system());
+        update.run(&mut world);
+        assert_eq!(*world.get_resource::<u32>().unwrap(), 3);
+        update.run(&mut world);
+        assert_eq!(*world.get_resource::<u32>().unwrap(), 6);
+    }
+
     #[test]
     fn remove_tracking() {
         let mut world = World::new();
@@ -339,7 +403,7 @@ mod tests {
         world.entity_mut(a).despawn();
 
         fn validate_removed(
-            removed_i32: RemovedComponents<i32>,
+            mut removed_i32: RemovedComponents<i32>,
             despawned: Res<Despawned>,
             mut ran: ResMut<bool>,
         ) {
@@ -356,6 +420,85 @@ mod tests {
         assert!(*world.get_resource::<bool>().unwrap(), "system ran");
     }
 
+    #[test]
+    fn removal_tracking_across_frames() {
+        fn count_removed(mut removed: RemovedComponents<i32>, mut count: ResMut<usize>) {
+            *count += removed.iter().count();
+        }
+
+        let mut world = World::new();
+        world.insert_resource(0usize);
+        let mut stage = SystemStage::single(count_removed.system());
+        let a = world.spawn().insert(1).id();
+        let b = world.spawn().insert(2).id();
+        let c = world.spawn().insert(3).id();
+
+        world.entity_mut(a).remove::<i32>();
+        world.clear_trackers();
+        stage.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<usize>().unwrap(),
+            1,
+            "removals are seen after the trackers are cleared once"
+        );
+        stage.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<usize>().unwrap(),
+            1,
+            "removals are read once by each system"
+        );
+
+        world.despawn(b);
+        world.clear_trackers();
+        world.clear_trackers();
+        world.despawn(c);
+        stage.run(&mut world);
+        assert_eq!(
+            *world.get_resource::<usize>().unwrap(),
+            2,
+            "removals are dropped after the trackers are cleared twice"
+        );
+    }
+
+    #[test]
+    fn pipe_result_into_error_handlers() {
+        fn fallible(value: Res<i32>, mut runs: ResMut<usize>) -> Result<i32, String> {
+            *runs += 1;
+            if *value < 0 {
+                Err(format!("negative value {}", *value))
+            } else {
+                Ok(*value)
+            }
+        }
+
+        let mut world = World::default();
+        world.insert_resource(-1);
+        world.insert_resource(0usize);
+        run_system(&mut world, fallible.pipe(log_err));
+        run_system(&mut world, fallible.pipe(ignore_err));
+        *world.get_resource_mut::<i32>().unwrap() = 1;
+        run_system(&mut world, fallible.pipe(panic_on_err));
+        assert_eq!(*world.get_resource::<usize>().unwrap(), 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "negative value -1")]
+    fn pipe_result_into_panic_on_err() {
+        fn fallible(value: Res<i32>) -> Result<(), String> {
+            if *value < 0 {
+                Err(format!("negative value {}", *value))
+            } else {
+                Ok(())
+            }
+        }
+
+        let mut world = World::default();
+        world.insert_resource(-1);
+        let mut system = fallible.pipe(panic_on_err);
+        system.initialize(&mut world);
+        system.run((), &mut world);
+    }
+
     #[test]
     fn configure_system_local() {
         let mut world = World::default();
@@ -566,6 +709,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn query_single_errors() {
+        #[derive(Eq, PartialEq, Debug)]
+        struct A(usize);
+
+        let mut world = World::default();
+        let mut system_state: SystemState<Query<&mut A>> = SystemState::new(&mut world);
+        {
+            let mut query = system_state.get_mut(&mut world);
+            assert!(matches!(
+                query.get_single_mut(),
+                Err(QuerySingleError::NoEntities(_))
+            ));
+        }
+
+        world.spawn().insert(A(1));
+        {
+            let mut query = system_state.get_mut(&mut world);
+            query.get_single_mut().unwrap().0 = 2;
+        }
+        assert_eq!(
+            *world.query::<&A>().iter(&world).next().unwrap(),
+            A(2),
+            "component was mutated through the single result"
+        );
+
+        world.spawn().insert(A(3));
+        let mut system_state: SystemState<Query<&A>> = SystemState::new(&mut world);
+        let query = system_state.get(&world);
+        assert!(matches!(
+            query.get_single(),
+            Err(QuerySingleError::MultipleEntities(_))
+        ));
+    }
+
     #[test]
     fn system_state_change_detection() {
         #[derive(Eq, PartialEq, Debug)]
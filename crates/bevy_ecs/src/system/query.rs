@@ -2,9 +2,10 @@ use crate::{
     component::Component,
     entity::Entity,
     query::{
-        Fetch, FilterFetch, QueryCombinationIter, QueryEntityError, QueryIter, QueryState,
-        ReadOnlyFetch, WorldQuery,
+        ArchetypeFilter, BatchingStrategy, Fetch, FilterFetch, QueryCombinationIter,
+        QueryEntityError, QueryIter, QueryState, ReadOnlyFetch, WorldQuery,
     },
+    storage::Column,
     world::{Mut, World},
 };
 use bevy_tasks::TaskPool;
@@ -298,7 +299,9 @@ where
         };
     }
 
-    /// Runs `f` on each query result in parallel using the given task pool.
+    /// Runs `f` on each query result in parallel using the given task pool, in batches of
+    /// `batch_size` results. See [`Self::par_for_each_batched`] to size the batches from the
+    /// number of results instead.
     ///
     /// This can only be called for read-only queries, see [`Self::par_for_each_mut`] for
     /// write-queries.
@@ -310,6 +313,23 @@ where
         f: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
     ) where
         Q::Fetch: ReadOnlyFetch,
+    {
+        self.par_for_each_batched(task_pool, BatchingStrategy::Fixed(batch_size), f);
+    }
+
+    /// Runs `f` on each query result in parallel using the given task pool, in batches sized by
+    /// `batching`. [`BatchingStrategy::default`] splits the results evenly between the threads.
+    ///
+    /// This can only be called for read-only queries, see [`Self::par_for_each_batched_mut`] for
+    /// write-queries.
+    #[inline]
+    pub fn par_for_each_batched(
+        &self,
+        task_pool: &TaskPool,
+        batching: BatchingStrategy,
+        f: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
+    ) where
+        Q::Fetch: ReadOnlyFetch,
     {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime
         // borrow checks when they conflict
@@ -317,7 +337,7 @@ where
             self.state.par_for_each_unchecked_manual(
                 self.world,
                 task_pool,
-                batch_size,
+                batching,
                 f,
                 self.last_change_tick,
                 self.change_tick,
@@ -325,13 +345,26 @@ where
         };
     }
 
-    /// Runs `f` on each query result in parallel using the given task pool.
+    /// Runs `f` on each query result in parallel using the given task pool, in batches of
+    /// `batch_size` results.
     #[inline]
     pub fn par_for_each_mut(
         &mut self,
         task_pool: &TaskPool,
         batch_size: usize,
         f: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
+    ) {
+        self.par_for_each_batched_mut(task_pool, BatchingStrategy::Fixed(batch_size), f);
+    }
+
+    /// Runs `f` on each query result in parallel using the given task pool, in batches sized by
+    /// `batching`.
+    #[inline]
+    pub fn par_for_each_batched_mut(
+        &mut self,
+        task_pool: &TaskPool,
+        batching: BatchingStrategy,
+        f: impl Fn(<Q::Fetch as Fetch<'w>>::Item) + Send + Sync + Clone,
     ) {
         // SAFE: system runs without conflicts with other systems. same-system queries have runtime
         // borrow checks when they conflict
@@ -339,7 +372,7 @@ where
             self.state.par_for_each_unchecked_manual(
                 self.world,
                 task_pool,
-                batch_size,
+                batching,
                 f,
                 self.last_change_tick,
                 self.change_tick,
@@ -385,6 +418,37 @@ where
         }
     }
 
+    /// Gets the query results for several distinct entities at once, so that they can all be
+    /// mutated at the same time, e.g. to swap data between them. Fails with
+    /// [`QueryEntityError::AliasedMutability`] if an entity is given more than once.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// # struct Health(u32);
+    /// fn swap_health(mut query: Query<&mut Health>, pair: Res<(Entity, Entity)>) {
+    ///     if let Ok([mut a, mut b]) = query.get_multiple_mut([pair.0, pair.1]) {
+    ///         std::mem::swap(&mut a.0, &mut b.0);
+    ///     }
+    /// }
+    /// # swap_health.system();
+    /// ```
+    #[inline]
+    pub fn get_multiple_mut<const N: usize>(
+        &mut self,
+        entities: [Entity; N],
+    ) -> Result<[<Q::Fetch as Fetch>::Item; N], QueryEntityError> {
+        // SAFE: system runs without conflicts with other systems, and the entities are checked
+        // to be distinct. same-system queries have runtime borrow checks when they conflict
+        unsafe {
+            self.state.get_multiple_unchecked_manual(
+                self.world,
+                entities,
+                self.last_change_tick,
+                self.change_tick,
+            )
+        }
+    }
+
     /// Gets the query result for the given [`Entity`].
     ///
     /// # Safety
@@ -482,6 +546,131 @@ where
         }
     }
 
+    /// Returns an [`Iterator`] over the components `T` of this query, as one contiguous slice per
+    /// matched table. This allows processing the components in bulk, e.g. with SIMD instructions.
+    ///
+    /// Tables without entities and tables not storing `T` (possible with `Option<&T>`) are
+    /// skipped. This fails if the query doesn't read `T`, or if it uses components that are not
+    /// stored in tables.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// struct Speed(f32);
+    ///
+    /// fn max_speed_system(query: Query<&Speed>) {
+    ///     let max = query
+    ///         .iter_slices::<Speed>()
+    ///         .unwrap()
+    ///         .flatten()
+    ///         .fold(0.0f32, |max, speed| max.max(speed.0));
+    ///     println!("max speed: {}", max);
+    /// }
+    /// # max_speed_system.system();
+    /// ```
+    #[inline]
+    pub fn iter_slices<T: Component>(
+        &self,
+    ) -> Result<impl Iterator<Item = &'_ [T]> + '_, QueryComponentError>
+    where
+        F: ArchetypeFilter,
+    {
+        let columns = self.table_columns::<T>(false)?;
+        // SAFE: the query has read access to `T`, which is stored in the columns, and the query
+        // visits every row of these columns
+        Ok(columns.map(|column| unsafe {
+            std::slice::from_raw_parts(column.get_data_ptr().cast::<T>().as_ptr(), column.len())
+        }))
+    }
+
+    /// Returns an [`Iterator`] over the components `T` of this query, as one contiguous mutable
+    /// slice per matched table. All the components handed out are flagged as changed.
+    ///
+    /// See [`Self::iter_slices`] for the requirements on the query.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// struct Position(f32);
+    ///
+    /// fn scale_system(mut query: Query<&mut Position>) {
+    ///     for positions in query.iter_slices_mut::<Position>().unwrap() {
+    ///         for position in positions {
+    ///             position.0 *= 2.0;
+    ///         }
+    ///     }
+    /// }
+    /// # scale_system.system();
+    /// ```
+    #[inline]
+    pub fn iter_slices_mut<T: Component>(
+        &mut self,
+    ) -> Result<impl Iterator<Item = &'_ mut [T]> + '_, QueryComponentError>
+    where
+        F: ArchetypeFilter,
+    {
+        let change_tick = self.change_tick;
+        let columns = self.table_columns::<T>(true)?;
+        // SAFE: the query has write access to `T`, which is stored in the columns, and the query
+        // visits every row of these columns. Each table is only visited once and `&mut self`
+        // prevents aliasing with other accesses through this query.
+        Ok(columns.map(move |column| unsafe {
+            for ticks in column.ticks.iter() {
+                (*ticks.get()).set_changed(change_tick);
+            }
+            std::slice::from_raw_parts_mut(column.get_data_ptr().cast::<T>().as_ptr(), column.len())
+        }))
+    }
+
+    /// Returns the non-empty columns of the component `T` in the tables matched by this query.
+    fn table_columns<T: Component>(
+        &self,
+        write: bool,
+    ) -> Result<impl Iterator<Item = &'w Column> + 'w, QueryComponentError> {
+        let world = self.world;
+        let access = self.state.component_access.access();
+        let component_id = world
+            .components()
+            .get_id(TypeId::of::<T>())
+            .filter(|&id| {
+                if write {
+                    access.has_write(id)
+                } else {
+                    access.has_read(id)
+                }
+            })
+            .ok_or(if write {
+                QueryComponentError::MissingWriteAccess
+            } else {
+                QueryComponentError::MissingReadAccess
+            })?;
+        // SAFE: the fetches are only created to find out whether they are dense
+        let (fetch, filter) = unsafe {
+            (
+                <Q::Fetch as Fetch>::init(
+                    world,
+                    &self.state.fetch_state,
+                    self.last_change_tick,
+                    self.change_tick,
+                ),
+                <F::Fetch as Fetch>::init(
+                    world,
+                    &self.state.filter_state,
+                    self.last_change_tick,
+                    self.change_tick,
+                ),
+            )
+        };
+        if !fetch.is_dense() || !filter.is_dense() {
+            return Err(QueryComponentError::NotTableStored);
+        }
+        let tables = &world.storages().tables;
+        Ok(self
+            .state
+            .matched_table_ids
+            .iter()
+            .filter_map(move |&table_id| tables[table_id].get_column(component_id))
+            .filter(|column| column.len() > 0))
+    }
+
     /// Gets the result of a single-result query.
     ///
     /// If the query has exactly one result, returns the result inside `Ok`
@@ -544,6 +733,22 @@ where
         }
     }
 
+    /// Same as [`Self::single`], for code that reads better with the `get_` prefix of the other
+    /// fallible accessors, e.g. `if let Ok(player) = query.get_single()`.
+    #[inline]
+    pub fn get_single(&self) -> Result<<Q::Fetch as Fetch<'_>>::Item, QuerySingleError>
+    where
+        Q::Fetch: ReadOnlyFetch,
+    {
+        self.single()
+    }
+
+    /// Same as [`Self::single_mut`], see [`Self::get_single`].
+    #[inline]
+    pub fn get_single_mut(&mut self) -> Result<<Q::Fetch as Fetch<'_>>::Item, QuerySingleError> {
+        self.single_mut()
+    }
+
     /// Returns true if this query contains no elements.
     #[inline]
     pub fn is_empty(&self) -> bool {
@@ -565,10 +770,12 @@ pub enum QueryComponentError {
     MissingComponent,
     #[error("The requested entity does not exist.")]
     NoSuchEntity,
+    #[error("This query uses components that are not stored in tables.")]
+    NotTableStored,
 }
 
 /// An error that occurs when evaluating a [`Query`] as a single expected resulted via
-/// [`Query::single`] or [`Query::single_mut`].
+/// [`Query::single`], [`Query::single_mut`], [`Query::get_single`] or [`Query::get_single_mut`].
 #[derive(Debug, Error)]
 pub enum QuerySingleError {
     #[error("No entities fit the query {0}")]
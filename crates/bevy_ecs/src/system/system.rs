@@ -2,7 +2,7 @@ use bevy_utils::tracing::warn;
 
 use crate::{
     archetype::{Archetype, ArchetypeComponentId},
-    component::ComponentId,
+    component::{ComponentId, CLAMPED_CHANGE_AGE},
     query::Access,
     world::World,
 };
@@ -80,13 +80,12 @@ pub(crate) fn check_system_change_tick(
     system_name: &str,
 ) {
     let tick_delta = change_tick.wrapping_sub(*last_change_tick);
-    const MAX_DELTA: u32 = (u32::MAX / 4) * 3;
     // Clamp to max delta
-    if tick_delta > MAX_DELTA {
+    if tick_delta > CLAMPED_CHANGE_AGE {
         warn!(
             "Too many intervening systems have run since the last time System '{}' was last run; it may fail to detect changes.",
             system_name
         );
-        *last_change_tick = change_tick.wrapping_sub(MAX_DELTA);
+        *last_change_tick = change_tick.wrapping_sub(CLAMPED_CHANGE_AGE);
     }
 }
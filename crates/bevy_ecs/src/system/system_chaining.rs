@@ -2,10 +2,11 @@ use crate::{
     archetype::{Archetype, ArchetypeComponentId},
     component::ComponentId,
     query::Access,
-    system::{IntoSystem, System, SystemId},
+    system::{In, IntoSystem, System, SystemId},
     world::World,
 };
-use std::borrow::Cow;
+use bevy_utils::tracing::error;
+use std::{borrow::Cow, fmt::Debug};
 
 /// A [`System`] that chains two systems together, creating a new system that routes the output of
 /// the first system into the input of the second system, yielding the output of the second system.
@@ -126,6 +127,30 @@ where
     /// Chain this system `A` with another system `B` creating a new system that feeds system A's
     /// output into system `B`, returning the output of system `B`.
     fn chain(self, system: SystemB) -> ChainSystem<Self::System, SystemB::System>;
+
+    /// Pipe the output of this system into `system`, the same as [`IntoChainSystem::chain`].
+    /// Systems returning a `Result` are commonly piped into an error handler like [`log_err`],
+    /// [`panic_on_err`] or [`ignore_err`].
+    ///
+    /// ```
+    /// use bevy_ecs::{prelude::*, system::log_err};
+    ///
+    /// struct Config(String);
+    ///
+    /// fn parse_config_system(config: Res<Config>) -> Result<(), std::num::ParseIntError> {
+    ///     let _value = config.0.parse::<u32>()?;
+    ///     Ok(())
+    /// }
+    ///
+    /// let mut world = World::default();
+    /// world.insert_resource(Config("not a number".to_string()));
+    /// let mut system = parse_config_system.pipe(log_err);
+    /// system.initialize(&mut world);
+    /// system.run((), &mut world);
+    /// ```
+    fn pipe(self, system: SystemB) -> ChainSystem<Self::System, SystemB::System> {
+        self.chain(system)
+    }
 }
 
 impl<SystemA, ParamA, Payload, SystemB, ParamB, Out>
@@ -147,3 +172,20 @@ where
         }
     }
 }
+
+/// Logs the error of a piped system, see [`IntoChainSystem::pipe`].
+pub fn log_err<T, E: Debug>(In(result): In<Result<T, E>>) {
+    if let Err(err) = result {
+        error!("{:?}", err);
+    }
+}
+
+/// Panics on the error of a piped system, see [`IntoChainSystem::pipe`].
+pub fn panic_on_err<T, E: Debug>(In(result): In<Result<T, E>>) {
+    if let Err(err) = result {
+        panic!("{:?}", err);
+    }
+}
+
+/// Discards the result of a piped system, see [`IntoChainSystem::pipe`].
+pub fn ignore_err<T, E>(In(_result): In<Result<T, E>>) {}
@@ -5,6 +5,7 @@ use crate::{
     change_detection::Ticks,
     component::{Component, ComponentId, ComponentTicks, Components},
     entity::{Entities, Entity},
+    event::ManualEventReader,
     query::{
         FilterFetch, FilteredAccess, FilteredAccessSet, QueryState, ReadOnlyFetch, WorldQuery,
     },
@@ -506,6 +507,100 @@ impl<'a> SystemParamFetch<'a> for CommandQueue {
     }
 }
 
+/// State accumulated by systems through [`Deferred`], and applied to the [`World`] at the next
+/// sync point, when the stage applies the buffers of its systems, like [`Commands`].
+pub trait SystemBuffer: FromWorld + Send + Sync + 'static {
+    /// Applies the state accumulated since the last sync point.
+    fn apply(&mut self, world: &mut World);
+}
+
+/// A [`SystemParam`] that accumulates user state during parallel execution and applies it with
+/// exclusive world access at the next sync point, e.g. to batch statistics or spatial index
+/// updates without locking shared resources. Like [`Local`], each system has its own buffer.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// use bevy_ecs::system::{Deferred, SystemBuffer};
+///
+/// #[derive(Default)]
+/// struct Stats(u32);
+///
+/// #[derive(Default)]
+/// struct StatsBuffer {
+///     kills: u32,
+/// }
+///
+/// impl SystemBuffer for StatsBuffer {
+///     fn apply(&mut self, world: &mut World) {
+///         world.get_resource_or_insert_with(Stats::default).0 += self.kills;
+///         self.kills = 0;
+///     }
+/// }
+///
+/// fn combat_system(mut stats: Deferred<StatsBuffer>) {
+///     stats.kills += 1;
+/// }
+/// # combat_system.system();
+/// ```
+pub struct Deferred<'a, T: SystemBuffer>(&'a mut T);
+
+impl<'a, T: SystemBuffer> Deref for Deferred<'a, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T: SystemBuffer> DerefMut for Deferred<'a, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.0
+    }
+}
+
+/// The [`SystemParamState`] of [`Deferred`].
+pub struct DeferredState<T: SystemBuffer>(T);
+
+impl<'a, T: SystemBuffer> SystemParam for Deferred<'a, T> {
+    type Fetch = DeferredState<T>;
+}
+
+// SAFE: Deferred only accesses internal state
+unsafe impl<T: SystemBuffer> ReadOnlySystemParamFetch for DeferredState<T> {}
+
+// SAFE: only local state is accessed
+unsafe impl<T: SystemBuffer> SystemParamState for DeferredState<T> {
+    type Config = ();
+
+    fn init(world: &mut World, _system_meta: &mut SystemMeta, _config: Self::Config) -> Self {
+        Self(T::from_world(world))
+    }
+
+    fn apply(&mut self, world: &mut World) {
+        self.0.apply(world);
+    }
+
+    fn default_config() {}
+}
+
+impl<'a, T: SystemBuffer> SystemParamFetch<'a> for DeferredState<T> {
+    type Item = Deferred<'a, T>;
+
+    #[inline]
+    unsafe fn get_param(
+        state: &'a mut Self,
+        _system_meta: &SystemMeta,
+        _world: &'a World,
+        _change_tick: u32,
+    ) -> Self::Item {
+        Deferred(&mut state.0)
+    }
+}
+
 /// A system local [`SystemParam`].
 ///
 /// A local may only be accessed by the system itself and is therefore not visible to other systems.
@@ -598,6 +693,11 @@ impl<'a, T: Component + FromWorld> SystemParamFetch<'a> for LocalState<T> {
 
 /// A [`SystemParam`] that grants access to the entities that had their `T` [`Component`] removed.
 ///
+/// Like an [`EventReader`](crate::event::EventReader), each system keeps track of the removals it
+/// already read. Removals are kept until the second call to
+/// [`World::clear_trackers`], so they are seen whatever the order of the systems, as long as the
+/// system runs at least once per frame.
+///
 /// # Examples
 ///
 /// Basic usage:
@@ -608,7 +708,7 @@ impl<'a, T: Component + FromWorld> SystemParamFetch<'a> for LocalState<T> {
 /// #
 /// # struct MyComponent;
 ///
-/// fn react_on_removal(removed: RemovedComponents<MyComponent>) {
+/// fn react_on_removal(mut removed: RemovedComponents<MyComponent>) {
 ///     removed.iter().for_each(|removed_entity| println!("{:?}", removed_entity));
 /// }
 ///
@@ -617,13 +717,21 @@ impl<'a, T: Component + FromWorld> SystemParamFetch<'a> for LocalState<T> {
 pub struct RemovedComponents<'a, T> {
     world: &'a World,
     component_id: ComponentId,
+    reader: &'a mut ManualEventReader<Entity>,
     marker: PhantomData<T>,
 }
 
 impl<'a, T> RemovedComponents<'a, T> {
-    /// Returns an iterator over the entities that had their `T` [`Component`] removed.
-    pub fn iter(&self) -> std::iter::Cloned<std::slice::Iter<'_, Entity>> {
-        self.world.removed_with_id(self.component_id)
+    /// Returns an iterator over the entities that had their `T` [`Component`] removed since the
+    /// last time this system read them.
+    pub fn iter(&mut self) -> impl DoubleEndedIterator<Item = Entity> + '_ {
+        let reader = &mut *self.reader;
+        self.world
+            .removed_components
+            .get(self.component_id)
+            .into_iter()
+            .flat_map(move |removed| reader.iter(removed))
+            .cloned()
     }
 }
 
@@ -633,6 +741,7 @@ unsafe impl<T: Component> ReadOnlySystemParamFetch for RemovedComponentsState<T>
 /// The [`SystemParamState`] of [`RemovedComponents`].
 pub struct RemovedComponentsState<T> {
     component_id: ComponentId,
+    reader: ManualEventReader<Entity>,
     marker: PhantomData<T>,
 }
 
@@ -648,6 +757,7 @@ unsafe impl<T: Component> SystemParamState for RemovedComponentsState<T> {
     fn init(world: &mut World, _system_meta: &mut SystemMeta, _config: Self::Config) -> Self {
         Self {
             component_id: world.components.get_or_insert_id::<T>(),
+            reader: Default::default(),
             marker: PhantomData,
         }
     }
@@ -668,6 +778,7 @@ impl<'a, T: Component> SystemParamFetch<'a> for RemovedComponentsState<T> {
         RemovedComponents {
             world,
             component_id: state.component_id,
+            reader: &mut state.reader,
             marker: PhantomData,
         }
     }
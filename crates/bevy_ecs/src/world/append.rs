@@ -0,0 +1,295 @@
+use crate::{
+    archetype::ArchetypeId,
+    component::{Component, ComponentId, ComponentTicks, Components, StorageType},
+    entity::{EntityMap, MapEntities, MapEntitiesError},
+    storage::{SparseSet, TableId},
+    world::World,
+};
+use bevy_utils::HashMap;
+
+type EntityMapper = fn(&mut World, &EntityMap) -> Result<(), MapEntitiesError>;
+
+/// The components whose entities are remapped by [`World::append`], registered with
+/// [`World::register_entity_mapping`].
+#[derive(Default)]
+pub(crate) struct EntityMappers {
+    mappers: SparseSet<ComponentId, EntityMapper>,
+}
+
+fn map_component_entities<T: Component + MapEntities>(
+    world: &mut World,
+    entity_map: &EntityMap,
+) -> Result<(), MapEntitiesError> {
+    let mut result = Ok(());
+    for entity in entity_map.values() {
+        if let Some(mut component) = world.get_mut::<T>(entity) {
+            if let Err(err) = component.map_entities(entity_map) {
+                result = result.and(Err(err));
+            }
+        }
+    }
+    result
+}
+
+impl World {
+    /// Registers `T` as referencing entities, which are remapped when its entity is moved to this
+    /// world by [`World::append`].
+    pub fn register_entity_mapping<T: Component + MapEntities>(&mut self) -> &mut Self {
+        let component_id = self.components.get_or_insert_id::<T>();
+        self.entity_mappers
+            .mappers
+            .insert(component_id, map_component_entities::<T>);
+        self
+    }
+
+    /// Moves all the entities of `other` to this world, with their components, and returns the
+    /// map from their entities in `other` to their new entities. `other` is left without entities,
+    /// but keeps its resources.
+    ///
+    /// The components registered with [`World::register_entity_mapping`] in this world then have
+    /// their entities remapped. This lets a level or a scene be loaded into a staging world, e.g.
+    /// on another thread, before moving it to the main world.
+    ///
+    /// ```
+    /// use bevy_ecs::{
+    ///     entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+    ///     world::World,
+    /// };
+    ///
+    /// struct Target(Entity);
+    ///
+    /// impl MapEntities for Target {
+    ///     fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+    ///         self.0 = entity_map.get(self.0)?;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut world = World::new();
+    /// world.register_entity_mapping::<Target>();
+    /// world.spawn().insert(1u32);
+    ///
+    /// let mut staging = World::new();
+    /// let target = staging.spawn().insert(2u32).id();
+    /// let follower = staging.spawn().insert(Target(target)).id();
+    ///
+    /// let entity_map = world.append(&mut staging).unwrap();
+    /// let target = entity_map.get(target).unwrap();
+    /// let follower = entity_map.get(follower).unwrap();
+    /// assert_eq!(world.get::<u32>(target), Some(&2));
+    /// assert_eq!(world.get::<Target>(follower).unwrap().0, target);
+    /// assert!(staging.get_entity(follower).is_none());
+    /// ```
+    ///
+    /// The entities are moved even if an error is returned, which happens when a component
+    /// references an entity that wasn't in `other`.
+    pub fn append(&mut self, other: &mut World) -> Result<EntityMap, MapEntitiesError> {
+        self.flush();
+        other.flush();
+        let change_tick = self.change_tick();
+        let mut entity_map = EntityMap::default();
+        let mut component_ids = HashMap::<ComponentId, ComponentId>::default();
+        let mut archetype_ids = HashMap::<ArchetypeId, ArchetypeId>::default();
+
+        for table_index in 0..other.storages.tables.len() {
+            let source_table_id = TableId::new(table_index);
+            // take the entities from the end of the table, so that no row is swapped
+            while let Some(&entity) = other.storages.tables[source_table_id].entities().last() {
+                let location = other.entities.get(entity).unwrap();
+                let source_archetype = &other.archetypes[location.archetype_id];
+                let source_components = source_archetype.components().collect::<Vec<_>>();
+                let source_table_row = source_archetype.entity_table_row(location.index);
+                for component_id in source_components.iter() {
+                    if !component_ids.contains_key(component_id) {
+                        let target_id =
+                            self.register_appended_component(&other.components, *component_id);
+                        component_ids.insert(*component_id, target_id);
+                    }
+                }
+                let target_archetype_id = *archetype_ids
+                    .entry(location.archetype_id)
+                    .or_insert_with(|| {
+                        let mut table_components = Vec::new();
+                        let mut sparse_set_components = Vec::new();
+                        for component_id in source_components.iter() {
+                            let target_id = component_ids[component_id];
+                            match self.components.get_info(target_id).unwrap().storage_type() {
+                                StorageType::Table => table_components.push(target_id),
+                                StorageType::SparseSet => sparse_set_components.push(target_id),
+                            }
+                        }
+                        table_components.sort();
+                        sparse_set_components.sort();
+                        // SAFE: the components were registered above
+                        let table_id = unsafe {
+                            self.storages
+                                .tables
+                                .get_id_or_insert(&table_components, &self.components)
+                        };
+                        self.archetypes.get_id_or_insert(
+                            table_id,
+                            table_components,
+                            sparse_set_components,
+                        )
+                    });
+
+                let new_entity = self.entities.alloc();
+                entity_map.insert(entity, new_entity);
+                let target_archetype = &mut self.archetypes[target_archetype_id];
+                let target_table = &mut self.storages.tables[target_archetype.table_id()];
+                let source_table = &mut other.storages.tables[source_table_id];
+                // SAFE: the components are moved to columns and sparse sets of the same component
+                // type, and the rows were just allocated
+                unsafe {
+                    let table_row = target_table.allocate(new_entity);
+                    for component_id in source_components.iter() {
+                        let source_info = other.components.get_info_unchecked(*component_id);
+                        let data = match source_info.storage_type() {
+                            StorageType::Table => source_table
+                                .get_column(*component_id)
+                                .unwrap()
+                                .get_data_unchecked(source_table_row),
+                            StorageType::SparseSet => other
+                                .storages
+                                .sparse_sets
+                                .get_mut(*component_id)
+                                .unwrap()
+                                .remove_and_forget(entity)
+                                .unwrap(),
+                        };
+                        let target_id = component_ids[component_id];
+                        let target_info = self.components.get_info_unchecked(target_id);
+                        match target_info.storage_type() {
+                            StorageType::Table => target_table
+                                .get_column_mut(target_id)
+                                .unwrap()
+                                .initialize(table_row, data, ComponentTicks::new(change_tick)),
+                            StorageType::SparseSet => self
+                                .storages
+                                .sparse_sets
+                                .get_or_insert(target_info)
+                                .insert(new_entity, data, change_tick),
+                        }
+                    }
+                    let new_location = target_archetype.allocate(new_entity, table_row);
+                    self.entities.meta[new_entity.id() as usize].location = new_location;
+
+                    source_table.swap_remove_and_forget_unchecked(source_table_row);
+                }
+
+                let removed = other.archetypes[location.archetype_id].swap_remove(location.index);
+                if let Some(swapped_entity) = removed.swapped_entity {
+                    other.entities.meta[swapped_entity.id() as usize]
+                        .location
+                        .index = location.index;
+                }
+                other.entities.free(entity);
+            }
+        }
+
+        if self.component_hooks.has_insert_hooks() {
+            for entity in entity_map.values() {
+                let location = self.entities.get(entity).unwrap();
+                let inserted = self.archetypes[location.archetype_id]
+                    .components()
+                    .map(|component_id| (component_id, true))
+                    .collect::<Vec<_>>();
+                self.trigger_insert_hooks(entity, location, &inserted);
+            }
+        }
+
+        let mappers = self
+            .entity_mappers
+            .mappers
+            .values()
+            .copied()
+            .collect::<Vec<_>>();
+        let mut result = Ok(());
+        for mapper in mappers {
+            result = result.and(mapper(self, &entity_map));
+        }
+        result.map(|_| entity_map)
+    }
+
+    /// Returns the id in this world of a component of another world, registering it if needed.
+    fn register_appended_component(
+        &mut self,
+        other_components: &Components,
+        component_id: ComponentId,
+    ) -> ComponentId {
+        let info = other_components.get_info(component_id).unwrap();
+        if let Some(id) = info
+            .type_id()
+            .and_then(|type_id| self.components.get_id(type_id))
+        {
+            return id;
+        }
+        self.register_component(info.descriptor()).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::{ComponentDescriptor, StorageType},
+        entity::{Entity, EntityMap, MapEntities, MapEntitiesError},
+        world::World,
+    };
+
+    #[derive(Debug, PartialEq)]
+    struct Link(Entity);
+
+    impl MapEntities for Link {
+        fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+            self.0 = entity_map.get(self.0)?;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn append_world() {
+        let mut world = World::new();
+        world.register_entity_mapping::<Link>();
+        world
+            .register_component(ComponentDescriptor::new::<u64>(StorageType::SparseSet))
+            .unwrap();
+        let existing = world.spawn().insert_bundle((0u32, "existing")).id();
+
+        let mut other = World::new();
+        other.insert_resource(5usize);
+        let a = other.spawn().insert_bundle((1u32, 10u64)).id();
+        let b = other.spawn().insert_bundle((2u32, Link(a))).id();
+        let c = other.spawn().insert(Link(b)).id();
+        let empty = other.spawn().id();
+
+        let entity_map = world.append(&mut other).unwrap();
+        let a = entity_map.get(a).unwrap();
+        let b = entity_map.get(b).unwrap();
+        let c = entity_map.get(c).unwrap();
+        let empty = entity_map.get(empty).unwrap();
+        assert_eq!(world.get::<u32>(existing), Some(&0));
+        assert_eq!(world.get::<u32>(a), Some(&1));
+        assert_eq!(world.get::<u64>(a), Some(&10));
+        assert_eq!(world.get::<u32>(b), Some(&2));
+        assert_eq!(world.get::<Link>(b), Some(&Link(a)));
+        assert_eq!(world.get::<Link>(c), Some(&Link(b)));
+        assert!(world.get_entity(empty).is_some());
+        assert_eq!(world.entities().len(), 5);
+
+        assert_eq!(other.entities().len(), 0);
+        assert_eq!(other.query::<&u32>().iter(&other).count(), 0);
+        assert_eq!(other.get_resource::<usize>(), Some(&5));
+        let reused = other.spawn().insert(3u32).id();
+        assert_eq!(other.get::<u32>(reused), Some(&3));
+    }
+
+    #[test]
+    fn append_world_with_dangling_entity() {
+        let mut world = World::new();
+        world.register_entity_mapping::<Link>();
+        let mut other = World::new();
+        other.spawn().insert(Link(Entity::new(100)));
+        assert!(world.append(&mut other).is_err());
+        assert_eq!(world.query::<&Link>().iter(&world).count(), 1);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    archetype::Archetype,
+    component::{Component, ComponentId, Components},
+    entity::Entity,
+};
+use fixedbitset::FixedBitSet;
+use std::fmt;
+
+/// A constraint on the components that entities can have together, such as a component requiring
+/// a companion component, registered with
+/// [`World::register_archetype_invariant`](crate::world::World::register_archetype_invariant).
+///
+/// In debug builds, inserting or removing components in a way that breaks an invariant panics
+/// with the offending entity and bundle. Invariants hold after every insertion and removal, so
+/// components that require each other must be inserted in the same bundle.
+#[derive(Clone)]
+pub struct ArchetypeInvariant {
+    kind: InvariantKind,
+    premise: InvariantComponent,
+    consequence: InvariantComponent,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum InvariantKind {
+    Requires,
+    Excludes,
+}
+
+#[derive(Clone)]
+struct InvariantComponent {
+    name: &'static str,
+    init: fn(&mut Components) -> ComponentId,
+}
+
+impl InvariantComponent {
+    fn of<T: Component>() -> Self {
+        InvariantComponent {
+            name: std::any::type_name::<T>(),
+            init: Components::get_or_insert_id::<T>,
+        }
+    }
+}
+
+impl ArchetypeInvariant {
+    /// Entities with an `A` must also have a `B`.
+    pub fn requires<A: Component, B: Component>() -> Self {
+        ArchetypeInvariant {
+            kind: InvariantKind::Requires,
+            premise: InvariantComponent::of::<A>(),
+            consequence: InvariantComponent::of::<B>(),
+        }
+    }
+
+    /// Entities can't have both an `A` and a `B`.
+    pub fn excludes<A: Component, B: Component>() -> Self {
+        ArchetypeInvariant {
+            kind: InvariantKind::Excludes,
+            premise: InvariantComponent::of::<A>(),
+            consequence: InvariantComponent::of::<B>(),
+        }
+    }
+}
+
+impl fmt::Debug for ArchetypeInvariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl fmt::Display for ArchetypeInvariant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let verb = match self.kind {
+            InvariantKind::Requires => "requires",
+            InvariantKind::Excludes => "excludes",
+        };
+        write!(
+            f,
+            "{} {} {}",
+            self.premise.name, verb, self.consequence.name
+        )
+    }
+}
+
+struct RegisteredInvariant {
+    invariant: ArchetypeInvariant,
+    premise: ComponentId,
+    consequence: ComponentId,
+}
+
+impl RegisteredInvariant {
+    fn holds(&self, archetype: &Archetype) -> bool {
+        if !archetype.contains(self.premise) {
+            return true;
+        }
+        match self.invariant.kind {
+            InvariantKind::Requires => archetype.contains(self.consequence),
+            InvariantKind::Excludes => !archetype.contains(self.consequence),
+        }
+    }
+}
+
+/// The [`ArchetypeInvariant`]s of a world, and the archetypes known to uphold them.
+#[derive(Default)]
+pub struct ArchetypeInvariants {
+    invariants: Vec<RegisteredInvariant>,
+    valid_archetypes: FixedBitSet,
+}
+
+impl ArchetypeInvariants {
+    pub(crate) fn add(&mut self, invariant: ArchetypeInvariant, components: &mut Components) {
+        let premise = (invariant.premise.init)(components);
+        let consequence = (invariant.consequence.init)(components);
+        self.invariants.push(RegisteredInvariant {
+            invariant,
+            premise,
+            consequence,
+        });
+        self.valid_archetypes.clear();
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ArchetypeInvariant> {
+        self.invariants
+            .iter()
+            .map(|registered| &registered.invariant)
+    }
+
+    /// The first invariant broken by `archetype`, if any.
+    pub fn broken_by(&mut self, archetype: &Archetype) -> Option<&ArchetypeInvariant> {
+        let index = archetype.id().index();
+        if self.invariants.is_empty() || self.valid_archetypes.contains(index) {
+            return None;
+        }
+        match self
+            .invariants
+            .iter()
+            .find(|registered| !registered.holds(archetype))
+        {
+            Some(registered) => Some(&registered.invariant),
+            None => {
+                self.valid_archetypes.grow(index + 1);
+                self.valid_archetypes.insert(index);
+                None
+            }
+        }
+    }
+
+    /// Panics if the archetype `entity` moved to after `action` breaks an invariant.
+    pub(crate) fn validate(
+        &mut self,
+        archetype: &Archetype,
+        entity: Option<Entity>,
+        action: fmt::Arguments,
+    ) {
+        if let Some(invariant) = self.broken_by(archetype) {
+            match entity {
+                Some(entity) => panic!(
+                    "Entity {:?} broke the archetype invariant `{}` after {}",
+                    entity, invariant, action
+                ),
+                None => panic!(
+                    "The archetype invariant `{}` is broken after {}",
+                    invariant, action
+                ),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ArchetypeInvariant;
+    use crate::world::World;
+
+    struct Velocity;
+    struct Position;
+    struct Frozen;
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.register_archetype_invariant(ArchetypeInvariant::requires::<Velocity, Position>());
+        world.register_archetype_invariant(ArchetypeInvariant::excludes::<Frozen, Velocity>());
+        world
+    }
+
+    #[test]
+    fn upheld_invariants() {
+        let mut world = world();
+        let mut entity = world.spawn();
+        entity.insert(Position);
+        entity.insert(Velocity);
+        entity.remove::<Velocity>();
+        entity.insert(Frozen);
+        world.spawn_batch(vec![(Velocity, Position)]);
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        should_panic(expected = "requires bevy_ecs::world::archetype_invariant::tests::Position")
+    )]
+    fn insert_without_requirement() {
+        world().spawn().insert(Velocity);
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "after removing"))]
+    fn remove_requirement() {
+        world()
+            .spawn()
+            .insert_bundle((Velocity, Position))
+            .remove::<Position>();
+    }
+
+    #[test]
+    #[cfg_attr(debug_assertions, should_panic(expected = "excludes"))]
+    fn insert_excluded() {
+        world().spawn().insert_bundle((Velocity, Position, Frozen));
+    }
+
+    #[test]
+    #[should_panic(expected = "registering the invariant")]
+    fn register_broken_invariant() {
+        let mut world = World::new();
+        world.spawn().insert(Velocity);
+        world.register_archetype_invariant(ArchetypeInvariant::requires::<Velocity, Position>());
+    }
+}
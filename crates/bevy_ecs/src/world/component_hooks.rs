@@ -0,0 +1,318 @@
+use crate::{
+    component::{Component, ComponentId},
+    entity::{Entity, EntityLocation},
+    storage::SparseSet,
+    world::{get_component, Mut, World},
+};
+
+/// The access to the [`World`] of a component hook, registered with [`World::on_add`],
+/// [`World::on_insert`] or [`World::on_remove`].
+///
+/// Hooks run in the middle of an insertion or removal, so they can read the world and mutate
+/// resources, e.g. a spatial index, but can't add or remove entities or components.
+pub struct HookWorld<'w> {
+    world: &'w World,
+}
+
+impl<'w> HookWorld<'w> {
+    pub fn world(&self) -> &World {
+        self.world
+    }
+
+    pub fn get_resource<T: Component>(&self) -> Option<&T> {
+        self.world.get_resource()
+    }
+
+    pub fn get_resource_mut<T: Component>(&mut self) -> Option<Mut<'_, T>> {
+        // SAFE: resources are stored apart from the components of entities, and the hook has the
+        // only access to the world while it runs
+        unsafe { self.world.get_resource_unchecked_mut() }
+    }
+
+    pub fn get<T: Component>(&self, entity: Entity) -> Option<&T> {
+        self.world.get(entity)
+    }
+}
+
+type ComponentHook = Box<dyn Fn(&mut HookWorld, Entity, *const u8) + Send + Sync>;
+
+#[derive(Default)]
+struct HookSet {
+    on_add: Vec<ComponentHook>,
+    on_insert: Vec<ComponentHook>,
+    on_remove: Vec<ComponentHook>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum HookKind {
+    Add,
+    Insert,
+    Remove,
+}
+
+impl HookSet {
+    fn hooks(&self, kind: HookKind) -> &[ComponentHook] {
+        match kind {
+            HookKind::Add => &self.on_add,
+            HookKind::Insert => &self.on_insert,
+            HookKind::Remove => &self.on_remove,
+        }
+    }
+}
+
+/// The hooks of each component type, run synchronously when a component is added to, inserted on
+/// or removed from an entity.
+#[derive(Default)]
+pub(crate) struct ComponentHooks {
+    hooks: SparseSet<ComponentId, HookSet>,
+    insert_hook_count: usize,
+    remove_hook_count: usize,
+}
+
+impl ComponentHooks {
+    pub(crate) fn add<T: Component>(
+        &mut self,
+        component_id: ComponentId,
+        kind: HookKind,
+        hook: impl Fn(&mut HookWorld, Entity, &T) + Send + Sync + 'static,
+    ) {
+        // SAFE: hooks of a component are only run with values of that component
+        let hook: ComponentHook =
+            Box::new(move |world, entity, value| hook(world, entity, unsafe { &*value.cast() }));
+        let set = self
+            .hooks
+            .get_or_insert_with(component_id, HookSet::default);
+        match kind {
+            HookKind::Add => set.on_add.push(hook),
+            HookKind::Insert => set.on_insert.push(hook),
+            HookKind::Remove => set.on_remove.push(hook),
+        }
+        match kind {
+            HookKind::Add | HookKind::Insert => self.insert_hook_count += 1,
+            HookKind::Remove => self.remove_hook_count += 1,
+        }
+    }
+
+    /// Whether any `on_add` or `on_insert` hook is registered.
+    #[inline]
+    pub(crate) fn has_insert_hooks(&self) -> bool {
+        self.insert_hook_count > 0
+    }
+
+    /// Whether any `on_remove` hook is registered.
+    #[inline]
+    pub(crate) fn has_remove_hooks(&self) -> bool {
+        self.remove_hook_count > 0
+    }
+
+    pub(crate) fn contains(&self, component_id: ComponentId, kind: HookKind) -> bool {
+        self.hooks
+            .get(component_id)
+            .map_or(false, |set| !set.hooks(kind).is_empty())
+    }
+}
+
+impl World {
+    /// Runs `hook` whenever a `T` is added to an entity that didn't have one, right after the
+    /// insertion. Hooks can keep acceleration structures, like a spatial index stored in a
+    /// resource, in sync with the components without waiting for a system to run.
+    ///
+    /// ```
+    /// use bevy_ecs::{entity::Entity, world::World};
+    /// use bevy_utils::HashMap;
+    ///
+    /// struct Name(&'static str);
+    /// #[derive(Default)]
+    /// struct NameIndex(HashMap<&'static str, Entity>);
+    ///
+    /// let mut world = World::new();
+    /// world.insert_resource(NameIndex::default());
+    /// world.on_add(|world, entity, name: &Name| {
+    ///     let mut index = world.get_resource_mut::<NameIndex>().unwrap();
+    ///     index.0.insert(name.0, entity);
+    /// });
+    /// world.on_remove(|world, _, name: &Name| {
+    ///     let mut index = world.get_resource_mut::<NameIndex>().unwrap();
+    ///     index.0.remove(name.0);
+    /// });
+    ///
+    /// let entity = world.spawn().insert(Name("player")).id();
+    /// assert_eq!(world.get_resource::<NameIndex>().unwrap().0["player"], entity);
+    /// world.despawn(entity);
+    /// assert!(world.get_resource::<NameIndex>().unwrap().0.is_empty());
+    /// ```
+    pub fn on_add<T: Component>(
+        &mut self,
+        hook: impl Fn(&mut HookWorld, Entity, &T) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.add_component_hook(HookKind::Add, hook)
+    }
+
+    /// Runs `hook` whenever a `T` is inserted on an entity, including when it replaces the
+    /// previous value, right after the insertion.
+    pub fn on_insert<T: Component>(
+        &mut self,
+        hook: impl Fn(&mut HookWorld, Entity, &T) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.add_component_hook(HookKind::Insert, hook)
+    }
+
+    /// Runs `hook` whenever a `T` is removed from an entity, including when the entity is
+    /// despawned, right before the removal.
+    pub fn on_remove<T: Component>(
+        &mut self,
+        hook: impl Fn(&mut HookWorld, Entity, &T) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.add_component_hook(HookKind::Remove, hook)
+    }
+
+    fn add_component_hook<T: Component>(
+        &mut self,
+        kind: HookKind,
+        hook: impl Fn(&mut HookWorld, Entity, &T) + Send + Sync + 'static,
+    ) -> &mut Self {
+        let component_id = self.components.get_or_insert_id::<T>();
+        self.component_hooks.add(component_id, kind, hook);
+        self
+    }
+
+    /// Runs the hooks of the given kind for each component of `entity` in `component_ids`.
+    pub(crate) fn trigger_component_hooks(
+        &self,
+        kind: HookKind,
+        entity: Entity,
+        location: EntityLocation,
+        component_ids: impl IntoIterator<Item = ComponentId>,
+    ) {
+        let mut hook_world = HookWorld { world: self };
+        for component_id in component_ids {
+            let set = match self.component_hooks.hooks.get(component_id) {
+                Some(set) => set,
+                None => continue,
+            };
+            let hooks = set.hooks(kind);
+            if hooks.is_empty() {
+                continue;
+            }
+            // SAFE: the component ids are valid, and `location` is the location of `entity`
+            let value = match unsafe { get_component(self, component_id, entity, location) } {
+                Some(value) => value,
+                None => continue,
+            };
+            for hook in hooks {
+                hook(&mut hook_world, entity, value);
+            }
+        }
+    }
+
+    /// Runs the `on_add` and `on_insert` hooks of the components inserted on `entity`, with
+    /// whether each component was added.
+    pub(crate) fn trigger_insert_hooks(
+        &self,
+        entity: Entity,
+        location: EntityLocation,
+        inserted: &[(ComponentId, bool)],
+    ) {
+        self.trigger_component_hooks(
+            HookKind::Add,
+            entity,
+            location,
+            inserted
+                .iter()
+                .filter(|(_, added)| *added)
+                .map(|(component_id, _)| *component_id),
+        );
+        self.trigger_component_hooks(
+            HookKind::Insert,
+            entity,
+            location,
+            inserted.iter().map(|(component_id, _)| *component_id),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{entity::Entity, world::World};
+
+    #[derive(Default)]
+    struct Log(Vec<(&'static str, Entity, u32)>);
+
+    fn log(world: &mut World) -> Vec<(&'static str, Entity, u32)> {
+        std::mem::take(&mut world.get_resource_mut::<Log>().unwrap().0)
+    }
+
+    #[test]
+    fn component_hooks() {
+        let mut world = World::new();
+        world.insert_resource(Log::default());
+        world
+            .on_add(|world, entity, value: &u32| {
+                let mut log = world.get_resource_mut::<Log>().unwrap();
+                log.0.push(("add", entity, *value));
+            })
+            .on_insert(|world, entity, value: &u32| {
+                let mut log = world.get_resource_mut::<Log>().unwrap();
+                log.0.push(("insert", entity, *value));
+            })
+            .on_remove(|world, entity, value: &u32| {
+                assert_eq!(world.get::<u32>(entity), Some(value));
+                let mut log = world.get_resource_mut::<Log>().unwrap();
+                log.0.push(("remove", entity, *value));
+            });
+
+        let entity = world.spawn().insert_bundle((1u32, "a")).id();
+        assert_eq!(
+            log(&mut world),
+            vec![("add", entity, 1), ("insert", entity, 1)]
+        );
+        world.entity_mut(entity).insert(2u32);
+        assert_eq!(log(&mut world), vec![("insert", entity, 2)]);
+        world.entity_mut(entity).remove::<&str>();
+        assert_eq!(log(&mut world), vec![]);
+        // nothing is removed if the entity doesn't have the whole bundle
+        assert!(world
+            .entity_mut(entity)
+            .remove_bundle::<(u32, u64)>()
+            .is_none());
+        assert_eq!(log(&mut world), vec![]);
+        world.entity_mut(entity).remove::<u32>();
+        assert_eq!(log(&mut world), vec![("remove", entity, 2)]);
+        world.entity_mut(entity).insert(3u32);
+        world
+            .entity_mut(entity)
+            .remove_bundle_intersection::<(u32, u64)>();
+        assert_eq!(
+            log(&mut world),
+            vec![
+                ("add", entity, 3),
+                ("insert", entity, 3),
+                ("remove", entity, 3)
+            ]
+        );
+
+        let entities = world
+            .spawn_batch(vec![(4u32,), (5u32,)])
+            .collect::<Vec<_>>();
+        assert_eq!(
+            log(&mut world),
+            vec![
+                ("add", entities[0], 4),
+                ("insert", entities[0], 4),
+                ("add", entities[1], 5),
+                ("insert", entities[1], 5)
+            ]
+        );
+        world.despawn(entities[0]);
+        assert_eq!(log(&mut world), vec![("remove", entities[0], 4)]);
+
+        let entity = Entity::new(100);
+        world
+            .insert_or_spawn_batch(vec![(entity, (6u32,))])
+            .unwrap();
+        assert_eq!(
+            log(&mut world),
+            vec![("add", entity, 6), ("insert", entity, 6)]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::{
+    component::{Component, ComponentId, ComponentInfo},
+    entity::Entity,
+    storage::SparseSet,
+    world::World,
+};
+use std::sync::Arc;
+
+type ComponentCloner = Arc<dyn Fn(&mut World, Entity, Entity) + Send + Sync>;
+
+/// How to clone each component type, registered with [`World::register_clone`] or
+/// [`World::register_clone_with`].
+#[derive(Default)]
+pub(crate) struct ComponentCloners {
+    cloners: SparseSet<ComponentId, ComponentCloner>,
+}
+
+impl World {
+    /// Registers `T` as cloned with [`Clone`] by [`World::clone_entity`].
+    pub fn register_clone<T: Component + Clone>(&mut self) -> &mut Self {
+        self.register_clone_with(T::clone)
+    }
+
+    /// Registers `T` as cloned with `clone` by [`World::clone_entity`], e.g. for types that
+    /// can't implement [`Clone`] or should be reset in the copies.
+    pub fn register_clone_with<T: Component>(
+        &mut self,
+        clone: impl Fn(&T) -> T + Send + Sync + 'static,
+    ) -> &mut Self {
+        let component_id = self.components.get_or_insert_id::<T>();
+        let cloner: ComponentCloner = Arc::new(move |world, source, target| {
+            if let Some(value) = world.get::<T>(source).map(&clone) {
+                world.entity_mut(target).insert(value);
+            }
+        });
+        self.component_cloners.cloners.insert(component_id, cloner);
+        self
+    }
+
+    /// Spawns a new entity with a copy of every component of `source`. Components are copied
+    /// with the clone function registered for their type, components without one are skipped.
+    ///
+    /// ```
+    /// use bevy_ecs::world::World;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Health(u32);
+    /// struct Selected;
+    ///
+    /// let mut world = World::new();
+    /// world.register_clone::<Health>();
+    /// let prefab = world.spawn().insert_bundle((Health(100), Selected)).id();
+    ///
+    /// let copy = world.clone_entity(prefab);
+    /// assert_eq!(world.get::<Health>(copy), Some(&Health(100)));
+    /// assert!(world.get::<Selected>(copy).is_none());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` does not exist.
+    pub fn clone_entity(&mut self, source: Entity) -> Entity {
+        self.clone_entity_filtered(source, |_| true)
+    }
+
+    /// Like [`World::clone_entity`], but only copies the components for which `filter` returns
+    /// `true`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` does not exist.
+    pub fn clone_entity_filtered(
+        &mut self,
+        source: Entity,
+        filter: impl FnMut(&ComponentInfo) -> bool,
+    ) -> Entity {
+        assert!(
+            self.entities.contains(source),
+            "Entity {:?} does not exist",
+            source
+        );
+        let target = self.spawn().id();
+        self.clone_components(source, target, filter);
+        target
+    }
+
+    /// Copies the registered components of `source` for which `filter` returns `true` into
+    /// `target`, replacing the components `target` already has. Does nothing if either entity
+    /// does not exist.
+    pub fn clone_components(
+        &mut self,
+        source: Entity,
+        target: Entity,
+        mut filter: impl FnMut(&ComponentInfo) -> bool,
+    ) {
+        let location = match self.entities.get(source) {
+            Some(location) if self.entities.contains(target) => location,
+            _ => return,
+        };
+        let cloners = self.archetypes[location.archetype_id]
+            .components()
+            .filter(|component_id| {
+                let info = self.components.get_info(*component_id).unwrap();
+                filter(info)
+            })
+            .filter_map(|component_id| self.component_cloners.cloners.get(component_id).cloned())
+            .collect::<Vec<_>>();
+        for cloner in cloners {
+            cloner(self, source, target);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::world::World;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Name(&'static str);
+    #[derive(Debug, PartialEq)]
+    struct Counter(u32);
+    struct NotCloned;
+
+    #[test]
+    fn clone_entity() {
+        let mut world = World::new();
+        world
+            .register_clone::<Name>()
+            .register_clone_with(|counter: &Counter| Counter(counter.0 + 1));
+        let source = world
+            .spawn()
+            .insert_bundle((Name("prefab"), Counter(1), NotCloned))
+            .id();
+
+        let copy = world.clone_entity(source);
+        assert_ne!(copy, source);
+        assert_eq!(world.get::<Name>(copy), Some(&Name("prefab")));
+        assert_eq!(world.get::<Counter>(copy), Some(&Counter(2)));
+        assert!(world.get::<NotCloned>(copy).is_none());
+        assert!(world.get::<NotCloned>(source).is_some());
+
+        let filtered = world.clone_entity_filtered(source, |info| !info.name().contains("Counter"));
+        assert_eq!(world.get::<Name>(filtered), Some(&Name("prefab")));
+        assert!(world.get::<Counter>(filtered).is_none());
+    }
+}
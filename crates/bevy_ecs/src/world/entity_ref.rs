@@ -2,10 +2,11 @@ use crate::{
     archetype::{Archetype, ArchetypeId, Archetypes, ComponentStatus},
     bundle::{Bundle, BundleInfo},
     change_detection::Ticks,
-    component::{Component, ComponentId, ComponentTicks, Components, StorageType},
+    component::{Component, ComponentId, ComponentInfo, ComponentTicks, Components, StorageType},
     entity::{Entities, Entity, EntityLocation},
+    event::Events,
     storage::{SparseSet, Storages},
-    world::{Mut, World},
+    world::{HookKind, Mut, World},
 };
 use std::any::TypeId;
 
@@ -45,6 +46,15 @@ impl<'w> EntityRef<'w> {
         self.world
     }
 
+    /// Returns the [`ComponentInfo`] of every component of the entity.
+    #[inline]
+    pub fn components(&self) -> impl Iterator<Item = &ComponentInfo> + '_ {
+        let components = self.world.components();
+        self.archetype()
+            .components()
+            .map(move |id| components.get_info(id).unwrap())
+    }
+
     #[inline]
     pub fn contains<T: Component>(&self) -> bool {
         self.contains_type_id(TypeId::of::<T>())
@@ -69,6 +79,33 @@ impl<'w> EntityRef<'w> {
         }
     }
 
+    /// Returns a pointer to the component with the given id, if the entity has it. This also
+    /// works for components without a Rust type, see [World::register_component_with_layout].
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<*const u8> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        // SAFE: entity location is valid, and the entity has the component
+        unsafe {
+            get_component(self.world, component_id, self.entity, self.location)
+                .map(|value| value as *const u8)
+        }
+    }
+
+    /// Returns the change ticks of the component with the given id, if the entity has it.
+    #[inline]
+    pub fn get_change_ticks_by_id(&self, component_id: ComponentId) -> Option<&'w ComponentTicks> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        // SAFE: the entity has the component, so `component_id` is valid
+        unsafe {
+            get_component_and_ticks(self.world, component_id, self.entity, self.location)
+                .map(|(_, ticks)| &*ticks)
+        }
+    }
+
     /// # Safety
     /// This allows aliased mutability. You must make sure this call does not result in multiple
     /// mutable references to the same component
@@ -127,6 +164,15 @@ impl<'w> EntityMut<'w> {
         &self.world.archetypes[self.location.archetype_id]
     }
 
+    /// Returns the [`ComponentInfo`] of every component of the entity.
+    #[inline]
+    pub fn components(&self) -> impl Iterator<Item = &ComponentInfo> + '_ {
+        let components = self.world.components();
+        self.archetype()
+            .components()
+            .map(move |id| components.get_info(id).unwrap())
+    }
+
     #[inline]
     pub fn contains<T: Component>(&self) -> bool {
         self.contains_type_id(TypeId::of::<T>())
@@ -151,6 +197,40 @@ impl<'w> EntityMut<'w> {
         }
     }
 
+    /// Returns a pointer to the component with the given id, if the entity has it. This also
+    /// works for components without a Rust type, see [World::register_component_with_layout].
+    #[inline]
+    pub fn get_by_id(&self, component_id: ComponentId) -> Option<*const u8> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        // SAFE: entity location is valid, and the entity has the component
+        unsafe {
+            get_component(self.world, component_id, self.entity, self.location)
+                .map(|value| value as *const u8)
+        }
+    }
+
+    /// Returns a mutable pointer to the component with the given id, if the entity has it, and
+    /// marks the component as changed.
+    #[inline]
+    pub fn get_mut_by_id(&mut self, component_id: ComponentId) -> Option<*mut u8> {
+        if !self.contains_id(component_id) {
+            return None;
+        }
+        let change_tick = self.world.change_tick();
+        // SAFE: world access is unique, entity location is valid, and the entity has the
+        // component
+        unsafe {
+            get_component_and_ticks(self.world, component_id, self.entity, self.location).map(
+                |(value, ticks)| {
+                    (*ticks).set_changed(change_tick);
+                    value
+                },
+            )
+        }
+    }
+
     #[inline]
     pub fn get_mut<T: Component>(&mut self) -> Option<Mut<'w, T>> {
         // SAFE: world access is unique, entity location is valid, and returned component is of type
@@ -191,74 +271,6 @@ impl<'w> EntityMut<'w> {
 
     // TODO: move relevant methods to World (add/remove bundle)
     pub fn insert_bundle<T: Bundle>(&mut self, bundle: T) -> &mut Self {
-        // Use a non-generic function to cut down on monomorphization
-        unsafe fn get_insert_bundle_info<'a>(
-            entities: &mut Entities,
-            archetypes: &'a mut Archetypes,
-            components: &mut Components,
-            storages: &mut Storages,
-            bundle_info: &BundleInfo,
-            current_location: EntityLocation,
-            entity: Entity,
-        ) -> (&'a Archetype, &'a Vec<ComponentStatus>, EntityLocation) {
-            // SAFE: component ids in `bundle_info` and self.location are valid
-            let new_archetype_id = add_bundle_to_archetype(
-                archetypes,
-                storages,
-                components,
-                current_location.archetype_id,
-                bundle_info,
-            );
-            if new_archetype_id == current_location.archetype_id {
-                let archetype = &archetypes[current_location.archetype_id];
-                let edge = archetype.edges().get_add_bundle(bundle_info.id).unwrap();
-                (archetype, &edge.bundle_status, current_location)
-            } else {
-                let (old_table_row, old_table_id) = {
-                    let old_archetype = &mut archetypes[current_location.archetype_id];
-                    let result = old_archetype.swap_remove(current_location.index);
-                    if let Some(swapped_entity) = result.swapped_entity {
-                        entities.meta[swapped_entity.id as usize].location = current_location;
-                    }
-                    (result.table_row, old_archetype.table_id())
-                };
-
-                let new_table_id = archetypes[new_archetype_id].table_id();
-
-                let new_location = if old_table_id == new_table_id {
-                    archetypes[new_archetype_id].allocate(entity, old_table_row)
-                } else {
-                    let (old_table, new_table) =
-                        storages.tables.get_2_mut(old_table_id, new_table_id);
-                    // PERF: store "non bundle" components in edge, then just move those to avoid
-                    // redundant copies
-                    let move_result =
-                        old_table.move_to_superset_unchecked(old_table_row, new_table);
-
-                    let new_location =
-                        archetypes[new_archetype_id].allocate(entity, move_result.new_row);
-                    // if an entity was moved into this entity's table spot, update its table row
-                    if let Some(swapped_entity) = move_result.swapped_entity {
-                        let swapped_location = entities.get(swapped_entity).unwrap();
-                        archetypes[swapped_location.archetype_id]
-                            .set_entity_table_row(swapped_location.index, old_table_row);
-                    }
-                    new_location
-                };
-
-                entities.meta[entity.id as usize].location = new_location;
-                let (old_archetype, new_archetype) =
-                    archetypes.get_2_mut(current_location.archetype_id, new_archetype_id);
-                let edge = old_archetype
-                    .edges()
-                    .get_add_bundle(bundle_info.id)
-                    .unwrap();
-                (&*new_archetype, &edge.bundle_status, new_location)
-
-                // Sparse set components are intentionally ignored here. They don't need to move
-            }
-        }
-
         let change_tick = self.world.change_tick();
         let bundle_info = self
             .world
@@ -292,10 +304,40 @@ impl<'w> EntityMut<'w> {
                 change_tick,
             )
         };
+        if self.world.component_hooks.has_insert_hooks() {
+            let inserted = bundle_info
+                .component_ids
+                .iter()
+                .zip(bundle_status)
+                .map(|(id, status)| (*id, matches!(status, ComponentStatus::Added)))
+                .collect::<Vec<_>>();
+            self.world
+                .trigger_insert_hooks(self.entity, new_location, &inserted);
+        }
+        self.world.validate_archetype_invariants(
+            new_location.archetype_id,
+            Some(self.entity),
+            format_args!("inserting {}", std::any::type_name::<T>()),
+        );
         self
     }
 
     pub fn remove_bundle<T: Bundle>(&mut self) -> Option<T> {
+        // the hooks only run if the whole bundle is removed
+        let bundle_info = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components);
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        if bundle_info.component_ids.is_empty()
+            || !bundle_info
+                .component_ids
+                .iter()
+                .all(|id| archetype.contains(*id))
+        {
+            return None;
+        }
+        self.trigger_remove_hooks::<T>();
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -375,12 +417,18 @@ impl<'w> EntityMut<'w> {
 
         self.location = new_location;
         entities.meta[self.entity.id as usize].location = new_location;
+        self.world.validate_archetype_invariants(
+            new_location.archetype_id,
+            Some(self.entity),
+            format_args!("removing {}", std::any::type_name::<T>()),
+        );
 
         Some(result)
     }
 
     /// Remove any components in the bundle that the entity has.
     pub fn remove_bundle_intersection<T: Bundle>(&mut self) {
+        self.trigger_remove_hooks::<T>();
         let archetypes = &mut self.world.archetypes;
         let storages = &mut self.world.storages;
         let components = &mut self.world.components;
@@ -410,8 +458,8 @@ impl<'w> EntityMut<'w> {
         for component_id in bundle_info.component_ids.iter().cloned() {
             if old_archetype.contains(component_id) {
                 removed_components
-                    .get_or_insert_with(component_id, Vec::new)
-                    .push(entity);
+                    .get_or_insert_with(component_id, Events::default)
+                    .send(entity);
 
                 // Make sure to drop components stored in sparse sets.
                 // Dense components are dropped later in `move_to_and_drop_missing_unchecked`.
@@ -459,18 +507,115 @@ impl<'w> EntityMut<'w> {
 
         self.location = new_location;
         entities.meta[self.entity.id as usize].location = new_location;
+        self.world.validate_archetype_invariants(
+            new_location.archetype_id,
+            Some(self.entity),
+            format_args!("removing {}", std::any::type_name::<T>()),
+        );
+    }
+
+    /// Runs the `on_remove` hooks of the components of `T` that the entity has.
+    fn trigger_remove_hooks<T: Bundle>(&mut self) {
+        if !self.world.component_hooks.has_remove_hooks() {
+            return;
+        }
+        let bundle_info = self
+            .world
+            .bundles
+            .init_info::<T>(&mut self.world.components);
+        let archetype = &self.world.archetypes[self.location.archetype_id];
+        let removed = bundle_info
+            .component_ids
+            .iter()
+            .cloned()
+            .filter(|id| archetype.contains(*id))
+            .collect::<Vec<_>>();
+        self.world
+            .trigger_component_hooks(HookKind::Remove, self.entity, self.location, removed);
     }
 
     pub fn insert<T: Component>(&mut self, value: T) -> &mut Self {
         self.insert_bundle((value,))
     }
 
+    /// Inserts the value behind `component` for the component with the given id, which may have
+    /// no Rust type, see [World::register_component_with_layout]. The value is moved: the caller
+    /// must not use or drop it afterwards.
+    ///
+    /// # Safety
+    /// `component_id` must be registered in the world of this entity, and `component` must point
+    /// to a valid value of that component.
+    pub unsafe fn insert_by_id(
+        &mut self,
+        component_id: ComponentId,
+        component: *mut u8,
+    ) -> &mut Self {
+        let change_tick = self.world.change_tick();
+        let bundle_info = self
+            .world
+            .bundles
+            .init_dynamic_info(&self.world.components, &[component_id]);
+
+        let (archetype, bundle_status, new_location) = get_insert_bundle_info(
+            &mut self.world.entities,
+            &mut self.world.archetypes,
+            &mut self.world.components,
+            &mut self.world.storages,
+            bundle_info,
+            self.location,
+            self.entity,
+        );
+        self.location = new_location;
+
+        let table = &mut self.world.storages.tables[archetype.table_id()];
+        let table_row = archetype.entity_table_row(new_location.index);
+        bundle_info.write_component(
+            &mut self.world.storages.sparse_sets,
+            self.entity,
+            table,
+            table_row,
+            bundle_status,
+            0,
+            component,
+            change_tick,
+        );
+        if self.world.component_hooks.has_insert_hooks() {
+            let inserted = [(
+                component_id,
+                matches!(bundle_status[0], ComponentStatus::Added),
+            )];
+            self.world
+                .trigger_insert_hooks(self.entity, new_location, &inserted);
+        }
+        let name = self
+            .world
+            .components
+            .get_info_unchecked(component_id)
+            .name()
+            .to_string();
+        self.world.validate_archetype_invariants(
+            new_location.archetype_id,
+            Some(self.entity),
+            format_args!("inserting {}", name),
+        );
+        self
+    }
+
     pub fn remove<T: Component>(&mut self) -> Option<T> {
         self.remove_bundle::<(T,)>().map(|v| v.0)
     }
 
     pub fn despawn(self) {
         let world = self.world;
+        if world.component_hooks.has_remove_hooks() {
+            let archetype = &world.archetypes[self.location.archetype_id];
+            world.trigger_component_hooks(
+                HookKind::Remove,
+                self.entity,
+                self.location,
+                archetype.components(),
+            );
+        }
         world.flush();
         let location = world
             .entities
@@ -483,8 +628,8 @@ impl<'w> EntityMut<'w> {
             for component_id in archetype.components() {
                 let removed_components = world
                     .removed_components
-                    .get_or_insert_with(component_id, Vec::new);
-                removed_components.push(self.entity);
+                    .get_or_insert_with(component_id, Events::default);
+                removed_components.send(self.entity);
             }
             let remove_result = archetype.swap_remove(location.index);
             if let Some(swapped_entity) = remove_result.swapped_entity {
@@ -531,11 +676,82 @@ impl<'w> EntityMut<'w> {
     }
 }
 
+// Use a non-generic function to cut down on monomorphization
+/// Moves `entity` to the archetype it has once the bundle is inserted, and returns that archetype
+/// with the status of the bundle's components in it.
+///
+/// # Safety
+/// `current_location` must be the location of `entity`, and the components of `bundle_info` must
+/// exist
+unsafe fn get_insert_bundle_info<'a>(
+    entities: &mut Entities,
+    archetypes: &'a mut Archetypes,
+    components: &mut Components,
+    storages: &mut Storages,
+    bundle_info: &BundleInfo,
+    current_location: EntityLocation,
+    entity: Entity,
+) -> (&'a Archetype, &'a Vec<ComponentStatus>, EntityLocation) {
+    // SAFE: component ids in `bundle_info` and self.location are valid
+    let new_archetype_id = add_bundle_to_archetype(
+        archetypes,
+        storages,
+        components,
+        current_location.archetype_id,
+        bundle_info,
+    );
+    if new_archetype_id == current_location.archetype_id {
+        let archetype = &archetypes[current_location.archetype_id];
+        let edge = archetype.edges().get_add_bundle(bundle_info.id).unwrap();
+        (archetype, &edge.bundle_status, current_location)
+    } else {
+        let (old_table_row, old_table_id) = {
+            let old_archetype = &mut archetypes[current_location.archetype_id];
+            let result = old_archetype.swap_remove(current_location.index);
+            if let Some(swapped_entity) = result.swapped_entity {
+                entities.meta[swapped_entity.id as usize].location = current_location;
+            }
+            (result.table_row, old_archetype.table_id())
+        };
+
+        let new_table_id = archetypes[new_archetype_id].table_id();
+
+        let new_location = if old_table_id == new_table_id {
+            archetypes[new_archetype_id].allocate(entity, old_table_row)
+        } else {
+            let (old_table, new_table) = storages.tables.get_2_mut(old_table_id, new_table_id);
+            // PERF: store "non bundle" components in edge, then just move those to avoid
+            // redundant copies
+            let move_result = old_table.move_to_superset_unchecked(old_table_row, new_table);
+
+            let new_location = archetypes[new_archetype_id].allocate(entity, move_result.new_row);
+            // if an entity was moved into this entity's table spot, update its table row
+            if let Some(swapped_entity) = move_result.swapped_entity {
+                let swapped_location = entities.get(swapped_entity).unwrap();
+                archetypes[swapped_location.archetype_id]
+                    .set_entity_table_row(swapped_location.index, old_table_row);
+            }
+            new_location
+        };
+
+        entities.meta[entity.id as usize].location = new_location;
+        let (old_archetype, new_archetype) =
+            archetypes.get_2_mut(current_location.archetype_id, new_archetype_id);
+        let edge = old_archetype
+            .edges()
+            .get_add_bundle(bundle_info.id)
+            .unwrap();
+        (&*new_archetype, &edge.bundle_status, new_location)
+
+        // Sparse set components are intentionally ignored here. They don't need to move
+    }
+}
+
 /// # Safety
 /// `entity_location` must be within bounds of the given archetype and `entity` must exist inside
 /// the archetype
 #[inline]
-unsafe fn get_component(
+pub(crate) unsafe fn get_component(
     world: &World,
     component_id: ComponentId,
     entity: Entity,
@@ -563,7 +779,7 @@ unsafe fn get_component(
 /// # Safety
 /// Caller must ensure that `component_id` is valid
 #[inline]
-unsafe fn get_component_and_ticks(
+pub(crate) unsafe fn get_component_and_ticks(
     world: &World,
     component_id: ComponentId,
     entity: Entity,
@@ -605,14 +821,14 @@ unsafe fn take_component(
     components: &Components,
     storages: &mut Storages,
     archetype: &Archetype,
-    removed_components: &mut SparseSet<ComponentId, Vec<Entity>>,
+    removed_components: &mut SparseSet<ComponentId, Events<Entity>>,
     component_id: ComponentId,
     entity: Entity,
     location: EntityLocation,
 ) -> *mut u8 {
     let component_info = components.get_info_unchecked(component_id);
-    let removed_components = removed_components.get_or_insert_with(component_id, Vec::new);
-    removed_components.push(entity);
+    let removed_components = removed_components.get_or_insert_with(component_id, Events::default);
+    removed_components.send(entity);
     match component_info.storage_type() {
         StorageType::Table => {
             let table = &storages.tables[archetype.table_id()];
@@ -879,6 +1095,45 @@ fn sorted_remove<T: Eq + Ord + Copy>(source: &mut Vec<T>, remove: &[T]) {
 
 #[cfg(test)]
 mod tests {
+    use crate::{
+        component::{ComponentDescriptor, StorageType},
+        world::World,
+    };
+    use std::any::TypeId;
+
+    #[test]
+    fn entity_components() {
+        let mut world = World::new();
+        world
+            .register_component(ComponentDescriptor::new::<u64>(StorageType::SparseSet))
+            .unwrap();
+        let entity = world.spawn().insert_bundle((1u32, 2u64)).id();
+
+        let mut type_ids = world
+            .entity(entity)
+            .components()
+            .map(|info| info.type_id().unwrap())
+            .collect::<Vec<_>>();
+        type_ids.sort();
+        let mut expected = vec![TypeId::of::<u32>(), TypeId::of::<u64>()];
+        expected.sort();
+        assert_eq!(type_ids, expected);
+
+        let mut entity_mut = world.entity_mut(entity);
+        assert!(entity_mut.contains::<u64>());
+        assert_eq!(entity_mut.remove::<u64>(), Some(2));
+        entity_mut.insert(3u8);
+        let names = entity_mut
+            .components()
+            .map(|info| info.name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&std::any::type_name::<u8>().to_string()));
+        assert!(!entity_mut.contains::<u64>());
+        entity_mut.despawn();
+        assert!(world.get_entity(entity).is_none());
+    }
+
     #[test]
     fn sorted_remove() {
         let mut a = vec![1, 2, 3, 4, 5, 6, 7];
@@ -1,10 +1,20 @@
+mod append;
+mod archetype_invariant;
+mod component_hooks;
+mod entity_clone;
 mod entity_ref;
 mod pointer;
+mod snapshot;
 mod spawn_batch;
 mod world_cell;
 
+pub use append::*;
+pub use archetype_invariant::*;
+pub use component_hooks::*;
+pub use entity_clone::*;
 pub use entity_ref::*;
 pub use pointer::*;
+pub use snapshot::*;
 pub use spawn_batch::*;
 pub use world_cell::*;
 
@@ -13,14 +23,16 @@ use crate::{
     bundle::{Bundle, Bundles},
     change_detection::Ticks,
     component::{
-        Component, ComponentDescriptor, ComponentId, ComponentTicks, Components, ComponentsError,
-        StorageType,
+        check_tick, Component, ComponentDescriptor, ComponentId, ComponentTicks, Components,
+        ComponentsError, StorageType, CHECK_TICK_THRESHOLD,
     },
     entity::{Entities, Entity},
+    event::Events,
     query::{FilterFetch, QueryState, WorldQuery},
     storage::{Column, SparseSet, Storages},
 };
 use std::{
+    alloc::Layout,
     any::TypeId,
     fmt,
     sync::atomic::{AtomicU32, Ordering},
@@ -35,6 +47,8 @@ impl Default for WorldId {
     }
 }
 
+pub(crate) type ResourceTickCheck = fn(&mut World, u32);
+
 /// [World] stores and exposes operations on [entities](Entity), [components](Component),
 /// and their associated metadata.
 /// Each [Entity] has a set of components. Each component can have up to one instance of each
@@ -47,12 +61,23 @@ pub struct World {
     pub(crate) archetypes: Archetypes,
     pub(crate) storages: Storages,
     pub(crate) bundles: Bundles,
-    pub(crate) removed_components: SparseSet<ComponentId, Vec<Entity>>,
+    /// The entities that had each component removed, in the last two calls to
+    /// [World::clear_trackers].
+    pub(crate) removed_components: SparseSet<ComponentId, Events<Entity>>,
     /// Access cache used by [WorldCell].
     pub(crate) archetype_component_access: ArchetypeComponentAccess,
+    pub(crate) archetype_invariants: ArchetypeInvariants,
+    pub(crate) component_hooks: ComponentHooks,
+    pub(crate) component_cloners: ComponentCloners,
+    pub(crate) entity_mappers: EntityMappers,
+    pub(crate) snapshot_registry: SnapshotRegistry,
+    /// Clamps the change ticks stored inside resources, such as the systems of
+    /// [`ComponentObservers`](crate::observer::ComponentObservers), by resource id.
+    pub(crate) resource_tick_checks: SparseSet<ComponentId, ResourceTickCheck>,
     main_thread_validator: MainThreadValidator,
     pub(crate) change_tick: AtomicU32,
     pub(crate) last_change_tick: u32,
+    last_check_tick: u32,
 }
 
 impl Default for World {
@@ -66,11 +91,18 @@ impl Default for World {
             bundles: Default::default(),
             removed_components: Default::default(),
             archetype_component_access: Default::default(),
+            archetype_invariants: Default::default(),
+            component_hooks: Default::default(),
+            component_cloners: Default::default(),
+            entity_mappers: Default::default(),
+            snapshot_registry: Default::default(),
+            resource_tick_checks: Default::default(),
             main_thread_validator: Default::default(),
             // Default value is `1`, and `last_change_tick`s default to `0`, such that changes
             // are detected on first system runs and for direct world queries.
             change_tick: AtomicU32::new(1),
             last_change_tick: 0,
+            last_check_tick: 0,
         }
     }
 }
@@ -124,6 +156,58 @@ impl World {
         &self.bundles
     }
 
+    /// Retrieves this world's [ArchetypeInvariants]
+    #[inline]
+    pub fn archetype_invariants(&self) -> &ArchetypeInvariants {
+        &self.archetype_invariants
+    }
+
+    /// Registers a constraint on the components entities can have together. In debug builds,
+    /// inserting or removing components in a way that breaks it panics.
+    ///
+    /// ```
+    /// use bevy_ecs::world::{ArchetypeInvariant, World};
+    ///
+    /// struct Velocity;
+    /// struct Position;
+    ///
+    /// let mut world = World::new();
+    /// world.register_archetype_invariant(ArchetypeInvariant::requires::<Velocity, Position>());
+    /// world.spawn().insert_bundle((Velocity, Position));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if an existing entity breaks the invariant.
+    pub fn register_archetype_invariant(&mut self, invariant: ArchetypeInvariant) {
+        self.archetype_invariants
+            .add(invariant, &mut self.components);
+        for archetype in self.archetypes.iter() {
+            if let Some(entity) = archetype.entities().first() {
+                self.archetype_invariants.validate(
+                    archetype,
+                    Some(*entity),
+                    format_args!("registering the invariant"),
+                );
+            }
+        }
+    }
+
+    /// Checks the [ArchetypeInvariants] in debug builds, after `entity` moved to `archetype_id`.
+    #[inline]
+    pub(crate) fn validate_archetype_invariants(
+        &mut self,
+        archetype_id: ArchetypeId,
+        entity: Option<Entity>,
+        action: fmt::Arguments,
+    ) {
+        #[cfg(debug_assertions)]
+        self.archetype_invariants
+            .validate(&self.archetypes[archetype_id], entity, action);
+        #[cfg(not(debug_assertions))]
+        let _ = (archetype_id, entity, action);
+    }
+
     /// Retrieves a [WorldCell], which safely enables multiple mutable World accesses at the same
     /// time, provided those accesses do not conflict with each other.
     #[inline]
@@ -165,6 +249,51 @@ impl World {
         Ok(component_id)
     }
 
+    /// Registers a table component without a Rust type, e.g. one defined by a scripting
+    /// language, whose values are `layout.size()` bytes that are dropped with `drop`. Use
+    /// [`ComponentDescriptor::new_with_layout`] and [World::register_component] to choose the
+    /// storage type.
+    ///
+    /// Values are inserted with [EntityMut::insert_by_id], and read with [EntityRef::get_by_id]
+    /// or a [DynamicQuery](crate::query::DynamicQuery).
+    ///
+    /// # Safety
+    /// The values of this component must be safe to send and share between threads, and `drop`
+    /// must be safe to call on a pointer to any value inserted for this component.
+    ///
+    /// ```
+    /// use bevy_ecs::world::World;
+    /// use std::alloc::Layout;
+    ///
+    /// unsafe fn drop_nothing(_: *mut u8) {}
+    ///
+    /// let mut world = World::new();
+    /// let health = unsafe {
+    ///     world.register_component_with_layout("Health", Layout::new::<f32>(), drop_nothing)
+    /// };
+    /// let mut value = 10.0f32;
+    /// let entity = unsafe {
+    ///     world
+    ///         .spawn()
+    ///         .insert_by_id(health, (&mut value as *mut f32).cast::<u8>())
+    ///         .id()
+    /// };
+    ///
+    /// let ptr = world.entity(entity).get_by_id(health).unwrap();
+    /// assert_eq!(unsafe { *ptr.cast::<f32>() }, 10.0);
+    /// ```
+    pub unsafe fn register_component_with_layout(
+        &mut self,
+        name: impl Into<String>,
+        layout: Layout,
+        drop: unsafe fn(*mut u8),
+    ) -> ComponentId {
+        let descriptor =
+            ComponentDescriptor::new_with_layout(name, StorageType::Table, layout, drop);
+        // components without a type id can't conflict
+        self.register_component(descriptor).unwrap()
+    }
+
     /// Retrieves an [EntityRef] that exposes read-only operations for the given `entity`.
     /// This will panic if the `entity` does not exist. Use [World::get_entity] if you want
     /// to check for entity existence instead of implicitly panic-ing.
@@ -334,6 +463,36 @@ impl World {
         SpawnBatchIter::new(self, iter.into_iter())
     }
 
+    /// Inserts each [Bundle] of the iterator on its [Entity], spawning the entities that don't
+    /// exist with the given id, e.g. to mirror the entities of a server. This is more efficient
+    /// than spawning or inserting on entities individually.
+    ///
+    /// Entities whose id is used by an entity of another generation are skipped, and returned as
+    /// an error. See [BundleBatch] for bundles of different types.
+    ///
+    /// ```
+    /// use bevy_ecs::{entity::Entity, world::World};
+    ///
+    /// let mut world = World::new();
+    /// let server_entities = vec![Entity::new(3), Entity::new(8)];
+    /// let bundles = server_entities.iter().map(|entity| (*entity, ("health", 100u32)));
+    /// world.insert_or_spawn_batch(bundles).unwrap();
+    /// assert_eq!(world.get::<u32>(Entity::new(8)), Some(&100));
+    /// ```
+    pub fn insert_or_spawn_batch<I, B>(&mut self, iter: I) -> Result<(), Vec<Entity>>
+    where
+        I: IntoIterator<Item = (Entity, B)>,
+        B: Bundle,
+    {
+        let mut invalid_entities = Vec::new();
+        insert_or_spawn_bundles(self, iter.into_iter(), &mut invalid_entities);
+        if invalid_entities.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid_entities)
+        }
+    }
+
     /// Retrieves a reference to the given `entity`'s [Component] of the given type.
     /// Returns [None] if the `entity` does not have a [Component] of the given type.
     /// ```
@@ -405,13 +564,14 @@ impl World {
             .unwrap_or(false)
     }
 
-    /// Clears component tracker state
+    /// Clears component tracker state, and clamps the change ticks that grew too old if needed.
     pub fn clear_trackers(&mut self) {
         for entities in self.removed_components.values_mut() {
-            entities.clear();
+            entities.update();
         }
 
         self.last_change_tick = self.increment_change_tick();
+        self.check_change_ticks();
     }
 
     /// Returns [QueryState] for the given [WorldQuery], which is used to efficiently
@@ -497,12 +657,17 @@ impl World {
 
     /// Returns an iterator of entities that had components of type `T` removed
     /// since the last call to [World::clear_trackers].
-    pub fn removed<T: Component>(&self) -> std::iter::Cloned<std::slice::Iter<'_, Entity>> {
-        if let Some(component_id) = self.components.get_id(TypeId::of::<T>()) {
-            self.removed_with_id(component_id)
-        } else {
-            [].iter().cloned()
-        }
+    ///
+    /// Removals are kept until the second call to [World::clear_trackers], so that the
+    /// [`RemovedComponents`](crate::system::RemovedComponents) of a system see them whatever the
+    /// order of the systems.
+    pub fn removed<T: Component>(&self) -> impl DoubleEndedIterator<Item = Entity> + '_ {
+        self.components
+            .get_id(TypeId::of::<T>())
+            .and_then(|component_id| self.removed_components.get(component_id))
+            .into_iter()
+            .flat_map(|removed| removed.iter_current_update_events())
+            .cloned()
     }
 
     /// Returns an iterator of entities that had components with the given `component_id` removed
@@ -510,12 +675,12 @@ impl World {
     pub fn removed_with_id(
         &self,
         component_id: ComponentId,
-    ) -> std::iter::Cloned<std::slice::Iter<'_, Entity>> {
-        if let Some(removed) = self.removed_components.get(component_id) {
-            removed.iter().cloned()
-        } else {
-            [].iter().cloned()
-        }
+    ) -> impl DoubleEndedIterator<Item = Entity> + '_ {
+        self.removed_components
+            .get(component_id)
+            .into_iter()
+            .flat_map(|removed| removed.iter_current_update_events())
+            .cloned()
     }
 
     /// Inserts a new resource with the given `value`.
@@ -730,6 +895,37 @@ impl World {
         result
     }
 
+    /// Replaces the resource `T` with `value` while `f` runs, then restores the original resource,
+    /// or removes `T` if it didn't exist. Useful to run a schedule against a stubbed resource in a
+    /// test.
+    /// ```
+    /// use bevy_ecs::world::World;
+    /// struct Gravity(f32);
+    /// let mut world = World::new();
+    /// world.insert_resource(Gravity(9.8));
+    ///
+    /// world.with_resource_override(Gravity(0.0), |world| {
+    ///     assert_eq!(world.get_resource::<Gravity>().unwrap().0, 0.0);
+    /// });
+    /// assert_eq!(world.get_resource::<Gravity>().unwrap().0, 9.8);
+    /// ```
+    pub fn with_resource_override<T: Component, U>(
+        &mut self,
+        value: T,
+        f: impl FnOnce(&mut World) -> U,
+    ) -> U {
+        let original = self.remove_resource::<T>();
+        self.insert_resource(value);
+        let result = f(self);
+        match original {
+            Some(original) => self.insert_resource(original),
+            None => {
+                self.remove_resource::<T>();
+            }
+        }
+        result
+    }
+
     /// # Safety
     /// `component_id` must be assigned to a component of type T
     #[inline]
@@ -873,7 +1069,11 @@ impl World {
     /// Empties queued entities and adds them to the empty [Archetype].
     /// This should be called before doing operations that might operate on queued entities,
     /// such as inserting a [Component].
-    pub(crate) fn flush(&mut self) {
+    ///
+    /// Entities reserved with [Entities::reserve_entity] are flushed by the world when needed,
+    /// and by each [SystemStage](crate::schedule::SystemStage) once it applied the command buffers
+    /// of its systems.
+    pub fn flush(&mut self) {
         let empty_archetype = self.archetypes.empty_mut();
         unsafe {
             let table = &mut self.storages.tables[empty_archetype.table_id()];
@@ -901,21 +1101,59 @@ impl World {
         *self.change_tick.get_mut()
     }
 
+    /// The change tick that changes are detected since, for queries run directly on the world.
+    /// Set by [`World::clear_trackers`].
     #[inline]
     pub fn last_change_tick(&self) -> u32 {
         self.last_change_tick
     }
 
+    /// Sets the change tick that changes are detected since, for queries run directly on the
+    /// world, e.g. to detect the changes since a tick that was saved earlier.
+    #[inline]
+    pub fn set_last_change_tick(&mut self, last_change_tick: u32) {
+        self.last_change_tick = last_change_tick;
+    }
+
+    /// The change tick the change ticks of the world were last checked at
+    #[inline]
+    pub fn last_check_tick(&self) -> u32 {
+        self.last_check_tick
+    }
+
+    /// Clamps the age of the change ticks of all components and resources, of
+    /// [`World::last_change_tick`] and of the systems stored in resources, so that they stay within
+    /// [`MAX_CHANGE_AGE`](crate::component::MAX_CHANGE_AGE) until the next check and old ticks
+    /// don't wrap around and look recent. This runs automatically from [`World::clear_trackers`]
+    /// and at the start and end of each [`SystemStage`](crate::schedule::SystemStage) run, and
+    /// only does work once [`CHECK_TICK_THRESHOLD`] ticks passed since the last check.
     pub fn check_change_ticks(&mut self) {
+        let change_tick = self.change_tick();
+        if change_tick.wrapping_sub(self.last_check_tick) < CHECK_TICK_THRESHOLD {
+            return;
+        }
+        self.check_all_change_ticks();
+    }
+
+    /// Clamps the age of all the change ticks of the world, regardless of when they were last
+    /// checked. See [`World::check_change_ticks`].
+    pub fn check_all_change_ticks(&mut self) {
         // Iterate over all component change ticks, clamping their age to max age
         // PERF: parallelize
         let change_tick = self.change_tick();
+        check_tick(&mut self.last_change_tick, change_tick);
         self.storages.tables.check_change_ticks(change_tick);
         self.storages.sparse_sets.check_change_ticks(change_tick);
         let resource_archetype = self.archetypes.resource_mut();
         for column in resource_archetype.unique_components.values_mut() {
             column.check_change_ticks(change_tick);
         }
+        let resource_tick_checks: Vec<ResourceTickCheck> =
+            self.resource_tick_checks.values().copied().collect();
+        for check in resource_tick_checks {
+            check(self, change_tick);
+        }
+        self.last_check_tick = change_tick;
     }
 }
 
@@ -0,0 +1,361 @@
+use crate::{
+    archetype::ArchetypeId,
+    component::{Component, ComponentId, ComponentTicks},
+    entity::{EntitiesSnapshot, Entity, EntityLocation},
+    storage::SparseSet,
+    world::{get_component_and_ticks, World, WorldId},
+};
+use bevy_utils::HashSet;
+use std::any::{Any, TypeId};
+
+type SnapshotData = Box<dyn Any + Send + Sync>;
+
+#[derive(Clone, Copy)]
+struct SnapshotFns {
+    capture: fn(&World) -> SnapshotData,
+    restore: fn(&mut World, &SnapshotData),
+}
+
+/// The components and resources captured by [`World::snapshot`], registered with
+/// [`World::register_snapshot`] and [`World::register_snapshot_resource`].
+#[derive(Default)]
+pub(crate) struct SnapshotRegistry {
+    components: SparseSet<ComponentId, SnapshotFns>,
+    resources: SparseSet<ComponentId, SnapshotFns>,
+}
+
+/// A copy of the entities of a [`World`] with their registered components, and of its registered
+/// resources, taken by [`World::snapshot`] and put back by [`World::restore`].
+pub struct WorldSnapshot {
+    world_id: WorldId,
+    entities: Vec<Entity>,
+    allocator: EntitiesSnapshot,
+    data: Vec<(SnapshotFns, SnapshotData)>,
+}
+
+impl WorldSnapshot {
+    /// The entities that were alive when the snapshot was taken.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+}
+
+fn alive_entities(world: &World) -> Vec<Entity> {
+    world
+        .archetypes
+        .iter()
+        .filter(|archetype| archetype.id() != ArchetypeId::resource())
+        .flat_map(|archetype| archetype.entities().iter().copied())
+        .collect()
+}
+
+fn entities_with(world: &World, component_id: ComponentId) -> Vec<(Entity, EntityLocation)> {
+    let mut entities = Vec::new();
+    for archetype in world.archetypes.iter() {
+        if archetype.id() != ArchetypeId::resource() && archetype.contains(component_id) {
+            for (index, entity) in archetype.entities().iter().enumerate() {
+                let location = EntityLocation {
+                    archetype_id: archetype.id(),
+                    index,
+                };
+                entities.push((*entity, location));
+            }
+        }
+    }
+    entities
+}
+
+fn capture_component<T: Component + Clone>(world: &World) -> SnapshotData {
+    let component_id = world.components.get_id(TypeId::of::<T>()).unwrap();
+    let values: Vec<(Entity, T, ComponentTicks)> = entities_with(world, component_id)
+        .into_iter()
+        .map(|(entity, location)| {
+            // SAFE: the component is registered, and stored in the archetype of `location`
+            unsafe {
+                let (value, ticks) =
+                    get_component_and_ticks(world, component_id, entity, location).unwrap();
+                (entity, (*value.cast::<T>()).clone(), (*ticks).clone())
+            }
+        })
+        .collect();
+    Box::new(values)
+}
+
+fn restore_component<T: Component + Clone>(world: &mut World, data: &SnapshotData) {
+    let values = data
+        .downcast_ref::<Vec<(Entity, T, ComponentTicks)>>()
+        .unwrap();
+    let component_id = world.components.get_id(TypeId::of::<T>()).unwrap();
+    let kept: HashSet<Entity> = values.iter().map(|(entity, _, _)| *entity).collect();
+    for (entity, _) in entities_with(world, component_id) {
+        if !kept.contains(&entity) {
+            world.entity_mut(entity).remove::<T>();
+        }
+    }
+    for (entity, value, ticks) in values.iter() {
+        let mut location = world.entities.get(*entity).unwrap();
+        if !world.archetypes[location.archetype_id].contains(component_id) {
+            location = world.entity_mut(*entity).insert(value.clone()).location();
+        }
+        // SAFE: the entity has the component, in the archetype of `location`. Assigning drops the
+        // previous value.
+        unsafe {
+            let (current_value, current_ticks) =
+                get_component_and_ticks(world, component_id, *entity, location).unwrap();
+            *current_value.cast::<T>() = value.clone();
+            *current_ticks = ticks.clone();
+        }
+    }
+}
+
+fn capture_resource<T: Component + Clone>(world: &World) -> SnapshotData {
+    let value: Option<(T, ComponentTicks)> = world
+        .components
+        .get_resource_id(TypeId::of::<T>())
+        .and_then(|component_id| world.get_populated_resource_column(component_id))
+        .map(|column| {
+            // SAFE: the column stores a single resource of type T
+            unsafe {
+                (
+                    (*column.get_data_ptr().cast::<T>().as_ptr()).clone(),
+                    column.get_ticks_unchecked(0).clone(),
+                )
+            }
+        });
+    Box::new(value)
+}
+
+fn restore_resource<T: Component + Clone>(world: &mut World, data: &SnapshotData) {
+    match data.downcast_ref::<Option<(T, ComponentTicks)>>().unwrap() {
+        Some((value, ticks)) => {
+            world.insert_resource(value.clone());
+            let component_id = world.components.get_resource_id(TypeId::of::<T>()).unwrap();
+            let column = world.get_populated_resource_column(component_id).unwrap();
+            // SAFE: the resource was just inserted, and the world is borrowed mutably
+            unsafe {
+                *column.get_ticks_mut_ptr_unchecked(0) = ticks.clone();
+            }
+        }
+        None => {
+            world.remove_resource::<T>();
+        }
+    }
+}
+
+impl World {
+    /// Registers the component `T` as copied by [`World::snapshot`].
+    pub fn register_snapshot<T: Component + Clone>(&mut self) -> &mut Self {
+        let component_id = self.components.get_or_insert_id::<T>();
+        self.snapshot_registry.components.insert(
+            component_id,
+            SnapshotFns {
+                capture: capture_component::<T>,
+                restore: restore_component::<T>,
+            },
+        );
+        self
+    }
+
+    /// Registers the resource `T` as copied by [`World::snapshot`].
+    pub fn register_snapshot_resource<T: Component + Clone>(&mut self) -> &mut Self {
+        let component_id = self.components.get_or_insert_resource_id::<T>();
+        self.snapshot_registry.resources.insert(
+            component_id,
+            SnapshotFns {
+                capture: capture_resource::<T>,
+                restore: restore_resource::<T>,
+            },
+        );
+        self
+    }
+
+    /// Copies the alive entities, the components registered with [`World::register_snapshot`]
+    /// and the resources registered with [`World::register_snapshot_resource`], with their change
+    /// ticks. Other components and resources are not captured, and are left as they are by
+    /// [`World::restore`].
+    ///
+    /// This is meant for rollback, e.g. to resimulate the frames of a networked game after a late
+    /// input arrives, so the components should be cheap to clone.
+    ///
+    /// ```
+    /// use bevy_ecs::world::World;
+    ///
+    /// #[derive(Clone, Debug, PartialEq)]
+    /// struct Position(f32);
+    ///
+    /// let mut world = World::new();
+    /// world.register_snapshot::<Position>();
+    /// let player = world.spawn().insert(Position(0.0)).id();
+    ///
+    /// let snapshot = world.snapshot();
+    /// world.get_mut::<Position>(player).unwrap().0 = 5.0;
+    /// let projectile = world.spawn().insert(Position(1.0)).id();
+    ///
+    /// world.restore(&snapshot);
+    /// assert_eq!(world.get::<Position>(player), Some(&Position(0.0)));
+    /// assert!(world.get_entity(projectile).is_none());
+    /// ```
+    pub fn snapshot(&mut self) -> WorldSnapshot {
+        self.flush();
+        let world: &World = self;
+        let data = world
+            .snapshot_registry
+            .components
+            .values()
+            .chain(world.snapshot_registry.resources.values())
+            .map(|fns| (*fns, (fns.capture)(world)))
+            .collect();
+        WorldSnapshot {
+            world_id: self.id,
+            entities: alive_entities(self),
+            allocator: self.entities.snapshot_allocator(),
+            data,
+        }
+    }
+
+    /// Puts back the state captured by [`World::snapshot`]: entities spawned since are despawned,
+    /// despawned entities are spawned again with the same [`Entity`], and the registered
+    /// components and resources get back their values and change ticks. The entities spawned next
+    /// get the same IDs as after the snapshot was taken.
+    ///
+    /// The values of components the entities still have are overwritten in place. Restoring
+    /// doesn't run the component hooks, nor the observers driven by them, so structures kept in
+    /// sync by hooks should be registered as snapshot resources too.
+    ///
+    /// The change tick of the world itself is not rolled back, but the restored components and
+    /// resources get back their captured change ticks. `Added` and `Changed` filters therefore
+    /// don't report the rollback: a system that saw a value change after the snapshot won't see it
+    /// change back.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the snapshot was taken from another world.
+    pub fn restore(&mut self, snapshot: &WorldSnapshot) {
+        assert_eq!(
+            self.id, snapshot.world_id,
+            "a snapshot can only be restored in the world it was taken from"
+        );
+        self.flush();
+        let component_hooks = std::mem::take(&mut self.component_hooks);
+
+        let alive: HashSet<Entity> = snapshot.entities.iter().copied().collect();
+        for entity in alive_entities(self) {
+            if !alive.contains(&entity) {
+                self.despawn(entity);
+            }
+        }
+        for entity in snapshot.entities.iter() {
+            if self.entities.get(*entity).is_none() {
+                self.entities.alloc_at(*entity);
+                let empty_archetype = self.archetypes.empty_mut();
+                let table = &mut self.storages.tables[empty_archetype.table_id()];
+                // SAFE: no components are allocated by archetype.allocate() because the archetype
+                // is empty
+                let location =
+                    unsafe { empty_archetype.allocate(*entity, table.allocate(*entity)) };
+                self.entities.meta[entity.id as usize].location = location;
+            }
+        }
+
+        for (fns, data) in snapshot.data.iter() {
+            (fns.restore)(self, data);
+        }
+        self.entities.restore_allocator(&snapshot.allocator);
+        self.component_hooks = component_hooks;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{component::ComponentTicks, world::World};
+    use std::any::TypeId;
+
+    #[derive(Clone, Debug, PartialEq)]
+    struct Position(u32);
+    #[derive(Clone, Debug, PartialEq)]
+    struct Frame(u32);
+    struct NotCaptured;
+
+    #[test]
+    fn snapshot_and_restore() {
+        let mut world = World::new();
+        world
+            .register_snapshot::<Position>()
+            .register_snapshot_resource::<Frame>();
+        world.insert_resource(Frame(0));
+        let moved = world.spawn().insert(Position(0)).id();
+        let despawned = world.spawn().insert(Position(1)).id();
+        let stripped = world.spawn().insert(Position(2)).id();
+        let kept = world.spawn().insert(NotCaptured).id();
+
+        let snapshot = world.snapshot();
+        let component_id = world.components.get_id(TypeId::of::<Position>()).unwrap();
+        let ticks_of = |world: &World, entity| -> ComponentTicks {
+            world
+                .entity(entity)
+                .get_change_ticks_by_id(component_id)
+                .unwrap()
+                .clone()
+        };
+        let moved_ticks = ticks_of(&world, moved);
+
+        world.increment_change_tick();
+        world.get_mut::<Position>(moved).unwrap().0 = 10;
+        world.despawn(despawned);
+        world.entity_mut(stripped).remove::<Position>();
+        let spawned = world.spawn().insert(Position(3)).id();
+        world.insert_resource(Frame(1));
+
+        world.restore(&snapshot);
+        assert_eq!(world.get::<Position>(moved), Some(&Position(0)));
+        assert_eq!(world.get::<Position>(despawned), Some(&Position(1)));
+        assert_eq!(world.get::<Position>(stripped), Some(&Position(2)));
+        assert!(world.get::<NotCaptured>(kept).is_some());
+        assert!(world.get_entity(spawned).is_none());
+        assert_eq!(world.get_resource::<Frame>(), Some(&Frame(0)));
+        assert_eq!(ticks_of(&world, moved).added, moved_ticks.added);
+        assert_eq!(ticks_of(&world, moved).changed, moved_ticks.changed);
+
+        // the entities spawned after a restore get the same ids every time
+        let respawned = world.spawn().id();
+        world.restore(&snapshot);
+        assert_eq!(world.entities().len(), 4);
+        assert_eq!(world.spawn().id(), respawned);
+    }
+
+    #[test]
+    fn restore_skips_hooks() {
+        #[derive(Default)]
+        struct HookRuns(u32);
+
+        let mut world = World::new();
+        world.register_snapshot::<Position>();
+        world.insert_resource(HookRuns::default());
+        world.on_insert(|world, _, _: &Position| {
+            world.get_resource_mut::<HookRuns>().unwrap().0 += 1;
+        });
+        world.on_remove(|world, _, _: &Position| {
+            world.get_resource_mut::<HookRuns>().unwrap().0 += 1;
+        });
+        let moved = world.spawn().insert(Position(0)).id();
+        let location = world.entity(moved).location();
+        let snapshot = world.snapshot();
+
+        world.get_mut::<Position>(moved).unwrap().0 = 10;
+        world.spawn().insert(Position(1));
+        let runs = world.get_resource::<HookRuns>().unwrap().0;
+
+        world.restore(&snapshot);
+        assert_eq!(world.get::<Position>(moved), Some(&Position(0)));
+        assert_eq!(
+            world.entity(moved).location().archetype_id,
+            location.archetype_id
+        );
+        assert_eq!(world.entity(moved).location().index, location.index);
+        assert_eq!(world.get_resource::<HookRuns>().unwrap().0, runs);
+
+        // the hooks still run after restoring
+        world.spawn().insert(Position(2));
+        assert_eq!(world.get_resource::<HookRuns>().unwrap().0, runs + 1);
+    }
+}
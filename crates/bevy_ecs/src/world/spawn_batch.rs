@@ -1,10 +1,12 @@
 use crate::{
     archetype::{Archetype, ArchetypeId, ComponentStatus},
     bundle::{Bundle, BundleInfo},
-    entity::{Entities, Entity},
+    entity::{AllocAtWithoutReplacement, Entities, Entity},
     storage::{SparseSets, Table},
-    world::{add_bundle_to_archetype, World},
+    world::{add_bundle_to_archetype, EntityMut, HookKind, World},
 };
+use bevy_utils::{HashMap, HashSet};
+use std::any::TypeId;
 
 pub struct SpawnBatchIter<'w, I>
 where
@@ -19,6 +21,8 @@ where
     bundle_info: &'w BundleInfo,
     bundle_status: &'w [ComponentStatus],
     change_tick: u32,
+    /// Entities already spawned one by one, because the bundle has component hooks.
+    spawned: std::vec::IntoIter<Entity>,
 }
 
 impl<'w, I> SpawnBatchIter<'w, I>
@@ -27,11 +31,27 @@ where
     I::Item: Bundle,
 {
     #[inline]
-    pub(crate) fn new(world: &'w mut World, iter: I) -> Self {
+    pub(crate) fn new(world: &'w mut World, mut iter: I) -> Self {
         // Ensure all entity allocations are accounted for so `self.entities` can realloc if
         // necessary
         world.flush();
 
+        // Component hooks need the world, which the iterator borrows, so the entities are spawned
+        // one by one ahead
+        let mut spawned = Vec::new();
+        if world.component_hooks.has_insert_hooks() {
+            let bundle_info = world.bundles.init_info::<I::Item>(&mut world.components);
+            let hooks = &world.component_hooks;
+            if bundle_info.component_ids.iter().any(|id| {
+                hooks.contains(*id, HookKind::Add) || hooks.contains(*id, HookKind::Insert)
+            }) {
+                spawned.extend(
+                    iter.by_ref()
+                        .map(|bundle| world.spawn().insert_bundle(bundle).id()),
+                );
+            }
+        }
+
         let (lower, upper) = iter.size_hint();
 
         let bundle_info = world.bundles.init_info::<I::Item>(&mut world.components);
@@ -47,6 +67,12 @@ where
                 bundle_info,
             )
         };
+        #[cfg(debug_assertions)]
+        world.archetype_invariants.validate(
+            &world.archetypes[archetype_id],
+            None,
+            format_args!("spawning a batch of {}", std::any::type_name::<I::Item>()),
+        );
         let (empty_archetype, archetype) = world
             .archetypes
             .get_2_mut(ArchetypeId::empty(), archetype_id);
@@ -67,6 +93,7 @@ where
             bundle_info,
             change_tick: *world.change_tick.get_mut(),
             bundle_status: &edge.bundle_status,
+            spawned: spawned.into_iter(),
         }
     }
 }
@@ -89,6 +116,9 @@ where
     type Item = Entity;
 
     fn next(&mut self) -> Option<Entity> {
+        if let Some(entity) = self.spawned.next() {
+            return Some(entity);
+        }
         let bundle = self.inner.next()?;
         let entity = self.entities.alloc();
         // SAFE: component values are immediately written to relevant storages (which have been
@@ -111,7 +141,9 @@ where
     }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
-        self.inner.size_hint()
+        let spawned = self.spawned.len();
+        let (lower, upper) = self.inner.size_hint();
+        (lower + spawned, upper.map(|upper| upper + spawned))
     }
 }
 
@@ -121,6 +153,252 @@ where
     T: Bundle,
 {
     fn len(&self) -> usize {
-        self.inner.len()
+        self.inner.len() + self.spawned.len()
+    }
+}
+
+/// Inserts each bundle of `iter` on its entity, spawning the entities that don't exist with their
+/// id. Entities whose id is used by another generation are pushed to `invalid_entities`. Returns
+/// how many entities were spawned.
+///
+/// The archetype of entities spawned with `B` is looked up once, so spawning entities is as fast
+/// as with [`World::spawn_batch`]. This includes the entities reserved ahead with
+/// [`Entities::reserve_entity`], which are spawned straight in that archetype rather than flushed
+/// to the empty archetype first. Bundles inserted on existing entities, and the component hooks
+/// of the spawned entities, are applied once all the entities were spawned.
+pub(crate) fn insert_or_spawn_bundles<I, B>(
+    world: &mut World,
+    iter: I,
+    invalid_entities: &mut Vec<Entity>,
+) -> usize
+where
+    I: Iterator<Item = (Entity, B)>,
+    B: Bundle,
+{
+    // the reserved entities are allocated, but their location is only written below
+    let mut reserved = HashSet::default();
+    world.entities.flush(|entity, _| {
+        reserved.insert(entity.id);
+    });
+    let change_tick = *world.change_tick.get_mut();
+    let bundle_info = world.bundles.init_info::<B>(&mut world.components);
+    let bundle_id = bundle_info.id();
+    // SAFE: empty archetype exists and bundle components were initialized above
+    let archetype_id = unsafe {
+        add_bundle_to_archetype(
+            &mut world.archetypes,
+            &mut world.storages,
+            &mut world.components,
+            ArchetypeId::empty(),
+            bundle_info,
+        )
+    };
+    world.validate_archetype_invariants(
+        archetype_id,
+        None,
+        format_args!("spawning a batch of {}", std::any::type_name::<B>()),
+    );
+    let (length, _) = iter.size_hint();
+    {
+        let archetype = &mut world.archetypes[archetype_id];
+        archetype.reserve(length);
+        world.storages.tables[archetype.table_id()].reserve(length);
+    }
+
+    let mut spawned = Vec::new();
+    let mut existing = Vec::new();
+    for (entity, bundle) in iter {
+        let allocated = if reserved.contains(&entity.id) {
+            if world.entities.meta[entity.id as usize].generation == entity.generation {
+                reserved.remove(&entity.id);
+                AllocAtWithoutReplacement::DidNotExist
+            } else {
+                AllocAtWithoutReplacement::ExistsWithWrongGeneration
+            }
+        } else {
+            world.entities.alloc_at_without_replacement_unlisted(entity)
+        };
+        match allocated {
+            AllocAtWithoutReplacement::Exists(_) => existing.push((entity, bundle)),
+            AllocAtWithoutReplacement::ExistsWithWrongGeneration => {
+                invalid_entities.push(entity);
+            }
+            AllocAtWithoutReplacement::DidNotExist if archetype_id == ArchetypeId::empty() => {
+                // the bundle has no components
+                let archetype = &mut world.archetypes[archetype_id];
+                let table = &mut world.storages.tables[archetype.table_id()];
+                // SAFE: no components are allocated by archetype.allocate() because the archetype
+                // is empty
+                unsafe {
+                    let location = archetype.allocate(entity, table.allocate(entity));
+                    world.entities.meta[entity.id as usize].location = location;
+                }
+                spawned.push(entity);
+            }
+            AllocAtWithoutReplacement::DidNotExist => {
+                let (empty_archetype, archetype) = world
+                    .archetypes
+                    .get_2_mut(ArchetypeId::empty(), archetype_id);
+                let table = &mut world.storages.tables[archetype.table_id()];
+                let bundle_info = world.bundles.get(bundle_id).unwrap();
+                let bundle_status = &empty_archetype
+                    .edges()
+                    .get_add_bundle(bundle_id)
+                    .unwrap()
+                    .bundle_status;
+                // SAFE: component values are immediately written to relevant storages (which have
+                // been allocated)
+                unsafe {
+                    let table_row = table.allocate(entity);
+                    let location = archetype.allocate(entity, table_row);
+                    bundle_info.write_components(
+                        &mut world.storages.sparse_sets,
+                        entity,
+                        table,
+                        table_row,
+                        bundle_status,
+                        bundle,
+                        change_tick,
+                    );
+                    world.entities.meta[entity.id as usize].location = location;
+                }
+                spawned.push(entity);
+            }
+        }
+    }
+
+    // the reserved entities that weren't in the batch are spawned empty, like with `World::flush`
+    let empty_archetype = world.archetypes.empty_mut();
+    let table = &mut world.storages.tables[empty_archetype.table_id()];
+    for id in reserved {
+        let meta = &mut world.entities.meta[id as usize];
+        let entity = Entity {
+            id,
+            generation: meta.generation,
+        };
+        // SAFE: no components are allocated by archetype.allocate() because the archetype is
+        // empty
+        meta.location = unsafe { empty_archetype.allocate(entity, table.allocate(entity)) };
+    }
+    world.entities.remove_allocated_from_freelist();
+
+    for (entity, bundle) in existing {
+        let location = world.entities.get(entity).unwrap();
+        // SAFE: `entity` exists and `location` is that entity's location
+        unsafe { EntityMut::new(world, entity, location) }.insert_bundle(bundle);
+    }
+    if world.component_hooks.has_insert_hooks() {
+        let inserted = world
+            .bundles
+            .get(bundle_id)
+            .unwrap()
+            .component_ids
+            .iter()
+            .map(|id| (*id, true))
+            .collect::<Vec<_>>();
+        for entity in spawned.iter() {
+            let location = world.entities.get(*entity).unwrap();
+            world.trigger_insert_hooks(*entity, location, &inserted);
+        }
+    }
+    spawned.len()
+}
+
+/// Bundles of different types to write to the world in bulk, such as the entities of a level or
+/// of a network update.
+///
+/// The bundles are grouped by type, and each group is written like with
+/// [`World::insert_or_spawn_batch`], so the archetype of each bundle type is only looked up once.
+/// The entities can be allocated ahead with [`BundleBatch::spawn`], so that bundles can refer to
+/// each other.
+///
+/// ```
+/// # use bevy_ecs::{prelude::*, world::BundleBatch};
+/// # struct Player;
+/// # struct Enemy;
+/// # struct Target(Entity);
+/// let mut world = World::default();
+/// let mut batch = BundleBatch::default();
+/// let player = batch.spawn(&world, (Player,));
+/// for _ in 0..10 {
+///     batch.spawn(&world, (Enemy, Target(player)));
+/// }
+/// batch.write(&mut world).unwrap();
+/// ```
+#[derive(Default)]
+pub struct BundleBatch {
+    groups: Vec<Box<dyn BundleGroup>>,
+    group_indices: HashMap<TypeId, usize>,
+    len: usize,
+}
+
+impl BundleBatch {
+    /// Reserves an entity in `world`, spawned with `bundle` once the batch is written.
+    pub fn spawn<B: Bundle>(&mut self, world: &World, bundle: B) -> Entity {
+        let entity = world.entities().reserve_entity();
+        self.insert(entity, bundle);
+        entity
+    }
+
+    /// Adds `bundle` to `entity` once the batch is written. The entity is spawned with that id if
+    /// it doesn't exist.
+    pub fn insert<B: Bundle>(&mut self, entity: Entity, bundle: B) -> &mut Self {
+        let groups = &mut self.groups;
+        let index = *self
+            .group_indices
+            .entry(TypeId::of::<B>())
+            .or_insert_with(|| {
+                groups.push(Box::new(TypedBundleGroup::<B>(Vec::new())));
+                groups.len() - 1
+            });
+        self.groups[index]
+            .as_any_mut()
+            .downcast_mut::<TypedBundleGroup<B>>()
+            .unwrap()
+            .0
+            .push((entity, bundle));
+        self.len += 1;
+        self
+    }
+
+    /// The number of bundles in the batch
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes the bundles to `world`, one bundle type at a time. Returns the entities whose id is
+    /// used by an entity of another generation, which are skipped.
+    pub fn write(self, world: &mut World) -> Result<(), Vec<Entity>> {
+        let mut invalid_entities = Vec::new();
+        for group in self.groups {
+            group.write(world, &mut invalid_entities);
+        }
+        if invalid_entities.is_empty() {
+            Ok(())
+        } else {
+            Err(invalid_entities)
+        }
+    }
+}
+
+trait BundleGroup: Send + Sync + 'static {
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any;
+
+    fn write(self: Box<Self>, world: &mut World, invalid_entities: &mut Vec<Entity>);
+}
+
+struct TypedBundleGroup<B: Bundle>(Vec<(Entity, B)>);
+
+impl<B: Bundle> BundleGroup for TypedBundleGroup<B> {
+    fn as_any_mut(&mut self) -> &mut dyn std::any::Any {
+        self
+    }
+
+    fn write(self: Box<Self>, world: &mut World, invalid_entities: &mut Vec<Entity>) {
+        insert_or_spawn_bundles(world, self.0.into_iter(), invalid_entities);
     }
 }
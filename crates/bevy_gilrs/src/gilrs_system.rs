@@ -1,4 +1,4 @@
-use crate::converter::{convert_axis, convert_button, convert_gamepad_id};
+use crate::converter::{convert_axis, convert_button, convert_gamepad_id, convert_gamepad_info};
 use bevy_app::Events;
 use bevy_ecs::world::World;
 use bevy_input::{gamepad::GamepadEventRaw, prelude::*};
@@ -8,10 +8,10 @@ pub fn gilrs_event_startup_system(world: &mut World) {
     let world = world.cell();
     let gilrs = world.get_non_send::<Gilrs>().unwrap();
     let mut event = world.get_resource_mut::<Events<GamepadEventRaw>>().unwrap();
-    for (id, _) in gilrs.gamepads() {
+    for (id, gamepad) in gilrs.gamepads() {
         event.send(GamepadEventRaw(
             convert_gamepad_id(id),
-            GamepadEventType::Connected,
+            GamepadEventType::Connected(convert_gamepad_info(gamepad)),
         ));
     }
 }
@@ -24,9 +24,10 @@ pub fn gilrs_event_system(world: &mut World) {
     while let Some(gilrs_event) = gilrs.next_event() {
         match gilrs_event.event {
             EventType::Connected => {
+                let gamepad = gilrs.gamepad(gilrs_event.id);
                 event.send(GamepadEventRaw(
                     convert_gamepad_id(gilrs_event.id),
-                    GamepadEventType::Connected,
+                    GamepadEventType::Connected(convert_gamepad_info(gamepad)),
                 ));
             }
             EventType::Disconnected => {
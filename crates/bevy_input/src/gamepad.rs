@@ -7,19 +7,37 @@ use bevy_utils::HashMap;
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct Gamepad(pub usize);
 
+/// The device a [`Gamepad`] is connected through, as reported by the platform.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct GamepadInfo {
+    pub name: String,
+    /// The USB vendor id of the device, if the platform exposes it
+    pub vendor_id: Option<u16>,
+    /// The USB product id of the device, if the platform exposes it
+    pub product_id: Option<u16>,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum GamepadEventType {
-    Connected,
+    Connected(GamepadInfo),
     Disconnected,
     ButtonChanged(GamepadButtonType, f32),
     AxisChanged(GamepadAxisType, f32),
 }
 
+/// A gamepad event, filtered through the [`GamepadSettings`] by [`gamepad_event_system`].
+///
+/// Unlike keyboard and mouse events, gamepad events don't carry the time at which they happened:
+/// the presses of gamepad buttons are timestamped in [`Input<GamepadButton>`](crate::Input) when
+/// [`gamepad_event_system`] processes them, in the same frame as they are polled. No input event
+/// is tagged with the frame it was received in.
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct GamepadEvent(pub Gamepad, pub GamepadEventType);
 
+/// A gamepad event as reported by the gamepad backend. See [`GamepadEvent`].
 #[derive(Debug, Clone, PartialEq)]
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct GamepadEventRaw(pub Gamepad, pub GamepadEventType);
@@ -69,6 +87,147 @@ pub enum GamepadAxisType {
 #[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct GamepadAxis(pub Gamepad, pub GamepadAxisType);
 
+#[derive(Debug, Clone)]
+struct PlayerSlot {
+    gamepad: Gamepad,
+    info: GamepadInfo,
+    connected: bool,
+}
+
+/// The connected gamepads, and the player each of them is assigned to.
+///
+/// Players are numbered from `0`. When [`Gamepads::auto_assign`] is enabled, which it is by
+/// default, a newly connected gamepad is assigned to the first free player. A player keeps its
+/// slot when its gamepad is disconnected: the gamepad gets it back when reconnected, even under
+/// a new [`Gamepad`] id, as long as no other disconnected player used the same kind of device.
+/// Call [`Gamepads::release_player`] to free the slot of a player that left the game.
+#[derive(Debug)]
+pub struct Gamepads {
+    connected: HashMap<Gamepad, GamepadInfo>,
+    players: Vec<Option<PlayerSlot>>,
+    /// Whether newly connected gamepads are assigned to the first free player
+    pub auto_assign: bool,
+}
+
+impl Default for Gamepads {
+    fn default() -> Self {
+        Gamepads {
+            connected: Default::default(),
+            players: Vec::new(),
+            auto_assign: true,
+        }
+    }
+}
+
+impl Gamepads {
+    /// Iterates over the connected gamepads
+    pub fn iter(&self) -> impl Iterator<Item = Gamepad> + '_ {
+        self.connected.keys().copied()
+    }
+
+    pub fn contains(&self, gamepad: Gamepad) -> bool {
+        self.connected.contains_key(&gamepad)
+    }
+
+    /// The device of a connected gamepad
+    pub fn info(&self, gamepad: Gamepad) -> Option<&GamepadInfo> {
+        self.connected.get(&gamepad)
+    }
+
+    /// The player a connected gamepad is assigned to
+    pub fn player(&self, gamepad: Gamepad) -> Option<usize> {
+        self.players.iter().position(
+            |slot| matches!(slot, Some(slot) if slot.connected && slot.gamepad == gamepad),
+        )
+    }
+
+    /// The gamepad of a player, if it is connected
+    pub fn player_gamepad(&self, player: usize) -> Option<Gamepad> {
+        match self.players.get(player) {
+            Some(Some(slot)) if slot.connected => Some(slot.gamepad),
+            _ => None,
+        }
+    }
+
+    /// The number of player slots, including the slots of players whose gamepad is disconnected
+    pub fn player_count(&self) -> usize {
+        self.players.iter().filter(|slot| slot.is_some()).count()
+    }
+
+    /// Assigns a connected gamepad to a player. The gamepad previously assigned to that player,
+    /// if any, takes the former slot of `gamepad`. Returns `false` if `gamepad` isn't connected.
+    pub fn assign_player(&mut self, gamepad: Gamepad, player: usize) -> bool {
+        let info = match self.connected.get(&gamepad) {
+            Some(info) => info.clone(),
+            None => return false,
+        };
+        if self.players.len() <= player {
+            self.players.resize(player + 1, None);
+        }
+        let previous = self.player(gamepad);
+        let replaced = self.players[player].replace(PlayerSlot {
+            gamepad,
+            info,
+            connected: true,
+        });
+        if let Some(previous) = previous.filter(|previous| *previous != player) {
+            self.players[previous] = replaced;
+        }
+        true
+    }
+
+    /// Frees the slot of a player, so that it can be assigned to another gamepad.
+    pub fn release_player(&mut self, player: usize) {
+        if let Some(slot) = self.players.get_mut(player) {
+            *slot = None;
+        }
+    }
+
+    fn connect(&mut self, gamepad: Gamepad, info: GamepadInfo) {
+        self.connected.insert(gamepad, info.clone());
+        let reconnected = self
+            .players
+            .iter()
+            .position(
+                |slot| matches!(slot, Some(slot) if !slot.connected && slot.gamepad == gamepad),
+            )
+            .or_else(|| {
+                let mut same_device = self.players.iter().enumerate().filter(
+                    |(_, slot)| matches!(slot, Some(slot) if !slot.connected && slot.info == info),
+                );
+                match (same_device.next(), same_device.next()) {
+                    (Some((player, _)), None) => Some(player),
+                    _ => None,
+                }
+            });
+        let player = match reconnected {
+            Some(player) => player,
+            None if self.auto_assign => match self.players.iter().position(Option::is_none) {
+                Some(player) => player,
+                None => {
+                    self.players.push(None);
+                    self.players.len() - 1
+                }
+            },
+            None => return,
+        };
+        self.players[player] = Some(PlayerSlot {
+            gamepad,
+            info,
+            connected: true,
+        });
+    }
+
+    fn disconnect(&mut self, gamepad: Gamepad) {
+        self.connected.remove(&gamepad);
+        for slot in self.players.iter_mut().flatten() {
+            if slot.gamepad == gamepad {
+                slot.connected = false;
+            }
+        }
+    }
+}
+
 #[derive(Default, Debug)]
 pub struct GamepadSettings {
     pub default_button_settings: ButtonSettings,
@@ -124,13 +283,22 @@ impl ButtonSettings {
     }
 }
 
+/// Settings applied to the raw values of a gamepad axis before they are published.
 #[derive(Debug, Clone)]
 pub struct AxisSettings {
+    /// Values above this are reported as `1.0`.
     pub positive_high: f32,
+    /// Values between `negative_low` and `positive_low` (the deadzone) are reported as `0.0`.
     pub positive_low: f32,
+    /// Values below this are reported as `-1.0`.
     pub negative_high: f32,
+    /// Values between `negative_low` and `positive_low` (the deadzone) are reported as `0.0`.
     pub negative_low: f32,
+    /// Changes smaller than this are ignored.
     pub threshold: f32,
+    /// Values outside of the deadzone are multiplied by this, before being clamped to the
+    /// `negative_high..=positive_high` range.
+    pub sensitivity: f32,
 }
 
 impl Default for AxisSettings {
@@ -141,15 +309,28 @@ impl Default for AxisSettings {
             negative_high: -0.95,
             negative_low: -0.05,
             threshold: 0.01,
+            sensitivity: 1.0,
         }
     }
 }
 
 impl AxisSettings {
+    /// Returns the default settings with a symmetric deadzone of the given size.
+    pub fn with_deadzone(deadzone: f32) -> Self {
+        AxisSettings {
+            positive_low: deadzone,
+            negative_low: -deadzone,
+            ..Default::default()
+        }
+    }
+
     fn filter(&self, new_value: f32, old_value: Option<f32>) -> Option<f32> {
         let new_value = if new_value <= self.positive_low && new_value >= self.negative_low {
             0.0
-        } else if new_value >= self.positive_high {
+        } else {
+            new_value * self.sensitivity
+        };
+        let new_value = if new_value >= self.positive_high {
             1.0
         } else if new_value <= self.negative_high {
             -1.0
@@ -207,13 +388,15 @@ pub fn gamepad_event_system(
     mut button_axis: ResMut<Axis<GamepadButton>>,
     mut raw_events: EventReader<GamepadEventRaw>,
     mut events: EventWriter<GamepadEvent>,
+    mut gamepads: ResMut<Gamepads>,
     settings: Res<GamepadSettings>,
 ) {
     button_input.clear();
     for event in raw_events.iter() {
         let (gamepad, event) = (event.0, &event.1);
         match event {
-            GamepadEventType::Connected => {
+            GamepadEventType::Connected(info) => {
+                gamepads.connect(gamepad, info.clone());
                 events.send(GamepadEvent(gamepad, event.clone()));
                 for button_type in ALL_BUTTON_TYPES.iter() {
                     let gamepad_button = GamepadButton(gamepad, *button_type);
@@ -225,6 +408,7 @@ pub fn gamepad_event_system(
                 }
             }
             GamepadEventType::Disconnected => {
+                gamepads.disconnect(gamepad);
                 events.send(GamepadEvent(gamepad, event.clone()));
                 for button_type in ALL_BUTTON_TYPES.iter() {
                     let gamepad_button = GamepadButton(gamepad, *button_type);
@@ -306,3 +490,92 @@ const ALL_AXIS_TYPES: [GamepadAxisType; 8] = [
     GamepadAxisType::DPadX,
     GamepadAxisType::DPadY,
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::{AxisSettings, Gamepad, GamepadInfo, Gamepads};
+
+    fn info(name: &str) -> GamepadInfo {
+        GamepadInfo {
+            name: name.to_string(),
+            vendor_id: Some(0x045e),
+            product_id: Some(0x02ea),
+        }
+    }
+
+    #[test]
+    fn player_reconnect() {
+        let mut gamepads = Gamepads::default();
+        gamepads.connect(Gamepad(0), info("first"));
+        gamepads.connect(Gamepad(1), info("second"));
+        assert_eq!(gamepads.player(Gamepad(1)), Some(1));
+
+        gamepads.disconnect(Gamepad(0));
+        assert_eq!(gamepads.player_gamepad(0), None);
+        assert_eq!(gamepads.player_count(), 2);
+
+        // a new gamepad doesn't take the slot of a disconnected player
+        gamepads.connect(Gamepad(2), info("third"));
+        assert_eq!(gamepads.player(Gamepad(2)), Some(2));
+
+        // the same device gets its slot back under a new id
+        gamepads.connect(Gamepad(3), info("first"));
+        assert_eq!(gamepads.player(Gamepad(3)), Some(0));
+
+        gamepads.release_player(1);
+        gamepads.connect(Gamepad(4), info("fourth"));
+        assert_eq!(gamepads.player(Gamepad(4)), Some(1));
+    }
+
+    #[test]
+    fn player_assignment() {
+        let mut gamepads = Gamepads {
+            auto_assign: false,
+            ..Default::default()
+        };
+        gamepads.connect(Gamepad(0), info("first"));
+        gamepads.connect(Gamepad(1), info("second"));
+        assert_eq!(gamepads.player(Gamepad(0)), None);
+        assert!(!gamepads.assign_player(Gamepad(2), 0));
+
+        assert!(gamepads.assign_player(Gamepad(0), 1));
+        assert!(gamepads.assign_player(Gamepad(1), 0));
+        assert_eq!(gamepads.player_gamepad(1), Some(Gamepad(0)));
+
+        // assigning to a taken slot swaps the players
+        assert!(gamepads.assign_player(Gamepad(0), 0));
+        assert_eq!(gamepads.player(Gamepad(0)), Some(0));
+        assert_eq!(gamepads.player(Gamepad(1)), Some(1));
+    }
+
+    #[test]
+    fn axis_deadzone() {
+        let settings = AxisSettings::with_deadzone(0.2);
+        assert_eq!(settings.filter(0.15, None), Some(0.0));
+        assert_eq!(settings.filter(-0.15, None), Some(0.0));
+        assert_eq!(settings.filter(0.5, None), Some(0.5));
+        assert_eq!(settings.filter(0.97, None), Some(1.0));
+        assert_eq!(settings.filter(-0.97, None), Some(-1.0));
+    }
+
+    #[test]
+    fn axis_threshold() {
+        let settings = AxisSettings::default();
+        assert_eq!(settings.filter(0.505, Some(0.5)), None);
+        assert_eq!(settings.filter(0.6, Some(0.5)), Some(0.6));
+        // returning to the deadzone is always reported
+        assert_eq!(settings.filter(0.02, Some(0.5)), Some(0.0));
+    }
+
+    #[test]
+    fn axis_sensitivity() {
+        let settings = AxisSettings {
+            sensitivity: 2.0,
+            ..Default::default()
+        };
+        assert_eq!(settings.filter(0.03, None), Some(0.0));
+        assert_eq!(settings.filter(0.25, None), Some(0.5));
+        assert_eq!(settings.filter(-0.25, None), Some(-0.5));
+        assert_eq!(settings.filter(0.6, None), Some(1.0));
+    }
+}
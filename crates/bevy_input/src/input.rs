@@ -1,4 +1,4 @@
-use bevy_utils::HashSet;
+use bevy_utils::{Duration, HashMap, HashSet, Instant};
 use std::hash::Hash;
 
 // unused import, but needed for intra doc link to work
@@ -13,6 +13,7 @@ use bevy_ecs::schedule::State;
 /// * [`Input::pressed`] will return `true` between a press and a release event.
 /// * [`Input::just_pressed`] will return `true` for one frame after a press event.
 /// * [`Input::just_released`] will return `true` for one frame after a release event.
+/// * [`Input::just_pressed_within`] will return `true` for the given duration after a press event.
 ///
 /// In case multiple systems are checking for [`Input::just_pressed`] or [`Input::just_released`]
 /// but only one should react, for example in the case of triggering
@@ -25,7 +26,8 @@ use bevy_ecs::schedule::State;
 ///
 /// When adding this resource for a new input type, you should:
 ///
-/// * Call the [`Input::press`] method for each press event.
+/// * Call the [`Input::press`] method for each press event, or [`Input::press_at`] if the event
+///   carries the time at which it happened.
 /// * Call the [`Input::release`] method for each release event.
 /// * Call the [`Input::clear`] method at each frame start, before processing events.
 #[derive(Debug)]
@@ -33,6 +35,7 @@ pub struct Input<T> {
     pressed: HashSet<T>,
     just_pressed: HashSet<T>,
     just_released: HashSet<T>,
+    last_pressed: HashMap<T, Instant>,
 }
 
 impl<T> Default for Input<T> {
@@ -41,6 +44,7 @@ impl<T> Default for Input<T> {
             pressed: Default::default(),
             just_pressed: Default::default(),
             just_released: Default::default(),
+            last_pressed: Default::default(),
         }
     }
 }
@@ -51,8 +55,14 @@ where
 {
     /// Register a press for input `input`.
     pub fn press(&mut self, input: T) {
+        self.press_at(input, Instant::now());
+    }
+
+    /// Register a press for input `input`, which happened at `timestamp`.
+    pub fn press_at(&mut self, input: T, timestamp: Instant) {
         if !self.pressed(input) {
             self.just_pressed.insert(input);
+            self.last_pressed.insert(input, timestamp);
         }
 
         self.pressed.insert(input);
@@ -74,6 +84,32 @@ where
         self.just_pressed.contains(&input)
     }
 
+    /// Returns the time at which `input` was last pressed, even if it has been released since.
+    pub fn last_pressed(&self, input: T) -> Option<Instant> {
+        self.last_pressed.get(&input).copied()
+    }
+
+    /// Check if `input` has been pressed during the last `duration`, even if it has been released
+    /// since. This is useful to buffer inputs, or to check the timing of a press.
+    pub fn just_pressed_within(&self, input: T, duration: Duration) -> bool {
+        self.last_pressed(input)
+            .map_or(false, |timestamp| timestamp.elapsed() <= duration)
+    }
+
+    /// Returns how long `input` has been held down, or `None` if it isn't pressed.
+    pub fn press_duration(&self, input: T) -> Option<Duration> {
+        self.press_duration_at(input, Instant::now())
+    }
+
+    /// Returns how long `input` has been held down at time `now`, or `None` if it isn't pressed.
+    pub fn press_duration_at(&self, input: T, now: Instant) -> Option<Duration> {
+        if !self.pressed(input) {
+            return None;
+        }
+        self.last_pressed(input)
+            .map(|timestamp| now.saturating_duration_since(timestamp))
+    }
+
     /// Clear the "just pressed" state of `input`. Future calls to [`Input::just_pressed`] for the
     /// given input will return false until a new press event occurs.
     /// Returns true if `input` is currently "just pressed"
@@ -98,6 +134,7 @@ where
         self.pressed.remove(&input);
         self.just_pressed.remove(&input);
         self.just_released.remove(&input);
+        self.last_pressed.remove(&input);
     }
 
     /// Clear just pressed and just released information.
@@ -196,4 +233,53 @@ mod test {
 
         assert!(!input.just_released(DummyInput::Input2));
     }
+
+    #[test]
+    fn just_pressed_within() {
+        use crate::Input;
+        use bevy_utils::{Duration, Instant};
+
+        let mut input = Input::default();
+        let long_ago = Instant::now() - Duration::from_secs(10);
+        input.press_at(1, long_ago);
+        input.press(2);
+        assert_eq!(input.last_pressed(1), Some(long_ago));
+
+        assert!(!input.just_pressed_within(1, Duration::from_secs(1)));
+        assert!(input.just_pressed_within(1, Duration::from_secs(20)));
+        assert!(input.just_pressed_within(2, Duration::from_secs(1)));
+
+        // a press is remembered after the input is released
+        input.release(2);
+        input.clear();
+        assert!(input.just_pressed_within(2, Duration::from_secs(1)));
+
+        // repeated presses while held don't change the press time
+        input.press(1);
+        assert_eq!(input.last_pressed(1), Some(long_ago));
+
+        input.reset(2);
+        assert!(!input.just_pressed_within(2, Duration::from_secs(1)));
+        assert_eq!(input.last_pressed(2), None);
+        assert_eq!(input.last_pressed(3), None);
+    }
+
+    #[test]
+    fn press_duration() {
+        use crate::Input;
+        use bevy_utils::{Duration, Instant};
+
+        let mut input = Input::default();
+        let now = Instant::now();
+        input.press_at(1, now - Duration::from_secs(2));
+        assert_eq!(
+            input.press_duration_at(1, now),
+            Some(Duration::from_secs(2))
+        );
+        assert!(input.press_duration(1).unwrap() >= Duration::from_secs(2));
+        assert_eq!(input.press_duration(2), None);
+
+        input.release(1);
+        assert_eq!(input.press_duration(1), None);
+    }
 }
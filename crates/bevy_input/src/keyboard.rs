@@ -1,6 +1,7 @@
 use crate::{ElementState, Input};
 use bevy_app::EventReader;
 use bevy_ecs::system::ResMut;
+use bevy_utils::Instant;
 
 /// A key input event from a keyboard device
 #[derive(Debug, Clone)]
@@ -8,6 +9,9 @@ pub struct KeyboardInput {
     pub scan_code: u32,
     pub key_code: Option<KeyCode>,
     pub state: ElementState,
+    /// The time at which the event was received from the OS. Events are only processed at the
+    /// start of the next frame, so this can be compared to the frame's time to measure latency.
+    pub timestamp: Instant,
 }
 
 /// Updates the Input<KeyCode> resource with the latest KeyboardInput events
@@ -20,11 +24,12 @@ pub fn keyboard_input_system(
         if let KeyboardInput {
             key_code: Some(key_code),
             state,
+            timestamp,
             ..
         } = event
         {
             match state {
-                ElementState::Pressed => keyboard_input.press(*key_code),
+                ElementState::Pressed => keyboard_input.press_at(*key_code, *timestamp),
                 ElementState::Released => keyboard_input.release(*key_code),
             }
         }
@@ -3,6 +3,9 @@ pub mod gamepad;
 mod input;
 pub mod keyboard;
 pub mod mouse;
+mod repeat;
+#[cfg(feature = "replay")]
+pub mod replay;
 pub mod system;
 pub mod touch;
 
@@ -12,18 +15,19 @@ use bevy_ecs::{
     system::IntoSystem,
 };
 pub use input::*;
+pub use repeat::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         gamepad::{
             Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEvent,
-            GamepadEventType,
+            GamepadEventType, Gamepads,
         },
         keyboard::KeyCode,
         mouse::MouseButton,
         touch::{TouchInput, Touches},
-        Axis, Input,
+        Axis, Input, InputRepeat, InputRepeated,
     };
 }
 
@@ -34,7 +38,7 @@ use touch::{touch_screen_input_system, TouchInput, Touches};
 
 use gamepad::{
     gamepad_event_system, GamepadAxis, GamepadButton, GamepadEvent, GamepadEventRaw,
-    GamepadSettings,
+    GamepadSettings, Gamepads,
 };
 
 /// Adds keyboard and mouse input to an App
@@ -54,6 +58,11 @@ impl Plugin for InputPlugin {
                 CoreStage::PreUpdate,
                 keyboard_input_system.system().label(InputSystem),
             )
+            .add_event::<InputRepeated<KeyCode>>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                input_repeat_system::<KeyCode>.system().after(InputSystem),
+            )
             // mouse
             .add_event::<MouseButtonInput>()
             .add_event::<MouseMotion>()
@@ -63,10 +72,18 @@ impl Plugin for InputPlugin {
                 CoreStage::PreUpdate,
                 mouse_button_input_system.system().label(InputSystem),
             )
+            .add_event::<InputRepeated<MouseButton>>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                input_repeat_system::<MouseButton>
+                    .system()
+                    .after(InputSystem),
+            )
             // gamepad
             .add_event::<GamepadEvent>()
             .add_event::<GamepadEventRaw>()
             .init_resource::<GamepadSettings>()
+            .init_resource::<Gamepads>()
             .init_resource::<Input<GamepadButton>>()
             .init_resource::<Axis<GamepadAxis>>()
             .init_resource::<Axis<GamepadButton>>()
@@ -74,6 +91,13 @@ impl Plugin for InputPlugin {
                 CoreStage::PreUpdate,
                 gamepad_event_system.system().label(InputSystem),
             )
+            .add_event::<InputRepeated<GamepadButton>>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                input_repeat_system::<GamepadButton>
+                    .system()
+                    .after(InputSystem),
+            )
             // touch
             .add_event::<TouchInput>()
             .init_resource::<Touches>()
@@ -1,12 +1,15 @@
 use crate::{ElementState, Input};
 use bevy_ecs::{event::EventReader, system::ResMut};
 use bevy_math::Vec2;
+use bevy_utils::Instant;
 
 /// A mouse button input event
 #[derive(Debug, Clone)]
 pub struct MouseButtonInput {
     pub button: MouseButton,
     pub state: ElementState,
+    /// The time at which the event was received from the OS.
+    pub timestamp: Instant,
 }
 
 /// A button on a mouse device
@@ -21,12 +24,14 @@ pub enum MouseButton {
 
 /// A mouse motion event
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct MouseMotion {
     pub delta: Vec2,
 }
 
 /// Unit of scroll
 #[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub enum MouseScrollUnit {
     Line,
     Pixel,
@@ -35,6 +40,7 @@ pub enum MouseScrollUnit {
 /// A mouse scroll wheel event, where x represents horizontal scroll and y represents vertical
 /// scroll.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
 pub struct MouseWheel {
     pub unit: MouseScrollUnit,
     pub x: f32,
@@ -49,7 +55,7 @@ pub fn mouse_button_input_system(
     mouse_button_input.clear();
     for event in mouse_button_input_events.iter() {
         match event.state {
-            ElementState::Pressed => mouse_button_input.press(event.button),
+            ElementState::Pressed => mouse_button_input.press_at(event.button, event.timestamp),
             ElementState::Released => mouse_button_input.release(event.button),
         }
     }
@@ -0,0 +1,150 @@
+use crate::Input;
+use bevy_ecs::{
+    component::Component,
+    event::EventWriter,
+    system::{Res, ResMut},
+};
+use bevy_utils::{Duration, HashMap, Instant};
+use std::hash::Hash;
+
+/// An event sent repeatedly while an input of type `T` is held down, independently of the OS
+/// key-repeat settings.
+///
+/// Repeat events are only sent for input types that have an [`InputRepeat<T>`] resource.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InputRepeated<T> {
+    pub input: T,
+    /// The number of times this input has repeated since it was pressed, starting at 1.
+    pub count: u32,
+}
+
+/// Enables [`InputRepeated<T>`] events for the input type `T`.
+///
+/// The first repeat is sent once an input has been held for `delay`, then a repeat is sent every
+/// `interval` until it is released.
+///
+/// ```
+/// # use bevy_input::{keyboard::KeyCode, InputRepeat};
+/// # use bevy_utils::Duration;
+/// # let mut world = bevy_ecs::world::World::default();
+/// world.insert_resource(InputRepeat::<KeyCode>::new(
+///     Duration::from_millis(500),
+///     Duration::from_millis(50),
+/// ));
+/// ```
+#[derive(Debug)]
+pub struct InputRepeat<T> {
+    pub delay: Duration,
+    pub interval: Duration,
+    repeats: HashMap<T, u32>,
+}
+
+impl<T> InputRepeat<T> {
+    pub fn new(delay: Duration, interval: Duration) -> Self {
+        Self {
+            delay,
+            interval,
+            repeats: Default::default(),
+        }
+    }
+}
+
+impl<T> Default for InputRepeat<T> {
+    fn default() -> Self {
+        Self::new(Duration::from_millis(500), Duration::from_millis(33))
+    }
+}
+
+impl<T> InputRepeat<T>
+where
+    T: Copy + Eq + Hash,
+{
+    /// Returns the number of times `input` should have repeated after being held for
+    /// `press_duration`.
+    fn repeat_count(&self, press_duration: Duration) -> u32 {
+        if press_duration < self.delay {
+            0
+        } else if self.interval == Duration::from_secs(0) {
+            1
+        } else {
+            1 + ((press_duration - self.delay).as_nanos() / self.interval.as_nanos()) as u32
+        }
+    }
+
+    /// Updates the repeat state of every pressed input at time `now`, calling `send` for each
+    /// repeat that happened since the last update.
+    pub fn update(
+        &mut self,
+        input: &Input<T>,
+        now: Instant,
+        mut send: impl FnMut(InputRepeated<T>),
+    ) {
+        self.repeats.retain(|key, _| input.pressed(*key));
+        for key in input.get_pressed() {
+            let press_duration = match input.press_duration_at(*key, now) {
+                Some(press_duration) => press_duration,
+                None => continue,
+            };
+            let target = self.repeat_count(press_duration);
+            let count = self.repeats.entry(*key).or_insert(0);
+            while *count < target {
+                *count += 1;
+                send(InputRepeated {
+                    input: *key,
+                    count: *count,
+                });
+            }
+        }
+    }
+}
+
+/// Sends [`InputRepeated<T>`] events for held inputs, if the [`InputRepeat<T>`] resource exists.
+pub fn input_repeat_system<T: Component + Copy + Eq + Hash>(
+    input: Res<Input<T>>,
+    repeat: Option<ResMut<InputRepeat<T>>>,
+    mut events: EventWriter<InputRepeated<T>>,
+) {
+    if let Some(mut repeat) = repeat {
+        repeat.update(&input, Instant::now(), |event| events.send(event));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputRepeat, InputRepeated};
+    use crate::Input;
+    use bevy_utils::{Duration, Instant};
+
+    #[test]
+    fn repeats_after_delay() {
+        let mut repeat = InputRepeat::new(Duration::from_millis(500), Duration::from_millis(100));
+        let mut input = Input::default();
+        let start = Instant::now();
+        input.press_at(1, start);
+
+        let mut events = Vec::new();
+        let mut update = |input: &Input<i32>, millis| {
+            repeat.update(input, start + Duration::from_millis(millis), |event| {
+                events.push(event)
+            });
+        };
+        update(&input, 100);
+        update(&input, 550);
+        // repeats missed during a long frame are all sent
+        update(&input, 760);
+        input.release(1);
+        update(&input, 1000);
+        input.press_at(1, start + Duration::from_millis(1100));
+        update(&input, 1600);
+
+        assert_eq!(
+            events,
+            vec![
+                InputRepeated { input: 1, count: 1 },
+                InputRepeated { input: 1, count: 2 },
+                InputRepeated { input: 1, count: 3 },
+                InputRepeated { input: 1, count: 1 },
+            ]
+        );
+    }
+}
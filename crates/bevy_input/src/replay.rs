@@ -0,0 +1,443 @@
+use crate::{
+    gamepad::GamepadEventRaw,
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseWheel},
+    touch::TouchInput,
+    ElementState, InputSystem,
+};
+use bevy_app::{AppBuilder, AppExit, CoreStage, EventReader, Events, Plugin};
+use bevy_core::{CoreSystem, SimulationTick, Time};
+use bevy_ecs::{
+    schedule::{ExclusiveSystemDescriptorCoercion, ParallelSystemDescriptorCoercion},
+    system::{IntoExclusiveSystem, IntoSystem, Res, ResMut},
+};
+use bevy_log::warn;
+use bevy_utils::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::{fs, io, ops::Range, path::PathBuf};
+use thiserror::Error;
+
+/// Records the input events of every frame into the [`InputRecorder`] resource, along with the
+/// [`SimulationTick`]s that ran during the frame.
+#[derive(Default)]
+pub struct InputRecordPlugin;
+
+impl Plugin for InputRecordPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.init_resource::<InputRecorder>()
+            .add_system_to_stage(CoreStage::First, input_record_start_system.system())
+            .add_system_to_stage(CoreStage::Last, input_record_system.system());
+    }
+}
+
+/// Replays the recording of the [`InputPlayback`] resource, which must be inserted before adding
+/// the plugin.
+///
+/// The input events from the devices are dropped, and the recorded frame times are replayed in
+/// [`Time`], so that fixed timesteps tick in the same frames as during the recording.
+#[derive(Default)]
+pub struct InputPlaybackPlugin;
+
+impl Plugin for InputPlaybackPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        if app.world().get_resource::<InputPlayback>().is_none() {
+            panic!("the `InputPlayback` resource must be inserted before `InputPlaybackPlugin`");
+        }
+        // exclusive, as it must run after the exclusive `time_system`
+        app.add_system_to_stage(
+            CoreStage::First,
+            playback_time_system
+                .exclusive_system()
+                .after(CoreSystem::Time),
+        )
+        .add_system_to_stage(
+            CoreStage::PreUpdate,
+            playback_input_system.system().before(InputSystem),
+        )
+        .add_system_to_stage(CoreStage::Last, playback_check_system.system());
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ReplayError {
+    #[error("failed to access the recording file: {0}")]
+    Io(#[from] io::Error),
+    #[error("failed to read or write the recording: {0}")]
+    Ron(#[from] ron::Error),
+}
+
+/// The input events of a series of frames.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InputRecording {
+    pub frames: Vec<RecordedFrame>,
+}
+
+impl InputRecording {
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self, ReplayError> {
+        let ron = fs::read_to_string(path.into())?;
+        Ok(ron::from_str(&ron)?)
+    }
+
+    pub fn save(&self, path: impl Into<PathBuf>) -> Result<(), ReplayError> {
+        let ron = ron::ser::to_string_pretty(self, Default::default())?;
+        fs::write(path.into(), ron)?;
+        Ok(())
+    }
+}
+
+/// The input events of a frame.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct RecordedFrame {
+    /// The [`Time::delta`] of the frame.
+    pub delta: Duration,
+    /// The [`SimulationTick`] at the start and at the end of the frame, which tell how many fixed
+    /// steps ran after the events of the frame. Used to detect replays going out of sync.
+    pub simulation_ticks: Range<u64>,
+    pub events: Vec<RecordedInput>,
+}
+
+/// An input event. The timestamps of events are stored as time since the startup of the app.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum RecordedInput {
+    Keyboard {
+        scan_code: u32,
+        key_code: Option<KeyCode>,
+        state: ElementState,
+        since_startup: Duration,
+    },
+    MouseButton {
+        button: MouseButton,
+        state: ElementState,
+        since_startup: Duration,
+    },
+    MouseMotion(MouseMotion),
+    MouseWheel(MouseWheel),
+    Gamepad(GamepadEventRaw),
+    Touch(TouchInput),
+}
+
+/// The recording made by [`InputRecordPlugin`]. It's saved to [`InputRecorder::path`] when the
+/// app exits, if set.
+#[derive(Debug, Default)]
+pub struct InputRecorder {
+    pub recording: InputRecording,
+    pub path: Option<PathBuf>,
+    // the simulation tick at the start of the current frame
+    frame_start: u64,
+}
+
+impl InputRecorder {
+    pub fn to_file(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: Some(path.into()),
+            ..Default::default()
+        }
+    }
+}
+
+/// The state of the replay of [`InputPlaybackPlugin`].
+#[derive(Debug)]
+pub struct InputPlayback {
+    recording: InputRecording,
+    // the next frame to replay
+    frame: usize,
+    // the replayed time, ignoring the real time of frames
+    time: Option<Time>,
+    // the simulation tick at the start of the current frame
+    frame_start: u64,
+    desynced_frame: Option<usize>,
+}
+
+impl InputPlayback {
+    pub fn new(recording: InputRecording) -> Self {
+        Self {
+            recording,
+            frame: 0,
+            time: None,
+            frame_start: 0,
+            desynced_frame: None,
+        }
+    }
+
+    pub fn from_file(path: impl Into<PathBuf>) -> Result<Self, ReplayError> {
+        Ok(Self::new(InputRecording::load(path)?))
+    }
+
+    pub fn recording(&self) -> &InputRecording {
+        &self.recording
+    }
+
+    /// The number of frames replayed so far.
+    pub fn frames_replayed(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns true once every recorded frame was replayed.
+    pub fn is_finished(&self) -> bool {
+        self.frame >= self.recording.frames.len()
+    }
+
+    /// The first frame that started or ended with a different [`SimulationTick`] than during the
+    /// recording, meaning the replay doesn't reproduce the recorded session.
+    pub fn desynced_frame(&self) -> Option<usize> {
+        self.desynced_frame
+    }
+
+    fn current_frame(&self) -> Option<&RecordedFrame> {
+        self.frame
+            .checked_sub(1)
+            .and_then(|frame| self.recording.frames.get(frame))
+    }
+}
+
+/// Remembers the [`SimulationTick`] at the start of the frame.
+pub fn input_record_start_system(
+    mut recorder: ResMut<InputRecorder>,
+    simulation_tick: Res<SimulationTick>,
+) {
+    recorder.frame_start = simulation_tick.get();
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn input_record_system(
+    mut recorder: ResMut<InputRecorder>,
+    time: Res<Time>,
+    simulation_tick: Res<SimulationTick>,
+    mut keyboard_events: EventReader<KeyboardInput>,
+    mut mouse_button_events: EventReader<MouseButtonInput>,
+    mut mouse_motion_events: EventReader<MouseMotion>,
+    mut mouse_wheel_events: EventReader<MouseWheel>,
+    mut gamepad_events: EventReader<GamepadEventRaw>,
+    mut touch_events: EventReader<TouchInput>,
+    mut app_exit_events: EventReader<AppExit>,
+) {
+    let since_startup = |timestamp: Instant| timestamp.saturating_duration_since(time.startup());
+    let mut events = Vec::new();
+    events.extend(keyboard_events.iter().map(|event| RecordedInput::Keyboard {
+        scan_code: event.scan_code,
+        key_code: event.key_code,
+        state: event.state,
+        since_startup: since_startup(event.timestamp),
+    }));
+    events.extend(
+        mouse_button_events
+            .iter()
+            .map(|event| RecordedInput::MouseButton {
+                button: event.button,
+                state: event.state,
+                since_startup: since_startup(event.timestamp),
+            }),
+    );
+    events.extend(
+        mouse_motion_events
+            .iter()
+            .cloned()
+            .map(RecordedInput::MouseMotion),
+    );
+    events.extend(
+        mouse_wheel_events
+            .iter()
+            .cloned()
+            .map(RecordedInput::MouseWheel),
+    );
+    events.extend(gamepad_events.iter().cloned().map(RecordedInput::Gamepad));
+    events.extend(touch_events.iter().copied().map(RecordedInput::Touch));
+    let simulation_ticks = recorder.frame_start..simulation_tick.get();
+    recorder.recording.frames.push(RecordedFrame {
+        delta: time.delta(),
+        simulation_ticks,
+        events,
+    });
+
+    if app_exit_events.iter().next().is_some() {
+        if let Some(path) = &recorder.path {
+            if let Err(error) = recorder.recording.save(path.clone()) {
+                warn!("failed to save the input recording: {}", error);
+            }
+        }
+    }
+}
+
+/// Replaces the time of the frame with the recorded one.
+pub fn playback_time_system(
+    mut playback: ResMut<InputPlayback>,
+    mut time: ResMut<Time>,
+    simulation_tick: Res<SimulationTick>,
+) {
+    let frame = playback.frame;
+    if frame >= playback.recording.frames.len() {
+        return;
+    }
+    playback.frame += 1;
+    playback.frame_start = simulation_tick.get();
+    let delta = playback.recording.frames[frame].delta;
+    let replayed = playback.time.get_or_insert_with(|| time.clone());
+    let last_update = replayed.last_update().unwrap_or_else(|| replayed.startup());
+    replayed.update_with_instant(last_update + delta);
+    *time = replayed.clone();
+}
+
+/// Replaces the input events of the frame with the recorded ones.
+#[allow(clippy::too_many_arguments)]
+pub fn playback_input_system(
+    playback: Res<InputPlayback>,
+    time: Res<Time>,
+    mut keyboard_events: ResMut<Events<KeyboardInput>>,
+    mut mouse_button_events: ResMut<Events<MouseButtonInput>>,
+    mut mouse_motion_events: ResMut<Events<MouseMotion>>,
+    mut mouse_wheel_events: ResMut<Events<MouseWheel>>,
+    mut gamepad_events: ResMut<Events<GamepadEventRaw>>,
+    mut touch_events: ResMut<Events<TouchInput>>,
+) {
+    keyboard_events.clear();
+    mouse_button_events.clear();
+    mouse_motion_events.clear();
+    mouse_wheel_events.clear();
+    gamepad_events.clear();
+    touch_events.clear();
+
+    let frame = match playback.current_frame() {
+        Some(frame) => frame,
+        None => return,
+    };
+    for event in frame.events.iter().cloned() {
+        match event {
+            RecordedInput::Keyboard {
+                scan_code,
+                key_code,
+                state,
+                since_startup,
+            } => keyboard_events.send(KeyboardInput {
+                scan_code,
+                key_code,
+                state,
+                timestamp: time.startup() + since_startup,
+            }),
+            RecordedInput::MouseButton {
+                button,
+                state,
+                since_startup,
+            } => mouse_button_events.send(MouseButtonInput {
+                button,
+                state,
+                timestamp: time.startup() + since_startup,
+            }),
+            RecordedInput::MouseMotion(event) => mouse_motion_events.send(event),
+            RecordedInput::MouseWheel(event) => mouse_wheel_events.send(event),
+            RecordedInput::Gamepad(event) => gamepad_events.send(event),
+            RecordedInput::Touch(event) => touch_events.send(event),
+        }
+    }
+}
+
+/// Compares the [`SimulationTick`]s at the start and at the end of the frame to the recorded
+/// ones.
+pub fn playback_check_system(
+    mut playback: ResMut<InputPlayback>,
+    simulation_tick: Res<SimulationTick>,
+) {
+    if playback.desynced_frame.is_some() {
+        return;
+    }
+    let simulation_ticks = playback.frame_start..simulation_tick.get();
+    let desynced = playback
+        .current_frame()
+        .map_or(false, |frame| frame.simulation_ticks != simulation_ticks);
+    if desynced {
+        playback.desynced_frame = Some(playback.frame - 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        InputPlayback, InputPlaybackPlugin, InputRecordPlugin, InputRecorder, InputRecording,
+    };
+    use crate::{
+        keyboard::{KeyCode, KeyboardInput},
+        ElementState, Input, InputPlugin,
+    };
+    use bevy_app::{App, AppBuilder, Events};
+    use bevy_core::{simulation_tick_system, SimulationTick, Time};
+    use bevy_ecs::system::IntoSystem;
+    use bevy_utils::Instant;
+
+    fn app() -> AppBuilder {
+        let mut app = App::build();
+        app.init_resource::<Time>()
+            .init_resource::<SimulationTick>()
+            .add_plugin(InputPlugin);
+        app
+    }
+
+    fn record(frames: usize) -> InputRecording {
+        let mut recording_app = app();
+        recording_app
+            .add_plugin(InputRecordPlugin)
+            .add_system(simulation_tick_system.system());
+        press(&mut recording_app, KeyCode::A);
+        for _ in 0..frames {
+            recording_app.app.update();
+        }
+        recording_app
+            .world()
+            .get_resource::<InputRecorder>()
+            .unwrap()
+            .recording
+            .clone()
+    }
+
+    fn press(app: &mut AppBuilder, key_code: KeyCode) {
+        app.world_mut()
+            .get_resource_mut::<Events<KeyboardInput>>()
+            .unwrap()
+            .send(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(key_code),
+                state: ElementState::Pressed,
+                timestamp: Instant::now(),
+            });
+    }
+
+    #[test]
+    fn record_and_replay() {
+        let recording = record(2);
+        assert_eq!(recording.frames.len(), 2);
+        assert_eq!(recording.frames[0].events.len(), 1);
+        assert!(recording.frames[1].events.is_empty());
+        assert_eq!(recording.frames[0].simulation_ticks, 0..1);
+        assert_eq!(recording.frames[1].simulation_ticks, 1..2);
+
+        let mut replay_app = app();
+        replay_app
+            .insert_resource(InputPlayback::new(recording))
+            .add_plugin(InputPlaybackPlugin)
+            .add_system(simulation_tick_system.system());
+        // device input is ignored during the replay
+        press(&mut replay_app, KeyCode::B);
+        replay_app.app.update();
+        let input = replay_app.world().get_resource::<Input<KeyCode>>().unwrap();
+        assert!(input.just_pressed(KeyCode::A));
+        assert!(!input.pressed(KeyCode::B));
+
+        replay_app.app.update();
+        let playback = replay_app.world().get_resource::<InputPlayback>().unwrap();
+        assert!(playback.is_finished());
+        assert_eq!(playback.desynced_frame(), None);
+    }
+
+    #[test]
+    fn detect_desync() {
+        let recording = record(2);
+
+        // the simulation doesn't advance during the replay
+        let mut replay_app = app();
+        replay_app
+            .insert_resource(InputPlayback::new(recording))
+            .add_plugin(InputPlaybackPlugin);
+        replay_app.app.update();
+        replay_app.app.update();
+        let playback = replay_app.world().get_resource::<InputPlayback>().unwrap();
+        assert_eq!(playback.desynced_frame(), Some(0));
+    }
+}
@@ -0,0 +1,588 @@
+use bevy_app::{AppBuilder, Plugin};
+use bevy_asset::AssetServerSettings;
+use bevy_ecs::component::Component;
+use bevy_log::{warn, Level, LogSettings};
+use bevy_utils::HashMap;
+use bevy_window::{WindowDescriptor, WindowMode};
+use std::{
+    fmt::Display,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ConfigError {
+    #[error("Could not read the config file {path:?}: {error}")]
+    Io {
+        path: PathBuf,
+        error: std::io::Error,
+    },
+    #[error("Invalid line {line} in the config file {path:?}: {content:?}")]
+    Syntax {
+        path: PathBuf,
+        line: usize,
+        content: String,
+    },
+    #[error("Missing value for the command line flag {0:?}")]
+    MissingArgument(String),
+    #[error("Unknown config key {0:?}")]
+    UnknownKey(String),
+    #[error("Invalid value {value:?} for the config key {key:?}: {error}")]
+    InvalidValue {
+        key: String,
+        value: String,
+        error: String,
+    },
+}
+
+/// The settings of an app, read from a config file, environment variables and command line
+/// flags. Each setting has a key made of a section and a name, e.g. `window.width`.
+///
+/// Later sources override earlier ones:
+/// 1. the config file, made of `name = value` lines under `[section]` headers, with `#` comments
+///    and optionally quoted values. Its path is given by the `--config` flag, the `BEVY_CONFIG`
+///    environment variable, or [`ConfigPlugin::path`]
+/// 2. environment variables, named `BEVY_<SECTION>__<NAME>`, e.g. `BEVY_WINDOW__WIDTH`
+/// 3. command line flags, written `--section.name value` or `--section.name=value`. Other
+///    arguments are left to the app
+///
+/// ```
+/// # use bevy_internal::AppConfig;
+/// let mut config = AppConfig::default();
+/// config
+///     .merge_file_contents("settings.cfg", "[window]\ntitle = \"My game\"\nwidth = 800")
+///     .unwrap();
+/// config.merge_env(vec![("BEVY_WINDOW__WIDTH".to_string(), "1024".to_string())]);
+/// config.merge_args(vec!["--window.width=1920".to_string()]).unwrap();
+///
+/// assert_eq!(config.get("window.title"), Some("My game"));
+/// assert_eq!(config.get("window.width"), Some("1920"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct AppConfig {
+    values: HashMap<String, String>,
+}
+
+impl AppConfig {
+    pub const ENV_PREFIX: &'static str = "BEVY_";
+    pub const CONFIG_FLAG: &'static str = "--config";
+    pub const CONFIG_ENV: &'static str = "BEVY_CONFIG";
+
+    /// Reads the settings of the process, using the config file at `default_path` unless another
+    /// one is given. The default file is optional, a file given explicitly must exist.
+    pub fn load(default_path: &Path) -> Result<Self, ConfigError> {
+        Self::load_from(
+            default_path,
+            std::env::args().skip(1).collect(),
+            std::env::vars().collect(),
+        )
+    }
+
+    fn load_from(
+        default_path: &Path,
+        args: Vec<String>,
+        vars: Vec<(String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let explicit_path = args
+            .iter()
+            .position(|arg| arg == Self::CONFIG_FLAG)
+            .map(|index| {
+                args.get(index + 1)
+                    .cloned()
+                    .ok_or_else(|| ConfigError::MissingArgument(Self::CONFIG_FLAG.to_string()))
+            })
+            .or_else(|| {
+                let prefix = format!("{}=", Self::CONFIG_FLAG);
+                args.iter()
+                    .find_map(|arg| arg.strip_prefix(&prefix).map(|path| Ok(path.to_string())))
+            })
+            .or_else(|| {
+                vars.iter()
+                    .find(|(name, _)| name == Self::CONFIG_ENV)
+                    .map(|(_, path)| Ok(path.clone()))
+            })
+            .transpose()?
+            .map(PathBuf::from);
+
+        let mut config = AppConfig::default();
+        let path = explicit_path.as_deref().unwrap_or(default_path);
+        match std::fs::read_to_string(path) {
+            Ok(contents) => {
+                config.merge_file_contents(path, &contents)?;
+            }
+            Err(error)
+                if explicit_path.is_none() && error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => {
+                return Err(ConfigError::Io {
+                    path: path.to_path_buf(),
+                    error,
+                })
+            }
+        }
+        config.merge_env(vars);
+        config.merge_args(args)?;
+        Ok(config)
+    }
+
+    pub fn merge_file_contents(
+        &mut self,
+        path: impl AsRef<Path>,
+        contents: &str,
+    ) -> Result<&mut Self, ConfigError> {
+        let mut section = String::new();
+        for (index, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
+                section = line[1..line.len() - 1].trim().to_string();
+                continue;
+            }
+            let (name, value) = split_once(line, "=").ok_or_else(|| ConfigError::Syntax {
+                path: path.as_ref().to_path_buf(),
+                line: index + 1,
+                content: line.to_string(),
+            })?;
+            let name = name.trim();
+            let key = if section.is_empty() {
+                name.to_string()
+            } else {
+                format!("{}.{}", section, name)
+            };
+            self.set(key, unquote(value.trim()));
+        }
+        Ok(self)
+    }
+
+    /// Adds the `BEVY_<SECTION>__<NAME>` variables among `vars`
+    pub fn merge_env(&mut self, vars: impl IntoIterator<Item = (String, String)>) -> &mut Self {
+        for (name, value) in vars {
+            if let Some(key) = name.strip_prefix(Self::ENV_PREFIX) {
+                if let Some((section, name)) = split_once(key, "__") {
+                    let key = format!("{}.{}", section, name).to_lowercase();
+                    self.set(key, value);
+                }
+            }
+        }
+        self
+    }
+
+    /// Adds the `--section.name` flags among `args`
+    pub fn merge_args(
+        &mut self,
+        args: impl IntoIterator<Item = String>,
+    ) -> Result<&mut Self, ConfigError> {
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let flag = match arg.strip_prefix("--") {
+                Some(flag) => flag,
+                None => continue,
+            };
+            let (key, value) = match split_once(flag, "=") {
+                Some((key, value)) => (key, Some(value)),
+                None => (flag, None),
+            };
+            if !key.contains('.') {
+                continue;
+            }
+            let value = match value {
+                Some(value) => value.to_string(),
+                None => args
+                    .next()
+                    .ok_or_else(|| ConfigError::MissingArgument(arg.clone()))?,
+            };
+            self.set(key, value);
+        }
+        Ok(self)
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.values.get(key).map(|value| value.as_str())
+    }
+
+    pub fn set(&mut self, key: impl Into<String>, value: impl Into<String>) -> &mut Self {
+        self.values.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the names and values of the settings of `section`
+    pub fn section<'a>(&'a self, section: &'a str) -> impl Iterator<Item = (&'a str, &'a str)> {
+        self.values.iter().filter_map(move |(key, value)| {
+            split_once(key, ".")
+                .filter(|(key_section, _)| *key_section == section)
+                .map(|(_, name)| (name, value.as_str()))
+        })
+    }
+
+    /// Overrides the fields of `settings` with the values of their section
+    pub fn apply<T: ConfigSettings>(&self, settings: &mut T) -> Result<(), ConfigError> {
+        for (name, value) in self.section(T::SECTION) {
+            apply_setting(settings, name, value)?;
+        }
+        Ok(())
+    }
+}
+
+fn apply_setting<T: ConfigSettings>(
+    settings: &mut T,
+    name: &str,
+    value: &str,
+) -> Result<(), ConfigError> {
+    settings.set(name, value).map_err(|error| match error {
+        ConfigError::UnknownKey(name) => {
+            ConfigError::UnknownKey(format!("{}.{}", T::SECTION, name))
+        }
+        ConfigError::InvalidValue { key, value, error } => ConfigError::InvalidValue {
+            key: format!("{}.{}", T::SECTION, key),
+            value,
+            error,
+        },
+        error => error,
+    })
+}
+
+fn split_once<'a>(value: &'a str, separator: &str) -> Option<(&'a str, &'a str)> {
+    let index = value.find(separator)?;
+    Some((&value[..index], &value[index + separator.len()..]))
+}
+
+fn unquote(value: &str) -> &str {
+    if value.len() >= 2 && value.starts_with('"') && value.ends_with('"') {
+        &value[1..value.len() - 1]
+    } else {
+        value
+    }
+}
+
+/// Parses the value of a setting, for [`ConfigSettings::set`]
+pub fn parse_config_value<T: FromStr>(name: &str, value: &str) -> Result<T, ConfigError>
+where
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|error: T::Err| ConfigError::InvalidValue {
+            key: name.to_string(),
+            value: value.to_string(),
+            error: error.to_string(),
+        })
+}
+
+/// A settings resource that can be read from an [`AppConfig`], with
+/// [`AppConfigExt::init_config_resource`].
+///
+/// ```
+/// # use bevy_internal::{parse_config_value, ConfigError, ConfigSettings};
+/// #[derive(Default)]
+/// struct Difficulty {
+///     enemies: u32,
+/// }
+///
+/// impl ConfigSettings for Difficulty {
+///     const SECTION: &'static str = "difficulty";
+///
+///     fn set(&mut self, name: &str, value: &str) -> Result<(), ConfigError> {
+///         match name {
+///             "enemies" => self.enemies = parse_config_value(name, value)?,
+///             _ => return Err(ConfigError::UnknownKey(name.to_string())),
+///         }
+///         Ok(())
+///     }
+/// }
+/// ```
+pub trait ConfigSettings: Component + Default {
+    /// The section of the settings, e.g. `window` for `window.width`
+    const SECTION: &'static str;
+
+    /// Sets the setting `name` of the section to `value`
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ConfigError>;
+}
+
+impl ConfigSettings for WindowDescriptor {
+    const SECTION: &'static str = "window";
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ConfigError> {
+        match name {
+            "title" => self.title = value.to_string(),
+            "width" => self.width = parse_config_value(name, value)?,
+            "height" => self.height = parse_config_value(name, value)?,
+            "scale_factor" => self.scale_factor_override = Some(parse_config_value(name, value)?),
+            "vsync" => self.vsync = parse_config_value(name, value)?,
+            "resizable" => self.resizable = parse_config_value(name, value)?,
+            "decorations" => self.decorations = parse_config_value(name, value)?,
+            "cursor_visible" => self.cursor_visible = parse_config_value(name, value)?,
+            "cursor_locked" => self.cursor_locked = parse_config_value(name, value)?,
+            "mode" => {
+                self.mode = match value {
+                    "windowed" => WindowMode::Windowed,
+                    "borderless_fullscreen" => WindowMode::BorderlessFullscreen,
+                    "fullscreen" => WindowMode::Fullscreen { use_size: false },
+                    "sized_fullscreen" => WindowMode::Fullscreen { use_size: true },
+                    _ => {
+                        return Err(ConfigError::InvalidValue {
+                            key: name.to_string(),
+                            value: value.to_string(),
+                            error: "expected windowed, borderless_fullscreen, fullscreen or \
+                                    sized_fullscreen"
+                                .to_string(),
+                        })
+                    }
+                }
+            }
+            _ => return Err(ConfigError::UnknownKey(name.to_string())),
+        }
+        Ok(())
+    }
+}
+
+impl ConfigSettings for LogSettings {
+    const SECTION: &'static str = "log";
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ConfigError> {
+        match name {
+            "filter" => self.filter = value.to_string(),
+            "level" => self.level = parse_config_value::<Level>(name, value)?,
+            _ => return Err(ConfigError::UnknownKey(name.to_string())),
+        }
+        Ok(())
+    }
+}
+
+impl ConfigSettings for AssetServerSettings {
+    const SECTION: &'static str = "asset";
+
+    fn set(&mut self, name: &str, value: &str) -> Result<(), ConfigError> {
+        match name {
+            "folder" => self.asset_folder = value.to_string(),
+            _ => return Err(ConfigError::UnknownKey(name.to_string())),
+        }
+        Ok(())
+    }
+}
+
+pub trait AppConfigExt {
+    /// Overrides the resource `T`, or its default value, with the settings of the [`AppConfig`]
+    /// resource. Settings resources must be initialized before the plugins that read them are
+    /// added.
+    ///
+    /// Unknown settings of the section of `T` are ignored with a warning, so that a stray
+    /// environment variable doesn't stop the app.
+    ///
+    /// # Panics
+    /// Panics if a setting of the section of `T` has an invalid value.
+    fn init_config_resource<T: ConfigSettings>(&mut self) -> &mut Self;
+}
+
+impl AppConfigExt for AppBuilder {
+    fn init_config_resource<T: ConfigSettings>(&mut self) -> &mut Self {
+        let world = self.world_mut();
+        let mut settings = world.remove_resource::<T>().unwrap_or_default();
+        if let Some(config) = world.get_resource::<AppConfig>() {
+            for (name, value) in config.section(T::SECTION) {
+                match apply_setting(&mut settings, name, value) {
+                    Ok(()) => {}
+                    Err(error @ ConfigError::UnknownKey(_)) => {
+                        warn!("Ignoring the app configuration: {}", error)
+                    }
+                    Err(error) => panic!("Invalid app configuration: {}", error),
+                }
+            }
+        }
+        world.insert_resource(settings);
+        self
+    }
+}
+
+/// Reads the [`AppConfig`] of the app, and applies it to the [`WindowDescriptor`], the
+/// [`LogSettings`] and the [`AssetServerSettings`]. Values set in code are overridden by the
+/// config, see [`AppConfig`] for the precedence of its sources.
+///
+/// It must be added before the plugins that read these settings, e.g. before the
+/// [`DefaultPlugins`](crate::DefaultPlugins). Other settings resources can be read from the
+/// config with [`AppConfigExt::init_config_resource`].
+///
+/// ```no_run
+/// # use bevy_internal::{prelude::*, ConfigPlugin};
+/// App::build()
+///     .add_plugin(ConfigPlugin::default())
+///     .add_plugins(DefaultPlugins)
+///     .run();
+/// ```
+pub struct ConfigPlugin {
+    /// The path of the config file, used unless another one is given on the command line or in
+    /// the environment
+    pub path: PathBuf,
+}
+
+impl Default for ConfigPlugin {
+    fn default() -> Self {
+        ConfigPlugin {
+            path: PathBuf::from("settings.cfg"),
+        }
+    }
+}
+
+impl Plugin for ConfigPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        let config = AppConfig::load(&self.path)
+            .unwrap_or_else(|error| panic!("Invalid app configuration: {}", error));
+        app.insert_resource(config)
+            .init_config_resource::<WindowDescriptor>()
+            .init_config_resource::<LogSettings>()
+            .init_config_resource::<AssetServerSettings>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AppConfig, AppConfigExt, ConfigError};
+    use bevy_app::App;
+    use bevy_window::WindowDescriptor;
+    use std::path::{Path, PathBuf};
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    fn temp_file(name: &str, contents: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("bevy_config_{}_{}", std::process::id(), name));
+        std::fs::write(&path, contents).unwrap();
+        path
+    }
+
+    #[test]
+    fn file_contents() {
+        let mut config = AppConfig::default();
+        config
+            .merge_file_contents(
+                "settings.cfg",
+                "# comment\nroot = 1\n\n[window]\ntitle = \"My = game\"\n  width=800  \n[ log ]\nlevel = \"debug",
+            )
+            .unwrap();
+        assert_eq!(config.get("root"), Some("1"));
+        assert_eq!(config.get("window.title"), Some("My = game"));
+        assert_eq!(config.get("window.width"), Some("800"));
+        // unbalanced quotes are kept
+        assert_eq!(config.get("log.level"), Some("\"debug"));
+        assert_eq!(config.get("comment"), None);
+
+        let error = config
+            .merge_file_contents("settings.cfg", "[window]\nwidth = 800\nheight")
+            .unwrap_err();
+        match error {
+            ConfigError::Syntax {
+                path,
+                line,
+                content,
+            } => {
+                assert_eq!(path, Path::new("settings.cfg"));
+                assert_eq!(line, 3);
+                assert_eq!(content, "height");
+            }
+            error => panic!("unexpected error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn env_and_args() {
+        let mut config = AppConfig::default();
+        config.merge_env(vec![
+            ("BEVY_WINDOW__WIDTH".to_string(), "800".to_string()),
+            ("BEVY_CONFIG".to_string(), "other.cfg".to_string()),
+            ("PATH".to_string(), "/bin".to_string()),
+        ]);
+        assert_eq!(config.get("window.width"), Some("800"));
+        assert_eq!(config.section("config").count(), 0);
+
+        config
+            .merge_args(args(&[
+                "level1",
+                "--verbose",
+                "--window.title",
+                "My game",
+                "--window.height=600",
+            ]))
+            .unwrap();
+        assert_eq!(config.get("window.title"), Some("My game"));
+        assert_eq!(config.get("window.height"), Some("600"));
+        assert_eq!(config.get("verbose"), None);
+
+        let error = config.merge_args(args(&["--window.width"])).unwrap_err();
+        assert!(matches!(error, ConfigError::MissingArgument(flag) if flag == "--window.width"));
+    }
+
+    #[test]
+    fn load_precedence() {
+        let default_path = temp_file(
+            "default.cfg",
+            "[window]\ntitle = file\nwidth = 1\nheight = 1\nvsync = true",
+        );
+        let vars = vec![
+            ("BEVY_WINDOW__WIDTH".to_string(), "2".to_string()),
+            ("BEVY_WINDOW__HEIGHT".to_string(), "2".to_string()),
+        ];
+        let config =
+            AppConfig::load_from(&default_path, args(&["--window.height", "3"]), vars.clone())
+                .unwrap();
+        assert_eq!(config.get("window.title"), Some("file"));
+        assert_eq!(config.get("window.width"), Some("2"));
+        assert_eq!(config.get("window.height"), Some("3"));
+
+        // an explicit config file replaces the default one
+        let explicit_path = temp_file("explicit.cfg", "[window]\ntitle = explicit");
+        let explicit_arg = format!("--config={}", explicit_path.display());
+        let config = AppConfig::load_from(&default_path, args(&[&explicit_arg]), vars).unwrap();
+        assert_eq!(config.get("window.title"), Some("explicit"));
+        assert_eq!(config.get("window.vsync"), None);
+        let env = vec![(
+            "BEVY_CONFIG".to_string(),
+            explicit_path.display().to_string(),
+        )];
+        let config = AppConfig::load_from(&default_path, Vec::new(), env).unwrap();
+        assert_eq!(config.get("window.title"), Some("explicit"));
+
+        // the default file is optional, an explicit one isn't
+        let missing = std::env::temp_dir().join("bevy_config_missing.cfg");
+        assert!(AppConfig::load_from(&missing, Vec::new(), Vec::new()).is_ok());
+        let missing_arg = format!("--config={}", missing.display());
+        assert!(matches!(
+            AppConfig::load_from(&default_path, args(&[&missing_arg]), Vec::new()),
+            Err(ConfigError::Io { .. })
+        ));
+        assert!(matches!(
+            AppConfig::load_from(&default_path, args(&["--config"]), Vec::new()),
+            Err(ConfigError::MissingArgument(_))
+        ));
+
+        std::fs::remove_file(default_path).unwrap();
+        std::fs::remove_file(explicit_path).unwrap();
+    }
+
+    #[test]
+    fn apply_settings() {
+        let mut config = AppConfig::default();
+        config.set("window.width", "800").set("window.size", "big");
+        let mut window = WindowDescriptor::default();
+        let error = config.apply(&mut window).unwrap_err();
+        assert!(matches!(error, ConfigError::UnknownKey(key) if key == "window.size"));
+
+        let mut app = App::build();
+        app.insert_resource(config)
+            .init_config_resource::<WindowDescriptor>();
+        let window = app.world().get_resource::<WindowDescriptor>().unwrap();
+        assert_eq!(window.width, 800.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid app configuration")]
+    fn invalid_setting() {
+        let mut config = AppConfig::default();
+        config.set("window.width", "wide");
+        App::build()
+            .insert_resource(config)
+            .init_config_resource::<WindowDescriptor>();
+    }
+}
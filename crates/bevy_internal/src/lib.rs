@@ -1,8 +1,12 @@
 /// `use bevy::prelude::*;` to import common components, bundles, and plugins.
 pub mod prelude;
 
+mod config;
 mod default_plugins;
+mod test_app;
+pub use config::*;
 pub use default_plugins::*;
+pub use test_app::*;
 
 pub mod app {
     //! Build bevy apps, create plugins, and read events.
@@ -82,6 +86,12 @@ pub mod audio {
     pub use bevy_audio::*;
 }
 
+#[cfg(feature = "bevy_console")]
+pub mod console {
+    //! An in-game developer console running commands at runtime.
+    pub use bevy_console::*;
+}
+
 #[cfg(feature = "bevy_gilrs")]
 pub mod gilrs {
     pub use bevy_gilrs::*;
@@ -35,6 +35,10 @@ pub use crate::ui::prelude::*;
 #[cfg(feature = "bevy_dynamic_plugin")]
 pub use crate::dynamic_plugin::*;
 
+#[doc(hidden)]
+#[cfg(feature = "bevy_console")]
+pub use crate::console::prelude::*;
+
 #[doc(hidden)]
 #[cfg(feature = "bevy_gilrs")]
 pub use crate::gilrs::*;
@@ -0,0 +1,311 @@
+use bevy_app::{App, AppBuilder, CoreStage, Events};
+use bevy_core::{CorePlugin, CoreSystem, Time};
+use bevy_ecs::{
+    bundle::Bundle,
+    component::Component,
+    entity::Entity,
+    query::{FilterFetch, WorldQuery},
+    schedule::{ExclusiveSystemDescriptorCoercion, Stage, SystemLabel},
+    system::{IntoExclusiveSystem, IntoSystem, ResMut},
+    world::World,
+};
+use bevy_input::{
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::{MouseButton, MouseButtonInput},
+    ElementState, InputPlugin,
+};
+use bevy_transform::TransformPlugin;
+use bevy_utils::{Duration, Instant};
+use std::{
+    fmt::Debug,
+    ops::{Deref, DerefMut},
+};
+
+/// A headless app for testing gameplay systems, without a window or a GPU.
+///
+/// It starts with the [`CorePlugin`], [`TransformPlugin`] and [`InputPlugin`], and is updated
+/// synchronously. Frames last [`TestApp::frame_time`], whatever the real time between updates,
+/// so that time based systems behave the same in every run.
+///
+/// ```
+/// # use bevy_internal::{prelude::*, TestApp};
+/// struct Position(f32);
+///
+/// fn move_right(keyboard: Res<Input<KeyCode>>, mut query: Query<&mut Position>) {
+///     if keyboard.pressed(KeyCode::Right) {
+///         for mut position in query.iter_mut() {
+///             position.0 += 1.0;
+///         }
+///     }
+/// }
+///
+/// let mut app = TestApp::new();
+/// app.add_system(move_right.system());
+/// let player = app.spawn((Position(0.0),));
+/// app.press_key(KeyCode::Right).update_frames(3);
+/// assert_eq!(app.component::<Position>(player).0, 3.0);
+/// ```
+pub struct TestApp {
+    app: AppBuilder,
+}
+
+/// The duration of the frames of a [`TestApp`], replacing the real time in [`Time`].
+struct TestTime {
+    frame_time: Duration,
+    time: Option<Time>,
+}
+
+impl Default for TestApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestApp {
+    /// The default duration of a frame, at 60 frames per second.
+    pub const DEFAULT_FRAME_TIME: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+    pub fn new() -> Self {
+        let mut app = App::build();
+        app.add_plugin(CorePlugin)
+            .add_plugin(TransformPlugin)
+            .add_plugin(InputPlugin);
+        Self::from_app(app)
+    }
+
+    /// Wraps an app with any set of plugins, e.g. [`MinimalPlugins`](crate::MinimalPlugins).
+    /// Its [`Time`] is replaced by the frame time of the test app, if present.
+    pub fn from_app(mut app: AppBuilder) -> Self {
+        app.insert_resource(TestTime {
+            frame_time: Self::DEFAULT_FRAME_TIME,
+            time: None,
+        })
+        // exclusive, as it must run after the exclusive `time_system`
+        .add_system_to_stage(
+            CoreStage::First,
+            test_time_system.exclusive_system().after(CoreSystem::Time),
+        );
+        Self { app }
+    }
+
+    pub fn frame_time(&self) -> Duration {
+        self.world().get_resource::<TestTime>().unwrap().frame_time
+    }
+
+    /// Sets the duration of the next frames.
+    ///
+    /// ```
+    /// # use bevy_internal::{core::Time, utils::Duration, TestApp};
+    /// let mut app = TestApp::new();
+    /// app.set_frame_time(Duration::from_millis(100)).update_frames(3);
+    /// let time = app.world().get_resource::<Time>().unwrap();
+    /// assert_eq!(time.delta(), Duration::from_millis(100));
+    /// ```
+    pub fn set_frame_time(&mut self, frame_time: Duration) -> &mut Self {
+        self.world_mut()
+            .get_resource_mut::<TestTime>()
+            .unwrap()
+            .frame_time = frame_time;
+        self
+    }
+
+    pub fn world(&self) -> &World {
+        &self.app.app.world
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.app.app.world
+    }
+
+    /// Runs a frame of the app.
+    pub fn update(&mut self) -> &mut Self {
+        self.app.finish_plugins();
+        self.app.app.update();
+        self
+    }
+
+    /// Runs `frames` frames of the app.
+    pub fn update_frames(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    /// Runs a frame of the app with the resource `T` replaced by `value`, then restores it.
+    pub fn update_with_resource<T: Component>(&mut self, value: T) -> &mut Self {
+        let app = &mut self.app.app;
+        let schedule = &mut app.schedule;
+        app.world
+            .with_resource_override(value, |world| schedule.run(world));
+        self
+    }
+
+    /// Replaces the system labelled `label` with a stub, e.g. a system that needs rendering or
+    /// asset IO. The stub keeps the labels, ordering and run criteria of the replaced system.
+    #[track_caller]
+    pub fn replace_system<Params>(
+        &mut self,
+        label: impl SystemLabel,
+        stub: impl IntoSystem<(), (), Params>,
+    ) -> &mut Self {
+        let label_name = format!("{:?}", label);
+        if !self.app.app.schedule.replace_system(label, stub.system()) {
+            panic!("no parallel system is labelled {}", label_name);
+        }
+        self
+    }
+
+    /// Sends an event, read by the systems during the next update.
+    #[track_caller]
+    pub fn send_event<T: Component>(&mut self, event: T) -> &mut Self {
+        match self.world_mut().get_resource_mut::<Events<T>>() {
+            Some(mut events) => events.send(event),
+            None => panic!(
+                "the event `{}` wasn't added to the app",
+                std::any::type_name::<T>()
+            ),
+        }
+        self
+    }
+
+    /// Presses a key, until it's released with [`TestApp::release_key`].
+    pub fn press_key(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_key(key_code, ElementState::Pressed)
+    }
+
+    pub fn release_key(&mut self, key_code: KeyCode) -> &mut Self {
+        self.send_key(key_code, ElementState::Released)
+    }
+
+    /// Presses a mouse button, until it's released with [`TestApp::release_mouse_button`].
+    pub fn press_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_mouse_button(button, ElementState::Pressed)
+    }
+
+    pub fn release_mouse_button(&mut self, button: MouseButton) -> &mut Self {
+        self.send_mouse_button(button, ElementState::Released)
+    }
+
+    fn send_key(&mut self, key_code: KeyCode, state: ElementState) -> &mut Self {
+        self.send_event(KeyboardInput {
+            scan_code: 0,
+            key_code: Some(key_code),
+            state,
+            timestamp: Instant::now(),
+        })
+    }
+
+    fn send_mouse_button(&mut self, button: MouseButton, state: ElementState) -> &mut Self {
+        self.send_event(MouseButtonInput {
+            button,
+            state,
+            timestamp: Instant::now(),
+        })
+    }
+
+    pub fn spawn(&mut self, bundle: impl Bundle) -> Entity {
+        self.world_mut().spawn().insert_bundle(bundle).id()
+    }
+
+    /// Returns a resource, panicking with its type name if it doesn't exist.
+    #[track_caller]
+    pub fn resource<T: Component>(&self) -> &T {
+        match self.world().get_resource::<T>() {
+            Some(resource) => resource,
+            None => panic!(
+                "the resource `{}` doesn't exist",
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+
+    /// Returns a component of an entity, panicking with its type name if the entity doesn't have
+    /// it.
+    #[track_caller]
+    pub fn component<T: Component>(&self, entity: Entity) -> &T {
+        let entity_ref = match self.world().get_entity(entity) {
+            Some(entity_ref) => entity_ref,
+            None => panic!("the entity {:?} doesn't exist", entity),
+        };
+        match entity_ref.get::<T>() {
+            Some(component) => component,
+            None => panic!(
+                "the entity {:?} doesn't have the component `{}`",
+                entity,
+                std::any::type_name::<T>()
+            ),
+        }
+    }
+
+    /// Returns true if the entity exists and has the component `T`.
+    pub fn has_component<T: Component>(&self, entity: Entity) -> bool {
+        self.world()
+            .get_entity(entity)
+            .map_or(false, |entity| entity.contains::<T>())
+    }
+
+    /// Returns the number of entities matching the filter `F`, e.g. `With<Player>`.
+    pub fn count<F: WorldQuery>(&mut self) -> usize
+    where
+        F::Fetch: FilterFetch,
+    {
+        let world = self.world_mut();
+        let mut query = world.query_filtered::<Entity, F>();
+        query.iter(world).count()
+    }
+
+    #[track_caller]
+    pub fn assert_resource<T: Component + PartialEq + Debug>(&self, expected: T) {
+        let resource = self.resource::<T>();
+        assert!(
+            *resource == expected,
+            "the resource `{}` is {:?}, expected {:?}",
+            std::any::type_name::<T>(),
+            resource,
+            expected
+        );
+    }
+
+    #[track_caller]
+    pub fn assert_component<T: Component + PartialEq + Debug>(&self, entity: Entity, expected: T) {
+        let component = self.component::<T>(entity);
+        assert!(
+            *component == expected,
+            "the component `{}` of {:?} is {:?}, expected {:?}",
+            std::any::type_name::<T>(),
+            entity,
+            component,
+            expected
+        );
+    }
+}
+
+impl Deref for TestApp {
+    type Target = AppBuilder;
+
+    fn deref(&self) -> &Self::Target {
+        &self.app
+    }
+}
+
+impl DerefMut for TestApp {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.app
+    }
+}
+
+fn test_time_system(mut test_time: ResMut<TestTime>, time: Option<ResMut<Time>>) {
+    let mut time = match time {
+        Some(time) => time,
+        None => return,
+    };
+    let frame_time = test_time.frame_time;
+    let replaced = test_time.time.get_or_insert_with(|| time.clone());
+    let instant = match replaced.last_update() {
+        Some(last_update) => last_update + frame_time,
+        None => replaced.startup(),
+    };
+    replaced.update_with_instant(instant);
+    *time = replaced.clone();
+}
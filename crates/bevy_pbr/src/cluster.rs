@@ -0,0 +1,353 @@
+use bevy_core::{Pod, Zeroable};
+use bevy_math::{Mat4, Vec2, Vec3};
+use bevy_transform::components::GlobalTransform;
+
+/// How the view frustum of a camera is divided into clusters, so that each fragment is only
+/// shaded with the [`PointLight`](crate::PointLight)s that can reach it. Add it to a camera
+/// entity; cameras without it use the default configuration.
+///
+/// The frustum is divided into `dimensions[0]` columns, `dimensions[1]` rows and
+/// `dimensions[2]` depth slices. The first slice covers everything closer than `near`, and the
+/// others divide the depth from `near` to `far` exponentially, the last one extending to
+/// infinity. More clusters mean fewer lights to shade per fragment, but more work on the CPU to
+/// assign the lights to the clusters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClusterConfig {
+    pub dimensions: [u32; 3],
+    pub near: f32,
+    pub far: f32,
+}
+
+impl Default for ClusterConfig {
+    fn default() -> Self {
+        ClusterConfig {
+            dimensions: [16, 9, 24],
+            near: 5.0,
+            far: 1000.0,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub(crate) struct ClusterUniform {
+    // w is unused
+    pub dimensions: [u32; 4],
+    // the row of the view matrix giving the view space z of a world position
+    pub view_z: [f32; 4],
+    // x is the depth of the end of the first slice, y and z the scale and bias that map the log of
+    // a depth to the other slices
+    pub slicing: [f32; 4],
+}
+
+/// The point lights reaching each cluster of a camera.
+pub(crate) struct Clusters {
+    pub uniform: ClusterUniform,
+    /// The offset in `light_indices` and the number of lights of each cluster, indexed by column,
+    /// then row from the top, then slice
+    pub clusters: Vec<[u32; 2]>,
+    pub light_indices: Vec<u32>,
+}
+
+impl Clusters {
+    /// Assigns the lights, given as their position and range, to the clusters of a camera.
+    pub fn new(
+        config: &ClusterConfig,
+        projection: &Mat4,
+        camera_transform: &GlobalTransform,
+        lights: &[(Vec3, f32)],
+    ) -> Clusters {
+        let [columns, rows, slices] = config.dimensions;
+        let (columns, rows, slices) = (columns.max(1), rows.max(1), slices.max(1));
+        let near = config.near.max(f32::EPSILON);
+        let scale = (slices - 1) as f32 / (config.far / near).ln().max(f32::EPSILON);
+        let bias = -near.ln() * scale;
+        let slice = |depth: f32| {
+            if depth < near {
+                0
+            } else {
+                (1.0 + (depth.ln() * scale + bias).floor()).min((slices - 1) as f32) as u32
+            }
+        };
+        let tile = |ndc: f32, count: u32| {
+            ((ndc + 1.0) * 0.5 * count as f32)
+                .floor()
+                .max(0.0)
+                .min((count - 1) as f32) as u32
+        };
+
+        let view = camera_transform.compute_matrix().inverse();
+        let uniform = ClusterUniform {
+            dimensions: [columns, rows, slices, 0],
+            view_z: [view.x_axis.z, view.y_axis.z, view.z_axis.z, view.w_axis.z],
+            slicing: [near, scale, bias, 0.0],
+        };
+
+        let cluster_count = (columns * rows * slices) as usize;
+        let mut counts = vec![0u32; cluster_count];
+        let mut light_ranges = Vec::new();
+        for (index, (position, range)) in lights.iter().enumerate() {
+            // the camera looks towards -z
+            let center = view.transform_point3(*position);
+            let (depth_min, depth_max) = (-center.z - range, -center.z + range);
+            if depth_max < 0.0 {
+                continue;
+            }
+
+            // the screen bounds of the light are the bounds of the corners of its bounding box,
+            // unless some are behind the camera
+            let mut min = Vec2::splat(f32::MAX);
+            let mut max = Vec2::splat(f32::MIN);
+            for corner in 0..8 {
+                let offset = Vec3::new(
+                    if corner & 1 == 0 { -range } else { *range },
+                    if corner & 2 == 0 { -range } else { *range },
+                    if corner & 4 == 0 { -range } else { *range },
+                );
+                let clip = *projection * (center + offset).extend(1.0);
+                if clip.w <= f32::EPSILON {
+                    min = Vec2::splat(-1.0);
+                    max = Vec2::splat(1.0);
+                    break;
+                }
+                let ndc = Vec2::new(clip.x, clip.y) / clip.w;
+                min = min.min(ndc);
+                max = max.max(ndc);
+            }
+            if max.x < -1.0 || min.x > 1.0 || max.y < -1.0 || min.y > 1.0 {
+                continue;
+            }
+
+            // rows are counted from the top of the screen
+            let light_range = (
+                tile(min.x, columns)..=tile(max.x, columns),
+                tile(-max.y, rows)..=tile(-min.y, rows),
+                slice(depth_min)..=slice(depth_max),
+            );
+            for z in light_range.2.clone() {
+                for y in light_range.1.clone() {
+                    for x in light_range.0.clone() {
+                        counts[(x + columns * (y + rows * z)) as usize] += 1;
+                    }
+                }
+            }
+            light_ranges.push((index as u32, light_range));
+        }
+
+        let mut offset = 0;
+        let clusters = counts
+            .iter()
+            .map(|count| {
+                let cluster = [offset, *count];
+                offset += count;
+                cluster
+            })
+            .collect::<Vec<_>>();
+        let mut light_indices = vec![0; offset as usize];
+        let mut next = clusters
+            .iter()
+            .map(|[offset, _]| *offset as usize)
+            .collect::<Vec<_>>();
+        for (index, (x_range, y_range, z_range)) in light_ranges {
+            for z in z_range {
+                for y in y_range.clone() {
+                    for x in x_range.clone() {
+                        let cluster = (x + columns * (y + rows * z)) as usize;
+                        light_indices[next[cluster]] = index;
+                        next[cluster] += 1;
+                    }
+                }
+            }
+        }
+
+        Clusters {
+            uniform,
+            clusters,
+            light_indices,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_math::{Vec4, Vec4Swizzles};
+
+    const NEAR: f32 = 0.1;
+    const FAR: f32 = 100.0;
+
+    fn projection() -> Mat4 {
+        Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 1.0, NEAR, FAR)
+    }
+
+    fn config() -> ClusterConfig {
+        ClusterConfig {
+            dimensions: [4, 4, 8],
+            near: 1.0,
+            far: 64.0,
+        }
+    }
+
+    /// The index of the cluster containing a position, as computed by the PBR shader.
+    fn cluster_index(
+        clusters: &Clusters,
+        camera_transform: &GlobalTransform,
+        position: Vec3,
+    ) -> usize {
+        let uniform = &clusters.uniform;
+        let [columns, rows, slices, _] = uniform.dimensions;
+        let view_proj = projection() * camera_transform.compute_matrix().inverse();
+        let clip = view_proj * position.extend(1.0);
+        let ndc = clip.xy() / clip.w;
+        let tile_x = ((ndc.x + 1.0) * 0.5 * columns as f32)
+            .floor()
+            .max(0.0)
+            .min((columns - 1) as f32) as u32;
+        let tile_y = ((1.0 - ndc.y) * 0.5 * rows as f32)
+            .floor()
+            .max(0.0)
+            .min((rows - 1) as f32) as u32;
+
+        let depth = -Vec4::from(uniform.view_z).dot(position.extend(1.0));
+        let [first_slice, scale, bias, _] = uniform.slicing;
+        let slice = if depth >= first_slice {
+            (1 + ((depth.ln() * scale + bias).floor().max(0.0) as u32)).min(slices - 1)
+        } else {
+            0
+        };
+        (tile_x + columns * (tile_y + rows * slice)) as usize
+    }
+
+    fn cluster_lights(clusters: &Clusters, cluster: usize) -> &[u32] {
+        let [offset, count] = clusters.clusters[cluster];
+        &clusters.light_indices[offset as usize..(offset + count) as usize]
+    }
+
+    #[test]
+    fn cluster_layout() {
+        let clusters = Clusters::new(&config(), &projection(), &GlobalTransform::identity(), &[]);
+        assert_eq!(clusters.uniform.dimensions, [4, 4, 8, 0]);
+        assert_eq!(clusters.clusters.len(), 4 * 4 * 8);
+        assert!(clusters.clusters.iter().all(|cluster| *cluster == [0, 0]));
+        assert!(clusters.light_indices.is_empty());
+
+        // the first slice ends at `near`, the last one starts at `far`
+        let [first_slice, scale, bias, _] = clusters.uniform.slicing;
+        assert_eq!(first_slice, 1.0);
+        assert!((1.0f32.ln() * scale + bias).abs() < 1e-5);
+        assert!((64.0f32.ln() * scale + bias - 7.0).abs() < 1e-5);
+
+        // empty dimensions are treated as one cluster
+        let clusters = Clusters::new(
+            &ClusterConfig {
+                dimensions: [0, 0, 0],
+                ..config()
+            },
+            &projection(),
+            &GlobalTransform::identity(),
+            &[(Vec3::new(0.0, 0.0, -10.0), 1.0)],
+        );
+        assert_eq!(clusters.uniform.dimensions, [1, 1, 1, 0]);
+        assert_eq!(clusters.clusters, vec![[0, 1]]);
+        assert_eq!(clusters.light_indices, vec![0]);
+    }
+
+    #[test]
+    fn assigned_lights() {
+        let camera_transform = GlobalTransform::identity();
+        let lights = [
+            // in front of the camera, in the top left of the screen
+            (Vec3::new(-8.0, 8.0, -10.0), 1.0),
+            // behind the camera
+            (Vec3::new(0.0, 0.0, 10.0), 1.0),
+            // in front of the camera, in the bottom right of the screen and far away
+            (Vec3::new(30.0, -30.0, -50.0), 2.0),
+            // outside of the frustum
+            (Vec3::new(100.0, 0.0, -10.0), 1.0),
+        ];
+        let clusters = Clusters::new(&config(), &projection(), &camera_transform, &lights);
+
+        // the offsets of the clusters follow each other
+        let mut offset = 0;
+        for [cluster_offset, count] in clusters.clusters.iter() {
+            assert_eq!(*cluster_offset, offset);
+            offset += count;
+        }
+        assert_eq!(offset as usize, clusters.light_indices.len());
+
+        for index in 0..lights.len() {
+            let assigned = clusters.light_indices.contains(&(index as u32));
+            assert_eq!(assigned, index == 0 || index == 2, "light {}", index);
+        }
+
+        // the clusters containing a light's center and the surface of its sphere list it, and
+        // the lights are only assigned to the clusters around them
+        for &index in [0, 2].iter() {
+            let (position, range) = lights[index];
+            let samples = [
+                Vec3::ZERO,
+                Vec3::X,
+                -Vec3::X,
+                Vec3::Y,
+                -Vec3::Y,
+                Vec3::Z,
+                -Vec3::Z,
+            ];
+            for sample in samples.iter() {
+                let cluster = cluster_index(
+                    &clusters,
+                    &camera_transform,
+                    position + *sample * range * 0.99,
+                );
+                assert!(cluster_lights(&clusters, cluster).contains(&(index as u32)));
+            }
+            let light_clusters = clusters
+                .clusters
+                .iter()
+                .enumerate()
+                .filter(|(cluster, _)| {
+                    cluster_lights(&clusters, *cluster).contains(&(index as u32))
+                })
+                .count();
+            assert!(
+                light_clusters <= 2,
+                "light {} is in {} clusters",
+                index,
+                light_clusters
+            );
+        }
+        let top_left = cluster_index(&clusters, &camera_transform, lights[0].0);
+        assert_eq!(top_left % 4, 0);
+        assert_eq!(top_left / 4 % 4, 0);
+        let bottom_right = cluster_index(&clusters, &camera_transform, lights[2].0);
+        assert_eq!(bottom_right % 4, 3);
+        assert_eq!(bottom_right / 4 % 4, 3);
+        assert!(bottom_right / 16 > top_left / 16);
+    }
+
+    #[test]
+    fn light_around_camera() {
+        let clusters = Clusters::new(
+            &config(),
+            &projection(),
+            &GlobalTransform::identity(),
+            &[(Vec3::new(0.0, 0.0, 1.0), 2.5)],
+        );
+        // the light reaches the whole screen, up to the second slice
+        for (cluster, [_, count]) in clusters.clusters.iter().enumerate() {
+            assert_eq!(*count, (cluster < 2 * 16) as u32, "cluster {}", cluster);
+        }
+    }
+
+    #[test]
+    fn clusters_depend_on_camera() {
+        let lights = [(Vec3::new(0.0, 0.0, -10.0), 1.0)];
+        let front = GlobalTransform::identity();
+        // the light is on the right of the screen of this camera, and closer to it
+        let side = GlobalTransform::from_xyz(-5.0, 0.0, -5.0);
+        let front_clusters = Clusters::new(&config(), &projection(), &front, &lights);
+        let side_clusters = Clusters::new(&config(), &projection(), &side, &lights);
+        assert_ne!(front_clusters.uniform.view_z, side_clusters.uniform.view_z);
+        assert_ne!(front_clusters.clusters, side_clusters.clusters);
+    }
+}
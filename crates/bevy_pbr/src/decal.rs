@@ -0,0 +1,84 @@
+use bevy_asset::Handle;
+use bevy_ecs::system::Query;
+use bevy_render::{
+    camera::VisibleEntities, color::Color, renderer::RenderResources, shader::ShaderDefs,
+    texture::Texture,
+};
+use std::f32::consts::FRAC_PI_2;
+
+/// A texture projected onto the scene inside a box, e.g. for bullet holes, blob shadows or terrain
+/// detail.
+///
+/// The box is the unit cube of the entity's transform, and the texture is projected along its
+/// local negative Y axis, so an untransformed decal lies flat on the ground. Decals are drawn in
+/// the decal pass, after all opaque geometry, and are placed using the depth of the main pass.
+/// Multisampling isn't supported: with [`Msaa`](bevy_render::prelude::Msaa) samples above 1, no
+/// decal pass is added.
+#[derive(Debug, Clone, RenderResources, ShaderDefs)]
+pub struct Decal {
+    /// Multiplied with the texture, if any
+    pub color: Color,
+    #[shader_def]
+    pub texture: Option<Handle<Texture>>,
+    /// The angle in radians between the projection axis and a surface normal at which the decal
+    /// starts fading out
+    pub fade_start_angle: f32,
+    /// The angle in radians between the projection axis and a surface normal past which the decal
+    /// is fully transparent
+    pub fade_end_angle: f32,
+    /// Decals are drawn in increasing order, so decals with a higher order are drawn on top of
+    /// lower ones. Decals with the same order are drawn from back to front.
+    #[render_resources(ignore)]
+    pub order: i32,
+}
+
+impl Default for Decal {
+    fn default() -> Self {
+        Decal {
+            color: Color::WHITE,
+            texture: None,
+            fade_start_angle: FRAC_PI_2 * 0.6,
+            fade_end_angle: FRAC_PI_2 * 0.9,
+            order: 0,
+        }
+    }
+}
+
+impl Decal {
+    pub fn new(texture: Handle<Texture>) -> Self {
+        Decal {
+            texture: Some(texture),
+            ..Default::default()
+        }
+    }
+
+    /// Fades the decal out on surfaces whose normal is between `start` and `end` radians away
+    /// from the projection axis.
+    pub fn with_fade_angles(mut self, start: f32, end: f32) -> Self {
+        self.fade_start_angle = start;
+        self.fade_end_angle = end;
+        self
+    }
+
+    pub fn with_order(mut self, order: i32) -> Self {
+        self.order = order;
+        self
+    }
+}
+
+/// A component that indicates that an entity should be drawn in the decal pass
+#[derive(Clone, Debug, Default)]
+pub struct DecalPass;
+
+/// Sorts the decals visible to each camera by [`Decal::order`]. The sort is stable, so decals of
+/// the same order keep their back to front order, and other entities keep their relative order.
+pub fn decal_order_system(decals: Query<&Decal>, mut cameras: Query<&mut VisibleEntities>) {
+    for mut visible_entities in cameras.iter_mut() {
+        visible_entities.value.sort_by_key(|visible_entity| {
+            decals
+                .get(visible_entity.entity)
+                .ok()
+                .map(|decal| decal.order)
+        });
+    }
+}
@@ -1,4 +1,13 @@
-use crate::{light::PointLight, material::StandardMaterial, render_graph::PBR_PIPELINE_HANDLE};
+use crate::{
+    decal::{Decal, DecalPass},
+    fog::Sky,
+    light::PointLight,
+    material::StandardMaterial,
+    render_graph::{
+        DECAL_MESH_HANDLE, DECAL_PIPELINE_HANDLE, PBR_PIPELINE_HANDLE, SKY_MESH_HANDLE,
+        SKY_PIPELINE_HANDLE,
+    },
+};
 use bevy_asset::Handle;
 use bevy_ecs::bundle::Bundle;
 use bevy_render::{
@@ -40,6 +49,68 @@ impl Default for PbrBundle {
     }
 }
 
+/// A component bundle for decal entities, whose transform places, orients and scales the box the
+/// decal is projected in
+#[derive(Bundle)]
+pub struct DecalBundle {
+    pub decal: Decal,
+    pub mesh: Handle<Mesh>,
+    pub decal_pass: DecalPass,
+    pub draw: Draw,
+    pub visible: Visible,
+    pub render_pipelines: RenderPipelines,
+    pub transform: Transform,
+    pub global_transform: GlobalTransform,
+}
+
+impl Default for DecalBundle {
+    fn default() -> Self {
+        Self {
+            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
+                DECAL_PIPELINE_HANDLE.typed(),
+            )]),
+            mesh: DECAL_MESH_HANDLE.typed(),
+            // decals are blended over the scene, so they are drawn from back to front
+            visible: Visible {
+                is_visible: true,
+                is_transparent: true,
+            },
+            decal: Default::default(),
+            decal_pass: Default::default(),
+            draw: Default::default(),
+            transform: Default::default(),
+            global_transform: Default::default(),
+        }
+    }
+}
+
+/// A component bundle for the sky. It is drawn around every camera of the main pass, so a single
+/// sky entity is needed.
+#[derive(Bundle)]
+pub struct SkyBundle {
+    pub sky: Sky,
+    pub mesh: Handle<Mesh>,
+    pub main_pass: MainPass,
+    pub draw: Draw,
+    pub visible: Visible,
+    pub render_pipelines: RenderPipelines,
+}
+
+impl Default for SkyBundle {
+    fn default() -> Self {
+        Self {
+            render_pipelines: RenderPipelines::from_pipelines(vec![RenderPipeline::new(
+                SKY_PIPELINE_HANDLE.typed(),
+            )]),
+            mesh: SKY_MESH_HANDLE.typed(),
+            sky: Default::default(),
+            main_pass: Default::default(),
+            draw: Default::default(),
+            visible: Default::default(),
+        }
+    }
+}
+
 /// A component bundle for "light" entities
 #[derive(Debug, Bundle, Default)]
 pub struct PointLightBundle {
@@ -0,0 +1,78 @@
+/// The settings of a physical camera, which decide how much light reaches its sensor.
+///
+/// See <https://google.github.io/filament/Filament.html#imagingpipeline/physicallybasedcamera>
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PhysicalCameraParameters {
+    /// The aperture, in f-stops: the ratio of the focal length to the diameter of the lens
+    /// opening. Larger values let less light in.
+    pub aperture_f_stops: f32,
+    /// The time the sensor is exposed, in seconds
+    pub shutter_speed_s: f32,
+    /// The sensitivity of the sensor, in ISO
+    pub sensitivity_iso: f32,
+}
+
+impl PhysicalCameraParameters {
+    /// The exposure value of these settings at ISO 100
+    pub fn ev100(&self) -> f32 {
+        f32::log2(self.aperture_f_stops * self.aperture_f_stops / self.shutter_speed_s)
+            - f32::log2(self.sensitivity_iso / 100.0)
+    }
+}
+
+impl Default for PhysicalCameraParameters {
+    fn default() -> Self {
+        PhysicalCameraParameters {
+            aperture_f_stops: 4.0,
+            shutter_speed_s: 1.0 / 250.0,
+            sensitivity_iso: 100.0,
+        }
+    }
+}
+
+/// The exposure of a camera, which scales the lighting of the PBR meshes it sees into the range
+/// of the tone mapping. Add it to a camera entity.
+///
+/// With an exposure, the intensities of the [`PointLight`](crate::PointLight)s, the
+/// illuminance of the [`DirectionalLight`](crate::DirectionalLight)s, the
+/// [`AmbientLight`](crate::AmbientLight) and the emissive colors of the materials are all in
+/// physical units, and scaled alike. Cameras without it only scale directional lights, with the
+/// default exposure.
+///
+/// | EV100 | Scene                                |
+/// |-------|--------------------------------------|
+/// | 15    | Sunlight, [`Exposure::SUNLIGHT`]     |
+/// | 12    | Overcast day, [`Exposure::OVERCAST`] |
+/// | 7     | Indoors, [`Exposure::INDOOR`]        |
+/// | 2     | Night street lighting                |
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exposure {
+    /// The exposure value at ISO 100. Higher values darken the image.
+    pub ev100: f32,
+}
+
+impl Exposure {
+    pub const SUNLIGHT: Exposure = Exposure { ev100: 15.0 };
+    pub const OVERCAST: Exposure = Exposure { ev100: 12.0 };
+    pub const INDOOR: Exposure = Exposure { ev100: 7.0 };
+
+    /// The factor the lighting is multiplied by, for a sensor that saturates at the maximum
+    /// luminance of this exposure
+    pub fn exposure(&self) -> f32 {
+        1.0 / (f32::powf(2.0, self.ev100) * 1.2)
+    }
+}
+
+impl Default for Exposure {
+    fn default() -> Self {
+        PhysicalCameraParameters::default().into()
+    }
+}
+
+impl From<PhysicalCameraParameters> for Exposure {
+    fn from(parameters: PhysicalCameraParameters) -> Self {
+        Exposure {
+            ev100: parameters.ev100(),
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use bevy_core::{Pod, Zeroable};
+use bevy_render::{color::Color, renderer::RenderResources, shader::ShaderDefs};
+
+/// How the density of [`Fog`] grows with the distance to the camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FogFalloff {
+    /// No fog before `start`, and fully fogged past `end`, in world units from the camera.
+    Linear { start: f32, end: f32 },
+    /// Fog amount of `1 - e^(-density * distance)`.
+    Exponential { density: f32 },
+    /// Fog amount of `1 - e^(-(density * distance)^2)`, clearer close to the camera and thicker
+    /// further away than [`FogFalloff::Exponential`].
+    ExponentialSquared { density: f32 },
+}
+
+/// Fog that thins out with altitude, settling in valleys and above water.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HeightFog {
+    /// The altitude below which the fog has its full density
+    pub base: f32,
+    /// How fast the fog thins out above `base`: its density is divided by `e` every
+    /// `1 / falloff` world units
+    pub falloff: f32,
+}
+
+/// Distance fog applied to the PBR meshes seen by a camera. Add it to a camera entity; cameras
+/// without it have infinite visibility.
+///
+/// For outdoor scenes, setting `color` to the horizon color of a [`Sky`] blends distant meshes
+/// into it.
+#[derive(Debug, Clone)]
+pub struct Fog {
+    pub color: Color,
+    pub falloff: FogFalloff,
+    pub height: Option<HeightFog>,
+    /// The color the fog takes when looking towards the directional light, simulating sunlight
+    /// scattering in the atmosphere. The alpha channel scales the effect, use a transparent color
+    /// to disable it.
+    pub directional_light_color: Color,
+    /// The higher the exponent, the tighter the glow around the directional light
+    pub directional_light_exponent: f32,
+}
+
+impl Default for Fog {
+    fn default() -> Self {
+        Fog {
+            color: Color::rgb(0.5, 0.6, 0.7),
+            falloff: FogFalloff::Linear {
+                start: 0.0,
+                end: 100.0,
+            },
+            height: None,
+            directional_light_color: Color::NONE,
+            directional_light_exponent: 8.0,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub(crate) struct FogUniform {
+    pub color: [f32; 4],
+    // rgb is premultiplied by alpha, and w is the exponent
+    pub directional_light: [f32; 4],
+    // x is the falloff mode, 0 disabling the fog, y and z the falloff parameters
+    pub falloff: [f32; 4],
+    // x enables height fog, y is the base and z the falloff
+    pub height: [f32; 4],
+}
+
+impl FogUniform {
+    pub fn new(fog: Option<&Fog>) -> FogUniform {
+        let fog = match fog {
+            Some(fog) => fog,
+            None => return FogUniform::zeroed(),
+        };
+        let falloff = match fog.falloff {
+            FogFalloff::Linear { start, end } => [1.0, start, end, 0.0],
+            FogFalloff::Exponential { density } => [2.0, density, 0.0, 0.0],
+            FogFalloff::ExponentialSquared { density } => [3.0, density, 0.0, 0.0],
+        };
+        let height = match fog.height {
+            Some(HeightFog { base, falloff }) => [1.0, base, falloff, 0.0],
+            None => [0.0; 4],
+        };
+        let [red, green, blue, alpha] = fog.directional_light_color.as_linear_rgba_f32();
+        FogUniform {
+            color: fog.color.as_linear_rgba_f32(),
+            directional_light: [
+                red * alpha,
+                green * alpha,
+                blue * alpha,
+                fog.directional_light_exponent,
+            ],
+            falloff,
+            height,
+        }
+    }
+}
+
+/// A simple sky model, drawn behind every mesh of the main pass. Spawn it with a
+/// [`SkyBundle`](crate::SkyBundle).
+///
+/// The sky is a gradient from the horizon to the zenith and to the ground, with a sun disc and
+/// glow in the direction of the first [`DirectionalLight`](crate::DirectionalLight).
+#[derive(Debug, Clone, RenderResources, ShaderDefs)]
+pub struct Sky {
+    pub zenith_color: Color,
+    pub horizon_color: Color,
+    pub ground_color: Color,
+    pub sun_color: Color,
+    /// The angular radius of the sun disc, in radians
+    pub sun_size: f32,
+    /// Whether the sun disc and glow are drawn
+    #[render_resources(ignore)]
+    #[shader_def]
+    pub sun: bool,
+}
+
+impl Default for Sky {
+    fn default() -> Self {
+        Sky {
+            zenith_color: Color::rgb(0.15, 0.35, 0.75),
+            horizon_color: Color::rgb(0.5, 0.6, 0.7),
+            ground_color: Color::rgb(0.25, 0.25, 0.25),
+            sun_color: Color::rgb(1.0, 0.95, 0.8),
+            sun_size: 0.02,
+            sun: true,
+        }
+    }
+}
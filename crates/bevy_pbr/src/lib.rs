@@ -1,26 +1,40 @@
 pub mod render_graph;
 
+mod cluster;
+mod decal;
 mod entity;
+mod exposure;
+mod fog;
 mod light;
 mod material;
+mod motion_vectors;
 
+pub use cluster::*;
+pub use decal::*;
 pub use entity::*;
+pub use exposure::*;
+pub use fog::*;
 pub use light::*;
 pub use material::*;
+pub use motion_vectors::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
+        decal::Decal,
         entity::*,
-        light::{DirectionalLight, PointLight},
+        exposure::{Exposure, PhysicalCameraParameters},
+        fog::{Fog, FogFalloff, HeightFog, Sky},
+        light::{DirectionalLight, LightLayers, PointLight},
         material::StandardMaterial,
+        motion_vectors::MotionVectors,
     };
 }
 
 use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, Assets, Handle};
-use bevy_ecs::system::IntoSystem;
-use bevy_render::{prelude::Color, shader};
+use bevy_ecs::{schedule::ParallelSystemDescriptorCoercion, system::IntoSystem};
+use bevy_render::{prelude::Color, shader, RenderStage, RenderSystem};
 use material::StandardMaterial;
 use render_graph::add_pbr_graph;
 
@@ -32,10 +46,45 @@ impl Plugin for PbrPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_asset::<StandardMaterial>()
             .register_type::<PointLight>()
+            .register_type::<LightLayers>()
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 shader::asset_shader_defs_system::<StandardMaterial>.system(),
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                shader::shader_defs_system::<LightLayers>.system(),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                shader::shader_defs_system::<Decal>.system(),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                shader::shader_defs_system::<Sky>.system(),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                decal::decal_order_system
+                    .system()
+                    .after(RenderSystem::VisibleEntities),
+            )
+            .add_system_to_stage(
+                RenderStage::RenderResource,
+                render_graph::main_pass_depth_binding_system.system(),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                motion_vectors::motion_vector_proxy_system.system(),
+            )
+            .add_system_to_stage(
+                RenderStage::RenderResource,
+                render_graph::motion_vectors_binding_system.system(),
+            )
+            .add_system_to_stage(
+                RenderStage::PostRender,
+                motion_vectors::previous_global_transform_system.system(),
+            )
             .init_resource::<AmbientLight>();
         add_pbr_graph(app.world_mut());
 
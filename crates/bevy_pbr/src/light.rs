@@ -1,8 +1,16 @@
-use bevy_core::{Pod, Zeroable};
+use crate::Exposure;
+use bevy_asset::Handle;
+use bevy_core::{Bytes, Pod, Zeroable};
 use bevy_ecs::reflect::ReflectComponent;
 use bevy_math::Vec3;
 use bevy_reflect::Reflect;
-use bevy_render::color::Color;
+use bevy_render::{
+    camera::Layer,
+    color::Color,
+    renderer::{RenderResource, RenderResourceType, RenderResources},
+    shader::{ShaderDefIterator, ShaderDefs},
+    texture::Texture,
+};
 use bevy_transform::components::GlobalTransform;
 
 /// A point light
@@ -31,12 +39,19 @@ impl Default for PointLight {
 pub(crate) struct PointLightUniform {
     pub pos: [f32; 4],
     pub color: [f32; 4],
-    // storing as a `[f32; 4]` for memory alignement
-    pub light_params: [f32; 4],
+    // x = inverse square range, y = radius
+    pub light_params: [f32; 2],
+    pub layers: u32,
+    // pads the uniform to a multiple of 16 bytes, the alignment of `pos`
+    _padding: u32,
 }
 
 impl PointLightUniform {
-    pub fn new(light: &PointLight, global_transform: &GlobalTransform) -> PointLightUniform {
+    pub fn new(
+        light: &PointLight,
+        global_transform: &GlobalTransform,
+        layers: LightLayers,
+    ) -> PointLightUniform {
         let (x, y, z) = global_transform.translation.into();
 
         // premultiply color by intensity
@@ -46,7 +61,9 @@ impl PointLightUniform {
         PointLightUniform {
             pos: [x, y, z, 1.0],
             color,
-            light_params: [1.0 / (light.range * light.range), light.radius, 0.0, 0.0],
+            light_params: [1.0 / (light.range * light.range), light.radius],
+            layers: layers.0,
+            _padding: 0,
         }
     }
 }
@@ -118,42 +135,169 @@ impl Default for DirectionalLight {
 #[repr(C)]
 #[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub(crate) struct DirectionalLightUniform {
-    pub dir: [f32; 4],
+    pub dir: [f32; 3],
+    pub layers: u32,
     pub color: [f32; 4],
 }
 
 impl DirectionalLightUniform {
-    pub fn new(light: &DirectionalLight) -> DirectionalLightUniform {
+    pub fn new(light: &DirectionalLight, layers: LightLayers) -> DirectionalLightUniform {
         // direction is negated to be ready for N.L
-        let dir: [f32; 4] = [
-            -light.direction.x,
-            -light.direction.y,
-            -light.direction.z,
-            0.0,
-        ];
-
-        // convert from illuminance (lux) to candelas
-        //
-        // exposure is hard coded at the moment but should be replaced
-        // by values coming from the camera
-        // see: https://google.github.io/filament/Filament.html#imagingpipeline/physicallybasedcamera/exposuresettings
-        const APERTURE: f32 = 4.0;
-        const SHUTTER_SPEED: f32 = 1.0 / 250.0;
-        const SENSITIVITY: f32 = 100.0;
-        let ev100 = f32::log2(APERTURE * APERTURE / SHUTTER_SPEED) - f32::log2(SENSITIVITY / 100.0);
-        let exposure = 1.0 / (f32::powf(2.0, ev100) * 1.2);
-        let intensity = light.illuminance * exposure;
+        let dir: [f32; 3] = (-light.direction).into();
 
-        // premultiply color by intensity
+        // premultiply color by illuminance (lux), which is scaled by the `Exposure` of each
+        // camera in the shader
         // we don't use the alpha at all, so no reason to multiply only [0..3]
-        let color: [f32; 4] = (light.color * intensity).into();
+        let color: [f32; 4] = (light.color * light.illuminance).into();
+
+        DirectionalLightUniform {
+            dir,
+            layers: layers.0,
+            color,
+        }
+    }
+}
+
+/// Describes which light layers an entity belongs to.
+///
+/// Lights only affect the meshes they share a layer with, e.g. to light a model previewed in the
+/// UI without lighting the rest of the scene. Add it to a [`PointLight`] or [`DirectionalLight`]
+/// to choose the layers it lights, and to a PBR mesh to choose the lights it receives. Light
+/// layers are independent from [`RenderLayers`](bevy_render::camera::RenderLayers), which decide
+/// which cameras see an entity.
+///
+/// There are 32 layers numbered `0` - [`TOTAL_LAYERS`](LightLayers::TOTAL_LAYERS). The
+/// [`Default`] instance contains layer `0`, and entities without this component belong to layer
+/// `0`.
+///
+/// Ambient light isn't affected by light layers.
+#[derive(Copy, Clone, Reflect, RenderResources, PartialEq, Eq, PartialOrd, Ord)]
+#[reflect(Component, PartialEq)]
+#[render_resources(from_self)]
+pub struct LightLayers(u32);
+
+impl std::fmt::Debug for LightLayers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("LightLayers")
+            .field(&self.iter().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl std::iter::FromIterator<Layer> for LightLayers {
+    fn from_iter<T: IntoIterator<Item = Layer>>(i: T) -> Self {
+        i.into_iter().fold(Self::none(), |mask, g| mask.with(g))
+    }
+}
+
+/// Defaults to containing to layer `0`, the first layer.
+impl Default for LightLayers {
+    fn default() -> Self {
+        LightLayers::layer(0)
+    }
+}
+
+impl LightLayers {
+    /// The total number of layers supported.
+    pub const TOTAL_LAYERS: usize = std::mem::size_of::<u32>() * 8;
+
+    /// The shader def of the meshes with light layers
+    pub const SHADER_DEF: &'static str = "LIGHT_LAYERS";
+
+    /// Create a new `LightLayers` belonging to the given layer.
+    pub fn layer(n: Layer) -> Self {
+        LightLayers(0).with(n)
+    }
+
+    /// Create a new `LightLayers` that belongs to all layers.
+    pub fn all() -> Self {
+        LightLayers(u32::MAX)
+    }
+
+    /// Create a new `LightLayers` that belongs to no layers.
+    pub fn none() -> Self {
+        LightLayers(0)
+    }
+
+    /// Create a `LightLayers` from a list of layers.
+    pub fn from_layers(layers: &[Layer]) -> Self {
+        layers.iter().copied().collect()
+    }
+
+    /// Add the given layer.
+    ///
+    /// # Panics
+    /// Panics when called with a layer greater than `TOTAL_LAYERS - 1`.
+    pub fn with(mut self, layer: Layer) -> Self {
+        assert!(usize::from(layer) < Self::TOTAL_LAYERS);
+        self.0 |= 1 << layer;
+        self
+    }
+
+    /// Removes the given layer.
+    ///
+    /// # Panics
+    /// Panics when called with a layer greater than `TOTAL_LAYERS - 1`.
+    pub fn without(mut self, layer: Layer) -> Self {
+        assert!(usize::from(layer) < Self::TOTAL_LAYERS);
+        self.0 &= !(1 << layer);
+        self
+    }
+
+    /// Get an iterator of the layers.
+    pub fn iter(&self) -> impl Iterator<Item = Layer> {
+        let mask = *self;
+        (0..Self::TOTAL_LAYERS as Layer).filter(move |g| LightLayers::layer(*g).intersects(&mask))
+    }
+
+    /// Determine if a `LightLayers` intersects another, i.e. if they share any common layers.
+    pub fn intersects(&self, other: &LightLayers) -> bool {
+        (self.0 & other.0) > 0
+    }
+}
 
-        DirectionalLightUniform { dir, color }
+impl RenderResource for LightLayers {
+    fn resource_type(&self) -> Option<RenderResourceType> {
+        Some(RenderResourceType::Buffer)
+    }
+
+    fn write_buffer_bytes(&self, buffer: &mut [u8]) {
+        self.0.write_bytes(buffer);
+    }
+
+    fn buffer_byte_len(&self) -> Option<usize> {
+        Some(self.0.byte_len())
+    }
+
+    fn texture(&self) -> Option<&Handle<Texture>> {
+        None
     }
 }
 
-// Ambient light color.
-#[derive(Debug)]
+// meshes without light layers don't bind them, and only receive the lights of layer `0`
+impl ShaderDefs for LightLayers {
+    fn shader_defs_len(&self) -> usize {
+        1
+    }
+
+    fn get_shader_def(&self, index: usize) -> Option<&str> {
+        if index == 0 {
+            Some(LightLayers::SHADER_DEF)
+        } else {
+            None
+        }
+    }
+
+    fn iter_shader_defs(&self) -> ShaderDefIterator {
+        ShaderDefIterator::new(self)
+    }
+}
+
+/// Ambient light color.
+///
+/// Used as a resource for every camera, and as a component on a camera entity to override the
+/// resource for the meshes it sees.
+#[derive(Debug, Clone)]
 pub struct AmbientLight {
     pub color: Color,
     /// Color is premultiplied by brightness before being passed to the shader
@@ -168,3 +312,26 @@ impl Default for AmbientLight {
         }
     }
 }
+
+#[repr(C)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
+pub(crate) struct CameraLightingUniform {
+    // x scales all the lighting, and y the directional lights on top of it
+    pub exposure: [f32; 4],
+    pub ambient_color: [f32; 4],
+}
+
+impl CameraLightingUniform {
+    pub fn new(exposure: Option<&Exposure>, ambient_light: &AmbientLight) -> CameraLightingUniform {
+        let exposure = match exposure {
+            Some(exposure) => [exposure.exposure(), 1.0, 0.0, 0.0],
+            None => [1.0, Exposure::default().exposure(), 0.0, 0.0],
+        };
+        // premultiply ambient brightness
+        let ambient_color: [f32; 4] = (ambient_light.color * ambient_light.brightness).into();
+        CameraLightingUniform {
+            exposure,
+            ambient_color,
+        }
+    }
+}
@@ -39,6 +39,15 @@ pub struct StandardMaterial {
     pub emissive: Color,
     #[shader_def]
     pub emissive_texture: Option<Handle<Texture>>,
+    /// A grayscale height map, white being the top of the surface, used for parallax occlusion
+    /// mapping. The mesh needs tangents, like with a `normal_map`
+    #[shader_def]
+    pub height_map: Option<Handle<Texture>>,
+    /// How deep the surface of the `height_map` goes, in UV units. Defaults to 0.1
+    pub parallax_depth_scale: f32,
+    /// The number of steps taken through the `height_map` at grazing angles, a quarter of them
+    /// being taken when looking straight at the surface. Defaults to 16
+    pub max_parallax_layer_count: f32,
     #[render_resources(ignore)]
     #[shader_def]
     pub unlit: bool,
@@ -67,6 +76,9 @@ impl Default for StandardMaterial {
             occlusion_texture: None,
             emissive: Color::BLACK,
             emissive_texture: None,
+            height_map: None,
+            parallax_depth_scale: 0.1,
+            max_parallax_layer_count: 16.0,
             unlit: false,
         }
     }
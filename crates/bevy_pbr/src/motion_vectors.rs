@@ -0,0 +1,122 @@
+use crate::render_graph::MOTION_VECTOR_PIPELINE_HANDLE;
+use bevy_asset::Handle;
+use bevy_ecs::{
+    entity::Entity,
+    query::{Added, Changed, With, Without},
+    system::{Commands, Query, RemovedComponents},
+};
+use bevy_math::Mat4;
+use bevy_render::{
+    draw::Draw,
+    mesh::Mesh,
+    pipeline::{RenderPipeline, RenderPipelines},
+    prelude::Visible,
+    renderer::RenderResources,
+};
+use bevy_transform::prelude::{BuildChildren, GlobalTransform, Transform};
+
+/// Renders the motion of an entity's mesh in the motion vector prepass.
+///
+/// The motion vectors are written to a texture bound as
+/// [`uniform::MOTION_VECTORS`](crate::render_graph::uniform::MOTION_VECTORS), for post-processing
+/// such as temporal anti-aliasing or motion blur. Each texel holds the screen space motion since
+/// the previous frame, in UV units, of the closest entity with motion vectors. Only the motion of
+/// the entity's transform and of the 3D camera is taken into account, not vertex animation.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MotionVectors;
+
+/// The entity drawing the motion vectors of an entity with [`MotionVectors`], spawned as its child.
+#[derive(Debug, Clone, Copy)]
+pub struct MotionVectorProxy(pub Entity);
+
+/// A component that indicates that an entity should be drawn in the motion vector prepass
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MotionVectorPass;
+
+/// The [`GlobalTransform`] of an entity in the previous frame, recorded by
+/// [`previous_global_transform_system`] after each frame is rendered.
+#[derive(Debug, Clone, Copy, RenderResources)]
+pub struct PreviousGlobalTransform {
+    pub model: Mat4,
+}
+
+impl Default for PreviousGlobalTransform {
+    fn default() -> Self {
+        PreviousGlobalTransform {
+            model: Mat4::IDENTITY,
+        }
+    }
+}
+
+impl From<&GlobalTransform> for PreviousGlobalTransform {
+    fn from(global_transform: &GlobalTransform) -> Self {
+        PreviousGlobalTransform {
+            model: global_transform.compute_matrix(),
+        }
+    }
+}
+
+/// Spawns, updates and despawns the [`MotionVectorProxy`] of entities with [`MotionVectors`].
+#[allow(clippy::type_complexity)]
+pub fn motion_vector_proxy_system(
+    mut commands: Commands,
+    mut removed: RemovedComponents<MotionVectors>,
+    added: Query<
+        (Entity, &Handle<Mesh>, &GlobalTransform),
+        (
+            Added<MotionVectors>,
+            Without<MotionVectorProxy>,
+            Without<MotionVectorPass>,
+        ),
+    >,
+    changed_meshes: Query<
+        (&Handle<Mesh>, &MotionVectorProxy),
+        (Changed<Handle<Mesh>>, Without<MotionVectorPass>),
+    >,
+    proxies: Query<&MotionVectorProxy>,
+    mut proxy_meshes: Query<&mut Handle<Mesh>, With<MotionVectorPass>>,
+) {
+    for (entity, mesh, global_transform) in added.iter() {
+        let proxy = commands
+            .spawn_bundle((
+                mesh.clone(),
+                MotionVectorPass,
+                Draw::default(),
+                Visible::default(),
+                RenderPipelines::from_pipelines(vec![RenderPipeline::new(
+                    MOTION_VECTOR_PIPELINE_HANDLE.typed(),
+                )]),
+                Transform::default(),
+                *global_transform,
+                PreviousGlobalTransform::from(global_transform),
+            ))
+            .id();
+        commands
+            .entity(entity)
+            .push_children(&[proxy])
+            .insert(MotionVectorProxy(proxy));
+    }
+
+    for (mesh, proxy) in changed_meshes.iter() {
+        if let Ok(mut proxy_mesh) = proxy_meshes.get_mut(proxy.0) {
+            *proxy_mesh = mesh.clone();
+        }
+    }
+
+    for entity in removed.iter() {
+        if let Ok(proxy) = proxies.get(entity) {
+            commands.entity(proxy.0).despawn();
+            commands.entity(entity).remove::<MotionVectorProxy>();
+        }
+    }
+}
+
+/// Records the [`GlobalTransform`] of each entity with a [`PreviousGlobalTransform`], once the
+/// frame is rendered.
+pub fn previous_global_transform_system(
+    mut query: Query<(&GlobalTransform, &mut PreviousGlobalTransform)>,
+) {
+    for (global_transform, mut previous_global_transform) in query.iter_mut() {
+        *previous_global_transform = PreviousGlobalTransform::from(global_transform);
+    }
+}
@@ -0,0 +1,198 @@
+use crate::{
+    exposure::Exposure,
+    fog::{Fog, FogUniform},
+    light::{AmbientLight, CameraLightingUniform},
+};
+use bevy_core::{bytes_of, Pod};
+use bevy_ecs::{
+    entity::Entity,
+    query::With,
+    system::{BoxedSystem, In, IntoChainSystem, IntoSystem, Local, Query, Res, ResMut},
+    world::World,
+};
+use bevy_render::{
+    camera::{ActiveCameras, Camera},
+    render_graph::{CommandQueue, Node, ResourceSlots, SystemNode},
+    renderer::{
+        BufferId, BufferInfo, BufferMapMode, BufferUsage, RenderContext, RenderResourceBinding,
+        RenderResourceContext,
+    },
+};
+use bevy_utils::HashMap;
+
+type CameraUniformSystemFactory =
+    Box<dyn Fn(CameraUniformNodeSystemState) -> BoxedSystem + Send + Sync>;
+
+/// A Render Graph [Node] that writes a uniform to the bindings of every active camera, such as
+/// its [`Fog`] or [`Exposure`].
+///
+/// The uniforms are computed by a system returning the uniform of each camera entity. Active
+/// cameras it returns no uniform for are left as they are.
+pub struct CameraUniformNode {
+    command_queue: CommandQueue,
+    binding: &'static str,
+    get_system: CameraUniformSystemFactory,
+}
+
+impl CameraUniformNode {
+    /// Writes the uniforms returned by `uniforms_system` to the camera bindings named `binding`.
+    pub fn new<U, S, Params>(binding: &'static str, uniforms_system: S) -> Self
+    where
+        U: Pod + Send + Sync,
+        S: IntoSystem<(), HashMap<Entity, U>, Params> + Clone + Send + Sync + 'static,
+    {
+        CameraUniformNode {
+            command_queue: Default::default(),
+            binding,
+            get_system: Box::new(move |state| {
+                let system = uniforms_system.clone().chain(
+                    camera_uniform_node_system::<U>
+                        .system()
+                        .config(|config| config.0 = Some(state)),
+                );
+                Box::new(system)
+            }),
+        }
+    }
+}
+
+impl Node for CameraUniformNode {
+    fn update(
+        &mut self,
+        _world: &World,
+        render_context: &mut dyn RenderContext,
+        _input: &ResourceSlots,
+        _output: &mut ResourceSlots,
+    ) {
+        self.command_queue.execute(render_context);
+    }
+}
+
+impl SystemNode for CameraUniformNode {
+    fn get_system(&self) -> BoxedSystem {
+        (self.get_system)(CameraUniformNodeSystemState {
+            command_queue: self.command_queue.clone(),
+            binding: self.binding,
+            staging_buffer: None,
+        })
+    }
+}
+
+/// Local "camera uniform node system" state
+#[derive(Debug, Default)]
+pub struct CameraUniformNodeSystemState {
+    command_queue: CommandQueue,
+    binding: &'static str,
+    staging_buffer: Option<(BufferId, usize)>,
+}
+
+pub fn camera_uniform_node_system<U: Pod + Send + Sync>(
+    In(uniforms): In<HashMap<Entity, U>>,
+    mut state: Local<CameraUniformNodeSystemState>,
+    mut active_cameras: ResMut<ActiveCameras>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+) {
+    let state = &mut state;
+    let render_resource_context = &**render_resource_context;
+    let uniform_size = std::mem::size_of::<U>();
+
+    let cameras = active_cameras
+        .iter_mut()
+        .filter_map(|active_camera| {
+            let uniform = uniforms.get(&active_camera.entity?)?;
+            Some((active_camera, uniform))
+        })
+        .collect::<Vec<_>>();
+    if cameras.is_empty() {
+        return;
+    }
+
+    let staging_buffer_size = uniform_size * cameras.len();
+    let staging_buffer = match state.staging_buffer {
+        Some((staging_buffer, size)) if size >= staging_buffer_size => {
+            render_resource_context.map_buffer(staging_buffer, BufferMapMode::Write);
+            staging_buffer
+        }
+        previous => {
+            if let Some((staging_buffer, _)) = previous {
+                render_resource_context.remove_buffer(staging_buffer);
+            }
+            let staging_buffer = render_resource_context.create_buffer(BufferInfo {
+                size: staging_buffer_size,
+                buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
+                mapped_at_creation: true,
+            });
+            state.staging_buffer = Some((staging_buffer, staging_buffer_size));
+            staging_buffer
+        }
+    };
+
+    for (index, (active_camera, uniform)) in cameras.into_iter().enumerate() {
+        let bindings = &mut active_camera.bindings;
+        if bindings.get(state.binding).is_none() {
+            let buffer = render_resource_context.create_buffer(BufferInfo {
+                size: uniform_size,
+                buffer_usage: BufferUsage::COPY_DST | BufferUsage::UNIFORM,
+                ..Default::default()
+            });
+            bindings.set(
+                state.binding,
+                RenderResourceBinding::Buffer {
+                    buffer,
+                    range: 0..uniform_size as u64,
+                    dynamic_index: None,
+                },
+            );
+        }
+
+        if let Some(RenderResourceBinding::Buffer { buffer, .. }) = bindings.get(state.binding) {
+            let offset = (index * uniform_size) as u64;
+            render_resource_context.write_mapped_buffer(
+                staging_buffer,
+                offset..(offset + uniform_size as u64),
+                &mut |data, _renderer| {
+                    data[0..uniform_size].copy_from_slice(bytes_of(uniform));
+                },
+            );
+            state.command_queue.copy_buffer_to_buffer(
+                staging_buffer,
+                offset,
+                *buffer,
+                0,
+                uniform_size as u64,
+            );
+        }
+    }
+
+    render_resource_context.unmap_buffer(staging_buffer);
+}
+
+/// The [`Fog`] of every camera. Cameras without fog get a disabled one, as the PBR shader expects
+/// the binding.
+pub(crate) fn fog_uniforms_system(
+    cameras: Query<(Entity, Option<&Fog>), With<Camera>>,
+) -> HashMap<Entity, FogUniform> {
+    cameras
+        .iter()
+        .map(|(entity, fog)| (entity, FogUniform::new(fog)))
+        .collect()
+}
+
+/// The [`Exposure`] and [`AmbientLight`] of every camera. Cameras without an exposure get the
+/// default scaling of the lights, and cameras without an ambient light get the [`AmbientLight`]
+/// resource.
+pub(crate) fn camera_lighting_uniforms_system(
+    ambient_light: Res<AmbientLight>,
+    cameras: Query<(Entity, Option<&Exposure>, Option<&AmbientLight>), With<Camera>>,
+) -> HashMap<Entity, CameraLightingUniform> {
+    cameras
+        .iter()
+        .map(|(entity, exposure, camera_ambient_light)| {
+            let lighting = CameraLightingUniform::new(
+                exposure,
+                camera_ambient_light.unwrap_or(&*ambient_light),
+            );
+            (entity, lighting)
+        })
+        .collect()
+}
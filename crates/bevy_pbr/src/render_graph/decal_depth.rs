@@ -0,0 +1,45 @@
+use crate::render_graph::uniform;
+use bevy_ecs::system::{Local, Res, ResMut};
+use bevy_render::{
+    render_graph::{base, RenderGraph, WindowTextureNode},
+    renderer::{RenderResourceBinding, RenderResourceBindings, RenderResourceContext, SamplerId},
+    texture::{FilterMode, SamplerDescriptor},
+};
+
+/// Binds the depth texture of the main pass as [`uniform::MAIN_PASS_DEPTH`], so that the decal
+/// pass can sample it.
+///
+/// The texture is created by the main depth texture node when the window is created or resized,
+/// so it is bound from the next frame on.
+pub fn main_pass_depth_binding_system(
+    mut sampler: Local<Option<SamplerId>>,
+    render_graph: Res<RenderGraph>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+    mut render_resource_bindings: ResMut<RenderResourceBindings>,
+) {
+    let depth_texture = render_graph
+        .get_node_state(base::node::MAIN_DEPTH_TEXTURE)
+        .ok()
+        .and_then(|node| node.output_slots.get(WindowTextureNode::OUT_TEXTURE))
+        .and_then(|resource| resource.get_texture());
+    let depth_texture = match depth_texture {
+        Some(depth_texture) => depth_texture,
+        None => return,
+    };
+
+    let sampler = *sampler.get_or_insert_with(|| {
+        render_resource_context.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            ..Default::default()
+        })
+    });
+    render_resource_bindings.set(
+        uniform::MAIN_PASS_DEPTH,
+        RenderResourceBinding::Texture(depth_texture),
+    );
+    render_resource_bindings.set(
+        &format!("{}_sampler", uniform::MAIN_PASS_DEPTH),
+        RenderResourceBinding::Sampler(sampler),
+    );
+}
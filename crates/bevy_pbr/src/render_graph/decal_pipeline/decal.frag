@@ -0,0 +1,62 @@
+#version 450
+
+layout(location = 0) in vec4 v_ClipPosition;
+layout(location = 1) flat in mat4 v_InverseViewProj;
+layout(location = 5) flat in mat4 v_InverseModel;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(set = 1, binding = 0) uniform texture2D MainPass_depth;
+layout(set = 1, binding = 1) uniform sampler MainPass_depth_sampler;
+
+layout(set = 2, binding = 0) uniform Transform {
+    mat4 Model;
+};
+
+layout(set = 3, binding = 0) uniform Decal_color {
+    vec4 color;
+};
+
+#ifdef DECAL_TEXTURE
+layout(set = 3, binding = 1) uniform texture2D Decal_texture;
+layout(set = 3, binding = 2) uniform sampler Decal_texture_sampler;
+#endif
+
+layout(set = 3, binding = 3) uniform Decal_fade_start_angle {
+    float fade_start_angle;
+};
+
+layout(set = 3, binding = 4) uniform Decal_fade_end_angle {
+    float fade_end_angle;
+};
+
+void main() {
+    // the scene position behind this fragment of the decal box
+    float depth = texelFetch(
+        sampler2D(MainPass_depth, MainPass_depth_sampler),
+        ivec2(gl_FragCoord.xy),
+        0
+    ).r;
+    vec4 world_position = v_InverseViewProj * vec4(v_ClipPosition.xy / v_ClipPosition.w, depth, 1.0);
+    world_position /= world_position.w;
+
+    // discard the parts of the scene outside of the decal box
+    vec3 decal_position = (v_InverseModel * world_position).xyz;
+    if (any(greaterThan(abs(decal_position), vec3(0.5)))) {
+        discard;
+    }
+
+    // fade out on surfaces at an angle to the projection axis, the decal's local Y axis
+    vec3 normal = normalize(cross(dFdx(world_position.xyz), dFdy(world_position.xyz)));
+    vec3 axis = normalize(Model[1].xyz);
+    float angle = acos(clamp(abs(dot(normal, axis)), 0.0, 1.0));
+    float fade = 1.0 - smoothstep(fade_start_angle, max(fade_end_angle, fade_start_angle + 0.0001), angle);
+
+    vec4 output_color = color;
+#ifdef DECAL_TEXTURE
+    vec2 uv = vec2(decal_position.x + 0.5, 0.5 - decal_position.z);
+    output_color *= texture(sampler2D(Decal_texture, Decal_texture_sampler), uv);
+#endif
+    output_color.a *= fade;
+    o_Target = output_color;
+}
@@ -0,0 +1,23 @@
+#version 450
+
+layout(location = 0) in vec3 Vertex_Position;
+
+layout(location = 0) out vec4 v_ClipPosition;
+// inverse matrices used to reconstruct the decal space position of the scene from its depth
+layout(location = 1) flat out mat4 v_InverseViewProj;
+layout(location = 5) flat out mat4 v_InverseModel;
+
+layout(set = 0, binding = 0) uniform CameraViewProj {
+    mat4 ViewProj;
+};
+
+layout(set = 2, binding = 0) uniform Transform {
+    mat4 Model;
+};
+
+void main() {
+    v_InverseViewProj = inverse(ViewProj);
+    v_InverseModel = inverse(Model);
+    v_ClipPosition = ViewProj * Model * vec4(Vertex_Position, 1.0);
+    gl_Position = v_ClipPosition;
+}
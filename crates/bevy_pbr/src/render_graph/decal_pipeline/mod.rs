@@ -0,0 +1,61 @@
+use bevy_asset::{Assets, HandleUntyped};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    mesh::Mesh,
+    pipeline::{
+        BlendComponent, BlendFactor, BlendOperation, BlendState, ColorTargetState, ColorWrite,
+        Face, FrontFace, PipelineDescriptor, PolygonMode, PrimitiveState, PrimitiveTopology,
+    },
+    shader::{Shader, ShaderStage, ShaderStages},
+    texture::TextureFormat,
+};
+
+pub const DECAL_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 6139452372934104813);
+
+/// The unit cube drawn for each decal
+pub const DECAL_MESH_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Mesh::TYPE_UUID, 9872716524193082411);
+
+pub(crate) fn build_decal_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    PipelineDescriptor {
+        // decals read the depth of the main pass instead of testing against it
+        depth_stencil: None,
+        // draw the back faces of the box, so that decals stay visible with the camera inside them
+        primitive: PrimitiveState {
+            topology: PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: FrontFace::Ccw,
+            cull_mode: Some(Face::Front),
+            polygon_mode: PolygonMode::Fill,
+            clamp_depth: false,
+            conservative: false,
+        },
+        color_target_states: vec![ColorTargetState {
+            format: TextureFormat::default(),
+            blend: Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::SrcAlpha,
+                    dst_factor: BlendFactor::OneMinusSrcAlpha,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent {
+                    src_factor: BlendFactor::Zero,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+            }),
+            write_mask: ColorWrite::ALL,
+        }],
+        ..PipelineDescriptor::new(ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(
+                ShaderStage::Vertex,
+                include_str!("decal.vert"),
+            )),
+            fragment: Some(shaders.add(Shader::from_glsl(
+                ShaderStage::Fragment,
+                include_str!("decal.frag"),
+            ))),
+        })
+    }
+}
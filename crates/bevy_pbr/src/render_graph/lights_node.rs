@@ -1,15 +1,17 @@
 use crate::{
+    cluster::{ClusterConfig, Clusters},
     light::{
-        AmbientLight, DirectionalLight, DirectionalLightUniform, PointLight, PointLightUniform,
+        DirectionalLight, DirectionalLightUniform, LightLayers, PointLight, PointLightUniform,
     },
     render_graph::uniform,
 };
-use bevy_core::{bytes_of, Pod, Zeroable};
+use bevy_core::{bytes_of, cast_slice, Pod, Zeroable};
 use bevy_ecs::{
     system::{BoxedSystem, IntoSystem, Local, Query, Res, ResMut},
     world::World,
 };
 use bevy_render::{
+    camera::{ActiveCameras, Camera},
     render_graph::{CommandQueue, Node, ResourceSlots, SystemNode},
     renderer::{
         BufferId, BufferInfo, BufferMapMode, BufferUsage, RenderContext, RenderResourceBinding,
@@ -19,17 +21,19 @@ use bevy_render::{
 use bevy_transform::prelude::*;
 
 /// A Render Graph [Node] that write light data from the ECS to GPU buffers
+///
+/// Point lights are not limited in number: they are written to a storage buffer, and assigned
+/// to the clusters of every active camera (see [`ClusterConfig`]), so that fragments are only
+/// shaded with the lights of their cluster.
 #[derive(Debug, Default)]
 pub struct LightsNode {
     command_queue: CommandQueue,
-    max_point_lights: usize,
     max_dir_lights: usize,
 }
 
 impl LightsNode {
-    pub fn new(max_point_lights: usize, max_dir_lights: usize) -> Self {
+    pub fn new(max_dir_lights: usize) -> Self {
         LightsNode {
-            max_point_lights,
             max_dir_lights,
             command_queue: CommandQueue::default(),
         }
@@ -62,9 +66,7 @@ impl SystemNode for LightsNode {
         let system = lights_node_system.system().config(|config| {
             config.0 = Some(LightsNodeSystemState {
                 command_queue: self.command_queue.clone(),
-                max_point_lights: self.max_point_lights,
                 max_dir_lights: self.max_dir_lights,
-                light_buffer: None,
                 staging_buffer: None,
             })
         });
@@ -75,126 +77,203 @@ impl SystemNode for LightsNode {
 /// Local "lights node system" state
 #[derive(Debug, Default)]
 pub struct LightsNodeSystemState {
-    light_buffer: Option<BufferId>,
-    staging_buffer: Option<BufferId>,
+    staging_buffer: Option<(BufferId, usize)>,
     command_queue: CommandQueue,
-    max_point_lights: usize,
     max_dir_lights: usize,
 }
 
+/// The data written to the staging buffer, and the buffers it is copied to
+#[derive(Default)]
+struct LightsUpload {
+    data: Vec<u8>,
+    copies: Vec<(usize, BufferId, usize)>,
+}
+
+impl LightsUpload {
+    /// Writes `bytes` to the buffer bound to `name`, replacing it with a bigger one if needed.
+    fn write(
+        &mut self,
+        render_resource_context: &dyn RenderResourceContext,
+        bindings: &mut RenderResourceBindings,
+        name: &str,
+        buffer_usage: BufferUsage,
+        bytes: &[u8],
+    ) {
+        let current = match bindings.get(name) {
+            Some(RenderResourceBinding::Buffer { buffer, range, .. }) => Some((*buffer, range.end)),
+            _ => None,
+        };
+        let buffer = match current {
+            Some((buffer, size)) if size >= bytes.len() as u64 => buffer,
+            previous => {
+                if let Some((buffer, _)) = previous {
+                    render_resource_context.remove_buffer(buffer);
+                }
+                let size = bytes.len().next_power_of_two();
+                let buffer = render_resource_context.create_buffer(BufferInfo {
+                    size,
+                    buffer_usage: buffer_usage | BufferUsage::COPY_DST,
+                    ..Default::default()
+                });
+                bindings.set(
+                    name,
+                    RenderResourceBinding::Buffer {
+                        buffer,
+                        range: 0..size as u64,
+                        dynamic_index: None,
+                    },
+                );
+                buffer
+            }
+        };
+        self.copies.push((self.data.len(), buffer, bytes.len()));
+        self.data.extend_from_slice(bytes);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn lights_node_system(
     mut state: Local<LightsNodeSystemState>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
-    ambient_light_resource: Res<AmbientLight>,
     // TODO: this write on RenderResourceBindings will prevent this system from running in parallel
     // with other systems that do the same
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
-    point_lights: Query<(&PointLight, &GlobalTransform)>,
-    dir_lights: Query<&DirectionalLight>,
+    mut active_cameras: ResMut<ActiveCameras>,
+    cameras: Query<(&Camera, &GlobalTransform, Option<&ClusterConfig>)>,
+    point_lights: Query<(&PointLight, &GlobalTransform, Option<&LightLayers>)>,
+    dir_lights: Query<(&DirectionalLight, Option<&LightLayers>)>,
 ) {
     let state = &mut state;
     let render_resource_context = &**render_resource_context;
+    let mut upload = LightsUpload::default();
 
-    // premultiply ambient brightness
-    let ambient_light: [f32; 4] =
-        (ambient_light_resource.color * ambient_light_resource.brightness).into();
-    let ambient_light_size = std::mem::size_of::<[f32; 4]>();
-
-    let point_light_count = point_lights.iter().len().min(state.max_point_lights);
-    let point_light_size = std::mem::size_of::<PointLightUniform>();
-    let point_light_array_size = point_light_size * point_light_count;
-    let point_light_array_max_size = point_light_size * state.max_point_lights;
-
-    let dir_light_count = dir_lights.iter().len().min(state.max_dir_lights);
-    let dir_light_size = std::mem::size_of::<DirectionalLightUniform>();
-    let dir_light_array_size = dir_light_size * dir_light_count;
-    let dir_light_array_max_size = dir_light_size * state.max_dir_lights;
-
-    let light_count_size = ambient_light_size + std::mem::size_of::<LightCount>();
-
-    let point_light_uniform_start = light_count_size;
-    let point_light_uniform_end = light_count_size + point_light_array_size;
+    let mut point_light_uniforms = Vec::new();
+    let mut point_light_spheres = Vec::new();
+    for (point_light, global_transform, layers) in point_lights.iter() {
+        point_light_uniforms.push(PointLightUniform::new(
+            &point_light,
+            &global_transform,
+            layers.copied().unwrap_or_default(),
+        ));
+        point_light_spheres.push((global_transform.translation, point_light.range));
+    }
+    // storage buffers can't be empty
+    if point_light_uniforms.is_empty() {
+        point_light_uniforms.push(PointLightUniform::zeroed());
+    }
 
-    let dir_light_uniform_start = light_count_size + point_light_array_max_size;
-    let dir_light_uniform_end =
-        light_count_size + point_light_array_max_size + dir_light_array_size;
+    let mut dir_light_uniforms = dir_lights
+        .iter()
+        .take(state.max_dir_lights)
+        .map(|(dir_light, layers)| {
+            DirectionalLightUniform::new(&dir_light, layers.copied().unwrap_or_default())
+        })
+        .collect::<Vec<_>>();
+    let dir_light_count = dir_light_uniforms.len();
+    dir_light_uniforms.resize(state.max_dir_lights, DirectionalLightUniform::zeroed());
 
-    let max_light_uniform_size =
-        light_count_size + point_light_array_max_size + dir_light_array_max_size;
+    let light_count = LightCount {
+        num_lights: [
+            point_light_spheres.len() as u32,
+            dir_light_count as u32,
+            0,
+            0,
+        ],
+    };
+    let mut lights = Vec::new();
+    lights.extend_from_slice(bytes_of(&light_count));
+    lights.extend_from_slice(cast_slice(&dir_light_uniforms));
+    upload.write(
+        render_resource_context,
+        &mut render_resource_bindings,
+        uniform::LIGHTS,
+        BufferUsage::UNIFORM,
+        &lights,
+    );
+    upload.write(
+        render_resource_context,
+        &mut render_resource_bindings,
+        uniform::POINT_LIGHTS,
+        BufferUsage::STORAGE,
+        cast_slice(&point_light_uniforms),
+    );
 
-    if let Some(staging_buffer) = state.staging_buffer {
-        if point_light_count == 0 && dir_light_count == 0 {
-            return;
+    for active_camera in active_cameras.iter_mut() {
+        let (camera, camera_transform, config) = match active_camera
+            .entity
+            .and_then(|entity| cameras.get(entity).ok())
+        {
+            Some(camera) => camera,
+            None => continue,
+        };
+        let mut clusters = Clusters::new(
+            &config.copied().unwrap_or_default(),
+            &camera.projection_matrix,
+            camera_transform,
+            &point_light_spheres,
+        );
+        if clusters.light_indices.is_empty() {
+            clusters.light_indices.push(0);
         }
-
-        render_resource_context.map_buffer(staging_buffer, BufferMapMode::Write);
-    } else {
-        let buffer = render_resource_context.create_buffer(BufferInfo {
-            size: max_light_uniform_size,
-            buffer_usage: BufferUsage::UNIFORM | BufferUsage::COPY_SRC | BufferUsage::COPY_DST,
-            ..Default::default()
-        });
-        render_resource_bindings.set(
-            uniform::LIGHTS,
-            RenderResourceBinding::Buffer {
-                buffer,
-                range: 0..max_light_uniform_size as u64,
-                dynamic_index: None,
-            },
+        let bindings = &mut active_camera.bindings;
+        upload.write(
+            render_resource_context,
+            bindings,
+            uniform::CAMERA_CLUSTERS,
+            BufferUsage::UNIFORM,
+            bytes_of(&clusters.uniform),
+        );
+        upload.write(
+            render_resource_context,
+            bindings,
+            uniform::CAMERA_CLUSTER_LIGHTS,
+            BufferUsage::STORAGE,
+            cast_slice(&clusters.clusters),
+        );
+        upload.write(
+            render_resource_context,
+            bindings,
+            uniform::CAMERA_CLUSTER_LIGHT_INDICES,
+            BufferUsage::STORAGE,
+            cast_slice(&clusters.light_indices),
         );
-        state.light_buffer = Some(buffer);
-
-        let staging_buffer = render_resource_context.create_buffer(BufferInfo {
-            size: max_light_uniform_size,
-            buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
-            mapped_at_creation: true,
-        });
-        state.staging_buffer = Some(staging_buffer);
     }
 
-    let staging_buffer = state.staging_buffer.unwrap();
+    let staging_buffer_size = upload.data.len();
+    let staging_buffer = match state.staging_buffer {
+        Some((staging_buffer, size)) if size >= staging_buffer_size => {
+            render_resource_context.map_buffer(staging_buffer, BufferMapMode::Write);
+            staging_buffer
+        }
+        previous => {
+            if let Some((staging_buffer, _)) = previous {
+                render_resource_context.remove_buffer(staging_buffer);
+            }
+            let size = staging_buffer_size.next_power_of_two();
+            let staging_buffer = render_resource_context.create_buffer(BufferInfo {
+                size,
+                buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
+                mapped_at_creation: true,
+            });
+            state.staging_buffer = Some((staging_buffer, size));
+            staging_buffer
+        }
+    };
     render_resource_context.write_mapped_buffer(
         staging_buffer,
-        0..max_light_uniform_size as u64,
+        0..staging_buffer_size as u64,
         &mut |data, _renderer| {
-            // ambient light
-            data[0..ambient_light_size].copy_from_slice(bytes_of(&ambient_light));
-
-            // light count
-            data[ambient_light_size..light_count_size].copy_from_slice(bytes_of(&[
-                point_light_count as u32,
-                dir_light_count as u32,
-                0,
-                0,
-            ]));
-
-            // point light array
-            for ((point_light, global_transform), slot) in point_lights.iter().zip(
-                data[point_light_uniform_start..point_light_uniform_end]
-                    .chunks_exact_mut(point_light_size),
-            ) {
-                slot.copy_from_slice(bytes_of(&PointLightUniform::new(
-                    &point_light,
-                    &global_transform,
-                )));
-            }
-
-            // directional light array
-            for (dir_light, slot) in dir_lights.iter().zip(
-                data[dir_light_uniform_start..dir_light_uniform_end]
-                    .chunks_exact_mut(dir_light_size),
-            ) {
-                slot.copy_from_slice(bytes_of(&DirectionalLightUniform::new(&dir_light)));
-            }
+            data.copy_from_slice(&upload.data);
         },
     );
     render_resource_context.unmap_buffer(staging_buffer);
-    let light_buffer = state.light_buffer.unwrap();
-    state.command_queue.copy_buffer_to_buffer(
-        staging_buffer,
-        0,
-        light_buffer,
-        0,
-        max_light_uniform_size as u64,
-    );
+    for (offset, buffer, size) in upload.copies.iter() {
+        state.command_queue.copy_buffer_to_buffer(
+            staging_buffer,
+            *offset as u64,
+            *buffer,
+            0,
+            *size as u64,
+        );
+    }
 }
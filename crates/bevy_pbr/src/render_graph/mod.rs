@@ -1,32 +1,81 @@
+mod camera_uniform_node;
+mod decal_depth;
+mod decal_pipeline;
 mod lights_node;
+mod motion_vector_node;
+mod motion_vector_pipeline;
 mod pbr_pipeline;
+mod sky_pipeline;
 
 use bevy_ecs::world::World;
+pub use camera_uniform_node::*;
+pub use decal_depth::*;
+pub use decal_pipeline::*;
 pub use lights_node::*;
+pub use motion_vector_node::*;
+pub use motion_vector_pipeline::*;
 pub use pbr_pipeline::*;
+pub use sky_pipeline::*;
 
 /// the names of pbr graph nodes
 pub mod node {
     pub const TRANSFORM: &str = "transform";
     pub const STANDARD_MATERIAL: &str = "standard_material";
     pub const LIGHTS: &str = "lights";
+    pub const LIGHT_LAYERS: &str = "light_layers";
+    pub const FOG: &str = "fog";
+    pub const CAMERA_LIGHTING: &str = "camera_lighting";
+    pub const SKY: &str = "sky";
+    pub const DECAL: &str = "decal";
+    pub const DECAL_PASS: &str = "decal_pass";
+    pub const PREVIOUS_TRANSFORM: &str = "previous_transform";
+    pub const PREVIOUS_VIEW_PROJ: &str = "previous_view_proj";
+    pub const MOTION_VECTOR_TEXTURE: &str = "motion_vector_texture";
+    pub const MOTION_VECTOR_DEPTH_TEXTURE: &str = "motion_vector_depth_texture";
+    pub const MOTION_VECTOR_PASS: &str = "motion_vector_pass";
 }
 
 /// the names of pbr uniforms
 pub mod uniform {
     pub const LIGHTS: &str = "Lights";
+    pub const POINT_LIGHTS: &str = "PointLights";
+    pub const CAMERA_CLUSTERS: &str = "CameraClusters";
+    pub const CAMERA_CLUSTER_LIGHTS: &str = "CameraClusterLights";
+    pub const CAMERA_CLUSTER_LIGHT_INDICES: &str = "CameraClusterLightIndices";
+    pub const CAMERA_FOG: &str = "CameraFog";
+    pub const CAMERA_LIGHTING: &str = "CameraLighting";
+    pub const MAIN_PASS_DEPTH: &str = "MainPass_depth";
+    pub const CAMERA_PREVIOUS_VIEW_PROJ: &str = "CameraPreviousViewProj";
+    pub const MOTION_VECTORS: &str = "MotionVectors";
 }
 
-use crate::prelude::StandardMaterial;
+use crate::{
+    decal::{Decal, DecalPass},
+    fog::Sky,
+    light::LightLayers,
+    motion_vectors::{MotionVectorPass, PreviousGlobalTransform},
+    prelude::StandardMaterial,
+};
 use bevy_asset::Assets;
 use bevy_render::{
+    mesh::{shape, Mesh},
+    pass::{
+        LoadOp, Operations, PassDescriptor, RenderPassColorAttachment,
+        RenderPassDepthStencilAttachment, TextureAttachment,
+    },
     pipeline::PipelineDescriptor,
-    render_graph::{base, AssetRenderResourcesNode, RenderGraph, RenderResourcesNode},
+    prelude::{Color, Msaa},
+    render_graph::{
+        base, AssetRenderResourcesNode, PassNode, RenderGraph, RenderResourcesNode,
+        WindowSwapChainNode, WindowTextureNode,
+    },
     shader::Shader,
+    texture::{Extent3d, TextureDescriptor, TextureDimension, TextureFormat, TextureUsage},
 };
 use bevy_transform::prelude::GlobalTransform;
+use bevy_utils::tracing::warn;
+use bevy_window::WindowId;
 
-pub const MAX_POINT_LIGHTS: usize = 10;
 pub const MAX_DIRECTIONAL_LIGHTS: usize = 1;
 pub(crate) fn add_pbr_graph(world: &mut World) {
     {
@@ -40,10 +89,7 @@ pub(crate) fn add_pbr_graph(world: &mut World) {
             AssetRenderResourcesNode::<StandardMaterial>::new(true),
         );
 
-        graph.add_system_node(
-            node::LIGHTS,
-            LightsNode::new(MAX_POINT_LIGHTS, MAX_DIRECTIONAL_LIGHTS),
-        );
+        graph.add_system_node(node::LIGHTS, LightsNode::new(MAX_DIRECTIONAL_LIGHTS));
 
         // TODO: replace these with "autowire" groups
         graph
@@ -55,10 +101,214 @@ pub(crate) fn add_pbr_graph(world: &mut World) {
         graph
             .add_node_edge(node::LIGHTS, base::node::MAIN_PASS)
             .unwrap();
+        graph.add_system_node(
+            node::LIGHT_LAYERS,
+            RenderResourcesNode::<LightLayers>::new(true),
+        );
+        graph
+            .add_node_edge(node::LIGHT_LAYERS, base::node::MAIN_PASS)
+            .unwrap();
+
+        graph.add_system_node(
+            node::FOG,
+            CameraUniformNode::new(uniform::CAMERA_FOG, fog_uniforms_system),
+        );
+        graph
+            .add_node_edge(node::FOG, base::node::MAIN_PASS)
+            .unwrap();
+        graph.add_system_node(
+            node::CAMERA_LIGHTING,
+            CameraUniformNode::new(uniform::CAMERA_LIGHTING, camera_lighting_uniforms_system),
+        );
+        graph
+            .add_node_edge(node::CAMERA_LIGHTING, base::node::MAIN_PASS)
+            .unwrap();
+        graph.add_system_node(node::SKY, RenderResourcesNode::<Sky>::new(true));
+        graph
+            .add_node_edge(node::SKY, base::node::MAIN_PASS)
+            .unwrap();
     }
-    let pipeline = build_pbr_pipeline(&mut world.get_resource_mut::<Assets<Shader>>().unwrap());
+    let mut shaders = world.get_resource_mut::<Assets<Shader>>().unwrap();
+    let pipeline = build_pbr_pipeline(&mut shaders);
+    let decal_pipeline = build_decal_pipeline(&mut shaders);
+    let sky_pipeline = build_sky_pipeline(&mut shaders);
+    let motion_vector_pipeline = build_motion_vector_pipeline(&mut shaders);
     let mut pipelines = world
         .get_resource_mut::<Assets<PipelineDescriptor>>()
         .unwrap();
     pipelines.set_untracked(PBR_PIPELINE_HANDLE, pipeline);
+    pipelines.set_untracked(DECAL_PIPELINE_HANDLE, decal_pipeline);
+    pipelines.set_untracked(SKY_PIPELINE_HANDLE, sky_pipeline);
+    pipelines.set_untracked(MOTION_VECTOR_PIPELINE_HANDLE, motion_vector_pipeline);
+    let mut meshes = world.get_resource_mut::<Assets<Mesh>>().unwrap();
+    meshes.set_untracked(DECAL_MESH_HANDLE, Mesh::from(shape::Cube { size: 1.0 }));
+    meshes.set_untracked(
+        SKY_MESH_HANDLE,
+        Mesh::from(shape::Icosphere {
+            radius: 1.0,
+            subdivisions: 3,
+        }),
+    );
+    add_decal_pass(world);
+    add_motion_vector_pass(world);
+}
+
+/// Adds the decal pass, which draws [`Decal`]s over the output of the main pass for the 3d
+/// camera.
+fn add_decal_pass(world: &mut World) {
+    let world = world.cell();
+    let mut graph = world.get_resource_mut::<RenderGraph>().unwrap();
+    let msaa = world.get_resource::<Msaa>().unwrap();
+    if graph.get_node_id(base::node::MAIN_PASS).is_err()
+        || graph.get_node_id(base::node::MAIN_DEPTH_TEXTURE).is_err()
+        || graph.get_node_id(base::node::CAMERA_3D).is_err()
+    {
+        return;
+    }
+    if msaa.samples > 1 {
+        warn!("Decals are not supported with multisampling, they will not be drawn.");
+        return;
+    }
+
+    graph.add_system_node(node::DECAL, RenderResourcesNode::<Decal>::new(true));
+    let mut decal_pass_node = PassNode::<&DecalPass>::new(PassDescriptor {
+        color_attachments: vec![RenderPassColorAttachment {
+            attachment: TextureAttachment::Input("color_attachment".to_string()),
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Load,
+                store: true,
+            },
+        }],
+        depth_stencil_attachment: None,
+        sample_count: 1,
+    });
+    decal_pass_node.add_camera(base::camera::CAMERA_3D);
+    graph.add_node(node::DECAL_PASS, decal_pass_node);
+
+    graph
+        .add_slot_edge(
+            base::node::PRIMARY_SWAP_CHAIN,
+            WindowSwapChainNode::OUT_TEXTURE,
+            node::DECAL_PASS,
+            "color_attachment",
+        )
+        .unwrap();
+    for input in [
+        base::node::MAIN_PASS,
+        base::node::MAIN_DEPTH_TEXTURE,
+        base::node::CAMERA_3D,
+        node::TRANSFORM,
+        node::DECAL,
+    ]
+    .iter()
+    {
+        graph.add_node_edge(*input, node::DECAL_PASS).unwrap();
+    }
+}
+
+/// Adds the motion vector prepass, which draws the screen space motion of the
+/// [`MotionVectors`](crate::MotionVectors) entities seen by the 3d camera to the texture of the
+/// [`node::MOTION_VECTOR_TEXTURE`] node, before the main pass.
+fn add_motion_vector_pass(world: &mut World) {
+    let mut graph = world.get_resource_mut::<RenderGraph>().unwrap();
+    if graph.get_node_id(base::node::MAIN_PASS).is_err()
+        || graph.get_node_id(base::node::CAMERA_3D).is_err()
+    {
+        return;
+    }
+
+    graph.add_system_node(
+        node::PREVIOUS_TRANSFORM,
+        RenderResourcesNode::<PreviousGlobalTransform>::new(true),
+    );
+    graph.add_system_node(
+        node::PREVIOUS_VIEW_PROJ,
+        CameraUniformNode::new(
+            uniform::CAMERA_PREVIOUS_VIEW_PROJ,
+            previous_view_proj_uniforms_system,
+        ),
+    );
+    for (name, format) in [
+        (node::MOTION_VECTOR_TEXTURE, MOTION_VECTOR_FORMAT),
+        (
+            node::MOTION_VECTOR_DEPTH_TEXTURE,
+            TextureFormat::Depth32Float,
+        ),
+    ]
+    .iter()
+    {
+        graph.add_node(
+            *name,
+            WindowTextureNode::new(
+                WindowId::primary(),
+                TextureDescriptor {
+                    size: Extent3d {
+                        depth_or_array_layers: 1,
+                        width: 1,
+                        height: 1,
+                    },
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format: *format,
+                    usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::SAMPLED,
+                },
+            ),
+        );
+    }
+
+    let mut motion_vector_pass_node = PassNode::<&MotionVectorPass>::new(PassDescriptor {
+        color_attachments: vec![RenderPassColorAttachment {
+            attachment: TextureAttachment::Input("color_attachment".to_string()),
+            resolve_target: None,
+            ops: Operations {
+                load: LoadOp::Clear(Color::NONE),
+                store: true,
+            },
+        }],
+        depth_stencil_attachment: Some(RenderPassDepthStencilAttachment {
+            attachment: TextureAttachment::Input("depth".to_string()),
+            depth_ops: Some(Operations {
+                load: LoadOp::Clear(1.0),
+                store: true,
+            }),
+            stencil_ops: None,
+        }),
+        sample_count: 1,
+    });
+    motion_vector_pass_node.add_camera(base::camera::CAMERA_3D);
+    graph.add_node(node::MOTION_VECTOR_PASS, motion_vector_pass_node);
+
+    graph
+        .add_slot_edge(
+            node::MOTION_VECTOR_TEXTURE,
+            WindowTextureNode::OUT_TEXTURE,
+            node::MOTION_VECTOR_PASS,
+            "color_attachment",
+        )
+        .unwrap();
+    graph
+        .add_slot_edge(
+            node::MOTION_VECTOR_DEPTH_TEXTURE,
+            WindowTextureNode::OUT_TEXTURE,
+            node::MOTION_VECTOR_PASS,
+            "depth",
+        )
+        .unwrap();
+    for input in [
+        base::node::CAMERA_3D,
+        node::TRANSFORM,
+        node::PREVIOUS_TRANSFORM,
+        node::PREVIOUS_VIEW_PROJ,
+    ]
+    .iter()
+    {
+        graph
+            .add_node_edge(*input, node::MOTION_VECTOR_PASS)
+            .unwrap();
+    }
+    graph
+        .add_node_edge(node::MOTION_VECTOR_PASS, base::node::MAIN_PASS)
+        .unwrap();
 }
@@ -0,0 +1,72 @@
+use crate::render_graph::{node, uniform};
+use bevy_ecs::{
+    entity::Entity,
+    system::{Local, Query, Res, ResMut},
+};
+use bevy_math::Mat4;
+use bevy_render::{
+    camera::Camera,
+    render_graph::{RenderGraph, WindowTextureNode},
+    renderer::{RenderResourceBinding, RenderResourceBindings, RenderResourceContext, SamplerId},
+    texture::{FilterMode, SamplerDescriptor},
+};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+
+/// The view projection of every camera in the previous frame, written to the camera bindings as
+/// [`uniform::CAMERA_PREVIOUS_VIEW_PROJ`] by a
+/// [`CameraUniformNode`](crate::render_graph::CameraUniformNode).
+pub(crate) fn previous_view_proj_uniforms_system(
+    mut view_projs: Local<HashMap<Entity, Mat4>>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+) -> HashMap<Entity, Mat4> {
+    let mut previous_view_projs = HashMap::default();
+    let mut current_view_projs = HashMap::default();
+    for (entity, camera, global_transform) in cameras.iter() {
+        let view_proj = camera.projection_matrix * global_transform.compute_matrix().inverse();
+        // cameras rendered for the first time didn't move
+        let previous_view_proj = view_projs.get(&entity).copied().unwrap_or(view_proj);
+        previous_view_projs.insert(entity, previous_view_proj);
+        current_view_projs.insert(entity, view_proj);
+    }
+    *view_projs = current_view_projs;
+    previous_view_projs
+}
+
+/// Binds the output of the motion vector prepass as [`uniform::MOTION_VECTORS`], so that
+/// post-processing passes can sample it.
+///
+/// The texture is created when the window is created or resized, so it is bound from the next
+/// frame on.
+pub fn motion_vectors_binding_system(
+    mut sampler: Local<Option<SamplerId>>,
+    render_graph: Res<RenderGraph>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+    mut render_resource_bindings: ResMut<RenderResourceBindings>,
+) {
+    let motion_vector_texture = render_graph
+        .get_node_state(node::MOTION_VECTOR_TEXTURE)
+        .ok()
+        .and_then(|node| node.output_slots.get(WindowTextureNode::OUT_TEXTURE))
+        .and_then(|resource| resource.get_texture());
+    let motion_vector_texture = match motion_vector_texture {
+        Some(motion_vector_texture) => motion_vector_texture,
+        None => return,
+    };
+
+    let sampler = *sampler.get_or_insert_with(|| {
+        render_resource_context.create_sampler(&SamplerDescriptor {
+            mag_filter: FilterMode::Nearest,
+            min_filter: FilterMode::Nearest,
+            ..Default::default()
+        })
+    });
+    render_resource_bindings.set(
+        uniform::MOTION_VECTORS,
+        RenderResourceBinding::Texture(motion_vector_texture),
+    );
+    render_resource_bindings.set(
+        &format!("{}_sampler", uniform::MOTION_VECTORS),
+        RenderResourceBinding::Sampler(sampler),
+    );
+}
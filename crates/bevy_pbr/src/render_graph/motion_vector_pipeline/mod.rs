@@ -0,0 +1,52 @@
+use bevy_asset::{Assets, HandleUntyped};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    pipeline::{
+        ColorTargetState, ColorWrite, CompareFunction, DepthBiasState, DepthStencilState,
+        PipelineDescriptor, StencilFaceState, StencilState,
+    },
+    shader::{Shader, ShaderStage, ShaderStages},
+    texture::TextureFormat,
+};
+
+pub const MOTION_VECTOR_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 4732158932157803265);
+
+/// The format of the motion vector texture, holding the screen space motion in UV units
+pub const MOTION_VECTOR_FORMAT: TextureFormat = TextureFormat::Rg16Float;
+
+pub(crate) fn build_motion_vector_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    PipelineDescriptor {
+        depth_stencil: Some(DepthStencilState {
+            format: TextureFormat::Depth32Float,
+            depth_write_enabled: true,
+            depth_compare: CompareFunction::Less,
+            stencil: StencilState {
+                front: StencilFaceState::IGNORE,
+                back: StencilFaceState::IGNORE,
+                read_mask: 0,
+                write_mask: 0,
+            },
+            bias: DepthBiasState {
+                constant: 0,
+                slope_scale: 0.0,
+                clamp: 0.0,
+            },
+        }),
+        color_target_states: vec![ColorTargetState {
+            format: MOTION_VECTOR_FORMAT,
+            blend: None,
+            write_mask: ColorWrite::ALL,
+        }],
+        ..PipelineDescriptor::new(ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(
+                ShaderStage::Vertex,
+                include_str!("motion_vector.vert"),
+            )),
+            fragment: Some(shaders.add(Shader::from_glsl(
+                ShaderStage::Fragment,
+                include_str!("motion_vector.frag"),
+            ))),
+        })
+    }
+}
@@ -0,0 +1,26 @@
+#version 450
+
+layout(location = 0) in vec3 Vertex_Position;
+
+layout(location = 0) out vec4 v_ClipPosition;
+layout(location = 1) out vec4 v_PreviousClipPosition;
+
+layout(set = 0, binding = 0) uniform CameraViewProj {
+    mat4 ViewProj;
+};
+layout(set = 0, binding = 1) uniform CameraPreviousViewProj {
+    mat4 PreviousViewProj;
+};
+
+layout(set = 1, binding = 0) uniform Transform {
+    mat4 Model;
+};
+layout(set = 1, binding = 1) uniform PreviousGlobalTransform_model {
+    mat4 PreviousModel;
+};
+
+void main() {
+    v_ClipPosition = ViewProj * Model * vec4(Vertex_Position, 1.0);
+    v_PreviousClipPosition = PreviousViewProj * PreviousModel * vec4(Vertex_Position, 1.0);
+    gl_Position = v_ClipPosition;
+}
@@ -35,17 +35,18 @@
 // The above integration needs to be approximated.
 
 // reflects the constants defined bevy_pbr/src/render_graph/mod.rs
-const int MAX_POINT_LIGHTS = 10;
 const int MAX_DIRECTIONAL_LIGHTS = 1;
 
 struct PointLight {
     vec4 pos;
     vec4 color;
-    vec4 lightParams;
+    vec2 lightParams; // x = inverse square range, y = radius
+    uint layers;
 };
  
 struct DirectionalLight {
-    vec4 direction;
+    vec3 direction;
+    uint layers;
     vec4 color;
 };
 
@@ -53,10 +54,14 @@ layout(location = 0) in vec3 v_WorldPosition;
 layout(location = 1) in vec3 v_WorldNormal;
 layout(location = 2) in vec2 v_Uv;
 
-#ifdef STANDARDMATERIAL_NORMAL_MAP
+#if defined(STANDARDMATERIAL_NORMAL_MAP) || defined(STANDARDMATERIAL_HEIGHT_MAP)
 layout(location = 3) in vec4 v_WorldTangent;
 #endif
 
+#ifdef VERTEX_COLORS
+layout(location = 4) in vec4 v_Color;
+#endif
+
 layout(location = 0) out vec4 o_Target;
 
 layout(set = 0, binding = 0) uniform CameraViewProj {
@@ -65,13 +70,43 @@ layout(set = 0, binding = 0) uniform CameraViewProj {
 layout(std140, set = 0, binding = 1) uniform CameraPosition {
     vec4 CameraPos;
 };
+layout(std140, set = 0, binding = 2) uniform CameraFog {
+    vec4 FogColor;
+    vec4 FogDirectionalLight; // rgb = color, w = exponent
+    vec4 FogFalloff; // x = mode (0 = no fog, 1 = linear, 2 = exponential, 3 = exponential squared)
+    vec4 FogHeight; // x = enabled, y = base, z = falloff
+};
+layout(std140, set = 0, binding = 3) uniform CameraClusters {
+    uvec4 ClusterDimensions; // x = columns, y = rows, z = slices
+    vec4 ClusterViewZ; // the row of the view matrix giving the view space z
+    vec4 ClusterSlicing; // x = end of the first slice, y = scale and z = bias of the log of the depth
+};
+layout(std430, set = 0, binding = 4) readonly buffer CameraClusterLights {
+    uvec2 ClusterLights[]; // x = offset in ClusterLightIndices, y = light count
+};
+layout(std430, set = 0, binding = 5) readonly buffer CameraClusterLightIndices {
+    uint ClusterLightIndices[];
+};
+layout(std140, set = 0, binding = 6) uniform CameraLighting {
+    vec4 Exposure; // x = scale of all the lighting, y = scale of the directional lights
+    vec4 AmbientColor;
+};
 
 layout(std140, set = 1, binding = 0) uniform Lights {
-    vec4 AmbientColor;
     uvec4 NumLights; // x = point lights, y = directional lights
-    PointLight PointLights[MAX_POINT_LIGHTS];
     DirectionalLight DirectionalLights[MAX_DIRECTIONAL_LIGHTS];
 };
+layout(std430, set = 1, binding = 1) readonly buffer PointLights {
+    PointLight PointLightList[];
+};
+
+#ifdef LIGHT_LAYERS
+layout(set = 2, binding = 1) uniform LightLayers {
+    uint MeshLightLayers;
+};
+#else
+const uint MeshLightLayers = 1u;
+#endif
 
 layout(set = 3, binding = 0) uniform StandardMaterial_base_color {
     vec4 base_color;
@@ -125,6 +160,20 @@ layout(set = 3,
        binding = 14) uniform sampler StandardMaterial_emissive_texture_sampler;
 #    endif
 
+#    ifdef STANDARDMATERIAL_HEIGHT_MAP
+layout(set = 3, binding = 15) uniform texture2D StandardMaterial_height_map;
+layout(set = 3,
+       binding = 16) uniform sampler StandardMaterial_height_map_sampler;
+
+layout(set = 3, binding = 17) uniform StandardMaterial_parallax_depth_scale {
+    float parallax_depth_scale;
+};
+
+layout(set = 3, binding = 18) uniform StandardMaterial_max_parallax_layer_count {
+    float max_parallax_layer_count;
+};
+#    endif
+
 #    define saturate(x) clamp(x, 0.0, 1.0)
 const float PI = 3.141592653589793;
 
@@ -349,18 +398,131 @@ vec3 dir_light(DirectionalLight light, float roughness, float NdotV, vec3 normal
 
 #endif
 
+// the index of the cluster containing a position, see bevy_pbr/src/cluster.rs
+uint cluster_index(vec3 world_position) {
+    vec4 clip_position = ViewProj * vec4(world_position, 1.0);
+    vec2 ndc = clip_position.xy / clip_position.w;
+    // rows are counted from the top of the screen
+    uvec2 tile = uvec2(clamp(
+        floor(vec2(ndc.x + 1.0, 1.0 - ndc.y) * 0.5 * vec2(ClusterDimensions.xy)),
+        vec2(0.0),
+        vec2(ClusterDimensions.xy - 1u)));
+
+    float depth = -dot(ClusterViewZ, vec4(world_position, 1.0));
+    uint slice = 0u;
+    if (depth >= ClusterSlicing.x) {
+        float log_slice = floor(log(depth) * ClusterSlicing.y + ClusterSlicing.z);
+        slice = min(1u + uint(max(log_slice, 0.0)), ClusterDimensions.z - 1u);
+    }
+    return tile.x + ClusterDimensions.x * (tile.y + ClusterDimensions.y * slice);
+}
+
+vec3 apply_fog(vec3 color, vec3 world_position) {
+    int mode = int(FogFalloff.x);
+    if (mode == 0) {
+        return color;
+    }
+
+    vec3 to_fragment = world_position - CameraPos.xyz;
+    float distance = length(to_fragment);
+    float amount;
+    if (mode == 1) {
+        amount = clamp((distance - FogFalloff.y) / max(FogFalloff.z - FogFalloff.y, 1e-4), 0.0, 1.0);
+    } else if (mode == 2) {
+        amount = 1.0 - exp(-FogFalloff.y * distance);
+    } else {
+        float density_distance = FogFalloff.y * distance;
+        amount = 1.0 - exp(-density_distance * density_distance);
+    }
+    if (FogHeight.x != 0.0) {
+        amount *= exp(-FogHeight.z * max(world_position.y - FogHeight.y, 0.0));
+    }
+
+    // sunlight scattering towards the camera
+    vec3 fog_color = FogColor.rgb;
+    if (NumLights.y > 0u) {
+        vec3 view_direction = to_fragment / max(distance, 1e-4);
+        float scattering = max(dot(view_direction, DirectionalLights[0].direction.xyz), 0.0);
+        fog_color += FogDirectionalLight.rgb * pow(scattering, FogDirectionalLight.w);
+    }
+    return mix(color, fog_color, amount * FogColor.a);
+}
+
+vec3 calculate_view() {
+    if (ViewProj[3][3] != 1.0) { // If the projection is not orthographic
+        return normalize(CameraPos.xyz - v_WorldPosition.xyz); // Only valid for a perpective projection
+    } else {
+        return normalize(vec3(-ViewProj[0][2],-ViewProj[1][2],-ViewProj[2][2])); // Ortho view vec
+    }
+}
+
+#if defined(STANDARDMATERIAL_HEIGHT_MAP) && !defined(STANDARDMATERIAL_UNLIT)
+// the height map is white at the top of the surface
+float sample_depth(vec2 uv, vec2 uv_dx, vec2 uv_dy) {
+    return 1.0 - textureGrad(sampler2D(StandardMaterial_height_map, StandardMaterial_height_map_sampler), uv, uv_dx, uv_dy).r;
+}
+
+// Parallax occlusion mapping: steps along the view ray through layers of the height map until it
+// is below the surface, then interpolates between the depths of the last two layers.
+// V_tangent is the view vector in tangent space
+vec2 parallaxed_uv(vec2 uv, vec3 V_tangent) {
+    // explicit gradients, as the loop below is non-uniform control flow
+    vec2 uv_dx = dFdx(uv);
+    vec2 uv_dy = dFdy(uv);
+
+    // grazing angles need more layers
+    float layer_count = max(mix(max_parallax_layer_count, max_parallax_layer_count * 0.25, abs(V_tangent.z)), 1.0);
+    float layer_depth = 1.0 / layer_count;
+    // the v texture coordinate goes down along the bitangent
+    vec2 delta_uv = parallax_depth_scale * layer_depth * V_tangent.xy * vec2(1.0, -1.0) / max(V_tangent.z, 1e-4);
+
+    float current_layer_depth = 0.0;
+    float texture_depth = sample_depth(uv, uv_dx, uv_dy);
+    for (int i = 0; i < int(layer_count) && current_layer_depth < texture_depth; ++i) {
+        uv -= delta_uv;
+        current_layer_depth += layer_depth;
+        texture_depth = sample_depth(uv, uv_dx, uv_dy);
+    }
+
+    vec2 previous_uv = uv + delta_uv;
+    float next_depth = texture_depth - current_layer_depth;
+    float previous_depth = sample_depth(previous_uv, uv_dx, uv_dy) - current_layer_depth + layer_depth;
+    float weight = next_depth / (next_depth - previous_depth);
+    return mix(uv, previous_uv, weight);
+}
+#endif
+
 void main() {
+    vec2 uv = v_Uv;
+#if defined(STANDARDMATERIAL_HEIGHT_MAP) && !defined(STANDARDMATERIAL_UNLIT)
+    {
+        vec3 N = normalize(v_WorldNormal);
+        vec3 T = normalize(v_WorldTangent.xyz);
+        vec3 B = cross(N, T) * v_WorldTangent.w;
+#    ifdef STANDARDMATERIAL_DOUBLE_SIDED
+        N = gl_FrontFacing ? N : -N;
+        T = gl_FrontFacing ? T : -T;
+        B = gl_FrontFacing ? B : -B;
+#    endif
+        vec3 V = calculate_view();
+        uv = parallaxed_uv(uv, vec3(dot(V, T), dot(V, B), dot(V, N)));
+    }
+#endif
+
     vec4 output_color = base_color;
 #ifdef STANDARDMATERIAL_BASE_COLOR_TEXTURE
     output_color *= texture(sampler2D(StandardMaterial_base_color_texture,
                                       StandardMaterial_base_color_texture_sampler),
-                            v_Uv);
+                            uv);
+#endif
+#ifdef VERTEX_COLORS
+    output_color *= v_Color;
 #endif
 
 #ifndef STANDARDMATERIAL_UNLIT
     // calculate non-linear roughness from linear perceptualRoughness
 #    ifdef STANDARDMATERIAL_METALLIC_ROUGHNESS_TEXTURE
-    vec4 metallic_roughness = texture(sampler2D(StandardMaterial_metallic_roughness_texture, StandardMaterial_metallic_roughness_texture_sampler), v_Uv);
+    vec4 metallic_roughness = texture(sampler2D(StandardMaterial_metallic_roughness_texture, StandardMaterial_metallic_roughness_texture_sampler), uv);
     // Sampling from GLTF standard channels for now
     float metallic = metallic * metallic_roughness.b;
     float perceptual_roughness = perceptual_roughness * metallic_roughness.g;
@@ -385,11 +547,11 @@ void main() {
 
 #    ifdef STANDARDMATERIAL_NORMAL_MAP
     mat3 TBN = mat3(T, B, N);
-    N = TBN * normalize(texture(sampler2D(StandardMaterial_normal_map, StandardMaterial_normal_map_sampler), v_Uv).rgb * 2.0 - 1.0);
+    N = TBN * normalize(texture(sampler2D(StandardMaterial_normal_map, StandardMaterial_normal_map_sampler), uv).rgb * 2.0 - 1.0);
 #    endif
 
 #    ifdef STANDARDMATERIAL_OCCLUSION_TEXTURE
-    float occlusion = texture(sampler2D(StandardMaterial_occlusion_texture, StandardMaterial_occlusion_texture_sampler), v_Uv).r;
+    float occlusion = texture(sampler2D(StandardMaterial_occlusion_texture, StandardMaterial_occlusion_texture_sampler), uv).r;
 #    else
     float occlusion = 1.0;
 #    endif
@@ -397,15 +559,10 @@ void main() {
 #    ifdef STANDARDMATERIAL_EMISSIVE_TEXTURE
     vec4 emissive = emissive;
     // TODO use .a for exposure compensation in HDR
-    emissive.rgb *= texture(sampler2D(StandardMaterial_emissive_texture, StandardMaterial_emissive_texture_sampler), v_Uv).rgb;
+    emissive.rgb *= texture(sampler2D(StandardMaterial_emissive_texture, StandardMaterial_emissive_texture_sampler), uv).rgb;
 #    endif
 
-    vec3 V;
-    if (ViewProj[3][3] != 1.0) { // If the projection is not orthographic
-        V = normalize(CameraPos.xyz - v_WorldPosition.xyz); // Only valid for a perpective projection
-    } else {
-        V = normalize(vec3(-ViewProj[0][2],-ViewProj[1][2],-ViewProj[2][2])); // Ortho view vec
-    }
+    vec3 V = calculate_view();
     // Neubelt and Pettineo 2013, "Crafting a Next-gen Material Pipeline for The Order: 1886"
     float NdotV = max(dot(N, V), 1e-4);
 
@@ -420,12 +577,22 @@ void main() {
 
     // accumulate color
     vec3 light_accum = vec3(0.0);
-    for (int i = 0; i < int(NumLights.x) && i < MAX_POINT_LIGHTS; ++i) {
-        light_accum += point_light(PointLights[i], roughness, NdotV, N, V, R, F0, diffuseColor);
+    uvec2 cluster = ClusterLights[cluster_index(v_WorldPosition)];
+    for (uint i = cluster.x; i < cluster.x + cluster.y; ++i) {
+        PointLight light = PointLightList[ClusterLightIndices[i]];
+        if ((light.layers & MeshLightLayers) == 0u) {
+            continue;
+        }
+        light_accum += point_light(light, roughness, NdotV, N, V, R, F0, diffuseColor);
     }
+    vec3 directional_light_accum = vec3(0.0);
     for (int i = 0; i < int(NumLights.y) && i < MAX_DIRECTIONAL_LIGHTS; ++i) {
-        light_accum += dir_light(DirectionalLights[i], roughness, NdotV, N, V, R, F0, diffuseColor);
+        if ((DirectionalLights[i].layers & MeshLightLayers) == 0u) {
+            continue;
+        }
+        directional_light_accum += dir_light(DirectionalLights[i], roughness, NdotV, N, V, R, F0, diffuseColor);
     }
+    light_accum += directional_light_accum * Exposure.y;
 
     vec3 diffuse_ambient = EnvBRDFApprox(diffuseColor, 1.0, NdotV);
     vec3 specular_ambient = EnvBRDFApprox(F0, perceptual_roughness, NdotV);
@@ -433,6 +600,7 @@ void main() {
     output_color.rgb = light_accum;
     output_color.rgb += (diffuse_ambient + specular_ambient) * AmbientColor.xyz * occlusion;
     output_color.rgb += emissive.rgb * output_color.a;
+    output_color.rgb *= Exposure.x;
 
     // tone_mapping
     output_color.rgb = reinhard_luminance(output_color.rgb);
@@ -441,5 +609,6 @@ void main() {
     // output_color.rgb = pow(output_color.rgb, vec3(1.0 / 2.2));
 #endif
 
+    output_color.rgb = apply_fog(output_color.rgb, v_WorldPosition);
     o_Target = output_color;
 }
@@ -4,10 +4,14 @@ layout(location = 0) in vec3 Vertex_Position;
 layout(location = 1) in vec3 Vertex_Normal;
 layout(location = 2) in vec2 Vertex_Uv;
 
-#ifdef STANDARDMATERIAL_NORMAL_MAP
+#if defined(STANDARDMATERIAL_NORMAL_MAP) || defined(STANDARDMATERIAL_HEIGHT_MAP)
 layout(location = 3) in vec4 Vertex_Tangent;
 #endif
 
+#ifdef VERTEX_COLORS
+layout(location = 4) in vec4 Vertex_Color;
+#endif
+
 layout(location = 0) out vec3 v_WorldPosition;
 layout(location = 1) out vec3 v_WorldNormal;
 layout(location = 2) out vec2 v_Uv;
@@ -16,10 +20,14 @@ layout(set = 0, binding = 0) uniform CameraViewProj {
     mat4 ViewProj;
 };
 
-#ifdef STANDARDMATERIAL_NORMAL_MAP
+#if defined(STANDARDMATERIAL_NORMAL_MAP) || defined(STANDARDMATERIAL_HEIGHT_MAP)
 layout(location = 3) out vec4 v_WorldTangent;
 #endif
 
+#ifdef VERTEX_COLORS
+layout(location = 4) out vec4 v_Color;
+#endif
+
 layout(set = 2, binding = 0) uniform Transform {
     mat4 Model;
 };
@@ -29,8 +37,11 @@ void main() {
     v_WorldPosition = world_position.xyz;
     v_WorldNormal = mat3(Model) * Vertex_Normal;
     v_Uv = Vertex_Uv;
-#ifdef STANDARDMATERIAL_NORMAL_MAP
+#if defined(STANDARDMATERIAL_NORMAL_MAP) || defined(STANDARDMATERIAL_HEIGHT_MAP)
     v_WorldTangent = vec4(mat3(Model) * Vertex_Tangent.xyz, Vertex_Tangent.w);
+#endif
+#ifdef VERTEX_COLORS
+    v_Color = Vertex_Color;
 #endif
     gl_Position = ViewProj * world_position;
 }
@@ -0,0 +1,66 @@
+use bevy_asset::{Assets, HandleUntyped};
+use bevy_reflect::TypeUuid;
+use bevy_render::{
+    mesh::Mesh,
+    pipeline::{
+        ColorTargetState, ColorWrite, CompareFunction, DepthBiasState, DepthStencilState,
+        FrontFace, PipelineDescriptor, PolygonMode, PrimitiveState, PrimitiveTopology,
+        StencilFaceState, StencilState,
+    },
+    shader::{Shader, ShaderStage, ShaderStages},
+    texture::TextureFormat,
+};
+
+pub const SKY_PIPELINE_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(PipelineDescriptor::TYPE_UUID, 4327011384570262941);
+
+/// The unit sphere drawn around the camera for the sky
+pub const SKY_MESH_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Mesh::TYPE_UUID, 15846230287714470926);
+
+pub(crate) fn build_sky_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor {
+    PipelineDescriptor {
+        // the sky is drawn on the far plane, only where no other mesh was drawn
+        depth_stencil: Some(DepthStencilState {
+            format: TextureFormat::Depth32Float,
+            depth_write_enabled: false,
+            depth_compare: CompareFunction::LessEqual,
+            stencil: StencilState {
+                front: StencilFaceState::IGNORE,
+                back: StencilFaceState::IGNORE,
+                read_mask: 0,
+                write_mask: 0,
+            },
+            bias: DepthBiasState {
+                constant: 0,
+                slope_scale: 0.0,
+                clamp: 0.0,
+            },
+        }),
+        // the camera is inside the sphere
+        primitive: PrimitiveState {
+            topology: PrimitiveTopology::TriangleList,
+            strip_index_format: None,
+            front_face: FrontFace::Ccw,
+            cull_mode: None,
+            polygon_mode: PolygonMode::Fill,
+            clamp_depth: false,
+            conservative: false,
+        },
+        color_target_states: vec![ColorTargetState {
+            format: TextureFormat::default(),
+            blend: None,
+            write_mask: ColorWrite::ALL,
+        }],
+        ..PipelineDescriptor::new(ShaderStages {
+            vertex: shaders.add(Shader::from_glsl(
+                ShaderStage::Vertex,
+                include_str!("sky.vert"),
+            )),
+            fragment: Some(shaders.add(Shader::from_glsl(
+                ShaderStage::Fragment,
+                include_str!("sky.frag"),
+            ))),
+        })
+    }
+}
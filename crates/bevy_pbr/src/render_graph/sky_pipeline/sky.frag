@@ -0,0 +1,58 @@
+#version 450
+
+// reflects the constants defined bevy_pbr/src/render_graph/mod.rs
+const int MAX_DIRECTIONAL_LIGHTS = 1;
+
+struct DirectionalLight {
+    vec3 direction;
+    uint layers;
+    vec4 color;
+};
+
+layout(location = 0) in vec3 v_Direction;
+
+layout(location = 0) out vec4 o_Target;
+
+layout(std140, set = 1, binding = 0) uniform Lights {
+    uvec4 NumLights; // x = point lights, y = directional lights
+    DirectionalLight DirectionalLights[MAX_DIRECTIONAL_LIGHTS];
+};
+
+layout(set = 2, binding = 0) uniform Sky_zenith_color {
+    vec4 zenith_color;
+};
+layout(set = 2, binding = 1) uniform Sky_horizon_color {
+    vec4 horizon_color;
+};
+layout(set = 2, binding = 2) uniform Sky_ground_color {
+    vec4 ground_color;
+};
+layout(set = 2, binding = 3) uniform Sky_sun_color {
+    vec4 sun_color;
+};
+layout(set = 2, binding = 4) uniform Sky_sun_size {
+    float sun_size;
+};
+
+void main() {
+    vec3 direction = normalize(v_Direction);
+
+    // the sky brightens towards the horizon, where light travels through more atmosphere
+    vec3 color;
+    if (direction.y >= 0.0) {
+        color = mix(horizon_color.rgb, zenith_color.rgb, pow(direction.y, 0.5));
+    } else {
+        color = mix(horizon_color.rgb, ground_color.rgb, pow(-direction.y, 0.25));
+    }
+
+#ifdef SKY_SUN
+    if (NumLights.y > 0u) {
+        float cos_angle = dot(direction, DirectionalLights[0].direction.xyz);
+        float disc = smoothstep(cos(sun_size), cos(sun_size * 0.8), cos_angle);
+        float glow = pow(max(cos_angle, 0.0), 64.0) * 0.5;
+        color = mix(color, sun_color.rgb, clamp(disc + glow, 0.0, 1.0));
+    }
+#endif
+
+    o_Target = vec4(color, 1.0);
+}
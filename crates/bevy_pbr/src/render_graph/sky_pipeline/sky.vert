@@ -0,0 +1,18 @@
+#version 450
+
+layout(location = 0) in vec3 Vertex_Position;
+
+layout(location = 0) out vec3 v_Direction;
+
+layout(set = 0, binding = 0) uniform CameraViewProj {
+    mat4 ViewProj;
+};
+layout(std140, set = 0, binding = 1) uniform CameraPosition {
+    vec4 CameraPos;
+};
+
+void main() {
+    v_Direction = Vertex_Position;
+    // centered on the camera and projected onto the far plane, behind every other mesh
+    gl_Position = (ViewProj * vec4(CameraPos.xyz + Vertex_Position, 1.0)).xyww;
+}
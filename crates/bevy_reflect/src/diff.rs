@@ -0,0 +1,330 @@
+use crate::{Reflect, ReflectMut, ReflectRef};
+use thiserror::Error;
+
+/// A structured patch between two reflected values, containing only what changed.
+///
+/// Diffs are created with [`Reflect::diff`] and applied with [`Reflect::apply_diff`].
+#[derive(Debug)]
+pub enum Diff {
+    /// The values are equal.
+    Unchanged,
+    /// The value changed as a whole, either because it isn't a struct, tuple, list or map, or
+    /// because its type or shape changed. Values that don't support
+    /// [`Reflect::reflect_partial_eq`] are always replaced.
+    Replaced(Box<dyn Reflect>),
+    /// The changed fields of a struct.
+    Struct(Vec<(String, Diff)>),
+    /// The changed fields of a tuple struct.
+    TupleStruct(Vec<(usize, Diff)>),
+    /// The changed fields of a tuple.
+    Tuple(Vec<(usize, Diff)>),
+    /// The changed items of a list, and the items pushed at its end.
+    List {
+        changed: Vec<(usize, Diff)>,
+        pushed: Vec<Box<dyn Reflect>>,
+    },
+    /// The changed values of a map. Maps with added or removed keys are [`Diff::Replaced`].
+    Map(Vec<(Box<dyn Reflect>, Diff)>),
+}
+
+impl Diff {
+    pub fn is_unchanged(&self) -> bool {
+        matches!(self, Diff::Unchanged)
+    }
+}
+
+impl Clone for Diff {
+    fn clone(&self) -> Self {
+        match self {
+            Diff::Unchanged => Diff::Unchanged,
+            Diff::Replaced(value) => Diff::Replaced(value.clone_value()),
+            Diff::Struct(fields) => Diff::Struct(fields.clone()),
+            Diff::TupleStruct(fields) => Diff::TupleStruct(fields.clone()),
+            Diff::Tuple(fields) => Diff::Tuple(fields.clone()),
+            Diff::List { changed, pushed } => Diff::List {
+                changed: changed.clone(),
+                pushed: pushed.iter().map(|value| value.clone_value()).collect(),
+            },
+            Diff::Map(entries) => Diff::Map(
+                entries
+                    .iter()
+                    .map(|(key, diff)| (key.clone_value(), diff.clone()))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Error)]
+pub enum DiffError {
+    #[error("the value doesn't have a field named `{0}`")]
+    MissingField(String),
+    #[error("the value doesn't have a field or item at index {0}")]
+    MissingIndex(usize),
+    #[error("the value doesn't have a map entry with the given key")]
+    MissingKey,
+    #[error("the diff doesn't have the same shape as the value")]
+    MismatchedKind,
+}
+
+/// Collects the changed fields, returning `None` if a field is missing.
+fn diff_fields<'a, K>(
+    fields: impl Iterator<Item = (K, Option<&'a dyn Reflect>, &'a dyn Reflect)>,
+) -> Option<Vec<(K, Diff)>> {
+    let mut changed = Vec::new();
+    for (key, field, other_field) in fields {
+        let diff = field?.diff(other_field);
+        if !diff.is_unchanged() {
+            changed.push((key, diff));
+        }
+    }
+    Some(changed)
+}
+
+/// Returns the changes needed to turn the value with the given type name and contents into
+/// `other`.
+pub(crate) fn diff(type_name: &str, value: ReflectRef, other: &dyn Reflect) -> Diff {
+    if type_name != other.type_name() {
+        return Diff::Replaced(other.clone_value());
+    }
+    let diff = match (value, other.reflect_ref()) {
+        (ReflectRef::Struct(value), ReflectRef::Struct(other)) => {
+            diff_fields(other.iter_fields().enumerate().map(|(i, other_field)| {
+                let name = other.name_at(i).unwrap();
+                (name.to_string(), value.field(name), other_field)
+            }))
+            .map(Diff::Struct)
+        }
+        (ReflectRef::TupleStruct(value), ReflectRef::TupleStruct(other)) => diff_fields(
+            other
+                .iter_fields()
+                .enumerate()
+                .map(|(i, other_field)| (i, value.field(i), other_field)),
+        )
+        .map(Diff::TupleStruct),
+        (ReflectRef::Tuple(value), ReflectRef::Tuple(other)) => diff_fields(
+            other
+                .iter_fields()
+                .enumerate()
+                .map(|(i, other_field)| (i, value.field(i), other_field)),
+        )
+        .map(Diff::Tuple),
+        (ReflectRef::List(value), ReflectRef::List(other)) if value.len() <= other.len() => {
+            diff_fields(
+                other
+                    .iter()
+                    .take(value.len())
+                    .enumerate()
+                    .map(|(i, other_item)| (i, value.get(i), other_item)),
+            )
+            .map(|changed| Diff::List {
+                changed,
+                pushed: other
+                    .iter()
+                    .skip(value.len())
+                    .map(|item| item.clone_value())
+                    .collect(),
+            })
+        }
+        (ReflectRef::Map(value), ReflectRef::Map(other)) if value.len() == other.len() => {
+            diff_fields(
+                other
+                    .iter()
+                    .map(|(key, other_value)| (key.clone_value(), value.get(key), other_value)),
+            )
+            .map(Diff::Map)
+        }
+        (ReflectRef::Value(value), ReflectRef::Value(other)) => {
+            if value.reflect_partial_eq(other) == Some(true) {
+                Some(Diff::Unchanged)
+            } else {
+                None
+            }
+        }
+        _ => None,
+    };
+    match diff {
+        Some(Diff::Struct(fields)) if fields.is_empty() => Diff::Unchanged,
+        Some(Diff::TupleStruct(fields)) | Some(Diff::Tuple(fields)) if fields.is_empty() => {
+            Diff::Unchanged
+        }
+        Some(Diff::List { changed, pushed }) if changed.is_empty() && pushed.is_empty() => {
+            Diff::Unchanged
+        }
+        Some(Diff::Map(entries)) if entries.is_empty() => Diff::Unchanged,
+        Some(diff) => diff,
+        None => Diff::Replaced(other.clone_value()),
+    }
+}
+
+/// Applies a diff to the contents of a value, for the diffs that aren't [`Diff::Unchanged`] or
+/// [`Diff::Replaced`].
+pub(crate) fn apply_diff(value: ReflectMut, diff: &Diff) -> Result<(), DiffError> {
+    match (value, diff) {
+        (ReflectMut::Struct(value), Diff::Struct(fields)) => {
+            for (name, diff) in fields {
+                value
+                    .field_mut(name)
+                    .ok_or_else(|| DiffError::MissingField(name.clone()))?
+                    .apply_diff(diff)?;
+            }
+        }
+        (ReflectMut::TupleStruct(value), Diff::TupleStruct(fields)) => {
+            for (index, diff) in fields {
+                value
+                    .field_mut(*index)
+                    .ok_or(DiffError::MissingIndex(*index))?
+                    .apply_diff(diff)?;
+            }
+        }
+        (ReflectMut::Tuple(value), Diff::Tuple(fields)) => {
+            for (index, diff) in fields {
+                value
+                    .field_mut(*index)
+                    .ok_or(DiffError::MissingIndex(*index))?
+                    .apply_diff(diff)?;
+            }
+        }
+        (ReflectMut::List(value), Diff::List { changed, pushed }) => {
+            for (index, diff) in changed {
+                value
+                    .get_mut(*index)
+                    .ok_or(DiffError::MissingIndex(*index))?
+                    .apply_diff(diff)?;
+            }
+            for item in pushed {
+                value.push(item.clone_value());
+            }
+        }
+        (ReflectMut::Map(value), Diff::Map(entries)) => {
+            for (key, diff) in entries {
+                value
+                    .get_mut(&**key)
+                    .ok_or(DiffError::MissingKey)?
+                    .apply_diff(diff)?;
+            }
+        }
+        _ => return Err(DiffError::MismatchedKind),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Diff, DiffError};
+    use crate as bevy_reflect;
+    use crate::*;
+    use bevy_utils::HashMap;
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    #[reflect(PartialEq)]
+    struct Foo {
+        a: u32,
+        b: Bar,
+        c: Vec<u32>,
+        d: HashMap<usize, u32>,
+        e: (u8, String),
+    }
+
+    #[derive(Reflect, Clone, Debug, PartialEq)]
+    struct Bar(u32, bool);
+
+    fn foo() -> Foo {
+        let mut d = HashMap::default();
+        d.insert(1, 1);
+        d.insert(2, 2);
+        Foo {
+            a: 1,
+            b: Bar(1, false),
+            c: vec![1, 2],
+            d,
+            e: (1, "hello".to_string()),
+        }
+    }
+
+    #[test]
+    fn diff_unchanged() {
+        assert!(foo().diff(&foo()).is_unchanged());
+    }
+
+    #[test]
+    fn diff_floats() {
+        assert!(1.0f32.diff(&1.0f32).is_unchanged());
+        assert!(1.0f64.diff(&1.0f64).is_unchanged());
+        assert!(matches!(1.0f32.diff(&2.0f32), Diff::Replaced(_)));
+        assert!(matches!(f32::NAN.diff(&f32::NAN), Diff::Replaced(_)));
+    }
+
+    #[test]
+    fn diff_incomparable_values() {
+        // `Option` doesn't support `reflect_partial_eq`, so it's replaced even if it didn't change
+        let value: Box<dyn Reflect> = Box::new(Some(1u32));
+        assert!(matches!(value.diff(&Some(1u32)), Diff::Replaced(_)));
+    }
+
+    #[test]
+    fn diff_changed_fields() {
+        let mut other = foo();
+        other.b.1 = true;
+        other.c.push(3);
+        other.d.insert(2, 4);
+        other.e.1 = "world".to_string();
+
+        let diff = foo().diff(&other);
+        let fields = match &diff {
+            Diff::Struct(fields) => fields,
+            diff => panic!("expected a struct diff, found {:?}", diff),
+        };
+        let names = fields
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["b", "c", "d", "e"]);
+        assert!(matches!(&fields[0].1, Diff::TupleStruct(fields) if fields.len() == 1));
+        assert!(
+            matches!(&fields[1].1, Diff::List { changed, pushed } if changed.is_empty() && pushed.len() == 1)
+        );
+
+        let mut value = foo();
+        value.apply_diff(&diff).unwrap();
+        assert_eq!(value, other);
+
+        // a diff only contains the changed fields, so it can be applied on top of other changes
+        let mut value = foo();
+        value.a = 5;
+        value.apply_diff(&diff.clone()).unwrap();
+        assert_eq!(value.a, 5);
+        assert_eq!(value.b, other.b);
+    }
+
+    #[test]
+    fn diff_replaced() {
+        let mut other = foo();
+        other.c.clear();
+        other.d.remove(&1);
+
+        let diff = foo().diff(&other);
+        let fields = match &diff {
+            Diff::Struct(fields) => fields,
+            diff => panic!("expected a struct diff, found {:?}", diff),
+        };
+        assert!(matches!(fields[0].1, Diff::Replaced(_)));
+        assert!(matches!(fields[1].1, Diff::Replaced(_)));
+
+        assert!(matches!(1u32.diff(&2u32), Diff::Replaced(_)));
+        assert!(matches!(1u32.diff(&1u64), Diff::Replaced(_)));
+    }
+
+    #[test]
+    fn apply_mismatched_diff() {
+        let diff = Diff::Struct(vec![("x".to_string(), Diff::Replaced(Box::new(1u32)))]);
+        assert_eq!(
+            foo().apply_diff(&diff),
+            Err(DiffError::MissingField("x".to_string()))
+        );
+        assert_eq!(
+            Bar(1, false).apply_diff(&diff),
+            Err(DiffError::MismatchedKind)
+        );
+    }
+}
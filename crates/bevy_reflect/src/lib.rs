@@ -1,3 +1,4 @@
+mod diff;
 mod list;
 mod map;
 mod path;
@@ -30,6 +31,7 @@ pub mod prelude {
     };
 }
 
+pub use diff::*;
 pub use impls::*;
 pub use list::*;
 pub use map::*;
@@ -1,4 +1,4 @@
-use crate::{serde::Serializable, List, Map, Struct, Tuple, TupleStruct};
+use crate::{serde::Serializable, Diff, DiffError, List, Map, Struct, Tuple, TupleStruct};
 use std::{any::Any, fmt::Debug};
 
 pub use bevy_utils::AHasher as ReflectHasher;
@@ -45,6 +45,23 @@ pub unsafe trait Reflect: Any + Send + Sync {
     /// Returns a serializable value, if serialization is supported. Otherwise `None` will be
     /// returned.
     fn serializable(&self) -> Option<Serializable>;
+
+    /// Returns the changes needed to turn this value into `other`.
+    fn diff(&self, other: &dyn Reflect) -> Diff {
+        crate::diff::diff(self.type_name(), self.reflect_ref(), other)
+    }
+
+    /// Applies a [`Diff`] created from a value of the same type.
+    fn apply_diff(&mut self, diff: &Diff) -> Result<(), DiffError> {
+        match diff {
+            Diff::Unchanged => Ok(()),
+            Diff::Replaced(value) => {
+                self.apply(&**value);
+                Ok(())
+            }
+            diff => crate::diff::apply_diff(self.reflect_mut(), diff),
+        }
+    }
 }
 
 impl Debug for dyn Reflect {
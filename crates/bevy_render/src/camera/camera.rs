@@ -1,12 +1,12 @@
-use super::CameraProjection;
+use super::{CameraProjection, Viewport};
 use bevy_ecs::{
     change_detection::DetectChanges,
     component::Component,
     entity::Entity,
     event::EventReader,
-    query::Added,
+    query::{Added, Changed},
     reflect::ReflectComponent,
-    system::{Query, QuerySet, Res},
+    system::{Query, QuerySet, RemovedComponents, Res},
 };
 use bevy_math::{Mat4, Vec2, Vec3};
 use bevy_reflect::{Reflect, ReflectDeserialize};
@@ -69,9 +69,11 @@ pub fn camera_system<T: CameraProjection + Component>(
     mut window_resized_events: EventReader<WindowResized>,
     mut window_created_events: EventReader<WindowCreated>,
     windows: Res<Windows>,
+    mut removed_viewports: RemovedComponents<Viewport>,
     mut queries: QuerySet<(
-        Query<(Entity, &mut Camera, &mut T)>,
+        Query<(Entity, &mut Camera, &mut T, Option<&Viewport>)>,
         Query<Entity, Added<Camera>>,
+        Query<Entity, Changed<Viewport>>,
     )>,
 ) {
     let mut changed_window_ids = Vec::new();
@@ -99,13 +101,21 @@ pub fn camera_system<T: CameraProjection + Component>(
     for entity in &mut queries.q1().iter() {
         added_cameras.push(entity);
     }
-    for (entity, mut camera, mut camera_projection) in queries.q0_mut().iter_mut() {
+    // cameras render to the size of their viewport
+    let mut changed_viewports = removed_viewports.iter().collect::<Vec<_>>();
+    changed_viewports.extend(queries.q2().iter());
+    for (entity, mut camera, mut camera_projection, viewport) in queries.q0_mut().iter_mut() {
         if let Some(window) = windows.get(camera.window) {
             if changed_window_ids.contains(&window.id())
                 || added_cameras.contains(&entity)
+                || changed_viewports.contains(&entity)
                 || camera_projection.is_changed()
             {
-                camera_projection.update(window.width(), window.height());
+                let size = viewport
+                    .copied()
+                    .unwrap_or_default()
+                    .size_in(Vec2::new(window.width(), window.height()));
+                camera_projection.update(size.x, size.y);
                 camera.projection_matrix = camera_projection.get_projection_matrix();
                 camera.depth_calculation = camera_projection.depth_calculation();
             }
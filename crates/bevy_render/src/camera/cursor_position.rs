@@ -0,0 +1,199 @@
+use super::Camera;
+use bevy_ecs::{
+    entity::Entity,
+    system::{Query, Res, ResMut},
+};
+use bevy_math::{Mat4, Vec2, Vec3};
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::HashMap;
+use bevy_window::{WindowId, Windows};
+
+/// A ray in world space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Ray {
+    pub origin: Vec3,
+    /// The normalized direction of the ray.
+    pub direction: Vec3,
+}
+
+impl Ray {
+    /// Returns the point at `distance` along the ray.
+    pub fn get_point(&self, distance: f32) -> Vec3 {
+        self.origin + self.direction * distance
+    }
+}
+
+impl Camera {
+    /// Given a position in screen space, use the camera to compute the ray going from the near
+    /// plane through that position.
+    pub fn screen_to_world_ray(
+        &self,
+        windows: &Windows,
+        camera_transform: &GlobalTransform,
+        screen_position: Vec2,
+    ) -> Option<Ray> {
+        let window = windows.get(self.window)?;
+        let window_size = Vec2::new(window.width(), window.height());
+        // Build a transform to convert from NDC to world using camera data
+        let ndc_to_world: Mat4 =
+            camera_transform.compute_matrix() * self.projection_matrix.inverse();
+        let ndc = screen_position / window_size * 2.0 - Vec2::ONE;
+        let near = ndc_to_world.project_point3(ndc.extend(0.0));
+        let far = ndc_to_world.project_point3(ndc.extend(1.0));
+        let direction = (far - near).normalize();
+        if !direction.is_finite() {
+            return None;
+        }
+        Some(Ray {
+            origin: near,
+            direction,
+        })
+    }
+}
+
+/// The cursor position as seen by a [`Camera`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CameraCursor {
+    /// The window the camera renders to.
+    pub window: WindowId,
+    /// The cursor position in the window, in logical pixels from the bottom left corner.
+    pub screen_position: Vec2,
+    /// The ray going from the camera's near plane through the cursor.
+    pub ray: Ray,
+}
+
+impl CameraCursor {
+    /// The cursor position in the world, for 2D cameras looking down the Z axis.
+    pub fn world_position_2d(&self) -> Vec2 {
+        self.ray.origin.truncate()
+    }
+}
+
+/// The position of the cursor in every window, and projected into the world for every
+/// [`Camera`]. Updated at the start of each frame by [`cursor_position_system`].
+#[derive(Debug, Default)]
+pub struct CursorPositions {
+    windows: HashMap<WindowId, Vec2>,
+    cameras: HashMap<Entity, CameraCursor>,
+}
+
+impl CursorPositions {
+    /// Returns the cursor position in the given window, if the cursor is inside of it.
+    pub fn window(&self, id: WindowId) -> Option<Vec2> {
+        self.windows.get(&id).copied()
+    }
+
+    /// Returns the cursor as seen by the given camera, if the cursor is inside the camera's
+    /// window.
+    pub fn camera(&self, camera: Entity) -> Option<&CameraCursor> {
+        self.cameras.get(&camera)
+    }
+
+    /// Iterates over the cursors of all cameras whose window contains the cursor.
+    pub fn iter_cameras(&self) -> impl Iterator<Item = (Entity, &CameraCursor)> {
+        self.cameras
+            .iter()
+            .map(|(entity, cursor)| (*entity, cursor))
+    }
+}
+
+pub fn cursor_position_system(
+    windows: Res<Windows>,
+    mut cursor_positions: ResMut<CursorPositions>,
+    cameras: Query<(Entity, &Camera, &GlobalTransform)>,
+) {
+    let cursor_positions = &mut *cursor_positions;
+    cursor_positions.windows.clear();
+    cursor_positions.cameras.clear();
+
+    for window in windows.iter() {
+        if let Some(position) = window.cursor_position() {
+            cursor_positions.windows.insert(window.id(), position);
+        }
+    }
+
+    for (entity, camera, transform) in cameras.iter() {
+        let screen_position = match cursor_positions.windows.get(&camera.window) {
+            Some(position) => *position,
+            None => continue,
+        };
+        if let Some(ray) = camera.screen_to_world_ray(&windows, transform, screen_position) {
+            cursor_positions.cameras.insert(
+                entity,
+                CameraCursor {
+                    window: camera.window,
+                    screen_position,
+                    ray,
+                },
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::{CameraProjection, OrthographicProjection, PerspectiveProjection};
+    use bevy_window::{Window, WindowDescriptor};
+
+    fn windows() -> Windows {
+        let mut windows = Windows::default();
+        windows.add(Window::new(
+            WindowId::primary(),
+            &WindowDescriptor {
+                width: 800.0,
+                height: 600.0,
+                ..Default::default()
+            },
+            800,
+            600,
+            1.0,
+            None,
+        ));
+        windows
+    }
+
+    fn camera(projection: &mut impl CameraProjection) -> Camera {
+        projection.update(800.0, 600.0);
+        Camera {
+            projection_matrix: projection.get_projection_matrix(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn orthographic_ray() {
+        let windows = windows();
+        let camera = camera(&mut OrthographicProjection::default());
+        let transform = GlobalTransform::from_xyz(100.0, 50.0, 999.9);
+
+        let ray = camera
+            .screen_to_world_ray(&windows, &transform, Vec2::new(600.0, 300.0))
+            .unwrap();
+        let cursor = CameraCursor {
+            window: WindowId::primary(),
+            screen_position: Vec2::new(600.0, 300.0),
+            ray,
+        };
+        assert!((cursor.world_position_2d() - Vec2::new(300.0, 50.0)).length() < 1e-3);
+        assert!((ray.direction - -Vec3::Z).length() < 1e-5);
+    }
+
+    #[test]
+    fn perspective_ray() {
+        let windows = windows();
+        let camera = camera(&mut PerspectiveProjection::default());
+        let transform = GlobalTransform::from_xyz(0.0, 0.0, 10.0);
+
+        // the center of the screen is straight ahead
+        let ray = camera
+            .screen_to_world_ray(&windows, &transform, Vec2::new(400.0, 300.0))
+            .unwrap();
+        assert!((ray.direction - -Vec3::Z).length() < 1e-5);
+
+        // projecting a point the ray goes through lands back on the cursor
+        let point = ray.get_point(5.0);
+        let screen_position = camera.world_to_screen(&windows, &transform, point).unwrap();
+        assert!((screen_position - Vec2::new(400.0, 300.0)).length() < 1e-2);
+    }
+}
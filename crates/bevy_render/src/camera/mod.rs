@@ -1,10 +1,16 @@
 mod active_cameras;
 #[allow(clippy::module_inception)]
 mod camera;
+mod cursor_position;
 mod projection;
+mod split_screen;
+mod viewport;
 mod visible_entities;
 
 pub use active_cameras::*;
 pub use camera::*;
+pub use cursor_position::*;
 pub use projection::*;
+pub use split_screen::*;
+pub use viewport::*;
 pub use visible_entities::*;
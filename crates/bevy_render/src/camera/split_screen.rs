@@ -0,0 +1,176 @@
+use super::{Camera, Viewport};
+use crate::render_graph::base;
+use bevy_app::AppBuilder;
+use bevy_ecs::{
+    entity::Entity,
+    system::{Commands, Query, Res},
+};
+use bevy_math::Vec2;
+
+/// Standard ways to divide a window between several cameras.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitScreenLayout {
+    /// Cameras side by side, from left to right.
+    Columns,
+    /// Cameras stacked from top to bottom.
+    Rows,
+    /// A grid filled row by row from the top left corner. The cameras of an incomplete last row
+    /// share its whole width, so three cameras are laid out as two on top of one.
+    Grid,
+}
+
+impl Default for SplitScreenLayout {
+    fn default() -> Self {
+        SplitScreenLayout::Grid
+    }
+}
+
+impl SplitScreenLayout {
+    /// The viewports of `count` cameras laid out in this layout.
+    pub fn viewports(&self, count: usize) -> Vec<Viewport> {
+        let (columns, rows) = match self {
+            SplitScreenLayout::Columns => (count, 1),
+            SplitScreenLayout::Rows => (1, count),
+            SplitScreenLayout::Grid => {
+                let columns = (count as f32).sqrt().ceil() as usize;
+                (columns, (count + columns.max(1) - 1) / columns.max(1))
+            }
+        };
+        (0..count)
+            .map(|index| {
+                let row = index / columns;
+                let columns_in_row = columns.min(count - row * columns);
+                let size = Vec2::new(1.0 / columns_in_row as f32, 1.0 / rows as f32);
+                let column = index % columns;
+                Viewport::new(
+                    Vec2::new(column as f32 * size.x, 1.0 - (row + 1) as f32 * size.y),
+                    size,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Lays out the cameras of a split-screen, added with [`AddSplitScreen::add_split_screen`].
+///
+/// Every frame, [`split_screen_system`] gives the cameras named in [`SplitScreen::cameras`] the
+/// [`Viewport`] of their slot in the layout.
+#[derive(Debug, Clone)]
+pub struct SplitScreen {
+    pub layout: SplitScreenLayout,
+    cameras: Vec<String>,
+}
+
+impl SplitScreen {
+    /// The name of the split-screen camera with the given index, e.g. for
+    /// [`PerspectiveCameraBundle::with_name`](crate::entity::PerspectiveCameraBundle::with_name).
+    pub fn camera_name(index: usize) -> String {
+        format!("SplitScreen{}", index)
+    }
+
+    /// The names of the split-screen cameras, in layout order.
+    pub fn cameras(&self) -> &[String] {
+        &self.cameras
+    }
+
+    /// Returns the viewport of the camera with the given name, if it is part of the split-screen.
+    pub fn viewport(&self, camera_name: &str) -> Option<Viewport> {
+        let index = self.cameras.iter().position(|name| name == camera_name)?;
+        self.layout
+            .viewports(self.cameras.len())
+            .get(index)
+            .copied()
+    }
+}
+
+pub fn split_screen_system(
+    mut commands: Commands,
+    split_screen: Option<Res<SplitScreen>>,
+    mut cameras: Query<(Entity, &Camera, Option<&mut Viewport>)>,
+) {
+    let split_screen = match split_screen {
+        Some(split_screen) => split_screen,
+        None => return,
+    };
+    for (entity, camera, viewport) in cameras.iter_mut() {
+        let target = match camera
+            .name
+            .as_ref()
+            .and_then(|name| split_screen.viewport(name))
+        {
+            Some(target) => target,
+            None => continue,
+        };
+        match viewport {
+            Some(mut viewport) => {
+                // only write when the layout changed, to keep change detection meaningful
+                if *viewport != target {
+                    *viewport = target;
+                }
+            }
+            None => {
+                commands.entity(entity).insert(target);
+            }
+        }
+    }
+}
+
+pub trait AddSplitScreen {
+    /// Splits the primary window between `count` cameras rendering to the main pass.
+    ///
+    /// Spawn the cameras with the names returned by [`SplitScreen::camera_name`]. Their viewports
+    /// are kept up to date with the [`SplitScreen`] resource, whose layout can be changed at any
+    /// time.
+    fn add_split_screen(&mut self, layout: SplitScreenLayout, count: usize) -> &mut Self;
+}
+
+impl AddSplitScreen for AppBuilder {
+    fn add_split_screen(&mut self, layout: SplitScreenLayout, count: usize) -> &mut Self {
+        let cameras = (0..count).map(SplitScreen::camera_name).collect::<Vec<_>>();
+        for camera in cameras.iter() {
+            base::add_main_pass_camera(self.world_mut(), camera)
+                .expect("RenderPlugin must be added with a main pass before the split-screen");
+        }
+        self.insert_resource(SplitScreen { layout, cameras })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SplitScreenLayout;
+    use crate::camera::Viewport;
+    use bevy_math::Vec2;
+
+    fn viewport(x: f32, y: f32, width: f32, height: f32) -> Viewport {
+        Viewport::new(Vec2::new(x, y), Vec2::new(width, height))
+    }
+
+    #[test]
+    fn layouts() {
+        assert_eq!(
+            SplitScreenLayout::Grid.viewports(1),
+            vec![Viewport::default()]
+        );
+        assert_eq!(
+            SplitScreenLayout::Grid.viewports(2),
+            SplitScreenLayout::Columns.viewports(2)
+        );
+        assert_eq!(
+            SplitScreenLayout::Rows.viewports(2),
+            vec![viewport(0.0, 0.5, 1.0, 0.5), viewport(0.0, 0.0, 1.0, 0.5)]
+        );
+        assert_eq!(
+            SplitScreenLayout::Grid.viewports(3),
+            vec![
+                viewport(0.0, 0.5, 0.5, 0.5),
+                viewport(0.5, 0.5, 0.5, 0.5),
+                viewport(0.0, 0.0, 1.0, 0.5),
+            ]
+        );
+        assert_eq!(
+            SplitScreenLayout::Grid.viewports(4)[3],
+            viewport(0.5, 0.0, 0.5, 0.5)
+        );
+        assert!(SplitScreenLayout::Grid.viewports(0).is_empty());
+    }
+}
@@ -0,0 +1,82 @@
+use bevy_ecs::reflect::ReflectComponent;
+use bevy_math::Vec2;
+use bevy_reflect::Reflect;
+
+/// The area of its window a [`Camera`](super::Camera) renders to, in fractions of the window
+/// size from the bottom left corner.
+///
+/// Viewports are relative to the window, so they follow window resizes. Cameras without this
+/// component render to the whole window.
+#[derive(Debug, Clone, Copy, PartialEq, Reflect)]
+#[reflect(Component, PartialEq)]
+pub struct Viewport {
+    pub origin: Vec2,
+    pub size: Vec2,
+}
+
+impl Default for Viewport {
+    fn default() -> Self {
+        Viewport {
+            origin: Vec2::ZERO,
+            size: Vec2::ONE,
+        }
+    }
+}
+
+impl Viewport {
+    pub fn new(origin: Vec2, size: Vec2) -> Self {
+        Viewport { origin, size }
+    }
+
+    /// The viewport in pixels for a window of the given size.
+    pub fn size_in(&self, window_size: Vec2) -> Vec2 {
+        self.size * window_size
+    }
+
+    /// The viewport in physical pixels from the top left corner of a window of the given
+    /// physical size, as `(x, y, width, height)`. This is the layout render passes expect.
+    pub fn physical_rect(&self, width: u32, height: u32) -> (u32, u32, u32, u32) {
+        let window_size = Vec2::new(width as f32, height as f32);
+        let min = (self.origin * window_size).round();
+        let max = ((self.origin + self.size) * window_size).round();
+        let top = height as f32 - max.y;
+        (
+            min.x.max(0.0) as u32,
+            top.max(0.0) as u32,
+            (max.x - min.x).max(0.0) as u32,
+            (max.y - min.y).max(0.0) as u32,
+        )
+    }
+
+    /// Returns `true` if `position`, in pixels from the bottom left corner of a window of the
+    /// given size, is inside the viewport.
+    pub fn contains(&self, window_size: Vec2, position: Vec2) -> bool {
+        let relative = position / window_size - self.origin;
+        relative.x >= 0.0
+            && relative.y >= 0.0
+            && relative.x <= self.size.x
+            && relative.y <= self.size.y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Viewport;
+    use bevy_math::Vec2;
+
+    #[test]
+    fn physical_rect() {
+        assert_eq!(
+            Viewport::default().physical_rect(800, 600),
+            (0, 0, 800, 600)
+        );
+        // the top right quarter of the window
+        let viewport = Viewport::new(Vec2::splat(0.5), Vec2::splat(0.5));
+        assert_eq!(viewport.physical_rect(800, 600), (400, 0, 400, 300));
+        // the bottom half
+        let viewport = Viewport::new(Vec2::ZERO, Vec2::new(1.0, 0.5));
+        assert_eq!(viewport.physical_rect(800, 600), (0, 300, 800, 300));
+        assert!(viewport.contains(Vec2::new(800.0, 600.0), Vec2::new(100.0, 100.0)));
+        assert!(!viewport.contains(Vec2::new(800.0, 600.0), Vec2::new(100.0, 400.0)));
+    }
+}
@@ -2,6 +2,7 @@ use super::{Camera, DepthCalculation};
 use crate::{draw::OutsideFrustum, prelude::Visible};
 use bevy_core::FloatOrd;
 use bevy_ecs::{entity::Entity, query::Without, reflect::ReflectComponent, system::Query};
+use bevy_math::Vec3;
 use bevy_reflect::Reflect;
 use bevy_transform::prelude::GlobalTransform;
 
@@ -24,6 +25,50 @@ impl VisibleEntities {
     }
 }
 
+/// The order transparent 2d entities, like sprites and 2d text, are drawn in by the cameras with
+/// [`DepthCalculation::ZDifference`], instead of only their z translation.
+///
+/// Entities are drawn by increasing `layer`, then increasing `order`, then, when both are
+/// `y_sort`, by decreasing y translation, so that entities lower on the screen are drawn over the
+/// ones behind them in top-down games. Ties are drawn by increasing z translation. Entities
+/// without this component have the [`Default`] sort key.
+#[derive(Debug, Default, Clone, Copy, Reflect, PartialEq)]
+#[reflect(Component, PartialEq)]
+pub struct SortKey2d {
+    pub layer: i32,
+    pub order: f32,
+    pub y_sort: bool,
+}
+
+impl SortKey2d {
+    /// The sort key of the entities on `layer`
+    pub fn layer(layer: i32) -> Self {
+        SortKey2d {
+            layer,
+            ..Default::default()
+        }
+    }
+
+    /// The sort key of the entities on `layer`, sorted by y translation
+    pub fn y_sorted(layer: i32) -> Self {
+        SortKey2d {
+            layer,
+            y_sort: true,
+            ..Default::default()
+        }
+    }
+
+    fn key(&self, translation: Vec3) -> (i32, FloatOrd, FloatOrd, FloatOrd) {
+        let y = if self.y_sort { -translation.y } else { 0.0 };
+        (
+            self.layer,
+            FloatOrd(self.order),
+            FloatOrd(y),
+            FloatOrd(translation.z),
+        )
+    }
+}
+
 type LayerMask = u32;
 
 /// An identifier for a rendering layer.
@@ -207,7 +252,10 @@ pub fn visible_entities_system(
         &mut VisibleEntities,
         Option<&RenderLayers>,
     )>,
-    visible_query: Query<(Entity, &Visible, Option<&RenderLayers>), Without<OutsideFrustum>>,
+    visible_query: Query<
+        (Entity, &Visible, Option<&RenderLayers>, Option<&SortKey2d>),
+        Without<OutsideFrustum>,
+    >,
     visible_transform_query: Query<&GlobalTransform, Without<OutsideFrustum>>,
 ) {
     for (camera, camera_global_transform, mut visible_entities, maybe_camera_mask) in
@@ -219,7 +267,8 @@ pub fn visible_entities_system(
 
         let mut no_transform_order = 0.0;
         let mut transparent_entities = Vec::new();
-        for (entity, visible, maybe_entity_mask) in visible_query.iter() {
+        let mut sorted_2d_entities = Vec::new();
+        for (entity, visible, maybe_entity_mask, maybe_sort_key) in visible_query.iter() {
             if !visible.is_visible {
                 continue;
             }
@@ -243,7 +292,17 @@ pub fn visible_entities_system(
                 order
             };
 
-            if visible.is_transparent {
+            if let (true, DepthCalculation::ZDifference, Ok(global_transform)) = (
+                visible.is_transparent,
+                camera.depth_calculation,
+                visible_transform_query.get(entity),
+            ) {
+                let key = maybe_sort_key
+                    .copied()
+                    .unwrap_or_default()
+                    .key(global_transform.translation);
+                sorted_2d_entities.push((key, VisibleEntity { entity, order }));
+            } else if visible.is_transparent {
                 transparent_entities.push(VisibleEntity { entity, order })
             } else {
                 visible_entities.value.push(VisibleEntity { entity, order })
@@ -257,7 +316,139 @@ pub fn visible_entities_system(
         transparent_entities.sort_by_key(|e| -e.order);
         visible_entities.value.extend(transparent_entities);
 
+        // sort transparent 2d entities by their sort key
+        sorted_2d_entities.sort_by_key(|(key, _)| *key);
+        visible_entities
+            .value
+            .extend(sorted_2d_entities.into_iter().map(|(_, e)| e));
+
         // TODO: check for big changes in visible entities len() vs capacity() (ex: 2x) and resize
         // to prevent holding unneeded memory
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{visible_entities_system, RenderLayers, SortKey2d, VisibleEntities};
+    use crate::{
+        camera::{Camera, DepthCalculation},
+        prelude::Visible,
+    };
+    use bevy_ecs::{
+        prelude::*,
+        schedule::{Stage, SystemStage},
+    };
+    use bevy_math::Vec3;
+    use bevy_transform::prelude::GlobalTransform;
+
+    #[test]
+    fn cameras_only_see_their_layers() {
+        let mut world = World::default();
+        let main_camera = world
+            .spawn()
+            .insert_bundle((
+                Camera::default(),
+                GlobalTransform::default(),
+                VisibleEntities::default(),
+            ))
+            .id();
+        let arms_camera = world
+            .spawn()
+            .insert_bundle((
+                Camera::default(),
+                GlobalTransform::default(),
+                VisibleEntities::default(),
+                RenderLayers::layer(1),
+            ))
+            .id();
+        let scenery = world
+            .spawn()
+            .insert_bundle((Visible::default(), GlobalTransform::default()))
+            .id();
+        let arms = world
+            .spawn()
+            .insert_bundle((
+                Visible::default(),
+                GlobalTransform::default(),
+                RenderLayers::layer(1),
+            ))
+            .id();
+        let everywhere = world
+            .spawn()
+            .insert_bundle((
+                Visible::default(),
+                GlobalTransform::default(),
+                RenderLayers::all(),
+            ))
+            .id();
+        world.spawn().insert_bundle((
+            Visible::default(),
+            GlobalTransform::default(),
+            RenderLayers::none(),
+        ));
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(visible_entities_system.system());
+        stage.run(&mut world);
+
+        let visible = |camera| {
+            let mut entities = world
+                .get::<VisibleEntities>(camera)
+                .unwrap()
+                .iter()
+                .map(|visible| visible.entity)
+                .collect::<Vec<_>>();
+            entities.sort();
+            entities
+        };
+        assert_eq!(visible(main_camera), vec![scenery, everywhere]);
+        assert_eq!(visible(arms_camera), vec![arms, everywhere]);
+    }
+
+    #[test]
+    fn sort_key_2d_orders_transparent_entities() {
+        let mut world = World::default();
+        let camera = world
+            .spawn()
+            .insert_bundle((
+                Camera {
+                    depth_calculation: DepthCalculation::ZDifference,
+                    ..Default::default()
+                },
+                GlobalTransform::from_xyz(0.0, 0.0, 1000.0),
+                VisibleEntities::default(),
+            ))
+            .id();
+        let mut spawn = |translation: Vec3, sort_key: Option<SortKey2d>| {
+            let mut entity = world.spawn();
+            entity.insert_bundle((
+                Visible {
+                    is_visible: true,
+                    is_transparent: true,
+                },
+                GlobalTransform::from_translation(translation),
+            ));
+            if let Some(sort_key) = sort_key {
+                entity.insert(sort_key);
+            }
+            entity.id()
+        };
+        let foreground = spawn(Vec3::new(0.0, 0.0, -10.0), Some(SortKey2d::layer(1)));
+        let front = spawn(Vec3::new(0.0, 0.0, 2.0), None);
+        let back = spawn(Vec3::new(0.0, 0.0, 1.0), None);
+        let low = spawn(Vec3::new(0.0, -5.0, 0.0), Some(SortKey2d::y_sorted(-1)));
+        let high = spawn(Vec3::new(0.0, 5.0, 0.0), Some(SortKey2d::y_sorted(-1)));
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(visible_entities_system.system());
+        stage.run(&mut world);
+
+        let visible = world
+            .get::<VisibleEntities>(camera)
+            .unwrap()
+            .iter()
+            .map(|visible| visible.entity)
+            .collect::<Vec<_>>();
+        assert_eq!(visible, vec![high, low, back, front, foreground]);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::draw::DrawStatistics;
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::system::{IntoSystem, Res, ResMut};
+
+/// Adds "draw call", "primitive" and "state change" diagnostics to an App, as counted by
+/// [DrawStatistics]. GPU memory usage is reported by the diagnostics of the render backend.
+#[derive(Default)]
+pub struct DrawDiagnosticsPlugin;
+
+impl Plugin for DrawDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_startup_system(Self::setup_system.system())
+            .add_system(Self::diagnostic_system.system());
+    }
+}
+
+impl DrawDiagnosticsPlugin {
+    pub const DRAW_CALLS: DiagnosticId =
+        DiagnosticId::from_u128(160944379342618361235097128357214781092);
+    pub const PRIMITIVES: DiagnosticId =
+        DiagnosticId::from_u128(219483512650736912873469028134508871723);
+    pub const PIPELINE_CHANGES: DiagnosticId =
+        DiagnosticId::from_u128(27539262716521904183498233416254087466);
+    pub const BIND_GROUP_CHANGES: DiagnosticId =
+        DiagnosticId::from_u128(301629448725815620137580254360271960350);
+    pub const BUFFER_CHANGES: DiagnosticId =
+        DiagnosticId::from_u128(95034166027437296331869462549720343118);
+
+    pub fn setup_system(mut diagnostics: ResMut<Diagnostics>) {
+        diagnostics.add(Diagnostic::new(Self::DRAW_CALLS, "draw_calls", 20));
+        diagnostics.add(Diagnostic::new(Self::PRIMITIVES, "primitives", 20));
+        diagnostics.add(Diagnostic::new(
+            Self::PIPELINE_CHANGES,
+            "pipeline_changes",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(
+            Self::BIND_GROUP_CHANGES,
+            "bind_group_changes",
+            20,
+        ));
+        diagnostics.add(Diagnostic::new(Self::BUFFER_CHANGES, "buffer_changes", 20));
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        draw_statistics: Res<DrawStatistics>,
+    ) {
+        diagnostics.add_measurement(Self::DRAW_CALLS, draw_statistics.draw_calls as f64);
+        diagnostics.add_measurement(Self::PRIMITIVES, draw_statistics.primitives as f64);
+        diagnostics.add_measurement(
+            Self::PIPELINE_CHANGES,
+            draw_statistics.pipeline_changes as f64,
+        );
+        diagnostics.add_measurement(
+            Self::BIND_GROUP_CHANGES,
+            draw_statistics.bind_group_changes as f64,
+        );
+        diagnostics.add_measurement(Self::BUFFER_CHANGES, draw_statistics.buffer_changes as f64);
+    }
+}
@@ -0,0 +1,2 @@
+mod draw_diagnostics_plugin;
+pub use draw_diagnostics_plugin::DrawDiagnosticsPlugin;
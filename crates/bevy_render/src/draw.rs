@@ -14,7 +14,14 @@ use bevy_ecs::{
     system::{Query, Res, ResMut, SystemParam},
 };
 use bevy_reflect::Reflect;
-use std::{ops::Range, sync::Arc};
+use bevy_tasks::AsyncComputeTaskPool;
+use std::{
+    ops::Range,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
 use thiserror::Error;
 
 /// A queued command for the renderer
@@ -47,6 +54,14 @@ pub enum RenderCommand {
         vertices: Range<u32>,
         instances: Range<u32>,
     },
+    /// Restricts the following draws to an area of the render target, in physical pixels from
+    /// the top left corner.
+    SetViewport {
+        x: u32,
+        y: u32,
+        width: u32,
+        height: u32,
+    },
 }
 
 #[derive(Debug, Clone, Reflect)]
@@ -149,6 +164,65 @@ impl Draw {
     }
 }
 
+/// Counts the draw calls and render state changes issued by render passes.
+///
+/// The counters are accumulated while the render graph runs and published at the end of each
+/// frame, so the public fields always describe the last fully rendered frame.
+#[derive(Debug, Default)]
+pub struct DrawStatistics {
+    /// Number of draw calls (indexed or not).
+    pub draw_calls: usize,
+    /// Number of primitives (triangles, lines or points) submitted by the draw calls.
+    pub primitives: usize,
+    /// Number of times a render pipeline was bound.
+    pub pipeline_changes: usize,
+    /// Number of times a bind group was bound.
+    pub bind_group_changes: usize,
+    /// Number of times a vertex or index buffer was bound.
+    pub buffer_changes: usize,
+    current_draw_calls: AtomicUsize,
+    current_primitives: AtomicUsize,
+    current_pipeline_changes: AtomicUsize,
+    current_bind_group_changes: AtomicUsize,
+    current_buffer_changes: AtomicUsize,
+}
+
+impl DrawStatistics {
+    /// Adds the counts of a single render pass to the frame currently being rendered.
+    pub fn record_pass(
+        &self,
+        draw_calls: usize,
+        primitives: usize,
+        pipeline_changes: usize,
+        bind_group_changes: usize,
+        buffer_changes: usize,
+    ) {
+        self.current_draw_calls
+            .fetch_add(draw_calls, Ordering::Relaxed);
+        self.current_primitives
+            .fetch_add(primitives, Ordering::Relaxed);
+        self.current_pipeline_changes
+            .fetch_add(pipeline_changes, Ordering::Relaxed);
+        self.current_bind_group_changes
+            .fetch_add(bind_group_changes, Ordering::Relaxed);
+        self.current_buffer_changes
+            .fetch_add(buffer_changes, Ordering::Relaxed);
+    }
+
+    /// Publishes the counts of the frame that was just rendered and resets the counters.
+    pub fn finish_frame(&mut self) {
+        self.draw_calls = std::mem::take(self.current_draw_calls.get_mut());
+        self.primitives = std::mem::take(self.current_primitives.get_mut());
+        self.pipeline_changes = std::mem::take(self.current_pipeline_changes.get_mut());
+        self.bind_group_changes = std::mem::take(self.current_bind_group_changes.get_mut());
+        self.buffer_changes = std::mem::take(self.current_buffer_changes.get_mut());
+    }
+}
+
+pub fn draw_statistics_system(mut draw_statistics: ResMut<DrawStatistics>) {
+    draw_statistics.finish_frame();
+}
+
 #[derive(Debug, Error)]
 pub enum DrawError {
     #[error("pipeline does not exist")]
@@ -161,6 +235,8 @@ pub enum DrawError {
     BufferAllocationFailure,
     #[error("the given asset does not have any render resources")]
     MissingAssetRenderResources,
+    #[error("the pipeline is being compiled in the background")]
+    PipelineCompiling,
 }
 
 #[derive(SystemParam)]
@@ -171,6 +247,7 @@ pub struct DrawContext<'a> {
     pub pipeline_compiler: ResMut<'a, PipelineCompiler>,
     pub render_resource_context: Res<'a, Box<dyn RenderResourceContext>>,
     pub shared_buffers: ResMut<'a, SharedBuffers>,
+    pub task_pool: Option<Res<'a, AsyncComputeTaskPool>>,
     #[system_param(ignore)]
     pub current_pipeline: Option<Handle<PipelineDescriptor>>,
 }
@@ -185,6 +262,9 @@ impl<'a> DrawContext<'a> {
             .ok_or(DrawError::BufferAllocationFailure)
     }
 
+    /// Sets the specialization of the pipeline, compiling it if it is new. Fails with
+    /// [`DrawError::PipelineCompiling`] while its shaders are compiled in the background, in which
+    /// case the draw should be skipped for this frame.
     pub fn set_pipeline(
         &mut self,
         draw: &mut Draw,
@@ -197,13 +277,16 @@ impl<'a> DrawContext<'a> {
         {
             specialized_pipeline
         } else {
-            self.pipeline_compiler.compile_pipeline(
-                &**self.render_resource_context,
-                &mut self.pipelines,
-                &mut self.shaders,
-                pipeline_handle,
-                specialization,
-            )
+            self.pipeline_compiler
+                .try_compile_pipeline(
+                    &**self.render_resource_context,
+                    &mut self.pipelines,
+                    &mut self.shaders,
+                    self.task_pool.as_deref().map(|task_pool| &task_pool.0),
+                    pipeline_handle,
+                    specialization,
+                )
+                .ok_or(DrawError::PipelineCompiling)?
         };
 
         draw.set_pipeline(&specialized_pipeline);
@@ -1,6 +1,7 @@
 pub mod camera;
 pub mod color;
 pub mod colorspace;
+pub mod diagnostic;
 pub mod draw;
 pub mod entity;
 pub mod mesh;
@@ -18,7 +19,7 @@ use bevy_ecs::{
 };
 use bevy_transform::TransformSystem;
 use bevy_utils::tracing::warn;
-use draw::{OutsideFrustum, Visible};
+use draw::{DrawStatistics, OutsideFrustum, Visible};
 
 pub use once_cell;
 
@@ -26,6 +27,7 @@ pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
         base::Msaa,
+        camera::{AddSplitScreen, CursorPositions, SortKey2d},
         color::Color,
         draw::{Draw, Visible},
         entity::*,
@@ -43,8 +45,9 @@ use bevy_app::prelude::*;
 use bevy_asset::{AddAsset, AssetStage};
 use bevy_ecs::schedule::{StageLabel, SystemLabel};
 use camera::{
-    ActiveCameras, Camera, DepthCalculation, OrthographicProjection, PerspectiveProjection,
-    RenderLayers, ScalingMode, VisibleEntities, WindowOrigin,
+    ActiveCameras, Camera, CursorPositions, DepthCalculation, OrthographicProjection,
+    PerspectiveProjection, RenderLayers, ScalingMode, SortKey2d, Viewport, VisibleEntities,
+    WindowOrigin,
 };
 use pipeline::{
     IndexFormat, PipelineCompiler, PipelineDescriptor, PipelineSpecialization, PrimitiveTopology,
@@ -52,7 +55,7 @@ use pipeline::{
 };
 use render_graph::{
     base::{self, BaseRenderGraphConfig, MainPass},
-    RenderGraph,
+    RenderGraph, TransientResources,
 };
 use renderer::{AssetRenderResourceBindings, RenderResourceBindings, RenderResourceContext};
 use shader::ShaderLoader;
@@ -66,6 +69,7 @@ use texture::HdrTextureLoader;
     feature = "bmp"
 ))]
 use texture::ImageTextureLoader;
+use texture::WindowCaptures;
 
 #[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
 pub enum RenderSystem {
@@ -148,6 +152,7 @@ impl Plugin for RenderPlugin {
         .add_asset::<Texture>()
         .add_asset::<Shader>()
         .add_asset::<PipelineDescriptor>()
+        .add_event::<shader::ShaderEvent>()
         .register_type::<Camera>()
         .register_type::<DepthCalculation>()
         .register_type::<Draw>()
@@ -164,6 +169,8 @@ impl Plugin for RenderPlugin {
         .register_type::<IndexFormat>()
         .register_type::<PipelineSpecialization>()
         .register_type::<RenderLayers>()
+        .register_type::<SortKey2d>()
+        .register_type::<Viewport>()
         .register_type::<ScalingMode>()
         .register_type::<VertexBufferLayout>()
         .register_type::<WindowOrigin>()
@@ -174,15 +181,28 @@ impl Plugin for RenderPlugin {
         .init_resource::<RenderResourceBindings>()
         .init_resource::<AssetRenderResourceBindings>()
         .init_resource::<ActiveCameras>()
+        .init_resource::<CursorPositions>()
+        .init_resource::<DrawStatistics>()
+        .init_resource::<TransientResources>()
+        .init_resource::<WindowCaptures>()
         .add_startup_system_to_stage(
             StartupStage::PreStartup,
             check_for_render_resource_context.system(),
         )
         .add_system_to_stage(CoreStage::PreUpdate, draw::clear_draw_system.system())
+        .add_system_to_stage(
+            CoreStage::PreUpdate,
+            camera::cursor_position_system.system(),
+        )
         .add_system_to_stage(
             CoreStage::PostUpdate,
             camera::active_cameras_system.system(),
         )
+        .add_system_to_stage(CoreStage::PostUpdate, camera::split_screen_system.system())
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            mesh::mesh_shader_defs_system.system(),
+        )
         .add_system_to_stage(
             CoreStage::PostUpdate,
             camera::camera_system::<OrthographicProjection>
@@ -225,6 +245,14 @@ impl Plugin for RenderPlugin {
         .add_system_to_stage(
             RenderStage::PostRender,
             shader::clear_shader_defs_system.system(),
+        )
+        .add_system_to_stage(
+            RenderStage::PostRender,
+            draw::draw_statistics_system.system(),
+        )
+        .add_system_to_stage(
+            RenderStage::PostRender,
+            render_graph::transient_resources_system.system(),
         );
 
         if let Some(ref config) = self.base_render_graph_config {
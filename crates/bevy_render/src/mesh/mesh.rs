@@ -1,6 +1,7 @@
 mod conversions;
 
 use crate::{
+    draw::OutsideFrustum,
     pipeline::{IndexFormat, PrimitiveTopology, RenderPipelines, VertexFormat},
     renderer::{BufferInfo, BufferUsage, RenderResourceContext, RenderResourceId},
 };
@@ -9,7 +10,7 @@ use bevy_core::cast_slice;
 use bevy_ecs::{
     entity::Entity,
     event::EventReader,
-    query::{Changed, With},
+    query::{Changed, With, Without},
     system::{Local, Query, QuerySet, Res},
     world::Mut,
 };
@@ -249,7 +250,10 @@ pub struct Mesh {
 /// }
 /// ```
 impl Mesh {
-    /// Per vertex coloring. Use in conjunction with [`Mesh::set_attribute`]
+    /// Per vertex coloring, in linear RGBA. Use in conjunction with [`Mesh::set_attribute`]
+    ///
+    /// The standard pipelines multiply it into the color of the material, see
+    /// [`Mesh::SHADER_DEF_VERTEX_COLORS`].
     pub const ATTRIBUTE_COLOR: &'static str = "Vertex_Color";
     /// The direction the vertex normal is facing in.
     /// Use in conjunction with [`Mesh::set_attribute`]
@@ -267,6 +271,10 @@ impl Mesh {
     /// Per vertex joint transform matrix index. Use in conjunction with [`Mesh::set_attribute`]
     pub const ATTRIBUTE_JOINT_INDEX: &'static str = "Vertex_JointIndex";
 
+    /// The shader def set on the pipelines of meshes with a [`Mesh::ATTRIBUTE_COLOR`], so that
+    /// shaders only read the attribute when the mesh provides it.
+    pub const SHADER_DEF_VERTEX_COLORS: &'static str = "VERTEX_COLORS";
+
     /// Construct a new mesh. You need to provide a PrimitiveTopology so that the
     /// renderer knows how to treat the vertex data. Most of the time this will be
     /// `PrimitiveTopology::TriangleList`.
@@ -599,6 +607,29 @@ pub fn mesh_resource_provider_system(
     }
 }
 
+/// Sets the shader defs that depend on the attributes of the mesh, like
+/// [`Mesh::SHADER_DEF_VERTEX_COLORS`]
+pub fn mesh_shader_defs_system(
+    meshes: Res<Assets<Mesh>>,
+    mut query: Query<(&Handle<Mesh>, &mut RenderPipelines), Without<OutsideFrustum>>,
+) {
+    for (handle, mut render_pipelines) in query.iter_mut() {
+        let has_vertex_colors = meshes.get(handle).map_or(false, |mesh| {
+            mesh.attribute(Mesh::ATTRIBUTE_COLOR).is_some()
+        });
+        if !has_vertex_colors {
+            continue;
+        }
+        for render_pipeline in render_pipelines.pipelines.iter_mut() {
+            render_pipeline
+                .specialization
+                .shader_specialization
+                .shader_defs
+                .insert(Mesh::SHADER_DEF_VERTEX_COLORS.to_string());
+        }
+    }
+}
+
 fn update_entity_mesh(
     render_resource_context: &dyn RenderResourceContext,
     mesh: &Mesh,
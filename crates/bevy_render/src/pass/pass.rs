@@ -18,6 +18,11 @@ impl TextureAttachment {
     }
 }
 
+/// The color the render target of the main pass is cleared with.
+///
+/// Used as a resource for every pass, and as a component on a camera entity to override the
+/// resource in the passes that draw the camera. Windows are not entities, so there is no
+/// per-window override: the clear color of a window is the one of the camera drawing it.
 #[derive(Clone, Debug)]
 pub struct ClearColor(pub Color);
 
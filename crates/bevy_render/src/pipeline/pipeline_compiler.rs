@@ -1,14 +1,18 @@
 use super::{state_descriptors::PrimitiveTopology, IndexFormat, PipelineDescriptor};
 use crate::{
-    pipeline::{BindType, VertexBufferLayout},
-    renderer::RenderResourceContext,
+    pipeline::{BindType, PipelineLayout, VertexBufferLayout},
+    renderer::{RenderResourceContext, ShaderCompiler},
     shader::{Shader, ShaderError},
+    texture::TextureSampleType,
 };
 use bevy_asset::{Assets, Handle};
 use bevy_reflect::{Reflect, ReflectDeserialize};
-use bevy_utils::{HashMap, HashSet};
+use bevy_tasks::TaskPool;
+use bevy_utils::{tracing::error, HashMap, HashSet};
 use once_cell::sync::Lazy;
+use parking_lot::Mutex;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 
 #[derive(Clone, Eq, PartialEq, Debug, Reflect)]
 #[reflect(PartialEq)]
@@ -16,6 +20,8 @@ pub struct PipelineSpecialization {
     pub shader_specialization: ShaderSpecialization,
     pub primitive_topology: PrimitiveTopology,
     pub dynamic_bindings: HashSet<String>,
+    /// The texture bindings bound to depth textures, which are sampled as depth without filtering
+    pub depth_textures: HashSet<String>,
     pub strip_index_format: Option<IndexFormat>,
     pub vertex_buffer_layout: VertexBufferLayout,
     pub sample_count: u32,
@@ -29,6 +35,7 @@ impl Default for PipelineSpecialization {
             shader_specialization: Default::default(),
             primitive_topology: Default::default(),
             dynamic_bindings: Default::default(),
+            depth_textures: Default::default(),
             vertex_buffer_layout: Default::default(),
         }
     }
@@ -59,39 +66,181 @@ struct SpecializedPipeline {
     specialization: PipelineSpecialization,
 }
 
-#[derive(Debug, Default)]
+/// A shader being compiled in the background
+#[derive(Debug)]
+struct PendingShader {
+    shader: Handle<Shader>,
+    specialization: ShaderSpecialization,
+    result: Arc<Mutex<Option<Result<Shader, ShaderError>>>>,
+    /// Whether compilation failed, in which case it isn't retried until the shader is modified
+    failed: bool,
+}
+
+/// Compiles and caches the specializations of pipelines, and of their shaders.
+///
+/// Shaders are compiled asynchronously by default: the first time a specialization is needed, its
+/// shaders are compiled on the [`AsyncComputeTaskPool`](bevy_tasks::AsyncComputeTaskPool), and
+/// [`DrawContext::set_pipeline`](crate::draw::DrawContext::set_pipeline) fails with
+/// [`DrawError::PipelineCompiling`](crate::draw::DrawError::PipelineCompiling) until they are
+/// ready, so entities using a new material or combination of shader defs are skipped for a few
+/// frames instead of stalling the frame. This requires a render backend providing a
+/// [`ShaderCompiler`].
+#[derive(Debug)]
 pub struct PipelineCompiler {
     specialized_shaders: HashMap<Handle<Shader>, Vec<SpecializedShader>>,
     specialized_shader_pipelines: HashMap<Handle<Shader>, Vec<Handle<PipelineDescriptor>>>,
     specialized_pipelines: HashMap<Handle<PipelineDescriptor>, Vec<SpecializedPipeline>>,
+    pending_shaders: Vec<PendingShader>,
+    asynchronous: bool,
+}
+
+impl Default for PipelineCompiler {
+    fn default() -> Self {
+        PipelineCompiler {
+            specialized_shaders: Default::default(),
+            specialized_shader_pipelines: Default::default(),
+            specialized_pipelines: Default::default(),
+            pending_shaders: Default::default(),
+            asynchronous: true,
+        }
+    }
 }
 
 impl PipelineCompiler {
-    fn compile_shader(
+    /// Whether shaders are compiled in the background
+    pub fn asynchronous(&self) -> bool {
+        self.asynchronous
+    }
+
+    /// Disable asynchronous compilation to never skip an entity, at the cost of stalling the
+    /// frames compiling new shaders, e.g. when rendering screenshots.
+    pub fn set_asynchronous(&mut self, asynchronous: bool) {
+        self.asynchronous = asynchronous;
+    }
+
+    /// Returns `true` if shaders are being compiled in the background
+    pub fn is_compiling(&self) -> bool {
+        self.pending_shaders
+            .iter()
+            .any(|pending_shader| !pending_shader.failed)
+    }
+
+    fn add_specialized_shader(
         &mut self,
-        render_resource_context: &dyn RenderResourceContext,
         shaders: &mut Assets<Shader>,
         shader_handle: &Handle<Shader>,
         shader_specialization: &ShaderSpecialization,
-    ) -> Result<Handle<Shader>, ShaderError> {
-        let specialized_shaders = self
-            .specialized_shaders
+        compiled_shader: Shader,
+    ) -> Handle<Shader> {
+        let specialized_handle = shaders.add(compiled_shader);
+        let weak_specialized_handle = specialized_handle.clone_weak();
+        self.specialized_shaders
             .entry(shader_handle.clone_weak())
-            .or_insert_with(Vec::new);
+            .or_insert_with(Vec::new)
+            .push(SpecializedShader {
+                shader: specialized_handle,
+                specialization: shader_specialization.clone(),
+            });
+        weak_specialized_handle
+    }
 
-        let shader = shaders.get(shader_handle).unwrap();
+    /// Returns `true` if the shader is compiled with the given specialization. Otherwise, starts
+    /// compiling it in the background if it isn't already.
+    fn poll_shader(
+        &mut self,
+        shaders: &mut Assets<Shader>,
+        task_pool: &TaskPool,
+        compiler: ShaderCompiler,
+        shader_handle: &Handle<Shader>,
+        shader_specialization: &ShaderSpecialization,
+    ) -> bool {
+        let compiled =
+            self.specialized_shaders
+                .get(shader_handle)
+                .map_or(false, |specialized_shaders| {
+                    specialized_shaders.iter().any(|specialized_shader| {
+                        specialized_shader.specialization == *shader_specialization
+                    })
+                });
+        if compiled {
+            return true;
+        }
+
+        let pending = self.pending_shaders.iter().position(|pending_shader| {
+            pending_shader.shader == *shader_handle
+                && pending_shader.specialization == *shader_specialization
+        });
+        if let Some(index) = pending {
+            let pending_shader = &mut self.pending_shaders[index];
+            if pending_shader.failed {
+                return false;
+            }
+            let result = pending_shader.result.lock().take();
+            return match result {
+                Some(Ok(compiled_shader)) => {
+                    self.pending_shaders.swap_remove(index);
+                    self.add_specialized_shader(
+                        shaders,
+                        shader_handle,
+                        shader_specialization,
+                        compiled_shader,
+                    );
+                    true
+                }
+                Some(Err(error)) => {
+                    error!("Failed to compile shader: {}", error);
+                    pending_shader.failed = true;
+                    false
+                }
+                None => false,
+            };
+        }
+
+        let shader = shaders.get(shader_handle).unwrap().clone();
+        let shader_def_vec = shader_specialization
+            .shader_defs
+            .iter()
+            .cloned()
+            .collect::<Vec<String>>();
+        let result = Arc::new(Mutex::new(None));
+        let task_result = result.clone();
+        task_pool
+            .spawn(async move {
+                *task_result.lock() = Some(compiler(&shader, Some(&shader_def_vec)));
+            })
+            .detach();
+        self.pending_shaders.push(PendingShader {
+            shader: shader_handle.clone_weak(),
+            specialization: shader_specialization.clone(),
+            result,
+            failed: false,
+        });
+        false
+    }
 
+    fn compile_shader(
+        &mut self,
+        render_resource_context: &dyn RenderResourceContext,
+        shaders: &mut Assets<Shader>,
+        shader_handle: &Handle<Shader>,
+        shader_specialization: &ShaderSpecialization,
+    ) -> Result<Handle<Shader>, ShaderError> {
         if let Some(specialized_shader) =
-            specialized_shaders
-                .iter()
-                .find(|current_specialized_shader| {
-                    current_specialized_shader.specialization == *shader_specialization
+            self.specialized_shaders
+                .get(shader_handle)
+                .and_then(|specialized_shaders| {
+                    specialized_shaders
+                        .iter()
+                        .find(|current_specialized_shader| {
+                            current_specialized_shader.specialization == *shader_specialization
+                        })
                 })
         {
             // if shader has already been compiled with current configuration, use existing shader
             Ok(specialized_shader.shader.clone_weak())
         } else {
             // if no shader exists with the current configuration, create new shader and compile
+            let shader = shaders.get(shader_handle).unwrap();
             let shader_def_vec = shader_specialization
                 .shader_defs
                 .iter()
@@ -99,13 +248,12 @@ impl PipelineCompiler {
                 .collect::<Vec<String>>();
             let compiled_shader =
                 render_resource_context.get_specialized_shader(shader, Some(&shader_def_vec))?;
-            let specialized_handle = shaders.add(compiled_shader);
-            let weak_specialized_handle = specialized_handle.clone_weak();
-            specialized_shaders.push(SpecializedShader {
-                shader: specialized_handle,
-                specialization: shader_specialization.clone(),
-            });
-            Ok(weak_specialized_handle)
+            Ok(self.add_specialized_shader(
+                shaders,
+                shader_handle,
+                shader_specialization,
+                compiled_shader,
+            ))
         }
     }
 
@@ -126,6 +274,52 @@ impl PipelineCompiler {
             .map(|specialized_pipeline| specialized_pipeline.pipeline.clone_weak())
     }
 
+    /// Compiles the pipeline if its shaders are compiled, and otherwise returns `None` while they
+    /// are compiled in the background. Falls back to [`PipelineCompiler::compile_pipeline`] if
+    /// compilation isn't asynchronous, there is no task pool, or the render backend doesn't
+    /// provide a [`ShaderCompiler`].
+    pub fn try_compile_pipeline(
+        &mut self,
+        render_resource_context: &dyn RenderResourceContext,
+        pipelines: &mut Assets<PipelineDescriptor>,
+        shaders: &mut Assets<Shader>,
+        task_pool: Option<&TaskPool>,
+        source_pipeline: &Handle<PipelineDescriptor>,
+        pipeline_specialization: &PipelineSpecialization,
+    ) -> Option<Handle<PipelineDescriptor>> {
+        if let (true, Some(task_pool), Some(compiler)) = (
+            self.asynchronous,
+            task_pool,
+            render_resource_context.shader_compiler(),
+        ) {
+            let shader_stages = &pipelines.get(source_pipeline).unwrap().shader_stages;
+            let stage_shaders = std::iter::once(shader_stages.vertex.clone_weak())
+                .chain(shader_stages.fragment.as_ref().map(Handle::clone_weak))
+                .collect::<Vec<_>>();
+            let mut ready = true;
+            for shader in stage_shaders.iter() {
+                ready &= self.poll_shader(
+                    shaders,
+                    task_pool,
+                    compiler,
+                    shader,
+                    &pipeline_specialization.shader_specialization,
+                );
+            }
+            if !ready {
+                return None;
+            }
+        }
+
+        Some(self.compile_pipeline(
+            render_resource_context,
+            pipelines,
+            shaders,
+            source_pipeline,
+            pipeline_specialization,
+        ))
+    }
+
     pub fn compile_pipeline(
         &mut self,
         render_resource_context: &dyn RenderResourceContext,
@@ -195,6 +389,9 @@ impl PipelineCompiler {
                 }
             }
         }
+        if !pipeline_specialization.depth_textures.is_empty() {
+            specialize_depth_textures(&mut layout, &pipeline_specialization.depth_textures);
+        }
         specialized_descriptor.layout = Some(layout);
 
         // create a vertex layout that provides all attributes from either the specialized vertex
@@ -306,6 +503,11 @@ impl PipelineCompiler {
 
     /// Update specialized shaders and remove any related specialized
     /// pipelines and assets.
+    ///
+    /// All specializations of the shader are recompiled before anything is replaced, so if
+    /// compilation fails the previously compiled shaders and pipelines stay in use. The
+    /// specializations still compiling in the background, or that failed to compile, are compiled
+    /// again from the new source the next time they are needed.
     pub fn update_shader(
         &mut self,
         shader: &Handle<Shader>,
@@ -313,40 +515,51 @@ impl PipelineCompiler {
         shaders: &mut Assets<Shader>,
         render_resource_context: &dyn RenderResourceContext,
     ) -> Result<(), ShaderError> {
-        if let Some(specialized_shaders) = self.specialized_shaders.get_mut(shader) {
-            for specialized_shader in specialized_shaders {
-                // Recompile specialized shader. If it fails, we bail immediately.
-                let shader_def_vec = specialized_shader
-                    .specialization
-                    .shader_defs
-                    .iter()
-                    .cloned()
-                    .collect::<Vec<String>>();
-                let new_handle =
-                    shaders.add(render_resource_context.get_specialized_shader(
-                        shaders.get(shader).unwrap(),
-                        Some(&shader_def_vec),
-                    )?);
-
-                // Replace handle and remove old from assets.
-                let old_handle = std::mem::replace(&mut specialized_shader.shader, new_handle);
-                shaders.remove(&old_handle);
-
-                // Find source pipelines that use the old specialized
-                // shader, and remove from tracking.
-                if let Some(source_pipelines) =
-                    self.specialized_shader_pipelines.remove(&old_handle)
-                {
-                    // Remove all specialized pipelines from tracking
-                    // and asset storage. They will be rebuilt on next
-                    // draw.
-                    for source_pipeline in source_pipelines {
-                        if let Some(specialized_pipelines) =
-                            self.specialized_pipelines.remove(&source_pipeline)
-                        {
-                            for p in specialized_pipelines {
-                                pipelines.remove(p.pipeline);
-                            }
+        self.pending_shaders
+            .retain(|pending_shader| pending_shader.shader != *shader);
+        let specialized_shaders =
+            if let Some(specialized_shaders) = self.specialized_shaders.get_mut(shader) {
+                specialized_shaders
+            } else {
+                return Ok(());
+            };
+
+        // Recompile every specialized shader first. If any of them fails, we bail without
+        // touching the existing ones.
+        let mut compiled_shaders = Vec::with_capacity(specialized_shaders.len());
+        for specialized_shader in specialized_shaders.iter() {
+            let shader_def_vec = specialized_shader
+                .specialization
+                .shader_defs
+                .iter()
+                .cloned()
+                .collect::<Vec<String>>();
+            compiled_shaders.push(
+                render_resource_context
+                    .get_specialized_shader(shaders.get(shader).unwrap(), Some(&shader_def_vec))?,
+            );
+        }
+
+        for (specialized_shader, compiled_shader) in
+            specialized_shaders.iter_mut().zip(compiled_shaders)
+        {
+            // Replace handle and remove old from assets.
+            let new_handle = shaders.add(compiled_shader);
+            let old_handle = std::mem::replace(&mut specialized_shader.shader, new_handle);
+            shaders.remove(&old_handle);
+
+            // Find source pipelines that use the old specialized
+            // shader, and remove from tracking.
+            if let Some(source_pipelines) = self.specialized_shader_pipelines.remove(&old_handle) {
+                // Remove all specialized pipelines from tracking
+                // and asset storage. They will be rebuilt on next
+                // draw.
+                for source_pipeline in source_pipelines {
+                    if let Some(specialized_pipelines) =
+                        self.specialized_pipelines.remove(&source_pipeline)
+                    {
+                        for p in specialized_pipelines {
+                            pipelines.remove(p.pipeline);
                         }
                     }
                 }
@@ -365,3 +578,121 @@ fn panic_shader_error(error: ShaderError) -> ! {
         .trim_end();
     panic!("{}\n", msg);
 }
+
+/// Samples the textures named in `depth_textures` as depth, and their `*_sampler` samplers without
+/// filtering, as shader reflection can't tell depth textures apart.
+fn specialize_depth_textures(layout: &mut PipelineLayout, depth_textures: &HashSet<String>) {
+    for bind_group in layout.bind_groups.iter_mut() {
+        let mut binding_changed = false;
+        for binding in bind_group.bindings.iter_mut() {
+            match binding.bind_type {
+                BindType::Texture {
+                    ref mut sample_type,
+                    ..
+                } if depth_textures.contains(&binding.name) => {
+                    *sample_type = TextureSampleType::Depth;
+                    binding_changed = true;
+                }
+                BindType::Sampler {
+                    ref mut filtering, ..
+                } if binding
+                    .name
+                    .strip_suffix("_sampler")
+                    .map_or(false, |texture| depth_textures.contains(texture)) =>
+                {
+                    *filtering = false;
+                    binding_changed = true;
+                }
+                _ => {}
+            }
+        }
+
+        if binding_changed {
+            bind_group.update_id();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::specialize_depth_textures;
+    use crate::{
+        pipeline::BindType,
+        shader::{Shader, ShaderStage},
+        texture::{TextureSampleType, TextureViewDimension},
+    };
+    use bevy_utils::HashSet;
+
+    #[test]
+    fn depth_texture_specialization() {
+        let fragment_shader = Shader::from_glsl(
+            ShaderStage::Fragment,
+            r#"
+            #version 450
+            layout(location = 0) out vec4 o_Target;
+            layout(set = 0, binding = 0) uniform texture2D MainPass_depth;
+            layout(set = 0, binding = 1) uniform sampler MainPass_depth_sampler;
+            layout(set = 0, binding = 2) uniform texture2D Color;
+            layout(set = 0, binding = 3) uniform sampler Color_sampler;
+
+            void main() {
+                float depth = texelFetch(
+                    sampler2D(MainPass_depth, MainPass_depth_sampler),
+                    ivec2(gl_FragCoord.xy),
+                    0
+                ).r;
+                o_Target = vec4(depth) * texture(sampler2D(Color, Color_sampler), vec2(0.5));
+            }
+        "#,
+        )
+        .get_spirv_shader(None)
+        .unwrap();
+
+        let mut layout = fragment_shader.reflect_layout(true).unwrap();
+        let reflected_id = layout.bind_groups[0].id;
+        let mut depth_textures = HashSet::default();
+        depth_textures.insert("MainPass_depth".to_string());
+        specialize_depth_textures(&mut layout, &depth_textures);
+        assert_ne!(layout.bind_groups[0].id, reflected_id);
+
+        let bind_type = |name: &str| {
+            layout.bind_groups[0]
+                .bindings
+                .iter()
+                .find(|binding| binding.name == name)
+                .map(|binding| binding.bind_type.clone())
+                .unwrap()
+        };
+        assert_eq!(
+            bind_type("MainPass_depth"),
+            BindType::Texture {
+                multisampled: false,
+                view_dimension: TextureViewDimension::D2,
+                sample_type: TextureSampleType::Depth,
+            }
+        );
+        assert_eq!(
+            bind_type("MainPass_depth_sampler"),
+            BindType::Sampler {
+                comparison: false,
+                filtering: false,
+            }
+        );
+        // other textures keep their reflected bind types
+        assert_eq!(
+            bind_type("Color"),
+            BindType::Texture {
+                multisampled: false,
+                view_dimension: TextureViewDimension::D2,
+                sample_type: TextureSampleType::Float { filterable: true },
+            }
+        );
+        assert_eq!(
+            bind_type("Color_sampler"),
+            BindType::Sampler {
+                comparison: false,
+                filtering: true,
+            }
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use super::{PipelineDescriptor, PipelineSpecialization};
 use crate::{
-    draw::{Draw, DrawContext, OutsideFrustum},
+    draw::{Draw, DrawContext, DrawError, OutsideFrustum},
     mesh::{Indices, Mesh},
     prelude::{Msaa, Visible},
     renderer::RenderResourceBindings,
@@ -92,6 +92,10 @@ pub fn draw_render_pipelines_system(
         Without<OutsideFrustum>,
     >,
 ) {
+    let global_depth_textures = render_resource_bindings
+        .iter_depth_textures(&**draw_context.render_resource_context)
+        .map(|name| name.to_string())
+        .collect::<HashSet<String>>();
     for (mut draw, mut render_pipelines, mesh_handle, visible) in query.iter_mut() {
         if !visible.is_visible {
             continue;
@@ -111,8 +115,18 @@ pub fn draw_render_pipelines_system(
         };
 
         let render_pipelines = &mut *render_pipelines;
+        let mut depth_textures = global_depth_textures.clone();
+        depth_textures.extend(
+            render_pipelines
+                .bindings
+                .iter_depth_textures(&**draw_context.render_resource_context)
+                .map(|name| name.to_string()),
+        );
         for pipeline in render_pipelines.pipelines.iter_mut() {
             pipeline.specialization.sample_count = msaa.samples;
+            if pipeline.specialization.depth_textures != depth_textures {
+                pipeline.specialization.depth_textures = depth_textures.clone();
+            }
             if pipeline.dynamic_bindings_generation
                 != render_pipelines.bindings.dynamic_bindings_generation()
             {
@@ -144,13 +158,15 @@ pub fn draw_render_pipelines_system(
                 &mut render_pipelines.bindings,
                 &mut render_resource_bindings,
             ];
-            draw_context
-                .set_pipeline(
-                    &mut draw,
-                    &render_pipeline.pipeline,
-                    &render_pipeline.specialization,
-                )
-                .unwrap();
+            match draw_context.set_pipeline(
+                &mut draw,
+                &render_pipeline.pipeline,
+                &render_pipeline.specialization,
+            ) {
+                // skip the pipeline until its shaders are compiled
+                Err(DrawError::PipelineCompiling) => continue,
+                result => result.unwrap(),
+            }
             draw_context
                 .set_bind_groups_from_bindings(&mut draw, render_resource_bindings)
                 .unwrap();
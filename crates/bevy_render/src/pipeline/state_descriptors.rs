@@ -103,6 +103,19 @@ impl Default for PrimitiveTopology {
     }
 }
 
+impl PrimitiveTopology {
+    /// Returns the number of primitives assembled from `vertices` vertices (or indices).
+    pub fn primitive_count(&self, vertices: usize) -> usize {
+        match self {
+            PrimitiveTopology::PointList => vertices,
+            PrimitiveTopology::LineList => vertices / 2,
+            PrimitiveTopology::LineStrip => vertices.saturating_sub(1),
+            PrimitiveTopology::TriangleList => vertices / 3,
+            PrimitiveTopology::TriangleStrip => vertices.saturating_sub(2),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub enum FrontFace {
     Ccw = 0,
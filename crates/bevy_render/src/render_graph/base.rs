@@ -1,8 +1,9 @@
 use super::{
-    CameraNode, PassNode, RenderGraph, SharedBuffersNode, TextureCopyNode, WindowSwapChainNode,
-    WindowTextureNode,
+    CameraNode, PassNode, RenderGraph, RenderGraphError, SharedBuffersNode, TextureCopyNode,
+    WindowSwapChainNode, WindowTextureNode,
 };
 use crate::{
+    camera::ActiveCameras,
     pass::{
         LoadOp, Operations, PassDescriptor, RenderPassColorAttachment,
         RenderPassDepthStencilAttachment, TextureAttachment,
@@ -19,6 +20,10 @@ use bevy_window::WindowId;
 #[reflect(Component)]
 pub struct MainPass;
 
+/// The multisample anti-aliasing settings of every camera and window.
+///
+/// The sample count is shared by the attachments of a pass and by the pipelines specialized for
+/// it, so it can't be overridden per camera.
 #[derive(Debug)]
 pub struct Msaa {
     pub samples: u32,
@@ -126,7 +131,8 @@ pub(crate) fn add_base_graph(config: &BaseRenderGraphConfig, world: &mut World)
                     dimension: TextureDimension::D2,
                     format: TextureFormat::Depth32Float, /* PERF: vulkan docs recommend using 24
                                                           * bit depth for better performance */
-                    usage: TextureUsage::OUTPUT_ATTACHMENT,
+                    // sampled by passes that read the scene depth, such as decals
+                    usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::SAMPLED,
                 },
             ),
         );
@@ -246,3 +252,24 @@ pub(crate) fn add_base_graph(config: &BaseRenderGraphConfig, world: &mut World)
             .unwrap();
     }
 }
+
+/// Renders the camera named `camera_name` in the main pass, in addition to the default 2d and 3d
+/// cameras. Cameras sharing the main pass can be given a [`Viewport`](crate::camera::Viewport),
+/// for example for split-screen.
+pub fn add_main_pass_camera(world: &mut World, camera_name: &str) -> Result<(), RenderGraphError> {
+    let world = world.cell();
+    let mut graph = world.get_resource_mut::<RenderGraph>().unwrap();
+    graph
+        .get_node_mut::<PassNode<&'static MainPass>>(node::MAIN_PASS)?
+        .add_camera(camera_name);
+    graph.add_system_node(
+        camera_name.to_string(),
+        CameraNode::new(camera_name.to_string()),
+    );
+    graph.add_node_edge(camera_name.to_string(), node::MAIN_PASS)?;
+    world
+        .get_resource_mut::<ActiveCameras>()
+        .unwrap()
+        .add(camera_name);
+    Ok(())
+}
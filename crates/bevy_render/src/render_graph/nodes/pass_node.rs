@@ -1,20 +1,22 @@
 use crate::{
-    camera::{ActiveCameras, VisibleEntities},
-    draw::{Draw, RenderCommand},
+    camera::{ActiveCameras, Camera, Viewport, VisibleEntities},
+    color::Color,
+    draw::{Draw, DrawStatistics, RenderCommand, Visible},
     pass::{ClearColor, LoadOp, PassDescriptor, TextureAttachment},
-    pipeline::{IndexFormat, PipelineDescriptor},
+    pipeline::{IndexFormat, PipelineDescriptor, PrimitiveTopology},
     render_graph::{Node, ResourceSlotInfo, ResourceSlots},
     renderer::{
         BindGroupId, BufferId, RenderContext, RenderResourceBindings, RenderResourceContext,
         RenderResourceType,
     },
 };
-use bevy_asset::{Assets, Handle};
+use bevy_asset::{Assets, Handle, HandleId};
 use bevy_ecs::{
     query::{QueryState, ReadOnlyFetch, WorldQuery},
     world::{Mut, World},
 };
 use bevy_utils::{tracing::debug, HashMap};
+use bevy_window::Windows;
 use std::fmt;
 
 pub struct PassNode<Q: WorldQuery> {
@@ -25,6 +27,8 @@ pub struct PassNode<Q: WorldQuery> {
     color_resolve_target_indices: Vec<Option<usize>>,
     depth_stencil_attachment_input_index: Option<usize>,
     default_clear_color_inputs: Vec<usize>,
+    /// The [`ClearColor`] of the first camera of the pass that has one, overriding the resource
+    camera_clear_color: Option<Color>,
     query_state: Option<QueryState<Q>>,
     commands: Vec<RenderCommand>,
 }
@@ -101,6 +105,7 @@ impl<Q: WorldQuery> PassNode<Q> {
             color_resolve_target_indices,
             depth_stencil_attachment_input_index,
             default_clear_color_inputs: Vec::new(),
+            camera_clear_color: None,
             query_state: None,
             commands: Vec::new(),
         }
@@ -110,6 +115,8 @@ impl<Q: WorldQuery> PassNode<Q> {
         self.cameras.push(camera_name.to_string());
     }
 
+    /// Clears the color attachment at `color_attachment_index` with the [`ClearColor`] of the
+    /// first camera of the pass that has one, or else with the [`ClearColor`] resource.
     pub fn use_default_clear_color(&mut self, color_attachment_index: usize) {
         self.default_clear_color_inputs.push(color_attachment_index);
     }
@@ -127,13 +134,17 @@ where
         let query_state = self.query_state.get_or_insert_with(|| world.query());
         let cameras = &self.cameras;
         let commands = &mut self.commands;
+        let camera_clear_color = &mut self.camera_clear_color;
+        *camera_clear_color = None;
         world.resource_scope(|world, mut active_cameras: Mut<ActiveCameras>| {
-            let mut pipeline_camera_commands = HashMap::default();
             let pipelines = world.get_resource::<Assets<PipelineDescriptor>>().unwrap();
+            let windows = world.get_resource::<Windows>();
             let render_resource_context = &**world
                 .get_resource::<Box<dyn RenderResourceContext>>()
                 .unwrap();
 
+            // whether the previous camera restricted the viewport, which must then be reset
+            let mut has_viewport = false;
             for camera_name in cameras.iter() {
                 let active_camera = if let Some(active_camera) = active_cameras.get_mut(camera_name)
                 {
@@ -142,12 +153,42 @@ where
                     continue;
                 };
 
-                let visible_entities = if let Some(entity) = active_camera.entity {
-                    world.get::<VisibleEntities>(entity).unwrap()
+                let camera_entity = if let Some(entity) = active_camera.entity {
+                    entity
                 } else {
                     continue;
                 };
-                for visible_entity in visible_entities.iter() {
+                let visible_entities = world.get::<VisibleEntities>(camera_entity).unwrap();
+                if camera_clear_color.is_none() {
+                    *camera_clear_color = world
+                        .get::<ClearColor>(camera_entity)
+                        .map(|clear_color| clear_color.0);
+                }
+
+                let viewport = world.get::<Viewport>(camera_entity);
+                if viewport.is_some() || has_viewport {
+                    let window = world
+                        .get::<Camera>(camera_entity)
+                        .and_then(|camera| windows?.get(camera.window));
+                    if let Some(window) = window {
+                        let (x, y, width, height) = viewport
+                            .copied()
+                            .unwrap_or_default()
+                            .physical_rect(window.physical_width(), window.physical_height());
+                        commands.push(RenderCommand::SetViewport {
+                            x,
+                            y,
+                            width,
+                            height,
+                        });
+                        has_viewport = viewport.is_some();
+                    }
+                }
+
+                // the camera bind groups of each pipeline, which differ from one camera to the next
+                let mut pipeline_camera_commands = HashMap::default();
+                let mut draws = Vec::new();
+                for (index, visible_entity) in visible_entities.iter().enumerate() {
                     if query_state.get(world, visible_entity.entity).is_err() {
                         // visible entity does not match the Pass query
                         continue;
@@ -159,6 +200,18 @@ where
                         continue;
                     };
 
+                    let is_transparent = world
+                        .get::<Visible>(visible_entity.entity)
+                        .map_or(false, |visible| visible.is_transparent);
+                    draws.push((DrawSortKey::new(draw, index, is_transparent), draw));
+                }
+
+                // batch opaque draws by pipeline, then bind groups, then vertex buffer to reduce
+                // state changes. the sort is stable, so draws sharing the same state keep their
+                // front-to-back order. transparent draws keep their back-to-front order.
+                draws.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                for (_, draw) in draws {
                     for render_command in draw.render_commands.iter() {
                         commands.push(render_command.clone());
                         // whenever a new pipeline is set, ensure the relevant camera bind groups
@@ -206,8 +259,13 @@ where
     ) {
         for (i, color_attachment) in self.descriptor.color_attachments.iter_mut().enumerate() {
             if self.default_clear_color_inputs.contains(&i) {
-                if let Some(default_clear_color) = world.get_resource::<ClearColor>() {
-                    color_attachment.ops.load = LoadOp::Clear(default_clear_color.0);
+                let clear_color = self.camera_clear_color.or_else(|| {
+                    world
+                        .get_resource::<ClearColor>()
+                        .map(|clear_color| clear_color.0)
+                });
+                if let Some(clear_color) = clear_color {
+                    color_attachment.ops.load = LoadOp::Clear(clear_color);
                 }
             }
             if let Some(input_index) = self.color_attachment_input_indices[i] {
@@ -234,6 +292,8 @@ where
         let pipelines = world.get_resource::<Assets<PipelineDescriptor>>().unwrap();
 
         let mut draw_state = DrawState::default();
+        let mut draw_counts = DrawCounts::default();
+        let mut topology = PrimitiveTopology::default();
         let commands = &mut self.commands;
         render_context.begin_pass(
             &self.descriptor,
@@ -246,7 +306,9 @@ where
                             continue;
                         }
                         render_pass.set_pipeline(&pipeline);
+                        draw_counts.pipeline_changes += 1;
                         let descriptor = pipelines.get(&pipeline).unwrap();
+                        topology = descriptor.primitive.topology;
                         draw_state.set_pipeline(&pipeline, descriptor);
                     }
                     RenderCommand::DrawIndexed {
@@ -260,6 +322,9 @@ where
                                 base_vertex,
                                 instances.clone(),
                             );
+                            draw_counts.draw_calls += 1;
+                            draw_counts.primitives += topology.primitive_count(indices.len())
+                                * instances.len();
                         } else {
                             debug!("Could not draw indexed because the pipeline layout wasn't fully set for pipeline: {:?}", draw_state.pipeline);
                         }
@@ -267,6 +332,9 @@ where
                     RenderCommand::Draw { vertices, instances } => {
                         if draw_state.can_draw() {
                             render_pass.draw(vertices.clone(), instances.clone());
+                            draw_counts.draw_calls += 1;
+                            draw_counts.primitives += topology.primitive_count(vertices.len())
+                                * instances.len();
                         } else {
                             debug!("Could not draw because the pipeline layout wasn't fully set for pipeline: {:?}", draw_state.pipeline);
                         }
@@ -280,6 +348,7 @@ where
                             continue;
                         }
                         render_pass.set_vertex_buffer(slot, buffer, offset);
+                        draw_counts.buffer_changes += 1;
                         draw_state.set_vertex_buffer(slot, buffer, offset);
                     }
                     RenderCommand::SetIndexBuffer { buffer, offset, index_format } => {
@@ -287,8 +356,24 @@ where
                             continue;
                         }
                         render_pass.set_index_buffer(buffer, offset, index_format);
+                        draw_counts.buffer_changes += 1;
                         draw_state.set_index_buffer(buffer, offset, index_format);
                     }
+                    RenderCommand::SetViewport {
+                        x,
+                        y,
+                        width,
+                        height,
+                    } => {
+                        render_pass.set_viewport(
+                            x as f32,
+                            y as f32,
+                            width as f32,
+                            height as f32,
+                            0.0,
+                            1.0,
+                        );
+                    }
                     RenderCommand::SetBindGroup {
                         index,
                         bind_group,
@@ -306,14 +391,76 @@ where
                             bind_group,
                             dynamic_uniform_indices.as_deref()
                         );
+                        draw_counts.bind_group_changes += 1;
                         draw_state.set_bind_group(index, bind_group);
                     }
                 }
             }
         });
+
+        if let Some(draw_statistics) = world.get_resource::<DrawStatistics>() {
+            draw_statistics.record_pass(
+                draw_counts.draw_calls,
+                draw_counts.primitives,
+                draw_counts.pipeline_changes,
+                draw_counts.bind_group_changes,
+                draw_counts.buffer_changes,
+            );
+        }
     }
 }
 
+/// The order in which entity draws are submitted within a pass.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+struct DrawSortKey {
+    /// `None` for opaque draws, which are batched. Transparent draws are sorted after all opaque
+    /// draws, in their visible entity order.
+    transparent_index: Option<usize>,
+    pipeline: Option<HandleId>,
+    bind_groups: Vec<(u32, BindGroupId)>,
+    vertex_buffer: Option<BufferId>,
+}
+
+impl DrawSortKey {
+    fn new(draw: &Draw, index: usize, is_transparent: bool) -> Self {
+        let mut key = DrawSortKey {
+            transparent_index: if is_transparent { Some(index) } else { None },
+            pipeline: None,
+            bind_groups: Vec::new(),
+            vertex_buffer: None,
+        };
+        if is_transparent {
+            return key;
+        }
+
+        for render_command in draw.render_commands.iter() {
+            match render_command {
+                RenderCommand::SetPipeline { pipeline } if key.pipeline.is_none() => {
+                    key.pipeline = Some(pipeline.id);
+                }
+                RenderCommand::SetBindGroup {
+                    index, bind_group, ..
+                } => key.bind_groups.push((*index, *bind_group)),
+                RenderCommand::SetVertexBuffer { buffer, .. } if key.vertex_buffer.is_none() => {
+                    key.vertex_buffer = Some(*buffer);
+                }
+                _ => {}
+            }
+        }
+        key
+    }
+}
+
+/// The number of draw calls and state changes issued by a single pass.
+#[derive(Debug, Default)]
+struct DrawCounts {
+    draw_calls: usize,
+    primitives: usize,
+    pipeline_changes: usize,
+    bind_group_changes: usize,
+    buffer_changes: usize,
+}
+
 /// Tracks the current pipeline state to ensure draw calls are valid.
 #[derive(Debug, Default)]
 struct DrawState {
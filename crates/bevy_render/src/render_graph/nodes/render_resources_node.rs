@@ -433,7 +433,7 @@ fn render_resources_node_system<T: RenderResources>(
     mut state: Local<RenderResourcesNodeState<Entity, T>>,
     mut entities_waiting_for_textures: Local<Vec<Entity>>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
-    removed: RemovedComponents<T>,
+    mut removed: RemovedComponents<T>,
     mut queries: QuerySet<(
         Query<(Entity, &T, &Visible, &mut RenderPipelines), Or<(Changed<T>, Changed<Visible>)>>,
         Query<(Entity, &T, &Visible, &mut RenderPipelines)>,
@@ -619,7 +619,7 @@ fn asset_render_resources_node_system<T: RenderResources + Asset>(
     mut asset_events: EventReader<AssetEvent<T>>,
     mut asset_render_resource_bindings: ResMut<AssetRenderResourceBindings>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
-    removed_handles: RemovedComponents<Handle<T>>,
+    mut removed_handles: RemovedComponents<Handle<T>>,
     mut queries: QuerySet<(
         Query<(&Handle<T>, &mut RenderPipelines), Changed<Handle<T>>>,
         Query<&mut RenderPipelines, With<Handle<T>>>,
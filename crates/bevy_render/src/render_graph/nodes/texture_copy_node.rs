@@ -1,6 +1,6 @@
 use crate::{
-    render_graph::{Node, ResourceSlots},
-    renderer::{BufferInfo, BufferUsage, RenderContext},
+    render_graph::{Node, ResourceSlots, TransientResources},
+    renderer::{BufferInfo, BufferMapMode, BufferUsage, RenderContext},
     texture::{Texture, TextureDescriptor, TEXTURE_ASSET_INDEX},
 };
 use bevy_app::{Events, ManualEventReader};
@@ -23,6 +23,7 @@ impl Node for TextureCopyNode {
     ) {
         let texture_events = world.get_resource::<Events<AssetEvent<Texture>>>().unwrap();
         let textures = world.get_resource::<Assets<Texture>>().unwrap();
+        let transient_resources = world.get_resource::<TransientResources>().unwrap();
         let mut copied_textures = HashSet::default();
         for event in self.texture_event_reader.iter(&texture_events) {
             match event {
@@ -54,13 +55,25 @@ impl Node for TextureCopyNode {
                                 aligned_data[offset..(offset + width * format_size)]
                                     .copy_from_slice(row);
                             });
-                        let texture_buffer = render_context.resources().create_buffer_with_data(
+                        // staging buffers are pooled by power of two sizes, so that they can be
+                        // reused by textures of similar sizes
+                        let render_resource_context = render_context.resources();
+                        let lease = transient_resources.acquire_buffer(
+                            render_resource_context,
                             BufferInfo {
-                                buffer_usage: BufferUsage::COPY_SRC,
+                                size: aligned_data.len().next_power_of_two(),
+                                buffer_usage: BufferUsage::COPY_SRC | BufferUsage::MAP_WRITE,
                                 ..Default::default()
                             },
-                            &aligned_data,
                         );
+                        let texture_buffer = transient_resources.buffer(lease).unwrap();
+                        render_resource_context.map_buffer(texture_buffer, BufferMapMode::Write);
+                        render_resource_context.write_mapped_buffer(
+                            texture_buffer,
+                            0..aligned_data.len() as u64,
+                            &mut |data, _renderer| data.copy_from_slice(&aligned_data),
+                        );
+                        render_resource_context.unmap_buffer(texture_buffer);
 
                         let texture_resource = render_context
                             .resources()
@@ -76,7 +89,7 @@ impl Node for TextureCopyNode {
                             0,
                             texture_descriptor.size,
                         );
-                        render_context.resources().remove_buffer(texture_buffer);
+                        transient_resources.release_buffer(lease).unwrap();
 
                         copied_textures.insert(&handle.id);
                     }
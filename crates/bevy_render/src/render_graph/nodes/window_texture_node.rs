@@ -1,5 +1,5 @@
 use crate::{
-    render_graph::{Node, ResourceSlotInfo, ResourceSlots},
+    render_graph::{Node, ResourceSlotInfo, ResourceSlots, TransientResources, TransientTexture},
     renderer::{RenderContext, RenderResourceId, RenderResourceType},
     texture::TextureDescriptor,
 };
@@ -8,9 +8,12 @@ use bevy_ecs::world::World;
 use bevy_window::{WindowCreated, WindowId, WindowResized, Windows};
 use std::borrow::Cow;
 
+/// A Render Graph [Node] that outputs a texture the size of a window, leased from the
+/// [`TransientResources`] pool and replaced when the window is resized.
 pub struct WindowTextureNode {
     window_id: WindowId,
     descriptor: TextureDescriptor,
+    texture: Option<TransientTexture>,
     window_created_event_reader: ManualEventReader<WindowCreated>,
     window_resized_event_reader: ManualEventReader<WindowResized>,
 }
@@ -22,6 +25,7 @@ impl WindowTextureNode {
         WindowTextureNode {
             window_id,
             descriptor,
+            texture: None,
             window_created_event_reader: Default::default(),
             window_resized_event_reader: Default::default(),
         }
@@ -48,6 +52,7 @@ impl Node for WindowTextureNode {
         let window_created_events = world.get_resource::<Events<WindowCreated>>().unwrap();
         let window_resized_events = world.get_resource::<Events<WindowResized>>().unwrap();
         let windows = world.get_resource::<Windows>().unwrap();
+        let transient_resources = world.get_resource::<TransientResources>().unwrap();
 
         let window = windows
             .get(self.window_id)
@@ -62,15 +67,21 @@ impl Node for WindowTextureNode {
                 .iter(&window_resized_events)
                 .any(|e| e.id == window.id())
         {
-            let render_resource_context = render_context.resources_mut();
-            if let Some(RenderResourceId::Texture(old_texture)) = output.get(WINDOW_TEXTURE) {
-                render_resource_context.remove_texture(old_texture);
+            if let Some(old_texture) = self.texture.take() {
+                transient_resources
+                    .release_texture(old_texture)
+                    .expect("The window texture was released by another node.");
             }
 
             self.descriptor.size.width = window.physical_width().max(1);
             self.descriptor.size.height = window.physical_height().max(1);
-            let texture_resource = render_resource_context.create_texture(self.descriptor);
-            output.set(WINDOW_TEXTURE, RenderResourceId::Texture(texture_resource));
+            let texture =
+                transient_resources.acquire_texture(render_context.resources(), self.descriptor);
+            self.texture = Some(texture);
+            output.set(
+                WINDOW_TEXTURE,
+                RenderResourceId::Texture(transient_resources.texture(texture).unwrap()),
+            );
         }
     }
 }
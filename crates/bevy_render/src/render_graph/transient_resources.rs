@@ -0,0 +1,363 @@
+use crate::{
+    renderer::{BufferId, BufferInfo, RenderResourceContext, TextureId},
+    texture::TextureDescriptor,
+};
+use bevy_ecs::system::Res;
+use parking_lot::Mutex;
+use thiserror::Error;
+
+#[derive(Error, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransientResourceError {
+    #[error("the transient resource was released, and may be in use by another node")]
+    Released,
+    #[error("the resource does not belong to the transient resource pool")]
+    Unknown,
+}
+
+/// A texture leased from the [`TransientResources`] pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransientTexture {
+    id: TextureId,
+    lease: u64,
+}
+
+/// A buffer leased from the [`TransientResources`] pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransientBuffer {
+    id: BufferId,
+    lease: u64,
+}
+
+#[derive(Debug)]
+struct PooledResource<D, I> {
+    descriptor: D,
+    id: I,
+    /// The lease currently holding the resource, if any
+    lease: Option<u64>,
+    /// The frame the resource was last leased or released in
+    last_used_frame: u64,
+}
+
+#[derive(Debug)]
+struct Pool<D, I> {
+    resources: Vec<PooledResource<D, I>>,
+}
+
+impl<D, I> Default for Pool<D, I> {
+    fn default() -> Self {
+        Pool {
+            resources: Vec::new(),
+        }
+    }
+}
+
+impl<D: PartialEq, I: Copy + PartialEq> Pool<D, I> {
+    /// Leases a resource of the given descriptor released before `frame`, if any.
+    fn acquire(&mut self, descriptor: &D, lease: u64, frame: u64) -> Option<I> {
+        let resource = self.resources.iter_mut().find(|resource| {
+            resource.lease.is_none()
+                && resource.last_used_frame < frame
+                && resource.descriptor == *descriptor
+        })?;
+        resource.lease = Some(lease);
+        resource.last_used_frame = frame;
+        Some(resource.id)
+    }
+
+    fn insert(&mut self, descriptor: D, id: I, lease: u64, frame: u64) {
+        self.resources.push(PooledResource {
+            descriptor,
+            id,
+            lease: Some(lease),
+            last_used_frame: frame,
+        });
+    }
+
+    fn get(&self, id: I, lease: u64) -> Result<&PooledResource<D, I>, TransientResourceError> {
+        let resource = self
+            .resources
+            .iter()
+            .find(|resource| resource.id == id)
+            .ok_or(TransientResourceError::Unknown)?;
+        if resource.lease == Some(lease) {
+            Ok(resource)
+        } else {
+            Err(TransientResourceError::Released)
+        }
+    }
+
+    fn release(&mut self, id: I, lease: u64, frame: u64) -> Result<(), TransientResourceError> {
+        self.get(id, lease)?;
+        let resource = self
+            .resources
+            .iter_mut()
+            .find(|resource| resource.id == id)
+            .unwrap();
+        resource.lease = None;
+        resource.last_used_frame = frame;
+        Ok(())
+    }
+
+    /// Removes the resources that haven't been leased since `frame`.
+    fn evict(&mut self, frame: u64, mut remove: impl FnMut(I)) {
+        self.resources.retain(|resource| {
+            let unused = resource.lease.is_none() && resource.last_used_frame < frame;
+            if unused {
+                remove(resource.id);
+            }
+            !unused
+        });
+    }
+}
+
+#[derive(Debug, Default)]
+struct TransientResourcesState {
+    frame: u64,
+    next_lease: u64,
+    textures: Pool<TextureDescriptor, TextureId>,
+    buffers: Pool<BufferInfo, BufferId>,
+}
+
+impl TransientResourcesState {
+    fn next_lease(&mut self) -> u64 {
+        self.next_lease += 1;
+        self.next_lease
+    }
+}
+
+/// A pool of textures and buffers that render graph nodes lease by descriptor, instead of
+/// creating and removing resources every time they need new ones.
+///
+/// Released resources are handed out again from the next frame on, once the commands of the
+/// frame that used them have been submitted, so nodes of the same frame never alias a resource.
+/// Leases are checked: using or releasing a resource through a lease that was already released
+/// is an error, as the resource may now belong to another node. Resources that stay unleased for
+/// [`TransientResources::max_unused_frames`] frames are removed.
+#[derive(Debug)]
+pub struct TransientResources {
+    state: Mutex<TransientResourcesState>,
+    max_unused_frames: u64,
+}
+
+impl Default for TransientResources {
+    fn default() -> Self {
+        TransientResources {
+            state: Default::default(),
+            max_unused_frames: 3,
+        }
+    }
+}
+
+impl TransientResources {
+    pub fn with_max_unused_frames(max_unused_frames: u64) -> Self {
+        TransientResources {
+            max_unused_frames,
+            ..Default::default()
+        }
+    }
+
+    /// The number of frames a released resource is kept in the pool before being removed
+    pub fn max_unused_frames(&self) -> u64 {
+        self.max_unused_frames
+    }
+
+    /// Leases a texture matching `descriptor`, creating it if none is available.
+    pub fn acquire_texture(
+        &self,
+        render_resource_context: &dyn RenderResourceContext,
+        descriptor: TextureDescriptor,
+    ) -> TransientTexture {
+        let mut state = self.state.lock();
+        let lease = state.next_lease();
+        let frame = state.frame;
+        let id = match state.textures.acquire(&descriptor, lease, frame) {
+            Some(id) => id,
+            None => {
+                let id = render_resource_context.create_texture(descriptor);
+                state.textures.insert(descriptor, id, lease, frame);
+                id
+            }
+        };
+        TransientTexture { id, lease }
+    }
+
+    /// Returns the id of a leased texture.
+    pub fn texture(&self, texture: TransientTexture) -> Result<TextureId, TransientResourceError> {
+        let state = self.state.lock();
+        state
+            .textures
+            .get(texture.id, texture.lease)
+            .map(|resource| resource.id)
+    }
+
+    /// Gives a leased texture back to the pool. It must not be used afterwards.
+    pub fn release_texture(&self, texture: TransientTexture) -> Result<(), TransientResourceError> {
+        let mut state = self.state.lock();
+        let frame = state.frame;
+        state.textures.release(texture.id, texture.lease, frame)
+    }
+
+    /// Leases a buffer matching `info`, creating it if none is available. Pooled buffers are
+    /// never mapped at creation: map writable buffers with
+    /// [`RenderResourceContext::map_buffer`] before writing to them.
+    pub fn acquire_buffer(
+        &self,
+        render_resource_context: &dyn RenderResourceContext,
+        info: BufferInfo,
+    ) -> TransientBuffer {
+        let info = BufferInfo {
+            mapped_at_creation: false,
+            ..info
+        };
+        let mut state = self.state.lock();
+        let lease = state.next_lease();
+        let frame = state.frame;
+        let id = match state.buffers.acquire(&info, lease, frame) {
+            Some(id) => id,
+            None => {
+                let id = render_resource_context.create_buffer(info.clone());
+                state.buffers.insert(info, id, lease, frame);
+                id
+            }
+        };
+        TransientBuffer { id, lease }
+    }
+
+    /// Returns the id of a leased buffer.
+    pub fn buffer(&self, buffer: TransientBuffer) -> Result<BufferId, TransientResourceError> {
+        let state = self.state.lock();
+        state
+            .buffers
+            .get(buffer.id, buffer.lease)
+            .map(|resource| resource.id)
+    }
+
+    /// Gives a leased buffer back to the pool. It must not be used afterwards.
+    pub fn release_buffer(&self, buffer: TransientBuffer) -> Result<(), TransientResourceError> {
+        let mut state = self.state.lock();
+        let frame = state.frame;
+        state.buffers.release(buffer.id, buffer.lease, frame)
+    }
+
+    /// The number of textures and buffers in the pool, leased or not
+    pub fn len(&self) -> usize {
+        let state = self.state.lock();
+        state.textures.resources.len() + state.buffers.resources.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Makes the resources released during the current frame available, and removes the ones
+    /// unused for too long.
+    pub fn end_frame(&self, render_resource_context: &dyn RenderResourceContext) {
+        let mut state = self.state.lock();
+        state.frame += 1;
+        if let Some(frame) = state.frame.checked_sub(self.max_unused_frames) {
+            state.textures.evict(frame, |texture| {
+                render_resource_context.remove_texture(texture)
+            });
+            state.buffers.evict(frame, |buffer| {
+                render_resource_context.remove_buffer(buffer)
+            });
+        }
+    }
+}
+
+pub fn transient_resources_system(
+    transient_resources: Res<TransientResources>,
+    render_resource_context: Res<Box<dyn RenderResourceContext>>,
+) {
+    transient_resources.end_frame(&**render_resource_context);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{TransientResourceError, TransientResources};
+    use crate::{
+        renderer::{BufferInfo, BufferUsage, HeadlessRenderResourceContext, RenderResourceContext},
+        texture::TextureDescriptor,
+    };
+
+    #[test]
+    fn reuse_across_frames() {
+        let context = HeadlessRenderResourceContext::default();
+        let resources = TransientResources::default();
+        let descriptor = TextureDescriptor::default();
+
+        let first = resources.acquire_texture(&context, descriptor);
+        let first_id = resources.texture(first).unwrap();
+        resources.release_texture(first).unwrap();
+
+        // released resources aren't aliased within a frame
+        let second = resources.acquire_texture(&context, descriptor);
+        assert_ne!(resources.texture(second).unwrap(), first_id);
+        resources.release_texture(second).unwrap();
+
+        resources.end_frame(&context);
+        let third = resources.acquire_texture(&context, descriptor);
+        assert_eq!(resources.texture(third).unwrap(), first_id);
+        assert_eq!(resources.len(), 2);
+
+        // a different descriptor gets a different resource
+        let info = BufferInfo {
+            size: 16,
+            buffer_usage: BufferUsage::COPY_SRC,
+            ..Default::default()
+        };
+        let buffer = resources.acquire_buffer(&context, info.clone());
+        let buffer_id = resources.buffer(buffer).unwrap();
+        resources.release_buffer(buffer).unwrap();
+        resources.end_frame(&context);
+        let other_buffer = resources.acquire_buffer(&context, BufferInfo { size: 32, ..info });
+        assert_ne!(resources.buffer(other_buffer).unwrap(), buffer_id);
+    }
+
+    #[test]
+    fn stale_leases() {
+        let context = HeadlessRenderResourceContext::default();
+        let resources = TransientResources::default();
+        let descriptor = TextureDescriptor::default();
+
+        let first = resources.acquire_texture(&context, descriptor);
+        resources.release_texture(first).unwrap();
+        assert_eq!(
+            resources.release_texture(first),
+            Err(TransientResourceError::Released)
+        );
+        resources.end_frame(&context);
+
+        // the resource now belongs to another lease
+        let second = resources.acquire_texture(&context, descriptor);
+        assert_eq!(
+            resources.texture(first),
+            Err(TransientResourceError::Released)
+        );
+        assert!(resources.texture(second).is_ok());
+    }
+
+    #[test]
+    fn eviction() {
+        let context = HeadlessRenderResourceContext::default();
+        let resources = TransientResources::with_max_unused_frames(2);
+        let info = BufferInfo {
+            size: 16,
+            buffer_usage: BufferUsage::COPY_SRC,
+            ..Default::default()
+        };
+
+        let kept = resources.acquire_buffer(&context, info.clone());
+        let released = resources.acquire_buffer(&context, info);
+        let released_id = resources.buffer(released).unwrap();
+        resources.release_buffer(released).unwrap();
+        for _ in 0..2 {
+            resources.end_frame(&context);
+            assert_eq!(resources.len(), 2);
+        }
+        resources.end_frame(&context);
+        assert_eq!(resources.len(), 1);
+        assert!(context.get_buffer_info(released_id).is_none());
+        assert!(resources.buffer(kept).is_ok());
+    }
+}
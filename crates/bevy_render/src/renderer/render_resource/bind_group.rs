@@ -6,7 +6,7 @@ use std::{
     sync::Arc,
 };
 
-#[derive(Hash, Eq, PartialEq, Debug, Copy, Clone)]
+#[derive(Hash, Eq, PartialEq, Ord, PartialOrd, Debug, Copy, Clone)]
 pub struct BindGroupId(pub u64);
 
 #[derive(Eq, PartialEq, Debug)]
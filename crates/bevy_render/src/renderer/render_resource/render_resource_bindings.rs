@@ -256,6 +256,22 @@ impl RenderResourceBindings {
             })
             .map(|(name, _)| name.as_str())
     }
+
+    /// The names of the bindings bound to textures with a depth format.
+    pub fn iter_depth_textures<'a>(
+        &'a self,
+        render_resource_context: &'a dyn RenderResourceContext,
+    ) -> impl Iterator<Item = &'a str> {
+        self.bindings
+            .iter()
+            .filter(move |(_, binding)| {
+                binding
+                    .get_texture()
+                    .and_then(|texture| render_resource_context.get_texture_descriptor(texture))
+                    .map_or(false, |descriptor| descriptor.format.is_depth())
+            })
+            .map(|(name, _)| name.as_str())
+    }
 }
 
 #[derive(Debug, Default)]
@@ -296,7 +312,11 @@ impl AssetRenderResourceBindings {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pipeline::{BindType, BindingDescriptor, BindingShaderStage, UniformProperty};
+    use crate::{
+        pipeline::{BindType, BindingDescriptor, BindingShaderStage, UniformProperty},
+        renderer::HeadlessRenderResourceContext,
+        texture::{TextureDescriptor, TextureFormat},
+    };
 
     #[test]
     fn test_bind_groups() {
@@ -377,4 +397,28 @@ mod tests {
             unmatched_bindings.update_bind_group_status(&bind_group_descriptor);
         assert_eq!(unmatched_bind_group_status, BindGroupStatus::NoMatch);
     }
+
+    #[test]
+    fn test_depth_textures() {
+        let render_resource_context = HeadlessRenderResourceContext::default();
+        let depth_texture = render_resource_context.create_texture(TextureDescriptor {
+            format: TextureFormat::Depth32Float,
+            ..Default::default()
+        });
+        let color_texture = render_resource_context.create_texture(TextureDescriptor::default());
+
+        let mut bindings = RenderResourceBindings::default();
+        bindings.set("Shadow", RenderResourceBinding::Texture(depth_texture));
+        bindings.set("Color", RenderResourceBinding::Texture(color_texture));
+        bindings.set(
+            "Color_sampler",
+            RenderResourceBinding::Sampler(SamplerId::new()),
+        );
+        assert_eq!(
+            bindings
+                .iter_depth_textures(&render_resource_context)
+                .collect::<Vec<_>>(),
+            vec!["Shadow"]
+        );
+    }
 }
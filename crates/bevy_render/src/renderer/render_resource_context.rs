@@ -11,6 +11,10 @@ use bevy_window::Window;
 use downcast_rs::{impl_downcast, Downcast};
 use std::ops::Range;
 
+/// Compiles a shader with the given shader defs, without access to the render resource context,
+/// so that it can run on another thread.
+pub type ShaderCompiler = fn(&Shader, Option<&[String]>) -> Result<Shader, ShaderError>;
+
 pub trait RenderResourceContext: Downcast + Send + Sync + 'static {
     fn create_swap_chain(&self, window: &Window);
     fn next_swap_chain_texture(&self, window: &Window) -> TextureId;
@@ -42,10 +46,16 @@ pub trait RenderResourceContext: Downcast + Send + Sync + 'static {
         shader: &Shader,
         macros: Option<&[String]>,
     ) -> Result<Shader, ShaderError>;
+    /// The function used by [`RenderResourceContext::get_specialized_shader`], if shaders can be
+    /// compiled in the background.
+    fn shader_compiler(&self) -> Option<ShaderCompiler> {
+        None
+    }
     fn remove_buffer(&self, buffer: BufferId);
     fn remove_texture(&self, texture: TextureId);
     fn remove_sampler(&self, sampler: SamplerId);
     fn get_buffer_info(&self, buffer: BufferId) -> Option<BufferInfo>;
+    fn get_texture_descriptor(&self, texture: TextureId) -> Option<TextureDescriptor>;
     fn get_aligned_uniform_size(&self, size: usize, dynamic: bool) -> usize;
     fn get_aligned_texture_size(&self, data_size: usize) -> usize;
     fn set_asset_resource_untyped(
@@ -4,7 +4,7 @@ use crate::{
 };
 
 use super::ShaderLayout;
-use bevy_app::EventReader;
+use bevy_app::{EventReader, EventWriter};
 use bevy_asset::{AssetEvent, AssetLoader, Assets, Handle, LoadContext, LoadedAsset};
 use bevy_ecs::system::{Res, ResMut};
 use bevy_reflect::TypeUuid;
@@ -326,23 +326,49 @@ impl AssetLoader for ShaderLoader {
     }
 }
 
+/// Events sent when a modified [Shader] has been recompiled.
+///
+/// Shaders are recompiled when their asset is modified, for example when hot reloading is enabled
+/// with `AssetServer::watch_for_changes`.
+#[derive(Debug)]
+pub enum ShaderEvent {
+    /// All specializations of the shader were recompiled. Pipelines using it will be rebuilt the
+    /// next time they are drawn.
+    Reloaded { handle: Handle<Shader> },
+    /// The shader failed to compile. Pipelines keep using the last successfully compiled version.
+    CompilationFailed {
+        handle: Handle<Shader>,
+        error: ShaderError,
+    },
+}
+
 pub fn shader_update_system(
     mut shaders: ResMut<Assets<Shader>>,
     mut pipelines: ResMut<Assets<PipelineDescriptor>>,
     mut shader_events: EventReader<AssetEvent<Shader>>,
+    mut shader_update_events: EventWriter<ShaderEvent>,
     mut pipeline_compiler: ResMut<PipelineCompiler>,
     render_resource_context: Res<Box<dyn RenderResourceContext>>,
 ) {
     for event in shader_events.iter() {
         match event {
             AssetEvent::Modified { handle } => {
-                if let Err(e) = pipeline_compiler.update_shader(
+                match pipeline_compiler.update_shader(
                     handle,
                     &mut pipelines,
                     &mut shaders,
                     &**render_resource_context,
                 ) {
-                    error!("Failed to update shader: {}", e);
+                    Ok(()) => shader_update_events.send(ShaderEvent::Reloaded {
+                        handle: handle.clone_weak(),
+                    }),
+                    Err(error) => {
+                        error!("Failed to update shader: {}", error);
+                        shader_update_events.send(ShaderEvent::CompilationFailed {
+                            handle: handle.clone_weak(),
+                            error,
+                        });
+                    }
                 }
             }
             // Creating shaders on the fly is unhandled since they
@@ -6,6 +6,7 @@ mod sampler_descriptor;
 mod texture;
 mod texture_descriptor;
 mod texture_dimension;
+mod window_capture;
 
 pub(crate) mod image_texture_conversion;
 
@@ -16,3 +17,4 @@ pub use sampler_descriptor::*;
 pub use texture::*;
 pub use texture_descriptor::*;
 pub use texture_dimension::*;
+pub use window_capture::*;
@@ -41,6 +41,27 @@ impl Default for TextureDescriptor {
     }
 }
 
+impl TextureDescriptor {
+    /// Returns the number of bytes used by the texture, including all of its mip levels and
+    /// samples. This is an estimate of the GPU memory used by the texture, as the actual memory
+    /// layout depends on the backend.
+    pub fn size_in_bytes(&self) -> usize {
+        let texels = (0..self.mip_level_count)
+            .map(|level| {
+                let width = (self.size.width >> level).max(1) as usize;
+                let height = (self.size.height >> level).max(1) as usize;
+                // only 3D textures shrink in depth, other textures have a fixed number of layers
+                let depth = match self.dimension {
+                    TextureDimension::D3 => (self.size.depth_or_array_layers >> level).max(1),
+                    _ => self.size.depth_or_array_layers,
+                } as usize;
+                width * height * depth
+            })
+            .sum::<usize>();
+        texels * self.format.pixel_size() * self.sample_count as usize
+    }
+}
+
 #[derive(Hash, Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd)]
 pub enum StorageTextureAccess {
     /// The texture can only be read in the shader and it must be annotated with `readonly`.
@@ -67,3 +88,31 @@ pub enum StorageTextureAccess {
     /// ```
     ReadWrite,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TextureDescriptor;
+    use crate::texture::{Extent3d, TextureDimension, TextureFormat};
+
+    #[test]
+    fn size_in_bytes() {
+        let descriptor = TextureDescriptor {
+            size: Extent3d::new(4, 4, 2),
+            format: TextureFormat::Rgba8Unorm,
+            ..Default::default()
+        };
+        assert_eq!(descriptor.size_in_bytes(), 4 * 4 * 2 * 4);
+
+        let mipmapped = TextureDescriptor {
+            mip_level_count: 3,
+            ..descriptor
+        };
+        assert_eq!(mipmapped.size_in_bytes(), (16 + 4 + 1) * 2 * 4);
+
+        let volume = TextureDescriptor {
+            dimension: TextureDimension::D3,
+            ..mipmapped
+        };
+        assert_eq!(volume.size_in_bytes(), (32 + 4 + 1) * 4);
+    }
+}
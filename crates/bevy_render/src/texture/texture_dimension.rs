@@ -261,6 +261,16 @@ impl TextureFormat {
         let info = self.pixel_info();
         info.type_size * info.num_components
     }
+
+    /// Whether textures of this format are depth textures, which are sampled without filtering.
+    pub fn is_depth(&self) -> bool {
+        matches!(
+            self,
+            TextureFormat::Depth32Float
+                | TextureFormat::Depth24Plus
+                | TextureFormat::Depth24PlusStencil8
+        )
+    }
 }
 
 impl Default for TextureFormat {
@@ -0,0 +1,75 @@
+use super::Texture;
+use bevy_utils::HashMap;
+use bevy_window::WindowId;
+
+/// Reads back what is rendered to windows, for example to compare frames with reference images
+/// in automated tests.
+///
+/// Only headless windows, created by the `HeadlessWindowPlugin`, can be captured: the render
+/// backend draws them to offscreen textures instead of displaying them.
+#[derive(Debug, Default)]
+pub struct WindowCaptures {
+    requests: Vec<WindowId>,
+    captures: HashMap<WindowId, Texture>,
+}
+
+impl WindowCaptures {
+    /// Captures the window when the current frame is rendered.
+    pub fn request(&mut self, window_id: WindowId) {
+        if !self.requests.contains(&window_id) {
+            self.requests.push(window_id);
+        }
+    }
+
+    /// Takes the last capture of the window, available once the frame it was requested in has
+    /// been rendered.
+    pub fn take(&mut self, window_id: WindowId) -> Option<Texture> {
+        self.captures.remove(&window_id)
+    }
+
+    /// Takes the windows to capture. Used by render backends.
+    pub fn drain_requests(&mut self) -> impl Iterator<Item = WindowId> + '_ {
+        self.requests.drain(..)
+    }
+
+    /// Stores the capture of a window. Used by render backends.
+    pub fn insert(&mut self, window_id: WindowId, texture: Texture) {
+        self.captures.insert(window_id, texture);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WindowCaptures;
+    use crate::texture::Texture;
+    use bevy_window::WindowId;
+
+    #[test]
+    fn captures() {
+        let mut captures = WindowCaptures::default();
+        let first = WindowId::new();
+        let second = WindowId::new();
+        captures.request(first);
+        captures.request(second);
+        // a window is captured once per frame
+        captures.request(first);
+        assert_eq!(
+            captures.drain_requests().collect::<Vec<_>>(),
+            vec![first, second]
+        );
+        assert_eq!(captures.drain_requests().count(), 0);
+
+        assert!(captures.take(first).is_none());
+        captures.insert(
+            first,
+            Texture {
+                data: vec![1, 2, 3, 4],
+                ..Default::default()
+            },
+        );
+        assert_eq!(captures.take(first).unwrap().data, vec![1, 2, 3, 4]);
+        // captures are taken once
+        assert!(captures.take(first).is_none());
+        assert!(captures.take(second).is_none());
+    }
+}
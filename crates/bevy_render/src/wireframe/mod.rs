@@ -1,5 +1,5 @@
 use crate::{
-    draw::DrawContext,
+    draw::{DrawContext, DrawError},
     mesh::Indices,
     pipeline::{PipelineDescriptor, PipelineSpecialization, RenderPipeline},
     prelude::*,
@@ -95,19 +95,22 @@ pub fn draw_wireframes_system(
                     .iter_dynamic_bindings()
                     .map(|name| name.to_string())
                     .collect::<HashSet<String>>(),
+                depth_textures: Default::default(),
                 vertex_buffer_layout: mesh.get_vertex_buffer_layout(),
             },
         );
         render_pipeline.dynamic_bindings_generation =
             render_pipelines.bindings.dynamic_bindings_generation();
 
-        draw_context
-            .set_pipeline(
-                &mut draw,
-                &render_pipeline.pipeline,
-                &render_pipeline.specialization,
-            )
-            .unwrap();
+        match draw_context.set_pipeline(
+            &mut draw,
+            &render_pipeline.pipeline,
+            &render_pipeline.specialization,
+        ) {
+            // skip the wireframe until its shaders are compiled
+            Err(DrawError::PipelineCompiling) => return,
+            result => result.unwrap(),
+        }
         draw_context
             .set_bind_groups_from_bindings(&mut draw, &mut [&mut render_pipelines.bindings])
             .unwrap();
@@ -6,26 +6,46 @@ use bevy_ecs::{
 };
 use bevy_transform::hierarchy::ChildBuilder;
 
-use crate::{Scene, SceneSpawner};
+use crate::{Scene, SceneOverrides, SceneSpawner};
 
 pub struct SpawnScene {
     scene_handle: Handle<Scene>,
+    overrides: Option<SceneOverrides>,
 }
 
 impl Command for SpawnScene {
     fn write(self, world: &mut World) {
         let mut spawner = world.get_resource_mut::<SceneSpawner>().unwrap();
-        spawner.spawn(self.scene_handle);
+        match self.overrides {
+            Some(overrides) => spawner.spawn_with_overrides(self.scene_handle, overrides),
+            None => spawner.spawn(self.scene_handle),
+        };
     }
 }
 
 pub trait SpawnSceneCommands {
     fn spawn_scene(&mut self, scene: Handle<Scene>);
+    /// Spawns a scene, applying `overrides` to its entities as they are spawned.
+    fn spawn_scene_with_overrides(&mut self, scene: Handle<Scene>, overrides: SceneOverrides);
 }
 
 impl<'a> SpawnSceneCommands for Commands<'a> {
     fn spawn_scene(&mut self, scene_handle: Handle<Scene>) {
-        self.add(SpawnScene { scene_handle });
+        self.add(SpawnScene {
+            scene_handle,
+            overrides: None,
+        });
+    }
+
+    fn spawn_scene_with_overrides(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        overrides: SceneOverrides,
+    ) {
+        self.add(SpawnScene {
+            scene_handle,
+            overrides: Some(overrides),
+        });
     }
 }
 
@@ -1,4 +1,4 @@
-use crate::{serde::SceneSerializer, Scene, SceneSpawnError};
+use crate::{serde::SceneSerializer, Scene, SceneMigrationsArc, SceneSpawnError};
 use anyhow::Result;
 use bevy_ecs::{
     entity::EntityMap,
@@ -6,11 +6,16 @@ use bevy_ecs::{
     world::World,
 };
 use bevy_reflect::{Reflect, TypeRegistryArc, TypeUuid};
+use bevy_utils::HashSet;
 use serde::Serialize;
 
 #[derive(Default, TypeUuid)]
 #[uuid = "749479b1-fb8c-4ff8-a775-623aa76014f5"]
 pub struct DynamicScene {
+    /// The version of the scene format this scene was saved with, used to apply
+    /// [`SceneMigrations`](crate::SceneMigrations) when it is loaded. Scenes created from a world
+    /// have the current version of the [`SceneMigrationsArc`] resource of the world.
+    pub version: u32,
     pub entities: Vec<Entity>,
 }
 
@@ -25,11 +30,72 @@ impl DynamicScene {
     }
 
     pub fn from_world(world: &World, type_registry: &TypeRegistryArc) -> Self {
-        let mut scene = DynamicScene::default();
+        Self::from_world_filtered(world, type_registry, |_| true)
+    }
+
+    /// Creates a scene of the entities of `world` with a reflected component that changed since
+    /// `last_change_tick`. Each of these entities is saved with all of its reflected components.
+    pub fn from_world_changed_since(
+        world: &World,
+        type_registry: &TypeRegistryArc,
+        last_change_tick: u32,
+        change_tick: u32,
+    ) -> Self {
+        let registry = type_registry.read();
+        let is_reflected = |component_id| {
+            world
+                .components()
+                .get_info(component_id)
+                .and_then(|info| registry.get(info.type_id()?))
+                .and_then(|registration| registration.data::<ReflectComponent>())
+                .is_some()
+        };
+        let changed = world
+            .archetypes()
+            .iter()
+            .flat_map(|archetype| {
+                let reflected = archetype
+                    .components()
+                    .filter(|&component_id| is_reflected(component_id))
+                    .collect::<Vec<_>>();
+                archetype.entities().iter().filter(move |&&entity| {
+                    let entity = world.entity(entity);
+                    reflected.iter().any(|&component_id| {
+                        entity
+                            .get_change_ticks_by_id(component_id)
+                            .map_or(false, |ticks| {
+                                ticks.is_changed(last_change_tick, change_tick)
+                            })
+                    })
+                })
+            })
+            .copied()
+            .collect::<HashSet<_>>();
+        drop(registry);
+        Self::from_world_filtered(world, type_registry, |entity| changed.contains(&entity))
+    }
+
+    fn from_world_filtered(
+        world: &World,
+        type_registry: &TypeRegistryArc,
+        filter: impl Fn(bevy_ecs::entity::Entity) -> bool,
+    ) -> Self {
+        let mut scene = DynamicScene {
+            version: world
+                .get_resource::<SceneMigrationsArc>()
+                .map_or(0, |migrations| migrations.read().version()),
+            entities: Vec::new(),
+        };
         let type_registry = type_registry.read();
         for archetype in world.archetypes().iter() {
             let entities_offset = scene.entities.len();
-            for entity in archetype.entities() {
+            let entities = archetype
+                .entities()
+                .iter()
+                .copied()
+                .filter(|&entity| filter(entity))
+                .collect::<Vec<_>>();
+            for entity in entities.iter() {
                 scene.entities.push(Entity {
                     entity: entity.id(),
                     components: Vec::new(),
@@ -43,7 +109,7 @@ impl DynamicScene {
                     .and_then(|info| type_registry.get(info.type_id().unwrap()))
                     .and_then(|registration| registration.data::<ReflectComponent>());
                 if let Some(reflect_component) = reflect_component {
-                    for (i, entity) in archetype.entities().iter().enumerate() {
+                    for (i, entity) in entities.iter().enumerate() {
                         if let Some(component) = reflect_component.reflect_component(world, *entity)
                         {
                             scene.entities[entities_offset + i]
@@ -58,6 +124,14 @@ impl DynamicScene {
         scene
     }
 
+    /// Sets the version of the scene format, which should be the current
+    /// [`SceneMigrations::version`](crate::SceneMigrations::version) when saving the scene. This is
+    /// only needed for scenes that weren't created from the world they're saved from.
+    pub fn with_version(mut self, version: u32) -> Self {
+        self.version = version;
+        self
+    }
+
     pub fn write_to_world(
         &self,
         world: &mut World,
@@ -1,20 +1,29 @@
 mod command;
 mod dynamic_scene;
+mod migration;
+mod round_trip;
+mod save;
 mod scene;
 mod scene_loader;
+mod scene_overrides;
 mod scene_spawner;
 pub mod serde;
 
 pub use command::*;
 pub use dynamic_scene::*;
+pub use migration::*;
+pub use round_trip::*;
+pub use save::*;
 pub use scene::*;
 pub use scene_loader::*;
+pub use scene_overrides::*;
 pub use scene_spawner::*;
 
 pub mod prelude {
     #[doc(hidden)]
     pub use crate::{
-        DynamicScene, Scene, SceneSpawner, SpawnSceneAsChildCommands, SpawnSceneCommands,
+        DynamicScene, SaveCompleted, SaveKind, SavePlugin, SaveRequest, SaveSlots, Scene,
+        SceneOverrides, SceneSpawner, SpawnSceneAsChildCommands, SpawnSceneCommands,
     };
 }
 
@@ -29,6 +38,7 @@ impl Plugin for ScenePlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.add_asset::<DynamicScene>()
             .add_asset::<Scene>()
+            .init_resource::<SceneMigrationsArc>()
             .init_asset_loader::<SceneLoader>()
             .init_resource::<SceneSpawner>()
             .add_system_to_stage(
@@ -0,0 +1,257 @@
+use crate::DynamicScene;
+use bevy_app::AppBuilder;
+use bevy_reflect::Reflect;
+use bevy_utils::HashMap;
+use parking_lot::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::{fmt::Debug, sync::Arc};
+
+type MigrationFn = dyn Fn(Box<dyn Reflect>) -> Box<dyn Reflect> + Send + Sync;
+
+struct ComponentMigration {
+    version: u32,
+    migrate: Box<MigrationFn>,
+}
+
+/// Functions upgrading the component data of scenes saved with an older version of the scene
+/// format.
+///
+/// Each migration upgrades a component type to a given version. When a scene saved with version
+/// `n` is loaded, every migration with a version greater than `n` is applied in order to the
+/// components of its type.
+#[derive(Default)]
+pub struct SceneMigrations {
+    version: u32,
+    migrations: HashMap<String, Vec<ComponentMigration>>,
+}
+
+impl SceneMigrations {
+    /// The current version of the scene format, which is the highest version of all registered
+    /// migrations. Scenes should be saved with this version.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Registers a migration upgrading components with the given type name to `version`.
+    ///
+    /// The migration receives the component as it was deserialized, usually a dynamic type such
+    /// as [`DynamicStruct`](bevy_reflect::DynamicStruct), and returns the upgraded value. A
+    /// migration can rename a type by returning a value with a different type name, in which case
+    /// the migrations of the new type name are applied next.
+    pub fn add(
+        &mut self,
+        type_name: &str,
+        version: u32,
+        migrate: impl Fn(Box<dyn Reflect>) -> Box<dyn Reflect> + Send + Sync + 'static,
+    ) -> &mut Self {
+        let migrations = self.migrations.entry(type_name.to_string()).or_default();
+        let index = migrations
+            .iter()
+            .position(|migration| migration.version > version)
+            .unwrap_or(migrations.len());
+        migrations.insert(
+            index,
+            ComponentMigration {
+                version,
+                migrate: Box::new(migrate),
+            },
+        );
+        self.version = self.version.max(version);
+        self
+    }
+
+    /// Upgrades a component from a scene saved with version `from_version`.
+    pub fn migrate_component(
+        &self,
+        mut component: Box<dyn Reflect>,
+        mut from_version: u32,
+    ) -> Box<dyn Reflect> {
+        while let Some(migration) =
+            self.migrations
+                .get(component.type_name())
+                .and_then(|migrations| {
+                    migrations
+                        .iter()
+                        .find(|migration| migration.version > from_version)
+                })
+        {
+            from_version = migration.version;
+            component = (migration.migrate)(component);
+        }
+        component
+    }
+
+    /// Upgrades all components of `scene` to the current version.
+    pub fn migrate(&self, scene: &mut DynamicScene) {
+        if scene.version >= self.version {
+            return;
+        }
+        let from_version = scene.version;
+        for entity in scene.entities.iter_mut() {
+            entity.components = entity
+                .components
+                .drain(..)
+                .map(|component| self.migrate_component(component, from_version))
+                .collect();
+        }
+        scene.version = self.version;
+    }
+}
+
+/// A shared [`SceneMigrations`], used as a resource so the scene loader can access it.
+#[derive(Clone, Default)]
+pub struct SceneMigrationsArc {
+    pub internal: Arc<RwLock<SceneMigrations>>,
+}
+
+impl SceneMigrationsArc {
+    pub fn read(&self) -> RwLockReadGuard<'_, SceneMigrations> {
+        self.internal.read()
+    }
+
+    pub fn write(&self) -> RwLockWriteGuard<'_, SceneMigrations> {
+        self.internal.write()
+    }
+}
+
+impl Debug for SceneMigrationsArc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let migrations = self.read();
+        f.debug_map()
+            .entries(migrations.migrations.iter().map(|(type_name, migrations)| {
+                let versions = migrations
+                    .iter()
+                    .map(|migration| migration.version)
+                    .collect::<Vec<_>>();
+                (type_name, versions)
+            }))
+            .finish()
+    }
+}
+
+pub trait AddSceneMigration {
+    /// Registers a migration upgrading components with the given type name to `version`. See
+    /// [`SceneMigrations::add`].
+    fn add_scene_migration(
+        &mut self,
+        type_name: &str,
+        version: u32,
+        migrate: impl Fn(Box<dyn Reflect>) -> Box<dyn Reflect> + Send + Sync + 'static,
+    ) -> &mut Self;
+}
+
+impl AddSceneMigration for AppBuilder {
+    fn add_scene_migration(
+        &mut self,
+        type_name: &str,
+        version: u32,
+        migrate: impl Fn(Box<dyn Reflect>) -> Box<dyn Reflect> + Send + Sync + 'static,
+    ) -> &mut Self {
+        {
+            let migrations = self
+                .world()
+                .get_resource::<SceneMigrationsArc>()
+                .expect("ScenePlugin must be added before scene migrations");
+            migrations.write().add(type_name, version, migrate);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SceneMigrations, SceneMigrationsArc};
+    use crate::{DynamicScene, Entity};
+    use bevy_ecs::world::World;
+    use bevy_reflect::{DynamicStruct, GetField, Reflect, Struct, TypeRegistryArc};
+
+    fn component(name: &str, field: &str, value: f32) -> Box<dyn Reflect> {
+        let mut component = DynamicStruct::default();
+        component.set_name(name.to_string());
+        component.insert(field, value);
+        Box::new(component)
+    }
+
+    fn rename_field(
+        from: &'static str,
+        to: &'static str,
+    ) -> impl Fn(Box<dyn Reflect>) -> Box<dyn Reflect> {
+        move |component| {
+            let component = component.downcast::<DynamicStruct>().unwrap();
+            let mut migrated = DynamicStruct::default();
+            migrated.set_name(component.name().to_string());
+            for (i, value) in component.iter_fields().enumerate() {
+                let name = match component.name_at(i).unwrap() {
+                    name if name == from => to,
+                    name => name,
+                };
+                migrated.insert_boxed(name, value.clone_value());
+            }
+            Box::new(migrated)
+        }
+    }
+
+    #[test]
+    fn migrate_scene() {
+        let mut migrations = SceneMigrations::default();
+        migrations
+            .add("game::Health", 2, rename_field("hp", "current"))
+            // registered out of order, but applied in version order
+            .add("game::Health", 1, rename_field("health", "hp"))
+            .add("game::Speed", 3, rename_field("speed", "value"));
+        assert_eq!(migrations.version(), 3);
+
+        let mut scene = DynamicScene {
+            version: 1,
+            entities: vec![Entity {
+                entity: 0,
+                components: vec![
+                    component("game::Health", "hp", 10.0),
+                    component("game::Speed", "speed", 2.0),
+                ],
+            }],
+        };
+        migrations.migrate(&mut scene);
+        assert_eq!(scene.version, 3);
+
+        let components = &scene.entities[0].components;
+        let health = components[0].downcast_ref::<DynamicStruct>().unwrap();
+        assert_eq!(health.get_field::<f32>("current"), Some(&10.0));
+        assert!(health.field("hp").is_none());
+        let speed = components[1].downcast_ref::<DynamicStruct>().unwrap();
+        assert_eq!(speed.get_field::<f32>("value"), Some(&2.0));
+    }
+
+    #[test]
+    fn rename_type() {
+        let mut migrations = SceneMigrations::default();
+        migrations
+            .add("game::Hp", 1, |component| {
+                let mut component = component.downcast::<DynamicStruct>().unwrap();
+                component.set_name("game::Health".to_string());
+                component
+            })
+            .add("game::Health", 2, rename_field("value", "current"));
+
+        let migrated = migrations.migrate_component(component("game::Hp", "value", 5.0), 0);
+        assert_eq!(migrated.type_name(), "game::Health");
+        let migrated = migrated.downcast_ref::<DynamicStruct>().unwrap();
+        assert_eq!(migrated.get_field::<f32>("current"), Some(&5.0));
+
+        // components from recent scenes are left untouched
+        let component = migrations.migrate_component(component("game::Hp", "value", 5.0), 2);
+        assert_eq!(component.type_name(), "game::Hp");
+    }
+
+    #[test]
+    fn scenes_have_the_current_version() {
+        let migrations = SceneMigrationsArc::default();
+        migrations
+            .write()
+            .add("game::Health", 2, rename_field("hp", "current"));
+        let mut world = World::default();
+        world.insert_resource(migrations);
+
+        let scene = DynamicScene::from_world(&world, &TypeRegistryArc::default());
+        assert_eq!(scene.version, 2);
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{serde::SceneDeserializer, DynamicScene, SceneSpawnError};
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    reflect::ReflectComponent,
+    world::World,
+};
+use bevy_reflect::{serde::ReflectSerializer, Reflect, TypeRegistry, TypeRegistryArc};
+use serde::de::DeserializeSeed;
+use std::fmt;
+use thiserror::Error;
+
+/// How a component differs after a world went through a scene round trip.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MismatchKind {
+    /// The component type isn't registered with `#[reflect(Component)]`, so it isn't saved in
+    /// scenes.
+    NotReflected,
+    /// The component is missing from the entity after the round trip.
+    Missing,
+    /// The component has a different value after the round trip.
+    Different { expected: String, found: String },
+}
+
+/// A component that didn't survive a scene round trip unchanged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComponentMismatch {
+    /// The entity in the original world.
+    pub entity: Entity,
+    pub type_name: String,
+    pub kind: MismatchKind,
+}
+
+impl fmt::Display for ComponentMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?} `{}`: ", self.entity, self.type_name)?;
+        match &self.kind {
+            MismatchKind::NotReflected => write!(f, "not registered as a reflected component"),
+            MismatchKind::Missing => write!(f, "missing after the round trip"),
+            MismatchKind::Different { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RoundTripError {
+    #[error("failed to serialize the scene: {0}")]
+    Serialize(ron::Error),
+    #[error("failed to deserialize the scene: {0}")]
+    Deserialize(ron::Error),
+    #[error(transparent)]
+    Spawn(#[from] SceneSpawnError),
+}
+
+/// Saves `world` to a RON scene, then loads the scene into a new [`World`].
+///
+/// Entities are recreated with the same ids, so components referencing entities can be
+/// compared, but entity generations are not preserved by scenes.
+pub fn round_trip_world(
+    world: &World,
+    type_registry: &TypeRegistryArc,
+) -> Result<World, RoundTripError> {
+    let scene = DynamicScene::from_world(world, type_registry);
+    let ron = scene
+        .serialize_ron(type_registry)
+        .map_err(RoundTripError::Serialize)?;
+    let mut deserializer =
+        ron::de::Deserializer::from_str(&ron).map_err(RoundTripError::Deserialize)?;
+    let scene = SceneDeserializer {
+        type_registry: &*type_registry.read(),
+    }
+    .deserialize(&mut deserializer)
+    .map_err(RoundTripError::Deserialize)?;
+
+    let mut copy = World::default();
+    copy.insert_resource(type_registry.clone());
+    let mut entity_map = EntityMap::default();
+    if let Some(max_id) = scene.entities.iter().map(|entity| entity.entity).max() {
+        let entities = (0..=max_id).map(|_| copy.spawn().id()).collect::<Vec<_>>();
+        for entity in scene.entities.iter() {
+            entity_map.insert(Entity::new(entity.entity), entities[entity.entity as usize]);
+        }
+        for entity in entities {
+            if entity_map.get(entity).is_err() {
+                copy.despawn(entity);
+            }
+        }
+    }
+    scene.write_to_world(&mut copy, &mut entity_map)?;
+    Ok(copy)
+}
+
+/// Compares every component of `original` to the component of the same entity in `copy`, using
+/// [`Reflect::reflect_partial_eq`], or the serialized values for types that don't support it.
+pub fn compare_worlds(
+    original: &World,
+    copy: &World,
+    type_registry: &TypeRegistry,
+) -> Vec<ComponentMismatch> {
+    let mut mismatches = Vec::new();
+    for archetype in original.archetypes().iter() {
+        for component_id in archetype.components() {
+            let info = original.components().get_info(component_id).unwrap();
+            let reflect_component = info
+                .type_id()
+                .and_then(|type_id| type_registry.get(type_id))
+                .and_then(|registration| registration.data::<ReflectComponent>());
+            for entity in archetype.entities() {
+                let mismatch = |kind| ComponentMismatch {
+                    entity: *entity,
+                    type_name: info.name().to_string(),
+                    kind,
+                };
+                let reflect_component = match reflect_component {
+                    Some(reflect_component) => reflect_component,
+                    None => {
+                        mismatches.push(mismatch(MismatchKind::NotReflected));
+                        continue;
+                    }
+                };
+                let expected = reflect_component
+                    .reflect_component(original, *entity)
+                    .unwrap();
+                let found =
+                    match reflect_component.reflect_component(copy, Entity::new(entity.id())) {
+                        Some(found) => found,
+                        None => {
+                            mismatches.push(mismatch(MismatchKind::Missing));
+                            continue;
+                        }
+                    };
+                let equal = expected.reflect_partial_eq(found).unwrap_or_else(|| {
+                    serialize(expected, type_registry) == serialize(found, type_registry)
+                });
+                if !equal {
+                    mismatches.push(mismatch(MismatchKind::Different {
+                        expected: serialize(expected, type_registry),
+                        found: serialize(found, type_registry),
+                    }));
+                }
+            }
+        }
+    }
+    mismatches
+}
+
+fn serialize(value: &dyn Reflect, type_registry: &TypeRegistry) -> String {
+    ron::to_string(&ReflectSerializer::new(value, type_registry))
+        .unwrap_or_else(|err| format!("<{}>", err))
+}
+
+/// Asserts that saving `world` to a scene and loading it back results in the same components.
+///
+/// This is useful to test save systems, and to catch components or fields that are missing
+/// reflection registrations.
+///
+/// # Panics
+///
+/// Panics if the round trip fails or if any component differs, listing every mismatch.
+pub fn assert_scene_round_trip(world: &World, type_registry: &TypeRegistryArc) {
+    let copy = round_trip_world(world, type_registry)
+        .unwrap_or_else(|err| panic!("scene round trip failed: {}", err));
+    let mismatches = compare_worlds(world, &copy, &*type_registry.read());
+    if !mismatches.is_empty() {
+        let mismatches = mismatches
+            .iter()
+            .map(|mismatch| format!("\n  {}", mismatch))
+            .collect::<String>();
+        panic!("components differ after scene round trip:{}", mismatches);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{compare_worlds, round_trip_world, MismatchKind};
+    use crate::assert_scene_round_trip;
+    use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+    use bevy_math::{Quat, Vec3};
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use bevy_transform::prelude::{Children, Parent, Transform};
+
+    #[derive(Reflect, Default, Debug, PartialEq)]
+    #[reflect(Component, PartialEq)]
+    struct Health {
+        current: f32,
+        max: f32,
+    }
+
+    struct NotReflected;
+
+    fn type_registry() -> TypeRegistryArc {
+        let type_registry = TypeRegistryArc::default();
+        {
+            let mut type_registry = type_registry.write();
+            type_registry.register::<Health>();
+            type_registry.register::<Transform>();
+            type_registry.register::<Parent>();
+            type_registry.register::<Children>();
+            // the types of the fields above
+            type_registry.register::<f32>();
+            type_registry.register::<Vec3>();
+            type_registry.register::<Quat>();
+            type_registry.register::<Entity>();
+        }
+        type_registry
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut world = World::default();
+        let despawned = world.spawn().id();
+        let parent = world
+            .spawn()
+            .insert_bundle((
+                Transform::from_xyz(1.0, 2.0, 3.0),
+                Health {
+                    current: 5.0,
+                    max: 10.0,
+                },
+            ))
+            .id();
+        let child = world.spawn().insert(Parent(parent)).id();
+        world.entity_mut(parent).insert(Children::with(&[child]));
+        // leave a gap in the entity ids
+        world.despawn(despawned);
+
+        assert_scene_round_trip(&world, &type_registry());
+    }
+
+    #[test]
+    fn mismatches() {
+        let mut world = World::default();
+        let entity = world
+            .spawn()
+            .insert_bundle((Health::default(), NotReflected))
+            .id();
+        let type_registry = type_registry();
+        let copy = round_trip_world(&world, &type_registry).unwrap();
+        let mismatches = compare_worlds(&world, &copy, &*type_registry.read());
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].entity, entity);
+        assert_eq!(mismatches[0].kind, MismatchKind::NotReflected);
+
+        let copy = World::default();
+        let mismatches = compare_worlds(&world, &copy, &*type_registry.read());
+        assert!(mismatches
+            .iter()
+            .any(|mismatch| mismatch.kind == MismatchKind::Missing));
+    }
+}
@@ -0,0 +1,481 @@
+use crate::{serde::SceneDeserializer, DynamicScene, SceneMigrationsArc, SceneSpawnError};
+use bevy_app::{AppBuilder, CoreStage, Plugin};
+use bevy_ecs::{
+    entity::EntityMap,
+    event::{Events, ManualEventReader},
+    schedule::ExclusiveSystemDescriptorCoercion,
+    system::IntoExclusiveSystem,
+    world::{Mut, World},
+};
+use bevy_reflect::TypeRegistryArc;
+use bevy_tasks::{IoTaskPool, Task};
+use bevy_utils::HashMap;
+use futures_lite::future;
+use serde::de::DeserializeSeed;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+use thiserror::Error;
+
+/// Adds [`SaveSlots`], which saves the world to named save slots when a [`SaveRequest`] is sent.
+#[derive(Default)]
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_event::<SaveRequest>()
+            .add_event::<SaveCompleted>()
+            .init_resource::<SaveSlots>()
+            .add_system_to_stage(CoreStage::Last, save_system.exclusive_system().at_end());
+    }
+}
+
+/// Whether a save writes the whole world or only what changed since the last save of the slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveKind {
+    /// Saves every entity, replacing the previous saves of the slot.
+    Full,
+    /// Saves the entities with a component that changed since the last save of the slot. Falls
+    /// back to a full save if the slot wasn't saved since the app started or was loaded.
+    ///
+    /// Despawned entities and removed components are not recorded, so do a full save after
+    /// despawning saved entities.
+    Incremental,
+}
+
+/// How save files are compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveCompression {
+    None,
+    /// Gzip compression, available with the `compression` feature.
+    #[cfg(feature = "compression")]
+    Gzip,
+}
+
+impl SaveCompression {
+    fn extension(&self) -> &'static str {
+        match self {
+            SaveCompression::None => "scn.ron",
+            #[cfg(feature = "compression")]
+            SaveCompression::Gzip => "scn.ron.gz",
+        }
+    }
+}
+
+/// An event requesting to save the world to a slot.
+#[derive(Debug, Clone)]
+pub struct SaveRequest {
+    pub slot: String,
+    pub kind: SaveKind,
+}
+
+/// An event sent once the file of a [`SaveRequest`] is written, or failed to be.
+#[derive(Debug)]
+pub struct SaveCompleted {
+    pub slot: String,
+    /// The path of the written file.
+    pub result: Result<PathBuf, SaveError>,
+}
+
+#[derive(Error, Debug)]
+pub enum SaveError {
+    #[error("failed to access the save file: {0}")]
+    Io(#[from] io::Error),
+    #[error("failed to serialize the save: {0}")]
+    Serialize(ron::Error),
+    #[error("failed to deserialize the save: {0}")]
+    Deserialize(ron::Error),
+    #[error(transparent)]
+    Spawn(#[from] SceneSpawnError),
+    #[error("the save slot `{0}` doesn't exist")]
+    NoSuchSlot(String),
+}
+
+/// The save slots of the app, each a directory of scene files in [`SaveSlots::directory`].
+///
+/// A slot holds a full save followed by the incremental saves made since then, which are applied
+/// in order by [`SaveSlots::load`]. Files are written on the [`IoTaskPool`], one save of a slot at
+/// a time: a save requested while the slot is being written starts once the previous save
+/// completed. Files are written aside and then moved in place, so a failed save leaves the
+/// previous saves of the slot intact.
+pub struct SaveSlots {
+    directory: PathBuf,
+    pub compression: SaveCompression,
+    slots: HashMap<String, SlotState>,
+    pending: Vec<PendingSave>,
+    queued: Vec<SaveRequest>,
+    reader: ManualEventReader<SaveRequest>,
+}
+
+struct PendingSave {
+    slot: String,
+    // the state of the slot once the save succeeded
+    state: SlotState,
+    // whether the state is still valid once the save completes, which a load of the slot prevents
+    commit: bool,
+    task: Task<Result<PathBuf, SaveError>>,
+}
+
+struct SlotState {
+    // the change tick of the last save, changes after it go into the next incremental save
+    last_save_tick: u32,
+    next_segment: u32,
+}
+
+impl Default for SaveSlots {
+    fn default() -> Self {
+        Self::new("saves")
+    }
+}
+
+impl SaveSlots {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+            compression: SaveCompression::None,
+            slots: Default::default(),
+            pending: Vec::new(),
+            queued: Vec::new(),
+            reader: Default::default(),
+        }
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns the files of a slot, in the order they must be applied.
+    pub fn segments(&self, slot: &str) -> Result<Vec<PathBuf>, SaveError> {
+        let slot_directory = self.directory.join(slot);
+        if !slot_directory.is_dir() {
+            return Err(SaveError::NoSuchSlot(slot.to_string()));
+        }
+        let mut segments = fs::read_dir(slot_directory)?
+            .map(|entry| entry.map(|entry| entry.path()))
+            .collect::<Result<Vec<_>, _>>()?;
+        segments.retain(|path| segment_index(path).is_some());
+        segments.sort_by_key(|path| segment_index(path));
+        Ok(segments)
+    }
+
+    /// Loads a slot into `world`, creating new entities for the saved ones.
+    ///
+    /// The next [`SaveKind::Incremental`] save of the slot will be a full save, since the loaded
+    /// entities don't have the ids they were saved with.
+    pub fn load(&mut self, slot: &str, world: &mut World) -> Result<(), SaveError> {
+        let type_registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+        let migrations = world.get_resource::<SceneMigrationsArc>().cloned();
+        let mut entity_map = EntityMap::default();
+        for path in self.segments(slot)? {
+            let bytes = decompress(&path, fs::read(&path)?)?;
+            let mut deserializer =
+                ron::de::Deserializer::from_bytes(&bytes).map_err(SaveError::Deserialize)?;
+            let mut scene = SceneDeserializer {
+                type_registry: &*type_registry.read(),
+            }
+            .deserialize(&mut deserializer)
+            .map_err(SaveError::Deserialize)?;
+            if let Some(migrations) = &migrations {
+                migrations.read().migrate(&mut scene);
+            }
+            scene.write_to_world(world, &mut entity_map)?;
+        }
+        self.slots.remove(slot);
+        for save in self.pending.iter_mut() {
+            if save.slot == slot {
+                save.commit = false;
+            }
+        }
+        Ok(())
+    }
+
+    fn is_saving(&self, slot: &str) -> bool {
+        self.pending.iter().any(|save| save.slot == slot)
+    }
+
+    fn start_save(&mut self, world: &mut World, request: &SaveRequest) {
+        let type_registry = world.get_resource::<TypeRegistryArc>().unwrap().clone();
+        let change_tick = world.increment_change_tick();
+        let previous = match request.kind {
+            SaveKind::Full => None,
+            SaveKind::Incremental => self.slots.get(&request.slot),
+        };
+        let (scene, segment) = match previous {
+            Some(previous) => (
+                DynamicScene::from_world_changed_since(
+                    world,
+                    &type_registry,
+                    previous.last_save_tick,
+                    change_tick,
+                ),
+                previous.next_segment,
+            ),
+            None => (DynamicScene::from_world(world, &type_registry), 0),
+        };
+        let state = SlotState {
+            last_save_tick: change_tick,
+            next_segment: segment + 1,
+        };
+
+        let slot_directory = self.directory.join(&request.slot);
+        let new_slot_directory = self.directory.join(format!("{}.tmp", request.slot));
+        let file_name = format!("{:04}.{}", segment, self.compression.extension());
+        let path = slot_directory.join(&file_name);
+        let compression = self.compression;
+        let ron = scene
+            .serialize_ron(&type_registry)
+            .map_err(SaveError::Serialize);
+        let task = world
+            .get_resource::<IoTaskPool>()
+            .expect("`IoTaskPool` resource not found.")
+            .spawn(async move {
+                let bytes = compress(compression, ron?.into_bytes())?;
+                if segment == 0 {
+                    // the previous saves are only replaced once the new one is written
+                    if new_slot_directory.exists() {
+                        fs::remove_dir_all(&new_slot_directory)?;
+                    }
+                    fs::create_dir_all(&new_slot_directory)?;
+                    fs::write(new_slot_directory.join(&file_name), bytes)?;
+                    if slot_directory.exists() {
+                        fs::remove_dir_all(&slot_directory)?;
+                    }
+                    fs::rename(&new_slot_directory, &slot_directory)?;
+                } else {
+                    // not a segment, so a partially written file isn't loaded
+                    let temporary_path = slot_directory.join(format!("{:04}.tmp", segment));
+                    fs::create_dir_all(&slot_directory)?;
+                    fs::write(&temporary_path, bytes)?;
+                    fs::rename(&temporary_path, &path)?;
+                }
+                Ok(path)
+            });
+        self.pending.push(PendingSave {
+            slot: request.slot.clone(),
+            state,
+            commit: true,
+            task,
+        });
+    }
+
+    fn poll_saves(&mut self, events: &mut Events<SaveCompleted>) {
+        let mut i = 0;
+        while i < self.pending.len() {
+            if let Some(result) = future::block_on(future::poll_once(&mut self.pending[i].task)) {
+                let save = self.pending.remove(i);
+                if save.commit {
+                    if result.is_ok() {
+                        self.slots.insert(save.slot.clone(), save.state);
+                    } else if save.state.next_segment == 1 {
+                        // the previous saves of the slot may have been removed already
+                        self.slots.remove(&save.slot);
+                    }
+                }
+                events.send(SaveCompleted {
+                    slot: save.slot,
+                    result,
+                });
+            } else {
+                i += 1;
+            }
+        }
+    }
+}
+
+/// Starts the saves requested by [`SaveRequest`] events and sends [`SaveCompleted`] events.
+pub fn save_system(world: &mut World) {
+    world.resource_scope(|world, mut slots: Mut<SaveSlots>| {
+        {
+            let mut events = world.get_resource_mut::<Events<SaveCompleted>>().unwrap();
+            slots.poll_saves(&mut events);
+        }
+        let mut requests = std::mem::take(&mut slots.queued);
+        {
+            let events = world.get_resource::<Events<SaveRequest>>().unwrap();
+            requests.extend(slots.reader.iter(events).cloned());
+        }
+        for request in requests {
+            if slots.is_saving(&request.slot) {
+                slots.queued.push(request);
+            } else {
+                slots.start_save(world, &request);
+            }
+        }
+    });
+}
+
+fn segment_index(path: &Path) -> Option<u32> {
+    let name = path.file_name()?.to_str()?;
+    let (index, extension) = name.split_at(name.find('.')?);
+    if extension == ".scn.ron" || extension == ".scn.ron.gz" {
+        index.parse().ok()
+    } else {
+        None
+    }
+}
+
+#[cfg(feature = "compression")]
+fn compress(compression: SaveCompression, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    use std::io::Write;
+
+    match compression {
+        SaveCompression::None => Ok(bytes),
+        SaveCompression::Gzip => {
+            let mut encoder =
+                flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+            encoder.write_all(&bytes)?;
+            encoder.finish()
+        }
+    }
+}
+
+#[cfg(not(feature = "compression"))]
+fn compress(_compression: SaveCompression, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    Ok(bytes)
+}
+
+fn decompress(path: &Path, bytes: Vec<u8>) -> io::Result<Vec<u8>> {
+    if path.extension().map_or(true, |extension| extension != "gz") {
+        return Ok(bytes);
+    }
+    #[cfg(feature = "compression")]
+    {
+        use std::io::Read;
+
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(&bytes[..]).read_to_end(&mut decompressed)?;
+        Ok(decompressed)
+    }
+    #[cfg(not(feature = "compression"))]
+    Err(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "compressed saves require the `compression` feature",
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{save_system, SaveCompleted, SaveKind, SaveRequest, SaveSlots};
+    use bevy_ecs::{event::Events, prelude::*, reflect::ReflectComponent};
+    use bevy_reflect::{Reflect, TypeRegistryArc};
+    use bevy_tasks::{IoTaskPool, TaskPool};
+    use std::{fs, path::Path};
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Health {
+        current: f32,
+    }
+
+    fn type_registry() -> TypeRegistryArc {
+        let type_registry = TypeRegistryArc::default();
+        type_registry.write().register::<Health>();
+        type_registry.write().register::<f32>();
+        type_registry
+    }
+
+    fn setup(directory: &Path) -> (World, Entity) {
+        let mut world = World::default();
+        world.insert_resource(type_registry());
+        world.insert_resource(IoTaskPool(TaskPool::new()));
+        world.insert_resource(Events::<SaveRequest>::default());
+        world.insert_resource(Events::<SaveCompleted>::default());
+        world.insert_resource(SaveSlots::new(directory));
+        world.spawn().insert(Health { current: 1.0 });
+        let entity = world.spawn().insert(Health { current: 2.0 }).id();
+        (world, entity)
+    }
+
+    /// Requests the saves in the same frame, and returns whether each succeeded.
+    fn save(world: &mut World, kinds: &[SaveKind]) -> Vec<bool> {
+        let mut reader = world
+            .get_resource::<Events<SaveCompleted>>()
+            .unwrap()
+            .get_reader_current();
+        let mut requests = world.get_resource_mut::<Events<SaveRequest>>().unwrap();
+        for kind in kinds {
+            requests.send(SaveRequest {
+                slot: "slot".to_string(),
+                kind: *kind,
+            });
+        }
+        let mut results = Vec::new();
+        while results.len() < kinds.len() {
+            save_system(world);
+            let events = world.get_resource::<Events<SaveCompleted>>().unwrap();
+            results.extend(reader.iter(events).map(|event| event.result.is_ok()));
+            std::thread::sleep(std::time::Duration::from_millis(1));
+        }
+        results
+    }
+
+    fn load(world: &mut World) -> Vec<f32> {
+        let mut loaded = World::default();
+        loaded.insert_resource(type_registry());
+        world
+            .get_resource_mut::<SaveSlots>()
+            .unwrap()
+            .load("slot", &mut loaded)
+            .unwrap();
+        let mut values = loaded
+            .query::<&Health>()
+            .iter(&loaded)
+            .map(|health| health.current)
+            .collect::<Vec<_>>();
+        values.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        values
+    }
+
+    #[test]
+    fn incremental_save() {
+        let directory =
+            std::env::temp_dir().join(format!("bevy_scene_save_{}", std::process::id()));
+        let (mut world, entity) = setup(&directory);
+
+        assert_eq!(save(&mut world, &[SaveKind::Full]), vec![true]);
+        world.get_mut::<Health>(entity).unwrap().current = 3.0;
+        assert_eq!(save(&mut world, &[SaveKind::Incremental]), vec![true]);
+
+        let segments = world
+            .get_resource::<SaveSlots>()
+            .unwrap()
+            .segments("slot")
+            .unwrap();
+        assert_eq!(segments.len(), 2);
+        let incremental = fs::read_to_string(&segments[1]).unwrap();
+        assert_eq!(incremental.matches("entity:").count(), 1);
+        assert_eq!(load(&mut world), vec![1.0, 3.0]);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn saves_of_a_slot_run_in_order() {
+        let directory =
+            std::env::temp_dir().join(format!("bevy_scene_save_order_{}", std::process::id()));
+        let (mut world, _) = setup(&directory);
+
+        let kinds = [SaveKind::Full, SaveKind::Incremental, SaveKind::Full];
+        assert_eq!(save(&mut world, &kinds), vec![true, true, true]);
+        let slots = world.get_resource::<SaveSlots>().unwrap();
+        assert_eq!(slots.segments("slot").unwrap().len(), 1);
+        assert_eq!(load(&mut world), vec![1.0, 2.0]);
+
+        fs::remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn failed_save_is_not_committed() {
+        // a file where the save directory should be
+        let directory =
+            std::env::temp_dir().join(format!("bevy_scene_save_failed_{}", std::process::id()));
+        fs::write(&directory, "").unwrap();
+        let (mut world, _) = setup(&directory);
+
+        assert_eq!(save(&mut world, &[SaveKind::Full]), vec![false]);
+        assert!(world.get_resource::<SaveSlots>().unwrap().slots.is_empty());
+
+        fs::remove_file(directory).unwrap();
+    }
+}
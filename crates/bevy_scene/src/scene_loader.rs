@@ -1,4 +1,4 @@
-use crate::serde::SceneDeserializer;
+use crate::{serde::SceneDeserializer, SceneMigrationsArc};
 use anyhow::Result;
 use bevy_asset::{AssetLoader, LoadContext, LoadedAsset};
 use bevy_ecs::world::{FromWorld, World};
@@ -9,13 +9,16 @@ use serde::de::DeserializeSeed;
 #[derive(Debug)]
 pub struct SceneLoader {
     type_registry: TypeRegistryArc,
+    migrations: SceneMigrationsArc,
 }
 
 impl FromWorld for SceneLoader {
     fn from_world(world: &mut World) -> Self {
         let type_registry = world.get_resource::<TypeRegistryArc>().unwrap();
+        let migrations = world.get_resource::<SceneMigrationsArc>().unwrap();
         SceneLoader {
             type_registry: (&*type_registry).clone(),
+            migrations: (&*migrations).clone(),
         }
     }
 }
@@ -31,7 +34,8 @@ impl AssetLoader for SceneLoader {
             let scene_deserializer = SceneDeserializer {
                 type_registry: &*self.type_registry.read(),
             };
-            let scene = scene_deserializer.deserialize(&mut deserializer)?;
+            let mut scene = scene_deserializer.deserialize(&mut deserializer)?;
+            self.migrations.read().migrate(&mut scene);
             load_context.set_default_asset(LoadedAsset::new(scene));
             Ok(())
         })
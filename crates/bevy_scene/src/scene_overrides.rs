@@ -0,0 +1,218 @@
+use crate::SceneSpawnError;
+use bevy_ecs::{
+    entity::{Entity, EntityMap},
+    reflect::ReflectComponent,
+    world::World,
+};
+use bevy_reflect::{GetPath, Reflect, TypeRegistry};
+use bevy_transform::prelude::Parent;
+use std::any::{type_name, TypeId};
+
+/// The entities of a scene instance a [`SceneOverride`] applies to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverrideTarget {
+    /// The entities of the scene that don't have a [`Parent`].
+    Roots,
+    /// A single entity, identified by its id in the scene.
+    Entity(Entity),
+}
+
+/// Sets a field of a component of the entities spawned from a scene.
+pub struct SceneOverride {
+    pub target: OverrideTarget,
+    type_id: TypeId,
+    type_name: &'static str,
+    path: String,
+    value: Box<dyn Reflect>,
+}
+
+impl SceneOverride {
+    /// Creates an override setting the field at `path` of component `T` to `value`. An empty
+    /// path replaces the whole component.
+    pub fn new<T: Reflect>(target: OverrideTarget, path: &str, value: impl Reflect) -> Self {
+        Self {
+            target,
+            type_id: TypeId::of::<T>(),
+            type_name: type_name::<T>(),
+            path: path.to_string(),
+            value: Box::new(value),
+        }
+    }
+
+    fn error(&self, reason: impl ToString) -> SceneSpawnError {
+        SceneSpawnError::InvalidOverride {
+            type_name: self.type_name.to_string(),
+            path: self.path.clone(),
+            reason: reason.to_string(),
+        }
+    }
+
+    fn apply_to_entity(
+        &self,
+        world: &mut World,
+        entity: Entity,
+        reflect_component: &ReflectComponent,
+    ) -> Result<(), SceneSpawnError> {
+        let mut component = reflect_component
+            .reflect_component_mut(world, entity)
+            .ok_or_else(|| self.error("the entity doesn't have this component"))?;
+        let field = component
+            .path_mut(&self.path)
+            .map_err(|err| self.error(err))?;
+        field.apply(&*self.value);
+        Ok(())
+    }
+}
+
+/// Changes applied to the entities of a scene while it is spawned, before any system can see
+/// them. This allows spawning variants of a scene without editing the scene asset.
+///
+/// ```
+/// # use bevy_scene::SceneOverrides;
+/// # use bevy_transform::prelude::Transform;
+/// # use bevy_math::Vec3;
+/// let overrides = SceneOverrides::default()
+///     .root::<Transform>("translation", Vec3::new(2.0, 0.0, 0.0));
+/// ```
+#[derive(Default)]
+pub struct SceneOverrides {
+    overrides: Vec<SceneOverride>,
+}
+
+impl SceneOverrides {
+    pub fn with(mut self, scene_override: SceneOverride) -> Self {
+        self.overrides.push(scene_override);
+        self
+    }
+
+    /// Sets the field at `path` of component `T` on the root entities of the scene.
+    pub fn root<T: Reflect>(self, path: &str, value: impl Reflect) -> Self {
+        self.with(SceneOverride::new::<T>(OverrideTarget::Roots, path, value))
+    }
+
+    /// Sets the field at `path` of component `T` on the entity with the id `entity` in the
+    /// scene.
+    pub fn entity<T: Reflect>(self, entity: Entity, path: &str, value: impl Reflect) -> Self {
+        self.with(SceneOverride::new::<T>(
+            OverrideTarget::Entity(entity),
+            path,
+            value,
+        ))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &SceneOverride> {
+        self.overrides.iter()
+    }
+
+    /// Applies the overrides to the entities spawned from a scene, where `entity_map` maps the
+    /// scene entities to the spawned ones.
+    pub(crate) fn apply(
+        &self,
+        world: &mut World,
+        entity_map: &EntityMap,
+        type_registry: &TypeRegistry,
+    ) -> Result<(), SceneSpawnError> {
+        for scene_override in self.overrides.iter() {
+            let reflect_component = type_registry
+                .get(scene_override.type_id)
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: scene_override.type_name.to_string(),
+                })?
+                .data::<ReflectComponent>()
+                .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+                    type_name: scene_override.type_name.to_string(),
+                })?;
+            match scene_override.target {
+                OverrideTarget::Roots => {
+                    let roots = entity_map
+                        .values()
+                        .filter(|entity| {
+                            world
+                                .get_entity(*entity)
+                                .map_or(false, |entity| !entity.contains::<Parent>())
+                        })
+                        .collect::<Vec<_>>();
+                    for entity in roots {
+                        scene_override.apply_to_entity(world, entity, reflect_component)?;
+                    }
+                }
+                OverrideTarget::Entity(scene_entity) => {
+                    let entity = entity_map
+                        .get(scene_entity)
+                        .map_err(|_| scene_override.error("the entity isn't in the scene"))?;
+                    scene_override.apply_to_entity(world, entity, reflect_component)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SceneOverrides;
+    use crate::SceneSpawnError;
+    use bevy_ecs::{entity::EntityMap, prelude::*, reflect::ReflectComponent};
+    use bevy_math::Vec3;
+    use bevy_reflect::{Reflect, TypeRegistry};
+    use bevy_transform::prelude::{Parent, Transform};
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Health(f32);
+
+    fn setup() -> (World, EntityMap, TypeRegistry) {
+        let mut world = World::default();
+        let mut entity_map = EntityMap::default();
+        let root = world
+            .spawn()
+            .insert_bundle((Transform::default(), Health(10.0)))
+            .id();
+        let child = world
+            .spawn()
+            .insert_bundle((Transform::default(), Health(5.0), Parent(root)))
+            .id();
+        entity_map.insert(Entity::new(0), root);
+        entity_map.insert(Entity::new(1), child);
+
+        let mut type_registry = TypeRegistry::default();
+        type_registry.register::<Transform>();
+        type_registry.register::<Health>();
+        (world, entity_map, type_registry)
+    }
+
+    #[test]
+    fn apply_overrides() {
+        let (mut world, entity_map, type_registry) = setup();
+        SceneOverrides::default()
+            .root::<Transform>("translation", Vec3::new(1.0, 2.0, 3.0))
+            .entity::<Health>(Entity::new(1), "0", 7.0f32)
+            .apply(&mut world, &entity_map, &type_registry)
+            .unwrap();
+
+        let root = entity_map.get(Entity::new(0)).unwrap();
+        let child = entity_map.get(Entity::new(1)).unwrap();
+        assert_eq!(
+            world.get::<Transform>(root).unwrap().translation,
+            Vec3::new(1.0, 2.0, 3.0)
+        );
+        assert_eq!(
+            world.get::<Transform>(child).unwrap().translation,
+            Vec3::ZERO
+        );
+        assert_eq!(world.get::<Health>(root).unwrap().0, 10.0);
+        assert_eq!(world.get::<Health>(child).unwrap().0, 7.0);
+    }
+
+    #[test]
+    fn invalid_override() {
+        let (mut world, entity_map, type_registry) = setup();
+        let result = SceneOverrides::default()
+            .root::<Transform>("position", Vec3::ONE)
+            .apply(&mut world, &entity_map, &type_registry);
+        assert!(matches!(
+            result,
+            Err(SceneSpawnError::InvalidOverride { .. })
+        ));
+    }
+}
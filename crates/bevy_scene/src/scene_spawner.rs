@@ -1,4 +1,4 @@
-use crate::{DynamicScene, Scene};
+use crate::{DynamicScene, Scene, SceneOverrides};
 use bevy_app::{Events, ManualEventReader};
 use bevy_asset::{AssetEvent, Assets, Handle};
 use bevy_ecs::{
@@ -6,7 +6,7 @@ use bevy_ecs::{
     reflect::{ReflectComponent, ReflectMapEntities},
     world::{Mut, World},
 };
-use bevy_reflect::TypeRegistryArc;
+use bevy_reflect::{TypeRegistry, TypeRegistryArc};
 use bevy_transform::prelude::Parent;
 use bevy_utils::{tracing::error, HashMap};
 use thiserror::Error;
@@ -36,6 +36,7 @@ pub struct SceneSpawner {
     scenes_to_spawn: Vec<(Handle<Scene>, InstanceId)>,
     scenes_to_despawn: Vec<Handle<DynamicScene>>,
     scenes_with_parent: Vec<(InstanceId, Entity)>,
+    scene_overrides: HashMap<InstanceId, SceneOverrides>,
 }
 
 #[derive(Error, Debug)]
@@ -48,6 +49,12 @@ pub enum SceneSpawnError {
     NonExistentScene { handle: Handle<DynamicScene> },
     #[error("scene does not exist")]
     NonExistentRealScene { handle: Handle<Scene> },
+    #[error("cannot override `{path}` of component `{type_name}`: {reason}")]
+    InvalidOverride {
+        type_name: String,
+        path: String,
+        reason: String,
+    },
 }
 
 impl SceneSpawner {
@@ -61,6 +68,18 @@ impl SceneSpawner {
         instance_id
     }
 
+    /// Spawns a scene, applying `overrides` to its entities as they are spawned. If an override
+    /// can't be applied, the instance isn't spawned.
+    pub fn spawn_with_overrides(
+        &mut self,
+        scene_handle: Handle<Scene>,
+        overrides: SceneOverrides,
+    ) -> InstanceId {
+        let instance_id = self.spawn(scene_handle);
+        self.scene_overrides.insert(instance_id, overrides);
+        instance_id
+    }
+
     pub fn spawn_as_child(&mut self, scene_handle: Handle<Scene>, parent: Entity) -> InstanceId {
         let instance_id = InstanceId::new();
         self.scenes_to_spawn.push((scene_handle, instance_id));
@@ -134,6 +153,21 @@ impl SceneSpawner {
         self.spawn_sync_internal(world, scene_handle, InstanceId::new())
     }
 
+    /// Spawns a scene, applying `overrides` to its entities as they are spawned. If an override
+    /// can't be applied, the instance isn't spawned and the error is returned.
+    pub fn spawn_sync_with_overrides(
+        &mut self,
+        world: &mut World,
+        scene_handle: Handle<Scene>,
+        overrides: SceneOverrides,
+    ) -> Result<InstanceId, SceneSpawnError> {
+        let instance_id = InstanceId::new();
+        self.scene_overrides.insert(instance_id, overrides);
+        let result = self.spawn_sync_internal(world, scene_handle, instance_id);
+        self.scene_overrides.remove(&instance_id);
+        result
+    }
+
     fn spawn_sync_internal(
         &mut self,
         world: &mut World,
@@ -153,46 +187,19 @@ impl SceneSpawner {
                         handle: scene_handle.clone(),
                     })?;
 
-            for archetype in scene.world.archetypes().iter() {
-                for scene_entity in archetype.entities() {
-                    let entity = *instance_info
-                        .entity_map
-                        .entry(*scene_entity)
-                        .or_insert_with(|| world.spawn().id());
-                    for component_id in archetype.components() {
-                        let component_info = scene
-                            .world
-                            .components()
-                            .get_info(component_id)
-                            .expect("component_ids in archetypes should have ComponentInfo");
-
-                        let reflect_component = type_registry
-                            .get(component_info.type_id().unwrap())
-                            .ok_or_else(|| SceneSpawnError::UnregisteredType {
-                                type_name: component_info.name().to_string(),
-                            })
-                            .and_then(|registration| {
-                                registration.data::<ReflectComponent>().ok_or_else(|| {
-                                    SceneSpawnError::UnregisteredComponent {
-                                        type_name: component_info.name().to_string(),
-                                    }
-                                })
-                            })?;
-                        reflect_component.copy_component(
-                            &scene.world,
-                            world,
-                            *scene_entity,
-                            entity,
-                        );
-                    }
-                }
-            }
-            for registration in type_registry.iter() {
-                if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
-                    map_entities_reflect
-                        .map_entities(world, &instance_info.entity_map)
-                        .unwrap();
+            let overrides = self.scene_overrides.remove(&instance_id);
+            if let Err(err) = Self::write_scene_instance(
+                world,
+                scene,
+                &type_registry,
+                &mut instance_info.entity_map,
+                overrides.as_ref(),
+            ) {
+                // don't leave the entities spawned so far orphaned in the world
+                for entity in instance_info.entity_map.values() {
+                    let _ = world.despawn(entity);
                 }
+                return Err(err);
             }
             self.spawned_instances.insert(instance_id, instance_info);
             let spawned = self
@@ -204,6 +211,54 @@ impl SceneSpawner {
         })
     }
 
+    fn write_scene_instance(
+        world: &mut World,
+        scene: &Scene,
+        type_registry: &TypeRegistry,
+        entity_map: &mut EntityMap,
+        overrides: Option<&SceneOverrides>,
+    ) -> Result<(), SceneSpawnError> {
+        for archetype in scene.world.archetypes().iter() {
+            for scene_entity in archetype.entities() {
+                let entity = *entity_map
+                    .entry(*scene_entity)
+                    .or_insert_with(|| world.spawn().id());
+                for component_id in archetype.components() {
+                    let component_info = scene
+                        .world
+                        .components()
+                        .get_info(component_id)
+                        .expect("component_ids in archetypes should have ComponentInfo");
+
+                    let reflect_component = type_registry
+                        .get(component_info.type_id().unwrap())
+                        .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                            type_name: component_info.name().to_string(),
+                        })
+                        .and_then(|registration| {
+                            registration.data::<ReflectComponent>().ok_or_else(|| {
+                                SceneSpawnError::UnregisteredComponent {
+                                    type_name: component_info.name().to_string(),
+                                }
+                            })
+                        })?;
+                    reflect_component.copy_component(&scene.world, world, *scene_entity, entity);
+                }
+            }
+        }
+        for registration in type_registry.iter() {
+            if let Some(map_entities_reflect) = registration.data::<ReflectMapEntities>() {
+                map_entities_reflect
+                    .map_entities(world, entity_map)
+                    .unwrap();
+            }
+        }
+        if let Some(overrides) = overrides {
+            overrides.apply(world, entity_map, type_registry)?;
+        }
+        Ok(())
+    }
+
     pub fn update_spawned_scenes(
         &mut self,
         world: &mut World,
@@ -234,7 +289,19 @@ impl SceneSpawner {
         Ok(())
     }
 
+    /// Spawns the queued scenes whose asset is loaded. A scene that fails to spawn doesn't stop
+    /// the others from spawning: the first error is returned once the whole queue was processed,
+    /// and the following ones are logged.
     pub fn spawn_queued_scenes(&mut self, world: &mut World) -> Result<(), SceneSpawnError> {
+        let mut result = Ok(());
+        let mut report = |err: SceneSpawnError| {
+            if result.is_ok() {
+                result = Err(err);
+            } else {
+                error!("{}", err);
+            }
+        };
+
         let scenes_to_spawn = std::mem::take(&mut self.dynamic_scenes_to_spawn);
 
         for scene_handle in scenes_to_spawn {
@@ -243,7 +310,7 @@ impl SceneSpawner {
                 Err(SceneSpawnError::NonExistentScene { .. }) => {
                     self.dynamic_scenes_to_spawn.push(scene_handle)
                 }
-                Err(err) => return Err(err),
+                Err(err) => report(err),
             }
         }
 
@@ -255,11 +322,11 @@ impl SceneSpawner {
                 Err(SceneSpawnError::NonExistentRealScene { handle }) => {
                     self.scenes_to_spawn.push((handle, instance_id))
                 }
-                Err(err) => return Err(err),
+                Err(err) => report(err),
             }
         }
 
-        Ok(())
+        result
     }
 
     pub(crate) fn set_scene_instance_parent_sync(&mut self, world: &mut World) {
@@ -314,13 +381,71 @@ pub fn scene_spawner_system(world: &mut World) {
             }
         }
 
-        scene_spawner.despawn_queued_scenes(world).unwrap();
-        scene_spawner
-            .spawn_queued_scenes(world)
-            .unwrap_or_else(|err| panic!("{}", err));
-        scene_spawner
-            .update_spawned_scenes(world, &updated_spawned_scenes)
-            .unwrap();
+        if let Err(err) = scene_spawner.despawn_queued_scenes(world) {
+            error!("{}", err);
+        }
+        if let Err(err) = scene_spawner.spawn_queued_scenes(world) {
+            error!("{}", err);
+        }
+        if let Err(err) = scene_spawner.update_spawned_scenes(world, &updated_spawned_scenes) {
+            error!("{}", err);
+        }
         scene_spawner.set_scene_instance_parent_sync(world);
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{SceneSpawnError, SceneSpawner};
+    use crate::{Scene, SceneOverrides};
+    use bevy_app::App;
+    use bevy_asset::{AddAsset, AssetPlugin, Assets};
+    use bevy_ecs::{prelude::*, reflect::ReflectComponent};
+    use bevy_reflect::Reflect;
+    use bevy_tasks::{IoTaskPool, TaskPool};
+
+    #[derive(Reflect, Default)]
+    #[reflect(Component)]
+    struct Health(f32);
+
+    #[test]
+    fn failed_overrides_spawn_nothing() {
+        let mut app = App::build();
+        app.insert_resource(IoTaskPool(TaskPool::new()))
+            .add_plugin(AssetPlugin)
+            .add_asset::<Scene>()
+            .register_type::<Health>();
+        let world = app.world_mut();
+
+        let mut scene_world = World::default();
+        scene_world.spawn().insert(Health(10.0));
+        scene_world.spawn().insert(Health(5.0));
+        let scene = world
+            .get_resource_mut::<Assets<Scene>>()
+            .unwrap()
+            .add(Scene::new(scene_world));
+
+        let mut scene_spawner = SceneSpawner::default();
+        let invalid = scene_spawner.spawn_with_overrides(
+            scene.clone(),
+            SceneOverrides::default().root::<Health>("1", 0.0f32),
+        );
+        let valid = scene_spawner
+            .spawn_with_overrides(scene, SceneOverrides::default().root::<Health>("0", 1.0f32));
+        let result = scene_spawner.spawn_queued_scenes(world);
+
+        assert!(matches!(
+            result,
+            Err(SceneSpawnError::InvalidOverride { .. })
+        ));
+        assert!(!scene_spawner.instance_is_ready(invalid));
+        assert!(scene_spawner.instance_is_ready(valid));
+        let mut healths = world
+            .query::<&Health>()
+            .iter(world)
+            .map(|health| health.0)
+            .collect::<Vec<_>>();
+        healths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(healths, vec![1.0, 1.0]);
+    }
+}
@@ -26,8 +26,31 @@ impl<'a> Serialize for SceneSerializer<'a> {
     where
         S: serde::Serializer,
     {
-        let mut state = serializer.serialize_seq(Some(self.scene.entities.len()))?;
-        for entity in self.scene.entities.iter() {
+        let mut state = serializer.serialize_struct(SCENE_STRUCT, 2)?;
+        state.serialize_field(SCENE_FIELD_VERSION, &self.scene.version)?;
+        state.serialize_field(
+            SCENE_FIELD_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.scene.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.end()
+    }
+}
+
+pub struct EntitiesSerializer<'a> {
+    pub entities: &'a [Entity],
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> Serialize for EntitiesSerializer<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let mut state = serializer.serialize_seq(Some(self.entities.len()))?;
+        for entity in self.entities.iter() {
             state.serialize_element(&EntitySerializer {
                 entity,
                 registry: self.registry,
@@ -91,11 +114,93 @@ impl<'a, 'de> DeserializeSeed<'de> for SceneDeserializer<'a> {
     fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
     where
         D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(SceneVisitor {
+            type_registry: self.type_registry,
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SceneField {
+    Version,
+    Entities,
+}
+
+pub const SCENE_STRUCT: &str = "Scene";
+pub const SCENE_FIELD_VERSION: &str = "version";
+pub const SCENE_FIELD_ENTITIES: &str = "entities";
+
+struct SceneVisitor<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> Visitor<'de> for SceneVisitor<'a> {
+    type Value = DynamicScene;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("scene")
+    }
+
+    /// Scenes saved before versioning was introduced are a list of entities.
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
     {
         Ok(DynamicScene {
-            entities: deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            version: 0,
+            entities: SceneEntitySeqVisitor {
                 type_registry: self.type_registry,
-            })?,
+            }
+            .visit_seq(seq)?,
+        })
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut version = None;
+        let mut entities = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SceneField::Version => {
+                    if version.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_VERSION));
+                    }
+                    version = Some(map.next_value::<u32>()?);
+                }
+                SceneField::Entities => {
+                    if entities.is_some() {
+                        return Err(Error::duplicate_field(SCENE_FIELD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SceneEntitiesDeserializer {
+                        type_registry: self.type_registry,
+                    })?);
+                }
+            }
+        }
+
+        let version = version.ok_or_else(|| Error::missing_field(SCENE_FIELD_VERSION))?;
+        let entities = entities.ok_or_else(|| Error::missing_field(SCENE_FIELD_ENTITIES))?;
+        Ok(DynamicScene { version, entities })
+    }
+}
+
+struct SceneEntitiesDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+}
+
+impl<'a, 'de> DeserializeSeed<'de> for SceneEntitiesDeserializer<'a> {
+    type Value = Vec<Entity>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(SceneEntitySeqVisitor {
+            type_registry: self.type_registry,
         })
     }
 }
@@ -249,3 +354,53 @@ impl<'a, 'de> Visitor<'de> for ComponentSeqVisitor<'a> {
         Ok(dynamic_properties)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SceneDeserializer;
+    use crate::{DynamicScene, Entity};
+    use bevy_reflect::{DynamicStruct, Struct, TypeRegistryArc};
+    use serde::de::DeserializeSeed;
+
+    fn deserialize(ron: &str) -> DynamicScene {
+        let registry = TypeRegistryArc::default();
+        let mut deserializer = ron::de::Deserializer::from_str(ron).unwrap();
+        let type_registry = registry.read();
+        let scene = SceneDeserializer {
+            type_registry: &*type_registry,
+        }
+        .deserialize(&mut deserializer)
+        .unwrap();
+        scene
+    }
+
+    #[test]
+    fn version_round_trip() {
+        let mut component = DynamicStruct::default();
+        component.set_name("scene::Component".to_string());
+        let scene = DynamicScene {
+            version: 3,
+            entities: vec![Entity {
+                entity: 7,
+                components: vec![Box::new(component)],
+            }],
+        };
+        let registry = TypeRegistryArc::default();
+        let scene = deserialize(&scene.serialize_ron(&registry).unwrap());
+        assert_eq!(scene.version, 3);
+        assert_eq!(scene.entities.len(), 1);
+        assert_eq!(scene.entities[0].entity, 7);
+        let component = scene.entities[0].components[0]
+            .downcast_ref::<DynamicStruct>()
+            .unwrap();
+        assert_eq!(component.name(), "scene::Component");
+        assert_eq!(component.field_len(), 0);
+    }
+
+    #[test]
+    fn unversioned_scene() {
+        let scene = deserialize("[(entity: 0, components: [])]");
+        assert_eq!(scene.version, 0);
+        assert_eq!(scene.entities.len(), 1);
+    }
+}
@@ -22,7 +22,9 @@ pub fn build_sprite_sheet_pipeline(shaders: &mut Assets<Shader>) -> PipelineDesc
     PipelineDescriptor {
         depth_stencil: Some(DepthStencilState {
             format: TextureFormat::Depth32Float,
-            depth_write_enabled: true,
+            // sprites are transparent, so they are drawn in the order of their `SortKey2d`
+            // rather than depth tested against each other
+            depth_write_enabled: false,
             depth_compare: CompareFunction::LessEqual,
             stencil: StencilState {
                 front: StencilFaceState::IGNORE,
@@ -78,7 +80,9 @@ pub fn build_sprite_pipeline(shaders: &mut Assets<Shader>) -> PipelineDescriptor
     PipelineDescriptor {
         depth_stencil: Some(DepthStencilState {
             format: TextureFormat::Depth32Float,
-            depth_write_enabled: true,
+            // sprites are transparent, so they are drawn in the order of their `SortKey2d`
+            // rather than depth tested against each other
+            depth_write_enabled: false,
             depth_compare: CompareFunction::LessEqual,
             stencil: StencilState {
                 front: StencilFaceState::IGNORE,
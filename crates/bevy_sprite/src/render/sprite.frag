@@ -1,6 +1,9 @@
 #version 450
 
 layout(location = 0) in vec2 v_Uv;
+#ifdef VERTEX_COLORS
+layout(location = 1) in vec4 v_Color;
+#endif
 
 layout(location = 0) out vec4 o_Target;
 
@@ -20,5 +23,8 @@ void main() {
         sampler2D(ColorMaterial_texture, ColorMaterial_texture_sampler),
         v_Uv);
 # endif
+#ifdef VERTEX_COLORS
+    color *= v_Color;
+#endif
     o_Target = color;
 }
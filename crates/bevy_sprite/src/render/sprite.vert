@@ -4,6 +4,11 @@ layout(location = 0) in vec3 Vertex_Position;
 layout(location = 1) in vec3 Vertex_Normal;
 layout(location = 2) in vec2 Vertex_Uv;
 
+#ifdef VERTEX_COLORS
+layout(location = 3) in vec4 Vertex_Color;
+layout(location = 1) out vec4 v_Color;
+#endif
+
 layout(location = 0) out vec2 v_Uv;
 
 layout(set = 0, binding = 0) uniform CameraViewProj {
@@ -38,6 +43,9 @@ void main() {
     }
 
     v_Uv = uv;
+#ifdef VERTEX_COLORS
+    v_Color = Vertex_Color;
+#endif
 
     vec3 position = Vertex_Position * vec3(size, 1.0);
     gl_Position = ViewProj * Model * vec4(position, 1.0);
@@ -7,4 +7,6 @@ pub enum TextError {
     NoSuchFont,
     #[error("failed to add glyph to newly-created atlas {0:?}")]
     FailedToAddGlyph(GlyphId),
+    #[error("failed to tessellate the outline of glyph {0:?}")]
+    FailedToTessellateGlyph(GlyphId),
 }
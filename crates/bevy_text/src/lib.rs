@@ -8,6 +8,7 @@ mod glyph_brush;
 mod pipeline;
 mod text;
 mod text2d;
+mod text_mesh;
 
 pub use draw::*;
 pub use error::*;
@@ -19,10 +20,14 @@ pub use glyph_brush::*;
 pub use pipeline::*;
 pub use text::*;
 pub use text2d::*;
+pub use text_mesh::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{Font, Text, Text2dBundle, TextAlignment, TextError, TextSection, TextStyle};
+    pub use crate::{
+        Font, Text, Text2dBillboard, Text2dBounds, Text2dBundle, TextAlignment, TextError,
+        TextMesh, TextMeshStyle, TextSection, TextStyle,
+    };
     #[doc(hidden)]
     pub use glyph_brush_layout::{HorizontalAlign, VerticalAlign};
 }
@@ -43,7 +48,9 @@ impl Plugin for TextPlugin {
             .add_asset::<FontAtlasSet>()
             .init_asset_loader::<FontLoader>()
             .insert_resource(DefaultTextPipeline::default())
+            .init_resource::<GlyphMeshCache>()
             .add_system_to_stage(CoreStage::PostUpdate, text2d_system.system())
+            .add_system_to_stage(CoreStage::PostUpdate, text_mesh_system.system())
             .add_system_to_stage(RenderStage::Draw, text2d::draw_text2d_system.system());
     }
 }
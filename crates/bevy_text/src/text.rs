@@ -105,3 +105,23 @@ impl Default for TextStyle {
 pub struct Text2dSize {
     pub size: Size,
 }
+
+/// The area that [`Text2dBundle`](crate::Text2dBundle) text is laid out in, in logical pixels.
+/// Lines longer than `size.width` wrap. Unbounded by default.
+#[derive(Copy, Clone, Debug)]
+pub struct Text2dBounds {
+    pub size: Size,
+}
+
+impl Default for Text2dBounds {
+    fn default() -> Self {
+        Self {
+            size: Size::new(f32::MAX, f32::MAX),
+        }
+    }
+}
+
+/// Marks a [`Text2dBundle`](crate::Text2dBundle) entity whose text always faces the active 3D
+/// camera, keeping its position and scale but ignoring its rotation.
+#[derive(Default, Copy, Clone, Debug)]
+pub struct Text2dBillboard;
@@ -2,15 +2,16 @@ use bevy_asset::Assets;
 use bevy_ecs::{
     bundle::Bundle,
     entity::Entity,
-    query::{Changed, With, Without},
+    query::{Changed, Or, With, Without},
     system::{Local, Query, QuerySet, Res, ResMut},
 };
-use bevy_math::{Size, Vec3};
+use bevy_math::{Quat, Size, Vec3};
 use bevy_render::{
-    draw::{DrawContext, Drawable, OutsideFrustum},
+    camera::ActiveCameras,
+    draw::{DrawContext, DrawError, Drawable, OutsideFrustum},
     mesh::Mesh,
     prelude::{Draw, Msaa, Texture, Visible},
-    render_graph::base::MainPass,
+    render_graph::base::{camera::CAMERA_3D, MainPass},
     renderer::RenderResourceBindings,
 };
 use bevy_sprite::{TextureAtlas, QUAD_HANDLE};
@@ -18,10 +19,18 @@ use bevy_transform::prelude::{GlobalTransform, Transform};
 use bevy_window::Windows;
 use glyph_brush_layout::{HorizontalAlign, VerticalAlign};
 
-use crate::{DefaultTextPipeline, DrawableText, Font, FontAtlasSet, Text, Text2dSize, TextError};
+use crate::{
+    DefaultTextPipeline, DrawableText, Font, FontAtlasSet, Text, Text2dBillboard, Text2dBounds,
+    Text2dSize, TextError,
+};
 
 /// The bundle of components needed to draw text in a 2D scene via a 2D `OrthographicCameraBundle`.
 /// [Example usage.](https://github.com/bevyengine/bevy/blob/latest/examples/2d/text2d.rs)
+///
+/// The text is transparent, so it is sorted with sprites by its
+/// [`SortKey2d`](bevy_render::camera::SortKey2d), then by the z of its `GlobalTransform`: give it
+/// a higher layer or z than the sprites it should be drawn over. Insert [`Text2dBillboard`] to
+/// draw the text facing the camera in a 3D scene.
 #[derive(Bundle, Clone, Debug)]
 pub struct Text2dBundle {
     pub draw: Draw,
@@ -31,6 +40,7 @@ pub struct Text2dBundle {
     pub global_transform: GlobalTransform,
     pub main_pass: MainPass,
     pub text_2d_size: Text2dSize,
+    pub text_2d_bounds: Text2dBounds,
 }
 
 impl Default for Text2dBundle {
@@ -50,13 +60,14 @@ impl Default for Text2dBundle {
             text_2d_size: Text2dSize {
                 size: Size::default(),
             },
+            text_2d_bounds: Default::default(),
         }
     }
 }
 
 /// System for drawing text in a 2D scene via a 2D `OrthographicCameraBundle`. Included in the
-/// default `TextPlugin`. Position is determined by the `Transform`'s translation, though scale and
-/// rotation are ignored.
+/// default `TextPlugin`. The glyphs are placed with the entity's `GlobalTransform`; the rotation is
+/// replaced by the rotation of the active 3D camera for [`Text2dBillboard`] entities.
 #[allow(clippy::type_complexity)]
 pub fn draw_text2d_system(
     mut context: DrawContext,
@@ -65,6 +76,8 @@ pub fn draw_text2d_system(
     windows: Res<Windows>,
     mut render_resource_bindings: ResMut<RenderResourceBindings>,
     text_pipeline: Res<DefaultTextPipeline>,
+    active_cameras: Res<ActiveCameras>,
+    camera_transforms: Query<&GlobalTransform>,
     mut query: Query<
         (
             Entity,
@@ -73,6 +86,7 @@ pub fn draw_text2d_system(
             &Text,
             &GlobalTransform,
             &Text2dSize,
+            Option<&Text2dBillboard>,
         ),
         (With<MainPass>, Without<OutsideFrustum>),
     >,
@@ -86,7 +100,15 @@ pub fn draw_text2d_system(
         1.
     };
 
-    for (entity, mut draw, visible, text, global_transform, calculated_size) in query.iter_mut() {
+    let camera_rotation = active_cameras
+        .get(CAMERA_3D)
+        .and_then(|camera| camera.entity)
+        .and_then(|entity| camera_transforms.get(entity).ok())
+        .map(|transform| transform.rotation);
+
+    for (entity, mut draw, visible, text, global_transform, calculated_size, billboard) in
+        query.iter_mut()
+    {
         if !visible.is_visible {
             continue;
         }
@@ -104,9 +126,14 @@ pub fn draw_text2d_system(
                 HorizontalAlign::Right => Vec3::new(-width, 0.0, 0.0),
             };
 
+            let global_transform = match billboard {
+                Some(_) => billboard_transform(global_transform, camera_rotation),
+                None => *global_transform,
+            };
+
             let mut drawable_text = DrawableText {
                 render_resource_bindings: &mut render_resource_bindings,
-                global_transform: *global_transform,
+                global_transform,
                 scale_factor,
                 msaa: &msaa,
                 text_glyphs: &text_glyphs.glyphs,
@@ -115,11 +142,28 @@ pub fn draw_text2d_system(
                 alignment_offset,
             };
 
-            drawable_text.draw(&mut draw, &mut context).unwrap();
+            match drawable_text.draw(&mut draw, &mut context) {
+                // skip the text until its shaders are compiled
+                Err(DrawError::PipelineCompiling) => {}
+                result => result.unwrap(),
+            }
         }
     }
 }
 
+/// The transform of billboard text, facing a camera with `camera_rotation`. Without a camera,
+/// the text keeps its rotation.
+fn billboard_transform(
+    global_transform: &GlobalTransform,
+    camera_rotation: Option<Quat>,
+) -> GlobalTransform {
+    let mut global_transform = *global_transform;
+    if let Some(rotation) = camera_rotation {
+        global_transform.rotation = rotation;
+    }
+    global_transform
+}
+
 #[derive(Debug, Default)]
 pub struct QueuedText2d {
     entities: Vec<Entity>,
@@ -136,11 +180,11 @@ pub fn text2d_system(
     mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
     mut text_pipeline: ResMut<DefaultTextPipeline>,
     mut text_queries: QuerySet<(
-        Query<Entity, (With<MainPass>, Changed<Text>)>,
-        Query<(&Text, &mut Text2dSize), With<MainPass>>,
+        Query<Entity, (With<MainPass>, Or<(Changed<Text>, Changed<Text2dBounds>)>)>,
+        Query<(&Text, Option<&Text2dBounds>, &mut Text2dSize), With<MainPass>>,
     )>,
 ) {
-    // Adds all entities where the text or the style has changed to the local queue
+    // Adds all entities where the text, the style or the bounds have changed to the local queue
     for entity in text_queries.q0_mut().iter_mut() {
         queued_text.entities.push(entity);
     }
@@ -159,14 +203,19 @@ pub fn text2d_system(
     let mut new_queue = Vec::new();
     let query = text_queries.q1_mut();
     for entity in queued_text.entities.drain(..) {
-        if let Ok((text, mut calculated_size)) = query.get_mut(entity) {
+        if let Ok((text, bounds, mut calculated_size)) = query.get_mut(entity) {
+            let bounds = bounds.copied().unwrap_or_default().size;
+            let bounds = Size::new(
+                scale_bound(bounds.width, scale_factor),
+                scale_bound(bounds.height, scale_factor),
+            );
             match text_pipeline.queue_text(
                 entity,
                 &fonts,
                 &text.sections,
                 scale_factor,
                 text.alignment,
-                Size::new(f32::MAX, f32::MAX),
+                bounds,
                 &mut *font_atlas_set_storage,
                 &mut *texture_atlases,
                 &mut *textures,
@@ -198,3 +247,110 @@ pub fn text2d_system(
 pub fn scale_value(value: f32, factor: f64) -> f32 {
     (value as f64 * factor) as f32
 }
+
+/// Scales a logical bound to physical pixels, keeping unbounded dimensions unbounded.
+fn scale_bound(value: f32, factor: f64) -> f32 {
+    if value == f32::MAX {
+        value
+    } else {
+        scale_value(value, factor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{billboard_transform, scale_bound, Text2dBundle};
+    use bevy_ecs::{
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+    use bevy_math::{Quat, Vec3};
+    use bevy_render::{
+        camera::{visible_entities_system, Camera, DepthCalculation, SortKey2d, VisibleEntities},
+        prelude::Visible,
+    };
+    use bevy_transform::components::GlobalTransform;
+
+    #[test]
+    fn scale_bounds() {
+        assert_eq!(scale_bound(f32::MAX, 2.0), f32::MAX);
+        assert_eq!(scale_bound(100.0, 2.0), 200.0);
+        assert_eq!(scale_bound(100.0, 0.5), 50.0);
+    }
+
+    #[test]
+    fn billboard_faces_camera() {
+        let global_transform = GlobalTransform {
+            translation: Vec3::new(1.0, 2.0, 3.0),
+            rotation: Quat::from_rotation_x(1.0),
+            scale: Vec3::splat(2.0),
+        };
+        let camera_rotation = Quat::from_rotation_y(0.5);
+        let billboard = billboard_transform(&global_transform, Some(camera_rotation));
+        assert_eq!(billboard.translation, global_transform.translation);
+        assert_eq!(billboard.scale, global_transform.scale);
+        assert_eq!(billboard.rotation, camera_rotation);
+
+        // without a 3d camera, the text keeps its rotation
+        let billboard = billboard_transform(&global_transform, None);
+        assert_eq!(billboard.rotation, global_transform.rotation);
+    }
+
+    #[test]
+    fn sorted_with_sprites() {
+        let mut world = World::default();
+        let camera = world
+            .spawn()
+            .insert_bundle((
+                Camera {
+                    depth_calculation: DepthCalculation::ZDifference,
+                    ..Default::default()
+                },
+                GlobalTransform::from_xyz(0.0, 0.0, 1000.0),
+                VisibleEntities::default(),
+            ))
+            .id();
+        let mut spawn_sprite = |z: f32| {
+            world
+                .spawn()
+                .insert_bundle((
+                    Visible {
+                        is_visible: true,
+                        is_transparent: true,
+                    },
+                    GlobalTransform::from_xyz(0.0, 0.0, z),
+                ))
+                .id()
+        };
+        let back_sprite = spawn_sprite(1.0);
+        let front_sprite = spawn_sprite(3.0);
+        let text = world
+            .spawn()
+            .insert_bundle(Text2dBundle {
+                global_transform: GlobalTransform::from_xyz(0.0, 0.0, 2.0),
+                ..Default::default()
+            })
+            .id();
+        let foreground_text = world
+            .spawn()
+            .insert_bundle(Text2dBundle::default())
+            .insert(SortKey2d::layer(1))
+            .id();
+
+        let mut stage = SystemStage::parallel();
+        stage.add_system(visible_entities_system.system());
+        stage.run(&mut world);
+
+        let visible = world
+            .get::<VisibleEntities>(camera)
+            .unwrap()
+            .iter()
+            .map(|visible| visible.entity)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            visible,
+            vec![back_sprite, text, front_sprite, foreground_text]
+        );
+    }
+}
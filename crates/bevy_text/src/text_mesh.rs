@@ -0,0 +1,621 @@
+use std::collections::hash_map::Entry;
+
+use ab_glyph::{Font as _, GlyphId, OutlineCurve, Point, PxScale, ScaleFont as _};
+use bevy_asset::{AssetEvent, Assets, Handle, HandleId};
+use bevy_ecs::{
+    entity::Entity,
+    event::EventReader,
+    query::Changed,
+    system::{Local, Query, Res, ResMut},
+};
+use bevy_math::Vec2;
+use bevy_render::{
+    mesh::{Indices, Mesh},
+    pipeline::PrimitiveTopology,
+};
+use bevy_utils::HashMap;
+use glyph_brush_layout::HorizontalAlign;
+use lyon_tessellation::{
+    geometry_builder::{BuffersBuilder, VertexBuffers},
+    math::point,
+    path::Path,
+    FillOptions, FillTessellator, FillVertex,
+};
+
+use crate::{Font, TextError};
+
+/// Text drawn as a 3D mesh, so it is lit and depth tested like any other geometry. Insert it on
+/// an entity with a `PbrBundle`: the entity's `Handle<Mesh>` is replaced with the mesh of the text
+/// whenever the text changes.
+///
+/// The text starts at the entity's origin on the baseline of the first line, in the xy plane
+/// facing +z. Each line is aligned horizontally on the origin.
+#[derive(Clone, Debug)]
+pub struct TextMesh {
+    pub value: String,
+    pub font: Handle<Font>,
+    pub style: TextMeshStyle,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextMeshStyle {
+    /// The height of the font, in world units.
+    pub font_size: f32,
+    /// The thickness of the glyphs along the z axis. Glyphs are flat when it is zero.
+    pub depth: f32,
+    /// The maximum distance between the curves of the glyphs and their mesh, in world units.
+    pub tolerance: f32,
+    pub alignment: HorizontalAlign,
+}
+
+impl Default for TextMeshStyle {
+    fn default() -> Self {
+        Self {
+            font_size: 1.0,
+            depth: 0.0,
+            tolerance: 0.01,
+            alignment: HorizontalAlign::Left,
+        }
+    }
+}
+
+/// The triangles of a single glyph, relative to its origin on the baseline.
+#[derive(Clone, Debug, Default)]
+pub struct GlyphMesh {
+    pub positions: Vec<[f32; 3]>,
+    pub normals: Vec<[f32; 3]>,
+    pub indices: Vec<u32>,
+}
+
+impl GlyphMesh {
+    pub fn new(font: &Font, glyph_id: GlyphId, style: &TextMeshStyle) -> Result<Self, TextError> {
+        let mut mesh = GlyphMesh::default();
+        let outline = match font.font.outline(glyph_id) {
+            Some(outline) => outline,
+            None => return Ok(mesh),
+        };
+        let scaled_font = font.font.as_scaled(PxScale::from(style.font_size));
+        let scale = Vec2::new(scaled_font.h_scale_factor(), scaled_font.v_scale_factor());
+        let contours = flatten_contours(&outline.curves, scale, style.tolerance);
+        if contours.is_empty() {
+            return Ok(mesh);
+        }
+
+        let mut builder = Path::builder();
+        for contour in contours.iter() {
+            builder.begin(point(contour[0].x, contour[0].y));
+            for position in contour[1..].iter() {
+                builder.line_to(point(position.x, position.y));
+            }
+            builder.end(true);
+        }
+        let path = builder.build();
+        let mut buffers: VertexBuffers<[f32; 2], u32> = VertexBuffers::new();
+        FillTessellator::new()
+            .tessellate_path(
+                &path,
+                &FillOptions::non_zero().with_tolerance(style.tolerance),
+                &mut BuffersBuilder::new(&mut buffers, |vertex: FillVertex| {
+                    vertex.position().to_array()
+                }),
+            )
+            .map_err(|_| TextError::FailedToTessellateGlyph(glyph_id))?;
+
+        // make every triangle counter-clockwise when seen from the front
+        for triangle in buffers.indices.chunks_exact_mut(3) {
+            let a = Vec2::from(buffers.vertices[triangle[0] as usize]);
+            let b = Vec2::from(buffers.vertices[triangle[1] as usize]);
+            let c = Vec2::from(buffers.vertices[triangle[2] as usize]);
+            if (b - a).perp_dot(c - a) < 0.0 {
+                triangle.swap(1, 2);
+            }
+        }
+
+        let half_depth = style.depth * 0.5;
+        mesh.add_face(&buffers, half_depth, 1.0);
+        if style.depth <= 0.0 {
+            return Ok(mesh);
+        }
+        mesh.add_face(&buffers, -half_depth, -1.0);
+
+        // the filled side of the contours is on the left of their direction when the outer
+        // contours are counter-clockwise
+        let filled_on_left = contours
+            .iter()
+            .map(|contour| signed_area(contour))
+            .sum::<f32>()
+            > 0.0;
+        for contour in contours.iter() {
+            for (i, &start) in contour.iter().enumerate() {
+                let end = contour[(i + 1) % contour.len()];
+                let (a, b) = if filled_on_left {
+                    (start, end)
+                } else {
+                    (end, start)
+                };
+                if a == b {
+                    continue;
+                }
+                let direction = (b - a).normalize();
+                let normal = [direction.y, -direction.x, 0.0];
+                let base = mesh.positions.len() as u32;
+                mesh.positions.extend_from_slice(&[
+                    [a.x, a.y, half_depth],
+                    [b.x, b.y, half_depth],
+                    [b.x, b.y, -half_depth],
+                    [a.x, a.y, -half_depth],
+                ]);
+                mesh.normals.extend_from_slice(&[normal; 4]);
+                mesh.indices.extend_from_slice(&[
+                    base,
+                    base + 3,
+                    base + 2,
+                    base,
+                    base + 2,
+                    base + 1,
+                ]);
+            }
+        }
+
+        Ok(mesh)
+    }
+
+    fn add_face(&mut self, buffers: &VertexBuffers<[f32; 2], u32>, z: f32, facing: f32) {
+        let base = self.positions.len() as u32;
+        self.positions
+            .extend(buffers.vertices.iter().map(|[x, y]| [*x, *y, z]));
+        self.normals
+            .extend(std::iter::repeat([0.0, 0.0, facing]).take(buffers.vertices.len()));
+        for triangle in buffers.indices.chunks_exact(3) {
+            if facing > 0.0 {
+                self.indices.extend_from_slice(&[
+                    base + triangle[0],
+                    base + triangle[1],
+                    base + triangle[2],
+                ]);
+            } else {
+                self.indices.extend_from_slice(&[
+                    base + triangle[0],
+                    base + triangle[2],
+                    base + triangle[1],
+                ]);
+            }
+        }
+    }
+}
+
+/// Splits the curves of a glyph outline into closed polylines, scaled to world units.
+fn flatten_contours(curves: &[OutlineCurve], scale: Vec2, tolerance: f32) -> Vec<Vec<Vec2>> {
+    let to_vec2 = |point: &Point| Vec2::new(point.x, point.y) * scale;
+    let mut contours: Vec<Vec<Vec2>> = Vec::new();
+    for curve in curves.iter() {
+        let (start, points) = match curve {
+            OutlineCurve::Line(p0, p1) => (to_vec2(p0), vec![to_vec2(p1)]),
+            OutlineCurve::Quad(p0, p1, p2) => {
+                let [p0, p1, p2] = [to_vec2(p0), to_vec2(p1), to_vec2(p2)];
+                let deviation = (p0 - 2.0 * p1 + p2).length() * 0.25;
+                let segments = segment_count(deviation, tolerance);
+                let points = (1..=segments)
+                    .map(|i| {
+                        let t = i as f32 / segments as f32;
+                        let mt = 1.0 - t;
+                        p0 * mt * mt + p1 * 2.0 * mt * t + p2 * t * t
+                    })
+                    .collect();
+                (p0, points)
+            }
+            OutlineCurve::Cubic(p0, p1, p2, p3) => {
+                let [p0, p1, p2, p3] = [to_vec2(p0), to_vec2(p1), to_vec2(p2), to_vec2(p3)];
+                let deviation = (p0 - 2.0 * p1 + p2)
+                    .length()
+                    .max((p1 - 2.0 * p2 + p3).length())
+                    * 0.75;
+                let segments = segment_count(deviation, tolerance);
+                let points = (1..=segments)
+                    .map(|i| {
+                        let t = i as f32 / segments as f32;
+                        let mt = 1.0 - t;
+                        p0 * mt * mt * mt
+                            + p1 * 3.0 * mt * mt * t
+                            + p2 * 3.0 * mt * t * t
+                            + p3 * t * t * t
+                    })
+                    .collect();
+                (p0, points)
+            }
+        };
+        match contours.last_mut() {
+            Some(contour) if contour.last() == Some(&start) => contour.extend(points),
+            _ => {
+                let mut contour = vec![start];
+                contour.extend(points);
+                contours.push(contour);
+            }
+        }
+    }
+
+    for contour in contours.iter_mut() {
+        if contour.len() > 1 && contour.first() == contour.last() {
+            contour.pop();
+        }
+    }
+    contours.retain(|contour| contour.len() >= 3);
+    contours
+}
+
+fn segment_count(deviation: f32, tolerance: f32) -> usize {
+    ((deviation / tolerance.max(f32::EPSILON)).sqrt().ceil() as usize).clamp(1, 64)
+}
+
+fn signed_area(contour: &[Vec2]) -> f32 {
+    let mut area = 0.0;
+    for (i, a) in contour.iter().enumerate() {
+        let b = contour[(i + 1) % contour.len()];
+        area += a.perp_dot(b);
+    }
+    area * 0.5
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct GlyphMeshKey {
+    font: HandleId,
+    glyph_id: GlyphId,
+    font_size: u32,
+    depth: u32,
+    tolerance: u32,
+}
+
+/// The meshes of the glyphs used by [`TextMesh`]es, built once for each font, glyph and style.
+#[derive(Debug, Default)]
+pub struct GlyphMeshCache {
+    meshes: HashMap<GlyphMeshKey, GlyphMesh>,
+}
+
+impl GlyphMeshCache {
+    pub fn get_or_insert(
+        &mut self,
+        font_handle: &Handle<Font>,
+        font: &Font,
+        glyph_id: GlyphId,
+        style: &TextMeshStyle,
+    ) -> Result<&GlyphMesh, TextError> {
+        let key = GlyphMeshKey {
+            font: font_handle.id,
+            glyph_id,
+            font_size: style.font_size.to_bits(),
+            depth: style.depth.to_bits(),
+            tolerance: style.tolerance.to_bits(),
+        };
+        Ok(match self.meshes.entry(key) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(GlyphMesh::new(font, glyph_id, style)?),
+        })
+    }
+
+    /// Removes the glyphs of a font, e.g. after it was modified.
+    pub fn remove_font(&mut self, font: HandleId) {
+        self.meshes.retain(|key, _| key.font != font);
+    }
+
+    pub fn clear(&mut self) {
+        self.meshes.clear();
+    }
+}
+
+/// Builds the mesh of a [`TextMesh`], using the cached meshes of its glyphs.
+pub fn build_text_mesh(
+    text: &TextMesh,
+    font: &Font,
+    cache: &mut GlyphMeshCache,
+) -> Result<Mesh, TextError> {
+    let style = &text.style;
+    let scaled_font = font.font.as_scaled(PxScale::from(style.font_size));
+    let line_height = scaled_font.height() + scaled_font.line_gap();
+
+    let mut positions = Vec::<[f32; 3]>::new();
+    let mut normals = Vec::<[f32; 3]>::new();
+    let mut uvs = Vec::<[f32; 2]>::new();
+    let mut indices = Vec::<u32>::new();
+    for (line_index, line) in text.value.lines().enumerate() {
+        let mut glyphs = Vec::new();
+        let mut caret = 0.0;
+        let mut previous = None;
+        for character in line.chars() {
+            let glyph_id = scaled_font.glyph_id(character);
+            if let Some(previous) = previous {
+                caret += scaled_font.kern(previous, glyph_id);
+            }
+            glyphs.push((glyph_id, caret));
+            caret += scaled_font.h_advance(glyph_id);
+            previous = Some(glyph_id);
+        }
+
+        let offset_x = match style.alignment {
+            HorizontalAlign::Left => 0.0,
+            HorizontalAlign::Center => -caret * 0.5,
+            HorizontalAlign::Right => -caret,
+        };
+        let offset_y = -(line_index as f32) * line_height;
+        for (glyph_id, x) in glyphs {
+            let glyph_mesh = cache.get_or_insert(&text.font, font, glyph_id, style)?;
+            let base = positions.len() as u32;
+            for [px, py, pz] in glyph_mesh.positions.iter() {
+                let position = [px + x + offset_x, py + offset_y, *pz];
+                positions.push(position);
+                uvs.push([position[0] / style.font_size, position[1] / style.font_size]);
+            }
+            normals.extend_from_slice(&glyph_mesh.normals);
+            indices.extend(glyph_mesh.indices.iter().map(|index| base + index));
+        }
+    }
+
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh.set_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.set_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.set_attribute(Mesh::ATTRIBUTE_UV_0, uvs);
+    Ok(mesh)
+}
+
+/// Rebuilds the mesh of each [`TextMesh`] that changed, or whose font was loaded or modified.
+pub fn text_mesh_system(
+    mut queued_text: Local<Vec<Entity>>,
+    mut font_events: EventReader<AssetEvent<Font>>,
+    fonts: Res<Assets<Font>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut glyph_mesh_cache: ResMut<GlyphMeshCache>,
+    changed_text: Query<Entity, Changed<TextMesh>>,
+    mut text_meshes: Query<(Entity, &TextMesh, &mut Handle<Mesh>)>,
+) {
+    queued_text.extend(changed_text.iter());
+    for event in font_events.iter() {
+        let handle = match event {
+            AssetEvent::Modified { handle } | AssetEvent::Removed { handle } => handle,
+            AssetEvent::Created { .. } => continue,
+        };
+        glyph_mesh_cache.remove_font(handle.id);
+        queued_text.extend(
+            text_meshes
+                .iter_mut()
+                .filter(|(_, text, _)| text.font == *handle)
+                .map(|(entity, _, _)| entity),
+        );
+    }
+
+    let mut new_queue = Vec::new();
+    for entity in queued_text.drain(..) {
+        let (_, text, mut mesh) = match text_meshes.get_mut(entity) {
+            Ok(text_mesh) => text_mesh,
+            Err(_) => continue,
+        };
+        let font = match fonts.get(&text.font) {
+            Some(font) => font,
+            None => {
+                // the font isn't loaded yet
+                new_queue.push(entity);
+                continue;
+            }
+        };
+        match build_text_mesh(text, font, &mut glyph_mesh_cache) {
+            Ok(text_mesh) => *mesh = meshes.add(text_mesh),
+            Err(e) => panic!("Fatal error when building text mesh: {}.", e),
+        }
+    }
+
+    *queued_text = new_queue;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_text_mesh, flatten_contours, signed_area, GlyphMesh, GlyphMeshCache, TextMesh,
+        TextMeshStyle,
+    };
+    use crate::Font;
+    use ab_glyph::{Font as _, PxScale, ScaleFont as _};
+    use bevy_asset::Handle;
+    use bevy_math::{Vec2, Vec3};
+    use bevy_render::mesh::{Indices, Mesh, VertexAttributeValues};
+    use glyph_brush_layout::HorizontalAlign;
+
+    fn font() -> Font {
+        Font::try_from_bytes(include_bytes!("../../../assets/fonts/FiraSans-Bold.ttf").to_vec())
+            .unwrap()
+    }
+
+    fn glyph_mesh(font: &Font, character: char, style: &TextMeshStyle) -> GlyphMesh {
+        GlyphMesh::new(font, font.font.glyph_id(character), style).unwrap()
+    }
+
+    /// The signed area of each triangle in the xy plane, positive when counter-clockwise.
+    fn triangle_areas(mesh: &GlyphMesh) -> impl Iterator<Item = (f32, [u32; 3])> + '_ {
+        let position = move |index: u32| {
+            let [x, y, _] = mesh.positions[index as usize];
+            Vec2::new(x, y)
+        };
+        mesh.indices.chunks_exact(3).map(move |triangle| {
+            let triangle = [triangle[0], triangle[1], triangle[2]];
+            let [a, b, c] = [
+                position(triangle[0]),
+                position(triangle[1]),
+                position(triangle[2]),
+            ];
+            ((b - a).perp_dot(c - a) * 0.5, triangle)
+        })
+    }
+
+    #[test]
+    fn flat_glyph() {
+        let font = font();
+        let style = TextMeshStyle::default();
+        // a glyph with a hole
+        let mesh = glyph_mesh(&font, 'O', &style);
+        assert!(!mesh.indices.is_empty());
+        assert_eq!(mesh.indices.len() % 3, 0);
+        assert_eq!(mesh.positions.len(), mesh.normals.len());
+        assert!(mesh.positions.iter().all(|position| position[2] == 0.0));
+        assert!(mesh.normals.iter().all(|normal| *normal == [0.0, 0.0, 1.0]));
+
+        // the triangles face the front and cover the outline, without its hole
+        let mut area = 0.0;
+        for (triangle_area, _) in triangle_areas(&mesh) {
+            assert!(triangle_area >= 0.0);
+            area += triangle_area;
+        }
+        let outline = font.font.outline(font.font.glyph_id('O')).unwrap();
+        let scaled_font = font.font.as_scaled(PxScale::from(style.font_size));
+        let scale = Vec2::new(scaled_font.h_scale_factor(), scaled_font.v_scale_factor());
+        let contours = flatten_contours(&outline.curves, scale, style.tolerance);
+        assert_eq!(contours.len(), 2);
+        let outline_area = contours
+            .iter()
+            .map(|contour| signed_area(contour))
+            .sum::<f32>()
+            .abs();
+        assert!((area - outline_area).abs() < outline_area * 1e-3);
+    }
+
+    #[test]
+    fn extruded_glyph() {
+        let font = font();
+        let flat = glyph_mesh(&font, 'I', &TextMeshStyle::default());
+        let style = TextMeshStyle {
+            depth: 0.2,
+            ..Default::default()
+        };
+        let mesh = glyph_mesh(&font, 'I', &style);
+        assert!(mesh
+            .positions
+            .iter()
+            .all(|position| position[2] == 0.1 || position[2] == -0.1));
+
+        // a front face, a back face, then a quad for each edge of the outline
+        let faces = flat.positions.len() * 2;
+        assert_eq!((mesh.positions.len() - faces) % 4, 0);
+        assert_eq!(mesh.normals[flat.positions.len()], [0.0, 0.0, -1.0]);
+
+        let center = mesh
+            .positions
+            .iter()
+            .fold(Vec3::ZERO, |sum, position| sum + Vec3::from(*position))
+            / mesh.positions.len() as f32;
+        for (area, triangle) in triangle_areas(&mesh) {
+            let normal = Vec3::from(mesh.normals[triangle[0] as usize]);
+            if normal.z > 0.0 {
+                assert!(area >= 0.0);
+            } else if normal.z < 0.0 {
+                // the back face is counter-clockwise when seen from the back
+                assert!(area <= 0.0);
+            } else {
+                // the sides of the convex glyph face away from its center
+                let position = Vec3::from(mesh.positions[triangle[0] as usize]);
+                assert!((normal.length() - 1.0).abs() < 1e-5);
+                assert!((position - center).dot(normal) > 0.0);
+            }
+        }
+    }
+
+    #[test]
+    fn empty_glyph() {
+        let mesh = glyph_mesh(&font(), ' ', &TextMeshStyle::default());
+        assert!(mesh.positions.is_empty());
+        assert!(mesh.indices.is_empty());
+    }
+
+    fn positions(mesh: &Mesh) -> &[[f32; 3]] {
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions,
+            _ => panic!("the text mesh has no positions"),
+        }
+    }
+
+    #[test]
+    fn text_mesh_lines() {
+        let font = font();
+        let mut cache = GlyphMeshCache::default();
+        let style = TextMeshStyle {
+            font_size: 2.0,
+            ..Default::default()
+        };
+        let glyph = glyph_mesh(&font, 'I', &style);
+        let text = TextMesh {
+            value: "I\nII".to_string(),
+            font: Handle::default(),
+            style,
+        };
+        let mesh = build_text_mesh(&text, &font, &mut cache).unwrap();
+        // the glyph is only built once
+        assert_eq!(cache.meshes.len(), 1);
+
+        let positions = positions(&mesh);
+        assert_eq!(positions.len(), glyph.positions.len() * 3);
+        match mesh.indices() {
+            Some(Indices::U32(indices)) => {
+                assert_eq!(indices.len(), glyph.indices.len() * 3);
+                assert!(indices
+                    .iter()
+                    .all(|index| (*index as usize) < positions.len()));
+            }
+            _ => panic!("the text mesh has no indices"),
+        }
+        match mesh.attribute(Mesh::ATTRIBUTE_UV_0) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => {
+                assert_eq!(uvs[0], [positions[0][0] / 2.0, positions[0][1] / 2.0]);
+            }
+            _ => panic!("the text mesh has no uvs"),
+        }
+
+        // the second line is one line below the first, and its second glyph one advance after
+        let scaled_font = font.font.as_scaled(PxScale::from(2.0));
+        let line_height = scaled_font.height() + scaled_font.line_gap();
+        let advance = scaled_font.h_advance(font.font.glyph_id('I'));
+        let count = glyph.positions.len();
+        for i in 0..count {
+            let first = positions[i];
+            let second_line = positions[count + i];
+            let third = positions[count * 2 + i];
+            assert!((second_line[0] - first[0]).abs() < 1e-5);
+            assert!((second_line[1] - (first[1] - line_height)).abs() < 1e-5);
+            assert!((third[0] - (second_line[0] + advance)).abs() < 1e-5);
+        }
+
+        cache.remove_font(Handle::<Font>::default().id);
+        assert!(cache.meshes.is_empty());
+    }
+
+    #[test]
+    fn text_mesh_alignment() {
+        let font = font();
+        let mut cache = GlyphMeshCache::default();
+        let mut text = TextMesh {
+            value: "II".to_string(),
+            font: Handle::default(),
+            style: TextMeshStyle::default(),
+        };
+        let bounds = |text: &TextMesh, cache: &mut GlyphMeshCache| {
+            let mesh = build_text_mesh(text, &font, cache).unwrap();
+            positions(&mesh)
+                .iter()
+                .fold((f32::MAX, f32::MIN), |(min, max), position| {
+                    (min.min(position[0]), max.max(position[0]))
+                })
+        };
+        let (left_min, left_max) = bounds(&text, &mut cache);
+        assert!(left_min >= 0.0);
+
+        let width = font
+            .font
+            .as_scaled(PxScale::from(1.0))
+            .h_advance(font.font.glyph_id('I'))
+            * 2.0;
+        text.style.alignment = HorizontalAlign::Right;
+        let (right_min, right_max) = bounds(&text, &mut cache);
+        assert!(right_max <= 0.0);
+        assert!((right_min - (left_min - width)).abs() < 1e-5);
+        assert!((right_max - (left_max - width)).abs() < 1e-5);
+
+        text.style.alignment = HorizontalAlign::Center;
+        let (center_min, _) = bounds(&text, &mut cache);
+        assert!((center_min - (left_min - width * 0.5)).abs() < 1e-5);
+    }
+}
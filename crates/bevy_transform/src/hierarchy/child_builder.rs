@@ -40,6 +40,33 @@ pub struct PushChildren {
     children: SmallVec<[Entity; 8]>,
 }
 
+#[derive(Debug)]
+pub struct RemoveChildren {
+    parent: Entity,
+    children: SmallVec<[Entity; 8]>,
+}
+
+/// Removes `children` from the [`Children`] of `parent`. Children which are parented to `parent`
+/// lose their [`Parent`] and [`PreviousParent`] components and become root entities.
+fn remove_children(parent: Entity, children: &[Entity], world: &mut World) {
+    for child in children.iter() {
+        if let Some(mut child) = world.get_entity_mut(*child) {
+            if child.get::<Parent>().map_or(false, |p| p.0 == parent) {
+                child.remove_bundle_intersection::<(Parent, PreviousParent)>();
+            }
+        }
+    }
+    if let Some(mut parent_children) = world.get_mut::<Children>(parent) {
+        parent_children.0.retain(|c| !children.contains(c));
+    }
+}
+
+impl Command for RemoveChildren {
+    fn write(self, world: &mut World) {
+        remove_children(self.parent, &self.children, world);
+    }
+}
+
 pub struct ChildBuilder<'a, 'b> {
     commands: &'b mut Commands<'a>,
     push_children: PushChildren,
@@ -75,13 +102,13 @@ impl<'a, 'b> ChildBuilder<'a, 'b> {
     pub fn spawn_bundle(&mut self, bundle: impl Bundle) -> EntityCommands<'a, '_> {
         let e = self.commands.spawn_bundle(bundle);
         self.push_children.children.push(e.id());
-        e
+        e.into_entity_commands()
     }
 
     pub fn spawn(&mut self) -> EntityCommands<'a, '_> {
         let e = self.commands.spawn();
         self.push_children.children.push(e.id());
-        e
+        e.into_entity_commands()
     }
 
     pub fn parent_entity(&self) -> Entity {
@@ -98,6 +125,10 @@ pub trait BuildChildren {
     fn with_children(&mut self, f: impl FnOnce(&mut ChildBuilder)) -> &mut Self;
     fn push_children(&mut self, children: &[Entity]) -> &mut Self;
     fn insert_children(&mut self, index: usize, children: &[Entity]) -> &mut Self;
+    /// Adds a single child to the end of the [`Children`] of this entity.
+    fn add_child(&mut self, child: Entity) -> &mut Self;
+    /// Removes the given children from this entity, without despawning them.
+    fn remove_children(&mut self, children: &[Entity]) -> &mut Self;
 }
 
 impl<'a, 'b> BuildChildren for EntityCommands<'a, 'b> {
@@ -137,6 +168,24 @@ impl<'a, 'b> BuildChildren for EntityCommands<'a, 'b> {
         });
         self
     }
+
+    fn add_child(&mut self, child: Entity) -> &mut Self {
+        let parent = self.id();
+        self.commands().add(PushChildren {
+            children: smallvec::smallvec![child],
+            parent,
+        });
+        self
+    }
+
+    fn remove_children(&mut self, children: &[Entity]) -> &mut Self {
+        let parent = self.id();
+        self.commands().add(RemoveChildren {
+            children: SmallVec::from(children),
+            parent,
+        });
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -196,6 +245,10 @@ pub trait BuildWorldChildren {
     fn with_children(&mut self, spawn_children: impl FnOnce(&mut WorldChildBuilder)) -> &mut Self;
     fn push_children(&mut self, children: &[Entity]) -> &mut Self;
     fn insert_children(&mut self, index: usize, children: &[Entity]) -> &mut Self;
+    /// Adds a single child to the end of the [`Children`] of this entity.
+    fn add_child(&mut self, child: Entity) -> &mut Self;
+    /// Removes the given children from this entity, without despawning them.
+    fn remove_children(&mut self, children: &[Entity]) -> &mut Self;
 }
 
 impl<'w> BuildWorldChildren for EntityMut<'w> {
@@ -256,6 +309,22 @@ impl<'w> BuildWorldChildren for EntityMut<'w> {
         }
         self
     }
+
+    fn add_child(&mut self, child: Entity) -> &mut Self {
+        self.push_children(&[child])
+    }
+
+    fn remove_children(&mut self, children: &[Entity]) -> &mut Self {
+        let parent = self.id();
+        {
+            // SAFE: self.update_location() is called below. Moving the children to other
+            // archetypes may have moved the parent within its table
+            let world = unsafe { self.world_mut() };
+            remove_children(parent, children, world);
+        }
+        self.update_location();
+        self
+    }
 }
 
 impl<'w> BuildWorldChildren for WorldChildBuilder<'w> {
@@ -315,6 +384,18 @@ impl<'w> BuildWorldChildren for WorldChildBuilder<'w> {
         }
         self
     }
+
+    fn add_child(&mut self, child: Entity) -> &mut Self {
+        self.push_children(&[child])
+    }
+
+    fn remove_children(&mut self, children: &[Entity]) -> &mut Self {
+        let parent = self
+            .current_entity
+            .expect("Cannot remove children without a parent. Try creating an entity first.");
+        remove_children(parent, children, self.world);
+        self
+    }
 }
 
 #[cfg(test)]
@@ -471,4 +552,87 @@ mod tests {
             PreviousParent(parent)
         );
     }
+
+    #[test]
+    fn add_and_remove_children_commands() {
+        let mut world = World::default();
+
+        let entities = world
+            .spawn_batch(vec![(1,), (2,), (3,), (4,)])
+            .collect::<Vec<Entity>>();
+        let parent = entities[0];
+        let child1 = entities[1];
+        let child2 = entities[2];
+        let child3 = entities[3];
+
+        let mut queue = CommandQueue::default();
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands
+                .entity(parent)
+                .add_child(child1)
+                .add_child(child2)
+                .add_child(child3);
+        }
+        queue.apply(&mut world);
+
+        let expected_children: SmallVec<[Entity; 8]> = smallvec![child1, child2, child3];
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().0.clone(),
+            expected_children
+        );
+        assert_eq!(*world.get::<Parent>(child3).unwrap(), Parent(parent));
+
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(parent).remove_children(&[child1, child3]);
+        }
+        queue.apply(&mut world);
+
+        let expected_children: SmallVec<[Entity; 8]> = smallvec![child2];
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().0.clone(),
+            expected_children
+        );
+        assert!(world.get::<Parent>(child1).is_none());
+        assert!(world.get::<PreviousParent>(child1).is_none());
+        assert!(world.get::<Parent>(child3).is_none());
+        assert_eq!(*world.get::<Parent>(child2).unwrap(), Parent(parent));
+    }
+
+    #[test]
+    fn add_and_remove_children_world() {
+        let mut world = World::default();
+
+        let entities = world
+            .spawn_batch(vec![(1,), (2,), (3,), (4,)])
+            .collect::<Vec<Entity>>();
+        let parent = entities[0];
+        let child1 = entities[1];
+        let child2 = entities[2];
+        let child3 = entities[3];
+
+        world
+            .entity_mut(parent)
+            .add_child(child1)
+            .add_child(child2)
+            .add_child(child3);
+
+        let expected_children: SmallVec<[Entity; 8]> = smallvec![child1, child2, child3];
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().0.clone(),
+            expected_children
+        );
+
+        world.entity_mut(parent).remove_children(&[child2]);
+
+        let expected_children: SmallVec<[Entity; 8]> = smallvec![child1, child3];
+        assert_eq!(
+            world.get::<Children>(parent).unwrap().0.clone(),
+            expected_children
+        );
+        assert!(world.get::<Parent>(child2).is_none());
+        assert!(world.get::<PreviousParent>(child2).is_none());
+        assert_eq!(*world.get::<Parent>(child1).unwrap(), Parent(parent));
+    }
 }
@@ -36,15 +36,38 @@ fn despawn_with_children_recursive_inner(world: &mut World, entity: Entity) {
     }
 }
 
+/// Despawns all descendants of `entity`, keeping the entity itself.
+pub fn despawn_descendants(world: &mut World, entity: Entity) {
+    if let Some(mut children) = world.get_mut::<Children>(entity) {
+        for e in std::mem::take(&mut children.0) {
+            despawn_with_children_recursive_inner(world, e);
+        }
+    }
+}
+
 impl Command for DespawnRecursive {
     fn write(self, world: &mut World) {
         despawn_with_children_recursive(world, self.entity);
     }
 }
 
+#[derive(Debug)]
+pub struct DespawnDescendants {
+    entity: Entity,
+}
+
+impl Command for DespawnDescendants {
+    fn write(self, world: &mut World) {
+        despawn_descendants(world, self.entity);
+    }
+}
+
 pub trait DespawnRecursiveExt {
     /// Despawns the provided entity and its children.
     fn despawn_recursive(&mut self);
+
+    /// Despawns all descendants of the provided entity, but not the entity itself.
+    fn despawn_descendants(&mut self);
 }
 
 impl<'a, 'b> DespawnRecursiveExt for EntityCommands<'a, 'b> {
@@ -53,6 +76,12 @@ impl<'a, 'b> DespawnRecursiveExt for EntityCommands<'a, 'b> {
         let entity = self.id();
         self.commands().add(DespawnRecursive { entity });
     }
+
+    /// Despawns all descendants of the provided entity, but not the entity itself.
+    fn despawn_descendants(&mut self) {
+        let entity = self.id();
+        self.commands().add(DespawnDescendants { entity });
+    }
 }
 
 #[cfg(test)]
@@ -139,4 +168,51 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn despawn_descendants() {
+        let mut world = World::default();
+        let mut queue = CommandQueue::default();
+        let parent;
+        let sibling;
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            parent = commands.spawn_bundle(("Parent".to_owned(), 0u32)).id();
+            commands.entity(parent).with_children(|parent| {
+                parent
+                    .spawn_bundle(("Child, to be deleted".to_owned(), 1u32))
+                    .with_children(|parent| {
+                        parent.spawn_bundle(("Grand child, to be deleted".to_owned(), 2u32));
+                    });
+            });
+            sibling = commands.spawn_bundle(("Sibling".to_owned(), 3u32)).id();
+            commands.entity(sibling).with_children(|parent| {
+                parent.spawn_bundle(("Child of sibling".to_owned(), 4u32));
+            });
+        }
+        queue.apply(&mut world);
+
+        {
+            let mut commands = Commands::new(&mut queue, &world);
+            commands.entity(parent).despawn_descendants();
+        }
+        queue.apply(&mut world);
+
+        let mut results = world
+            .query::<(&String, &u32)>()
+            .iter(&world)
+            .map(|(a, b)| (a.clone(), *b))
+            .collect::<Vec<_>>();
+        results.sort_unstable_by_key(|(_, index)| *index);
+
+        assert!(world.get::<Children>(parent).unwrap().is_empty());
+        assert_eq!(
+            results,
+            vec![
+                ("Parent".to_owned(), 0u32),
+                ("Sibling".to_owned(), 3u32),
+                ("Child of sibling".to_owned(), 4u32)
+            ]
+        );
+    }
 }
@@ -2,7 +2,9 @@ mod child_builder;
 #[allow(clippy::module_inception)]
 mod hierarchy;
 mod hierarchy_maintenance_system;
+mod path;
 
 pub use child_builder::*;
 pub use hierarchy::*;
 pub use hierarchy_maintenance_system::*;
+pub use path::*;
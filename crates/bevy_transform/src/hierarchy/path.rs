@@ -0,0 +1,196 @@
+use crate::components::{Children, Parent};
+use bevy_core::Name;
+use bevy_ecs::{entity::Entity, query::Without, world::World};
+
+/// Looks up entities by the path of their [`Name`]s in the hierarchy, such as `"Level/Enemies/*"`.
+///
+/// Each segment of the path matches the name of an entity, starting with the root entities:
+/// - `*` matches any sequence of characters, and `?` any single character, so `Enemy*` matches
+///   `Enemy` and `Enemy2`
+/// - `**` matches any number of levels, including none, so `Level/**/Door` matches every `Door`
+///   under `Level`
+///
+/// Entities without a [`Name`] are never matched, but `**` goes through them.
+pub trait QueryPathExt {
+    /// The entities matching `path` from the root entities, in hierarchy order
+    fn query_path(&mut self, path: &str) -> Vec<Entity>;
+
+    /// The entities matching `path` from the children of `entity`, in hierarchy order
+    fn query_path_from(&mut self, entity: Entity, path: &str) -> Vec<Entity>;
+}
+
+impl QueryPathExt for World {
+    fn query_path(&mut self, path: &str) -> Vec<Entity> {
+        let roots = self
+            .query_filtered::<Entity, Without<Parent>>()
+            .iter(self)
+            .collect::<Vec<_>>();
+        let segments = parse_path(path);
+        let mut matches = Vec::new();
+        for root in roots {
+            match_entity(self, root, &segments, &mut matches);
+        }
+        matches
+    }
+
+    fn query_path_from(&mut self, entity: Entity, path: &str) -> Vec<Entity> {
+        let segments = parse_path(path);
+        let mut matches = Vec::new();
+        match_children(self, entity, &segments, &mut matches);
+        matches
+    }
+}
+
+#[derive(Debug)]
+enum Segment<'a> {
+    Name(&'a str),
+    AnyLevels,
+}
+
+fn parse_path(path: &str) -> Vec<Segment> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| match segment {
+            "**" => Segment::AnyLevels,
+            name => Segment::Name(name),
+        })
+        .collect()
+}
+
+fn match_children(world: &World, entity: Entity, segments: &[Segment], matches: &mut Vec<Entity>) {
+    if let Some(children) = world.get::<Children>(entity) {
+        for child in children.iter() {
+            match_entity(world, *child, segments, matches);
+        }
+    }
+}
+
+fn match_entity(world: &World, entity: Entity, segments: &[Segment], matches: &mut Vec<Entity>) {
+    let (segment, rest) = match segments.split_first() {
+        Some(split) => split,
+        None => return,
+    };
+    match segment {
+        Segment::Name(pattern) => {
+            let name = match world.get::<Name>(entity) {
+                Some(name) => name,
+                None => return,
+            };
+            if !glob_match(pattern, name.as_str()) {
+                return;
+            }
+            if rest.is_empty() {
+                if !matches.contains(&entity) {
+                    matches.push(entity);
+                }
+            } else {
+                match_children(world, entity, rest, matches);
+            }
+        }
+        Segment::AnyLevels => {
+            if rest.is_empty() {
+                // a trailing `**` matches the entity and all its descendants
+                if world.get::<Name>(entity).is_some() && !matches.contains(&entity) {
+                    matches.push(entity);
+                }
+            } else {
+                match_entity(world, entity, rest, matches);
+            }
+            match_children(world, entity, segments, matches);
+        }
+    }
+}
+
+/// Whether `name` matches `pattern`, where `*` matches any sequence of characters and `?` any
+/// single character.
+pub fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    let (mut p, mut n) = (0, 0);
+    // the position of the last `*` in the pattern, and of the name when it was reached
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(c) if *c == '?' || *c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                // let the last `*` match one more character
+                Some((star, star_n)) => {
+                    p = star + 1;
+                    n = star_n + 1;
+                    backtrack = Some((star, star_n + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{glob_match, QueryPathExt};
+    use crate::hierarchy::BuildWorldChildren;
+    use bevy_core::Name;
+    use bevy_ecs::{entity::Entity, world::World};
+
+    #[test]
+    fn glob() {
+        assert!(glob_match("Enemy*", "Enemy"));
+        assert!(glob_match("Enemy*", "Enemy2"));
+        assert!(glob_match("*_door", "red_door"));
+        assert!(glob_match("a*b*c", "aXbYbZc"));
+        assert!(glob_match("Enemy?", "Enemy2"));
+        assert!(!glob_match("Enemy?", "Enemy"));
+        assert!(!glob_match("Enemy", "Enemy2"));
+        assert!(!glob_match("*_door", "red_doors"));
+    }
+
+    #[test]
+    fn query_path() {
+        let mut world = World::default();
+        let mut enemies = Vec::new();
+        let mut doors = Vec::new();
+        let level = world
+            .spawn()
+            .insert(Name::new("Level"))
+            .with_children(|level| {
+                level
+                    .spawn()
+                    .insert(Name::new("Enemies"))
+                    .with_children(|parent| {
+                        enemies.push(parent.spawn().insert(Name::new("Goblin")).id());
+                        enemies.push(parent.spawn().insert(Name::new("Orc")).id());
+                        parent.spawn();
+                    });
+                level.spawn().with_children(|parent| {
+                    doors.push(parent.spawn().insert(Name::new("Door")).id());
+                });
+                doors.push(level.spawn().insert(Name::new("Door")).id());
+            })
+            .id();
+        world.spawn().insert(Name::new("Menu"));
+
+        assert_eq!(world.query_path("Level"), vec![level]);
+        assert_eq!(world.query_path("/Level/Enemies/*"), enemies);
+        assert_eq!(world.query_path("Level/Enemies/G*"), vec![enemies[0]]);
+        assert_eq!(
+            world.query_path("Level/Enemies/Dragon"),
+            Vec::<Entity>::new()
+        );
+        assert_eq!(world.query_path("Level/**/Door"), doors);
+        assert_eq!(world.query_path("**/Orc"), vec![enemies[1]]);
+        assert_eq!(
+            world.query_path_from(level, "Enemies/Orc"),
+            vec![enemies[1]]
+        );
+        assert_eq!(world.query_path("Level/**").len(), 5);
+        assert_eq!(world.query_path("**").len(), 7);
+    }
+}
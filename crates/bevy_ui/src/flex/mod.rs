@@ -1,17 +1,16 @@
 mod convert;
 
-use crate::{CalculatedSize, Node, Style};
-use bevy_app::EventReader;
+use crate::{CalculatedSize, Node, Style, UiScale};
 use bevy_ecs::{
     entity::Entity,
     query::{Changed, FilterFetch, With, Without, WorldQuery},
-    system::{Query, Res, ResMut},
+    system::{Local, Query, Res, ResMut},
 };
 use bevy_log::warn;
 use bevy_math::Vec2;
 use bevy_transform::prelude::{Children, Parent, Transform};
 use bevy_utils::HashMap;
-use bevy_window::{Window, WindowId, WindowScaleFactorChanged, Windows};
+use bevy_window::{Window, WindowId, Windows};
 use std::fmt;
 use stretch::{number::Number, Stretch};
 
@@ -198,7 +197,8 @@ pub enum FlexError {
 #[allow(clippy::too_many_arguments, clippy::type_complexity)]
 pub fn flex_node_system(
     windows: Res<Windows>,
-    mut scale_factor_events: EventReader<WindowScaleFactorChanged>,
+    ui_scale: Res<UiScale>,
+    mut last_scale_factors: Local<(f64, f64)>,
     mut flex_surface: ResMut<FlexSurface>,
     root_node_query: Query<Entity, (With<Node>, Without<Parent>)>,
     node_query: Query<(Entity, &Style, Option<&CalculatedSize>), (With<Node>, Changed<Style>)>,
@@ -216,20 +216,23 @@ pub fn flex_node_system(
     }
 
     // assume one window for time being...
-    let logical_to_physical_factor = if let Some(primary_window) = windows.get_primary() {
-        primary_window.scale_factor()
-    } else {
-        1.
-    };
+    let (ui_to_physical_factor, logical_to_physical_factor) =
+        if let Some(primary_window) = windows.get_primary() {
+            (
+                ui_scale.physical_scale(primary_window),
+                primary_window.scale_factor(),
+            )
+        } else {
+            (ui_scale.scale, 1.)
+        };
 
-    if scale_factor_events.iter().next_back().is_some() {
-        update_changed(
-            &mut *flex_surface,
-            logical_to_physical_factor,
-            full_node_query,
-        );
+    // if the window scale factor or the ui scale changed, all nodes need to be updated
+    #[allow(clippy::float_cmp)]
+    if *last_scale_factors != (ui_to_physical_factor, logical_to_physical_factor) {
+        *last_scale_factors = (ui_to_physical_factor, logical_to_physical_factor);
+        update_changed(&mut *flex_surface, ui_to_physical_factor, full_node_query);
     } else {
-        update_changed(&mut *flex_surface, logical_to_physical_factor, node_query);
+        update_changed(&mut *flex_surface, ui_to_physical_factor, node_query);
     }
 
     fn update_changed<F: WorldQuery>(
@@ -251,7 +254,7 @@ pub fn flex_node_system(
     }
 
     for (entity, style, calculated_size) in changed_size_query.iter() {
-        flex_surface.upsert_leaf(entity, &style, *calculated_size, logical_to_physical_factor);
+        flex_surface.upsert_leaf(entity, &style, *calculated_size, ui_to_physical_factor);
     }
 
     // TODO: handle removed nodes
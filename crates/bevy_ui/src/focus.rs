@@ -1,11 +1,13 @@
 use crate::Node;
-use bevy_core::FloatOrd;
+use bevy_app::EventWriter;
+use bevy_core::{FloatOrd, Time};
 use bevy_ecs::{
     entity::Entity,
     system::{Local, Query, Res},
 };
 use bevy_input::{mouse::MouseButton, touch::Touches, Input};
 use bevy_transform::components::GlobalTransform;
+use bevy_utils::Duration;
 use bevy_window::Windows;
 use smallvec::SmallVec;
 
@@ -22,6 +24,49 @@ impl Default for Interaction {
     }
 }
 
+/// The presses and releases of the nodes with an [`Interaction`], sent by [`ui_focus_system`].
+///
+/// Unlike [`Interaction::Clicked`], which is set as soon as a node is pressed, these events tell
+/// apart a release over the pressed node from a release after the pointer was dragged off it.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum InteractionEvent {
+    /// The node was pressed.
+    Pressed(Entity),
+    /// The node was pressed, then released with the pointer over it.
+    Clicked(Entity),
+    /// The node was pressed, then released with the pointer outside of it.
+    Cancelled(Entity),
+    /// The node was clicked twice within [`DoubleClickSettings::max_interval`]. Sent after the
+    /// second [`InteractionEvent::Clicked`].
+    DoubleClicked(Entity),
+}
+
+impl InteractionEvent {
+    pub fn entity(&self) -> Entity {
+        match *self {
+            InteractionEvent::Pressed(entity)
+            | InteractionEvent::Clicked(entity)
+            | InteractionEvent::Cancelled(entity)
+            | InteractionEvent::DoubleClicked(entity) => entity,
+        }
+    }
+}
+
+/// Configures the detection of [`InteractionEvent::DoubleClicked`].
+#[derive(Copy, Clone, Debug)]
+pub struct DoubleClickSettings {
+    /// The maximum time between the two clicks of a double click.
+    pub max_interval: Duration,
+}
+
+impl Default for DoubleClickSettings {
+    fn default() -> Self {
+        DoubleClickSettings {
+            max_interval: Duration::from_millis(500),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Eq, PartialEq, Debug)]
 pub enum FocusPolicy {
     Block,
@@ -37,14 +82,21 @@ impl Default for FocusPolicy {
 #[derive(Default)]
 pub struct State {
     entities_to_reset: SmallVec<[Entity; 1]>,
+    /// The nodes pressed and not released yet.
+    pressed: SmallVec<[Entity; 1]>,
+    /// The last node clicked, with the time of the click in seconds since startup.
+    last_click: Option<(Entity, f64)>,
 }
 
-#[allow(clippy::type_complexity)]
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
 pub fn ui_focus_system(
     mut state: Local<State>,
     windows: Res<Windows>,
     mouse_button_input: Res<Input<MouseButton>>,
     touches_input: Res<Touches>,
+    time: Res<Time>,
+    double_click: Res<DoubleClickSettings>,
+    mut events: EventWriter<InteractionEvent>,
     mut node_query: Query<(
         Entity,
         &Node,
@@ -53,12 +105,26 @@ pub fn ui_focus_system(
         Option<&FocusPolicy>,
     )>,
 ) {
+    // forget the pressed nodes that were despawned before being released
+    state
+        .pressed
+        .retain(|entity| node_query.get_component::<Node>(*entity).is_ok());
+
+    let mouse_released =
+        mouse_button_input.just_released(MouseButton::Left) || touches_input.just_released(0);
+
     let cursor_position = if let Some(cursor_position) = windows
         .get_primary()
         .and_then(|window| window.cursor_position())
     {
         cursor_position
     } else {
+        // the pointer was released outside of the window
+        if mouse_released {
+            for entity in state.pressed.drain(..) {
+                events.send(InteractionEvent::Cancelled(entity));
+            }
+        }
         return;
     };
 
@@ -69,8 +135,6 @@ pub fn ui_focus_system(
         }
     }
 
-    let mouse_released =
-        mouse_button_input.just_released(MouseButton::Left) || touches_input.just_released(0);
     if mouse_released {
         for (_entity, _node, _global_transform, interaction, _focus_policy) in node_query.iter_mut()
         {
@@ -115,13 +179,18 @@ pub fn ui_focus_system(
     moused_over_z_sorted_nodes.sort_by_key(|(_, _, _, z)| -*z);
 
     let mut moused_over_z_sorted_nodes = moused_over_z_sorted_nodes.into_iter();
+    // the nodes under the pointer that aren't blocked by a node above them
+    let mut reached = SmallVec::<[Entity; 1]>::new();
     // set Clicked or Hovered on top nodes
     for (entity, focus_policy, interaction, _) in moused_over_z_sorted_nodes.by_ref() {
+        reached.push(entity);
         if let Some(mut interaction) = interaction {
             if mouse_clicked {
                 // only consider nodes with Interaction "clickable"
                 if *interaction != Interaction::Clicked {
                     *interaction = Interaction::Clicked;
+                    state.pressed.push(entity);
+                    events.send(InteractionEvent::Pressed(entity));
                     // if the mouse was simultaneously released, reset this Interaction in the next
                     // frame
                     if mouse_released {
@@ -148,4 +217,231 @@ pub fn ui_focus_system(
             }
         }
     }
+
+    if mouse_released {
+        let now = time.seconds_since_startup();
+        let max_interval = double_click.max_interval.as_secs_f64();
+        for entity in std::mem::take(&mut state.pressed) {
+            if !reached.contains(&entity) {
+                events.send(InteractionEvent::Cancelled(entity));
+                continue;
+            }
+            events.send(InteractionEvent::Clicked(entity));
+            match state.last_click {
+                Some((last_entity, last_time))
+                    if last_entity == entity && now - last_time <= max_interval =>
+                {
+                    events.send(InteractionEvent::DoubleClicked(entity));
+                    state.last_click = None;
+                }
+                _ => state.last_click = Some((entity, now)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ui_focus_system, DoubleClickSettings, Interaction, InteractionEvent};
+    use crate::Node;
+    use bevy_core::Time;
+    use bevy_ecs::{
+        entity::Entity,
+        event::Events,
+        schedule::{Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+    use bevy_input::{mouse::MouseButton, touch::Touches, Input};
+    use bevy_math::Vec2;
+    use bevy_transform::components::GlobalTransform;
+    use bevy_utils::Duration;
+    use bevy_window::{Window, WindowDescriptor, WindowId, Windows};
+
+    /// A position over the node.
+    fn over() -> Vec2 {
+        Vec2::new(50.0, 50.0)
+    }
+
+    /// A position in the window, outside of the node.
+    fn outside() -> Vec2 {
+        Vec2::new(150.0, 150.0)
+    }
+
+    struct TestUi {
+        world: World,
+        stage: SystemStage,
+        node: Entity,
+        millis: u64,
+    }
+
+    impl TestUi {
+        fn new() -> Self {
+            let mut world = World::default();
+            let mut windows = Windows::default();
+            windows.add(Window::new(
+                WindowId::primary(),
+                &WindowDescriptor::default(),
+                200,
+                200,
+                1.0,
+                None,
+            ));
+            world.insert_resource(windows);
+            world.insert_resource(Input::<MouseButton>::default());
+            world.insert_resource(Touches::default());
+            world.insert_resource(Time::default());
+            world.insert_resource(DoubleClickSettings::default());
+            world.insert_resource(Events::<InteractionEvent>::default());
+            // a 100x100 node at the bottom left of the window
+            let node = world
+                .spawn()
+                .insert_bundle((
+                    Node {
+                        size: Vec2::new(100.0, 100.0),
+                    },
+                    GlobalTransform::from_xyz(50.0, 50.0, 0.0),
+                    Interaction::default(),
+                ))
+                .id();
+
+            let mut stage = SystemStage::parallel();
+            stage.add_system(ui_focus_system.system());
+            TestUi {
+                world,
+                stage,
+                node,
+                millis: 0,
+            }
+        }
+
+        /// Runs a frame `millis` after the previous one, with the pointer at `cursor` and the left
+        /// mouse button pressed and/or released, and returns the events sent.
+        fn frame(
+            &mut self,
+            millis: u64,
+            cursor: Option<Vec2>,
+            press: bool,
+            release: bool,
+        ) -> Vec<InteractionEvent> {
+            self.world
+                .get_resource_mut::<Windows>()
+                .unwrap()
+                .get_primary_mut()
+                .unwrap()
+                .update_cursor_position_from_backend(cursor);
+            let mut input = self.world.get_resource_mut::<Input<MouseButton>>().unwrap();
+            input.clear();
+            if press {
+                input.press(MouseButton::Left);
+            }
+            if release {
+                input.release(MouseButton::Left);
+            }
+            self.millis += millis;
+            let mut time = self.world.get_resource_mut::<Time>().unwrap();
+            let instant = time.startup() + Duration::from_millis(self.millis);
+            time.update_with_instant(instant);
+
+            self.stage.run(&mut self.world);
+            self.world
+                .get_resource_mut::<Events<InteractionEvent>>()
+                .unwrap()
+                .drain()
+                .collect()
+        }
+
+        fn click(&mut self, millis: u64) -> Vec<InteractionEvent> {
+            let mut events = self.frame(millis, Some(over()), true, false);
+            events.extend(self.frame(10, Some(over()), false, true));
+            events
+        }
+    }
+
+    #[test]
+    fn pressed_and_clicked() {
+        let mut ui = TestUi::new();
+        let node = ui.node;
+        assert_eq!(ui.frame(0, Some(over()), false, false), vec![]);
+        assert_eq!(
+            ui.frame(10, Some(over()), true, false),
+            vec![InteractionEvent::Pressed(node)]
+        );
+        assert_eq!(
+            ui.world.get::<Interaction>(node),
+            Some(&Interaction::Clicked)
+        );
+        assert_eq!(ui.frame(10, Some(over()), false, false), vec![]);
+        assert_eq!(
+            ui.frame(10, Some(over()), false, true),
+            vec![InteractionEvent::Clicked(node)]
+        );
+    }
+
+    #[test]
+    fn cancelled() {
+        let mut ui = TestUi::new();
+        let node = ui.node;
+        assert_eq!(
+            ui.frame(0, Some(over()), true, false),
+            vec![InteractionEvent::Pressed(node)]
+        );
+        // the pointer is dragged off the node before the release
+        assert_eq!(
+            ui.frame(10, Some(outside()), false, true),
+            vec![InteractionEvent::Cancelled(node)]
+        );
+
+        // the pointer is released outside of the window
+        ui.frame(10, Some(over()), true, false);
+        assert_eq!(
+            ui.frame(10, None, false, true),
+            vec![InteractionEvent::Cancelled(node)]
+        );
+    }
+
+    #[test]
+    fn double_clicked() {
+        let mut ui = TestUi::new();
+        let node = ui.node;
+        ui.click(0);
+        assert_eq!(
+            ui.click(100),
+            vec![
+                InteractionEvent::Pressed(node),
+                InteractionEvent::Clicked(node),
+                InteractionEvent::DoubleClicked(node)
+            ]
+        );
+        // a third click starts a new double click
+        assert_eq!(
+            ui.click(100),
+            vec![
+                InteractionEvent::Pressed(node),
+                InteractionEvent::Clicked(node)
+            ]
+        );
+
+        // clicks further apart than the max interval aren't a double click
+        assert_eq!(
+            ui.click(1000),
+            vec![
+                InteractionEvent::Pressed(node),
+                InteractionEvent::Clicked(node)
+            ]
+        );
+    }
+
+    #[test]
+    fn despawned_while_pressed() {
+        let mut ui = TestUi::new();
+        let node = ui.node;
+        assert_eq!(
+            ui.frame(0, Some(over()), true, false),
+            vec![InteractionEvent::Pressed(node)]
+        );
+        ui.world.despawn(node);
+        assert_eq!(ui.frame(10, Some(over()), false, true), vec![]);
+        assert_eq!(ui.frame(10, None, false, true), vec![]);
+    }
 }
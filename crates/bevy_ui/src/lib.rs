@@ -3,7 +3,10 @@ mod flex;
 mod focus;
 mod margins;
 mod render;
+mod scale;
 mod ui_node;
+mod viewport_root;
+mod virtual_gamepad;
 
 pub mod entity;
 pub mod update;
@@ -14,11 +17,17 @@ pub use flex::*;
 pub use focus::*;
 pub use margins::*;
 pub use render::*;
+pub use scale::*;
 pub use ui_node::*;
+pub use viewport_root::*;
+pub use virtual_gamepad::*;
 
 pub mod prelude {
     #[doc(hidden)]
-    pub use crate::{entity::*, ui_node::*, widget::Button, Anchors, Interaction, Margins};
+    pub use crate::{
+        entity::*, ui_node::*, widget::Button, Anchors, Interaction, InteractionEvent, Margins,
+        UiScale, UiScaleMode, ViewportUiRoot, VirtualButton, VirtualStick,
+    };
 }
 
 use bevy_app::prelude::*;
@@ -45,6 +54,9 @@ pub enum UiSystem {
 impl Plugin for UiPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app.init_resource::<FlexSurface>()
+            .init_resource::<UiScale>()
+            .init_resource::<DoubleClickSettings>()
+            .add_event::<InteractionEvent>()
             .register_type::<AlignContent>()
             .register_type::<AlignItems>()
             .register_type::<AlignSelf>()
@@ -67,6 +79,10 @@ impl Plugin for UiPlugin {
                     .label(UiSystem::Focus)
                     .after(InputSystem),
             )
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                virtual_gamepad_system.system().after(InputSystem),
+            )
             // add these stages to front because these must run before transform update systems
             .add_system_to_stage(
                 CoreStage::PostUpdate,
@@ -76,6 +92,10 @@ impl Plugin for UiPlugin {
                 CoreStage::PostUpdate,
                 widget::image_node_system.system().before(UiSystem::Flex),
             )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                viewport_ui_root_system.system().before(UiSystem::Flex),
+            )
             .add_system_to_stage(
                 CoreStage::PostUpdate,
                 flex_node_system
@@ -145,10 +145,24 @@ pub(crate) fn add_ui_graph(world: &mut World) {
             .unwrap();
     }
 
-    // ensure ui pass runs after main pass
+    // ensure ui pass runs after main pass, and after the other passes drawing to the swap chain
+    // over it, such as the decal pass. the ui pass clears the main depth texture they might read
     graph
         .add_node_edge(base::node::MAIN_PASS, node::UI_PASS)
         .unwrap();
+    let ordered_passes = [
+        graph.get_node_id(base::node::MAIN_PASS).unwrap(),
+        graph.get_node_id(node::UI_PASS).unwrap(),
+    ];
+    let swap_chain_passes = graph
+        .iter_node_outputs(base::node::PRIMARY_SWAP_CHAIN)
+        .unwrap()
+        .map(|(_, node)| node.id)
+        .filter(|id| !ordered_passes.contains(id))
+        .collect::<Vec<_>>();
+    for pass in swap_chain_passes {
+        graph.add_node_edge(pass, node::UI_PASS).unwrap();
+    }
 
     // setup ui camera
     graph.add_system_node(node::CAMERA_UI, CameraNode::new(camera::CAMERA_UI));
@@ -0,0 +1,98 @@
+use bevy_window::Window;
+
+/// Scales all UI layout units, including sizes, positions, margins and font sizes.
+///
+/// This makes it possible to author a UI once and have it look the same across window sizes
+/// and resolutions.
+#[derive(Debug, Clone)]
+pub struct UiScale {
+    /// Multiplier applied on top of the scale given by `mode`.
+    pub scale: f64,
+    /// How UI units relate to the size of the window.
+    pub mode: UiScaleMode,
+}
+
+impl Default for UiScale {
+    fn default() -> Self {
+        UiScale {
+            scale: 1.0,
+            mode: UiScaleMode::WindowSize,
+        }
+    }
+}
+
+/// Defines what a UI unit corresponds to, see [UiScale].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UiScaleMode {
+    /// UI units are logical pixels of the window.
+    WindowSize,
+    /// UI units are pixels of a virtual reference resolution. The UI is scaled uniformly so that
+    /// the reference resolution fits inside the window.
+    ReferenceResolution { width: f32, height: f32 },
+}
+
+impl UiScale {
+    /// Returns the number of logical window pixels covered by a single UI unit.
+    pub fn logical_scale(&self, window: &Window) -> f64 {
+        let mode_scale = match self.mode {
+            UiScaleMode::WindowSize => 1.0,
+            UiScaleMode::ReferenceResolution { width, height } => {
+                (window.width() / width).min(window.height() / height) as f64
+            }
+        };
+        self.scale * mode_scale
+    }
+
+    /// Returns the number of physical window pixels covered by a single UI unit.
+    pub fn physical_scale(&self, window: &Window) -> f64 {
+        window.scale_factor() * self.logical_scale(window)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_window::{WindowDescriptor, WindowId};
+
+    fn window(physical_width: u32, physical_height: u32, scale_factor: f64) -> Window {
+        Window::new(
+            WindowId::new(),
+            &WindowDescriptor::default(),
+            physical_width,
+            physical_height,
+            scale_factor,
+            None,
+        )
+    }
+
+    #[test]
+    fn window_size_scale() {
+        let ui_scale = UiScale {
+            scale: 2.0,
+            ..Default::default()
+        };
+        let window = window(1280, 720, 1.5);
+        assert_eq!(ui_scale.logical_scale(&window), 2.0);
+        assert_eq!(ui_scale.physical_scale(&window), 3.0);
+    }
+
+    #[test]
+    fn reference_resolution_scale() {
+        let ui_scale = UiScale {
+            scale: 1.0,
+            mode: UiScaleMode::ReferenceResolution {
+                width: 1280.0,
+                height: 720.0,
+            },
+        };
+
+        // same aspect ratio, four times the pixels
+        assert_eq!(ui_scale.logical_scale(&window(2560, 1440, 1.0)), 2.0);
+        // the narrower axis decides the scale
+        assert_eq!(ui_scale.logical_scale(&window(1280, 1440, 1.0)), 1.0);
+        // hidpi windows keep the same logical layout
+        let hidpi = window(2560, 1440, 2.0);
+        assert_eq!(ui_scale.logical_scale(&hidpi), 1.0);
+        assert_eq!(ui_scale.physical_scale(&hidpi), 2.0);
+    }
+}
@@ -0,0 +1,41 @@
+use crate::{PositionType, Style, Val};
+use bevy_ecs::{entity::Entity, system::Query};
+use bevy_math::{Rect, Size};
+use bevy_render::camera::Viewport;
+
+/// Makes a root UI node cover the [`Viewport`] of a camera, so each player of a split-screen can
+/// have their own UI. The node's position type, position and size are managed by
+/// [`viewport_ui_root_system`].
+#[derive(Debug, Clone, Copy)]
+pub struct ViewportUiRoot {
+    pub camera: Entity,
+}
+
+pub fn viewport_ui_root_system(
+    mut roots: Query<(&ViewportUiRoot, &mut Style)>,
+    cameras: Query<Option<&Viewport>>,
+) {
+    for (root, mut style) in roots.iter_mut() {
+        let viewport = match cameras.get(root.camera) {
+            Ok(viewport) => viewport.copied().unwrap_or_default(),
+            Err(_) => continue,
+        };
+        let position = Rect {
+            left: Val::Percent(viewport.origin.x * 100.0),
+            bottom: Val::Percent(viewport.origin.y * 100.0),
+            ..Default::default()
+        };
+        let size = Size::new(
+            Val::Percent(viewport.size.x * 100.0),
+            Val::Percent(viewport.size.y * 100.0),
+        );
+        if style.position_type != PositionType::Absolute
+            || style.position != position
+            || style.size != size
+        {
+            style.position_type = PositionType::Absolute;
+            style.position = position;
+            style.size = size;
+        }
+    }
+}
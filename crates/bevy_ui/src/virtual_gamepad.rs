@@ -0,0 +1,338 @@
+use crate::Node;
+use bevy_app::EventWriter;
+use bevy_ecs::{
+    entity::Entity,
+    system::{Local, Query, Res},
+};
+use bevy_input::{
+    gamepad::{
+        Gamepad, GamepadAxis, GamepadAxisType, GamepadButton, GamepadButtonType, GamepadEventRaw,
+        GamepadEventType, GamepadInfo,
+    },
+    touch::Touches,
+};
+use bevy_math::Vec2;
+use bevy_transform::components::GlobalTransform;
+use bevy_utils::{HashMap, HashSet};
+
+/// The gamepad virtual controls belong to when created with [`VirtualButton::new`] and
+/// [`VirtualStick::new`].
+pub const VIRTUAL_GAMEPAD: Gamepad = Gamepad(usize::MAX);
+
+/// A UI node that holds a gamepad button down while it is touched.
+///
+/// Virtual controls go through the same path as physical gamepads: the [`Gamepad`] they belong to
+/// is connected while any of its controls exists, and their input is published to the
+/// `Input<GamepadButton>` and `Axis` resources after the usual [`GamepadSettings`] filtering, so
+/// that games read touch screens and controllers the same way.
+///
+/// [`GamepadSettings`]: bevy_input::gamepad::GamepadSettings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VirtualButton {
+    pub button: GamepadButton,
+}
+
+impl VirtualButton {
+    pub fn new(button_type: GamepadButtonType) -> Self {
+        VirtualButton {
+            button: GamepadButton(VIRTUAL_GAMEPAD, button_type),
+        }
+    }
+}
+
+/// A UI node that acts as an analog stick: the offset of the touch from the center of the node,
+/// relative to its half size, sets the `x` and `y` axes, between `-1.0` and `1.0`. The axes go
+/// back to `0.0` when the touch is released.
+///
+/// See [`VirtualButton`] for how virtual controls reach the input resources.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VirtualStick {
+    pub x: GamepadAxis,
+    pub y: GamepadAxis,
+}
+
+impl VirtualStick {
+    pub fn new(x_type: GamepadAxisType, y_type: GamepadAxisType) -> Self {
+        VirtualStick {
+            x: GamepadAxis(VIRTUAL_GAMEPAD, x_type),
+            y: GamepadAxis(VIRTUAL_GAMEPAD, y_type),
+        }
+    }
+}
+
+/// Local "virtual gamepad system" state
+#[derive(Debug, Default)]
+pub struct VirtualGamepadState {
+    connected: HashSet<Gamepad>,
+    /// The touch held on each control
+    touches: HashMap<Entity, u64>,
+    /// The last value sent for each control, `x` only for buttons
+    values: HashMap<Entity, Vec2>,
+}
+
+/// Turns touches on [`VirtualButton`] and [`VirtualStick`] nodes into gamepad input.
+pub fn virtual_gamepad_system(
+    mut state: Local<VirtualGamepadState>,
+    touches: Res<Touches>,
+    mut events: EventWriter<GamepadEventRaw>,
+    buttons: Query<(Entity, &Node, &GlobalTransform, &VirtualButton)>,
+    sticks: Query<(Entity, &Node, &GlobalTransform, &VirtualStick)>,
+) {
+    let state = &mut *state;
+
+    let gamepads = buttons
+        .iter()
+        .map(|(_, _, _, button)| button.button.0)
+        .chain(sticks.iter().map(|(_, _, _, stick)| stick.x.0))
+        .collect::<HashSet<_>>();
+    for gamepad in gamepads.difference(&state.connected) {
+        events.send(GamepadEventRaw(
+            *gamepad,
+            GamepadEventType::Connected(GamepadInfo {
+                name: "Virtual Gamepad".to_string(),
+                vendor_id: None,
+                product_id: None,
+            }),
+        ));
+    }
+    for gamepad in state.connected.difference(&gamepads) {
+        events.send(GamepadEventRaw(*gamepad, GamepadEventType::Disconnected));
+    }
+    state.connected = gamepads;
+
+    // controls that were despawned don't hold their touch anymore
+    let exists = |entity: &Entity| buttons.get(*entity).is_ok() || sticks.get(*entity).is_ok();
+    state.touches.retain(|entity, _| exists(entity));
+    state.values.retain(|entity, _| exists(entity));
+
+    for (entity, node, global_transform, button) in buttons.iter() {
+        if let Some(offset) = state.update(entity, node, global_transform, &touches) {
+            let value = if offset.is_some() { 1.0 } else { 0.0 };
+            let previous = state.values.insert(entity, Vec2::new(value, 0.0));
+            if previous.map_or(true, |previous| previous.x != value) {
+                events.send(GamepadEventRaw(
+                    button.button.0,
+                    GamepadEventType::ButtonChanged(button.button.1, value),
+                ));
+            }
+        }
+    }
+
+    for (entity, node, global_transform, stick) in sticks.iter() {
+        if let Some(offset) = state.update(entity, node, global_transform, &touches) {
+            let value = offset.unwrap_or(Vec2::ZERO);
+            let previous = state.values.insert(entity, value);
+            if previous.map_or(true, |previous| previous.x != value.x) {
+                events.send(GamepadEventRaw(
+                    stick.x.0,
+                    GamepadEventType::AxisChanged(stick.x.1, value.x),
+                ));
+            }
+            if previous.map_or(true, |previous| previous.y != value.y) {
+                events.send(GamepadEventRaw(
+                    stick.y.0,
+                    GamepadEventType::AxisChanged(stick.y.1, value.y),
+                ));
+            }
+        }
+    }
+}
+
+impl VirtualGamepadState {
+    /// Tracks the touch held on a control. Returns `None` if the control isn't touched and wasn't
+    /// last frame, else the offset of the touch from the center of the node, relative to its half
+    /// size, or `Some(None)` if the touch was released.
+    fn update(
+        &mut self,
+        entity: Entity,
+        node: &Node,
+        global_transform: &GlobalTransform,
+        touches: &Touches,
+    ) -> Option<Option<Vec2>> {
+        let center = global_transform.translation.truncate();
+        let extents = node.size / 2.0;
+        let touch = match self.touches.get(&entity) {
+            Some(id) => touches.get_pressed(*id),
+            None => touches.iter_just_pressed().find(|touch| {
+                let offset = (touch.position() - center).abs();
+                offset.x < extents.x && offset.y < extents.y
+            }),
+        };
+        match touch {
+            Some(touch) => {
+                self.touches.insert(entity, touch.id());
+                let offset = (touch.position() - center) / extents.max(Vec2::splat(f32::EPSILON));
+                Some(Some(offset.max(Vec2::splat(-1.0)).min(Vec2::ONE)))
+            }
+            None => self.touches.remove(&entity).map(|_| None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{virtual_gamepad_system, VirtualButton, VirtualStick, VIRTUAL_GAMEPAD};
+    use crate::Node;
+    use bevy_ecs::{
+        entity::Entity,
+        event::Events,
+        schedule::{ParallelSystemDescriptorCoercion, Stage, SystemStage},
+        system::IntoSystem,
+        world::World,
+    };
+    use bevy_input::{
+        gamepad::{GamepadAxisType, GamepadButtonType, GamepadEventRaw, GamepadEventType},
+        touch::{touch_screen_input_system, TouchInput, TouchPhase, Touches},
+    };
+    use bevy_math::Vec2;
+    use bevy_transform::components::GlobalTransform;
+
+    struct TestUi {
+        world: World,
+        stage: SystemStage,
+    }
+
+    impl TestUi {
+        fn new() -> Self {
+            let mut world = World::default();
+            world.insert_resource(Touches::default());
+            world.insert_resource(Events::<TouchInput>::default());
+            world.insert_resource(Events::<GamepadEventRaw>::default());
+            let mut stage = SystemStage::parallel();
+            stage
+                .add_system(touch_screen_input_system.system().label("touches"))
+                .add_system(virtual_gamepad_system.system().after("touches"));
+            TestUi { world, stage }
+        }
+
+        /// Spawns a 100x100 control centered on (50, 50).
+        fn spawn(&mut self, control: impl Send + Sync + 'static) -> Entity {
+            self.world
+                .spawn()
+                .insert_bundle((
+                    Node {
+                        size: Vec2::new(100.0, 100.0),
+                    },
+                    GlobalTransform::from_xyz(50.0, 50.0, 0.0),
+                    control,
+                ))
+                .id()
+        }
+
+        /// Runs a frame with the touch events, and returns the gamepad events sent.
+        fn frame(&mut self, touches: &[(TouchPhase, Vec2)]) -> Vec<GamepadEventType> {
+            let mut touch_events = self.world.get_resource_mut::<Events<TouchInput>>().unwrap();
+            touch_events.update();
+            for (phase, position) in touches.iter() {
+                touch_events.send(TouchInput {
+                    phase: *phase,
+                    position: *position,
+                    force: None,
+                    id: 0,
+                });
+            }
+
+            self.stage.run(&mut self.world);
+            self.world
+                .get_resource_mut::<Events<GamepadEventRaw>>()
+                .unwrap()
+                .drain()
+                .map(|GamepadEventRaw(gamepad, event)| {
+                    assert_eq!(gamepad, VIRTUAL_GAMEPAD);
+                    event
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn connection() {
+        let mut ui = TestUi::new();
+        assert_eq!(ui.frame(&[]), vec![]);
+        let button = ui.spawn(VirtualButton::new(GamepadButtonType::South));
+        let stick = ui.spawn(VirtualStick::new(
+            GamepadAxisType::LeftStickX,
+            GamepadAxisType::LeftStickY,
+        ));
+        match ui.frame(&[]).as_slice() {
+            [GamepadEventType::Connected(info)] => assert_eq!(info.name, "Virtual Gamepad"),
+            events => panic!("expected a connection, got {:?}", events),
+        }
+
+        // the gamepad stays connected while any of its controls exists
+        ui.world.despawn(button);
+        assert_eq!(ui.frame(&[]), vec![]);
+        ui.world.despawn(stick);
+        assert_eq!(ui.frame(&[]), vec![GamepadEventType::Disconnected]);
+    }
+
+    #[test]
+    fn button() {
+        let mut ui = TestUi::new();
+        ui.spawn(VirtualButton::new(GamepadButtonType::South));
+        ui.frame(&[]);
+
+        // touches outside of the button are ignored
+        assert_eq!(
+            ui.frame(&[(TouchPhase::Started, Vec2::new(150.0, 50.0))]),
+            vec![]
+        );
+        assert_eq!(
+            ui.frame(&[(TouchPhase::Ended, Vec2::new(150.0, 50.0))]),
+            vec![]
+        );
+
+        assert_eq!(
+            ui.frame(&[(TouchPhase::Started, Vec2::new(60.0, 40.0))]),
+            vec![GamepadEventType::ButtonChanged(
+                GamepadButtonType::South,
+                1.0
+            )]
+        );
+        // the button stays pressed while the touch is held, even off the button
+        assert_eq!(
+            ui.frame(&[(TouchPhase::Moved, Vec2::new(150.0, 50.0))]),
+            vec![]
+        );
+        assert_eq!(
+            ui.frame(&[(TouchPhase::Ended, Vec2::new(150.0, 50.0))]),
+            vec![GamepadEventType::ButtonChanged(
+                GamepadButtonType::South,
+                0.0
+            )]
+        );
+    }
+
+    #[test]
+    fn stick() {
+        let mut ui = TestUi::new();
+        ui.spawn(VirtualStick::new(
+            GamepadAxisType::LeftStickX,
+            GamepadAxisType::LeftStickY,
+        ));
+        ui.frame(&[]);
+
+        assert_eq!(
+            ui.frame(&[(TouchPhase::Started, Vec2::new(75.0, 25.0))]),
+            vec![
+                GamepadEventType::AxisChanged(GamepadAxisType::LeftStickX, 0.5),
+                GamepadEventType::AxisChanged(GamepadAxisType::LeftStickY, -0.5),
+            ]
+        );
+        // the axes are clamped, and only changed axes are sent
+        assert_eq!(
+            ui.frame(&[(TouchPhase::Moved, Vec2::new(200.0, 25.0))]),
+            vec![GamepadEventType::AxisChanged(
+                GamepadAxisType::LeftStickX,
+                1.0
+            )]
+        );
+        assert_eq!(
+            ui.frame(&[(TouchPhase::Ended, Vec2::new(200.0, 25.0))]),
+            vec![
+                GamepadEventType::AxisChanged(GamepadAxisType::LeftStickX, 0.0),
+                GamepadEventType::AxisChanged(GamepadAxisType::LeftStickY, 0.0),
+            ]
+        );
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{CalculatedSize, Node, Style, Val};
+use crate::{CalculatedSize, Node, Style, UiScale, Val};
 use bevy_asset::Assets;
 use bevy_ecs::{
     entity::Entity,
@@ -7,7 +7,7 @@ use bevy_ecs::{
 };
 use bevy_math::Size;
 use bevy_render::{
-    draw::{Draw, DrawContext, Drawable, OutsideFrustum},
+    draw::{Draw, DrawContext, DrawError, Drawable, OutsideFrustum},
     mesh::Mesh,
     prelude::{Msaa, Visible},
     renderer::RenderResourceBindings,
@@ -49,6 +49,7 @@ pub fn text_system(
     mut textures: ResMut<Assets<Texture>>,
     fonts: Res<Assets<Font>>,
     windows: Res<Windows>,
+    ui_scale: Res<UiScale>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
     mut font_atlas_set_storage: ResMut<Assets<FontAtlasSet>>,
     mut text_pipeline: ResMut<DefaultTextPipeline>,
@@ -58,10 +59,11 @@ pub fn text_system(
         Query<(&Text, &Style, &mut CalculatedSize)>,
     )>,
 ) {
+    // text is laid out in physical pixels, so the ui scale is applied to the font size as well
     let scale_factor = if let Some(window) = windows.get_primary() {
-        window.scale_factor()
+        ui_scale.physical_scale(window)
     } else {
-        1.
+        ui_scale.scale
     };
 
     let inv_scale_factor = 1. / scale_factor;
@@ -178,7 +180,11 @@ pub fn draw_text_system(
                 alignment_offset: (node.size / -2.0).extend(0.0),
             };
 
-            drawable_text.draw(&mut draw, &mut context).unwrap();
+            match drawable_text.draw(&mut draw, &mut context) {
+                // skip the text until its shaders are compiled
+                Err(DrawError::PipelineCompiling) => {}
+                result => result.unwrap(),
+            }
         }
     }
 }
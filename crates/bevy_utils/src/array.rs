@@ -0,0 +1,44 @@
+use std::convert::TryInto;
+
+/// Creates an array of length `N`, initializing the element at each index with `func(index)`.
+///
+/// This is a safe alternative to building arrays out of [`MaybeUninit`](std::mem::MaybeUninit)
+/// elements, for types that are neither `Copy` nor `Default`.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_utils::array_init;
+/// let squares: [String; 4] = array_init(|i| (i * i).to_string());
+/// assert_eq!(squares, ["0", "1", "4", "9"]);
+/// ```
+pub fn array_init<T, F, const N: usize>(func: F) -> [T; N]
+where
+    F: FnMut(usize) -> T,
+{
+    let elements = (0..N).map(func).collect::<Vec<T>>();
+    match elements.try_into() {
+        Ok(array) => array,
+        Err(_) => unreachable!("exactly N elements were collected"),
+    }
+}
+
+/// Creates an array of length `N` from the first `N` items of `iter`, or returns `None` if the
+/// iterator has fewer than `N` items.
+///
+/// # Examples
+///
+/// ```
+/// use bevy_utils::array_from_iter;
+/// let array: Option<[u32; 3]> = array_from_iter(1..);
+/// assert_eq!(array, Some([1, 2, 3]));
+///
+/// let array: Option<[u32; 3]> = array_from_iter(1..3);
+/// assert_eq!(array, None);
+/// ```
+pub fn array_from_iter<T, I, const N: usize>(iter: I) -> Option<[T; N]>
+where
+    I: IntoIterator<Item = T>,
+{
+    iter.into_iter().take(N).collect::<Vec<T>>().try_into().ok()
+}
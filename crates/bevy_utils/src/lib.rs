@@ -1,12 +1,13 @@
+mod array;
 mod enum_variant_meta;
+pub use array::*;
 pub use enum_variant_meta::*;
 
-pub use ahash::AHasher;
+pub use ahash::{AHasher, RandomState};
 pub use instant::{Duration, Instant};
 pub use tracing;
 pub use uuid::Uuid;
 
-use ahash::RandomState;
 use std::{future::Future, pin::Pin};
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -15,8 +16,8 @@ pub type BoxedFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
 #[cfg(target_arch = "wasm32")]
 pub type BoxedFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
 
-/// A hasher builder that will create a fixed hasher.
-#[derive(Default)]
+/// A hasher builder that will create a fixed hasher, pre-seeded with the same keys in every run.
+#[derive(Clone, Copy, Default)]
 pub struct FixedState;
 
 impl std::hash::BuildHasher for FixedState {
@@ -31,6 +32,49 @@ impl std::hash::BuildHasher for FixedState {
     }
 }
 
+/// A hasher builder creating [`EntityHasher`]s.
+#[derive(Clone, Copy, Default)]
+pub struct EntityHash;
+
+impl std::hash::BuildHasher for EntityHash {
+    type Hasher = EntityHasher;
+
+    #[inline]
+    fn build_hasher(&self) -> EntityHasher {
+        EntityHasher::default()
+    }
+}
+
+/// A very fast hasher for keys hashed as a single `u64` made of a unique index in the low bits,
+/// such as entities, whose hash is the index multiplied by a large odd constant. Used by the
+/// `EntityHashMap` and `EntityHashSet` of `bevy_ecs`.
+///
+/// The index is kept intact in the low bits, where the hash map picks buckets from, and spread
+/// to the high bits, which the hash map compares first. Hashing anything but a single `u64`
+/// panics.
+#[derive(Default)]
+pub struct EntityHasher {
+    hash: u64,
+}
+
+impl std::hash::Hasher for EntityHasher {
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, _bytes: &[u8]) {
+        panic!("EntityHasher can only hash a single u64");
+    }
+
+    #[inline]
+    fn write_u64(&mut self, bits: u64) {
+        // the upper bits of the golden ratio, with the lowest bit set to keep the index intact
+        const UPPER_PHI: u64 = 0x9e37_79b9_0000_0001;
+        self.hash = bits.wrapping_mul(UPPER_PHI);
+    }
+}
+
 /// A std hash map implementing AHash, a high speed keyed hashing algorithm
 /// intended for use in in-memory hashmaps.
 ///
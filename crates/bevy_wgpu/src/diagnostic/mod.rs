@@ -1,2 +1,4 @@
+mod wgpu_node_timing_diagnostics_plugin;
 mod wgpu_resource_diagnostics_plugin;
+pub use wgpu_node_timing_diagnostics_plugin::WgpuNodeTimingDiagnosticsPlugin;
 pub use wgpu_resource_diagnostics_plugin::WgpuResourceDiagnosticsPlugin;
@@ -0,0 +1,51 @@
+use crate::renderer::WgpuNodeTimings;
+use bevy_app::prelude::*;
+use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
+use bevy_ecs::system::{IntoSystem, Res, ResMut};
+use bevy_utils::{FixedState, Uuid};
+use std::hash::{BuildHasher, Hash, Hasher};
+
+/// Adds a "gpu time" diagnostic for every render graph node, measured with timestamp queries.
+///
+/// Timestamp queries must be enabled by adding
+/// [`WgpuFeature::TimestampQuery`](crate::WgpuFeature) to the features of the
+/// [`WgpuOptions`](crate::WgpuOptions) resource. Reading the timings back waits for the GPU to
+/// finish each frame, so this should only be used while profiling.
+#[derive(Default)]
+pub struct WgpuNodeTimingDiagnosticsPlugin;
+
+impl Plugin for WgpuNodeTimingDiagnosticsPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_system(Self::diagnostic_system.system());
+    }
+}
+
+impl WgpuNodeTimingDiagnosticsPlugin {
+    /// Returns the id of the diagnostic measuring the render graph node with the given name.
+    pub fn node_diagnostic_id(node_name: &str) -> DiagnosticId {
+        let mut hasher = FixedState.build_hasher();
+        node_name.hash(&mut hasher);
+        // the upper bits keep these ids apart from other diagnostics
+        DiagnosticId(Uuid::from_u128(
+            (0x6770_755f_7469_6d65 << 64) | hasher.finish() as u128,
+        ))
+    }
+
+    pub fn diagnostic_system(
+        mut diagnostics: ResMut<Diagnostics>,
+        node_timings: Option<Res<WgpuNodeTimings>>,
+    ) {
+        let node_timings = match node_timings {
+            Some(node_timings) => node_timings,
+            None => return,
+        };
+        for (name, duration) in node_timings.iter() {
+            let id = Self::node_diagnostic_id(name);
+            if diagnostics.get(id).is_none() {
+                diagnostics
+                    .add(Diagnostic::new(id, format!("gpu_time {}", name), 20).with_suffix("ms"));
+            }
+            diagnostics.add_measurement(id, duration.as_secs_f64() * 1000.0);
+        }
+    }
+}
@@ -4,6 +4,9 @@ use bevy_diagnostic::{Diagnostic, DiagnosticId, Diagnostics};
 use bevy_ecs::system::{IntoSystem, Res, ResMut};
 use bevy_render::renderer::RenderResourceContext;
 
+/// Number of bytes in a mebibyte, the unit of the memory diagnostics.
+const MEBIBYTE: f64 = 1024.0 * 1024.0;
+
 #[derive(Default)]
 pub struct WgpuResourceDiagnosticsPlugin;
 
@@ -23,6 +26,8 @@ impl WgpuResourceDiagnosticsPlugin {
         DiagnosticId::from_u128(96406067032931216377076410852598331304);
     pub const BUFFERS: DiagnosticId =
         DiagnosticId::from_u128(133146619577893994787249934474491530491);
+    pub const BUFFER_MEMORY: DiagnosticId =
+        DiagnosticId::from_u128(63709187327598325148906215870216398513);
     pub const RENDER_PIPELINES: DiagnosticId =
         DiagnosticId::from_u128(278527620040377353875091478462209885377);
     pub const SAMPLERS: DiagnosticId =
@@ -35,6 +40,8 @@ impl WgpuResourceDiagnosticsPlugin {
         DiagnosticId::from_u128(112048874168736161226721327099863374234);
     pub const TEXTURES: DiagnosticId =
         DiagnosticId::from_u128(305955424195390184883220102469231911115);
+    pub const TEXTURE_MEMORY: DiagnosticId =
+        DiagnosticId::from_u128(190311842471935261627352393512780655309);
     pub const TEXTURE_VIEWS: DiagnosticId =
         DiagnosticId::from_u128(257307432866562594739240898780307437578);
     pub const WINDOW_SURFACES: DiagnosticId =
@@ -56,8 +63,12 @@ impl WgpuResourceDiagnosticsPlugin {
         ));
 
         diagnostics.add(Diagnostic::new(Self::BUFFERS, "buffers", 10));
+        diagnostics
+            .add(Diagnostic::new(Self::BUFFER_MEMORY, "buffer_memory", 10).with_suffix("MiB"));
 
         diagnostics.add(Diagnostic::new(Self::TEXTURES, "textures", 10));
+        diagnostics
+            .add(Diagnostic::new(Self::TEXTURE_MEMORY, "texture_memory", 10).with_suffix("MiB"));
 
         diagnostics.add(Diagnostic::new(Self::TEXTURE_VIEWS, "texture_views", 10));
 
@@ -121,11 +132,29 @@ impl WgpuResourceDiagnosticsPlugin {
             render_resource_context.resources.buffers.read().len() as f64,
         );
 
+        let buffer_memory: usize = render_resource_context
+            .resources
+            .buffer_infos
+            .read()
+            .values()
+            .map(|buffer_info| buffer_info.size)
+            .sum();
+        diagnostics.add_measurement(Self::BUFFER_MEMORY, buffer_memory as f64 / MEBIBYTE);
+
         diagnostics.add_measurement(
             Self::TEXTURES,
             render_resource_context.resources.textures.read().len() as f64,
         );
 
+        let texture_memory: usize = render_resource_context
+            .resources
+            .texture_descriptors
+            .read()
+            .values()
+            .map(|descriptor| descriptor.size_in_bytes())
+            .sum();
+        diagnostics.add_measurement(Self::TEXTURE_MEMORY, texture_memory as f64 / MEBIBYTE);
+
         diagnostics.add_measurement(
             Self::TEXTURE_VIEWS,
             render_resource_context.resources.texture_views.read().len() as f64,
@@ -0,0 +1,127 @@
+use bevy_utils::Duration;
+use futures_lite::future;
+use std::{borrow::Cow, convert::TryInto};
+
+/// Size of a single timestamp written by a query.
+const TIMESTAMP_SIZE: u64 = std::mem::size_of::<u64>() as u64;
+
+/// The GPU time spent in each render graph node during the last rendered frame.
+///
+/// Timings are only measured when [`WgpuFeature::TimestampQuery`](crate::WgpuFeature) is enabled
+/// in the [`WgpuOptions`](crate::WgpuOptions) and supported by the device.
+#[derive(Debug, Default)]
+pub struct WgpuNodeTimings {
+    timings: Vec<(Cow<'static, str>, Duration)>,
+}
+
+impl WgpuNodeTimings {
+    /// Returns the GPU time spent in the node with the given name.
+    pub fn get(&self, name: &str) -> Option<Duration> {
+        self.timings
+            .iter()
+            .find(|(node_name, _)| node_name == name)
+            .map(|(_, duration)| *duration)
+    }
+
+    /// Iterates over the node names and their GPU time, in execution order.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, Duration)> {
+        self.timings
+            .iter()
+            .map(|(name, duration)| (name.as_ref(), *duration))
+    }
+}
+
+/// Measures the GPU time of render graph nodes by writing a timestamp before and after each node.
+#[derive(Debug, Default)]
+pub struct WgpuGpuTimer {
+    query_set: Option<wgpu::QuerySet>,
+    buffer: Option<wgpu::Buffer>,
+    capacity: u32,
+    nodes: Vec<Cow<'static, str>>,
+}
+
+impl WgpuGpuTimer {
+    pub fn is_supported(device: &wgpu::Device) -> bool {
+        device.features().contains(wgpu::Features::TIMESTAMP_QUERY)
+    }
+
+    /// Prepares the timer to measure up to `node_count` nodes.
+    pub fn begin_frame(&mut self, device: &wgpu::Device, node_count: usize) {
+        self.nodes.clear();
+        let query_count = node_count as u32 * 2;
+        if self.capacity < query_count {
+            self.query_set = Some(device.create_query_set(&wgpu::QuerySetDescriptor {
+                label: Some("render_graph_timestamps"),
+                ty: wgpu::QueryType::Timestamp,
+                count: query_count,
+            }));
+            self.buffer = Some(device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("render_graph_timestamps"),
+                size: query_count as u64 * TIMESTAMP_SIZE,
+                usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+                mapped_at_creation: false,
+            }));
+            self.capacity = query_count;
+        }
+    }
+
+    /// Writes the timestamp marking the start of a node, returning the index of the node.
+    pub fn begin_node(
+        &mut self,
+        command_encoder: &mut wgpu::CommandEncoder,
+        name: Cow<'static, str>,
+    ) -> usize {
+        let index = self.nodes.len();
+        self.nodes.push(name);
+        let query_set = self.query_set.as_ref().unwrap();
+        command_encoder.write_timestamp(query_set, index as u32 * 2);
+        index
+    }
+
+    /// Writes the timestamp marking the end of the node at `index`.
+    pub fn end_node(&mut self, command_encoder: &mut wgpu::CommandEncoder, index: usize) {
+        let query_set = self.query_set.as_ref().unwrap();
+        command_encoder.write_timestamp(query_set, index as u32 * 2 + 1);
+    }
+
+    /// Reads back the timestamps written this frame. This waits for the GPU to finish the frame,
+    /// so timings should only be enabled while profiling.
+    pub fn finish_frame(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) -> WgpuNodeTimings {
+        let (query_set, buffer) = match (&self.query_set, &self.buffer) {
+            (Some(query_set), Some(buffer)) if !self.nodes.is_empty() => (query_set, buffer),
+            _ => return WgpuNodeTimings::default(),
+        };
+        let query_count = self.nodes.len() as u32 * 2;
+        let mut command_encoder =
+            device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        command_encoder.resolve_query_set(query_set, 0..query_count, buffer, 0);
+        queue.submit(std::iter::once(command_encoder.finish()));
+
+        let buffer_slice = buffer.slice(0..query_count as u64 * TIMESTAMP_SIZE);
+        let data = buffer_slice.map_async(wgpu::MapMode::Read);
+        device.poll(wgpu::Maintain::Wait);
+        if future::block_on(data).is_err() {
+            return WgpuNodeTimings::default();
+        }
+
+        let timestamps = buffer_slice
+            .get_mapped_range()
+            .chunks_exact(TIMESTAMP_SIZE as usize)
+            .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+            .collect::<Vec<_>>();
+        buffer.unmap();
+
+        // timestamps are in ticks of `period` nanoseconds
+        let period = queue.get_timestamp_period() as f64;
+        let timings = self
+            .nodes
+            .drain(..)
+            .zip(timestamps.chunks_exact(2))
+            .map(|(name, timestamps)| {
+                let ticks = timestamps[1].saturating_sub(timestamps[0]);
+                (name, Duration::from_nanos((ticks as f64 * period) as u64))
+            })
+            .collect();
+        WgpuNodeTimings { timings }
+    }
+}
@@ -1,4 +1,4 @@
-use super::{WgpuRenderContext, WgpuRenderResourceContext};
+use super::{WgpuGpuTimer, WgpuRenderContext, WgpuRenderResourceContext};
 use bevy_ecs::world::World;
 use bevy_render::{
     render_graph::{Edge, NodeId, ResourceSlots, StageBorrow},
@@ -6,7 +6,7 @@ use bevy_render::{
 };
 use bevy_utils::HashMap;
 use parking_lot::RwLock;
-use std::sync::Arc;
+use std::{borrow::Cow, sync::Arc};
 
 #[derive(Debug)]
 pub struct WgpuRenderGraphExecutor {
@@ -20,6 +20,7 @@ impl WgpuRenderGraphExecutor {
         device: Arc<wgpu::Device>,
         queue: &mut wgpu::Queue,
         stages: &mut [StageBorrow],
+        mut gpu_timer: Option<&mut WgpuGpuTimer>,
     ) {
         let render_resource_context = {
             let context = world
@@ -72,12 +73,30 @@ impl WgpuRenderGraphExecutor {
                                 panic!("No edge connected to input.")
                             }
                         }
+                        let timer_index = gpu_timer.as_deref_mut().map(|gpu_timer| {
+                            let name = node_state
+                                .name
+                                .clone()
+                                .unwrap_or(Cow::Borrowed(node_state.type_name));
+                            let command_encoder = render_context
+                                .command_encoder
+                                .get_or_create(&render_context.device);
+                            gpu_timer.begin_node(command_encoder, name)
+                        });
                         node_state.node.update(
                             world,
                             &mut render_context,
                             &node_state.input_slots,
                             &mut node_state.output_slots,
                         );
+                        if let (Some(gpu_timer), Some(index)) =
+                            (gpu_timer.as_deref_mut(), timer_index)
+                        {
+                            let command_encoder = render_context
+                                .command_encoder
+                                .get_or_create(&render_context.device);
+                            gpu_timer.end_node(command_encoder, index);
+                        }
 
                         node_outputs
                             .write()
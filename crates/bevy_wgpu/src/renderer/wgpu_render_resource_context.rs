@@ -7,11 +7,13 @@ use bevy_render::{
         BindGroupDescriptor, BindGroupDescriptorId, BindingShaderStage, PipelineDescriptor,
     },
     renderer::{
-        BindGroup, BufferId, BufferInfo, BufferMapMode, RenderResourceBinding,
-        RenderResourceContext, RenderResourceId, SamplerId, TextureId,
+        BindGroup, BufferId, BufferInfo, BufferMapMode, BufferUsage, RenderResourceBinding,
+        RenderResourceContext, RenderResourceId, SamplerId, ShaderCompiler, TextureId,
     },
     shader::{glsl_to_spirv, Shader, ShaderError, ShaderSource},
-    texture::{Extent3d, SamplerDescriptor, TextureDescriptor},
+    texture::{
+        Extent3d, SamplerDescriptor, Texture, TextureDescriptor, TextureFormat, TextureUsage,
+    },
 };
 use bevy_utils::tracing::trace;
 use bevy_window::{Window, WindowId};
@@ -43,11 +45,64 @@ impl WgpuRenderResourceContext {
         }
     }
 
+    /// Sets the surface a window is rendered to. The swap chain of a replaced surface is removed,
+    /// and recreated for the new surface when the next frame is rendered.
     pub fn set_window_surface(&self, window_id: WindowId, surface: wgpu::Surface) {
+        let mut window_swap_chains = self.resources.window_swap_chains.write();
         let mut window_surfaces = self.resources.window_surfaces.write();
+        window_swap_chains.remove(&window_id);
         window_surfaces.insert(window_id, surface);
     }
 
+    /// Reads back the texture a headless window was rendered to, once the commands rendering it
+    /// have been submitted to `queue`. Returns `None` if the window isn't headless.
+    pub fn read_window_texture(&self, queue: &wgpu::Queue, window_id: WindowId) -> Option<Texture> {
+        let texture = *self.resources.window_textures.read().get(&window_id)?;
+        let descriptor = *self.resources.texture_descriptors.read().get(&texture)?;
+        let Extent3d { width, height, .. } = descriptor.size;
+        let bytes_per_row = width as usize * descriptor.format.pixel_size();
+        let padded_bytes_per_row = self.get_aligned_texture_size(bytes_per_row);
+
+        let buffer = self.create_buffer(BufferInfo {
+            size: padded_bytes_per_row * height as usize,
+            buffer_usage: BufferUsage::COPY_DST | BufferUsage::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut command_encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
+        self.copy_texture_to_buffer(
+            &mut command_encoder,
+            texture,
+            [0, 0, 0],
+            0,
+            buffer,
+            0,
+            padded_bytes_per_row as u32,
+            descriptor.size,
+        );
+        queue.submit(std::iter::once(command_encoder.finish()));
+
+        self.map_buffer(buffer, BufferMapMode::Read);
+        let mut data = Vec::with_capacity(bytes_per_row * height as usize);
+        {
+            let buffers = self.resources.buffers.read();
+            let mapped = buffers.get(&buffer).unwrap().slice(..).get_mapped_range();
+            for row in mapped.chunks(padded_bytes_per_row) {
+                data.extend_from_slice(&row[..bytes_per_row]);
+            }
+        }
+        self.unmap_buffer(buffer);
+        self.remove_buffer(buffer);
+
+        Some(Texture::new(
+            descriptor.size,
+            descriptor.dimension,
+            data,
+            descriptor.format,
+        ))
+    }
+
     pub fn copy_buffer_to_buffer(
         &self,
         command_encoder: &mut wgpu::CommandEncoder,
@@ -234,7 +289,7 @@ impl WgpuRenderResourceContext {
         let mut window_swap_chains = self.resources.window_swap_chains.write();
         let mut swap_chain_outputs = self.resources.swap_chain_frames.write();
 
-        let window_swap_chain = window_swap_chains.get_mut(&window_id).unwrap();
+        let window_swap_chain = window_swap_chains.get_mut(&window_id)?;
         let next_texture = window_swap_chain.get_current_frame().ok()?;
         let id = TextureId::new();
         swap_chain_outputs.insert(id, next_texture);
@@ -360,12 +415,30 @@ impl RenderResourceContext for WgpuRenderResourceContext {
 
     fn create_swap_chain(&self, window: &Window) {
         let surfaces = self.resources.window_surfaces.read();
+        let surface = match surfaces.get(&window.id()) {
+            Some(surface) => surface,
+            None => {
+                // headless windows are rendered to a texture that can be read back
+                let texture = self.create_texture(TextureDescriptor {
+                    size: Extent3d::new(window.physical_width(), window.physical_height(), 1),
+                    format: TextureFormat::default(),
+                    usage: TextureUsage::OUTPUT_ATTACHMENT | TextureUsage::COPY_SRC,
+                    ..Default::default()
+                });
+                let previous = self
+                    .resources
+                    .window_textures
+                    .write()
+                    .insert(window.id(), texture);
+                if let Some(previous) = previous {
+                    self.remove_texture(previous);
+                }
+                return;
+            }
+        };
         let mut window_swap_chains = self.resources.window_swap_chains.write();
 
         let swap_chain_descriptor: wgpu::SwapChainDescriptor = window.wgpu_into();
-        let surface = surfaces
-            .get(&window.id())
-            .expect("No surface found for window.");
         let swap_chain = self
             .device
             .create_swap_chain(surface, &swap_chain_descriptor);
@@ -374,6 +447,23 @@ impl RenderResourceContext for WgpuRenderResourceContext {
     }
 
     fn next_swap_chain_texture(&self, window: &bevy_window::Window) -> TextureId {
+        if !self
+            .resources
+            .window_surfaces
+            .read()
+            .contains_key(&window.id())
+        {
+            let texture = self
+                .resources
+                .window_textures
+                .read()
+                .get(&window.id())
+                .cloned();
+            return texture.unwrap_or_else(|| {
+                self.create_swap_chain(window);
+                self.resources.window_textures.read()[&window.id()]
+            });
+        }
         if let Some(texture_id) = self.try_next_swap_chain_texture(window.id()) {
             texture_id
         } else {
@@ -614,6 +704,14 @@ impl RenderResourceContext for WgpuRenderResourceContext {
         self.resources.buffer_infos.read().get(&buffer).cloned()
     }
 
+    fn get_texture_descriptor(&self, texture: TextureId) -> Option<TextureDescriptor> {
+        self.resources
+            .texture_descriptors
+            .read()
+            .get(&texture)
+            .copied()
+    }
+
     fn write_mapped_buffer(
         &self,
         id: BufferId,
@@ -682,13 +780,21 @@ impl RenderResourceContext for WgpuRenderResourceContext {
         shader: &Shader,
         macros: Option<&[String]>,
     ) -> Result<Shader, ShaderError> {
-        let spirv_data = match shader.source {
-            ShaderSource::Spirv(ref bytes) => bytes.clone(),
-            ShaderSource::Glsl(ref source) => glsl_to_spirv(&source, shader.stage, macros)?,
-        };
-        Ok(Shader {
-            source: ShaderSource::Spirv(spirv_data),
-            ..*shader
-        })
+        compile_shader(shader, macros)
     }
+
+    fn shader_compiler(&self) -> Option<ShaderCompiler> {
+        Some(compile_shader)
+    }
+}
+
+fn compile_shader(shader: &Shader, macros: Option<&[String]>) -> Result<Shader, ShaderError> {
+    let spirv_data = match shader.source {
+        ShaderSource::Spirv(ref bytes) => bytes.clone(),
+        ShaderSource::Glsl(ref source) => glsl_to_spirv(&source, shader.stage, macros)?,
+    };
+    Ok(Shader {
+        source: ShaderSource::Spirv(spirv_data),
+        ..*shader
+    })
 }
@@ -1,5 +1,5 @@
 use crate::{
-    renderer::{WgpuRenderGraphExecutor, WgpuRenderResourceContext},
+    renderer::{WgpuGpuTimer, WgpuRenderGraphExecutor, WgpuRenderResourceContext},
     wgpu_type_converter::WgpuInto,
     WgpuBackend, WgpuOptions, WgpuPowerOptions,
 };
@@ -8,9 +8,13 @@ use bevy_ecs::world::{Mut, World};
 use bevy_render::{
     render_graph::{DependentNodeStager, RenderGraph, RenderGraphStager},
     renderer::RenderResourceContext,
+    texture::WindowCaptures,
 };
-use bevy_window::{WindowCreated, WindowResized, Windows};
-use std::{ops::Deref, sync::Arc};
+use bevy_utils::tracing::warn;
+use bevy_window::{
+    ApplicationLifecycle, WindowCreated, WindowResized, WindowSurfaceRecreated, Windows,
+};
+use std::sync::Arc;
 
 pub struct WgpuRenderer {
     pub instance: wgpu::Instance,
@@ -18,7 +22,14 @@ pub struct WgpuRenderer {
     pub queue: wgpu::Queue,
     pub window_resized_event_reader: ManualEventReader<WindowResized>,
     pub window_created_event_reader: ManualEventReader<WindowCreated>,
+    pub window_surface_recreated_event_reader: ManualEventReader<WindowSurfaceRecreated>,
+    pub application_lifecycle_event_reader: ManualEventReader<ApplicationLifecycle>,
+    /// Whether the application is suspended, in which case nothing is rendered, as the surfaces
+    /// of its windows may have been destroyed
+    pub suspended: bool,
     pub initialized: bool,
+    /// Measures the GPU time of each render graph node, if timestamp queries are supported
+    pub gpu_timer: Option<WgpuGpuTimer>,
 }
 
 impl WgpuRenderer {
@@ -68,13 +79,22 @@ impl WgpuRenderer {
             .await
             .unwrap();
         let device = Arc::new(device);
+        let gpu_timer = if WgpuGpuTimer::is_supported(&device) {
+            Some(WgpuGpuTimer::default())
+        } else {
+            None
+        };
         WgpuRenderer {
             instance,
             device,
             queue,
             window_resized_event_reader: Default::default(),
             window_created_event_reader: Default::default(),
+            window_surface_recreated_event_reader: Default::default(),
+            application_lifecycle_event_reader: Default::default(),
+            suspended: false,
             initialized: false,
+            gpu_timer,
         }
     }
 
@@ -88,19 +108,26 @@ impl WgpuRenderer {
             .unwrap();
         let windows = world.get_resource::<Windows>().unwrap();
         let window_created_events = world.get_resource::<Events<WindowCreated>>().unwrap();
-        for window_created_event in self
+        let window_surface_recreated_events = world
+            .get_resource::<Events<WindowSurfaceRecreated>>()
+            .unwrap();
+        let window_ids = self
             .window_created_event_reader
             .iter(&window_created_events)
-        {
+            .map(|event| event.id)
+            .chain(
+                self.window_surface_recreated_event_reader
+                    .iter(&window_surface_recreated_events)
+                    .map(|event| event.id),
+            );
+        for window_id in window_ids {
             let window = windows
-                .get(window_created_event.id)
+                .get(window_id)
                 .expect("Received window created event for non-existent window.");
-            #[cfg(feature = "bevy_winit")]
-            {
-                let winit_windows = world.get_resource::<bevy_winit::WinitWindows>().unwrap();
-                let winit_window = winit_windows.get_window(window.id()).unwrap();
-                // SAFE: The raw window handle created from a `winit::Window` is always valid.
-                let surface = unsafe { self.instance.create_surface(winit_window.deref()) };
+            // headless windows have no surface, they are rendered to textures instead
+            if let Some(raw_window_handle) = window.raw_window_handle() {
+                // SAFE: the creator of the window guarantees that its raw window handle is valid
+                let surface = unsafe { self.instance.create_surface(raw_window_handle) };
                 render_resource_context.set_window_surface(window.id(), surface);
             }
         }
@@ -114,17 +141,82 @@ impl WgpuRenderer {
             let stages = stager.get_stages(&render_graph).unwrap();
             let mut borrowed = stages.borrow(&mut render_graph);
 
+            if let Some(gpu_timer) = &mut self.gpu_timer {
+                let node_count = borrowed
+                    .iter()
+                    .flat_map(|stage| stage.jobs.iter())
+                    .map(|job| job.node_states.len())
+                    .sum();
+                gpu_timer.begin_frame(&self.device, node_count);
+            }
+
             // execute stages
             let graph_executor = WgpuRenderGraphExecutor {
                 max_thread_count: 2,
             };
-            graph_executor.execute(world, self.device.clone(), &mut self.queue, &mut borrowed);
+            graph_executor.execute(
+                world,
+                self.device.clone(),
+                &mut self.queue,
+                &mut borrowed,
+                self.gpu_timer.as_mut(),
+            );
+
+            if let Some(gpu_timer) = &mut self.gpu_timer {
+                let node_timings = gpu_timer.finish_frame(&self.device, &self.queue);
+                world.insert_resource(node_timings);
+            }
         })
     }
 
+    pub fn capture_windows(&mut self, world: &mut World) {
+        let world = world.cell();
+        let mut window_captures = match world.get_resource_mut::<WindowCaptures>() {
+            Some(window_captures) => window_captures,
+            None => return,
+        };
+        let render_resource_context = world
+            .get_resource::<Box<dyn RenderResourceContext>>()
+            .unwrap();
+        let render_resource_context = render_resource_context
+            .downcast_ref::<WgpuRenderResourceContext>()
+            .unwrap();
+        let requests = window_captures.drain_requests().collect::<Vec<_>>();
+        for window_id in requests {
+            match render_resource_context.read_window_texture(&self.queue, window_id) {
+                Some(texture) => window_captures.insert(window_id, texture),
+                None => warn!(
+                    "Window {:?} can't be captured, only headless windows can.",
+                    window_id
+                ),
+            }
+        }
+    }
+
+    pub fn handle_application_lifecycle_events(&mut self, world: &mut World) {
+        let lifecycle_events = world
+            .get_resource::<Events<ApplicationLifecycle>>()
+            .unwrap();
+        for lifecycle_event in self
+            .application_lifecycle_event_reader
+            .iter(&lifecycle_events)
+        {
+            match lifecycle_event {
+                ApplicationLifecycle::Suspended => self.suspended = true,
+                ApplicationLifecycle::Resumed => self.suspended = false,
+                ApplicationLifecycle::LowMemory => {}
+            }
+        }
+    }
+
     pub fn update(&mut self, world: &mut World) {
+        self.handle_application_lifecycle_events(world);
+        if self.suspended {
+            return;
+        }
         self.handle_window_created_events(world);
         self.run_graph(world);
+        self.capture_windows(world);
 
         let render_resource_context = world
             .get_resource::<Box<dyn RenderResourceContext>>()
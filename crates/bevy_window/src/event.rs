@@ -30,6 +30,30 @@ pub struct WindowCreated {
     pub id: WindowId,
 }
 
+/// An event that is sent when the native surface of a window was replaced, for example when an
+/// Android application is resumed. Render backends recreate what they render to from the new
+/// [`Window::raw_window_handle`](crate::Window::raw_window_handle).
+#[derive(Debug, Clone)]
+pub struct WindowSurfaceRecreated {
+    pub id: WindowId,
+}
+
+/// An event that is sent when the operating system changes the state of the application, mostly
+/// on mobile platforms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApplicationLifecycle {
+    /// The application was sent to the background. It is updated once more, without rendering,
+    /// then not updated until it is resumed. Mobile operating systems may terminate suspended
+    /// applications, so this is the time to save their state.
+    Suspended,
+    /// The application came back to the foreground, and is updated again.
+    Resumed,
+    /// The operating system is running out of memory, and may terminate the application if it
+    /// doesn't release some. The winit backend doesn't report it, as winit doesn't expose memory
+    /// warnings, but platform integrations can send it.
+    LowMemory,
+}
+
 /// An event that is sent whenever a close was requested for a window. For example: when the "close"
 /// button is pressed on a window.
 #[derive(Debug, Clone)]
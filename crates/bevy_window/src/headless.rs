@@ -0,0 +1,80 @@
+use crate::{CreateWindow, Window, WindowCreated, Windows};
+use bevy_app::{AppBuilder, CoreStage, EventReader, EventWriter, Plugin};
+use bevy_ecs::system::{IntoSystem, ResMut};
+
+/// A window backend creating windows that aren't displayed, for apps running on machines
+/// without a display server, such as automated tests. Use it instead of the `WinitPlugin`.
+///
+/// Headless windows have no native window, render backends draw them to offscreen textures that
+/// can be read back with `WindowCaptures`. Their physical size is their requested size multiplied
+/// by their scale factor override, if any.
+#[derive(Default)]
+pub struct HeadlessWindowPlugin;
+
+impl Plugin for HeadlessWindowPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app.add_system_to_stage(
+            CoreStage::PreUpdate,
+            create_headless_windows_system.system(),
+        );
+    }
+}
+
+pub fn create_headless_windows_system(
+    mut windows: ResMut<Windows>,
+    mut create_window_events: EventReader<CreateWindow>,
+    mut window_created_events: EventWriter<WindowCreated>,
+) {
+    for create_window_event in create_window_events.iter() {
+        let descriptor = &create_window_event.descriptor;
+        let scale_factor = descriptor.scale_factor_override.unwrap_or(1.0);
+        windows.add(Window::new(
+            create_window_event.id,
+            descriptor,
+            (descriptor.width as f64 * scale_factor) as u32,
+            (descriptor.height as f64 * scale_factor) as u32,
+            scale_factor,
+            None,
+        ));
+        window_created_events.send(WindowCreated {
+            id: create_window_event.id,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::HeadlessWindowPlugin;
+    use crate::{WindowCreated, WindowDescriptor, WindowId, WindowPlugin, Windows};
+    use bevy_app::{App, Events};
+
+    #[test]
+    fn creates_primary_window() {
+        let mut app = App::build();
+        app.insert_resource(WindowDescriptor {
+            width: 100.0,
+            height: 50.0,
+            scale_factor_override: Some(2.0),
+            ..Default::default()
+        })
+        .add_plugin(WindowPlugin::default())
+        .add_plugin(HeadlessWindowPlugin);
+        let mut app = app.app;
+        app.update();
+
+        let windows = app.world.get_resource::<Windows>().unwrap();
+        let window = windows.get_primary().unwrap();
+        assert_eq!(window.physical_width(), 200);
+        assert_eq!(window.physical_height(), 100);
+        assert_eq!(window.scale_factor(), 2.0);
+        assert_eq!(window.width(), 100.0);
+
+        let created = app.world.get_resource::<Events<WindowCreated>>().unwrap();
+        let ids = created
+            .get_reader()
+            .iter(created)
+            .map(|event| event.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![WindowId::primary()]);
+    }
+}
@@ -1,10 +1,14 @@
 mod event;
+mod headless;
+mod raw_window;
 mod system;
 mod window;
 mod windows;
 
 use bevy_ecs::system::IntoSystem;
 pub use event::*;
+pub use headless::*;
+pub use raw_window::*;
 pub use system::*;
 pub use window::*;
 pub use windows::*;
@@ -49,6 +53,8 @@ impl Plugin for WindowPlugin {
             .add_event::<WindowBackendScaleFactorChanged>()
             .add_event::<FileDragAndDrop>()
             .add_event::<WindowMoved>()
+            .add_event::<WindowSurfaceRecreated>()
+            .add_event::<ApplicationLifecycle>()
             .init_resource::<Windows>();
 
         if self.add_primary_window {
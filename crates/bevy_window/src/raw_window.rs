@@ -0,0 +1,95 @@
+use crate::{Window, WindowCreated, WindowDescriptor, WindowId, WindowResized, Windows};
+use bevy_app::Events;
+use bevy_ecs::world::World;
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+
+/// A [`RawWindowHandle`] that render backends can create a surface from.
+#[derive(Debug, Clone)]
+pub struct RawWindowHandleWrapper(RawWindowHandle);
+
+impl RawWindowHandleWrapper {
+    /// # Safety
+    ///
+    /// The handle must stay valid as long as the wrapper and the [`Window`] it is given to exist.
+    pub unsafe fn new(handle: RawWindowHandle) -> Self {
+        RawWindowHandleWrapper(handle)
+    }
+
+    pub fn get_handle(&self) -> RawWindowHandle {
+        self.0
+    }
+}
+
+// SAFE: the creator of the wrapper guarantees that the handle is valid
+unsafe impl HasRawWindowHandle for RawWindowHandleWrapper {
+    fn raw_window_handle(&self) -> RawWindowHandle {
+        self.0
+    }
+}
+
+// SAFE: the handle is only read to create surfaces, which render backends do from any thread
+unsafe impl Send for RawWindowHandleWrapper {}
+unsafe impl Sync for RawWindowHandleWrapper {}
+
+/// Adds a window backed by a native window owned by an embedding application, such as a panel
+/// of an editor, so that bevy can render to it without creating windows itself. The application
+/// keeps handling the events of the window: it must report size changes with
+/// [`resize_raw_window`], and forward the input events it wants bevy to see.
+///
+/// Pass [`WindowId::primary`] as `id` to render with the default cameras, after disabling the
+/// creation of the primary window in the [`WindowPlugin`](crate::WindowPlugin).
+///
+/// # Safety
+///
+/// `handle` must stay valid until the window is removed from [`Windows`].
+pub unsafe fn add_raw_window(
+    world: &mut World,
+    id: WindowId,
+    descriptor: &WindowDescriptor,
+    handle: RawWindowHandle,
+    physical_width: u32,
+    physical_height: u32,
+    scale_factor: f64,
+) {
+    let window = Window::new(
+        id,
+        descriptor,
+        physical_width,
+        physical_height,
+        scale_factor,
+        None,
+    )
+    .with_raw_window_handle(RawWindowHandleWrapper::new(handle));
+    world.get_resource_mut::<Windows>().unwrap().add(window);
+    world
+        .get_resource_mut::<Events<WindowCreated>>()
+        .unwrap()
+        .send(WindowCreated { id });
+}
+
+/// Reports that a window added with [`add_raw_window`] was resized to the given physical size.
+pub fn resize_raw_window(
+    world: &mut World,
+    id: WindowId,
+    physical_width: u32,
+    physical_height: u32,
+) {
+    let mut windows = world.get_resource_mut::<Windows>().unwrap();
+    let window = match windows.get_mut(id) {
+        Some(window) => window,
+        None => return,
+    };
+    if window.physical_width() == physical_width && window.physical_height() == physical_height {
+        return;
+    }
+    window.update_actual_size_from_backend(physical_width, physical_height);
+    let resized = WindowResized {
+        id,
+        width: window.width(),
+        height: window.height(),
+    };
+    world
+        .get_resource_mut::<Events<WindowResized>>()
+        .unwrap()
+        .send(resized);
+}
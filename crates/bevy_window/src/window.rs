@@ -1,3 +1,4 @@
+use crate::RawWindowHandleWrapper;
 use bevy_math::{IVec2, Vec2};
 use bevy_utils::{tracing::warn, Uuid};
 
@@ -44,6 +45,8 @@ pub struct WindowResizeConstraints {
     pub min_height: f32,
     pub max_width: f32,
     pub max_height: f32,
+    /// The ratio of width to height the window is forced to keep, if any.
+    pub aspect_ratio: Option<f32>,
 }
 
 impl Default for WindowResizeConstraints {
@@ -53,6 +56,7 @@ impl Default for WindowResizeConstraints {
             min_height: 120.,
             max_width: f32::INFINITY,
             max_height: f32::INFINITY,
+            aspect_ratio: None,
         }
     }
 }
@@ -64,6 +68,7 @@ impl WindowResizeConstraints {
             mut min_height,
             mut max_width,
             mut max_height,
+            mut aspect_ratio,
         } = self;
         min_width = min_width.max(1.);
         min_height = min_height.max(1.);
@@ -81,13 +86,38 @@ impl WindowResizeConstraints {
             );
             max_height = min_height;
         }
+        if let Some(ratio) = aspect_ratio {
+            if !(ratio.is_finite() && ratio > 0.) {
+                warn!("The given aspect ratio {} is not a positive number", ratio);
+                aspect_ratio = None;
+            }
+        }
         WindowResizeConstraints {
             min_width,
             min_height,
             max_width,
             max_height,
+            aspect_ratio,
         }
     }
+
+    /// Returns the size closest to the given logical size which satisfies the constraints. The
+    /// constraints should have been validated by [`WindowResizeConstraints::check_constraints`].
+    ///
+    /// When an aspect ratio is set, the width is kept and the height adjusted, unless that would
+    /// break the size limits.
+    pub fn constrain(&self, width: f32, height: f32) -> (f32, f32) {
+        let width = width.clamp(self.min_width, self.max_width);
+        let height = height.clamp(self.min_height, self.max_height);
+        let ratio = match self.aspect_ratio {
+            Some(ratio) => ratio,
+            None => return (width, height),
+        };
+
+        let height = (width / ratio).clamp(self.min_height, self.max_height);
+        let width = (height * ratio).clamp(self.min_width, self.max_width);
+        (width, height)
+    }
 }
 
 /// An operating system window that can present content and receive user input.
@@ -127,6 +157,7 @@ pub struct Window {
     mode: WindowMode,
     #[cfg(target_arch = "wasm32")]
     pub canvas: Option<String>,
+    raw_window_handle: Option<RawWindowHandleWrapper>,
     command_queue: Vec<WindowCommand>,
 }
 
@@ -220,10 +251,22 @@ impl Window {
             mode: window_descriptor.mode,
             #[cfg(target_arch = "wasm32")]
             canvas: window_descriptor.canvas.clone(),
+            raw_window_handle: None,
             command_queue: Vec::new(),
         }
     }
 
+    /// Sets the native window that render backends create the surface of the window from.
+    pub fn with_raw_window_handle(mut self, raw_window_handle: RawWindowHandleWrapper) -> Self {
+        self.raw_window_handle = Some(raw_window_handle);
+        self
+    }
+
+    /// The native window backing this window, if any
+    pub fn raw_window_handle(&self) -> Option<&RawWindowHandleWrapper> {
+        self.raw_window_handle.as_ref()
+    }
+
     #[inline]
     pub fn id(&self) -> WindowId {
         self.id
@@ -321,9 +364,11 @@ impl Window {
             .push(WindowCommand::SetPosition { position })
     }
 
-    /// Modifies the minimum and maximum window bounds for resizing in logical pixels.
+    /// Modifies the minimum and maximum window bounds for resizing in logical pixels, and the
+    /// aspect ratio the window has to keep.
     #[inline]
     pub fn set_resize_constraints(&mut self, resize_constraints: WindowResizeConstraints) {
+        self.resize_constraints = resize_constraints;
         self.command_queue
             .push(WindowCommand::SetResizeConstraints { resize_constraints });
     }
@@ -332,6 +377,10 @@ impl Window {
     /// specified width and height.
     #[allow(clippy::float_cmp)]
     pub fn set_resolution(&mut self, width: f32, height: f32) {
+        let (width, height) = self
+            .resize_constraints
+            .check_constraints()
+            .constrain(width, height);
         if self.requested_width == width && self.requested_height == height {
             return;
         }
@@ -374,6 +423,15 @@ impl Window {
         self.physical_height = physical_height;
     }
 
+    #[allow(missing_docs)]
+    #[inline]
+    pub fn update_raw_window_handle_from_backend(
+        &mut self,
+        raw_window_handle: RawWindowHandleWrapper,
+    ) {
+        self.raw_window_handle = Some(raw_window_handle);
+    }
+
     #[allow(missing_docs)]
     #[inline]
     pub fn update_actual_position_from_backend(&mut self, position: IVec2) {
@@ -5,12 +5,14 @@ use bevy_input::{
     ElementState,
 };
 use bevy_math::Vec2;
+use bevy_utils::Instant;
 
 pub fn convert_keyboard_input(keyboard_input: &winit::event::KeyboardInput) -> KeyboardInput {
     KeyboardInput {
         scan_code: keyboard_input.scancode,
         state: convert_element_state(keyboard_input.state),
         key_code: keyboard_input.virtual_keycode.map(convert_virtual_key_code),
+        timestamp: Instant::now(),
     }
 }
 
@@ -0,0 +1,493 @@
+use crate::{converters, WinitWindows};
+use bevy_app::{App, AppBuilder, AppExit, Events, ManualEventReader};
+use bevy_ecs::world::World;
+use bevy_input::{
+    keyboard::KeyboardInput,
+    mouse::{MouseButtonInput, MouseMotion, MouseScrollUnit, MouseWheel},
+    touch::TouchInput,
+};
+use bevy_math::{ivec2, Vec2};
+use bevy_utils::{tracing::warn, Instant};
+use bevy_window::{
+    ApplicationLifecycle, CreateWindow, CursorEntered, CursorLeft, CursorMoved, FileDragAndDrop,
+    RawWindowHandleWrapper, ReceivedCharacter, WindowBackendScaleFactorChanged,
+    WindowCloseRequested, WindowCreated, WindowDescriptor, WindowFocused, WindowId, WindowMoved,
+    WindowResized, WindowScaleFactorChanged, WindowSurfaceRecreated, Windows,
+};
+use raw_window_handle::HasRawWindowHandle;
+use winit::{
+    dpi::LogicalSize,
+    event::{self, DeviceEvent, Event, WindowEvent},
+    event_loop::EventLoopWindowTarget,
+};
+
+/// Drives an [`App`] from winit events, without owning the event loop.
+///
+/// [`winit_runner`](crate::winit_runner) uses it to run the app in its own event loop. To embed
+/// bevy in an application that already owns a winit event loop, such as an editor, build the app
+/// without running it, give it to [`WinitEventHandler::from_builder`] and feed every event of the
+/// loop to [`WinitEventHandler::handle_event`]:
+/// the app is updated when the loop has handled its other events, and the windows it requests
+/// are created in the loop. Windows created by the application can be given to the app with
+/// [`WinitEventHandler::add_window`].
+pub struct WinitEventHandler {
+    app: App,
+    create_window_event_reader: ManualEventReader<CreateWindow>,
+    app_exit_event_reader: ManualEventReader<AppExit>,
+    active: bool,
+}
+
+impl WinitEventHandler {
+    /// Drives an app whose plugins are already finished. Use [`WinitEventHandler::from_builder`]
+    /// to drive an app that is still being built.
+    pub fn new(app: App) -> Self {
+        WinitEventHandler {
+            app,
+            create_window_event_reader: Default::default(),
+            app_exit_event_reader: Default::default(),
+            active: true,
+        }
+    }
+
+    /// Finishes the plugins of the app, which [`AppBuilder::run`] otherwise does, and drives the
+    /// built app.
+    pub fn from_builder(mut builder: AppBuilder) -> Self {
+        builder.finish_plugins();
+        Self::new(std::mem::take(&mut builder.app))
+    }
+
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    pub fn into_app(self) -> App {
+        self.app
+    }
+
+    /// Whether the app is updated, which it isn't between the [`Event::Suspended`] and
+    /// [`Event::Resumed`] events
+    pub fn is_active(&self) -> bool {
+        self.active
+    }
+
+    /// Forwards a winit event to the app, updating it on [`Event::MainEventsCleared`].
+    ///
+    /// Returns `true` once the app has sent an [`AppExit`] event, after which the event loop
+    /// should stop feeding it.
+    pub fn handle_event<T>(
+        &mut self,
+        event: Event<'_, T>,
+        event_loop: &EventLoopWindowTarget<T>,
+    ) -> bool {
+        let exit_requested = self.exit_requested();
+
+        match event {
+            event::Event::WindowEvent {
+                event,
+                window_id: winit_window_id,
+                ..
+            } => {
+                let world = self.app.world.cell();
+                let winit_windows = world.get_resource_mut::<WinitWindows>().unwrap();
+                let mut windows = world.get_resource_mut::<Windows>().unwrap();
+                let window_id =
+                    if let Some(window_id) = winit_windows.get_window_id(winit_window_id) {
+                        window_id
+                    } else {
+                        warn!(
+                            "Skipped event for unknown winit Window Id {:?}",
+                            winit_window_id
+                        );
+                        return exit_requested;
+                    };
+
+                let window = if let Some(window) = windows.get_mut(window_id) {
+                    window
+                } else {
+                    warn!("Skipped event for unknown Window Id {:?}", winit_window_id);
+                    return exit_requested;
+                };
+
+                match event {
+                    WindowEvent::Resized(size) => {
+                        let constraints = window.resize_constraints().check_constraints();
+                        if constraints.aspect_ratio.is_some() {
+                            // winit can't lock the aspect ratio, so resize the window back
+                            let winit_window = winit_windows.get_window(window_id).unwrap();
+                            let logical_size = size.to_logical::<f32>(winit_window.scale_factor());
+                            let (width, height) =
+                                constraints.constrain(logical_size.width, logical_size.height);
+                            if (width - logical_size.width).abs() >= 1.0
+                                || (height - logical_size.height).abs() >= 1.0
+                            {
+                                winit_window.set_inner_size(LogicalSize::new(width, height));
+                            }
+                        }
+                        window.update_actual_size_from_backend(size.width, size.height);
+                        let mut resize_events =
+                            world.get_resource_mut::<Events<WindowResized>>().unwrap();
+                        resize_events.send(WindowResized {
+                            id: window_id,
+                            width: window.width(),
+                            height: window.height(),
+                        });
+                    }
+                    WindowEvent::CloseRequested => {
+                        let mut window_close_requested_events = world
+                            .get_resource_mut::<Events<WindowCloseRequested>>()
+                            .unwrap();
+                        window_close_requested_events.send(WindowCloseRequested { id: window_id });
+                    }
+                    WindowEvent::KeyboardInput { ref input, .. } => {
+                        let mut keyboard_input_events =
+                            world.get_resource_mut::<Events<KeyboardInput>>().unwrap();
+                        keyboard_input_events.send(converters::convert_keyboard_input(input));
+                    }
+                    WindowEvent::CursorMoved { position, .. } => {
+                        let mut cursor_moved_events =
+                            world.get_resource_mut::<Events<CursorMoved>>().unwrap();
+                        let winit_window = winit_windows.get_window(window_id).unwrap();
+                        let position = position.to_logical(winit_window.scale_factor());
+                        let inner_size = winit_window
+                            .inner_size()
+                            .to_logical::<f32>(winit_window.scale_factor());
+
+                        // move origin to bottom left
+                        let y_position = inner_size.height - position.y;
+
+                        let position = Vec2::new(position.x, y_position);
+                        window.update_cursor_position_from_backend(Some(position));
+
+                        cursor_moved_events.send(CursorMoved {
+                            id: window_id,
+                            position,
+                        });
+                    }
+                    WindowEvent::CursorEntered { .. } => {
+                        let mut cursor_entered_events =
+                            world.get_resource_mut::<Events<CursorEntered>>().unwrap();
+                        cursor_entered_events.send(CursorEntered { id: window_id });
+                    }
+                    WindowEvent::CursorLeft { .. } => {
+                        let mut cursor_left_events =
+                            world.get_resource_mut::<Events<CursorLeft>>().unwrap();
+                        window.update_cursor_position_from_backend(None);
+                        cursor_left_events.send(CursorLeft { id: window_id });
+                    }
+                    WindowEvent::MouseInput { state, button, .. } => {
+                        let mut mouse_button_input_events = world
+                            .get_resource_mut::<Events<MouseButtonInput>>()
+                            .unwrap();
+                        mouse_button_input_events.send(MouseButtonInput {
+                            button: converters::convert_mouse_button(button),
+                            state: converters::convert_element_state(state),
+                            timestamp: Instant::now(),
+                        });
+                    }
+                    WindowEvent::MouseWheel { delta, .. } => match delta {
+                        event::MouseScrollDelta::LineDelta(x, y) => {
+                            let mut mouse_wheel_input_events =
+                                world.get_resource_mut::<Events<MouseWheel>>().unwrap();
+                            mouse_wheel_input_events.send(MouseWheel {
+                                unit: MouseScrollUnit::Line,
+                                x,
+                                y,
+                            });
+                        }
+                        event::MouseScrollDelta::PixelDelta(p) => {
+                            let mut mouse_wheel_input_events =
+                                world.get_resource_mut::<Events<MouseWheel>>().unwrap();
+                            mouse_wheel_input_events.send(MouseWheel {
+                                unit: MouseScrollUnit::Pixel,
+                                x: p.x as f32,
+                                y: p.y as f32,
+                            });
+                        }
+                    },
+                    WindowEvent::Touch(touch) => {
+                        let mut touch_input_events =
+                            world.get_resource_mut::<Events<TouchInput>>().unwrap();
+
+                        let winit_window = winit_windows.get_window(window_id).unwrap();
+                        let mut location = touch.location.to_logical(winit_window.scale_factor());
+
+                        // On a mobile window, the start is from the top while on PC/Linux/OSX from
+                        // bottom
+                        if cfg!(target_os = "android") || cfg!(target_os = "ios") {
+                            let window_height = windows.get_primary().unwrap().height();
+                            location.y = window_height - location.y;
+                        }
+                        touch_input_events.send(converters::convert_touch_input(touch, location));
+                    }
+                    WindowEvent::ReceivedCharacter(c) => {
+                        let mut char_input_events = world
+                            .get_resource_mut::<Events<ReceivedCharacter>>()
+                            .unwrap();
+
+                        char_input_events.send(ReceivedCharacter {
+                            id: window_id,
+                            char: c,
+                        })
+                    }
+                    WindowEvent::ScaleFactorChanged {
+                        scale_factor,
+                        new_inner_size,
+                    } => {
+                        let mut backend_scale_factor_change_events = world
+                            .get_resource_mut::<Events<WindowBackendScaleFactorChanged>>()
+                            .unwrap();
+                        backend_scale_factor_change_events.send(WindowBackendScaleFactorChanged {
+                            id: window_id,
+                            scale_factor,
+                        });
+                        #[allow(clippy::float_cmp)]
+                        if window.scale_factor() != scale_factor {
+                            let mut scale_factor_change_events = world
+                                .get_resource_mut::<Events<WindowScaleFactorChanged>>()
+                                .unwrap();
+
+                            scale_factor_change_events.send(WindowScaleFactorChanged {
+                                id: window_id,
+                                scale_factor,
+                            });
+                        }
+
+                        window.update_scale_factor_from_backend(scale_factor);
+
+                        if window.physical_width() != new_inner_size.width
+                            || window.physical_height() != new_inner_size.height
+                        {
+                            let mut resize_events =
+                                world.get_resource_mut::<Events<WindowResized>>().unwrap();
+                            resize_events.send(WindowResized {
+                                id: window_id,
+                                width: window.width(),
+                                height: window.height(),
+                            });
+                        }
+                        window.update_actual_size_from_backend(
+                            new_inner_size.width,
+                            new_inner_size.height,
+                        );
+                    }
+                    WindowEvent::Focused(focused) => {
+                        window.update_focused_status_from_backend(focused);
+                        let mut focused_events =
+                            world.get_resource_mut::<Events<WindowFocused>>().unwrap();
+                        focused_events.send(WindowFocused {
+                            id: window_id,
+                            focused,
+                        });
+                    }
+                    WindowEvent::DroppedFile(path_buf) => {
+                        let mut events =
+                            world.get_resource_mut::<Events<FileDragAndDrop>>().unwrap();
+                        events.send(FileDragAndDrop::DroppedFile {
+                            id: window_id,
+                            path_buf,
+                        });
+                    }
+                    WindowEvent::HoveredFile(path_buf) => {
+                        let mut events =
+                            world.get_resource_mut::<Events<FileDragAndDrop>>().unwrap();
+                        events.send(FileDragAndDrop::HoveredFile {
+                            id: window_id,
+                            path_buf,
+                        });
+                    }
+                    WindowEvent::HoveredFileCancelled => {
+                        let mut events =
+                            world.get_resource_mut::<Events<FileDragAndDrop>>().unwrap();
+                        events.send(FileDragAndDrop::HoveredFileCancelled { id: window_id });
+                    }
+                    WindowEvent::Moved(position) => {
+                        let position = ivec2(position.x, position.y);
+                        window.update_actual_position_from_backend(position);
+                        let mut events = world.get_resource_mut::<Events<WindowMoved>>().unwrap();
+                        events.send(WindowMoved {
+                            id: window_id,
+                            position,
+                        });
+                    }
+                    _ => {}
+                }
+            }
+            event::Event::DeviceEvent {
+                event: DeviceEvent::MouseMotion { delta },
+                ..
+            } => {
+                let mut mouse_motion_events = self
+                    .app
+                    .world
+                    .get_resource_mut::<Events<MouseMotion>>()
+                    .unwrap();
+                mouse_motion_events.send(MouseMotion {
+                    delta: Vec2::new(delta.0 as f32, delta.1 as f32),
+                });
+            }
+            event::Event::Suspended => {
+                self.send_lifecycle_event(ApplicationLifecycle::Suspended);
+                // let the app react before it stops being updated, the renderer skips the frame
+                if self.active {
+                    self.update(event_loop);
+                }
+                self.active = false;
+            }
+            event::Event::Resumed => {
+                // the native windows of Android apps are destroyed when they are suspended
+                if cfg!(target_os = "android") {
+                    self.refresh_raw_window_handles();
+                }
+                self.send_lifecycle_event(ApplicationLifecycle::Resumed);
+                self.active = true;
+            }
+            event::Event::MainEventsCleared => {
+                if self.active {
+                    self.update(event_loop);
+                }
+            }
+            _ => (),
+        }
+        exit_requested
+    }
+
+    fn send_lifecycle_event(&mut self, event: ApplicationLifecycle) {
+        let mut lifecycle_events = self
+            .app
+            .world
+            .get_resource_mut::<Events<ApplicationLifecycle>>()
+            .unwrap();
+        lifecycle_events.send(event);
+    }
+
+    /// Gives every window the current handle of its native window, for the renderer to recreate
+    /// its surface.
+    fn refresh_raw_window_handles(&mut self) {
+        let world = self.app.world.cell();
+        let winit_windows = world.get_resource::<WinitWindows>().unwrap();
+        let mut windows = world.get_resource_mut::<Windows>().unwrap();
+        let mut surface_recreated_events = world
+            .get_resource_mut::<Events<WindowSurfaceRecreated>>()
+            .unwrap();
+        for window in windows.iter_mut() {
+            if let Some(winit_window) = winit_windows.get_window(window.id()) {
+                // SAFE: the winit window is kept alive as long as the bevy window
+                let raw_window_handle =
+                    unsafe { RawWindowHandleWrapper::new(winit_window.raw_window_handle()) };
+                window.update_raw_window_handle_from_backend(raw_window_handle);
+                surface_recreated_events.send(WindowSurfaceRecreated { id: window.id() });
+            }
+        }
+    }
+
+    /// Creates the windows requested by the app, then updates it once. Embedders that don't
+    /// forward [`Event::MainEventsCleared`] can call it to update the app on their own schedule.
+    pub fn update<T>(&mut self, event_loop: &EventLoopWindowTarget<T>) {
+        handle_create_window_events(
+            &mut self.app.world,
+            event_loop,
+            &mut self.create_window_event_reader,
+        );
+        self.app.update();
+    }
+
+    /// Gives a winit window created by the embedding application to the app, which sees it as
+    /// any other [`Window`](bevy_window::Window). `descriptor` only provides the settings winit
+    /// can't be queried for, the window isn't changed to match it.
+    ///
+    /// To use it as the primary window, pass [`WindowId::primary`] and disable the creation of
+    /// the primary window in the [`WindowPlugin`](bevy_window::WindowPlugin).
+    pub fn add_window(
+        &mut self,
+        id: WindowId,
+        winit_window: winit::window::Window,
+        descriptor: &WindowDescriptor,
+    ) {
+        let world = self.app.world.cell();
+        let mut winit_windows = world.get_resource_mut::<WinitWindows>().unwrap();
+        let mut windows = world.get_resource_mut::<Windows>().unwrap();
+        let mut window_created_events = world.get_resource_mut::<Events<WindowCreated>>().unwrap();
+        windows.add(winit_windows.add_window(id, winit_window, descriptor));
+        window_created_events.send(WindowCreated { id });
+    }
+
+    fn exit_requested(&mut self) -> bool {
+        let app_exit_event_reader = &mut self.app_exit_event_reader;
+        self.app
+            .world
+            .get_resource::<Events<AppExit>>()
+            .map_or(false, |app_exit_events| {
+                app_exit_event_reader
+                    .iter(&app_exit_events)
+                    .next_back()
+                    .is_some()
+            })
+    }
+}
+
+fn handle_create_window_events<T>(
+    world: &mut World,
+    event_loop: &EventLoopWindowTarget<T>,
+    create_window_event_reader: &mut ManualEventReader<CreateWindow>,
+) {
+    let world = world.cell();
+    let mut winit_windows = world.get_resource_mut::<WinitWindows>().unwrap();
+    let mut windows = world.get_resource_mut::<Windows>().unwrap();
+    let create_window_events = world.get_resource::<Events<CreateWindow>>().unwrap();
+    let mut window_created_events = world.get_resource_mut::<Events<WindowCreated>>().unwrap();
+    for create_window_event in create_window_event_reader.iter(&create_window_events) {
+        let window = winit_windows.create_window(
+            event_loop,
+            create_window_event.id,
+            &create_window_event.descriptor,
+        );
+        windows.add(window);
+        window_created_events.send(WindowCreated {
+            id: create_window_event.id,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WinitEventHandler;
+    use bevy_app::{App, AppBuilder, Plugin};
+    use bevy_ecs::system::{IntoSystem, ResMut};
+
+    struct Finished;
+
+    struct Updates(usize);
+
+    struct CountUpdatesPlugin;
+
+    impl Plugin for CountUpdatesPlugin {
+        fn build(&self, app: &mut AppBuilder) {
+            app.insert_resource(Updates(0));
+        }
+
+        fn finish(&self, app: &mut AppBuilder) {
+            fn count_updates(mut updates: ResMut<Updates>) {
+                updates.0 += 1;
+            }
+            app.insert_resource(Finished)
+                .add_system(count_updates.system());
+        }
+    }
+
+    #[test]
+    fn handler_finishes_plugins() {
+        let mut builder = App::build();
+        builder.add_plugin(CountUpdatesPlugin);
+        let mut handler = WinitEventHandler::from_builder(builder);
+        assert!(handler.app().world.contains_resource::<Finished>());
+
+        // creating windows needs an event loop, so the app is updated directly
+        handler.app_mut().update();
+        handler.app_mut().update();
+        assert_eq!(handler.app().world.get_resource::<Updates>().unwrap().0, 2);
+    }
+}
@@ -1,6 +1,7 @@
 use bevy_math::IVec2;
 use bevy_utils::HashMap;
-use bevy_window::{Window, WindowDescriptor, WindowId, WindowMode};
+use bevy_window::{RawWindowHandleWrapper, Window, WindowDescriptor, WindowId, WindowMode};
+use raw_window_handle::HasRawWindowHandle;
 use winit::dpi::LogicalSize;
 
 #[derive(Debug, Default)]
@@ -11,9 +12,9 @@ pub struct WinitWindows {
 }
 
 impl WinitWindows {
-    pub fn create_window(
+    pub fn create_window<T>(
         &mut self,
-        event_loop: &winit::event_loop::EventLoopWindowTarget<()>,
+        event_loop: &winit::event_loop::EventLoopWindowTarget<T>,
         window_id: WindowId,
         window_descriptor: &WindowDescriptor,
     ) -> Window {
@@ -42,18 +43,20 @@ impl WinitWindows {
             )),
             _ => {
                 let WindowDescriptor {
-                    width,
-                    height,
                     scale_factor_override,
                     ..
                 } = window_descriptor;
+                let (width, height) = window_descriptor
+                    .resize_constraints
+                    .check_constraints()
+                    .constrain(window_descriptor.width, window_descriptor.height);
                 if let Some(sf) = scale_factor_override {
                     winit_window_builder.with_inner_size(
-                        winit::dpi::LogicalSize::new(*width, *height).to_physical::<f64>(*sf),
+                        winit::dpi::LogicalSize::new(width, height).to_physical::<f64>(*sf),
                     )
                 } else {
                     winit_window_builder
-                        .with_inner_size(winit::dpi::LogicalSize::new(*width, *height))
+                        .with_inner_size(winit::dpi::LogicalSize::new(width, height))
                 }
             }
             .with_resizable(window_descriptor.resizable)
@@ -112,9 +115,6 @@ impl WinitWindows {
 
         winit_window.set_cursor_visible(window_descriptor.cursor_visible);
 
-        self.window_id_to_winit.insert(window_id, winit_window.id());
-        self.winit_to_window_id.insert(winit_window.id(), window_id);
-
         #[cfg(target_arch = "wasm32")]
         {
             use winit::platform::web::WindowExtWebSys;
@@ -131,12 +131,28 @@ impl WinitWindows {
             }
         }
 
+        self.add_window(window_id, winit_window, window_descriptor)
+    }
+
+    /// Adds a winit window created elsewhere, such as by an application embedding bevy.
+    pub fn add_window(
+        &mut self,
+        window_id: WindowId,
+        winit_window: winit::window::Window,
+        window_descriptor: &WindowDescriptor,
+    ) -> Window {
+        self.window_id_to_winit.insert(window_id, winit_window.id());
+        self.winit_to_window_id.insert(winit_window.id(), window_id);
+
         let position = winit_window
             .outer_position()
             .ok()
             .map(|position| IVec2::new(position.x, position.y));
         let inner_size = winit_window.inner_size();
         let scale_factor = winit_window.scale_factor();
+        // SAFE: the winit window is kept alive as long as the bevy window
+        let raw_window_handle =
+            unsafe { RawWindowHandleWrapper::new(winit_window.raw_window_handle()) };
         self.windows.insert(winit_window.id(), winit_window);
         Window::new(
             window_id,
@@ -146,6 +162,7 @@ impl WinitWindows {
             scale_factor,
             position,
         )
+        .with_raw_window_handle(raw_window_handle)
     }
 
     pub fn get_window(&self, id: WindowId) -> Option<&winit::window::Window> {
@@ -1,4 +1,8 @@
-use bevy::{asset::LoadState, prelude::*, sprite::TextureAtlasBuilder};
+use bevy::{
+    asset::{AssetFolder, LoadState},
+    prelude::*,
+    sprite::TextureAtlasBuilder,
+};
 
 /// In this example we generate a new texture atlas (sprite sheet) from a folder containing
 /// individual sprites
@@ -21,11 +25,11 @@ enum AppState {
 
 #[derive(Default)]
 struct RpgSpriteHandles {
-    handles: Vec<HandleUntyped>,
+    handles: AssetFolder<Texture>,
 }
 
 fn load_textures(mut rpg_sprite_handles: ResMut<RpgSpriteHandles>, asset_server: Res<AssetServer>) {
-    rpg_sprite_handles.handles = asset_server.load_folder("textures/rpg").unwrap();
+    rpg_sprite_handles.handles = asset_server.load_folder_typed("textures/rpg").unwrap();
 }
 
 fn check_textures(
@@ -33,9 +37,7 @@ fn check_textures(
     rpg_sprite_handles: ResMut<RpgSpriteHandles>,
     asset_server: Res<AssetServer>,
 ) {
-    if let LoadState::Loaded =
-        asset_server.get_group_load_state(rpg_sprite_handles.handles.iter().map(|handle| handle.id))
-    {
+    if let LoadState::Loaded = asset_server.get_group_load_state(rpg_sprite_handles.handles.ids()) {
         state.set(AppState::Finished).unwrap();
     }
 }
@@ -49,9 +51,9 @@ fn setup(
     mut materials: ResMut<Assets<ColorMaterial>>,
 ) {
     let mut texture_atlas_builder = TextureAtlasBuilder::default();
-    for handle in rpg_sprite_handles.handles.iter() {
+    for handle in rpg_sprite_handles.handles.handles() {
         let texture = textures.get(handle).unwrap();
-        texture_atlas_builder.add_texture(handle.clone_weak().typed::<Texture>(), texture);
+        texture_atlas_builder.add_texture(handle.clone_weak(), texture);
     }
 
     let texture_atlas = texture_atlas_builder.finish(&mut textures).unwrap();
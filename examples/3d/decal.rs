@@ -0,0 +1,69 @@
+use bevy::prelude::*;
+
+/// This example projects decals onto a simple scene: a blob shadow under a cube, and a logo
+/// sliding over the ground and the cube, drawn on top of the shadow.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(slide.system())
+        .run();
+}
+
+struct Sliding;
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+    // cube
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..Default::default()
+    });
+    // blob shadow, a flat box around the base of the cube
+    commands.spawn_bundle(DecalBundle {
+        decal: Decal {
+            color: Color::rgba(0.0, 0.0, 0.0, 0.5),
+            ..Default::default()
+        },
+        transform: Transform::from_scale(Vec3::new(1.6, 0.2, 1.6)),
+        ..Default::default()
+    });
+    // logo, faded out on the sides of the cube and drawn over the shadow
+    commands
+        .spawn_bundle(DecalBundle {
+            decal: Decal::new(asset_server.load("branding/icon.png"))
+                .with_fade_angles(0.5, 1.0)
+                .with_order(1),
+            transform: Transform::from_scale(Vec3::new(1.5, 2.0, 1.5)),
+            ..Default::default()
+        })
+        .insert(Sliding);
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+    // camera
+    commands.spawn_bundle(PerspectiveCameraBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..Default::default()
+    });
+}
+
+fn slide(time: Res<Time>, mut query: Query<&mut Transform, With<Sliding>>) {
+    for mut transform in query.iter_mut() {
+        transform.translation.x = time.seconds_since_startup().sin() as f32 * 1.5;
+    }
+}
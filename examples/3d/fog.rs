@@ -0,0 +1,87 @@
+use bevy::prelude::*;
+
+/// This example shows an outdoor scene with distance and height fog blending into a sky. Press
+/// space to switch between the fog falloff modes.
+fn main() {
+    App::build()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(switch_falloff.system())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let sky = Sky::default();
+    // ground
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 500.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+    // rows of pillars fading into the distance
+    let pillar = meshes.add(Mesh::from(shape::Box::new(1.0, 8.0, 1.0)));
+    let material = materials.add(Color::rgb(0.8, 0.7, 0.6).into());
+    for x in -2..=2 {
+        for z in 0..20 {
+            commands.spawn_bundle(PbrBundle {
+                mesh: pillar.clone(),
+                material: material.clone(),
+                transform: Transform::from_xyz(x as f32 * 6.0, 4.0, -(z as f32) * 8.0),
+                ..Default::default()
+            });
+        }
+    }
+    // sun
+    commands.spawn().insert(DirectionalLight::new(
+        Color::rgb(1.0, 0.95, 0.8),
+        32000.0,
+        Vec3::new(0.2, -0.3, 1.0),
+    ));
+    // sky
+    commands.spawn_bundle(SkyBundle {
+        sky: sky.clone(),
+        ..Default::default()
+    });
+    // camera
+    commands
+        .spawn_bundle(PerspectiveCameraBundle {
+            transform: Transform::from_xyz(0.0, 3.0, 10.0)
+                .looking_at(Vec3::new(0.0, 3.0, 0.0), Vec3::Y),
+            ..Default::default()
+        })
+        .insert(Fog {
+            color: sky.horizon_color,
+            falloff: FogFalloff::Linear {
+                start: 5.0,
+                end: 120.0,
+            },
+            height: Some(HeightFog {
+                base: 0.0,
+                falloff: 0.2,
+            }),
+            directional_light_color: Color::rgba(1.0, 0.9, 0.7, 0.5),
+            directional_light_exponent: 16.0,
+        });
+}
+
+fn switch_falloff(keyboard_input: Res<Input<KeyCode>>, mut fogs: Query<&mut Fog>) {
+    if !keyboard_input.just_pressed(KeyCode::Space) {
+        return;
+    }
+    for mut fog in fogs.iter_mut() {
+        fog.falloff = match fog.falloff {
+            FogFalloff::Linear { .. } => FogFalloff::Exponential { density: 0.02 },
+            FogFalloff::Exponential { .. } => FogFalloff::ExponentialSquared { density: 0.02 },
+            FogFalloff::ExponentialSquared { .. } => FogFalloff::Linear {
+                start: 5.0,
+                end: 120.0,
+            },
+        };
+        info!("fog falloff: {:?}", fog.falloff);
+    }
+}
@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+
+/// This example shows how light layers restrict lights to some meshes: the red light only lights
+/// the cube on its layer, and the white light everything else.
+fn main() {
+    App::build()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    let material = materials.add(Color::rgb(0.8, 0.7, 0.6).into());
+
+    // plane, on the default layer 0
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 6.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+    // this cube is only on layer 0, and only receives the white light
+    commands.spawn_bundle(PbrBundle {
+        mesh: cube.clone(),
+        material: material.clone(),
+        transform: Transform::from_xyz(-1.0, 0.5, 0.0),
+        ..Default::default()
+    });
+    // this cube is only on layer 1, and only receives the red light
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: cube,
+            material,
+            transform: Transform::from_xyz(1.0, 0.5, 0.0),
+            ..Default::default()
+        })
+        .insert(LightLayers::layer(1));
+    // white light, on the default layer 0
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(-2.0, 4.0, 4.0),
+        ..Default::default()
+    });
+    // red light, on layer 1
+    commands
+        .spawn_bundle(PointLightBundle {
+            point_light: PointLight {
+                color: Color::RED,
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(2.0, 4.0, 4.0),
+            ..Default::default()
+        })
+        .insert(LightLayers::layer(1));
+    // camera
+    commands.spawn_bundle(PerspectiveCameraBundle {
+        transform: Transform::from_xyz(0.0, 3.0, 6.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..Default::default()
+    });
+}
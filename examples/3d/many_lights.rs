@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+
+/// This example spawns hundreds of point lights, which the renderer assigns to clusters of the
+/// view frustum so that each fragment is only shaded with the lights that can reach it.
+fn main() {
+    App::build()
+        .insert_resource(Msaa { samples: 4 })
+        .insert_resource(AmbientLight {
+            brightness: 0.01,
+            ..Default::default()
+        })
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(rotate_camera.system())
+        .run();
+}
+
+const LIGHTS_PER_SIDE: usize = 20;
+const SPACING: f32 = 2.0;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let size = LIGHTS_PER_SIDE as f32 * SPACING;
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size })),
+        material: materials.add(Color::rgb(0.7, 0.7, 0.7).into()),
+        ..Default::default()
+    });
+
+    let cube = meshes.add(Mesh::from(shape::Cube { size: 0.5 }));
+    let cube_material = materials.add(Color::rgb(0.8, 0.7, 0.6).into());
+    for x in 0..LIGHTS_PER_SIDE {
+        for z in 0..LIGHTS_PER_SIDE {
+            let position = Vec3::new(
+                (x as f32 + 0.5) * SPACING - size / 2.0,
+                0.0,
+                (z as f32 + 0.5) * SPACING - size / 2.0,
+            );
+            // a light between every four cubes, with a hue depending on its position
+            commands.spawn_bundle(PointLightBundle {
+                point_light: PointLight {
+                    color: Color::hsl((x * LIGHTS_PER_SIDE + z) as f32 * 137.5 % 360.0, 1.0, 0.5),
+                    intensity: 10.0,
+                    range: SPACING * 1.5,
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(position + Vec3::new(0.0, 0.5, 0.0)),
+                ..Default::default()
+            });
+            commands.spawn_bundle(PbrBundle {
+                mesh: cube.clone(),
+                material: cube_material.clone(),
+                transform: Transform::from_translation(
+                    position + Vec3::new(SPACING / 2.0, 0.25, SPACING / 2.0),
+                ),
+                ..Default::default()
+            });
+        }
+    }
+
+    commands.spawn_bundle(PerspectiveCameraBundle {
+        transform: Transform::from_xyz(0.0, size / 2.0, size).looking_at(Vec3::ZERO, Vec3::Y),
+        ..Default::default()
+    });
+}
+
+fn rotate_camera(time: Res<Time>, mut query: Query<&mut Transform, With<PerspectiveProjection>>) {
+    let rotation = Quat::from_rotation_y(time.delta_seconds() * 0.2);
+    for mut transform in query.iter_mut() {
+        transform.translation = rotation * transform.translation;
+        transform.rotation = rotation * transform.rotation;
+    }
+}
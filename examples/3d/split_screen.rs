@@ -0,0 +1,97 @@
+use bevy::{
+    prelude::*,
+    render::camera::{SplitScreen, SplitScreenLayout},
+};
+
+const PLAYERS: usize = 3;
+
+/// This example shows how to split the window between several cameras, each with its own UI.
+/// Press space to change the layout.
+fn main() {
+    App::build()
+        .insert_resource(Msaa { samples: 4 })
+        .add_plugins(DefaultPlugins)
+        .add_split_screen(SplitScreenLayout::Grid, PLAYERS)
+        .add_startup_system(setup.system())
+        .add_system(change_layout.system())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    // plane
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Plane { size: 5.0 })),
+        material: materials.add(Color::rgb(0.3, 0.5, 0.3).into()),
+        ..Default::default()
+    });
+    // cube
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        transform: Transform::from_xyz(0.0, 0.5, 0.0),
+        ..Default::default()
+    });
+    // light
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+
+    commands.spawn_bundle(UiCameraBundle::default());
+    let font = asset_server.load("fonts/FiraSans-Bold.ttf");
+    for player in 0..PLAYERS {
+        // each player looks at the scene from a different side
+        let angle = player as f32 / PLAYERS as f32 * std::f32::consts::TAU;
+        let camera = commands
+            .spawn_bundle(PerspectiveCameraBundle {
+                transform: Transform::from_xyz(5.0 * angle.cos(), 2.5, 5.0 * angle.sin())
+                    .looking_at(Vec3::ZERO, Vec3::Y),
+                ..PerspectiveCameraBundle::with_name(&SplitScreen::camera_name(player))
+            })
+            .id();
+
+        // the root node covers the camera's viewport, so the label stays in its corner
+        commands
+            .spawn_bundle(NodeBundle {
+                visible: Visible {
+                    is_visible: false,
+                    ..Default::default()
+                },
+                ..Default::default()
+            })
+            .insert(ViewportUiRoot { camera })
+            .with_children(|parent| {
+                parent.spawn_bundle(TextBundle {
+                    style: Style {
+                        margin: Rect::all(Val::Px(10.0)),
+                        ..Default::default()
+                    },
+                    text: Text::with_section(
+                        format!("Player {}", player + 1),
+                        TextStyle {
+                            font: font.clone(),
+                            font_size: 30.0,
+                            color: Color::WHITE,
+                        },
+                        Default::default(),
+                    ),
+                    ..Default::default()
+                });
+            });
+    }
+}
+
+fn change_layout(keyboard_input: Res<Input<KeyCode>>, mut split_screen: ResMut<SplitScreen>) {
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        split_screen.layout = match split_screen.layout {
+            SplitScreenLayout::Grid => SplitScreenLayout::Columns,
+            SplitScreenLayout::Columns => SplitScreenLayout::Rows,
+            SplitScreenLayout::Rows => SplitScreenLayout::Grid,
+        };
+    }
+}
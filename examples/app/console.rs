@@ -0,0 +1,69 @@
+use bevy::{console::ConsolePlugin, prelude::*};
+
+/// This example adds an in-game developer console, opened with the ` key, and registers commands
+/// to change the game at runtime. Type `help` to list the commands, use the up and down arrows to
+/// browse the history and tab to complete command names.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ConsolePlugin)
+        .add_console_command(
+            ConsoleCommand::new(
+                "background",
+                "Sets the background color",
+                |world: &mut World, args: &ConsoleArgs, output: &mut ConsoleOutput| {
+                    let (r, g, b) = (args.get::<f32>(0), args.get::<f32>(1), args.get::<f32>(2));
+                    let color = Color::rgb(*r.unwrap(), *g.unwrap(), *b.unwrap());
+                    world.insert_resource(ClearColor(color));
+                    output.print(format!("background set to {:?}", color));
+                },
+            )
+            .with_arg::<f32>("red")
+            .with_arg::<f32>("green")
+            .with_arg::<f32>("blue"),
+        )
+        .add_console_command(
+            ConsoleCommand::new(
+                "say",
+                "Prints a message, in quotes if it has spaces",
+                |_: &mut World, args: &ConsoleArgs, output: &mut ConsoleOutput| {
+                    let times = args.get::<usize>(1).copied().unwrap_or(1);
+                    for _ in 0..times {
+                        output.print(args.get::<String>(0).unwrap().clone());
+                    }
+                },
+            )
+            .with_arg::<String>("message")
+            .with_optional_arg::<usize>("times"),
+        )
+        .add_startup_system(setup.system())
+        .run();
+}
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn_bundle(UiCameraBundle::default());
+    commands
+        .spawn_bundle(TextBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    top: Val::Px(5.0),
+                    left: Val::Px(5.0),
+                    ..Default::default()
+                },
+                display: Display::None,
+                ..Default::default()
+            },
+            text: Text::with_section(
+                "",
+                TextStyle {
+                    font: asset_server.load("fonts/FiraMono-Medium.ttf"),
+                    font_size: 20.0,
+                    color: Color::WHITE,
+                },
+                Default::default(),
+            ),
+            ..Default::default()
+        })
+        .insert(ConsoleText);
+}
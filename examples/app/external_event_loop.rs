@@ -0,0 +1,82 @@
+use bevy::{
+    prelude::*,
+    winit::{
+        winit::{
+            event::{ElementState, Event, KeyboardInput, VirtualKeyCode, WindowEvent},
+            event_loop::{ControlFlow, EventLoop},
+        },
+        WinitEventHandler,
+    },
+};
+
+/// This example shows how to embed bevy in an application that owns the winit event loop, such as
+/// an editor. The application forwards the events it doesn't handle itself to bevy, and decides
+/// when bevy updates: press P to pause and resume it.
+fn main() {
+    let mut builder = App::build();
+    builder
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(rotate.system());
+    let mut bevy = WinitEventHandler::from_builder(builder);
+
+    let mut paused = false;
+    let event_loop = EventLoop::new();
+    event_loop.run(move |event, event_loop, control_flow| {
+        *control_flow = ControlFlow::Poll;
+        match event {
+            Event::WindowEvent {
+                event:
+                    WindowEvent::KeyboardInput {
+                        input:
+                            KeyboardInput {
+                                state: ElementState::Pressed,
+                                virtual_keycode: Some(VirtualKeyCode::P),
+                                ..
+                            },
+                        ..
+                    },
+                ..
+            } => {
+                paused = !paused;
+                println!("bevy is {}", if paused { "paused" } else { "running" });
+            }
+            Event::MainEventsCleared => {
+                if !paused {
+                    bevy.update(event_loop);
+                }
+            }
+            event => {
+                if bevy.handle_event(event, event_loop) {
+                    *control_flow = ControlFlow::Exit;
+                }
+            }
+        }
+    });
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        ..Default::default()
+    });
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+    commands.spawn_bundle(PerspectiveCameraBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..Default::default()
+    });
+}
+
+fn rotate(time: Res<Time>, mut query: Query<&mut Transform, With<Handle<Mesh>>>) {
+    for mut transform in query.iter_mut() {
+        transform.rotate(Quat::from_rotation_y(time.delta_seconds()));
+    }
+}
@@ -0,0 +1,84 @@
+use bevy::{
+    app::{AppExit, ScheduleRunnerPlugin, ScheduleRunnerSettings},
+    prelude::*,
+    render::{
+        pass::ClearColor,
+        pipeline::PipelineCompiler,
+        texture::{TextureFormat, WindowCaptures},
+    },
+    utils::Duration,
+    window::{HeadlessWindowPlugin, WindowId},
+    winit::WinitPlugin,
+};
+
+/// This example renders a scene without displaying a window, and reads the rendered frame back,
+/// as automated tests comparing frames with reference images would. It runs on machines without
+/// a display server, but still needs a GPU.
+fn main() {
+    App::build()
+        .insert_resource(ScheduleRunnerSettings::run_loop(Duration::from_secs_f64(
+            1.0 / 60.0,
+        )))
+        .insert_resource(WindowDescriptor {
+            width: 320.0,
+            height: 240.0,
+            ..Default::default()
+        })
+        .insert_resource(ClearColor(Color::BLACK))
+        .add_plugins_with(DefaultPlugins, |group| group.disable::<WinitPlugin>())
+        .add_plugin(ScheduleRunnerPlugin::default())
+        .add_plugin(HeadlessWindowPlugin)
+        .add_startup_system(setup.system())
+        .add_system(capture.system())
+        .run();
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut pipeline_compiler: ResMut<PipelineCompiler>,
+) {
+    // compile shaders as soon as they are needed, so that no entity is missing from the frames
+    pipeline_compiler.set_asynchronous(false);
+
+    commands.spawn_bundle(PbrBundle {
+        mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+        material: materials.add(Color::rgb(0.8, 0.7, 0.6).into()),
+        ..Default::default()
+    });
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 8.0, 4.0),
+        ..Default::default()
+    });
+    commands.spawn_bundle(PerspectiveCameraBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..Default::default()
+    });
+}
+
+fn capture(
+    mut frame: Local<u32>,
+    mut window_captures: ResMut<WindowCaptures>,
+    mut app_exit_events: EventWriter<AppExit>,
+) {
+    *frame += 1;
+    // leave time for the assets to be uploaded to the GPU
+    if *frame == 10 {
+        window_captures.request(WindowId::primary());
+    }
+
+    if let Some(texture) = window_captures.take(WindowId::primary()) {
+        let texture = texture.convert(TextureFormat::Rgba8UnormSrgb).unwrap();
+        let lit_pixels = texture
+            .data
+            .chunks(4)
+            .filter(|pixel| pixel[..3].iter().any(|channel| *channel > 0))
+            .count();
+        println!(
+            "Captured a {}x{} frame, the cube covers {} pixels",
+            texture.size.width, texture.size.height, lit_pixels
+        );
+        app_exit_events.send(AppExit);
+    }
+}
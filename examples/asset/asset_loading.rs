@@ -38,7 +38,7 @@ fn setup(
 
     // You can load all assets in a folder like this. They will be loaded in parallel without
     // blocking
-    let _scenes: Vec<HandleUntyped> = asset_server.load_folder("models/monkey").unwrap();
+    let _scenes: Vec<HandleUntyped> = asset_server.load_untyped_folder("models/monkey").unwrap();
 
     // Then any asset in the folder can be accessed like this:
     let monkey_handle = asset_server.get_handle("models/monkey/Monkey.gltf#Mesh0/Primitive0");
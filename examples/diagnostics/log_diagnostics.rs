@@ -13,9 +13,14 @@ fn main() {
         // Any plugin can register diagnostics
         // Uncomment this to add some render resource diagnostics:
         // .add_plugin(bevy::wgpu::diagnostic::WgpuResourceDiagnosticsPlugin::default())
+        // Uncomment this to add GPU time diagnostics for each render graph node (this requires the
+        // `WgpuFeature::TimestampQuery` feature to be enabled in `WgpuOptions`):
+        // .add_plugin(bevy::wgpu::diagnostic::WgpuNodeTimingDiagnosticsPlugin::default())
         // Uncomment this to add an entity count diagnostics:
         // .add_plugin(bevy::diagnostic::EntityCountDiagnosticsPlugin::default())
         // Uncomment this to add an asset count diagnostics:
         // .add_plugin(bevy::asset::diagnostic::AssetCountDiagnosticsPlugin::<Texture>::default())
+        // Uncomment this to write the diagnostics history to a file when the app exits:
+        // .add_plugin(bevy::diagnostic::ExportDiagnosticsPlugin::json("diagnostics.json"))
         .run();
 }
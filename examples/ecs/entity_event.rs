@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+
+/// This example creates an event aimed at entities, and attaches callbacks to some entities that
+/// run whenever the event targets them, instead of filtering every event in a system.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_entity_event::<Damage>()
+        .init_resource::<DamageTimer>()
+        .add_startup_system(setup.system())
+        .add_system(damage_system.system())
+        .run();
+}
+
+struct Damage {
+    target: Entity,
+    amount: u32,
+}
+
+impl EntityEvent for Damage {
+    fn target(&self) -> Entity {
+        self.target
+    }
+}
+
+struct Health(u32);
+
+struct DamageTimer(Timer);
+
+impl Default for DamageTimer {
+    fn default() -> Self {
+        DamageTimer(Timer::from_seconds(1.0, true))
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands
+        .spawn()
+        .insert(Name::new("Knight"))
+        .insert(Health(10))
+        .observe(|damage: &Damage, commands: &mut Commands| {
+            info!("The knight takes {} damage", damage.amount);
+            // the callback reacts through commands, applied once all the events are handled
+            commands.add(Hurt(damage.target, damage.amount));
+        });
+    // the wall has no callback, damage aimed at it is ignored
+    commands
+        .spawn()
+        .insert(Name::new("Wall"))
+        .insert(Health(100));
+}
+
+// damages every entity with health once per second
+fn damage_system(
+    time: Res<Time>,
+    mut timer: ResMut<DamageTimer>,
+    query: Query<Entity, With<Health>>,
+    mut damage_events: EventWriter<Damage>,
+) {
+    if timer.0.tick(time.delta()).just_finished() {
+        for target in query.iter() {
+            damage_events.send(Damage { target, amount: 3 });
+        }
+    }
+}
+
+struct Hurt(Entity, u32);
+
+impl bevy::ecs::system::Command for Hurt {
+    fn write(self, world: &mut World) {
+        if let Some(mut health) = world.get_mut::<Health>(self.0) {
+            health.0 = health.0.saturating_sub(self.1);
+            info!("The knight has {} health left", health.0);
+            if health.0 == 0 {
+                world.despawn(self.0);
+            }
+        }
+    }
+}
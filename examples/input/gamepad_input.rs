@@ -1,35 +1,28 @@
 use bevy::{
-    input::gamepad::{Gamepad, GamepadButton, GamepadEvent, GamepadEventType},
+    input::gamepad::{GamepadButton, GamepadEvent, GamepadEventType},
     prelude::*,
-    utils::HashSet,
 };
 
 fn main() {
     App::build()
         .add_plugins(DefaultPlugins)
-        .init_resource::<GamepadLobby>()
-        .add_system_to_stage(CoreStage::PreUpdate, connection_system.system())
+        .add_system(connection_system.system())
         .add_system(gamepad_system.system())
         .run();
 }
 
-#[derive(Default)]
-struct GamepadLobby {
-    gamepads: HashSet<Gamepad>,
-}
-
-fn connection_system(
-    mut lobby: ResMut<GamepadLobby>,
-    mut gamepad_event: EventReader<GamepadEvent>,
-) {
+fn connection_system(gamepads: Res<Gamepads>, mut gamepad_event: EventReader<GamepadEvent>) {
     for event in gamepad_event.iter() {
         match &event {
-            GamepadEvent(gamepad, GamepadEventType::Connected) => {
-                lobby.gamepads.insert(*gamepad);
-                info!("{:?} Connected", gamepad);
+            GamepadEvent(gamepad, GamepadEventType::Connected(info)) => {
+                info!(
+                    "{:?} Connected: {}, player {:?}",
+                    gamepad,
+                    info.name,
+                    gamepads.player(*gamepad)
+                );
             }
             GamepadEvent(gamepad, GamepadEventType::Disconnected) => {
-                lobby.gamepads.remove(gamepad);
                 info!("{:?} Disconnected", gamepad);
             }
             _ => (),
@@ -38,12 +31,12 @@ fn connection_system(
 }
 
 fn gamepad_system(
-    lobby: Res<GamepadLobby>,
+    gamepads: Res<Gamepads>,
     button_inputs: Res<Input<GamepadButton>>,
     button_axes: Res<Axis<GamepadButton>>,
     axes: Res<Axis<GamepadAxis>>,
 ) {
-    for gamepad in lobby.gamepads.iter().cloned() {
+    for gamepad in gamepads.iter() {
         if button_inputs.just_pressed(GamepadButton(gamepad, GamepadButtonType::South)) {
             info!("{:?} just pressed South", gamepad);
         } else if button_inputs.just_released(GamepadButton(gamepad, GamepadButtonType::South)) {
@@ -13,8 +13,8 @@ fn main() {
 fn gamepad_events(mut gamepad_event: EventReader<GamepadEvent>) {
     for event in gamepad_event.iter() {
         match &event {
-            GamepadEvent(gamepad, GamepadEventType::Connected) => {
-                info!("{:?} Connected", gamepad);
+            GamepadEvent(gamepad, GamepadEventType::Connected(info)) => {
+                info!("{:?} Connected: {}", gamepad, info.name);
             }
             GamepadEvent(gamepad, GamepadEventType::Disconnected) => {
                 info!("{:?} Disconnected", gamepad);
@@ -6,7 +6,7 @@ use bevy::{
         pipeline::{PipelineDescriptor, RenderPipeline},
         render_graph::{base, AssetRenderResourcesNode, RenderGraph},
         renderer::RenderResources,
-        shader::ShaderStages,
+        shader::{ShaderEvent, ShaderStages},
     },
 };
 
@@ -17,6 +17,7 @@ fn main() {
         .add_plugins(DefaultPlugins)
         .add_asset::<MyMaterial>()
         .add_startup_system(setup.system())
+        .add_system(report_shader_reloads.system())
         .run();
 }
 
@@ -26,6 +27,19 @@ struct MyMaterial {
     pub color: Color,
 }
 
+/// Shader compilation errors are reported as events, so a typo in a shader being edited doesn't
+/// crash the app. The last working version of the shader stays in use until the error is fixed.
+fn report_shader_reloads(mut shader_events: EventReader<ShaderEvent>) {
+    for event in shader_events.iter() {
+        match event {
+            ShaderEvent::Reloaded { handle } => info!("reloaded shader {:?}", handle),
+            ShaderEvent::CompilationFailed { handle, error } => {
+                warn!("shader {:?} failed to compile: {}", handle, error)
+            }
+        }
+    }
+}
+
 fn setup(
     mut commands: Commands,
     asset_server: ResMut<AssetServer>,
@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+
+/// This example illustrates how to drive gamepad input from on-screen controls, for touch screens.
+/// The player can be moved with any connected controller, or with the virtual stick and button.
+fn main() {
+    App::build()
+        .add_plugins(DefaultPlugins)
+        .add_startup_system(setup.system())
+        .add_system(player_system.system())
+        .run();
+}
+
+struct Player;
+
+fn player_system(
+    time: Res<Time>,
+    gamepads: Res<Gamepads>,
+    button_inputs: Res<Input<GamepadButton>>,
+    axes: Res<Axis<GamepadAxis>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut query: Query<(&mut Transform, &Handle<ColorMaterial>), With<Player>>,
+) {
+    // virtual controls read the same as any other gamepad
+    let mut direction = Vec2::ZERO;
+    let mut pressed = false;
+    for gamepad in gamepads.iter() {
+        direction += Vec2::new(
+            axes.get(GamepadAxis(gamepad, GamepadAxisType::LeftStickX))
+                .unwrap_or(0.0),
+            axes.get(GamepadAxis(gamepad, GamepadAxisType::LeftStickY))
+                .unwrap_or(0.0),
+        );
+        pressed |= button_inputs.pressed(GamepadButton(gamepad, GamepadButtonType::South));
+    }
+    for (mut transform, material) in query.iter_mut() {
+        transform.translation += direction.extend(0.0) * 300.0 * time.delta_seconds();
+        if let Some(material) = materials.get_mut(material) {
+            material.color = if pressed {
+                Color::rgb(0.9, 0.5, 0.5)
+            } else {
+                Color::rgb(0.5, 0.5, 0.9)
+            };
+        }
+    }
+}
+
+fn setup(mut commands: Commands, mut materials: ResMut<Assets<ColorMaterial>>) {
+    commands.spawn_bundle(OrthographicCameraBundle::new_2d());
+    commands.spawn_bundle(UiCameraBundle::default());
+    commands
+        .spawn_bundle(SpriteBundle {
+            material: materials.add(Color::rgb(0.5, 0.5, 0.9).into()),
+            sprite: Sprite::new(Vec2::new(50.0, 50.0)),
+            ..Default::default()
+        })
+        .insert(Player);
+
+    let control_material = materials.add(Color::rgba(1.0, 1.0, 1.0, 0.2).into());
+    // the stick, in the bottom left corner
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(200.0), Val::Px(200.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    left: Val::Px(40.0),
+                    bottom: Val::Px(40.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            material: control_material.clone(),
+            ..Default::default()
+        })
+        .insert(VirtualStick::new(
+            GamepadAxisType::LeftStickX,
+            GamepadAxisType::LeftStickY,
+        ));
+    // the button, in the bottom right corner
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Px(100.0), Val::Px(100.0)),
+                position_type: PositionType::Absolute,
+                position: Rect {
+                    right: Val::Px(60.0),
+                    bottom: Val::Px(90.0),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            material: control_material,
+            ..Default::default()
+        })
+        .insert(VirtualButton::new(GamepadButtonType::South));
+}